@@ -0,0 +1,33 @@
+/*
+ * C interface to pack, built with the `ffi` feature:
+ *
+ *   cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * Each function returns a NUL-terminated JSON document, never NULL, that the
+ * caller must release with pack_string_free(). It is what the CLI prints
+ * with --json: a report, or a refusal envelope whose "outcome" is "REFUSAL".
+ * Strings passed in are UTF-8 and are not retained.
+ */
+#ifndef PACK_H
+#define PACK_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Verify a pack directory or .pack.tar; returns a pack.verify.v0 report. */
+char *pack_verify(const char *pack_dir);
+
+/* Seal the paths in artifacts_json (a JSON array) into output, or the
+ * default pack/<pack_id>/ when output is NULL; note may be NULL. Returns a
+ * pack.seal.v0 report. */
+char *pack_seal(const char *artifacts_json, const char *output, const char *note);
+
+/* Release a string returned by pack_verify or pack_seal. */
+void pack_string_free(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PACK_H */
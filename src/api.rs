@@ -0,0 +1,181 @@
+//! Stable library interface for embedding pack without the CLI.
+//!
+//! These functions never print, write witness records, or map results to exit
+//! codes. Refusals come back as the same [`RefusalEnvelope`] the CLI prints on
+//! exit 2; `INVALID` verify outcomes and diff changes are ordinary `Ok` results
+//! for the caller to inspect.
+//!
+//! ```no_run
+//! use std::path::{Path, PathBuf};
+//!
+//! let sealed = pack::api::seal(
+//!     &[PathBuf::from("nov.lock.json")],
+//!     Some(Path::new("evidence/nov")),
+//!     None,
+//!     &pack::api::SealOptions::default(),
+//! )?;
+//! let report = pack::api::verify(&sealed.output_dir, &pack::api::VerifyOptions::default())?;
+//! assert_eq!(report.outcome, pack::api::VerifyOutcome::OK);
+//! # Ok::<(), Box<pack::api::RefusalEnvelope>>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::refusal::RefusalDetail;
+
+pub use crate::diff::{DiffReport, NestedDiff, DIFF_REPORT_VERSION};
+pub use crate::events::{EventBus, PackEvent};
+pub use crate::inspect::{InspectMember, InspectReport, INSPECT_REPORT_VERSION};
+pub use crate::outcomes::{
+    command_contract, CommandContract, ExitMeaning, COMMANDS, OUTCOMES_REPORT_VERSION,
+};
+pub use crate::refusal::{RefusalCode, RefusalEnvelope};
+pub use crate::seal::command::{SealOptions, SealResult};
+pub use crate::seal::manifest::{Manifest, Member};
+pub use crate::verify::{
+    DirSource, EntryKind, EntryMeta, HashCheck, MemorySource, PackSource, SchemaMemberResult,
+    VerifyOptions, VerifyOutcome, VerifyReport, VERIFY_REPORT_VERSION,
+};
+
+/// Seal `artifacts` into a pack at `output` (default `pack/<pack_id>/`).
+pub fn seal(
+    artifacts: &[PathBuf],
+    output: Option<&Path>,
+    note: Option<String>,
+    options: &SealOptions,
+) -> Result<SealResult, Box<RefusalEnvelope>> {
+    crate::seal::command::execute_seal_with_options(artifacts, output, note, options)
+}
+
+/// Verify a pack directory or `.pack.tar`.
+///
+/// Returns the report for both `OK` and `INVALID` outcomes; a pack that
+/// cannot be verified at all is an `Err`.
+pub fn verify(
+    pack_dir: &Path,
+    options: &VerifyOptions,
+) -> Result<VerifyReport, Box<RefusalEnvelope>> {
+    let report = crate::verify::verify_pack(pack_dir, options);
+    match report.outcome {
+        VerifyOutcome::REFUSAL => Err(into_envelope(report)),
+        _ => Ok(report),
+    }
+}
+
+/// Verify a pack read through `source` instead of a directory on disk:
+/// a [`MemorySource`], or the caller's own [`PackSource`] over an archive or
+/// remote store.
+///
+/// Outcomes are as for [`verify`]; `options.member_root` does not apply.
+pub fn verify_source(
+    source: &dyn PackSource,
+    options: &VerifyOptions,
+) -> Result<VerifyReport, Box<RefusalEnvelope>> {
+    let report = crate::verify::verify_source(source, options);
+    match report.outcome {
+        VerifyOutcome::REFUSAL => Err(into_envelope(report)),
+        _ => Ok(report),
+    }
+}
+
+/// Compare the manifests of packs A and B.
+pub fn diff(a_dir: &Path, b_dir: &Path) -> Result<DiffReport, Box<RefusalEnvelope>> {
+    crate::diff::diff_packs(a_dir, b_dir).map_err(|report| into_envelope(*report))
+}
+
+/// Read a pack's manifest and member sizes without hashing anything.
+///
+/// Deprecation notes are reported when `pack_dir` sits inside a filesystem
+/// store.
+pub fn inspect(pack_dir: &Path) -> Result<InspectReport, Box<RefusalEnvelope>> {
+    crate::inspect::inspect_pack(pack_dir, None)
+}
+
+/// Lift the `{code, message}` refusal embedded in a verify-shaped report into
+/// the standard envelope.
+fn into_envelope(report: VerifyReport) -> Box<RefusalEnvelope> {
+    let reason = report.refusal.unwrap_or_default();
+    let field = |key: &str| reason[key].as_str().unwrap_or_default().to_string();
+    Box::new(RefusalEnvelope {
+        version: "pack.v0".to_string(),
+        outcome: "REFUSAL".to_string(),
+        refusal: RefusalDetail {
+            code: field("code"),
+            message: field("message"),
+            detail: None,
+            next_command: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn sealed(tmp: &TempDir, name: &str, content: &str) -> SealResult {
+        let file = tmp.path().join(format!("{name}.json"));
+        fs::write(&file, content).unwrap();
+        seal(
+            &[file],
+            Some(&tmp.path().join(format!("{name}-pack"))),
+            None,
+            &SealOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn seal_verify_diff_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let a = sealed(&tmp, "a", "{}");
+        let b = sealed(&tmp, "b", "[]");
+
+        let report = verify(&a.output_dir, &VerifyOptions::default()).unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.pack_id.as_deref(), Some(a.pack_id.as_str()));
+
+        let diff = diff(&a.output_dir, &b.output_dir).unwrap();
+        assert!(diff.has_changes());
+        assert_eq!(diff.a_pack_id, a.pack_id);
+    }
+
+    #[test]
+    fn invalid_is_ok_and_refusal_is_err() {
+        let tmp = TempDir::new().unwrap();
+        let a = sealed(&tmp, "a", "{}");
+        fs::write(a.output_dir.join("a.json"), "tampered").unwrap();
+        let report = verify(&a.output_dir, &VerifyOptions::default()).unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+
+        let err = verify(tmp.path(), &VerifyOptions::default()).unwrap_err();
+        assert_eq!(err.refusal.code, "E_BAD_PACK");
+        let err = diff(tmp.path(), &a.output_dir).unwrap_err();
+        assert_eq!(err.refusal.code, "E_BAD_PACK");
+        assert!(err.refusal.message.contains("pack A"));
+    }
+    #[test]
+    fn verify_source_checks_a_pack_held_in_memory() {
+        let tmp = TempDir::new().unwrap();
+        let a = sealed(&tmp, "a", "{}");
+        let mut source: MemorySource = ["manifest.json", "a.json"]
+            .into_iter()
+            .map(|name| (name, fs::read(a.output_dir.join(name)).unwrap()))
+            .collect();
+        let report = verify_source(&source, &VerifyOptions::default()).unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.pack_id.as_deref(), Some(a.pack_id.as_str()));
+
+        source.insert("a.json", "[]");
+        source.insert("stray.txt", "x");
+        let report = verify_source(&source, &VerifyOptions::default()).unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        let codes: Vec<_> = report.invalid.iter().map(|f| f.code.as_str()).collect();
+        assert_eq!(codes, ["HASH_MISMATCH", "EXTRA_MEMBER"]);
+
+        source.remove("manifest.json");
+        let err = verify_source(&source, &VerifyOptions::default()).unwrap_err();
+        assert_eq!(err.refusal.code, "E_BAD_PACK");
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::archive::{is_archive, unpack_archive};
+use crate::seal::manifest::Manifest;
+use crate::sign::load_signing_key;
+use crate::verify::{decode_manifest, verify_pack, VerifyOptions, VerifyOutcome};
+
+use super::statement::Statement;
+
+/// Optional `pack attest` behaviours.
+#[derive(Debug, Clone, Default)]
+pub struct AttestOptions {
+    /// `--sign`: wrap the statement in a DSSE envelope signed with Ed25519.
+    pub sign: bool,
+    /// `--signing-key`: key file for `--sign`; `None` reads `PACK_SIGNING_KEY`.
+    pub signing_key: Option<PathBuf>,
+    /// `--output`: write the statement here instead of printing it.
+    pub output: Option<PathBuf>,
+}
+
+/// A pack turned into an in-toto statement.
+#[derive(Debug, Clone)]
+pub struct AttestResult {
+    pub pack_id: String,
+    /// The statement, or its DSSE envelope with `--sign`, as JSON.
+    pub attestation: String,
+    /// Hex public key of the `--sign` signer.
+    pub signer: Option<String>,
+}
+
+/// Execute `pack attest`: verify `pack_dir` (a directory or `.pack.tar`) in
+/// full and convert its manifest into an in-toto Statement.
+///
+/// A pack that is not `OK` is refused with `E_BAD_PACK`, so an attestation
+/// only ever describes bytes that verified. With `--output`, the statement is
+/// written to that file.
+pub fn execute_attest(
+    pack_dir: &Path,
+    options: &AttestOptions,
+) -> Result<AttestResult, Box<RefusalEnvelope>> {
+    let signing_key = if options.sign {
+        let key = load_signing_key(options.signing_key.as_deref()).map_err(|message| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(message),
+                options
+                    .signing_key
+                    .as_ref()
+                    .map(|path| json!({ "signing_key": path.display().to_string() })),
+            ))
+        })?;
+        Some(key)
+    } else {
+        None
+    };
+
+    let manifest = verified_manifest(pack_dir)?;
+    let statement = Statement::from_manifest(&manifest);
+    let (attestation, signer) = match &signing_key {
+        Some(key) => (
+            statement.sign(key).to_json(),
+            Some(hex::encode(key.verifying_key().as_bytes())),
+        ),
+        None => (statement.to_json(), None),
+    };
+
+    if let Some(path) = &options.output {
+        fs::write(path, format!("{attestation}\n")).map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot write attestation: {}: {e}", path.display())),
+                None,
+            ))
+        })?;
+    }
+    Ok(AttestResult {
+        pack_id: manifest.pack_id,
+        attestation,
+        signer,
+    })
+}
+
+/// The manifest of `pack_dir` after a full verify has passed.
+fn verified_manifest(pack_dir: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+    if is_archive(pack_dir) {
+        let scratch = tempfile::tempdir().map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot create scratch directory: {e}")),
+                None,
+            ))
+        })?;
+        unpack_archive(pack_dir, scratch.path())?;
+        return verified_manifest(scratch.path());
+    }
+
+    let report = verify_pack(pack_dir, &VerifyOptions::default());
+    match report.outcome {
+        VerifyOutcome::OK => {}
+        VerifyOutcome::INVALID => {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::BadPack,
+                Some(format!(
+                    "Pack failed verification; run pack verify {}",
+                    pack_dir.display()
+                )),
+                Some(json!({
+                    "pack_id": report.pack_id,
+                    "invalid": report.invalid,
+                })),
+            )))
+        }
+        VerifyOutcome::REFUSAL => {
+            let reason = report.refusal.unwrap_or_default();
+            let code = RefusalCode::ALL
+                .into_iter()
+                .find(|code| reason["code"] == code.as_str())
+                .unwrap_or(RefusalCode::BadPack);
+            return Err(Box::new(RefusalEnvelope::new(
+                code,
+                reason["message"].as_str().map(str::to_string),
+                None,
+            )));
+        }
+    }
+
+    // Verify already read and parsed this manifest.
+    let bytes = fs::read(pack_dir.join("manifest.json")).map_err(|e| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(format!("Cannot read manifest.json: {e}")),
+            None,
+        ))
+    })?;
+    decode_manifest(&bytes)
+        .and_then(|(content, _)| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        .map_err(|message| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::BadPack,
+                Some(format!("Invalid manifest.json: {message}")),
+                None,
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    #[test]
+    fn attest_writes_a_statement_and_refuses_a_tampered_pack() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifact = src.path().join("a.json");
+        fs::write(&artifact, r#"{"a": 1}"#).unwrap();
+        let pack = out.path().join("pack");
+        let sealed = execute_seal(std::slice::from_ref(&artifact), Some(&pack), None).unwrap();
+
+        let statement = out.path().join("statement.json");
+        let options = AttestOptions {
+            output: Some(statement.clone()),
+            ..AttestOptions::default()
+        };
+        let result = execute_attest(&pack, &options).unwrap();
+        assert_eq!(result.pack_id, sealed.pack_id);
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&statement).unwrap()).unwrap();
+        assert_eq!(written["subject"][0]["name"], "a.json");
+
+        fs::write(pack.join("a.json"), "tampered").unwrap();
+        let refusal = execute_attest(&pack, &AttestOptions::default()).unwrap_err();
+        assert_eq!(refusal.refusal.code, "E_BAD_PACK");
+    }
+}
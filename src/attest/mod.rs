@@ -0,0 +1,8 @@
+mod command;
+mod statement;
+
+pub use command::{execute_attest, AttestOptions, AttestResult};
+pub use statement::{
+    Envelope, EnvelopeSignature, PackPredicate, PredicateMember, Statement, Subject, PAYLOAD_TYPE,
+    PREDICATE_TYPE, STATEMENT_TYPE,
+};
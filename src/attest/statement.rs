@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::Serialize;
+
+use crate::seal::manifest::Manifest;
+
+/// in-toto Statement layer this command emits.
+pub const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+/// `predicateType` of the pack predicate.
+pub const PREDICATE_TYPE: &str = "https://github.com/cmdrvl/pack/attestation/v0";
+/// DSSE `payloadType` for a signed statement.
+pub const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// An in-toto Statement whose subjects are a pack's members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Statement {
+    #[serde(rename = "_type")]
+    pub statement_type: String,
+    pub subject: Vec<Subject>,
+    #[serde(rename = "predicateType")]
+    pub predicate_type: String,
+    pub predicate: PackPredicate,
+}
+
+/// One member as an in-toto ResourceDescriptor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Subject {
+    pub name: String,
+    /// Algorithm name (`sha256`, `sha512`, `blake3`) to hex digest.
+    pub digest: BTreeMap<String, String>,
+}
+
+/// Pack metadata carried as the predicate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackPredicate {
+    pub pack_id: String,
+    pub manifest_version: String,
+    pub created: String,
+    pub tool_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub member_count: usize,
+    /// Member types and versions, in manifest order; digests are in `subject`.
+    pub members: Vec<PredicateMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PredicateMember {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub member_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+/// A DSSE envelope around a signed [`Statement`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Envelope {
+    #[serde(rename = "payloadType")]
+    pub payload_type: String,
+    /// The statement's JSON, base64.
+    pub payload: String,
+    pub signatures: Vec<EnvelopeSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnvelopeSignature {
+    /// Signer's Ed25519 public key, hex, as in `manifest.sig.json`.
+    pub keyid: String,
+    /// Ed25519 signature over the DSSE pre-authentication encoding, base64.
+    pub sig: String,
+}
+
+impl Statement {
+    /// The statement for a verified pack's manifest.
+    pub fn from_manifest(manifest: &Manifest) -> Self {
+        let subject = manifest
+            .members
+            .iter()
+            .map(|member| {
+                let (algorithm, hex) = member
+                    .bytes_hash
+                    .split_once(':')
+                    .unwrap_or(("sha256", member.bytes_hash.as_str()));
+                Subject {
+                    name: member.path.clone(),
+                    digest: BTreeMap::from([(algorithm.to_string(), hex.to_string())]),
+                }
+            })
+            .collect();
+        let members = manifest
+            .members
+            .iter()
+            .map(|member| PredicateMember {
+                path: member.path.clone(),
+                member_type: member.member_type.clone(),
+                artifact_version: member.artifact_version.clone(),
+                size_bytes: member.size_bytes,
+            })
+            .collect();
+        Self {
+            statement_type: STATEMENT_TYPE.to_string(),
+            subject,
+            predicate_type: PREDICATE_TYPE.to_string(),
+            predicate: PackPredicate {
+                pack_id: manifest.pack_id.clone(),
+                manifest_version: manifest.version.clone(),
+                created: manifest.created.clone(),
+                tool_version: manifest.tool_version.clone(),
+                note: manifest.note.clone(),
+                member_count: manifest.member_count,
+                members,
+            },
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("statement serialization cannot fail")
+    }
+
+    /// Sign the statement into a DSSE envelope with `key`.
+    pub fn sign(&self, key: &SigningKey) -> Envelope {
+        let payload = serde_json::to_vec(self).expect("statement serialization cannot fail");
+        let signature = key.sign(&pae(PAYLOAD_TYPE, &payload));
+        Envelope {
+            payload_type: PAYLOAD_TYPE.to_string(),
+            payload: STANDARD.encode(&payload),
+            signatures: vec![EnvelopeSignature {
+                keyid: hex::encode(key.verifying_key().as_bytes()),
+                sig: STANDARD.encode(signature.to_bytes()),
+            }],
+        }
+    }
+}
+
+impl Envelope {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("envelope serialization cannot fail")
+    }
+}
+
+/// DSSE pre-authentication encoding: the bytes a DSSE signature covers.
+fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = format!(
+        "DSSEv1 {} {payload_type} {} ",
+        payload_type.len(),
+        payload.len()
+    )
+    .into_bytes();
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::manifest::Member;
+    use ed25519_dalek::{Signature, Verifier};
+
+    fn manifest() -> Manifest {
+        let member = |path: &str, bytes_hash: &str| Member {
+            path: path.to_string(),
+            bytes_hash: bytes_hash.to_string(),
+            size_bytes: Some(2),
+            member_type: "other".to_string(),
+            artifact_version: None,
+            visibility: None,
+            origin: None,
+            executable: None,
+            ciphertext_hash: None,
+            content_type: None,
+            charset: None,
+        };
+        let mut manifest = Manifest::new(
+            "2026-01-15T10:00:00Z".to_string(),
+            None,
+            "0.2.3".to_string(),
+            vec![
+                member("a.json", &format!("sha256:{}", "a".repeat(64))),
+                member("b.bin", &format!("blake3:{}", "b".repeat(64))),
+            ],
+        );
+        manifest.pack_id = format!("sha256:{}", "c".repeat(64));
+        manifest
+    }
+
+    #[test]
+    fn members_become_subjects_keyed_by_their_hash_algorithm() {
+        let statement = Statement::from_manifest(&manifest());
+        let value: serde_json::Value = serde_json::from_str(&statement.to_json()).unwrap();
+        assert_eq!(value["_type"], STATEMENT_TYPE);
+        assert_eq!(value["predicateType"], PREDICATE_TYPE);
+        assert_eq!(value["subject"][0]["name"], "a.json");
+        assert_eq!(value["subject"][0]["digest"]["sha256"], "a".repeat(64));
+        assert_eq!(value["subject"][1]["digest"]["blake3"], "b".repeat(64));
+        assert_eq!(
+            value["predicate"]["pack_id"],
+            format!("sha256:{}", "c".repeat(64))
+        );
+    }
+
+    #[test]
+    fn signed_envelope_verifies_over_the_pae() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let statement = Statement::from_manifest(&manifest());
+        let envelope = statement.sign(&key);
+        assert_eq!(envelope.payload_type, PAYLOAD_TYPE);
+        let payload = STANDARD.decode(&envelope.payload).unwrap();
+        assert_eq!(payload, serde_json::to_vec(&statement).unwrap());
+
+        let sig = STANDARD.decode(&envelope.signatures[0].sig).unwrap();
+        let sig = Signature::from_slice(&sig).unwrap();
+        key.verifying_key()
+            .verify(&pae(PAYLOAD_TYPE, &payload), &sig)
+            .unwrap();
+        assert_eq!(
+            envelope.signatures[0].keyid,
+            hex::encode(key.verifying_key().as_bytes())
+        );
+    }
+}
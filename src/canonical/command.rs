@@ -0,0 +1,61 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::json::canonicalize;
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Execute `pack canon`: the canonical form of the JSON document in `file`,
+/// or on stdin when `file` is `-`, for comparing what two tools hash.
+///
+/// A file that cannot be read or is not JSON refuses with `E_IO`.
+pub fn execute_canon(file: &Path) -> (String, u8) {
+    let refuse = |message: String| {
+        let refusal = RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(message),
+            Some(json!({ "file": file.display().to_string() })),
+        );
+        (refusal.to_json(), 2)
+    };
+    let bytes = if file == Path::new("-") {
+        let mut bytes = Vec::new();
+        match io::stdin().read_to_end(&mut bytes) {
+            Ok(_) => bytes,
+            Err(e) => return refuse(format!("Cannot read stdin: {e}")),
+        }
+    } else {
+        match fs::read(file) {
+            Ok(bytes) => bytes,
+            Err(e) => return refuse(format!("Cannot read {}: {e}", file.display())),
+        }
+    };
+    match serde_json::from_slice::<Value>(&bytes) {
+        Ok(value) => (canonicalize(&value), 0),
+        Err(e) => refuse(format!("Invalid JSON in {}: {e}", file.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn canon_prints_canonical_text_or_refuses() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("doc.json");
+        fs::write(&file, "{ \"b\": 1.0,\n  \"a\": [1e21] }\n").unwrap();
+        assert_eq!(
+            execute_canon(&file),
+            (r#"{"a":[1e+21],"b":1}"#.to_string(), 0)
+        );
+
+        fs::write(&file, "{").unwrap();
+        let (output, code) = execute_canon(&file);
+        assert_eq!(code, 2);
+        assert!(output.contains("E_IO"), "{output}");
+    }
+}
@@ -0,0 +1,168 @@
+use serde_json::{Number, Value};
+
+/// Serialize `value` as RFC 8785 (JSON Canonicalization Scheme) text.
+///
+/// This is the one serializer behind every byte string pack hashes or signs:
+/// `pack_id`, manifest signatures and timestamps, witness record ids, and
+/// signed deprecation notes. Output has no insignificant whitespace, object
+/// keys sorted by their UTF-16 code units, and strings escaped only where
+/// JSON requires it (`\"`, `\\`, the short escapes `\b \t \n \f \r`, and
+/// `\u00xx` for other control characters). Integers are written exactly,
+/// including `u64` and `i64` values beyond 2^53 that RFC 8785 would round;
+/// other numbers are written the way ECMAScript prints a double (`1.5`,
+/// `1e+21`, `1e-7`, `0` for `-0`).
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_number(out: &mut String, n: &Number) {
+    if let Some(u) = n.as_u64() {
+        out.push_str(&u.to_string());
+    } else if let Some(i) = n.as_i64() {
+        out.push_str(&i.to_string());
+    } else {
+        out.push_str(&format_double(n.as_f64().expect("JSON number is finite")));
+    }
+}
+
+/// ECMAScript `Number.prototype.toString` for a finite double, as RFC 8785
+/// section 3.2.2.3 requires.
+fn format_double(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    let sign = if value < 0.0 { "-" } else { "" };
+    // Rust's `{:e}` gives the shortest digits that round-trip, as
+    // ECMAScript does: `d[.ddd]e<exp>`.
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("LowerExp output has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // The value is 0.<digits> x 10^n.
+    let n = exponent
+        .parse::<i32>()
+        .expect("LowerExp exponent is an integer")
+        + 1;
+
+    let body = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let e = n - 1;
+        let e_sign = if e < 0 { '-' } else { '+' };
+        let mantissa = if k == 1 {
+            digits
+        } else {
+            format!("{}.{}", &digits[..1], &digits[1..])
+        };
+        format!("{mantissa}e{e_sign}{}", e.abs())
+    };
+    format!("{sign}{body}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canon(json: &str) -> String {
+        canonicalize(&serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn numbers_follow_ecmascript_formatting() {
+        // RFC 8785 appendix B samples that a JSON parser can represent.
+        let cases = [
+            ("0.0", "0"),
+            ("-0.0", "0"),
+            ("1.0", "1"),
+            ("-1.5", "-1.5"),
+            ("1e21", "1e+21"),
+            ("1e20", "100000000000000000000"),
+            ("123456789012345680000", "123456789012345680000"),
+            ("1e-7", "1e-7"),
+            ("0.000001", "0.000001"),
+            ("4.50", "4.5"),
+            ("2e-3", "0.002"),
+            ("1.7976931348623157e308", "1.7976931348623157e+308"),
+            ("5e-324", "5e-324"),
+            ("9007199254740993", "9007199254740993"),
+            ("-9223372036854775808", "-9223372036854775808"),
+            ("333333333.33333329", "333333333.3333333"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(canon(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn keys_sort_by_utf16_and_strings_escape_minimally() {
+        // U+1F600 is a surrogate pair (D83D ...), which sorts before U+FB33
+        // in UTF-16 although it follows it as a code point.
+        assert_eq!(
+            canon(r#"{"דּ": 1, "😀": 2, "b": [true, null], "a": "x"}"#),
+            "{\"a\":\"x\",\"b\":[true,null],\"\u{1F600}\":2,\"\u{FB33}\":1}"
+        );
+        assert_eq!(
+            canon(r#""\u0008\t\n\u000c\r\u001f\u007f \"\\ \/ é""#),
+            "\"\\b\\t\\n\\f\\r\\u001f\u{7f} \\\"\\\\ / é\""
+        );
+    }
+}
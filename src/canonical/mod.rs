@@ -0,0 +1,5 @@
+mod command;
+mod json;
+
+pub use command::execute_canon;
+pub use json::canonicalize;
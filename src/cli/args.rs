@@ -1,6 +1,12 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::cmp::Ordering;
 use std::path::PathBuf;
 
+use crate::encrypt::Recipient;
+use crate::network::manifest::RemotePack;
+use crate::seal::annotate::MemberAnnotation;
+use crate::seal::visibility::VisibilityRule;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "pack",
@@ -16,22 +22,52 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub schema: bool,
 
+    /// Print the standalone JSON Schema for one output shape and exit.
+    #[arg(long, global = true, value_name = "SHAPE", conflicts_with = "schema")]
+    pub schema_for: Option<SchemaTarget>,
+
     /// Suppress witness ledger recording.
     #[arg(long, global = true)]
     pub no_witness: bool,
 
+    /// Log progress on stderr: -v for command start/finish and pack
+    /// milestones, -vv for every member, -vvv for seal's collection totals.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Log one JSON object per line (phase, event, member, bytes,
+    /// elapsed_ms); logs at -vv unless more -v are given.
+    #[arg(long, global = true)]
+    pub log_json: bool,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
 
+// Parsed once per process, so the size of the seal variant does not matter.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Seal artifacts into an evidence pack directory.
     Seal {
-        /// Files or directories to include.
-        #[arg(required = true)]
+        /// Files or directories to include; `-` reads one member from stdin
+        /// (requires --stdin-name).
+        #[arg(required_unless_present_any = ["transaction", "from_list"])]
         artifacts: Vec<PathBuf>,
 
+        /// Read more artifact paths from FILE ("-" for stdin), one per line.
+        /// Use for path lists too long for the command line.
+        #[arg(long, value_name = "FILE")]
+        from_list: Option<PathBuf>,
+
+        /// Entries in --from-list are NUL-delimited (as from `find -print0`).
+        #[arg(short = '0', long = "null", requires = "from_list")]
+        null: bool,
+
+        /// Member path for the `-` artifact (e.g. reports/daily.json).
+        #[arg(long, value_name = "NAME")]
+        stdin_name: Option<String>,
+
         /// Output directory (default: pack/<pack_id>/).
         #[arg(long)]
         output: Option<PathBuf>,
@@ -39,45 +75,609 @@ pub enum Command {
         /// Optional annotation in manifest.
         #[arg(long)]
         note: Option<String>,
+
+        /// Output as JSON (a pack.seal.v0 report, or an array of them with
+        /// --transaction).
+        #[arg(long)]
+        json: bool,
+
+        /// JSON file mapping source paths to precomputed sha256 hashes.
+        #[arg(long, value_name = "FILE")]
+        trust_hashes: Option<PathBuf>,
+
+        /// Digest for member hashes, recorded as each bytes_hash prefix
+        /// (default: the hash_algorithm setting, else sha256).
+        #[arg(
+            long,
+            value_enum,
+            value_name = "ALGORITHM",
+            conflicts_with = "trust_hashes"
+        )]
+        hash_algorithm: Option<HashAlgorithm>,
+
+        /// Cover members' recorded executable bits with pack_id, so changing
+        /// one invalidates the pack.
+        #[arg(long)]
+        hash_executable: bool,
+
+        /// Maximum note size in bytes (default: 65536).
+        #[arg(long, value_name = "BYTES")]
+        max_note_bytes: Option<usize>,
+
+        /// Include .git metadata and allow output inside a git work tree.
+        #[arg(long)]
+        allow_git: bool,
+
+        /// Refuse with E_LIMIT as soon as the inputs yield more than N members.
+        #[arg(long, value_name = "N")]
+        max_members: Option<usize>,
+
+        /// Refuse with E_LIMIT as soon as the inputs total more than this
+        /// many bytes.
+        #[arg(long, value_name = "BYTES")]
+        max_total_bytes: Option<u64>,
+
+        /// Report files found and bytes so far on stderr while collecting
+        /// large input trees.
+        #[arg(long)]
+        progress: bool,
+
+        /// Maximum size of each URL artifact in bytes (default: 1 GiB).
+        #[arg(long, value_name = "BYTES")]
+        max_remote_bytes: Option<u64>,
+
+        /// Follow redirects from URL artifacts to a different host.
+        #[arg(long)]
+        allow_cross_host_redirects: bool,
+
+        /// Write a single deterministic .pack.tar file instead of a directory.
+        #[arg(long)]
+        archive: bool,
+
+        /// Sign the manifest with Ed25519, writing manifest.sig.json.
+        #[arg(long)]
+        sign: bool,
+
+        /// Ed25519 key for --sign (PKCS#8 PEM or 32-byte hex/base64 seed).
+        /// Defaults to PACK_SIGNING_KEY.
+        #[arg(long, value_name = "FILE", requires = "sign")]
+        signing_key: Option<PathBuf>,
+
+        /// Obtain an RFC 3161 timestamp token over the manifest from this
+        /// Time Stamp Authority, writing manifest.tst.json.
+        #[arg(long, value_name = "URL")]
+        timestamp_url: Option<String>,
+
+        /// Encrypt every member to this age X25519 recipient (age1...),
+        /// leaving the manifest readable. Repeatable; any one recipient's
+        /// identity decrypts.
+        #[arg(
+            long = "encrypt",
+            value_name = "RECIPIENT",
+            value_parser = Recipient::parse,
+            conflicts_with = "resume"
+        )]
+        encrypt: Vec<Recipient>,
+
+        /// Tag members matching PATTERN (path, directory, or glob) with a
+        /// visibility level such as auditors-only. Repeatable; last match wins.
+        #[arg(long, value_name = "PATTERN=LEVEL", value_parser = VisibilityRule::from_flag)]
+        restrict: Vec<VisibilityRule>,
+
+        /// Label one member for reviewers, e.g. "loans/tape.csv=the disputed
+        /// loan tape". Repeatable; recorded in the manifest's annotations.
+        #[arg(long, value_name = "MEMBER_PATH=TEXT", value_parser = MemberAnnotation::from_flag)]
+        annotate: Vec<MemberAnnotation>,
+
+        /// Empty directories in input trees: drop them, keep each as a
+        /// zero-byte .packkeep member, or list them in the manifest.
+        #[arg(long, value_enum, value_name = "MODE", default_value_t = EmptyDirs::Drop)]
+        empty_dirs: EmptyDirs,
+
+        /// Follow symlinks in inputs and seal their targets under the link's
+        /// path, refusing a directory link that loops back on itself.
+        #[arg(long, conflicts_with = "skip_symlinks")]
+        follow_symlinks: bool,
+
+        /// Leave symlinks in inputs out of the pack, listing them in the
+        /// manifest's provenance. By default a symlink is refused with E_IO.
+        #[arg(long)]
+        skip_symlinks: bool,
+
+        /// Seal each .zip artifact as a directory named after it, so its
+        /// entries become members under that prefix.
+        #[arg(long)]
+        expand_archives: bool,
+
+        /// Record when each member finished copying and hashing in the
+        /// manifest's provenance, for correlating long seals with upstream state.
+        #[arg(long)]
+        member_timestamps: bool,
+
+        /// Record each member's absolute source path and modification time,
+        /// the hostname, and the invoking user in the manifest's provenance.
+        /// Covered by pack_id, so the same inputs sealed elsewhere differ.
+        #[arg(long)]
+        provenance: bool,
+
+        /// Refuse with E_AMBIGUOUS_TYPE when a member's type rests on its
+        /// filename or path alone rather than its content.
+        #[arg(long)]
+        strict_types: bool,
+
+        /// Custom type detection rules (TOML or JSON) mapping versions, path
+        /// globs, and JSON keys to member types; tried before the built-in
+        /// rules and recorded in the manifest.
+        #[arg(long, value_name = "FILE")]
+        detect_rules: Option<PathBuf>,
+
+        /// Stage members in DIR with a checkpoint of each one hashed; after a
+        /// failure, the same command continues from it and seals the same pack.
+        /// DIR is removed once the pack is created.
+        #[arg(long, value_name = "DIR", conflicts_with = "transaction")]
+        resume: Option<PathBuf>,
+
+        /// Make pack_id independent of when the pack is sealed: pin created
+        /// to SOURCE_DATE_EPOCH when it is set, otherwise leave created out
+        /// of pack_id. The mode is recorded in the manifest.
+        #[arg(long, conflicts_with = "member_timestamps")]
+        reproducible: bool,
+
+        /// JSON file of upstream descriptors keyed by member path
+        /// ({"<path>": {"registry", "digest", "media_type"}}) to record as
+        /// each member's origin.
+        #[arg(long, value_name = "FILE")]
+        origins: Option<PathBuf>,
+
+        /// Carry every member of this existing pack (directory or .pack.tar)
+        /// into the new pack and record its pack_id as the parent in the
+        /// manifest's lineage. An artifact whose member path the pack
+        /// already holds is refused with E_DUPLICATE.
+        #[arg(long, value_name = "PACK", conflicts_with = "transaction")]
+        merge: Option<PathBuf>,
+
+        /// Seal every pack in a JSON plan ({"seals": [{"artifacts", "output",
+        /// "note", "restrict", "origins", "annotate"}]}) atomically: all packs are created
+        /// or none.
+        #[arg(
+            long,
+            value_name = "PLAN",
+            conflicts_with_all = ["artifacts", "from_list", "stdin_name", "output", "note", "restrict", "origins", "annotate"]
+        )]
+        transaction: Option<PathBuf>,
     },
 
     /// Verify pack integrity (members + pack_id).
     Verify {
-        /// Path to the pack directory.
+        /// Path to the pack directory or .pack.tar archive (with --all, a
+        /// directory to search for packs).
         pack_dir: PathBuf,
 
+        /// Verify every pack under PACK_DIR: each directory holding a
+        /// manifest.json and each .pack.tar file.
+        #[arg(long, conflicts_with_all = ["member_root", "repair_report", "against"])]
+        all: bool,
+
+        /// With --all, verify this many packs at once.
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "all", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
         /// Output as JSON.
         #[arg(long)]
         json: bool,
+
+        /// Print each finding as a JSON line as soon as it is found, then a
+        /// summary line (pack.verify_stream.v0).
+        #[arg(long, conflicts_with_all = ["json", "all", "repair_report"])]
+        json_lines: bool,
+
+        /// Resolve member bytes from this directory instead of PACK_DIR.
+        #[arg(long, value_name = "PATH")]
+        member_root: Option<PathBuf>,
+
+        /// Verify the members in PACK_DIR against this manifest file, kept
+        /// apart from them, instead of PACK_DIR/manifest.json.
+        #[arg(long, value_name = "MANIFEST", conflicts_with = "member_root")]
+        against: Option<PathBuf>,
+
+        /// Flag notes and member annotations larger than this many bytes
+        /// (default: 65536).
+        #[arg(long, value_name = "BYTES")]
+        max_note_bytes: Option<usize>,
+
+        /// Downgrade a finding code (optionally CODE:PATH) to a warning. Repeatable.
+        #[arg(long = "ignore-finding", value_name = "CODE[:PATH]")]
+        ignore_findings: Vec<String>,
+
+        /// JSON file of justified suppressions.
+        #[arg(long, value_name = "FILE")]
+        suppressions: Option<PathBuf>,
+
+        /// Report INVALID unless manifest.sig.json is present and valid.
+        #[arg(long)]
+        require_signature: bool,
+
+        /// Accept signatures only from this Ed25519 public key (PEM or 32-byte hex/base64).
+        #[arg(long, value_name = "FILE")]
+        public_key: Option<PathBuf>,
+
+        /// Also check that reports reference lockfiles and members present in
+        /// the pack (CROSS_REFERENCE_MISMATCH / CROSS_REFERENCE_MISSING).
+        #[arg(long)]
+        deep: bool,
+
+        /// Also check that CSV/TSV registry members parse, with a consistent
+        /// header, and match the row counts declared in registry.json
+        /// (REGISTRY_MALFORMED).
+        #[arg(long)]
+        validate_registry: bool,
+
+        /// Policy for a pack with no members: allow it silently, warn
+        /// (EMPTY_PACK warning, still OK), or fail (INVALID).
+        #[arg(long, value_enum, default_value_t = EmptyPackPolicy::Warn, value_name = "POLICY")]
+        empty_pack: EmptyPackPolicy,
+
+        /// Also verify packs embedded as members (`<dir>/manifest.json` typed
+        /// pack, or `*.pack.tar`), reporting their findings under the nested path.
+        #[arg(long)]
+        resolve_nested: bool,
+
+        /// Report how long each check took, in milliseconds, under `timings`.
+        #[arg(long)]
+        timings: bool,
+
+        /// Skip content hashing: check manifest structure, member existence,
+        /// recorded sizes (SIZE_MISMATCH), and pack_id only.
+        #[arg(long)]
+        quick: bool,
+
+        /// Require manifest.tst.json and check its RFC 3161 token stamps the
+        /// manifest (TIMESTAMP_MISSING / TIMESTAMP_INVALID).
+        #[arg(long)]
+        check_timestamp: bool,
+
+        /// PEM CA certificate(s) the timestamp token's TSA certificate must
+        /// chain to; without it checks.timestamp is "unverified".
+        #[arg(long, value_name = "FILE", requires = "check_timestamp")]
+        tsa_cert: Option<PathBuf>,
+
+        /// age identity file (AGE-SECRET-KEY-1...) to decrypt encrypted
+        /// members with, checking their plaintext hashes and sizes.
+        #[arg(long, value_name = "FILE")]
+        decrypt_key: Option<PathBuf>,
+
+        /// Fail on warning-class findings (NONCANONICAL_MANIFEST_ENCODING,
+        /// MISSING_MEMBER_SIZE, DEPRECATED) instead of only reporting them.
+        #[arg(long)]
+        strict: bool,
+
+        /// JSON trust policy ({"min_tool_version", "allowed_signers",
+        /// "max_age_days"}); each violated rule is a TRUST_POLICY finding.
+        #[arg(long, value_name = "FILE")]
+        trust_policy: Option<PathBuf>,
+
+        /// JSON member policy ({"required": [{"type", "artifact_version",
+        /// "path", "content_type", "min"}], "allowed_types", "forbidden_types",
+        /// "allowed_content_types", "forbidden_content_types"}); each
+        /// violation is a POLICY_VIOLATION finding.
+        #[arg(long, value_name = "FILE")]
+        policy: Option<PathBuf>,
+
+        /// Directory of draft 2020-12 JSON Schemas named
+        /// <artifact_version>.schema.json; members of those versions are
+        /// validated against them instead of the built-in checks.
+        #[arg(long, value_name = "DIR")]
+        schema_dir: Option<PathBuf>,
+
+        /// Also write a pack.repair.v0 remediation plan for the findings to FILE.
+        #[arg(long, value_name = "FILE")]
+        repair_report: Option<PathBuf>,
     },
 
     /// Deterministically diff two packs.
     Diff {
         /// First pack directory.
-        a: PathBuf,
+        #[arg(required_unless_present = "remote")]
+        a: Option<PathBuf>,
 
         /// Second pack directory.
-        b: PathBuf,
+        #[arg(required_unless_present = "remote")]
+        b: Option<PathBuf>,
+
+        /// Diff two stored packs from their manifests alone, never fetching
+        /// members. Give exactly twice: A, then B. STORE is as for
+        /// `pull --from` and defaults to PACK_STORE.
+        #[arg(
+            long,
+            value_name = "PACK_ID[@STORE]",
+            value_parser = RemotePack::from_flag,
+            conflicts_with_all = ["a", "b"]
+        )]
+        remote: Vec<RemotePack>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+
+        /// Member ordering for human output (JSON keeps manifest order).
+        #[arg(long, value_enum, default_value_t = SortOrder::Bytewise)]
+        sort: SortOrder,
+    },
+
+    /// Show a pack's manifest: header fields and a member table.
+    Inspect {
+        /// Path to the pack directory or .pack.tar archive.
+        pack_dir: PathBuf,
+
+        /// Show every recorded field of one member.
+        #[arg(long, value_name = "PATH")]
+        member: Option<String>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+
+        /// Member ordering for human output (JSON keeps manifest order).
+        #[arg(long, value_enum, default_value_t = SortOrder::Bytewise)]
+        sort: SortOrder,
+    },
+
+    /// Recompute a pack's pack_id from its manifest and compare it with the
+    /// recorded one. Member bytes are not read; use verify for that.
+    Id {
+        /// Path to the pack directory or .pack.tar archive.
+        pack_dir: PathBuf,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Copy members out of a pack, re-hashing each against the manifest.
+    /// Nothing is written unless every selected member matches.
+    Extract {
+        /// Path to the pack directory or .pack.tar archive.
+        pack_dir: PathBuf,
+
+        /// Member to extract: a path, a directory prefix, or a glob (`*`
+        /// within a segment, `**` across). Repeatable; defaults to all.
+        #[arg(long, value_name = "PATH")]
+        member: Vec<String>,
+
+        /// Directory to write members under, at their member paths.
+        #[arg(
+            long,
+            value_name = "DIR",
+            required_unless_present = "verify_only_selected",
+            conflicts_with = "verify_only_selected"
+        )]
+        out: Option<PathBuf>,
+
+        /// age identity file (AGE-SECRET-KEY-1...) for encrypted members.
+        #[arg(long, value_name = "FILE")]
+        decrypt_key: Option<PathBuf>,
+
+        /// Also extract members carrying a visibility tag (`--restrict`).
+        #[arg(long)]
+        include_restricted: bool,
+
+        /// Spot-check instead: re-hash N randomly sampled members in scratch
+        /// space and report, keeping nothing.
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        verify_only_selected: Option<u32>,
+
+        /// Seed for the --verify-only-selected sample; the same seed draws the
+        /// same members. Defaults to a fresh seed, printed in the report.
+        #[arg(long, value_name = "SEED", requires = "verify_only_selected")]
+        seed: Option<u32>,
+
+        /// Sign the spot-check report with Ed25519 (key from --signing-key or
+        /// PACK_SIGNING_KEY).
+        #[arg(long, requires = "verify_only_selected")]
+        sign: bool,
+
+        /// Ed25519 key for --sign (PKCS#8 PEM or 32-byte hex/base64 seed).
+        #[arg(long, value_name = "FILE", requires = "sign")]
+        signing_key: Option<PathBuf>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a pack's ancestry: the parents recorded in its lineage, read
+    /// from the store, with the members each step changed.
+    Lineage {
+        /// Path to the pack directory or .pack.tar archive.
+        pack_dir: PathBuf,
+
+        /// Store holding the parent packs: file:// URL or directory path, or
+        /// an HTTP store. Defaults to PACK_STORE.
+        #[arg(long, value_name = "STORE")]
+        store: Option<String>,
 
         /// Output as JSON.
         #[arg(long)]
         json: bool,
     },
 
-    /// Publish a pack to data-fabric.
+    /// Publish a pack to a store (data-fabric URL or filesystem path).
     Push {
         /// Pack directory to publish.
         pack_dir: PathBuf,
+
+        /// Store to publish to: http(s):// data-fabric URL, cas+http(s):// HTTP
+        /// store URL, file:// URL, or directory path. Defaults to PACK_STORE,
+        /// then PACK_DATA_FABRIC_BASE_URL.
+        #[arg(long, value_name = "STORE")]
+        to: Option<String>,
     },
 
-    /// Fetch a pack by ID from data-fabric.
+    /// Fetch a pack by ID from a store and verify it before materializing.
     Pull {
         /// Pack ID to fetch.
-        pack_id: String,
+        #[arg(required_unless_present = "series", conflicts_with = "series")]
+        pack_id: Option<String>,
+
+        /// Series (alias) to resolve via the remote index instead of a pack ID.
+        #[arg(long, requires = "as_of")]
+        series: Option<String>,
+
+        /// RFC3339 timestamp: fetch the pack current for --series at this time.
+        #[arg(long, value_name = "TIMESTAMP", requires = "series")]
+        as_of: Option<String>,
 
         /// Output directory.
         #[arg(long = "out")]
         out_dir: PathBuf,
+
+        /// Store to fetch from: http(s):// data-fabric URL, cas+http(s):// HTTP
+        /// store URL, file:// URL, or directory path. Defaults to PACK_STORE,
+        /// then PACK_DATA_FABRIC_BASE_URL.
+        #[arg(long, value_name = "STORE")]
+        from: Option<String>,
+
+        /// JSON trust policy the fetched pack must satisfy, as for
+        /// `verify --trust-policy`; a violation refuses with E_BAD_PACK.
+        #[arg(long, value_name = "FILE")]
+        trust_policy: Option<PathBuf>,
+    },
+
+    /// Mark a stored pack as deprecated in the store index. The pack itself is
+    /// not modified; verify, inspect, and pull warn when they meet it.
+    Deprecate {
+        /// Pack ID to deprecate.
+        pack_id: String,
+
+        /// Why the pack should no longer be used.
+        #[arg(long, value_name = "TEXT")]
+        reason: String,
+
+        /// Filesystem store holding the pack: file:// URL or directory path.
+        /// Defaults to PACK_STORE.
+        #[arg(long, value_name = "STORE")]
+        store: Option<String>,
+
+        /// Write the note without a signature.
+        #[arg(long)]
+        unsigned: bool,
+
+        /// Ed25519 key to sign the note with (PKCS#8 PEM or 32-byte
+        /// hex/base64 seed). Defaults to PACK_SIGNING_KEY, then
+        /// PACK_SIGNING_KEY_FILE.
+        #[arg(long, value_name = "FILE", conflicts_with = "unsigned")]
+        signing_key: Option<PathBuf>,
+    },
+
+    /// Verify every pack in a filesystem store, then keep verifying new or
+    /// modified packs as they appear.
+    Watch {
+        /// Store root (the directory holding sha256/<hex>/ pack directories).
+        store_root: PathBuf,
+
+        /// Shell command run for each pack that does not verify OK. Receives
+        /// PACK_DIR, PACK_ID, and PACK_VERIFY_OUTCOME in the environment and
+        /// the verify JSON on stdin.
+        #[arg(long, value_name = "CMD")]
+        on_invalid: Option<String>,
+
+        /// Verify the current store contents once and exit.
+        #[arg(long)]
+        once: bool,
+
+        /// Seconds between full rescans (default: 2).
+        #[arg(long, value_name = "SECS")]
+        interval: Option<u64>,
+    },
+
+    /// Remove packs from a filesystem store that no retention rule keeps.
+    /// Packs named in --retain are never removed.
+    Gc {
+        /// Filesystem store: file:// URL or directory path. Defaults to
+        /// PACK_STORE.
+        #[arg(long, value_name = "STORE")]
+        store: Option<String>,
+
+        /// Keep the N most recently created packs.
+        #[arg(long, value_name = "N", required_unless_present = "keep_since")]
+        keep_last: Option<usize>,
+
+        /// Keep packs created at or after DATE (RFC3339 or YYYY-MM-DD).
+        #[arg(long, value_name = "DATE")]
+        keep_since: Option<String>,
+
+        /// File of pack IDs to keep regardless of age, one per line.
+        #[arg(long, value_name = "FILE")]
+        retain: Option<PathBuf>,
+
+        /// Report what would be removed without removing anything.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Report on the packs held by a filesystem store.
+    Store {
+        #[command(subcommand)]
+        command: StoreCommand,
+    },
+
+    /// Run built-in known-answer tests of hashing, canonical JSON, and a
+    /// golden seal/verify round trip. Exits 1 on any mismatch.
+    SelfTest {
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Verify a pack and print its members as an in-toto Statement, for
+    /// supply-chain provenance tooling.
+    Attest {
+        /// Path to the pack directory or .pack.tar archive.
+        pack_dir: PathBuf,
+
+        /// Write the statement to FILE instead of stdout.
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Sign the statement with Ed25519 and print a DSSE envelope (key from
+        /// --signing-key or PACK_SIGNING_KEY).
+        #[arg(long)]
+        sign: bool,
+
+        /// Ed25519 key for --sign (PKCS#8 PEM or 32-byte hex/base64 seed).
+        #[arg(long, value_name = "FILE", requires = "sign")]
+        signing_key: Option<PathBuf>,
+    },
+
+    /// Show how seal would type a file: the member type, the rule that
+    /// matched, and how confident that rule is.
+    Detect {
+        /// File to classify.
+        file: PathBuf,
+
+        /// Member path to detect against, when it differs from FILE (path
+        /// rules such as registry/ look at the member path).
+        #[arg(long, value_name = "PATH")]
+        member_path: Option<String>,
+
+        /// Custom detection rules to try first, as for seal --detect-rules.
+        #[arg(long, value_name = "FILE")]
+        detect_rules: Option<PathBuf>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the RFC 8785 canonical form of a JSON document, the bytes pack
+    /// hashes for `pack_id`. For debugging hash mismatches against other tools.
+    Canon {
+        /// JSON file to canonicalize, or `-` for stdin.
+        file: PathBuf,
     },
 
     /// Query witness ledger.
@@ -85,6 +685,94 @@ pub enum Command {
         #[command(subcommand)]
         command: WitnessCommand,
     },
+
+    /// List every command's outcomes, exit codes, and refusal codes.
+    Outcomes {
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Write a starter config.toml, detection rules file, and verify policy
+    /// into DIR. Existing files are never overwritten.
+    Quickstart {
+        /// Directory to write the files into.
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect layered settings (config file, then environment).
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// Check artifact files against the schemas verify uses.
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the effective configuration and where each value came from.
+    Show {
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchemaCommand {
+    /// Validate one artifact file (lock.v0, a report, verify rules, a pack
+    /// manifest, ...) against the schema for its artifact version. Exits 1
+    /// when it fails or no schema applies.
+    Validate {
+        /// Artifact file to validate.
+        file: PathBuf,
+
+        /// Schema to apply, instead of the file's top-level "version".
+        #[arg(long, value_name = "VERSION")]
+        artifact_version: Option<String>,
+
+        /// Directory of JSON Schema (draft 2020-12) documents named
+        /// <artifact_version>.schema.json, used in place of built-in schemas.
+        #[arg(long, value_name = "DIR")]
+        schema_dir: Option<PathBuf>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StoreCommand {
+    /// Count how many packs reference each unique member content hash.
+    Refcount {
+        /// Filesystem store: file:// URL or directory path. Defaults to
+        /// PACK_STORE.
+        #[arg(long, value_name = "STORE")]
+        store: Option<String>,
+
+        /// Only list hashes referenced at least N times.
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        min_refs: usize,
+
+        /// Only list this content hash, to see which packs hold it.
+        #[arg(long, value_name = "sha256:HEX")]
+        hash: Option<String>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -94,6 +782,11 @@ pub enum WitnessCommand {
         #[command(flatten)]
         filters: WitnessFilters,
 
+        /// Also list ledger lines that are not witness records (torn or
+        /// edited); they are always skipped.
+        #[arg(long)]
+        report_corrupt: bool,
+
         /// Output as JSON.
         #[arg(long)]
         json: bool,
@@ -106,6 +799,30 @@ pub enum WitnessCommand {
         json: bool,
     },
 
+    /// Show the last matching witness records, and with --follow keep
+    /// printing records as they are appended.
+    Tail {
+        #[command(flatten)]
+        filters: WitnessFilters,
+
+        /// Number of records to show before following.
+        #[arg(short = 'n', long = "lines", value_name = "N", default_value_t = crate::witness::tail::DEFAULT_TAIL_LINES)]
+        lines: usize,
+
+        /// Keep running and print matching records as they are appended.
+        #[arg(short = 'f', long)]
+        follow: bool,
+
+        /// Seconds between ledger polls when file events are unavailable
+        /// (default: 1).
+        #[arg(long, value_name = "SECS", requires = "follow")]
+        interval: Option<u64>,
+
+        /// Print one compact JSON record per line (JSONL).
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Count witness records.
     Count {
         #[command(flatten)]
@@ -115,6 +832,49 @@ pub enum WitnessCommand {
         #[arg(long)]
         json: bool,
     },
+
+    /// Validate every ledger line against the witness.v0 schema.
+    Validate {
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Write matching records to a JSON array, CSV, or Parquet file, or
+    /// one file per month or day, for loading into a warehouse.
+    Export {
+        #[command(flatten)]
+        filters: WitnessFilters,
+
+        /// File format.
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// Split records into <OUT>/<month|day>=<date>/witness.<ext> by their
+        /// UTC timestamp instead of writing OUT as one file.
+        #[arg(long, value_enum, default_value_t = ExportPartition::None)]
+        partition: ExportPartition,
+
+        /// Output file, or directory with --partition.
+        #[arg(long, value_name = "PATH")]
+        out: PathBuf,
+
+        /// Print the export summary as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the prev_hash links of a hash-chained ledger.
+    VerifyChain {
+        /// Head reported by an earlier verify-chain; INVALID if no record
+        /// hashes to it (the ledger was truncated or rewritten).
+        #[arg(long, value_name = "HASH")]
+        expect_head: Option<String>,
+
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Args, Debug, Clone, Default)]
@@ -139,3 +899,169 @@ pub struct WitnessFilters {
     #[arg(long = "input-hash")]
     pub input_hash: Option<String>,
 }
+
+/// File format for `pack witness export`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// How `pack witness export` splits records across files.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportPartition {
+    /// One file at `--out`.
+    #[default]
+    None,
+    Month,
+    Day,
+}
+
+impl ExportPartition {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Month => "month",
+            Self::Day => "day",
+        }
+    }
+}
+
+/// Output shapes selectable with `--schema-for`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaTarget {
+    Manifest,
+    Verify,
+    Diff,
+    Inspect,
+    Witness,
+    Refusal,
+    Seal,
+}
+
+impl SchemaTarget {
+    /// The `pack --schema` definition this shape names.
+    pub fn definition(self) -> &'static str {
+        match self {
+            Self::Manifest => "manifest",
+            Self::Verify => "verify_report",
+            Self::Diff => "diff_report",
+            Self::Inspect => "inspect_report",
+            Self::Witness => "witness_record",
+            Self::Refusal => "refusal_envelope",
+            Self::Seal => "seal_report",
+        }
+    }
+}
+
+/// What `pack seal` does with empty directories in input trees.
+///
+/// A pack holds only files, so by default empty directories are dropped.
+/// `Keep` adds a zero-byte `.packkeep` member inside each one; `List` records
+/// their paths in the manifest's `provenance.empty_dirs`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyDirs {
+    #[default]
+    Drop,
+    Keep,
+    List,
+}
+
+impl EmptyDirs {
+    /// The `--empty-dirs` value for this mode.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Drop => "drop",
+            Self::Keep => "keep",
+            Self::List => "list",
+        }
+    }
+}
+
+/// How `pack verify` treats a pack with no members.
+///
+/// An empty pack passes every integrity check, so by default verify stays
+/// `OK` but adds an `EMPTY_PACK` warning so automation can tell "verified
+/// evidence" from "verified nothing". `Fail` reports it as an `INVALID`
+/// finding instead; `Allow` says nothing.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyPackPolicy {
+    Allow,
+    #[default]
+    Warn,
+    Fail,
+}
+
+impl EmptyPackPolicy {
+    /// The `--empty-pack` value for this policy.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        }
+    }
+}
+
+/// Digest `pack seal` uses for member `bytes_hash` values.
+///
+/// The algorithm is recorded as the hash's prefix (`sha512:<hex>`), so verify
+/// needs no flag to check any of them. `pack_id` is always SHA-256.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// The `--hash-algorithm` value for this digest, which is also its
+    /// `bytes_hash` prefix.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+            Self::Blake3 => "blake3",
+        }
+    }
+}
+
+/// Member ordering for human-readable listings.
+///
+/// `Bytewise` is the manifest/hashing order. `Display` sorts case-insensitively
+/// for reviewers and never affects manifests, JSON output, or `pack_id`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Bytewise,
+    Display,
+}
+
+impl SortOrder {
+    /// Compare two member paths under this ordering.
+    ///
+    /// Display order folds case with Unicode lowercase mapping (locale-independent)
+    /// and falls back to bytewise order so the result is total and stable.
+    pub fn compare(self, a: &str, b: &str) -> Ordering {
+        match self {
+            Self::Bytewise => a.cmp(b),
+            Self::Display => a
+                .to_lowercase()
+                .cmp(&b.to_lowercase())
+                .then_with(|| a.cmp(b)),
+        }
+    }
+}
@@ -1,5 +1,13 @@
 mod args;
 mod exit;
+mod output;
+mod progress;
 
-pub use args::{Cli, Command, WitnessCommand, WitnessFilters};
+pub use args::{
+    Cli, Command, ConfigCommand, EmptyDirs, EmptyPackPolicy, ExportFormat, ExportPartition,
+    HashAlgorithm, SchemaCommand, SchemaTarget, SortOrder, StoreCommand, WitnessCommand,
+    WitnessFilters,
+};
 pub use exit::ExitCode;
+pub use output::{print_line, stdout_closed};
+pub use progress::{clear_progress, progress_enabled, progress_line};
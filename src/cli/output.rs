@@ -0,0 +1,32 @@
+use std::fmt::Display;
+use std::io::{self, ErrorKind, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STDOUT_CLOSED: AtomicBool = AtomicBool::new(false);
+
+/// Print `text` and a newline to stdout, flushed.
+///
+/// Unlike `println!`, a reader that has gone away (`pack verify --json | head
+/// -1`) is not a panic: the broken pipe is remembered, later output is
+/// dropped, and `run` exits with the command's own code once it finishes.
+/// Other write errors are reported on stderr. A progress line on stderr is erased first.
+pub fn print_line(text: impl Display) {
+    super::clear_progress();
+    if stdout_closed() {
+        return;
+    }
+    let mut stdout = io::stdout().lock();
+    let written = writeln!(stdout, "{text}").and_then(|()| stdout.flush());
+    match written {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => {
+            STDOUT_CLOSED.store(true, Ordering::Relaxed);
+        }
+        Err(e) => eprintln!("pack: cannot write to stdout: {e}"),
+    }
+}
+
+/// Whether a `print_line` has found stdout closed by its reader.
+pub fn stdout_closed() -> bool {
+    STDOUT_CLOSED.load(Ordering::Relaxed)
+}
@@ -0,0 +1,231 @@
+use std::io::{self, IsTerminal, Write};
+use std::ops::ControlFlow;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::events::{EventBus, PackEvent};
+
+/// How often the line is redrawn while members stream past.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The line currently drawn on stderr, if any.
+static LINE: Mutex<Option<ProgressLine>> = Mutex::new(None);
+
+/// Whether to draw the interactive progress line: only when both stdout and
+/// stderr are terminals and the command is not printing `--json`.
+pub fn progress_enabled(json_output: bool) -> bool {
+    !json_output && io::stdout().is_terminal() && io::stderr().is_terminal()
+}
+
+/// Add the interactive progress line to `events`. The line is redrawn in
+/// place on stderr and cleared by the next [`print_line`](super::print_line)
+/// or [`clear_progress`].
+pub fn progress_line(events: EventBus) -> EventBus {
+    events.subscribe(|event| {
+        let mut line = LINE.lock().unwrap_or_else(|e| e.into_inner());
+        let progress = line.get_or_insert_with(ProgressLine::default);
+        if progress.update(event) {
+            let text = progress.render(progress.phase_started.elapsed());
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K{text}");
+            let _ = stderr.flush();
+            progress.last_draw = Some(Instant::now());
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// Erase the progress line, if one is drawn.
+pub fn clear_progress() {
+    let mut line = LINE.lock().unwrap_or_else(|e| e.into_inner());
+    if line
+        .take()
+        .is_some_and(|progress| progress.last_draw.is_some())
+    {
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K");
+        let _ = stderr.flush();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Phase {
+    #[default]
+    Collect,
+    Hash,
+    Finalize,
+}
+
+/// Counters behind the progress line.
+#[derive(Debug, Clone)]
+struct ProgressLine {
+    phase: Phase,
+    phase_started: Instant,
+    members: usize,
+    bytes: u64,
+    total_members: usize,
+    total_bytes: u64,
+    last_draw: Option<Instant>,
+}
+
+impl Default for ProgressLine {
+    fn default() -> Self {
+        Self {
+            phase: Phase::Collect,
+            phase_started: Instant::now(),
+            members: 0,
+            bytes: 0,
+            total_members: 0,
+            total_bytes: 0,
+            last_draw: None,
+        }
+    }
+}
+
+impl ProgressLine {
+    /// Apply `event`; returns whether the line should be redrawn now.
+    fn update(&mut self, event: &PackEvent) -> bool {
+        match event {
+            PackEvent::CollectProgress { files, bytes } => {
+                self.members = *files;
+                self.bytes = *bytes;
+            }
+            PackEvent::HashingStarted { members, bytes } => {
+                *self = Self {
+                    phase: Phase::Hash,
+                    total_members: *members,
+                    total_bytes: *bytes,
+                    last_draw: self.last_draw,
+                    ..Self::default()
+                };
+                return true;
+            }
+            PackEvent::MemberHashed { size, .. } | PackEvent::MemberVerified { size, .. } => {
+                self.members += 1;
+                self.bytes += size;
+                if self.members == self.total_members {
+                    return true;
+                }
+            }
+            PackEvent::ManifestFinalized { .. } => {
+                self.phase = Phase::Finalize;
+                return true;
+            }
+            _ => return false,
+        }
+        self.last_draw
+            .is_none_or(|drawn| drawn.elapsed() >= REDRAW_INTERVAL)
+    }
+
+    /// `hashing: 12/40 members, 1.2 GiB/4.0 GiB (30%), 210.5 MiB/s, ETA 0:14`
+    fn render(&self, elapsed: Duration) -> String {
+        match self.phase {
+            Phase::Collect => format!(
+                "collecting: {} files, {}",
+                self.members,
+                format_bytes(self.bytes)
+            ),
+            Phase::Finalize => "finalizing".to_string(),
+            Phase::Hash => {
+                let mut text = format!(
+                    "hashing: {}/{} members, {}",
+                    self.members,
+                    self.total_members,
+                    format_bytes(self.bytes)
+                );
+                // Older manifests record no sizes, so progress falls back to
+                // member counts.
+                let fraction = if self.total_bytes > 0 {
+                    text.push_str(&format!("/{}", format_bytes(self.total_bytes)));
+                    self.bytes as f64 / self.total_bytes as f64
+                } else if self.total_members > 0 {
+                    self.members as f64 / self.total_members as f64
+                } else {
+                    1.0
+                };
+                text.push_str(&format!(" ({:.0}%)", (fraction * 100.0).min(100.0)));
+                let seconds = elapsed.as_secs_f64();
+                if seconds > 0.0 && self.bytes > 0 {
+                    text.push_str(&format!(
+                        ", {}/s",
+                        format_bytes((self.bytes as f64 / seconds) as u64)
+                    ));
+                }
+                if fraction > 0.0 && fraction < 1.0 {
+                    let remaining = seconds * (1.0 - fraction) / fraction;
+                    text.push_str(&format!(", ETA {}", format_duration(remaining)));
+                }
+                text
+            }
+        }
+    }
+}
+
+/// `512 B`, `1.5 KiB`, `210.5 MiB`, `4.0 GiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// `0:14`, `12:05`, `1:02:03`.
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashing_line_shows_counts_rate_and_eta() {
+        let mut line = ProgressLine::default();
+        assert!(line.update(&PackEvent::HashingStarted {
+            members: 4,
+            bytes: 4 * 1024 * 1024,
+        }));
+        line.update(&PackEvent::MemberHashed {
+            path: "a.bin".to_string(),
+            bytes_hash: String::new(),
+            size: 1024 * 1024,
+        });
+        assert_eq!(
+            line.render(Duration::from_secs(2)),
+            "hashing: 1/4 members, 1.0 MiB/4.0 MiB (25%), 512.0 KiB/s, ETA 0:06"
+        );
+
+        line.total_bytes = 0;
+        assert_eq!(
+            line.render(Duration::from_secs(2)),
+            "hashing: 1/4 members, 1.0 MiB (25%), 512.0 KiB/s, ETA 0:06"
+        );
+        assert!(line.update(&PackEvent::ManifestFinalized {
+            pack_id: String::new(),
+            member_count: 4,
+        }));
+        assert_eq!(line.render(Duration::ZERO), "finalizing");
+    }
+
+    #[test]
+    fn sizes_and_durations_are_humanized() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+        assert_eq!(format_duration(725.0), "12:05");
+        assert_eq!(format_duration(3723.0), "1:02:03");
+    }
+}
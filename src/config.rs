@@ -0,0 +1,464 @@
+//! Layered settings: `~/.config/pack/config.toml`, then environment
+//! variables, then command-line flags.
+//!
+//! Every setting has an environment variable; a value in the environment
+//! wins over the config file, and a flag on the command line wins over both.
+//! Code that used to read one of those variables reads it through
+//! [`env_or_config`] instead, so the file fills in whatever the environment
+//! leaves unset.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::cli::HashAlgorithm;
+use crate::network::push::DATA_FABRIC_BASE_URL_ENV;
+use crate::network::store::PACK_STORE_ENV;
+use crate::seal::hasher::{DEFAULT_HASH_BUFFER_BYTES, HASH_BUFFER_ENV};
+use crate::witness::{witness_ledger_path, WITNESS_CHAIN_ENV, WITNESS_ENRICH_ENV};
+
+/// Path of the config file, overriding the default location.
+pub const CONFIG_ENV: &str = "PACK_CONFIG";
+
+/// Default parent directory for `pack seal` output.
+pub const OUTPUT_ROOT_ENV: &str = "PACK_OUTPUT_ROOT";
+
+/// Default `pack seal --hash-algorithm`.
+pub const HASH_ALGORITHM_ENV: &str = "PACK_HASH_ALGORITHM";
+
+/// Key file for `--sign` when neither `--signing-key` nor `PACK_SIGNING_KEY`
+/// is given.
+pub const SIGNING_KEY_FILE_ENV: &str = "PACK_SIGNING_KEY_FILE";
+
+/// Version tag for `pack config show --json` output.
+pub const CONFIG_REPORT_VERSION: &str = "pack.config.v0";
+
+/// The TOML type a setting takes in the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    String,
+    Bool,
+    Integer,
+}
+
+/// One configurable value: its config file key and environment variable.
+#[derive(Debug, Clone, Copy)]
+pub struct Setting {
+    pub key: &'static str,
+    pub env: &'static str,
+    pub kind: SettingKind,
+}
+
+/// Every setting the config file accepts, in `pack config show` order.
+pub const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "witness",
+        env: "EPISTEMIC_WITNESS",
+        kind: SettingKind::String,
+    },
+    Setting {
+        key: "witness_chain",
+        env: WITNESS_CHAIN_ENV,
+        kind: SettingKind::Bool,
+    },
+    Setting {
+        key: "witness_enrich",
+        env: WITNESS_ENRICH_ENV,
+        kind: SettingKind::Bool,
+    },
+    Setting {
+        key: "output_root",
+        env: OUTPUT_ROOT_ENV,
+        kind: SettingKind::String,
+    },
+    Setting {
+        key: "hash_algorithm",
+        env: HASH_ALGORITHM_ENV,
+        kind: SettingKind::String,
+    },
+    Setting {
+        key: "hash_buffer_bytes",
+        env: HASH_BUFFER_ENV,
+        kind: SettingKind::Integer,
+    },
+    Setting {
+        key: "store",
+        env: PACK_STORE_ENV,
+        kind: SettingKind::String,
+    },
+    Setting {
+        key: "data_fabric_base_url",
+        env: DATA_FABRIC_BASE_URL_ENV,
+        kind: SettingKind::String,
+    },
+    Setting {
+        key: "signing_key_file",
+        env: SIGNING_KEY_FILE_ENV,
+        kind: SettingKind::String,
+    },
+];
+
+/// A parsed config file. Values are kept in their environment-variable form
+/// (`1`/`0` for booleans), so they substitute for the variable directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Where the file was looked for; `None` when no home directory is known.
+    pub path: Option<PathBuf>,
+    /// Whether the file existed.
+    pub found: bool,
+    values: BTreeMap<&'static str, String>,
+}
+
+impl Config {
+    /// The file's value for the setting read from `env`, if it sets one.
+    pub fn get(&self, env: &str) -> Option<&str> {
+        let setting = SETTINGS.iter().find(|setting| setting.env == env)?;
+        self.values.get(setting.key).map(String::as_str)
+    }
+}
+
+/// The config file location: `PACK_CONFIG`, else
+/// `$XDG_CONFIG_HOME/pack/config.toml`, else `~/.config/pack/config.toml`.
+pub fn config_path() -> Option<PathBuf> {
+    config_path_from_env(|key| std::env::var(key).ok())
+}
+
+fn config_path_from_env<F>(get_env: F) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let non_empty = |key| get_env(key).filter(|value| !value.trim().is_empty());
+    if let Some(path) = non_empty(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    if let Some(config_home) = non_empty("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(config_home).join("pack").join("config.toml"));
+    }
+    #[cfg(unix)]
+    let home = non_empty("HOME");
+    #[cfg(windows)]
+    let home = non_empty("USERPROFILE");
+    home.map(|home| {
+        PathBuf::from(home)
+            .join(".config")
+            .join("pack")
+            .join("config.toml")
+    })
+}
+
+/// Read the config file. A missing file is an empty config, unless
+/// `PACK_CONFIG` named it.
+pub fn load_config() -> Result<Config, String> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e)
+            if e.kind() == std::io::ErrorKind::NotFound
+                && std::env::var_os(CONFIG_ENV).is_none() =>
+        {
+            return Ok(Config {
+                path: Some(path),
+                ..Config::default()
+            });
+        }
+        Err(e) => return Err(format!("Cannot read config {}: {e}", path.display())),
+    };
+    let values =
+        parse_config(&content).map_err(|e| format!("Invalid config {}: {e}", path.display()))?;
+    Ok(Config {
+        path: Some(path),
+        found: true,
+        values,
+    })
+}
+
+pub(crate) fn parse_config(content: &str) -> Result<BTreeMap<&'static str, String>, String> {
+    let table: toml::Table = content
+        .parse()
+        .map_err(|e: toml::de::Error| e.message().to_string())?;
+    let mut values = BTreeMap::new();
+    for (key, value) in table {
+        let setting = SETTINGS
+            .iter()
+            .find(|setting| setting.key == key)
+            .ok_or_else(|| format!("unknown setting {key:?}"))?;
+        let value = match (setting.kind, value) {
+            (SettingKind::String, toml::Value::String(value)) => value,
+            (SettingKind::Bool, toml::Value::Boolean(value)) => {
+                if value { "1" } else { "0" }.to_string()
+            }
+            (SettingKind::Integer, toml::Value::Integer(value)) if value > 0 => value.to_string(),
+            (kind, _) => {
+                let expected = match kind {
+                    SettingKind::String => "a string",
+                    SettingKind::Bool => "true or false",
+                    SettingKind::Integer => "a positive integer",
+                };
+                return Err(format!("{key} must be {expected}"));
+            }
+        };
+        if setting.env == HASH_ALGORITHM_ENV && HashAlgorithm::from_str(&value, false).is_err() {
+            return Err(format!(
+                "hash_algorithm must be one of sha256, sha512, blake3, not {value:?}"
+            ));
+        }
+        values.insert(setting.key, value);
+    }
+    Ok(values)
+}
+
+static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+
+/// The config file for this process, read once.
+fn global_config() -> &'static Result<Config, String> {
+    CONFIG.get_or_init(load_config)
+}
+
+/// Read the config file now, so a broken one refuses the command up front
+/// instead of being ignored by [`env_or_config`].
+pub fn check_config() -> Result<(), String> {
+    global_config().as_ref().map(|_| ()).map_err(Clone::clone)
+}
+
+/// The environment variable `key` if it is set and non-empty, else the
+/// config file's value for the setting it belongs to.
+///
+/// Drop-in replacement for `std::env::var(key).ok()`; variables that are not
+/// settings (`HOME`) come from the environment only.
+pub fn env_or_config(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .or_else(|| {
+            global_config()
+                .as_ref()
+                .ok()
+                .and_then(|config| config.get(key))
+                .map(str::to_string)
+        })
+}
+
+/// Default parent directory for seal output when `--output` is not given.
+pub fn output_root() -> Option<PathBuf> {
+    env_or_config(OUTPUT_ROOT_ENV)
+        .filter(|root| !root.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// Default `--hash-algorithm`. An unrecognised `PACK_HASH_ALGORITHM` is
+/// ignored in favour of SHA-256.
+pub fn hash_algorithm() -> HashAlgorithm {
+    env_or_config(HASH_ALGORITHM_ENV)
+        .and_then(|name| HashAlgorithm::from_str(name.trim(), false).ok())
+        .unwrap_or_default()
+}
+
+/// Where an effective setting came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingSource {
+    Default,
+    File,
+    Env,
+}
+
+/// One row of `pack config show`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectiveSetting {
+    pub key: String,
+    pub env: String,
+    pub value: Value,
+    pub source: SettingSource,
+}
+
+/// The merged configuration, as printed by `pack config show --json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigReport {
+    pub version: String,
+    pub file: Option<String>,
+    pub file_found: bool,
+    pub settings: Vec<EffectiveSetting>,
+}
+
+impl ConfigReport {
+    /// Merge `config` with the environment seen through `get_env`.
+    pub fn new<F>(config: &Config, get_env: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let settings = SETTINGS
+            .iter()
+            .map(|setting| {
+                let (raw, source) = match get_env(setting.env).filter(|v| !v.is_empty()) {
+                    Some(value) => (Some(value), SettingSource::Env),
+                    None => match config.get(setting.env) {
+                        Some(value) => (Some(value.to_string()), SettingSource::File),
+                        None => (None, SettingSource::Default),
+                    },
+                };
+                EffectiveSetting {
+                    key: setting.key.to_string(),
+                    env: setting.env.to_string(),
+                    value: match raw {
+                        Some(raw) => typed_value(setting.kind, raw),
+                        None => default_value(setting),
+                    },
+                    source,
+                }
+            })
+            .collect();
+        Self {
+            version: CONFIG_REPORT_VERSION.to_string(),
+            file: config.path.as_ref().map(|path| path.display().to_string()),
+            file_found: config.found,
+            settings,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("config report serialization cannot fail")
+    }
+
+    /// TOML-like `key = value` lines, each commented with its source.
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![match (&self.file, self.file_found) {
+            (Some(file), true) => format!("# config: {file}"),
+            (Some(file), false) => format!("# config: {file} (not found)"),
+            (None, _) => "# config: none (no home directory)".to_string(),
+        }];
+        for setting in &self.settings {
+            let value = match &setting.value {
+                Value::Null => "(unset)".to_string(),
+                value => value.to_string(),
+            };
+            let source = match setting.source {
+                SettingSource::Default => "default".to_string(),
+                SettingSource::File => "file".to_string(),
+                SettingSource::Env => format!("env {}", setting.env),
+            };
+            lines.push(format!("{} = {value}  # {source}", setting.key));
+        }
+        lines.join("\n")
+    }
+}
+
+fn typed_value(kind: SettingKind, raw: String) -> Value {
+    match kind {
+        SettingKind::String => Value::String(raw),
+        SettingKind::Bool => Value::Bool(raw.trim() == "1"),
+        SettingKind::Integer => raw
+            .trim()
+            .parse::<u64>()
+            .map_or(Value::String(raw), Value::from),
+    }
+}
+
+fn default_value(setting: &Setting) -> Value {
+    match setting.key {
+        "witness" => json!(witness_ledger_path().display().to_string()),
+        "witness_chain" | "witness_enrich" => json!(false),
+        "output_root" => json!("pack"),
+        "hash_algorithm" => json!(HashAlgorithm::default().as_str()),
+        "hash_buffer_bytes" => json!(DEFAULT_HASH_BUFFER_BYTES),
+        _ => Value::Null,
+    }
+}
+
+/// Run `pack config show`. Returns (output, exit_code).
+pub fn execute_config_show(json: bool) -> (String, u8) {
+    match global_config() {
+        Ok(config) => {
+            let report = ConfigReport::new(config, |key| std::env::var(key).ok());
+            let output = if json {
+                report.to_json()
+            } else {
+                report.to_human()
+            };
+            (output, 0)
+        }
+        Err(message) => (
+            crate::refusal::RefusalEnvelope::new(
+                crate::refusal::RefusalCode::Io,
+                Some(message.clone()),
+                None,
+            )
+            .to_json(),
+            2,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_wins_over_file_and_file_over_default() {
+        let values = parse_config(
+            "store = \"/srv/packs\"\nwitness_chain = true\nhash_algorithm = \"blake3\"\n",
+        )
+        .unwrap();
+        let config = Config {
+            path: Some(PathBuf::from("config.toml")),
+            found: true,
+            values,
+        };
+        let report = ConfigReport::new(&config, |key| {
+            (key == PACK_STORE_ENV).then(|| "/mnt/other".to_string())
+        });
+        let row = |key: &str| {
+            report
+                .settings
+                .iter()
+                .find(|setting| setting.key == key)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(row("store").value, json!("/mnt/other"));
+        assert_eq!(row("store").source, SettingSource::Env);
+        assert_eq!(row("witness_chain").value, json!(true));
+        assert_eq!(row("witness_chain").source, SettingSource::File);
+        assert_eq!(row("hash_algorithm").value, json!("blake3"));
+        assert_eq!(row("output_root").value, json!("pack"));
+        assert_eq!(row("output_root").source, SettingSource::Default);
+        assert_eq!(config.get(WITNESS_CHAIN_ENV), Some("1"));
+    }
+
+    #[test]
+    fn unknown_keys_and_wrong_types_are_rejected() {
+        assert!(parse_config("stor = \"x\"")
+            .unwrap_err()
+            .contains("unknown setting"));
+        assert!(parse_config("witness_chain = \"yes\"").is_err());
+        assert!(parse_config("hash_buffer_bytes = 0").is_err());
+        assert!(parse_config("hash_algorithm = \"md5\"").is_err());
+        assert!(parse_config("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn config_path_prefers_pack_config_then_xdg() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            config_path_from_env(env(&[(CONFIG_ENV, "/etc/pack.toml"), ("HOME", "/h")])),
+            Some(PathBuf::from("/etc/pack.toml"))
+        );
+        assert_eq!(
+            config_path_from_env(env(&[("XDG_CONFIG_HOME", "/x"), ("HOME", "/h")])),
+            Some(PathBuf::from("/x/pack/config.toml"))
+        );
+    }
+}
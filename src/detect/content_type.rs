@@ -0,0 +1,171 @@
+use std::path::Path;
+
+/// Bytes from the start of a file that content-type detection looks at.
+pub const SNIFF_BYTES: usize = 8 * 1024;
+
+/// Media type for content that is not text.
+pub const BINARY_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Detected media type and, for text, character set of a member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType {
+    /// IANA media type, e.g. `application/json`, `text/csv`, or
+    /// `application/octet-stream` for unrecognised binary content.
+    pub media_type: String,
+    /// `utf-8`, `utf-16le`, or `utf-16be` for text; `None` for binary.
+    pub charset: Option<String>,
+}
+
+impl ContentType {
+    fn new(media_type: &str, charset: Option<&str>) -> Self {
+        Self {
+            media_type: media_type.to_string(),
+            charset: charset.map(str::to_string),
+        }
+    }
+}
+
+/// Binary formats recognised by their leading bytes.
+const MAGIC: [(&[u8], &str); 6] = [
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"PAR1", "application/vnd.apache.parquet"),
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+];
+
+/// Text formats recognised by file extension.
+const TEXT_EXTENSIONS: [(&str, &str); 12] = [
+    ("json", "application/json"),
+    ("jsonl", "application/x-ndjson"),
+    ("ndjson", "application/x-ndjson"),
+    ("csv", "text/csv"),
+    ("tsv", "text/tab-separated-values"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("toml", "application/toml"),
+    ("xml", "application/xml"),
+    ("html", "text/html"),
+    ("md", "text/markdown"),
+    ("txt", "text/plain"),
+];
+
+/// Detect a member's content type from the first [`SNIFF_BYTES`] of its
+/// content (`head`) and its path.
+///
+/// Binary formats are recognised by their leading bytes, and anything else
+/// holding a NUL byte or invalid UTF-8 is `application/octet-stream`. UTF-16
+/// needs a byte order mark. Text is typed by extension, then as JSON when it
+/// opens with `{` or `[`, and is otherwise `text/plain`. A multi-byte
+/// character cut off at the end of `head` does not make text binary.
+pub fn detect_content_type(head: &[u8], path: &str) -> ContentType {
+    let head = &head[..head.len().min(SNIFF_BYTES)];
+    if let Some((_, media_type)) = MAGIC.iter().find(|(magic, _)| head.starts_with(magic)) {
+        return ContentType::new(media_type, None);
+    }
+
+    let (charset, text) = if let Some(rest) = head.strip_prefix(b"\xFF\xFE") {
+        ("utf-16le", decode_utf16(rest, u16::from_le_bytes))
+    } else if let Some(rest) = head.strip_prefix(b"\xFE\xFF") {
+        ("utf-16be", decode_utf16(rest, u16::from_be_bytes))
+    } else {
+        let body = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+        ("utf-8", decode_utf8_prefix(body))
+    };
+    let Some(text) = text.filter(|text| !text.contains('\0')) else {
+        return ContentType::new(BINARY_CONTENT_TYPE, None);
+    };
+
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    let by_extension = TEXT_EXTENSIONS
+        .iter()
+        .find(|(known, _)| extension.as_deref() == Some(known));
+    let media_type = match by_extension {
+        Some((_, media_type)) => media_type,
+        None if text.trim_start().starts_with(['{', '[']) => "application/json",
+        None => "text/plain",
+    };
+    ContentType::new(media_type, Some(charset))
+}
+
+/// UTF-8 text, allowing a character truncated at the end.
+fn decode_utf8_prefix(bytes: &[u8]) -> Option<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        Err(e) if e.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..e.valid_up_to()]).into_owned())
+        }
+        Err(_) => None,
+    }
+}
+
+/// UTF-16 text, allowing a code unit or surrogate pair truncated at the end.
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Option<String> {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    let mut text = String::new();
+    let mut decoded = char::decode_utf16(units.iter().copied()).peekable();
+    while let Some(c) = decoded.next() {
+        match c {
+            Ok(c) => text.push(c),
+            Err(_) if decoded.peek().is_none() => break,
+            Err(_) => return None,
+        }
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(content: &[u8], path: &str) -> (String, Option<String>) {
+        let detected = detect_content_type(content, path);
+        (detected.media_type, detected.charset)
+    }
+
+    fn text(media_type: &str) -> (String, Option<String>) {
+        (media_type.to_string(), Some("utf-8".to_string()))
+    }
+
+    #[test]
+    fn text_is_typed_by_extension_then_content() {
+        assert_eq!(detect(br#"{"a": 1}"#, "a.json"), text("application/json"));
+        assert_eq!(detect(b"  [1, 2]", "export"), text("application/json"));
+        assert_eq!(detect(b"id,amount\n1,2\n", "loans.CSV"), text("text/csv"));
+        assert_eq!(detect(b"plain words", "notes"), text("text/plain"));
+        assert_eq!(detect(b"", "empty.txt"), text("text/plain"));
+        // The euro sign is cut after its first byte.
+        assert_eq!(detect(b"total \xE2", "t.txt"), text("text/plain"));
+
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("a,b".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        assert_eq!(
+            detect(&utf16, "x.csv"),
+            ("text/csv".to_string(), Some("utf-16le".to_string()))
+        );
+    }
+
+    #[test]
+    fn binary_content_is_not_text() {
+        let binary = |media_type: &str| (media_type.to_string(), None);
+        assert_eq!(
+            detect(b"%PDF-1.7\n", "report.json"),
+            binary("application/pdf")
+        );
+        assert_eq!(
+            detect(b"PAR1\x15\x04", "t.parquet"),
+            binary("application/vnd.apache.parquet")
+        );
+        assert_eq!(detect(b"{\"a\":\0}", "a.json"), binary(BINARY_CONTENT_TYPE));
+        assert_eq!(detect(b"\xFF\x00\x10", "blob"), binary(BINARY_CONTENT_TYPE));
+    }
+}
@@ -1,3 +1,26 @@
+use super::rules::DetectionRule;
+
+/// How much a matched detection rule says about the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Typed by filename or path alone; `seal --strict-types` refuses these.
+    Low,
+    /// Typed by line-based YAML key checks.
+    Medium,
+    /// Typed by an exact `version` string, or by no rule matching at all.
+    High,
+}
+
+impl Confidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
 /// Result of member type detection.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MemberTypeResult {
@@ -5,6 +28,31 @@ pub struct MemberTypeResult {
     pub member_type: String,
     /// Parsed artifact version, if available.
     pub artifact_version: Option<String>,
+    /// The rule that decided the type, or `none` for the `other` fallback.
+    /// Rules from a `--detect-rules` file are `detect_rules:<name>`.
+    pub rule: String,
+    pub confidence: Confidence,
+}
+
+impl MemberTypeResult {
+    fn new(
+        member_type: &str,
+        artifact_version: Option<String>,
+        rule: &str,
+        confidence: Confidence,
+    ) -> Self {
+        Self {
+            member_type: member_type.to_string(),
+            artifact_version,
+            rule: rule.to_string(),
+            confidence,
+        }
+    }
+
+    /// The type rests on a weak heuristic that `--strict-types` refuses.
+    pub fn is_ambiguous(&self) -> bool {
+        self.confidence == Confidence::Low
+    }
 }
 
 /// Detect member type and artifact version from file content.
@@ -19,6 +67,35 @@ pub struct MemberTypeResult {
 /// - Registry artifacts (`registry.json`, registry tables) → `registry`
 /// - Everything else → `other`
 pub fn detect_member_type(content: &[u8], path: &str) -> MemberTypeResult {
+    detect_member_type_with_rules(content, path, &[])
+}
+
+/// [`detect_member_type`], trying an organization's `rules` first.
+pub fn detect_member_type_with_rules(
+    content: &[u8],
+    path: &str,
+    rules: &[DetectionRule],
+) -> MemberTypeResult {
+    if !rules.is_empty() {
+        let json = std::str::from_utf8(content)
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(text).ok());
+        for (index, rule) in rules.iter().enumerate() {
+            if rule.matches(json.as_ref(), path) {
+                let name = rule
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("rule {}", index + 1));
+                return MemberTypeResult::new(
+                    &rule.member_type,
+                    rule.version.clone(),
+                    &format!("detect_rules:{name}"),
+                    rule.confidence(),
+                );
+            }
+        }
+    }
+
     // Try JSON detection first.
     if let Ok(text) = std::str::from_utf8(content) {
         if let Some(result) = detect_from_json(text) {
@@ -34,16 +111,10 @@ pub fn detect_member_type(content: &[u8], path: &str) -> MemberTypeResult {
 
     // Registry heuristic by filename.
     if is_registry_path(path) {
-        return MemberTypeResult {
-            member_type: "registry".to_string(),
-            artifact_version: None,
-        };
+        return MemberTypeResult::new("registry", None, "registry_path", Confidence::Low);
     }
 
-    MemberTypeResult {
-        member_type: "other".to_string(),
-        artifact_version: None,
-    }
+    MemberTypeResult::new("other", None, "none", Confidence::High)
 }
 
 /// Attempt to detect type from JSON content by looking for a `version` field.
@@ -51,29 +122,20 @@ fn detect_from_json(text: &str) -> Option<MemberTypeResult> {
     let value: serde_json::Value = serde_json::from_str(text).ok()?;
     let version = value.get("version")?.as_str()?;
 
-    match version {
-        "lock.v0" => Some(MemberTypeResult {
-            member_type: "lockfile".to_string(),
-            artifact_version: Some("lock.v0".to_string()),
-        }),
-        "rvl.v0" | "shape.v0" | "verify.v0" | "compare.v0" => Some(MemberTypeResult {
-            member_type: "report".to_string(),
-            artifact_version: Some(version.to_string()),
-        }),
-        "canon.v0" | "assess.v0" => Some(MemberTypeResult {
-            member_type: "artifact".to_string(),
-            artifact_version: Some(version.to_string()),
-        }),
-        "verify.rules.v0" => Some(MemberTypeResult {
-            member_type: "rules".to_string(),
-            artifact_version: Some("verify.rules.v0".to_string()),
-        }),
-        "pack.v0" => Some(MemberTypeResult {
-            member_type: "pack".to_string(),
-            artifact_version: Some("pack.v0".to_string()),
-        }),
-        _ => None,
-    }
+    let member_type = match version {
+        "lock.v0" => "lockfile",
+        "rvl.v0" | "shape.v0" | "verify.v0" | "compare.v0" => "report",
+        "canon.v0" | "assess.v0" => "artifact",
+        "verify.rules.v0" => "rules",
+        "pack.v0" => "pack",
+        _ => return None,
+    };
+    Some(MemberTypeResult::new(
+        member_type,
+        Some(version.to_string()),
+        "json_version",
+        Confidence::High,
+    ))
 }
 
 /// Attempt to detect YAML profile (schema_version + profile_id).
@@ -89,10 +151,12 @@ fn detect_from_yaml(text: &str) -> Option<MemberTypeResult> {
     });
 
     if has_schema_version && has_profile_id {
-        Some(MemberTypeResult {
-            member_type: "profile".to_string(),
-            artifact_version: None,
-        })
+        Some(MemberTypeResult::new(
+            "profile",
+            None,
+            "yaml_profile_keys",
+            Confidence::Medium,
+        ))
     } else {
         None
     }
@@ -122,10 +186,18 @@ fn detect_fingerprint_yaml(text: &str, path: &str) -> Option<MemberTypeResult> {
             .map(|v| v.trim().trim_matches('"').trim_matches('\'').to_string())
             .filter(|v| !v.is_empty());
 
-        Some(MemberTypeResult {
-            member_type: "fingerprint".to_string(),
+        // The extension alone is a weak signal; the keys back it up.
+        let (rule, confidence) = if by_content {
+            ("fingerprint_keys", Confidence::Medium)
+        } else {
+            ("fingerprint_extension", Confidence::Low)
+        };
+        Some(MemberTypeResult::new(
+            "fingerprint",
             artifact_version,
-        })
+            rule,
+            confidence,
+        ))
     } else {
         None
     }
@@ -285,6 +357,60 @@ mod tests {
         assert_eq!(result.member_type, "other");
     }
 
+    #[test]
+    fn path_and_extension_matches_are_low_confidence() {
+        let registry = detect_member_type(b"[1, 2, 3]", "registry/loans.json");
+        assert_eq!(registry.member_type, "registry");
+        assert_eq!(registry.rule, "registry_path");
+        assert!(registry.is_ambiguous());
+
+        let bare = detect_member_type(b"format: pdf", "cbre.fp.yaml");
+        assert_eq!(bare.rule, "fingerprint_extension");
+        assert!(bare.is_ambiguous());
+
+        let backed = detect_member_type(b"fingerprint_id: x\nassertions: []", "cbre.fp.yaml");
+        assert_eq!(backed.rule, "fingerprint_keys");
+        assert_eq!(backed.confidence, Confidence::Medium);
+
+        let lock = detect_member_type(br#"{"version": "lock.v0"}"#, "registry/nov.lock.json");
+        assert_eq!(lock.rule, "json_version");
+        assert_eq!(lock.confidence, Confidence::High);
+        assert!(!detect_member_type(b"text", "notes.txt").is_ambiguous());
+    }
+
+    #[test]
+    fn custom_rules_are_tried_before_built_in_ones() {
+        let rules = vec![
+            DetectionRule {
+                name: None,
+                member_type: "artifact".to_string(),
+                version: Some("ledger.v1".to_string()),
+                glob: None,
+                json_keys: vec![],
+            },
+            DetectionRule {
+                name: Some("tape".to_string()),
+                member_type: "report".to_string(),
+                version: None,
+                glob: Some("registry/*.json".to_string()),
+                json_keys: vec!["loans".to_string()],
+            },
+        ];
+        let ledger =
+            detect_member_type_with_rules(br#"{"version": "ledger.v1"}"#, "ledger.json", &rules);
+        assert_eq!(ledger.member_type, "artifact");
+        assert_eq!(ledger.artifact_version.as_deref(), Some("ledger.v1"));
+        assert_eq!(ledger.rule, "detect_rules:rule 1");
+
+        let tape = detect_member_type_with_rules(br#"{"loans": []}"#, "registry/tape.json", &rules);
+        assert_eq!(tape.member_type, "report");
+        assert_eq!(tape.rule, "detect_rules:tape");
+        assert_eq!(tape.confidence, Confidence::Medium);
+
+        let untouched = detect_member_type_with_rules(b"[]", "registry/tape.json", &rules);
+        assert_eq!(untouched.rule, "registry_path");
+    }
+
     #[test]
     fn binary_content_falls_to_other() {
         let content = &[0xFF, 0xFE, 0x00, 0x01, 0x02];
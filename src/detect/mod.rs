@@ -1,3 +1,11 @@
+mod content_type;
 mod member_type;
+mod report;
+mod rules;
 
-pub use member_type::{detect_member_type, MemberTypeResult};
+pub use content_type::{detect_content_type, ContentType, BINARY_CONTENT_TYPE, SNIFF_BYTES};
+pub use member_type::{
+    detect_member_type, detect_member_type_with_rules, Confidence, MemberTypeResult,
+};
+pub use report::{execute_detect, DetectReport, DETECT_REPORT_VERSION};
+pub use rules::{load_detection_rules, DetectionRule, MEMBER_TYPES};
@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::member_type::{detect_member_type_with_rules, MemberTypeResult};
+use super::rules::load_detection_rules;
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::finalize::MAX_DETECT_BYTES;
+
+/// Version tag for `pack detect --json` output.
+pub const DETECT_REPORT_VERSION: &str = "pack.detect.v0";
+
+/// How seal would type one file, as printed by `pack detect`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetectReport {
+    pub version: String,
+    /// The member path detection was run against.
+    pub path: String,
+    #[serde(rename = "type")]
+    pub member_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_version: Option<String>,
+    pub rule: String,
+    pub confidence: String,
+    /// `seal --strict-types` would refuse this file.
+    pub ambiguous: bool,
+}
+
+impl DetectReport {
+    pub fn new(path: &str, detected: &MemberTypeResult) -> Self {
+        Self {
+            version: DETECT_REPORT_VERSION.to_string(),
+            path: path.to_string(),
+            member_type: detected.member_type.clone(),
+            artifact_version: detected.artifact_version.clone(),
+            rule: detected.rule.clone(),
+            confidence: detected.confidence.as_str().to_string(),
+            ambiguous: detected.is_ambiguous(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("detect report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![
+            format!("path: {}", self.path),
+            format!("type: {}", self.member_type),
+        ];
+        if let Some(version) = &self.artifact_version {
+            lines.push(format!("artifact_version: {version}"));
+        }
+        lines.push(format!("rule: {}", self.rule));
+        let strict = if self.ambiguous {
+            " (refused by --strict-types)"
+        } else {
+            ""
+        };
+        lines.push(format!("confidence: {}{strict}", self.confidence));
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack detect`: type `file` as seal would, with `member_path`
+/// (default: the file path as given) standing in for its path in the pack and
+/// any `detect_rules` tried first.
+pub fn execute_detect(
+    file: &Path,
+    member_path: Option<&str>,
+    detect_rules: Option<&Path>,
+    json: bool,
+) -> (String, u8) {
+    let refuse = |message: String| {
+        let refusal = RefusalEnvelope::new(RefusalCode::Io, Some(message), None);
+        (refusal.to_json(), 2)
+    };
+    let rules = match detect_rules.map(load_detection_rules).transpose() {
+        Ok(rules) => rules.unwrap_or_default(),
+        Err(message) => return refuse(message),
+    };
+    let content = match fs::metadata(file).and_then(|meta| {
+        if meta.len() > MAX_DETECT_BYTES {
+            Ok(Vec::new())
+        } else {
+            fs::read(file)
+        }
+    }) {
+        Ok(content) => content,
+        Err(e) => return refuse(format!("Cannot read {}: {e}", file.display())),
+    };
+    let path =
+        member_path.map_or_else(|| file.to_string_lossy().replace('\\', "/"), str::to_string);
+    let report = DetectReport::new(
+        &path,
+        &detect_member_type_with_rules(&content, &path, &rules),
+    );
+    let output = if json {
+        report.to_json()
+    } else {
+        report.to_human()
+    };
+    (output, 0)
+}
@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::member_type::Confidence;
+use crate::seal::visibility::path_matches;
+
+/// Member types a detection rule may assign; the manifest schema allows no
+/// others.
+pub const MEMBER_TYPES: [&str; 8] = [
+    "lockfile", "report", "artifact", "rules", "pack", "profile", "registry", "other",
+];
+
+/// An organization's own type detection rule, loaded by `--detect-rules`.
+///
+/// Every predicate that is set must hold; rules are tried in file order, before
+/// the built-in ones, and the first match wins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetectionRule {
+    /// Name reported by `pack detect`; defaults to `rule <N>` (1-based).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Member type to record on a match.
+    #[serde(rename = "type")]
+    pub member_type: String,
+    /// The member is a JSON object with this `version`, which is recorded as
+    /// its `artifact_version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The member path matches this pattern, as for `seal --restrict`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub glob: Option<String>,
+    /// The member is a JSON object with all of these top-level keys.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub json_keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DetectionRulesFile {
+    rules: Vec<DetectionRule>,
+}
+
+impl DetectionRule {
+    /// What a match is worth: a version pins the content, keys describe it,
+    /// and a glob alone only looks at the path.
+    pub fn confidence(&self) -> Confidence {
+        if self.version.is_some() {
+            Confidence::High
+        } else if !self.json_keys.is_empty() {
+            Confidence::Medium
+        } else {
+            Confidence::Low
+        }
+    }
+
+    /// Whether the rule holds for a member. `json` is the member parsed as a
+    /// JSON value, when it is one.
+    pub fn matches(&self, json: Option<&serde_json::Value>, path: &str) -> bool {
+        if let Some(glob) = &self.glob {
+            if !path_matches(glob, path) {
+                return false;
+            }
+        }
+        if self.version.is_none() && self.json_keys.is_empty() {
+            return true;
+        }
+        let Some(object) = json.and_then(|value| value.as_object()) else {
+            return false;
+        };
+        if let Some(version) = &self.version {
+            if object.get("version").and_then(|v| v.as_str()) != Some(version) {
+                return false;
+            }
+        }
+        self.json_keys.iter().all(|key| object.contains_key(key))
+    }
+}
+
+/// Load a detection rules file: `{"rules": [...]}` as JSON, or `[[rules]]`
+/// tables when the file ends in `.toml`.
+pub fn load_detection_rules(path: &Path) -> Result<Vec<DetectionRule>, String> {
+    let invalid =
+        |message: String| format!("Invalid detection rules: {}: {message}", path.display());
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read detection rules: {}: {e}", path.display()))?;
+    let file: DetectionRulesFile = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&content).map_err(|e| invalid(e.message().to_string()))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?
+    };
+    for (index, rule) in file.rules.iter().enumerate() {
+        let label = rule
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("rule {}", index + 1));
+        if !MEMBER_TYPES.contains(&rule.member_type.as_str()) {
+            return Err(invalid(format!(
+                "{label}: type {:?} is not one of {}",
+                rule.member_type,
+                MEMBER_TYPES.join(", ")
+            )));
+        }
+        if rule.version.is_none() && rule.glob.is_none() && rule.json_keys.is_empty() {
+            return Err(invalid(format!(
+                "{label}: needs at least one of version, glob, or json_keys"
+            )));
+        }
+    }
+    Ok(file.rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_and_json_files_load_the_same_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("rules.toml");
+        fs::write(
+            &toml_path,
+            "[[rules]]\ntype = \"artifact\"\nversion = \"ledger.v1\"\n\n\
+             [[rules]]\nname = \"exports\"\ntype = \"registry\"\nglob = \"exports/**/*.csv\"\n",
+        )
+        .unwrap();
+        let json_path = dir.path().join("rules.json");
+        fs::write(
+            &json_path,
+            r#"{"rules": [{"type": "artifact", "version": "ledger.v1"},
+                {"name": "exports", "type": "registry", "glob": "exports/**/*.csv"}]}"#,
+        )
+        .unwrap();
+
+        let rules = load_detection_rules(&toml_path).unwrap();
+        assert_eq!(rules, load_detection_rules(&json_path).unwrap());
+        assert_eq!(rules[0].confidence(), Confidence::High);
+        assert_eq!(rules[1].confidence(), Confidence::Low);
+        assert!(rules[1].matches(None, "exports/2026/01/loans.csv"));
+        assert!(!rules[1].matches(None, "loans.csv"));
+    }
+
+    #[test]
+    fn unknown_types_and_empty_rules_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.json");
+        fs::write(&path, r#"{"rules": [{"type": "ledger", "version": "x"}]}"#).unwrap();
+        assert!(load_detection_rules(&path)
+            .unwrap_err()
+            .contains("type \"ledger\""));
+        fs::write(&path, r#"{"rules": [{"name": "bare", "type": "report"}]}"#).unwrap();
+        assert!(load_detection_rules(&path).unwrap_err().contains("bare:"));
+    }
+}
@@ -3,50 +3,150 @@ use std::path::Path;
 
 use serde_json::json;
 
+use crate::cli::SortOrder;
+use crate::network::manifest::{fetch_remote_manifest, RemotePack};
+use crate::refusal::RefusalEnvelope;
 use crate::seal::manifest::Manifest;
 use crate::verify::VerifyReport;
 
-use super::compare::compare_manifests;
+use super::compare::{compare_manifests, DiffEntry, DiffReport, NestedDiff};
+
+/// Optional diff behaviours beyond the two pack directories and output mode.
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// `--sort`: member ordering for human output.
+    pub sort: SortOrder,
+}
 
 /// Execute `pack diff <A> <B>`.
 ///
 /// Returns (output_string, exit_code).
 pub fn execute_diff(a_dir: &Path, b_dir: &Path, json_output: bool) -> (String, u8) {
-    let a_manifest = match read_manifest(a_dir, "A") {
-        Ok(m) => m,
+    execute_diff_with_options(a_dir, b_dir, json_output, &DiffOptions::default())
+}
+
+/// Execute `pack diff` with explicit [`DiffOptions`].
+pub fn execute_diff_with_options(
+    a_dir: &Path,
+    b_dir: &Path,
+    json_output: bool,
+    options: &DiffOptions,
+) -> (String, u8) {
+    match diff_packs(a_dir, b_dir) {
+        Ok(diff) => {
+            let exit_code = if diff.has_changes() { 1 } else { 0 };
+            let output = if json_output {
+                diff.to_json()
+            } else {
+                diff.to_human(options.sort)
+            };
+            (output, exit_code)
+        }
         Err(report) => {
             let output = if json_output {
                 report.to_json()
             } else {
                 report.to_human()
             };
-            return (output, 2);
+            (output, 2)
         }
-    };
+    }
+}
 
-    let b_manifest = match read_manifest(b_dir, "B") {
-        Ok(m) => m,
-        Err(report) => {
+/// Compare the manifests of packs A and B, expanding changed nested packs.
+///
+/// A pack whose manifest cannot be read refuses with a verify-shaped report,
+/// as `pack diff --json` prints it.
+pub fn diff_packs(a_dir: &Path, b_dir: &Path) -> Result<DiffReport, Box<VerifyReport>> {
+    let a_manifest = read_manifest(a_dir, "A")?;
+    let b_manifest = read_manifest(b_dir, "B")?;
+
+    let mut diff = compare_manifests(&a_manifest, &b_manifest);
+    diff.nested = nested_diffs(&a_manifest, &b_manifest, a_dir, b_dir, &diff.changed);
+    Ok(diff)
+}
+
+/// Execute `pack diff --remote A --remote B`: compare two stored packs from
+/// their manifests alone.
+pub fn execute_remote_diff(
+    a: &RemotePack,
+    b: &RemotePack,
+    json_output: bool,
+    options: &DiffOptions,
+) -> (String, u8) {
+    match diff_remote_packs(a, b) {
+        Ok(diff) => {
+            let exit_code = if diff.has_changes() { 1 } else { 0 };
+            let output = if json_output {
+                diff.to_json()
+            } else {
+                diff.to_human(options.sort)
+            };
+            (output, exit_code)
+        }
+        Err(envelope) => {
+            let report = VerifyReport::refusal(json!({
+                "code": envelope.refusal.code,
+                "message": envelope.refusal.message,
+            }));
             let output = if json_output {
                 report.to_json()
             } else {
                 report.to_human()
             };
-            return (output, 2);
+            (output, 2)
         }
-    };
-
-    let diff = compare_manifests(&a_manifest, &b_manifest);
+    }
+}
 
-    let exit_code = if diff.has_changes() { 1 } else { 0 };
+/// Compare two stored packs by fetching only their manifests.
+///
+/// Nested packs are not expanded, since that would need member bytes.
+pub fn diff_remote_packs(
+    a: &RemotePack,
+    b: &RemotePack,
+) -> Result<DiffReport, Box<RefusalEnvelope>> {
+    let a_manifest = fetch_remote_manifest(a)?;
+    let b_manifest = fetch_remote_manifest(b)?;
+    Ok(compare_manifests(&a_manifest, &b_manifest))
+}
 
-    let output = if json_output {
-        diff.to_json()
-    } else {
-        diff.to_human()
+/// Diff the embedded manifests of changed members typed `pack` on both sides.
+///
+/// Only one level is expanded; nested members that cannot be read or parsed as
+/// `pack.v0` are left as plain `changed` entries.
+fn nested_diffs(
+    a_manifest: &Manifest,
+    b_manifest: &Manifest,
+    a_dir: &Path,
+    b_dir: &Path,
+    changed: &[DiffEntry],
+) -> Vec<NestedDiff> {
+    let is_pack = |manifest: &Manifest, path: &str| {
+        manifest
+            .members
+            .iter()
+            .any(|m| m.path == path && m.member_type == "pack")
+    };
+    let load = |dir: &Path, path: &str| -> Option<Manifest> {
+        let content = fs::read_to_string(dir.join(path)).ok()?;
+        serde_json::from_str::<Manifest>(&content)
+            .ok()
+            .filter(|m| m.version == "pack.v0")
     };
 
-    (output, exit_code)
+    changed
+        .iter()
+        .filter(|entry| is_pack(a_manifest, &entry.path) && is_pack(b_manifest, &entry.path))
+        .filter_map(|entry| {
+            let a = load(a_dir, &entry.path)?;
+            let b = load(b_dir, &entry.path)?;
+            Some(NestedDiff {
+                path: entry.path.clone(),
+                diff: compare_manifests(&a, &b),
+            })
+        })
+        .collect()
 }
 
 fn read_manifest(pack_dir: &Path, label: &str) -> Result<Manifest, Box<VerifyReport>> {
@@ -106,8 +206,15 @@ mod tests {
                 Member {
                     path: path.to_string(),
                     bytes_hash: format!("sha256:{}", hex::encode(hasher.finalize())),
+                    size_bytes: None,
                     member_type: "other".to_string(),
                     artifact_version: None,
+                    visibility: None,
+                    origin: None,
+                    executable: None,
+                    ciphertext_hash: None,
+                    content_type: None,
+                    charset: None,
                 }
             })
             .collect();
@@ -169,6 +276,44 @@ mod tests {
         assert_eq!(report["added"].as_array().unwrap().len(), 1);
     }
 
+    #[test]
+    fn nested_pack_manifests_are_diffed() {
+        use crate::seal::command::execute_seal;
+
+        let seal_outer = |inner_files: &[(&str, &str)]| {
+            let src = TempDir::new().unwrap();
+            let files: Vec<_> = inner_files
+                .iter()
+                .map(|(name, content)| {
+                    let path = src.path().join(name);
+                    std::fs::write(&path, content).unwrap();
+                    path
+                })
+                .collect();
+            let out = TempDir::new().unwrap();
+            let inner = out.path().join("inner");
+            execute_seal(&files, Some(&inner), None).unwrap();
+            let outer = out.path().join("outer");
+            execute_seal(&[inner], Some(&outer), None).unwrap();
+            (out, outer)
+        };
+        let (_a, a) = seal_outer(&[("x.json", "1")]);
+        let (_b, b) = seal_outer(&[("x.json", "2"), ("y.json", "3")]);
+
+        let (output, code) = execute_diff(&a, &b, true);
+        assert_eq!(code, 1);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let nested = report["nested"].as_array().unwrap();
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0]["path"], "inner/manifest.json");
+        assert_eq!(nested[0]["diff"]["changed"][0]["path"], "x.json");
+        assert_eq!(nested[0]["diff"]["added"][0]["path"], "y.json");
+
+        let (human, _) = execute_diff(&a, &b, false);
+        assert!(human.contains("  nested: inner/manifest.json"));
+        assert!(human.contains("      + y.json"));
+    }
+
     #[test]
     fn added_and_removed_detected() {
         let a = create_pack(&[("old.json", "data")], None);
@@ -2,7 +2,11 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::seal::manifest::{Manifest, Member};
+use crate::cli::SortOrder;
+use crate::seal::manifest::{Manifest, Member, MemberOrigin};
+
+/// Diff report schema version.
+pub const DIFF_REPORT_VERSION: &str = "pack.diff.v0";
 
 /// A single difference between two packs.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -13,6 +17,28 @@ pub struct DiffEntry {
     pub a_hash: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub b_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldChange>,
+    /// The member's `seal --annotate` label, from B, or from A when the
+    /// member was removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+}
+
+/// A metadata field whose value differs between two members with identical bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+/// Member-level diff of a changed member that is itself a pack manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NestedDiff {
+    /// Path of the embedded `pack.v0` manifest member.
+    pub path: String,
+    pub diff: DiffReport,
 }
 
 /// Result of comparing two pack manifests.
@@ -25,19 +51,28 @@ pub struct DiffReport {
     pub added: Vec<DiffEntry>,
     pub removed: Vec<DiffEntry>,
     pub changed: Vec<DiffEntry>,
+    #[serde(default)]
+    pub metadata_changed: Vec<DiffEntry>,
     pub unchanged: usize,
+    /// Embedded pack manifests that changed, compared member by member.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nested: Vec<NestedDiff>,
 }
 
 impl DiffReport {
     pub fn has_changes(&self) -> bool {
-        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || !self.metadata_changed.is_empty()
     }
 
     pub fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).expect("diff report serialization cannot fail")
     }
 
-    pub fn to_human(&self) -> String {
+    /// Human report with each section listed in the requested member order.
+    pub fn to_human(&self, sort: SortOrder) -> String {
         let mut lines = Vec::new();
         if self.has_changes() {
             lines.push("pack diff: CHANGES".to_string());
@@ -49,30 +84,73 @@ impl DiffReport {
 
         if !self.added.is_empty() {
             lines.push(format!("  added: {}", self.added.len()));
-            for e in &self.added {
-                lines.push(format!("    + {}", e.path));
+            for e in sorted_entries(&self.added, sort) {
+                lines.push(entry_line('+', e));
             }
         }
         if !self.removed.is_empty() {
             lines.push(format!("  removed: {}", self.removed.len()));
-            for e in &self.removed {
-                lines.push(format!("    - {}", e.path));
+            for e in sorted_entries(&self.removed, sort) {
+                lines.push(entry_line('-', e));
             }
         }
         if !self.changed.is_empty() {
             lines.push(format!("  changed: {}", self.changed.len()));
-            for e in &self.changed {
-                lines.push(format!("    ~ {}", e.path));
+            for e in sorted_entries(&self.changed, sort) {
+                lines.push(entry_line('~', e));
+            }
+        }
+        if !self.metadata_changed.is_empty() {
+            lines.push(format!(
+                "  metadata_changed: {}",
+                self.metadata_changed.len()
+            ));
+            for e in sorted_entries(&self.metadata_changed, sort) {
+                let fields: Vec<String> = e
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "{}: {} -> {}",
+                            f.field,
+                            f.a.as_deref().unwrap_or("-"),
+                            f.b.as_deref().unwrap_or("-")
+                        )
+                    })
+                    .collect();
+                lines.push(format!("    ! {} ({})", e.path, fields.join(", ")));
             }
         }
         if self.unchanged > 0 {
             lines.push(format!("  unchanged: {}", self.unchanged));
         }
+        let mut nested: Vec<&NestedDiff> = self.nested.iter().collect();
+        nested.sort_by(|a, b| sort.compare(&a.path, &b.path));
+        for n in nested {
+            lines.push(format!("  nested: {}", n.path));
+            for line in n.diff.to_human(sort).lines().skip(1) {
+                lines.push(format!("  {line}"));
+            }
+        }
 
         lines.join("\n")
     }
 }
 
+/// `    ~ path`, followed by the member's annotation when it has one.
+fn entry_line(symbol: char, entry: &DiffEntry) -> String {
+    match &entry.annotation {
+        Some(annotation) => format!("    {symbol} {}: {annotation}", entry.path),
+        None => format!("    {symbol} {}", entry.path),
+    }
+}
+
+fn sorted_entries(entries: &[DiffEntry], sort: SortOrder) -> Vec<&DiffEntry> {
+    let mut entries: Vec<&DiffEntry> = entries.iter().collect();
+    entries.sort_by(|a, b| sort.compare(&a.path, &b.path));
+    entries
+}
+
 /// Compare two manifests and produce a deterministic diff report.
 pub fn compare_manifests(a: &Manifest, b: &Manifest) -> DiffReport {
     let a_members: BTreeMap<&str, &Member> =
@@ -83,7 +161,14 @@ pub fn compare_manifests(a: &Manifest, b: &Manifest) -> DiffReport {
     let mut added = Vec::new();
     let mut removed = Vec::new();
     let mut changed = Vec::new();
+    let mut metadata_changed = Vec::new();
     let mut unchanged = 0usize;
+    let annotation = |path: &str| {
+        b.annotations
+            .get(path)
+            .or_else(|| a.annotations.get(path))
+            .cloned()
+    };
 
     // Find removed and changed (in A but not in B, or different hash)
     for (path, a_member) in &a_members {
@@ -94,6 +179,8 @@ pub fn compare_manifests(a: &Manifest, b: &Manifest) -> DiffReport {
                     path: path.to_string(),
                     a_hash: Some(a_member.bytes_hash.clone()),
                     b_hash: None,
+                    fields: Vec::new(),
+                    annotation: annotation(path),
                 });
             }
             Some(b_member) => {
@@ -103,9 +190,31 @@ pub fn compare_manifests(a: &Manifest, b: &Manifest) -> DiffReport {
                         path: path.to_string(),
                         a_hash: Some(a_member.bytes_hash.clone()),
                         b_hash: Some(b_member.bytes_hash.clone()),
+                        fields: Vec::new(),
+                        annotation: annotation(path),
                     });
                 } else {
-                    unchanged += 1;
+                    let mut fields = metadata_changes(a_member, b_member);
+                    let (a_note, b_note) = (a.annotations.get(*path), b.annotations.get(*path));
+                    if a_note != b_note {
+                        fields.push(FieldChange {
+                            field: "annotation".to_string(),
+                            a: a_note.cloned(),
+                            b: b_note.cloned(),
+                        });
+                    }
+                    if fields.is_empty() {
+                        unchanged += 1;
+                    } else {
+                        metadata_changed.push(DiffEntry {
+                            kind: "metadata_changed".to_string(),
+                            path: path.to_string(),
+                            a_hash: Some(a_member.bytes_hash.clone()),
+                            b_hash: Some(b_member.bytes_hash.clone()),
+                            fields,
+                            annotation: annotation(path),
+                        });
+                    }
                 }
             }
         }
@@ -119,28 +228,86 @@ pub fn compare_manifests(a: &Manifest, b: &Manifest) -> DiffReport {
                 path: path.to_string(),
                 a_hash: None,
                 b_hash: Some(b_member.bytes_hash.clone()),
+                fields: Vec::new(),
+                annotation: annotation(path),
             });
         }
     }
 
-    let outcome = if added.is_empty() && removed.is_empty() && changed.is_empty() {
+    let outcome = if added.is_empty()
+        && removed.is_empty()
+        && changed.is_empty()
+        && metadata_changed.is_empty()
+    {
         "NO_CHANGES"
     } else {
         "CHANGES"
     };
 
     DiffReport {
-        version: "pack.diff.v0".to_string(),
+        version: DIFF_REPORT_VERSION.to_string(),
         outcome: outcome.to_string(),
         a_pack_id: a.pack_id.clone(),
         b_pack_id: b.pack_id.clone(),
         added,
         removed,
         changed,
+        metadata_changed,
         unchanged,
+        nested: Vec::new(),
     }
 }
 
+/// Metadata fields that differ between two members whose bytes are identical.
+///
+/// These are governance changes (reclassification, relabeling) rather than
+/// content changes, so they are reported separately from `changed`.
+fn metadata_changes(a: &Member, b: &Member) -> Vec<FieldChange> {
+    let mut fields = Vec::new();
+    if a.member_type != b.member_type {
+        fields.push(FieldChange {
+            field: "type".to_string(),
+            a: Some(a.member_type.clone()),
+            b: Some(b.member_type.clone()),
+        });
+    }
+    if a.artifact_version != b.artifact_version {
+        fields.push(FieldChange {
+            field: "artifact_version".to_string(),
+            a: a.artifact_version.clone(),
+            b: b.artifact_version.clone(),
+        });
+    }
+    if a.visibility != b.visibility {
+        fields.push(FieldChange {
+            field: "visibility".to_string(),
+            a: a.visibility.clone(),
+            b: b.visibility.clone(),
+        });
+    }
+    if a.executable != b.executable {
+        let describe = |executable: Option<bool>| executable.map(|flag| flag.to_string());
+        fields.push(FieldChange {
+            field: "executable".to_string(),
+            a: describe(a.executable),
+            b: describe(b.executable),
+        });
+    }
+    if a.origin != b.origin {
+        let describe = |origin: &Option<MemberOrigin>| {
+            origin
+                .as_ref()
+                .map(|o| serde_json::to_string(o).expect("origin serialization cannot fail"))
+        };
+        fields.push(FieldChange {
+            field: "origin".to_string(),
+            a: describe(&a.origin),
+            b: describe(&b.origin),
+        });
+    }
+    fields
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,8 +317,15 @@ mod tests {
         Member {
             path: path.to_string(),
             bytes_hash: format!("sha256:{hash}"),
+            size_bytes: None,
             member_type: "other".to_string(),
             artifact_version: None,
+            visibility: None,
+            origin: None,
+            executable: None,
+            ciphertext_hash: None,
+            content_type: None,
+            charset: None,
         }
     }
 
@@ -165,6 +339,10 @@ mod tests {
             tool_version: "0.1.0".to_string(),
             members,
             member_count,
+            annotations: BTreeMap::new(),
+            provenance: None,
+            encryption: None,
+            lineage: None,
         }
     }
 
@@ -223,6 +401,34 @@ mod tests {
         assert_eq!(report.changed[0].b_hash.as_deref(), Some("sha256:999"));
     }
 
+    #[test]
+    fn annotations_are_carried_and_relabeling_is_metadata() {
+        let mut a = manifest(
+            "sha256:aaa",
+            vec![member("tape.csv", "111"), member("old.csv", "222")],
+        );
+        a.annotations
+            .insert("tape.csv".to_string(), "draft tape".to_string());
+        a.annotations
+            .insert("old.csv".to_string(), "superseded".to_string());
+        let mut b = manifest("sha256:bbb", vec![member("tape.csv", "111")]);
+        b.annotations
+            .insert("tape.csv".to_string(), "the disputed loan tape".to_string());
+
+        let report = compare_manifests(&a, &b);
+        assert_eq!(report.removed[0].annotation.as_deref(), Some("superseded"));
+        let relabeled = &report.metadata_changed[0];
+        assert_eq!(
+            relabeled.annotation.as_deref(),
+            Some("the disputed loan tape")
+        );
+        assert_eq!(relabeled.fields[0].field, "annotation");
+        assert_eq!(relabeled.fields[0].a.as_deref(), Some("draft tape"));
+        assert!(report
+            .to_human(SortOrder::Bytewise)
+            .contains("    - old.csv: superseded"));
+    }
+
     #[test]
     fn mixed_changes() {
         let a = manifest(
@@ -262,6 +468,77 @@ mod tests {
         assert_eq!(report.removed[1].path, "z.json");
     }
 
+    #[test]
+    fn type_change_with_same_bytes_is_metadata_changed() {
+        let a = manifest("sha256:aaa", vec![member("x.json", "111")]);
+        let mut relabeled = member("x.json", "111");
+        relabeled.member_type = "report".to_string();
+        relabeled.artifact_version = Some("rvl.v0".to_string());
+        let b = manifest("sha256:bbb", vec![relabeled]);
+
+        let report = compare_manifests(&a, &b);
+        assert_eq!(report.outcome, "CHANGES");
+        assert!(report.changed.is_empty());
+        assert_eq!(report.unchanged, 0);
+        assert_eq!(report.metadata_changed.len(), 1);
+        let entry = &report.metadata_changed[0];
+        assert_eq!(entry.kind, "metadata_changed");
+        assert_eq!(entry.fields.len(), 2);
+        assert_eq!(entry.fields[0].field, "type");
+        assert_eq!(entry.fields[0].a.as_deref(), Some("other"));
+        assert_eq!(entry.fields[0].b.as_deref(), Some("report"));
+        assert_eq!(entry.fields[1].field, "artifact_version");
+        assert_eq!(entry.fields[1].a, None);
+
+        let human = report.to_human(SortOrder::Bytewise);
+        assert!(human.contains("metadata_changed: 1"));
+        assert!(human.contains("! x.json (type: other -> report, artifact_version: - -> rvl.v0)"));
+    }
+
+    #[test]
+    fn visibility_change_is_metadata_changed() {
+        let a = manifest("sha256:aaa", vec![member("x.json", "111")]);
+        let mut restricted = member("x.json", "111");
+        restricted.visibility = Some("auditors-only".to_string());
+        let b = manifest("sha256:bbb", vec![restricted]);
+
+        let report = compare_manifests(&a, &b);
+        let entry = &report.metadata_changed[0];
+        assert_eq!(entry.fields.len(), 1);
+        assert_eq!(entry.fields[0].field, "visibility");
+        assert_eq!(entry.fields[0].b.as_deref(), Some("auditors-only"));
+    }
+
+    #[test]
+    fn origin_change_is_metadata_changed() {
+        let a = manifest("sha256:aaa", vec![member("x.json", "111")]);
+        let mut sourced = member("x.json", "111");
+        sourced.origin = Some(MemberOrigin {
+            registry: "ghcr.io/acme/models".to_string(),
+            digest: "sha256:abc".to_string(),
+            media_type: None,
+        });
+        let b = manifest("sha256:bbb", vec![sourced]);
+
+        let report = compare_manifests(&a, &b);
+        let field = &report.metadata_changed[0].fields[0];
+        assert_eq!(field.field, "origin");
+        assert_eq!(field.a, None);
+        assert!(field.b.as_deref().unwrap().contains("ghcr.io/acme/models"));
+    }
+
+    #[test]
+    fn content_change_takes_precedence_over_metadata() {
+        let a = manifest("sha256:aaa", vec![member("x.json", "111")]);
+        let mut b_member = member("x.json", "999");
+        b_member.member_type = "report".to_string();
+        let b = manifest("sha256:bbb", vec![b_member]);
+
+        let report = compare_manifests(&a, &b);
+        assert_eq!(report.changed.len(), 1);
+        assert!(report.metadata_changed.is_empty());
+    }
+
     #[test]
     fn human_output_format() {
         let a = manifest("sha256:aaa", vec![member("x.json", "111")]);
@@ -270,11 +547,35 @@ mod tests {
             vec![member("x.json", "111"), member("y.json", "222")],
         );
         let report = compare_manifests(&a, &b);
-        let human = report.to_human();
+        let human = report.to_human(SortOrder::Bytewise);
         assert!(human.contains("CHANGES"));
         assert!(human.contains("+ y.json"));
     }
 
+    #[test]
+    fn display_sort_is_case_insensitive_in_human_output_only() {
+        let a = manifest("sha256:aaa", vec![]);
+        let b = manifest(
+            "sha256:bbb",
+            vec![
+                member("Z.json", "1"),
+                member("a.json", "2"),
+                member("B.json", "3"),
+            ],
+        );
+        let report = compare_manifests(&a, &b);
+
+        let bytewise: Vec<&str> = report.added.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(bytewise, vec!["B.json", "Z.json", "a.json"]);
+
+        let human = report.to_human(SortOrder::Display);
+        let listed: Vec<&str> = human
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("+ "))
+            .collect();
+        assert_eq!(listed, vec!["a.json", "B.json", "Z.json"]);
+    }
+
     #[test]
     fn json_output_roundtrips() {
         let a = manifest("sha256:aaa", vec![member("x.json", "111")]);
@@ -1,4 +1,9 @@
 mod command;
 mod compare;
 
-pub use command::execute_diff;
+pub use command::{
+    diff_packs, diff_remote_packs, execute_diff, execute_diff_with_options, execute_remote_diff,
+    DiffOptions,
+};
+pub(crate) use compare::compare_manifests;
+pub use compare::{DiffReport, NestedDiff, DIFF_REPORT_VERSION};
@@ -0,0 +1,189 @@
+//! Member encryption in the age v1 file format
+//! (<https://age-encryption.org/v1>) with X25519 recipients, through the
+//! `age` crate, so `age -d` can open a sealed member and `pack` can open a
+//! file from `age -r`.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use age::{DecryptError, Decryptor, Encryptor};
+
+use super::key::{Identity, Recipient};
+
+/// `encryption.scheme` recorded in the manifest of an encrypted pack.
+pub const ENCRYPTION_SCHEME: &str = "age-x25519";
+
+/// Encrypt `reader` to every recipient, writing the age file to `writer`.
+pub fn encrypt<R: Read, W: Write>(
+    mut reader: R,
+    writer: W,
+    recipients: &[Recipient],
+) -> io::Result<()> {
+    let encryptor = Encryptor::with_recipients(
+        recipients
+            .iter()
+            .map(|recipient| recipient.as_age() as &dyn age::Recipient),
+    )
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    let mut stream = encryptor.wrap_output(writer)?;
+    io::copy(&mut reader, &mut stream)?;
+    stream.finish()?.flush()
+}
+
+/// Replace the file at `path` with its encryption to `recipients`, keeping
+/// its permissions.
+pub fn encrypt_file(path: &Path, recipients: &[Recipient]) -> io::Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let mut encrypted = tempfile::NamedTempFile::new_in(parent)?;
+    encrypt(
+        io::BufReader::new(fs::File::open(path)?),
+        io::BufWriter::new(encrypted.as_file_mut()),
+        recipients,
+    )?;
+    fs::set_permissions(encrypted.path(), fs::metadata(path)?.permissions())?;
+    encrypted.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Decrypt the age file in `reader` with the first identity that matches one
+/// of its recipients, writing the plaintext to `writer`.
+///
+/// Returns the number of plaintext bytes written. Errors name the first
+/// problem: a malformed header, no matching identity, a bad header MAC, or a
+/// payload chunk that fails authentication (truncated or altered).
+pub fn decrypt<R: Read, W: Write>(
+    reader: R,
+    mut writer: W,
+    identities: &[Identity],
+) -> Result<u64, String> {
+    let decryptor = Decryptor::new(reader).map_err(|e| decrypt_error(&e))?;
+    let mut plaintext = decryptor
+        .decrypt(
+            identities
+                .iter()
+                .map(|identity| identity.as_age() as &dyn age::Identity),
+        )
+        .map_err(|e| decrypt_error(&e))?;
+    // Read and write separately so a payload that fails authentication is
+    // told apart from a failing writer.
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut written = 0u64;
+    loop {
+        let n = match plaintext.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("age payload fails authentication: {e}")),
+        };
+        writer.write_all(&buf[..n]).map_err(|e| e.to_string())?;
+        written += n as u64;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(written)
+}
+
+fn decrypt_error(error: &DecryptError) -> String {
+    match error {
+        DecryptError::NoMatchingKeys => {
+            "no identity in the key file is a recipient of this file".to_string()
+        }
+        DecryptError::InvalidMac => "age header MAC does not match".to_string(),
+        other => format!("not a readable age file: {other}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTRO: &[u8] = b"age-encryption.org/v1\n";
+    /// Plaintext bytes per age STREAM chunk, and the tag each chunk carries.
+    const CHUNK: usize = 64 * 1024;
+    const TAG: usize = 16;
+
+    fn round_trip(plaintext: &[u8], identity: &Identity) -> Vec<u8> {
+        let mut sealed = Vec::new();
+        encrypt(plaintext, &mut sealed, &[identity.recipient()]).unwrap();
+        let mut opened = Vec::new();
+        let written = decrypt(
+            sealed.as_slice(),
+            &mut opened,
+            std::slice::from_ref(identity),
+        )
+        .unwrap();
+        assert_eq!(written, plaintext.len() as u64);
+        opened
+    }
+
+    #[test]
+    fn round_trips_across_chunk_boundaries() {
+        let identity = Identity::generate();
+        for len in [0, 1, CHUNK - 1, CHUNK, CHUNK + 1, 2 * CHUNK, 2 * CHUNK + 7] {
+            let plaintext: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            assert_eq!(round_trip(&plaintext, &identity), plaintext, "len {len}");
+        }
+    }
+
+    #[test]
+    fn any_recipient_decrypts_and_others_cannot() {
+        let alice = Identity::generate();
+        let bob = Identity::generate();
+        let mallory = Identity::generate();
+        let mut sealed = Vec::new();
+        encrypt(
+            b"loan,balance".as_slice(),
+            &mut sealed,
+            &[alice.recipient(), bob.recipient()],
+        )
+        .unwrap();
+        assert!(sealed.starts_with(INTRO));
+
+        for identity in [&alice, &bob] {
+            let mut opened = Vec::new();
+            decrypt(
+                sealed.as_slice(),
+                &mut opened,
+                std::slice::from_ref(identity),
+            )
+            .unwrap();
+            assert_eq!(opened, b"loan,balance");
+        }
+        let error = decrypt(sealed.as_slice(), io::sink(), &[mallory]).unwrap_err();
+        assert!(error.contains("no identity"), "{error}");
+    }
+
+    #[test]
+    fn tampering_and_truncation_are_detected() {
+        let identity = Identity::generate();
+        let plaintext = vec![9u8; CHUNK + 10];
+        let mut sealed = Vec::new();
+        encrypt(plaintext.as_slice(), &mut sealed, &[identity.recipient()]).unwrap();
+
+        let mut flipped = sealed.clone();
+        let last = flipped.len() - 1;
+        flipped[last] ^= 1;
+        assert!(decrypt(
+            flipped.as_slice(),
+            io::sink(),
+            std::slice::from_ref(&identity)
+        )
+        .is_err());
+
+        // Dropping the final chunk leaves a full chunk not flagged as last.
+        let truncated = &sealed[..sealed.len() - (10 + TAG)];
+        let error = decrypt(truncated, io::sink(), std::slice::from_ref(&identity)).unwrap_err();
+        assert!(error.contains("fails authentication"), "{error}");
+
+        // Changing a header byte breaks the MAC.
+        let mut header = sealed.clone();
+        let at = header.windows(4).position(|w| w == b"--- ").unwrap() - 2;
+        header[at] = if header[at] == b'A' { b'B' } else { b'A' };
+        assert!(decrypt(
+            header.as_slice(),
+            io::sink(),
+            std::slice::from_ref(&identity)
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use age::secrecy::ExposeSecret;
+use age::x25519;
+
+/// A public key members are encrypted to, written as `age1...`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Recipient(x25519::Recipient);
+
+impl Recipient {
+    /// Parse an `age1...` recipient as printed by `age-keygen`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        text.parse()
+            .map(Self)
+            .map_err(|e| format!("Invalid age recipient {text}: {e}"))
+    }
+
+    pub(super) fn as_age(&self) -> &x25519::Recipient {
+        &self.0
+    }
+}
+
+impl fmt::Display for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Recipient({self})")
+    }
+}
+
+/// A secret key that decrypts members sealed to its [`Recipient`], written as
+/// `AGE-SECRET-KEY-1...`.
+pub struct Identity(x25519::Identity);
+
+impl Identity {
+    /// A fresh identity from the operating system's random source.
+    pub fn generate() -> Self {
+        Self(x25519::Identity::generate())
+    }
+
+    /// Parse an `AGE-SECRET-KEY-1...` identity as written by `age-keygen`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        text.trim().parse().map(Self).map_err(str::to_string)
+    }
+
+    /// The recipient whose ciphertext this identity decrypts.
+    pub fn recipient(&self) -> Recipient {
+        Recipient(self.0.to_public())
+    }
+
+    /// The identity in the upper-case form `age-keygen` writes.
+    pub fn to_secret_string(&self) -> String {
+        self.0.to_string().expose_secret().to_string()
+    }
+
+    pub(super) fn as_age(&self) -> &x25519::Identity {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Identity({})", self.recipient())
+    }
+}
+
+/// Load the identities in an age identity file: one `AGE-SECRET-KEY-1...` per
+/// line, with blank lines and `#` comments ignored.
+pub fn load_identities(path: &Path) -> Result<Vec<Identity>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read decryption key {}: {e}", path.display()))?;
+    let identities = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(Identity::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid age identity in {}: {e}", path.display()))?;
+    if identities.is_empty() {
+        return Err(format!(
+            "No AGE-SECRET-KEY-1 identity in {}",
+            path.display()
+        ));
+    }
+    Ok(identities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_round_trip_and_match_age_keygen_format() {
+        let identity = Identity::generate();
+        let secret = identity.to_secret_string();
+        assert!(secret.starts_with("AGE-SECRET-KEY-1"));
+        let recipient = identity.recipient().to_string();
+        assert!(recipient.starts_with("age1"));
+        assert_eq!(recipient.len(), 62);
+
+        let parsed = Identity::parse(&secret).unwrap();
+        assert_eq!(parsed.recipient(), identity.recipient());
+        assert_eq!(Recipient::parse(&recipient).unwrap(), identity.recipient());
+
+        assert!(Recipient::parse(&secret).is_err());
+        assert!(Identity::parse(&recipient).is_err());
+        let mut corrupted = recipient.clone();
+        corrupted.replace_range(10..11, if &recipient[10..11] == "q" { "p" } else { "q" });
+        assert!(Recipient::parse(&corrupted).is_err());
+    }
+
+    #[test]
+    fn identity_file_skips_comments() {
+        let identity = Identity::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.txt");
+        fs::write(
+            &path,
+            format!(
+                "# created: 2026-10-17\n# public key: {}\n{}\n",
+                identity.recipient(),
+                identity.to_secret_string()
+            ),
+        )
+        .unwrap();
+        let loaded = load_identities(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].recipient(), identity.recipient());
+
+        fs::write(&path, "# nothing here\n").unwrap();
+        assert!(load_identities(&path)
+            .unwrap_err()
+            .contains("No AGE-SECRET-KEY-1"));
+    }
+}
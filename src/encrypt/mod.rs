@@ -0,0 +1,5 @@
+mod age;
+mod key;
+
+pub use age::{decrypt, encrypt, encrypt_file, ENCRYPTION_SCHEME};
+pub use key::{load_identities, Identity, Recipient};
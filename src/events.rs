@@ -0,0 +1,122 @@
+//! Lifecycle events emitted while seal, verify, and push run.
+//!
+//! Commands publish [`PackEvent`]s on the [`EventBus`] carried by their
+//! options; progress reporting and library callers subscribe to it instead of
+//! each command printing on its own.
+
+use std::fmt;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::verify::InvalidFinding;
+
+/// Something that happened during a command, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PackEvent {
+    /// Seal's input walk advanced: running totals after every directory
+    /// entry. Breaking cancels the seal with `E_IO`.
+    CollectProgress { files: usize, bytes: u64 },
+    /// A member was admitted to the seal, once collection is complete.
+    ArtifactCollected { path: String, source: String },
+    /// A member was copied into staging and hashed. Breaking cancels the
+    /// seal with `E_IO`.
+    MemberHashed {
+        path: String,
+        bytes_hash: String,
+        size: u64,
+    },
+    /// Seal or verify is about to hash `members` files totalling `bytes`
+    /// (verify counts recorded `size_bytes`, so older manifests may report 0).
+    HashingStarted { members: usize, bytes: u64 },
+    /// The manifest is final and `pack_id` is known.
+    ManifestFinalized {
+        pack_id: String,
+        member_count: usize,
+    },
+    /// Verify read and hashed a member (not emitted by `--quick`); `ok` is
+    /// whether the hash matched the manifest.
+    MemberVerified { path: String, size: u64, ok: bool },
+    /// Verify found a problem that fails the pack, announced while later
+    /// checks are still running (suppressed findings are not reported).
+    VerificationFinding {
+        #[serde(flatten)]
+        finding: InvalidFinding,
+    },
+    /// Push handed the pack to the store.
+    PackPublished { pack_id: String, outcome: String },
+}
+
+type Subscriber = dyn Fn(&PackEvent) -> ControlFlow<()> + Send + Sync;
+
+/// Subscribers to the events of one command run.
+///
+/// Cloning shares the subscribers; the default bus has none and costs
+/// nothing to emit on.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Vec<Arc<Subscriber>>,
+}
+
+impl EventBus {
+    /// Add a subscriber. Returning `ControlFlow::Break` from an event the
+    /// command can stop at cancels the command.
+    pub fn subscribe(
+        mut self,
+        subscriber: impl Fn(&PackEvent) -> ControlFlow<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.subscribers.push(Arc::new(subscriber));
+        self
+    }
+
+    /// Deliver `event` to every subscriber; breaks if any of them does.
+    pub fn emit(&self, event: &PackEvent) -> ControlFlow<()> {
+        let mut flow = ControlFlow::Continue(());
+        for subscriber in &self.subscribers {
+            if subscriber(event).is_break() {
+                flow = ControlFlow::Break(());
+            }
+        }
+        flow
+    }
+
+    /// Deliver an event the command cannot stop at.
+    pub fn notify(&self, event: &PackEvent) {
+        let _ = self.emit(event);
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EventBus({} subscribers)", self.subscribers.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn every_subscriber_sees_the_event_and_any_can_break() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let bus = EventBus::default()
+            .subscribe(move |event| {
+                log.lock().unwrap().push(event.clone());
+                ControlFlow::Continue(())
+            })
+            .subscribe(|event| match event {
+                PackEvent::CollectProgress { files, .. } if *files > 1 => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            });
+
+        let progress = |files| PackEvent::CollectProgress { files, bytes: 0 };
+        assert!(bus.emit(&progress(1)).is_continue());
+        assert!(bus.emit(&progress(2)).is_break());
+        assert_eq!(*seen.lock().unwrap(), [progress(1), progress(2)]);
+        assert!(EventBus::default().emit(&progress(2)).is_continue());
+    }
+}
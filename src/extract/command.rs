@@ -0,0 +1,536 @@
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::encrypt::{decrypt, load_identities, Identity};
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::archive::{is_archive, unpack_archive};
+use crate::seal::collect::is_safe_member_path;
+use crate::seal::copy::set_executable;
+use crate::seal::hasher::{hash_algorithm_of, hash_buffer_size, stream_hash, MemberHasher};
+use crate::seal::manifest::{Manifest, Member};
+use crate::seal::visibility::path_matches;
+use crate::verify::read_manifest;
+
+use super::report::{ExtractReport, ExtractedMember, EXTRACT_REPORT_VERSION};
+
+/// Options for `pack extract` beyond the pack and output directory.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    /// `--member` patterns: a member path, a directory prefix, or a glob.
+    /// Empty extracts every member.
+    pub members: Vec<String>,
+    /// `--decrypt-key`: age identity file for packs sealed with `--encrypt`.
+    pub decrypt_key: Option<PathBuf>,
+    /// `--include-restricted`: release members carrying a `visibility` tag.
+    pub include_restricted: bool,
+}
+
+/// Execute `pack extract <PACK_DIR> --out <DIR>`.
+///
+/// Returns (output, exit_code): 0 when every selected member was written,
+/// 2 with a refusal otherwise.
+pub fn execute_extract(
+    pack_dir: &Path,
+    out_dir: &Path,
+    json_output: bool,
+    options: &ExtractOptions,
+) -> (String, u8) {
+    match extract_pack(pack_dir, out_dir, options) {
+        Ok(report) => {
+            let output = if json_output {
+                report.to_json()
+            } else {
+                report.to_human()
+            };
+            (output, 0)
+        }
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Copy the selected members of `pack_dir` (a directory or `.pack.tar`) to
+/// their member paths under `out_dir`, re-hashing each against the manifest.
+///
+/// The manifest must still hash to its `pack_id`. Members are staged inside
+/// `out_dir` and only moved into place once every one has matched, so a
+/// refusal leaves `out_dir` as it was. Existing files are never overwritten.
+pub fn extract_pack(
+    pack_dir: &Path,
+    out_dir: &Path,
+    options: &ExtractOptions,
+) -> Result<ExtractReport, Box<RefusalEnvelope>> {
+    let manifest = checked_manifest(pack_dir)?;
+    let selected = select_members(&manifest.members, &options.members)?;
+    for member in &selected {
+        check_member_path(member)?;
+        if let Some(level) = member.visibility.as_deref() {
+            if !options.include_restricted {
+                return Err(io_refusal(
+                    format!(
+                        "Member {} is restricted ({level}); pass --include-restricted to extract it",
+                        member.path
+                    ),
+                    Some(json!({ "path": member.path, "visibility": level })),
+                ));
+            }
+        }
+        if out_dir.join(&member.path).symlink_metadata().is_ok() {
+            return Err(io_refusal(
+                format!(
+                    "Refusing to overwrite {}",
+                    out_dir.join(&member.path).display()
+                ),
+                Some(json!({ "path": member.path })),
+            ));
+        }
+    }
+    let identities = identities_for(&selected, options)?;
+    let (_unpacked, member_root) = member_root(pack_dir)?;
+    let member_root = member_root.as_path();
+
+    let created_out = !out_dir.exists();
+    fs::create_dir_all(out_dir).map_err(|e| {
+        io_refusal(
+            format!("Cannot create output directory {}: {e}", out_dir.display()),
+            None,
+        )
+    })?;
+    let result = stage_and_promote(&selected, member_root, out_dir, &identities);
+    if result.is_err() && created_out {
+        let _ = fs::remove_dir(out_dir);
+    }
+    let members = result?;
+
+    Ok(ExtractReport {
+        version: EXTRACT_REPORT_VERSION.to_string(),
+        outcome: "EXTRACTED".to_string(),
+        pack_id: manifest.pack_id,
+        output: out_dir.display().to_string(),
+        members,
+    })
+}
+
+/// The manifest of `pack_dir`, refused with `E_BAD_PACK` unless it still
+/// hashes to its `pack_id`.
+pub(super) fn checked_manifest(pack_dir: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+    let manifest = read_manifest(pack_dir)?;
+    let recomputed = manifest.recompute_pack_id();
+    if recomputed != manifest.pack_id {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some("manifest.json does not hash to its pack_id".to_string()),
+            Some(json!({
+                "pack": pack_dir.display().to_string(),
+                "expected": manifest.pack_id,
+                "actual": recomputed,
+            })),
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Refuse a member whose path would escape the directory it is written to.
+pub(super) fn check_member_path(member: &Member) -> Result<(), Box<RefusalEnvelope>> {
+    if is_safe_member_path(&member.path) {
+        return Ok(());
+    }
+    Err(Box::new(RefusalEnvelope::new(
+        RefusalCode::BadPack,
+        Some(format!("Unsafe member path: {}", member.path)),
+        Some(json!({ "path": member.path })),
+    )))
+}
+
+/// The `--decrypt-key` identities, when any of `selected` is encrypted.
+pub(super) fn identities_for(
+    selected: &[&Member],
+    options: &ExtractOptions,
+) -> Result<Vec<Identity>, Box<RefusalEnvelope>> {
+    if !selected.iter().any(|m| m.ciphertext_hash.is_some()) {
+        return Ok(Vec::new());
+    }
+    let Some(key) = options.decrypt_key.as_deref() else {
+        return Err(io_refusal(
+            "Pack members are encrypted; pass --decrypt-key to extract them".to_string(),
+            None,
+        ));
+    };
+    load_identities(key).map_err(|message| io_refusal(message, None))
+}
+
+/// The directory holding the pack's members. An archive is unpacked to
+/// scratch space first, kept alive by the returned guard; its members are
+/// re-hashed on the way out like a directory's.
+pub(super) fn member_root(
+    pack_dir: &Path,
+) -> Result<(Option<tempfile::TempDir>, PathBuf), Box<RefusalEnvelope>> {
+    if !is_archive(pack_dir) {
+        return Ok((None, pack_dir.to_path_buf()));
+    }
+    let unpacked = tempfile::tempdir()
+        .map_err(|e| io_refusal(format!("Cannot create scratch directory: {e}"), None))?;
+    unpack_archive(pack_dir, unpacked.path())?;
+    let root = unpacked.path().to_path_buf();
+    Ok((Some(unpacked), root))
+}
+
+/// Members matching any of `patterns` (all members when there are none), in
+/// manifest order. A pattern that matches nothing refuses with `E_IO`.
+pub(super) fn select_members<'a>(
+    members: &'a [Member],
+    patterns: &[String],
+) -> Result<Vec<&'a Member>, Box<RefusalEnvelope>> {
+    if patterns.is_empty() {
+        return Ok(members.iter().collect());
+    }
+    if let Some(pattern) = patterns
+        .iter()
+        .find(|pattern| !members.iter().any(|m| path_matches(pattern, &m.path)))
+    {
+        return Err(io_refusal(
+            format!("--member {pattern} matches no member of the pack"),
+            Some(json!({ "pattern": pattern })),
+        ));
+    }
+    Ok(members
+        .iter()
+        .filter(|m| {
+            patterns
+                .iter()
+                .any(|pattern| path_matches(pattern, &m.path))
+        })
+        .collect())
+}
+
+fn stage_and_promote(
+    selected: &[&Member],
+    member_root: &Path,
+    out_dir: &Path,
+    identities: &[Identity],
+) -> Result<Vec<ExtractedMember>, Box<RefusalEnvelope>> {
+    let staging = tempfile::Builder::new()
+        .prefix(".pack-extract-")
+        .tempdir_in(out_dir)
+        .map_err(|e| io_refusal(format!("Cannot create staging directory: {e}"), None))?;
+
+    let mut extracted = Vec::with_capacity(selected.len());
+    for member in selected {
+        let staged = staging.path().join(&member.path);
+        extract_member(member, &member_root.join(&member.path), &staged, identities)?;
+        extracted.push(ExtractedMember {
+            path: member.path.clone(),
+            bytes_hash: member.bytes_hash.clone(),
+            size_bytes: fs::metadata(&staged).map(|m| m.len()).unwrap_or_default(),
+            decrypted: member.ciphertext_hash.is_some(),
+        });
+    }
+
+    let paths: Vec<&str> = selected.iter().map(|m| m.path.as_str()).collect();
+    promote(staging.path(), out_dir, &paths, |from, to| {
+        fs::rename(from, to)
+    })?;
+    Ok(extracted)
+}
+
+/// Move each staged member at `paths` to its place under `out_dir` with
+/// `rename`. If one cannot be moved, the members already moved and the
+/// directories created for them are removed again, leaving `out_dir` as it
+/// was.
+fn promote(
+    staging: &Path,
+    out_dir: &Path,
+    paths: &[&str],
+    mut rename: impl FnMut(&Path, &Path) -> io::Result<()>,
+) -> Result<(), Box<RefusalEnvelope>> {
+    let mut moved = Vec::new();
+    let mut created_dirs = Vec::new();
+    for path in paths {
+        let target = out_dir.join(path);
+        let promoted = promote_one(&staging.join(path), &target, &mut rename, &mut created_dirs);
+        if let Err(e) = promoted {
+            for target in moved.iter().rev() {
+                let _ = fs::remove_file(target);
+            }
+            // Outer directories were recorded first, so remove inner ones first.
+            for dir in created_dirs.iter().rev() {
+                let _ = fs::remove_dir(dir);
+            }
+            return Err(io_refusal(
+                format!("Cannot write {}: {e}", target.display()),
+                Some(json!({ "path": path, "rolled_back": moved.len() })),
+            ));
+        }
+        moved.push(target);
+    }
+    Ok(())
+}
+
+/// Move `staged` to `target`, recording the directories created for it in
+/// `created_dirs`, outermost first.
+fn promote_one(
+    staged: &Path,
+    target: &Path,
+    rename: &mut impl FnMut(&Path, &Path) -> io::Result<()>,
+    created_dirs: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        let missing: Vec<PathBuf> = parent
+            .ancestors()
+            .take_while(|dir| !dir.exists())
+            .map(Path::to_path_buf)
+            .collect();
+        fs::create_dir_all(parent)?;
+        created_dirs.extend(missing.into_iter().rev());
+    }
+    rename(staged, target)
+}
+
+/// Write one member to `dest`, hashing the bytes as they are written, and
+/// refuse with `E_HASH_MISMATCH` when they differ from the manifest.
+pub(super) fn extract_member(
+    member: &Member,
+    source: &Path,
+    dest: &Path,
+    identities: &[Identity],
+) -> Result<(), Box<RefusalEnvelope>> {
+    let write_error = |e: io::Error| {
+        io_refusal(
+            format!("Cannot write {}: {e}", dest.display()),
+            Some(json!({ "path": member.path })),
+        )
+    };
+    let input = fs::File::open(source).map_err(|e| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(format!("Cannot read member {}: {e}", member.path)),
+            Some(json!({ "path": member.path })),
+        ))
+    })?;
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let mut output = BufWriter::new(fs::File::create(dest).map_err(write_error)?);
+
+    // An unsupported prefix cannot match, so it is an E_HASH_MISMATCH below.
+    let algorithm = hash_algorithm_of(&member.bytes_hash).unwrap_or_default();
+    let (actual, size) = if member.ciphertext_hash.is_some() {
+        let mut hasher = MemberHasher::new(algorithm);
+        let size = decrypt(
+            input,
+            Tee {
+                first: &mut output,
+                second: &mut hasher,
+            },
+            identities,
+        )
+        .map_err(|reason| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::BadPack,
+                Some(format!("Cannot decrypt member {}: {reason}", member.path)),
+                Some(json!({ "path": member.path })),
+            ))
+        })?;
+        (hasher.finalize(), size)
+    } else {
+        stream_hash(
+            input,
+            Some(&mut output),
+            hash_buffer_size(),
+            algorithm,
+            true,
+        )
+        .map_err(write_error)?
+    };
+    output.flush().map_err(write_error)?;
+
+    if actual != member.bytes_hash || member.size_bytes.is_some_and(|expected| expected != size) {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::HashMismatch,
+            Some(format!(
+                "Member {} does not match its manifest hash",
+                member.path
+            )),
+            Some(json!({
+                "path": member.path,
+                "expected": member.bytes_hash,
+                "actual": actual,
+            })),
+        )));
+    }
+    if member.executable == Some(true) {
+        set_executable(dest).map_err(write_error)?;
+    }
+    Ok(())
+}
+
+/// Writes every byte to both writers.
+struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Write, B: Write> Write for Tee<A, B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.first.write_all(buf)?;
+        self.second.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.first.flush()?;
+        self.second.flush()
+    }
+}
+
+pub(super) fn io_refusal(
+    message: String,
+    detail: Option<serde_json::Value>,
+) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(RefusalCode::Io, Some(message), detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::{execute_seal_with_options, SealOptions};
+    use tempfile::TempDir;
+
+    fn seal(tmp: &TempDir, options: &SealOptions) -> PathBuf {
+        let src = tmp.path().join("src");
+        fs::create_dir_all(src.join("logs")).unwrap();
+        fs::write(src.join("a.json"), r#"{"a": 1}"#).unwrap();
+        fs::write(src.join("logs/one.txt"), "one").unwrap();
+        fs::write(src.join("logs/two.txt"), "two").unwrap();
+        let pack = tmp
+            .path()
+            .join(if options.archive { "p.pack.tar" } else { "p" });
+        execute_seal_with_options(std::slice::from_ref(&src), Some(&pack), None, options).unwrap();
+        pack
+    }
+
+    #[test]
+    fn extracts_glob_selection_from_an_archive() {
+        let tmp = TempDir::new().unwrap();
+        let options = SealOptions {
+            archive: true,
+            ..SealOptions::default()
+        };
+        let pack = seal(&tmp, &options);
+        let out = tmp.path().join("out");
+        let report = extract_pack(
+            &pack,
+            &out,
+            &ExtractOptions {
+                members: vec!["src/logs/*.txt".to_string()],
+                ..ExtractOptions::default()
+            },
+        )
+        .unwrap();
+        let paths: Vec<&str> = report.members.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, ["src/logs/one.txt", "src/logs/two.txt"]);
+        assert_eq!(
+            fs::read_to_string(out.join("src/logs/two.txt")).unwrap(),
+            "two"
+        );
+        assert!(!out.join("src/a.json").exists());
+        assert_eq!(fs::read_dir(&out).unwrap().count(), 1, "staging removed");
+
+        let again = extract_pack(&pack, &out, &ExtractOptions::default()).unwrap_err();
+        assert_eq!(again.refusal.code, "E_IO");
+        assert!(
+            !out.join("src/a.json").exists(),
+            "nothing written on refusal"
+        );
+    }
+
+    #[test]
+    fn failed_promotion_removes_the_members_already_moved() {
+        let tmp = TempDir::new().unwrap();
+        let staging = tmp.path().join("staging");
+        let out = tmp.path().join("out");
+        let paths = ["a.json", "logs/one.txt", "logs/deep/two.txt"];
+        for path in paths {
+            fs::create_dir_all(staging.join(path).parent().unwrap()).unwrap();
+            fs::write(staging.join(path), path).unwrap();
+        }
+        fs::create_dir(&out).unwrap();
+        fs::write(out.join("existing.txt"), "keep").unwrap();
+
+        let mut renames = 0;
+        let refusal = promote(&staging, &out, &paths, |from, to| {
+            renames += 1;
+            if renames == 3 {
+                return Err(io::Error::other("disk full"));
+            }
+            fs::rename(from, to)
+        })
+        .unwrap_err();
+        assert_eq!(refusal.refusal.code, "E_IO");
+        assert!(refusal.refusal.message.contains("disk full"));
+        let left: Vec<_> = fs::read_dir(&out)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(left, ["existing.txt"], "out_dir is as it was");
+    }
+
+    #[test]
+    fn tampered_member_refuses_and_writes_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let pack = seal(&tmp, &SealOptions::default());
+        fs::write(pack.join("src/logs/two.txt"), "TWO").unwrap();
+        let out = tmp.path().join("out");
+        let (output, exit_code) = execute_extract(&pack, &out, true, &ExtractOptions::default());
+        assert_eq!(exit_code, 2);
+        let refusal: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(refusal["refusal"]["code"], "E_HASH_MISMATCH");
+        assert_eq!(refusal["refusal"]["detail"]["path"], "src/logs/two.txt");
+        assert!(!out.exists());
+
+        let missing = extract_pack(
+            &pack,
+            &out,
+            &ExtractOptions {
+                members: vec!["nope/**".to_string()],
+                ..ExtractOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(missing.refusal.code, "E_IO");
+    }
+
+    #[test]
+    fn encrypted_members_need_the_key() {
+        let tmp = TempDir::new().unwrap();
+        let identity = Identity::generate();
+        let options = SealOptions {
+            encrypt_to: vec![identity.recipient()],
+            ..SealOptions::default()
+        };
+        let pack = seal(&tmp, &options);
+        let out = tmp.path().join("out");
+        let refused = extract_pack(&pack, &out, &ExtractOptions::default()).unwrap_err();
+        assert!(refused.refusal.message.contains("--decrypt-key"));
+
+        let key = tmp.path().join("key.txt");
+        fs::write(&key, identity.to_secret_string()).unwrap();
+        let report = extract_pack(
+            &pack,
+            &out,
+            &ExtractOptions {
+                members: vec!["src/a.json".to_string()],
+                decrypt_key: Some(key),
+                ..ExtractOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(report.members[0].decrypted);
+        assert_eq!(
+            fs::read_to_string(out.join("src/a.json")).unwrap(),
+            r#"{"a": 1}"#
+        );
+    }
+}
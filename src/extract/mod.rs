@@ -0,0 +1,10 @@
+mod command;
+mod report;
+mod spot_check;
+
+pub use command::{execute_extract, extract_pack, ExtractOptions};
+pub use report::{
+    ExtractReport, ExtractedMember, ReportSignature, SpotCheckReport, SpotCheckedMember,
+    EXTRACT_REPORT_VERSION, SPOT_CHECK_REPORT_VERSION,
+};
+pub use spot_check::{execute_spot_check, spot_check_pack, SpotCheckOptions};
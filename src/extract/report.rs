@@ -0,0 +1,170 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+use serde::{Deserialize, Serialize};
+
+use crate::canonical::canonicalize;
+use crate::sign::parse_public_key;
+
+/// Version tag for `pack extract --json` output.
+pub const EXTRACT_REPORT_VERSION: &str = "pack.extract.v0";
+
+/// Version tag for `pack extract --verify-only-selected --json` output.
+pub const SPOT_CHECK_REPORT_VERSION: &str = "pack.spot_check.v0";
+
+/// A successful extraction, as printed by `pack extract --json`.
+///
+/// Failures print the standard refusal envelope instead, and leave nothing
+/// in the output directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExtractReport {
+    pub version: String,
+    pub outcome: String,
+    pub pack_id: String,
+    /// Directory the members were written under, at their member paths.
+    pub output: String,
+    /// Extracted members in manifest order.
+    pub members: Vec<ExtractedMember>,
+}
+
+/// One member written out, with the hash it was checked against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExtractedMember {
+    pub path: String,
+    pub bytes_hash: String,
+    pub size_bytes: u64,
+    /// The member was decrypted with `--decrypt-key`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub decrypted: bool,
+}
+
+impl ExtractReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("extract report serialization cannot fail")
+    }
+
+    /// `pack extract: EXTRACTED` with the pack ID and output directory, then
+    /// one line per member.
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![
+            format!("pack extract: {}", self.outcome),
+            format!("  pack_id: {}", self.pack_id),
+            format!("  output: {}", self.output),
+            format!("  members: {}", self.members.len()),
+        ];
+        lines.extend(
+            self.members
+                .iter()
+                .map(|member| format!("    {}", member.path)),
+        );
+        lines.join("\n")
+    }
+}
+
+/// A re-hash of a seeded random sample of members, as printed by
+/// `pack extract --verify-only-selected`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpotCheckReport {
+    pub version: String,
+    /// `OK` when every sampled member matched the manifest, `INVALID`
+    /// otherwise.
+    pub outcome: String,
+    pub pack_id: String,
+    /// The seed the sample was drawn with; the same seed over the same pack
+    /// draws the same members.
+    pub seed: u32,
+    /// Members the sample was drawn from.
+    pub pool: usize,
+    /// Sampled members in manifest order.
+    pub members: Vec<SpotCheckedMember>,
+    /// `--sign`: Ed25519 signature over the rest of the report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ReportSignature>,
+}
+
+/// One sampled member and how its bytes compared with the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpotCheckedMember {
+    pub path: String,
+    pub bytes_hash: String,
+    /// `OK`, `HASH_MISMATCH`, or `UNREADABLE`.
+    pub status: String,
+    /// The hash found on `HASH_MISMATCH`, or the reason on `UNREADABLE`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub decrypted: bool,
+}
+
+/// Signature over a report's RFC 8785 form with `signature` left out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportSignature {
+    pub algorithm: String,
+    /// Signer's public key, hex, as in `manifest.sig.json`.
+    pub public_key: String,
+    pub signature: String,
+}
+
+impl SpotCheckReport {
+    /// Sign the report with `key`, replacing any earlier signature.
+    pub fn sign(&mut self, key: &SigningKey) {
+        let signature = key.sign(&self.signed_bytes());
+        self.signature = Some(ReportSignature {
+            algorithm: "ed25519".to_string(),
+            public_key: hex::encode(key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+    }
+
+    /// Check the report's signature, returning the signer's public key.
+    pub fn verify_signature(&self) -> Result<String, String> {
+        let signed = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| "report is not signed".to_string())?;
+        if signed.algorithm != "ed25519" {
+            return Err(format!("unsupported algorithm {}", signed.algorithm));
+        }
+        let signer = parse_public_key(&signed.public_key)?;
+        let signature = hex::decode(&signed.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| "signature is not 64 hex-encoded bytes".to_string())?;
+        signer
+            .verify(&self.signed_bytes(), &signature)
+            .map_err(|_| "signature does not match the report".to_string())?;
+        Ok(signed.public_key.clone())
+    }
+
+    fn signed_bytes(&self) -> Vec<u8> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        let value = serde_json::to_value(&unsigned).expect("spot check serialization cannot fail");
+        canonicalize(&value).into_bytes()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("spot check serialization cannot fail")
+    }
+
+    /// `pack extract: OK` with the pack ID, seed, and sample size, then one
+    /// line per sampled member with its status.
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![
+            format!("pack extract: {}", self.outcome),
+            format!("  pack_id: {}", self.pack_id),
+            format!("  seed: {}", self.seed),
+            format!("  sampled: {} of {}", self.members.len(), self.pool),
+        ];
+        for member in &self.members {
+            lines.push(match &member.actual {
+                Some(actual) => format!("    {} {} ({actual})", member.status, member.path),
+                None => format!("    {} {}", member.status, member.path),
+            });
+        }
+        if let Some(signature) = &self.signature {
+            lines.push(format!("  signed by: {}", signature.public_key));
+        }
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,295 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::manifest::Member;
+use crate::sign::load_signing_key;
+
+use super::command::{
+    check_member_path, checked_manifest, extract_member, identities_for, io_refusal, member_root,
+    select_members, ExtractOptions,
+};
+use super::report::{SpotCheckReport, SpotCheckedMember, SPOT_CHECK_REPORT_VERSION};
+
+/// `pack extract --verify-only-selected` options.
+#[derive(Debug, Clone, Default)]
+pub struct SpotCheckOptions {
+    /// How many members to sample; fewer when the pack has fewer.
+    pub count: usize,
+    /// `--seed`: draw the sample reproducibly. A fresh seed is drawn (and
+    /// reported) otherwise.
+    pub seed: Option<u32>,
+    /// `--sign`: sign the report with Ed25519.
+    pub sign: bool,
+    /// `--signing-key`: Ed25519 private key file.
+    pub signing_key: Option<PathBuf>,
+}
+
+/// Execute `pack extract <PACK_DIR> --verify-only-selected <N>`.
+///
+/// Returns (output, exit_code): 0 when every sampled member matched, 1 when
+/// one did not, 2 with a refusal otherwise.
+pub fn execute_spot_check(
+    pack_dir: &Path,
+    json_output: bool,
+    extract: &ExtractOptions,
+    options: &SpotCheckOptions,
+) -> (String, u8) {
+    match spot_check_pack(pack_dir, extract, options) {
+        Ok(report) => {
+            let code = if report.outcome == "OK" { 0 } else { 1 };
+            let output = if json_output {
+                report.to_json()
+            } else {
+                report.to_human()
+            };
+            (output, code)
+        }
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Extract a seeded random sample of the selected members to scratch space
+/// and re-hash each against the manifest, without writing anything the
+/// caller keeps.
+///
+/// The sample is drawn from the `--member` selection, less restricted
+/// members unless `include_restricted` is set. A member that does not match
+/// or cannot be read is reported rather than refused, so one bad member does
+/// not hide the rest of the sample.
+pub fn spot_check_pack(
+    pack_dir: &Path,
+    extract: &ExtractOptions,
+    options: &SpotCheckOptions,
+) -> Result<SpotCheckReport, Box<RefusalEnvelope>> {
+    let signing_key = if options.sign {
+        let key = load_signing_key(options.signing_key.as_deref()).map_err(|message| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(message),
+                options
+                    .signing_key
+                    .as_ref()
+                    .map(|path| json!({ "signing_key": path.display().to_string() })),
+            ))
+        })?;
+        Some(key)
+    } else {
+        None
+    };
+
+    let manifest = checked_manifest(pack_dir)?;
+    let pool: Vec<&Member> = select_members(&manifest.members, &extract.members)?
+        .into_iter()
+        .filter(|m| extract.include_restricted || m.visibility.is_none())
+        .collect();
+    if pool.is_empty() {
+        return Err(io_refusal(
+            "No unrestricted members to sample; pass --include-restricted to sample restricted ones"
+                .to_string(),
+            None,
+        ));
+    }
+    let seed = options.seed.unwrap_or_else(fresh_seed);
+    let sampled = sample(&pool, options.count, seed);
+    for member in &sampled {
+        check_member_path(member)?;
+    }
+    let identities = identities_for(&sampled, extract)?;
+    let (_unpacked, member_root) = member_root(pack_dir)?;
+    let scratch = tempfile::tempdir()
+        .map_err(|e| io_refusal(format!("Cannot create scratch directory: {e}"), None))?;
+
+    let mut members = Vec::with_capacity(sampled.len());
+    for (i, member) in sampled.iter().enumerate() {
+        // Members are written under their index, so member paths never have
+        // to be recreated and the scratch directory holds one file at a time.
+        let dest = scratch.path().join(i.to_string());
+        let (status, actual) =
+            match extract_member(member, &member_root.join(&member.path), &dest, &identities) {
+                Ok(()) => ("OK", None),
+                Err(envelope) if envelope.refusal.code == "E_HASH_MISMATCH" => {
+                    let actual = envelope
+                        .refusal
+                        .detail
+                        .as_ref()
+                        .and_then(|detail| detail["actual"].as_str())
+                        .map(str::to_string);
+                    ("HASH_MISMATCH", actual)
+                }
+                Err(envelope) if envelope.refusal.code == "E_BAD_PACK" => {
+                    ("UNREADABLE", Some(envelope.refusal.message))
+                }
+                Err(envelope) => return Err(envelope),
+            };
+        let _ = fs::remove_file(&dest);
+        members.push(SpotCheckedMember {
+            path: member.path.clone(),
+            bytes_hash: member.bytes_hash.clone(),
+            status: status.to_string(),
+            actual,
+            decrypted: member.ciphertext_hash.is_some(),
+        });
+    }
+
+    let outcome = if members.iter().all(|m| m.status == "OK") {
+        "OK"
+    } else {
+        "INVALID"
+    };
+    let mut report = SpotCheckReport {
+        version: SPOT_CHECK_REPORT_VERSION.to_string(),
+        outcome: outcome.to_string(),
+        pack_id: manifest.pack_id,
+        seed,
+        pool: pool.len(),
+        members,
+        signature: None,
+    };
+    if let Some(key) = &signing_key {
+        report.sign(key);
+    }
+    Ok(report)
+}
+
+/// The `count` members of `pool` ranking lowest by `blake3(seed || path)`,
+/// in manifest order. The same seed over the same pool draws the same sample.
+fn sample<'a>(pool: &[&'a Member], count: usize, seed: u32) -> Vec<&'a Member> {
+    let mut ranked: Vec<(blake3::Hash, usize)> = pool
+        .iter()
+        .enumerate()
+        .map(|(i, member)| {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&seed.to_le_bytes());
+            hasher.update(member.path.as_bytes());
+            (hasher.finalize(), i)
+        })
+        .collect();
+    ranked.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+    let mut picked: Vec<usize> = ranked.iter().take(count).map(|(_, i)| *i).collect();
+    picked.sort_unstable();
+    picked.into_iter().map(|i| pool[i]).collect()
+}
+
+fn fresh_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() ^ d.as_secs() as u32)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::{execute_seal_with_options, SealOptions};
+    use tempfile::TempDir;
+
+    const SIGNING_KEY_HEX: &str =
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
+    fn seal(tmp: &TempDir) -> PathBuf {
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        for i in 0..8 {
+            fs::write(src.join(format!("m{i}.txt")), format!("member {i}")).unwrap();
+        }
+        let pack = tmp.path().join("p");
+        execute_seal_with_options(
+            std::slice::from_ref(&src),
+            Some(&pack),
+            None,
+            &SealOptions::default(),
+        )
+        .unwrap();
+        pack
+    }
+
+    fn options(seed: u32) -> SpotCheckOptions {
+        SpotCheckOptions {
+            count: 3,
+            seed: Some(seed),
+            ..SpotCheckOptions::default()
+        }
+    }
+
+    fn paths(report: &SpotCheckReport) -> Vec<String> {
+        report.members.iter().map(|m| m.path.clone()).collect()
+    }
+
+    #[test]
+    fn seeded_sample_is_reproducible_and_writes_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let pack = seal(&tmp);
+        let before = fs::read_dir(tmp.path()).unwrap().count();
+
+        let first = spot_check_pack(&pack, &ExtractOptions::default(), &options(7)).unwrap();
+        let again = spot_check_pack(&pack, &ExtractOptions::default(), &options(7)).unwrap();
+        assert_eq!(first.outcome, "OK");
+        assert_eq!((first.members.len(), first.pool), (3, 8));
+        assert_eq!(paths(&first), paths(&again));
+        assert!(first.members.iter().all(|m| m.status == "OK"));
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), before);
+
+        let all = SpotCheckOptions {
+            count: 20,
+            ..options(7)
+        };
+        let all = spot_check_pack(&pack, &ExtractOptions::default(), &all).unwrap();
+        assert_eq!(all.members.len(), 8, "capped at the pool");
+    }
+
+    #[test]
+    fn tampered_member_is_reported_invalid() {
+        let tmp = TempDir::new().unwrap();
+        let pack = seal(&tmp);
+        let everything = SpotCheckOptions {
+            count: 8,
+            ..options(1)
+        };
+        fs::write(pack.join("src/m3.txt"), "tampered").unwrap();
+        fs::remove_file(pack.join("src/m5.txt")).unwrap();
+
+        let report = spot_check_pack(&pack, &ExtractOptions::default(), &everything).unwrap();
+        assert_eq!(report.outcome, "INVALID");
+        let status = |path: &str| {
+            let member = report.members.iter().find(|m| m.path == path).unwrap();
+            (member.status.as_str(), member.actual.is_some())
+        };
+        assert_eq!(status("src/m3.txt"), ("HASH_MISMATCH", true));
+        assert_eq!(status("src/m5.txt"), ("UNREADABLE", true));
+        assert_eq!(status("src/m0.txt"), ("OK", false));
+
+        let (_, code) = execute_spot_check(&pack, true, &ExtractOptions::default(), &everything);
+        assert_eq!(code, 1);
+    }
+
+    #[test]
+    fn signed_report_verifies_and_detects_edits() {
+        let tmp = TempDir::new().unwrap();
+        let pack = seal(&tmp);
+        let key_file = tmp.path().join("signing.key");
+        fs::write(&key_file, SIGNING_KEY_HEX).unwrap();
+        let signed = SpotCheckOptions {
+            sign: true,
+            signing_key: Some(key_file),
+            ..options(9)
+        };
+
+        let (output, code) = execute_spot_check(&pack, true, &ExtractOptions::default(), &signed);
+        assert_eq!(code, 0);
+        let mut report: SpotCheckReport = serde_json::from_str(&output).unwrap();
+        let signer = report.verify_signature().unwrap();
+        assert_eq!(
+            signer,
+            report.signature.as_ref().unwrap().public_key,
+            "signer is the report's public key"
+        );
+
+        report.members[0].status = "HASH_MISMATCH".to_string();
+        assert!(report.verify_signature().is_err());
+        report.signature = None;
+        assert!(report.verify_signature().is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! C ABI for calling pack from other languages without spawning the binary
+//! (feature `ffi`). Declarations are in `include/pack.h`.
+//!
+//! Every function returns a NUL-terminated JSON document that the caller owns
+//! and must release with [`pack_string_free`]. It is exactly what the CLI
+//! prints with `--json`: a `pack.verify.v0` or `pack.seal.v0` report, or the
+//! refusal envelope for anything that could not be done. Invalid arguments
+//! and panics are refusals too (`E_IO`), so a call never unwinds into C and
+//! never returns NULL. No witness records are written.
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::api::{RefusalCode, RefusalEnvelope, SealOptions, VerifyOptions};
+use crate::seal::report::SealReport;
+
+/// Verify the pack directory or `.pack.tar` at `pack_dir`, as
+/// `pack verify --json` does. The report's `outcome` is `OK`, `INVALID`, or
+/// `REFUSAL`.
+///
+/// # Safety
+///
+/// `pack_dir` must be NULL or point to a NUL-terminated string that stays
+/// valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn pack_verify(pack_dir: *const c_char) -> *mut c_char {
+    guarded("pack_verify", || {
+        let pack_dir = PathBuf::from(required_str(pack_dir, "pack_dir")?);
+        let report = crate::verify::verify_pack(&pack_dir, &VerifyOptions::default());
+        Ok(report.to_json())
+    })
+}
+
+/// Seal the artifacts named by `artifacts_json`, a JSON array of paths, into
+/// `output` (NULL for the default `pack/<pack_id>/`) with an optional
+/// `note`, as `pack seal --json` does.
+///
+/// # Safety
+///
+/// Each argument must be NULL or point to a NUL-terminated string that stays
+/// valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn pack_seal(
+    artifacts_json: *const c_char,
+    output: *const c_char,
+    note: *const c_char,
+) -> *mut c_char {
+    guarded("pack_seal", || {
+        let artifacts: Vec<PathBuf> =
+            serde_json::from_str(required_str(artifacts_json, "artifacts_json")?).map_err(|e| {
+                argument_refusal(format!("artifacts_json is not an array of paths: {e}"))
+            })?;
+        let output = optional_str(output, "output")?.map(Path::new);
+        let note = optional_str(note, "note")?.map(str::to_string);
+        let result = crate::api::seal(&artifacts, output, note, &SealOptions::default())?;
+        Ok(SealReport::new(&result).to_json())
+    })
+}
+
+/// Release a string returned by any `pack_*` function. NULL is ignored.
+///
+/// # Safety
+///
+/// `text` must be NULL or a pointer returned by this library that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn pack_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+/// Run `call`, turning refusals and panics into refusal JSON for C.
+fn guarded(
+    function: &str,
+    call: impl FnOnce() -> Result<String, Box<RefusalEnvelope>>,
+) -> *mut c_char {
+    let json = match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(json)) => json,
+        Ok(Err(refusal)) => refusal.to_json(),
+        Err(_) => RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(format!("{function} panicked")),
+            Some(json!({ "function": function })),
+        )
+        .to_json(),
+    };
+    // serde_json escapes NUL, so the JSON never contains one.
+    CString::new(json)
+        .expect("JSON text has no NUL bytes")
+        .into_raw()
+}
+
+unsafe fn required_str<'a>(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<&'a str, Box<RefusalEnvelope>> {
+    optional_str(ptr, name)?.ok_or_else(|| argument_refusal(format!("{name} is NULL")))
+}
+
+unsafe fn optional_str<'a>(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<Option<&'a str>, Box<RefusalEnvelope>> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(Some)
+        .map_err(|_| argument_refusal(format!("{name} is not valid UTF-8")))
+}
+
+fn argument_refusal(message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(RefusalCode::Io, Some(message), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn take(text: *mut c_char) -> serde_json::Value {
+        let value = serde_json::from_slice(unsafe { CStr::from_ptr(text) }.to_bytes()).unwrap();
+        unsafe { pack_string_free(text) };
+        value
+    }
+
+    #[test]
+    fn seal_and_verify_round_trip_through_the_c_abi() {
+        let tmp = TempDir::new().unwrap();
+        let artifact = tmp.path().join("nov.lock.json");
+        fs::write(&artifact, r#"{"version": "lock.v0"}"#).unwrap();
+        let out = tmp.path().join("pack");
+        let artifacts = CString::new(json!([artifact]).to_string()).unwrap();
+        let output = CString::new(out.to_str().unwrap()).unwrap();
+
+        let sealed =
+            take(unsafe { pack_seal(artifacts.as_ptr(), output.as_ptr(), std::ptr::null()) });
+        assert_eq!(sealed["outcome"], "PACK_CREATED", "{sealed}");
+
+        let verified = take(unsafe { pack_verify(output.as_ptr()) });
+        assert_eq!(verified["outcome"], "OK", "{verified}");
+        assert_eq!(verified["pack_id"], sealed["pack_id"]);
+
+        let refused = take(unsafe { pack_verify(std::ptr::null()) });
+        assert_eq!(refused["refusal"]["code"], "E_IO");
+        let bad = CString::new("[1]").unwrap();
+        let refused = take(unsafe { pack_seal(bad.as_ptr(), std::ptr::null(), std::ptr::null()) });
+        assert_eq!(refused["outcome"], "REFUSAL");
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::cli::SortOrder;
+use crate::network::deprecate::find_deprecation;
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::archive::{is_archive, unpack_archive};
+use crate::seal::limits::MAX_MANIFEST_BYTES;
+use crate::seal::manifest::Manifest;
+
+use super::report::{InspectMember, InspectReport, INSPECT_REPORT_VERSION};
+
+/// Execute `pack inspect <PACK_DIR> [--member <PATH>]`.
+///
+/// `sort` orders the human member table; JSON keeps manifest order. `store`
+/// is a filesystem store whose index is consulted for a deprecation note.
+/// Returns (output_string, exit_code): 0 with the report, or 2 with a
+/// refusal envelope.
+pub fn execute_inspect(
+    pack_dir: &Path,
+    member: Option<&str>,
+    json_output: bool,
+    sort: SortOrder,
+    store: Option<&Path>,
+) -> (String, u8) {
+    let inspected = inspect_pack(pack_dir, store).and_then(|report| match member {
+        None => Ok(if json_output {
+            report.to_json()
+        } else {
+            report.to_human(sort)
+        }),
+        Some(path) => {
+            let found = report
+                .members
+                .iter()
+                .find(|m| m.path == path)
+                .ok_or_else(|| {
+                    Box::new(RefusalEnvelope::new(
+                        RefusalCode::Io,
+                        Some(format!("No member {path} in pack {}", report.pack_id)),
+                        Some(json!({ "member": path })),
+                    ))
+                })?;
+            Ok(if json_output {
+                report.member_to_json(found)
+            } else {
+                report.member_to_human(found)
+            })
+        }
+    });
+    match inspected {
+        Ok(output) => (output, 0),
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Read a pack directory (or `.pack.tar`) manifest and the size of each
+/// member file. Nothing is hashed; use `pack verify` for integrity.
+///
+/// A deprecation note is looked up in the store holding `pack_dir`, then in
+/// `store`.
+pub fn inspect_pack(
+    pack_dir: &Path,
+    store: Option<&Path>,
+) -> Result<InspectReport, Box<RefusalEnvelope>> {
+    if is_archive(pack_dir) {
+        let scratch = tempfile::tempdir().map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot create scratch directory: {e}")),
+                None,
+            ))
+        })?;
+        unpack_archive(pack_dir, scratch.path())?;
+        return inspect_pack(scratch.path(), store);
+    }
+
+    let manifest_path = pack_dir.join("manifest.json");
+    let bad_pack = |message: String| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(message),
+            Some(json!({ "pack": pack_dir.display().to_string() })),
+        ))
+    };
+    if let Ok(meta) = fs::metadata(&manifest_path) {
+        if meta.len() > MAX_MANIFEST_BYTES {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Limit,
+                Some(format!(
+                    "manifest.json is {} bytes, exceeding the {MAX_MANIFEST_BYTES}-byte limit",
+                    meta.len()
+                )),
+                None,
+            )));
+        }
+    }
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| bad_pack(format!("Cannot read manifest.json: {e}")))?;
+    let manifest: Manifest = serde_json::from_str(&content)
+        .map_err(|e| bad_pack(format!("Invalid manifest.json: {e}")))?;
+    if manifest.version != "pack.v0" {
+        return Err(bad_pack(format!(
+            "Unsupported manifest version: {}",
+            manifest.version
+        )));
+    }
+
+    let members = manifest
+        .members
+        .iter()
+        .map(|member| {
+            let size = fs::symlink_metadata(pack_dir.join(&member.path))
+                .ok()
+                .filter(|meta| meta.is_file())
+                .map(|meta| meta.len());
+            InspectMember::new(
+                member,
+                size,
+                manifest.annotations.get(&member.path).cloned(),
+            )
+        })
+        .collect();
+    Ok(InspectReport {
+        version: INSPECT_REPORT_VERSION.to_string(),
+        deprecation: find_deprecation(Some(pack_dir), &manifest.pack_id, store),
+        pack_id: manifest.pack_id,
+        created: manifest.created,
+        note: manifest.note,
+        tool_version: manifest.tool_version,
+        member_count: manifest.member_count,
+        members,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reports_members_with_sizes_and_refuses_unknown_member() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("nov.lock.json");
+        fs::write(&file, r#"{"version":"lock.v0"}"#).unwrap();
+        let sealed = execute_seal(&[file], Some(&tmp.path().join("pack")), None).unwrap();
+
+        let report = inspect_pack(&sealed.output_dir, None).unwrap();
+        assert_eq!(report.pack_id, sealed.pack_id);
+        assert_eq!(report.members[0].member_type, "lockfile");
+        assert_eq!(report.members[0].size_bytes, Some(21));
+
+        let (output, code) = execute_inspect(
+            &sealed.output_dir,
+            Some("nov.lock.json"),
+            true,
+            SortOrder::Bytewise,
+            None,
+        );
+        assert_eq!(code, 0);
+        let detail: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(detail["member"]["artifact_version"], "lock.v0");
+
+        let (output, code) = execute_inspect(
+            &sealed.output_dir,
+            Some("nope.json"),
+            false,
+            SortOrder::Bytewise,
+            None,
+        );
+        assert_eq!(code, 2);
+        assert!(output.contains("E_IO"));
+
+        let (_, code) = execute_inspect(tmp.path(), None, false, SortOrder::Bytewise, None);
+        assert_eq!(code, 2);
+    }
+}
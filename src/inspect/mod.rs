@@ -0,0 +1,5 @@
+mod command;
+mod report;
+
+pub use command::{execute_inspect, inspect_pack};
+pub use report::{InspectMember, InspectReport, INSPECT_REPORT_VERSION};
@@ -0,0 +1,289 @@
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cli::SortOrder;
+use crate::network::deprecate::Deprecation;
+use crate::seal::manifest::{Member, MemberOrigin};
+
+/// Version tag for `pack inspect --json` output.
+pub const INSPECT_REPORT_VERSION: &str = "pack.inspect.v0";
+
+/// Hex digits of `bytes_hash` shown in the human member table.
+const HASH_PREFIX_LEN: usize = 12;
+
+/// A pack's manifest summary, as printed by `pack inspect`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InspectReport {
+    pub version: String,
+    pub pack_id: String,
+    pub created: String,
+    pub note: Option<String>,
+    pub tool_version: String,
+    pub member_count: usize,
+    pub members: Vec<InspectMember>,
+    /// Deprecation note from the store index, when the pack was deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<Deprecation>,
+}
+
+/// One manifest member with its on-disk size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InspectMember {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub member_type: String,
+    pub artifact_version: Option<String>,
+    /// Size of the member file in the pack; `None` when it is missing.
+    pub size_bytes: Option<u64>,
+    pub bytes_hash: String,
+    /// Media type detected at seal; absent for packs sealed before it was
+    /// recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<MemberOrigin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable: Option<bool>,
+    /// Reviewer label from `seal --annotate`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+}
+
+impl InspectMember {
+    pub fn new(member: &Member, size_bytes: Option<u64>, annotation: Option<String>) -> Self {
+        Self {
+            path: member.path.clone(),
+            member_type: member.member_type.clone(),
+            artifact_version: member.artifact_version.clone(),
+            size_bytes,
+            bytes_hash: member.bytes_hash.clone(),
+            content_type: member.content_type.clone(),
+            charset: member.charset.clone(),
+            visibility: member.visibility.clone(),
+            origin: member.origin.clone(),
+            executable: member.executable,
+            annotation,
+        }
+    }
+
+    fn size(&self) -> String {
+        self.size_bytes
+            .map_or_else(|| "-".to_string(), |size| size.to_string())
+    }
+
+    fn hash_prefix(&self) -> &str {
+        let hex = self
+            .bytes_hash
+            .split_once(':')
+            .map_or(self.bytes_hash.as_str(), |(_, hex)| hex);
+        hex.get(..HASH_PREFIX_LEN).unwrap_or(hex)
+    }
+}
+
+impl InspectReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("inspect report serialization cannot fail")
+    }
+
+    /// Header lines followed by an aligned member table, its rows (and the
+    /// annotations after it) ordered by `sort`.
+    pub fn to_human(&self, sort: SortOrder) -> String {
+        let mut lines = vec![format!("pack inspect: {}", self.pack_id)];
+        lines.extend(self.deprecation_line());
+        lines.push(format!("  created: {}", self.created));
+        if let Some(note) = &self.note {
+            lines.push(format!("  note: {note}"));
+        }
+        lines.push(format!("  tool_version: {}", self.tool_version));
+        lines.push(format!("  members: {}", self.member_count));
+        if self.members.is_empty() {
+            return lines.join("\n");
+        }
+
+        let mut members: Vec<&InspectMember> = self.members.iter().collect();
+        members.sort_by(|a, b| sort.compare(&a.path, &b.path));
+        let header = ["PATH", "TYPE", "VERSION", "CONTENT", "SIZE", "HASH"];
+        let rows: Vec<[String; 6]> = std::iter::once(header.map(str::to_string))
+            .chain(members.iter().map(|m| {
+                [
+                    m.path.clone(),
+                    m.member_type.clone(),
+                    m.artifact_version
+                        .clone()
+                        .unwrap_or_else(|| "-".to_string()),
+                    m.content_type.clone().unwrap_or_else(|| "-".to_string()),
+                    m.size(),
+                    m.hash_prefix().to_string(),
+                ]
+            }))
+            .collect();
+        let mut widths = [0; 6];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for row in &rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .enumerate()
+                .map(|(i, (cell, width))| match i {
+                    // Sizes are right-aligned; the last column needs no padding
+                    4 => format!("{cell:>width$}"),
+                    5 => cell.clone(),
+                    _ => format!("{cell:<width$}"),
+                })
+                .collect();
+            lines.push(format!("    {}", cells.join("  ")));
+        }
+        let annotated: Vec<&InspectMember> = members
+            .into_iter()
+            .filter(|m| m.annotation.is_some())
+            .collect();
+        if !annotated.is_empty() {
+            lines.push("  annotations:".to_string());
+            for m in annotated {
+                let text = m.annotation.as_deref().unwrap_or_default();
+                lines.push(format!("    {}: {text}", m.path));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// `pack inspect --member <PATH> --json`: one member with its pack.
+    pub fn member_to_json(&self, member: &InspectMember) -> String {
+        let mut detail = json!({
+            "version": self.version,
+            "pack_id": self.pack_id,
+            "member": member,
+        });
+        if let Some(deprecation) = &self.deprecation {
+            detail["deprecation"] = json!(deprecation);
+        }
+        serde_json::to_string_pretty(&detail).expect("inspect report serialization cannot fail")
+    }
+
+    /// `pack inspect --member <PATH>`: every recorded field of one member.
+    pub fn member_to_human(&self, member: &InspectMember) -> String {
+        let mut lines = vec![format!("pack inspect: {}", self.pack_id)];
+        lines.extend(self.deprecation_line());
+        lines.extend([
+            format!("  member: {}", member.path),
+            format!("  type: {}", member.member_type),
+            format!(
+                "  artifact_version: {}",
+                member.artifact_version.as_deref().unwrap_or("-")
+            ),
+            format!("  size_bytes: {}", member.size()),
+            format!("  bytes_hash: {}", member.bytes_hash),
+        ]);
+        if let Some(content_type) = &member.content_type {
+            lines.push(format!("  content_type: {content_type}"));
+        }
+        if let Some(charset) = &member.charset {
+            lines.push(format!("  charset: {charset}"));
+        }
+        if let Some(visibility) = &member.visibility {
+            lines.push(format!("  visibility: {visibility}"));
+        }
+        if let Some(annotation) = &member.annotation {
+            lines.push(format!("  annotation: {annotation}"));
+        }
+        if let Some(origin) = &member.origin {
+            lines.push(format!("  origin: {} {}", origin.registry, origin.digest));
+            if let Some(media_type) = &origin.media_type {
+                lines.push(format!("  origin_media_type: {media_type}"));
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn deprecation_line(&self) -> Option<String> {
+        self.deprecation
+            .as_ref()
+            .map(|d| format!("  DEPRECATED: {}", d.describe(&self.pack_id, &[])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(path: &str, size_bytes: Option<u64>) -> InspectMember {
+        InspectMember {
+            path: path.to_string(),
+            member_type: "other".to_string(),
+            artifact_version: None,
+            size_bytes,
+            bytes_hash: format!("sha256:{}", "ab".repeat(32)),
+            content_type: None,
+            charset: None,
+            visibility: None,
+            origin: None,
+            executable: None,
+            annotation: None,
+        }
+    }
+
+    fn report(members: Vec<InspectMember>) -> InspectReport {
+        InspectReport {
+            version: INSPECT_REPORT_VERSION.to_string(),
+            pack_id: "sha256:abc".to_string(),
+            created: "2026-01-15T00:00:00Z".to_string(),
+            note: None,
+            tool_version: "0.2.3".to_string(),
+            member_count: members.len(),
+            members,
+            deprecation: None,
+        }
+    }
+
+    #[test]
+    fn human_table_aligns_columns() {
+        let mut members = vec![member("a.json", Some(7)), member("logs/run.log", None)];
+        members[0].content_type = Some("application/json".to_string());
+        members[1].annotation = Some("the flaky run".to_string());
+        let report = report(members);
+        let human = report.to_human(SortOrder::Bytewise);
+        assert!(human.contains("    PATH          TYPE   VERSION  CONTENT           SIZE  HASH"));
+        assert!(human
+            .contains("    a.json        other  -        application/json     7  abababababab"));
+        assert!(human
+            .contains("    logs/run.log  other  -        -                    -  abababababab"));
+        assert!(human.ends_with("  annotations:\n    logs/run.log: the flaky run"));
+        assert!(!human.contains("note:"));
+    }
+
+    #[test]
+    fn sort_orders_the_human_table_but_not_json() {
+        let mut members = vec![member("Z.json", Some(1)), member("a.json", Some(2))];
+        members[0].annotation = Some("upper".to_string());
+        members[1].annotation = Some("lower".to_string());
+        let report = report(members);
+        let row = |human: &str, path: &str| {
+            human
+                .lines()
+                .position(|line| line.starts_with(&format!("    {path} ")))
+                .unwrap()
+        };
+
+        let bytewise = report.to_human(SortOrder::Bytewise);
+        assert!(row(&bytewise, "Z.json") < row(&bytewise, "a.json"));
+        assert!(bytewise.ends_with("    Z.json: upper\n    a.json: lower"));
+
+        let display = report.to_human(SortOrder::Display);
+        assert!(row(&display, "a.json") < row(&display, "Z.json"));
+        assert!(display.ends_with("    a.json: lower\n    Z.json: upper"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(
+            json["members"][0]["path"], "Z.json",
+            "JSON keeps manifest order"
+        );
+    }
+}
@@ -1,29 +1,100 @@
+//! Seal artifacts into immutable, self-verifiable evidence packs.
+//!
+//! [`api`] is the supported library interface. The other modules back the
+//! `pack` binary and may change between releases.
+
+// The `json!` literal in `schema::pack_schema` outgrows the default limit.
+#![recursion_limit = "256"]
+
+pub mod api;
+#[doc(hidden)]
+pub mod attest;
+#[doc(hidden)]
+pub mod canonical;
+#[doc(hidden)]
 pub mod cli;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
 pub mod detect;
+#[doc(hidden)]
 pub mod diff;
+#[doc(hidden)]
+pub mod encrypt;
+#[doc(hidden)]
+pub mod events;
+#[doc(hidden)]
+pub mod extract;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[doc(hidden)]
+pub mod inspect;
+#[doc(hidden)]
+pub mod lineage;
+#[doc(hidden)]
+pub mod lockfile;
+#[doc(hidden)]
+pub mod logging;
+#[doc(hidden)]
 pub mod network;
+#[doc(hidden)]
 pub mod operator;
+#[doc(hidden)]
+pub mod outcomes;
+#[doc(hidden)]
+pub mod quickstart;
+#[doc(hidden)]
 pub mod refusal;
+#[doc(hidden)]
 pub mod schema;
+#[doc(hidden)]
 pub mod seal;
+#[doc(hidden)]
+pub mod selftest;
+#[doc(hidden)]
+pub mod sign;
+#[doc(hidden)]
+pub mod timestamp;
+#[doc(hidden)]
 pub mod verify;
+#[doc(hidden)]
+pub mod watch;
+#[doc(hidden)]
 pub mod witness;
 
-use clap::Parser;
-use cli::{Cli, Command, ExitCode, WitnessCommand};
+use clap::{CommandFactory, FromArgMatches};
+use cli::{Cli, Command, ConfigCommand, ExitCode, SchemaCommand, StoreCommand, WitnessCommand};
 use serde_json::{Map, Value};
+use std::ops::ControlFlow;
 use std::path::Path;
 
 /// Run the pack CLI and return an exit code.
+///
+/// A reader that closes stdout early (`pack verify --json | head -1`) is not a
+/// failure in itself: the command still finishes, including its witness
+/// record, and exits with its own code.
 pub fn run() -> u8 {
-    let cli = Cli::parse();
+    witness::mark_command_start();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    logging::init(cli.verbose, cli.log_json);
+    let command = matches.subcommand_name().map(str::to_string);
+    if let Some(command) = &command {
+        logging::command_started(command);
+    }
+    let code = run_cli(cli);
+    if let Some(command) = &command {
+        logging::command_finished(command, code);
+    }
+    code
+}
 
+fn run_cli(cli: Cli) -> u8 {
     // --describe short-circuits before input validation.
     if cli.describe {
         let op = operator::operator_json();
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&op).expect("operator json serialization cannot fail")
+        cli::print_line(
+            serde_json::to_string_pretty(&op).expect("operator json serialization cannot fail"),
         );
         return ExitCode::Success.into();
     }
@@ -31,9 +102,17 @@ pub fn run() -> u8 {
     // --schema short-circuits before input validation.
     if cli.schema {
         let s = schema::pack_schema();
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&s).expect("schema serialization cannot fail")
+        cli::print_line(
+            serde_json::to_string_pretty(&s).expect("schema serialization cannot fail"),
+        );
+        return ExitCode::Success.into();
+    }
+
+    if let Some(target) = cli.schema_for {
+        let s = schema::definition_schema(target.definition())
+            .expect("every --schema-for target names a schema definition");
+        cli::print_line(
+            serde_json::to_string_pretty(&s).expect("schema serialization cannot fail"),
         );
         return ExitCode::Success.into();
     }
@@ -43,114 +122,605 @@ pub fn run() -> u8 {
         return ExitCode::Refusal.into();
     };
 
+    // A config file that cannot be read must not be silently ignored.
+    if let Err(message) = config::check_config() {
+        eprintln!("pack: {message}");
+        return ExitCode::Refusal.into();
+    }
+
     let no_witness = cli.no_witness;
 
     match command {
         Command::Seal {
-            artifacts,
+            mut artifacts,
+            from_list,
+            null,
+            stdin_name,
             output,
             note,
-        } => match seal::command::execute_seal(&artifacts, output.as_deref(), note.clone()) {
-            Ok(result) => {
-                let output_text = format!(
-                    "PACK_CREATED {}\n{}",
-                    result.pack_id,
-                    result.output_dir.display()
-                );
-                if !no_witness {
-                    let mut params = Map::new();
-                    params.insert(
-                        "artifacts".to_string(),
-                        Value::Array(artifacts.iter().map(|path| path_value(path)).collect()),
-                    );
-                    if let Some(output_dir) = output.as_deref() {
-                        params.insert("output".to_string(), path_value(output_dir));
-                    }
-                    if let Some(note) = &note {
-                        params.insert("note".to_string(), Value::String(note.clone()));
+            json,
+            trust_hashes,
+            hash_algorithm,
+            hash_executable,
+            max_note_bytes,
+            allow_git,
+            max_remote_bytes,
+            allow_cross_host_redirects,
+            archive,
+            sign,
+            signing_key,
+            timestamp_url,
+            encrypt,
+            restrict,
+            annotate,
+            origins,
+            merge,
+            empty_dirs,
+            follow_symlinks,
+            skip_symlinks,
+            expand_archives,
+            member_timestamps,
+            provenance,
+            strict_types,
+            detect_rules,
+            resume,
+            reproducible,
+            max_members,
+            max_total_bytes,
+            progress,
+            transaction,
+        } => {
+            // --trust-hashes records SHA-256, so a configured default yields.
+            let hash_algorithm = hash_algorithm.unwrap_or_else(|| {
+                if trust_hashes.is_some() {
+                    cli::HashAlgorithm::Sha256
+                } else {
+                    config::hash_algorithm()
+                }
+            });
+            let symlinks = if follow_symlinks {
+                seal::collect::SymlinkPolicy::Follow
+            } else if skip_symlinks {
+                seal::collect::SymlinkPolicy::Skip
+            } else {
+                seal::collect::SymlinkPolicy::Refuse
+            };
+            let options = seal::command::SealOptions {
+                trust_hashes: trust_hashes.clone(),
+                hash_algorithm,
+                hash_executable,
+                max_note_bytes,
+                allow_git,
+                remote: seal::remote::RemoteOptions {
+                    max_bytes: max_remote_bytes.unwrap_or(seal::remote::DEFAULT_MAX_REMOTE_BYTES),
+                    allow_cross_host_redirects,
+                },
+                archive,
+                sign,
+                signing_key: signing_key.clone(),
+                timestamp_url: timestamp_url.clone(),
+                encrypt_to: encrypt.clone(),
+                restrict: restrict.clone(),
+                origins: origins.clone(),
+                annotate: annotate.clone(),
+                empty_dirs,
+                symlinks,
+                expand_archives,
+                member_timestamps,
+                provenance,
+                strict_types,
+                detect_rules: detect_rules.clone(),
+                resume: resume.clone(),
+                reproducible,
+                source_date_epoch: std::env::var(seal::command::SOURCE_DATE_EPOCH_ENV).ok(),
+                merge: merge.clone(),
+                max_members,
+                max_total_bytes,
+                events: if progress {
+                    progress_events(logging::event_bus())
+                } else {
+                    interactive_progress(logging::event_bus(), json)
+                },
+                stdin_name: stdin_name.clone(),
+                output_root: config::output_root(),
+            };
+            if let Some(plan) = &transaction {
+                return dispatch_seal_transaction(plan, &options, json, no_witness);
+            }
+            if let Some(list) = &from_list {
+                let listed = seal::list::read_artifact_list(list, null).and_then(|listed| {
+                    artifacts.extend(listed);
+                    seal::stdin::check_stdin_unshared(list, &artifacts)
+                });
+                if let Err(envelope) = listed {
+                    let output_text = envelope.to_json();
+                    if !no_witness {
+                        let mut params = Map::new();
+                        params.insert("from_list".to_string(), path_value(list));
+                        let record = witness::WitnessRecord::new(
+                            "seal",
+                            vec![],
+                            "REFUSAL",
+                            2,
+                            params,
+                            &stdout_bytes(&output_text),
+                            None,
+                        );
+                        append_witness_warning(&record);
                     }
-                    params.insert(
-                        "member_count".to_string(),
-                        Value::from(result.member_count as u64),
-                    );
-                    params.insert("output_dir".to_string(), path_value(&result.output_dir));
-                    let record = witness::WitnessRecord::new(
-                        "seal",
-                        result.witness_inputs.clone(),
-                        "PACK_CREATED",
-                        0,
-                        params,
-                        &stdout_bytes(&output_text),
-                        Some(result.pack_id.clone()),
-                    );
-                    append_witness_warning(&record);
+                    cli::print_line(output_text);
+                    return ExitCode::Refusal.into();
                 }
-                println!("{output_text}");
-                ExitCode::Success.into()
             }
-            Err(envelope) => {
-                let output_text = envelope.to_json();
-                if !no_witness {
-                    let mut params = Map::new();
-                    params.insert(
-                        "artifacts".to_string(),
-                        Value::Array(artifacts.iter().map(|path| path_value(path)).collect()),
-                    );
-                    if let Some(output_dir) = output.as_deref() {
-                        params.insert("output".to_string(), path_value(output_dir));
+            match seal::command::execute_seal_with_options(
+                &artifacts,
+                output.as_deref(),
+                note.clone(),
+                &options,
+            ) {
+                Ok(result) => {
+                    let report = seal::report::SealReport::new(&result);
+                    let output_text = if json {
+                        report.to_json()
+                    } else {
+                        report.to_human()
+                    };
+                    for excluded in &result.excluded_git {
+                        eprintln!(
+                            "pack: excluded git metadata: {} (use --allow-git to include)",
+                            excluded.display()
+                        );
                     }
-                    if let Some(note) = &note {
-                        params.insert("note".to_string(), Value::String(note.clone()));
+                    for skipped in &result.skipped_symlinks {
+                        eprintln!("pack: warning: skipped symlink: {}", skipped.display());
                     }
-                    let inputs = artifacts.iter().map(|path| input_from_path(path)).collect();
-                    let record = witness::WitnessRecord::new(
-                        "seal",
-                        inputs,
-                        "REFUSAL",
-                        2,
-                        params,
-                        &stdout_bytes(&output_text),
-                        None,
+                    if let Some(work_tree) = &result.output_work_tree {
+                        eprintln!(
+                        "pack: warning: output is inside git work tree {}; the pack may be committed",
+                        work_tree.display()
                     );
-                    append_witness_warning(&record);
+                    }
+                    if !no_witness {
+                        let mut params = Map::new();
+                        params.insert(
+                            "artifacts".to_string(),
+                            Value::Array(artifacts.iter().map(|path| path_value(path)).collect()),
+                        );
+                        if let Some(list) = &from_list {
+                            params.insert("from_list".to_string(), path_value(list));
+                        }
+                        if let Some(name) = &stdin_name {
+                            params.insert("stdin_name".to_string(), Value::String(name.clone()));
+                        }
+                        if let Some(output_dir) = output.as_deref() {
+                            params.insert("output".to_string(), path_value(output_dir));
+                        }
+                        if let Some(note) = &note {
+                            params.insert("note".to_string(), Value::String(note.clone()));
+                        }
+                        params.insert(
+                            "member_count".to_string(),
+                            Value::from(result.member_count as u64),
+                        );
+                        params.insert("output_dir".to_string(), path_value(&result.output_dir));
+                        if allow_git {
+                            params.insert("allow_git".to_string(), Value::Bool(true));
+                        }
+                        if archive {
+                            params.insert("archive".to_string(), Value::Bool(true));
+                        }
+                        if json {
+                            params.insert("json".to_string(), Value::Bool(true));
+                        }
+                        if !restrict.is_empty() {
+                            params.insert(
+                                "restrict".to_string(),
+                                Value::Array(
+                                    restrict
+                                        .iter()
+                                        .map(|rule| {
+                                            serde_json::json!({
+                                                "pattern": rule.pattern,
+                                                "level": rule.level,
+                                            })
+                                        })
+                                        .collect(),
+                                ),
+                            );
+                        }
+                        if let Some(path) = &origins {
+                            params.insert("origins".to_string(), path_value(path));
+                        }
+                        if let (Some(path), Some(parent)) = (&merge, &result.parent_pack_id) {
+                            params.insert(
+                                "merge".to_string(),
+                                serde_json::json!({
+                                    "pack": path_value(path),
+                                    "parent_pack_id": parent,
+                                }),
+                            );
+                        }
+                        if !annotate.is_empty() {
+                            params.insert(
+                                "annotate".to_string(),
+                                Value::Array(
+                                    annotate
+                                        .iter()
+                                        .map(|annotation| Value::String(annotation.path.clone()))
+                                        .collect(),
+                                ),
+                            );
+                        }
+                        if member_timestamps {
+                            params.insert("member_timestamps".to_string(), Value::Bool(true));
+                        }
+                        if provenance {
+                            params.insert("provenance".to_string(), Value::Bool(true));
+                        }
+                        if strict_types {
+                            params.insert("strict_types".to_string(), Value::Bool(true));
+                        }
+                        if let Some(path) = &detect_rules {
+                            params.insert("detect_rules".to_string(), path_value(path));
+                        }
+                        if let Some(dir) = &resume {
+                            params.insert("resume".to_string(), path_value(dir));
+                        }
+                        if reproducible {
+                            params.insert("reproducible".to_string(), Value::Bool(true));
+                        }
+                        if let Some(limit) = max_members {
+                            params.insert("max_members".to_string(), Value::from(limit as u64));
+                        }
+                        if let Some(limit) = max_total_bytes {
+                            params.insert("max_total_bytes".to_string(), Value::from(limit));
+                        }
+                        if expand_archives {
+                            params.insert("expand_archives".to_string(), Value::Bool(true));
+                        }
+                        if hash_executable {
+                            params.insert("hash_executable".to_string(), Value::Bool(true));
+                        }
+                        if hash_algorithm != cli::HashAlgorithm::Sha256 {
+                            params.insert(
+                                "hash_algorithm".to_string(),
+                                Value::String(hash_algorithm.as_str().to_string()),
+                            );
+                        }
+                        if empty_dirs != cli::EmptyDirs::Drop {
+                            params.insert(
+                                "empty_dirs".to_string(),
+                                Value::String(empty_dirs.as_str().to_string()),
+                            );
+                        }
+                        if symlinks != seal::collect::SymlinkPolicy::Refuse {
+                            params.insert(
+                                "symlinks".to_string(),
+                                Value::String(symlinks.as_str().to_string()),
+                            );
+                        }
+                        if let Some(signer) = &result.signer {
+                            params.insert(
+                                "signature".to_string(),
+                                serde_json::json!({
+                                    "algorithm": "ed25519",
+                                    "public_key": signer,
+                                }),
+                            );
+                        }
+                        if let Some((tsa_url, gen_time)) = &result.timestamp {
+                            params.insert(
+                                "timestamp".to_string(),
+                                serde_json::json!({
+                                    "tsa_url": tsa_url,
+                                    "gen_time": gen_time,
+                                }),
+                            );
+                        }
+                        if !result.remote.is_empty() {
+                            params.insert(
+                                "remote".to_string(),
+                                Value::Array(
+                                    result
+                                        .remote
+                                        .iter()
+                                        .map(|r| {
+                                            serde_json::json!({
+                                                "url": r.url,
+                                                "final_url": r.final_url,
+                                                "bytes": r.bytes,
+                                            })
+                                        })
+                                        .collect(),
+                                ),
+                            );
+                        }
+                        if !result.excluded_git.is_empty() || result.output_work_tree.is_some() {
+                            params.insert(
+                                "git".to_string(),
+                                serde_json::json!({
+                                    "excluded": result
+                                        .excluded_git
+                                        .iter()
+                                        .map(|path| path_value(path))
+                                        .collect::<Vec<_>>(),
+                                    "output_work_tree": result
+                                        .output_work_tree
+                                        .as_deref()
+                                        .map(path_value),
+                                }),
+                            );
+                        }
+                        if let (Some(path), Some(trust)) = (&trust_hashes, result.trust) {
+                            params.insert(
+                                "trust_hashes".to_string(),
+                                serde_json::json!({
+                                    "file": path_value(path),
+                                    "mode": seal::manifest::TrustHashesMode::Sampled.as_str(),
+                                    "trusted": trust.trusted,
+                                    "sampled": trust.sampled,
+                                }),
+                            );
+                        }
+                        let record = witness::WitnessRecord::new(
+                            "seal",
+                            result.witness_inputs.clone(),
+                            "PACK_CREATED",
+                            0,
+                            params,
+                            &stdout_bytes(&output_text),
+                            Some(result.pack_id.clone()),
+                        )
+                        .with_pack_size(
+                            result.member_count,
+                            result
+                                .witness_inputs
+                                .iter()
+                                .filter_map(|input| input.bytes)
+                                .sum(),
+                        );
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Success.into()
+                }
+                Err(envelope) => {
+                    let output_text = envelope.to_json();
+                    if !no_witness {
+                        let mut params = Map::new();
+                        params.insert(
+                            "artifacts".to_string(),
+                            Value::Array(artifacts.iter().map(|path| path_value(path)).collect()),
+                        );
+                        if let Some(list) = &from_list {
+                            params.insert("from_list".to_string(), path_value(list));
+                        }
+                        if let Some(name) = &stdin_name {
+                            params.insert("stdin_name".to_string(), Value::String(name.clone()));
+                        }
+                        if let Some(output_dir) = output.as_deref() {
+                            params.insert("output".to_string(), path_value(output_dir));
+                        }
+                        if let Some(note) = &note {
+                            params.insert("note".to_string(), Value::String(note.clone()));
+                        }
+                        if let Some(path) = &trust_hashes {
+                            params.insert(
+                                "trust_hashes".to_string(),
+                                serde_json::json!({ "file": path_value(path) }),
+                            );
+                        }
+                        if let Some(limit) = max_members {
+                            params.insert("max_members".to_string(), Value::from(limit as u64));
+                        }
+                        if let Some(limit) = max_total_bytes {
+                            params.insert("max_total_bytes".to_string(), Value::from(limit));
+                        }
+                        if expand_archives {
+                            params.insert("expand_archives".to_string(), Value::Bool(true));
+                        }
+                        let inputs = artifacts.iter().map(|path| input_from_path(path)).collect();
+                        let record = witness::WitnessRecord::new(
+                            "seal",
+                            inputs,
+                            "REFUSAL",
+                            2,
+                            params,
+                            &stdout_bytes(&output_text),
+                            None,
+                        );
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Refusal.into()
                 }
-                println!("{output_text}");
-                ExitCode::Refusal.into()
             }
-        },
-        Command::Verify { pack_dir, json } => {
-            let (output, exit_code) = verify::execute_verify(&pack_dir, json);
+        }
+        Command::Verify {
+            pack_dir,
+            all,
+            jobs,
+            json,
+            json_lines,
+            member_root,
+            against,
+            max_note_bytes,
+            ignore_findings,
+            suppressions,
+            require_signature,
+            public_key,
+            deep,
+            validate_registry,
+            empty_pack,
+            resolve_nested,
+            timings,
+            quick,
+            strict,
+            check_timestamp,
+            tsa_cert,
+            decrypt_key,
+            trust_policy,
+            policy,
+            schema_dir,
+            repair_report,
+        } => {
+            let options = verify::VerifyOptions {
+                member_root: member_root.clone(),
+                against: against.clone(),
+                max_note_bytes,
+                ignore_findings: ignore_findings
+                    .iter()
+                    .map(|raw| verify::Suppression::from_flag(raw))
+                    .collect(),
+                suppressions_file: suppressions.clone(),
+                require_signature,
+                public_key: public_key.clone(),
+                deep,
+                validate_registry,
+                store: network::store::filesystem_store_root(None, config::env_or_config),
+                empty_pack,
+                resolve_nested,
+                timings,
+                trust_policy: trust_policy.clone(),
+                policy: policy.clone(),
+                events: if all {
+                    logging::event_bus()
+                } else {
+                    interactive_progress(logging::event_bus(), json || json_lines)
+                },
+                quick,
+                strict,
+                check_timestamp,
+                tsa_cert: tsa_cert.clone(),
+                decrypt_key: decrypt_key.clone(),
+                schema_dir: schema_dir.clone(),
+            };
+            // --json-lines prints findings while verify runs; the witness
+            // record hashes them together with the summary line.
+            let streamed = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let (output, exit_code) = if all {
+                verify::execute_verify_all(&pack_dir, json, &options, jobs.into())
+            } else if json_lines {
+                let sink = std::sync::Arc::clone(&streamed);
+                verify::execute_verify_json_lines(&pack_dir, &options, move |line| {
+                    sink.lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .extend(stdout_bytes(&line));
+                    cli::print_line(line);
+                })
+            } else if let Some(path) = &repair_report {
+                verify::execute_verify_with_repair_report(&pack_dir, json, &options, path)
+            } else {
+                verify::execute_verify_with_options(&pack_dir, json, &options)
+            };
             if !no_witness {
-                let outcome = match exit_code {
-                    0 => "OK",
-                    1 => "INVALID",
-                    _ => "REFUSAL",
-                };
+                let outcome = outcomes::outcome_for("verify", exit_code);
                 let mut params = Map::new();
                 params.insert("pack_dir".to_string(), path_value(&pack_dir));
                 params.insert("json".to_string(), Value::Bool(json));
+                if json_lines {
+                    params.insert("json_lines".to_string(), Value::Bool(true));
+                }
+                if all {
+                    params.insert("all".to_string(), Value::Bool(true));
+                    params.insert("jobs".to_string(), Value::from(jobs));
+                }
+                if let Some(member_root) = &member_root {
+                    params.insert("member_root".to_string(), path_value(member_root));
+                }
+                if let Some(against) = &against {
+                    params.insert("against".to_string(), path_value(against));
+                }
+                if let Some(limit) = max_note_bytes {
+                    params.insert("max_note_bytes".to_string(), Value::from(limit as u64));
+                }
+                if !ignore_findings.is_empty() {
+                    params.insert(
+                        "ignore_findings".to_string(),
+                        Value::Array(ignore_findings.into_iter().map(Value::String).collect()),
+                    );
+                }
+                if let Some(path) = &suppressions {
+                    params.insert("suppressions".to_string(), path_value(path));
+                }
+                if require_signature {
+                    params.insert("require_signature".to_string(), Value::Bool(true));
+                }
+                if let Some(path) = &public_key {
+                    params.insert("public_key".to_string(), path_value(path));
+                }
+                if let Some(path) = &trust_policy {
+                    params.insert("trust_policy".to_string(), path_value(path));
+                }
+                if let Some(path) = &policy {
+                    params.insert("policy".to_string(), path_value(path));
+                }
+                if let Some(path) = &tsa_cert {
+                    params.insert("tsa_cert".to_string(), path_value(path));
+                }
+                if let Some(path) = &schema_dir {
+                    params.insert("schema_dir".to_string(), path_value(path));
+                }
+                if deep {
+                    params.insert("deep".to_string(), Value::Bool(true));
+                }
+                if validate_registry {
+                    params.insert("validate_registry".to_string(), Value::Bool(true));
+                }
+                if resolve_nested {
+                    params.insert("resolve_nested".to_string(), Value::Bool(true));
+                }
+                if timings {
+                    params.insert("timings".to_string(), Value::Bool(true));
+                }
+                if quick {
+                    params.insert("quick".to_string(), Value::Bool(true));
+                }
+                if let Some(path) = &repair_report {
+                    params.insert("repair_report".to_string(), path_value(path));
+                }
+                if empty_pack != cli::EmptyPackPolicy::Warn {
+                    params.insert(
+                        "empty_pack".to_string(),
+                        Value::String(empty_pack.as_str().to_string()),
+                    );
+                }
+                let mut stdout =
+                    std::mem::take(&mut *streamed.lock().unwrap_or_else(|e| e.into_inner()));
+                stdout.extend(stdout_bytes(&output));
                 let record = witness::WitnessRecord::new(
                     "verify",
                     vec![input_from_path(&pack_dir)],
                     outcome,
                     exit_code,
                     params,
-                    &stdout_bytes(&output),
-                    extract_pack_id(&output, json),
-                );
+                    &stdout,
+                    extract_pack_id(&output, json || json_lines),
+                )
+                .with_pack_dir(&pack_dir);
                 append_witness_warning(&record);
             }
-            println!("{output}");
+            cli::print_line(output);
             exit_code
         }
-        Command::Diff { a, b, json } => {
-            let (output, exit_code) = diff::execute_diff(&a, &b, json);
+        Command::Diff {
+            a,
+            b,
+            remote,
+            json,
+            sort,
+        } => {
+            let options = diff::DiffOptions { sort };
+            if !remote.is_empty() {
+                return dispatch_remote_diff(&remote, json, &options, no_witness);
+            }
+            let (a, b) = (
+                a.expect("clap requires A without --remote"),
+                b.expect("clap requires B without --remote"),
+            );
+            let (output, exit_code) = diff::execute_diff_with_options(&a, &b, json, &options);
             if !no_witness {
-                let outcome = match exit_code {
-                    0 => "NO_CHANGES",
-                    1 => "CHANGES",
-                    _ => "REFUSAL",
-                };
+                let outcome = outcomes::outcome_for("diff", exit_code);
                 let mut params = Map::new();
                 params.insert("a".to_string(), path_value(&a));
                 params.insert("b".to_string(), path_value(&b));
@@ -166,113 +736,885 @@ pub fn run() -> u8 {
                 );
                 append_witness_warning(&record);
             }
-            println!("{output}");
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Inspect {
+            pack_dir,
+            member,
+            json,
+            sort,
+        } => {
+            let store = network::store::filesystem_store_root(None, config::env_or_config);
+            let (output, exit_code) = inspect::execute_inspect(
+                &pack_dir,
+                member.as_deref(),
+                json,
+                sort,
+                store.as_deref(),
+            );
+            if !no_witness {
+                let outcome = outcomes::outcome_for("inspect", exit_code);
+                let mut params = Map::new();
+                params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                if let Some(member) = &member {
+                    params.insert("member".to_string(), Value::String(member.clone()));
+                }
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "inspect",
+                    vec![input_from_path(&pack_dir)],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    None,
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Extract {
+            pack_dir,
+            member,
+            out,
+            decrypt_key,
+            include_restricted,
+            verify_only_selected,
+            seed,
+            sign,
+            signing_key,
+            json,
+        } => {
+            let options = extract::ExtractOptions {
+                members: member.clone(),
+                decrypt_key: decrypt_key.clone(),
+                include_restricted,
+            };
+            let (output, exit_code) = match (verify_only_selected, &out) {
+                (Some(count), _) => {
+                    let spot_check = extract::SpotCheckOptions {
+                        count: count as usize,
+                        seed,
+                        sign,
+                        signing_key,
+                    };
+                    extract::execute_spot_check(&pack_dir, json, &options, &spot_check)
+                }
+                (None, Some(out)) => extract::execute_extract(&pack_dir, out, json, &options),
+                (None, None) => unreachable!("clap requires --out without --verify-only-selected"),
+            };
+            if !no_witness {
+                // Exit 0 is EXTRACTED unless the run was a spot check.
+                let outcome = match (verify_only_selected, exit_code) {
+                    (Some(_), 0) => "OK",
+                    _ => outcomes::outcome_for("extract", exit_code),
+                };
+                let mut params = Map::new();
+                params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                params.insert(
+                    "member".to_string(),
+                    Value::Array(member.into_iter().map(Value::String).collect()),
+                );
+                if let Some(out) = &out {
+                    params.insert("out".to_string(), path_value(out));
+                }
+                if let Some(count) = verify_only_selected {
+                    params.insert("verify_only_selected".to_string(), Value::from(count));
+                    params.insert("seed".to_string(), seed.map_or(Value::Null, Value::from));
+                }
+                if sign {
+                    params.insert("sign".to_string(), Value::Bool(true));
+                }
+                params.insert(
+                    "decrypt_key".to_string(),
+                    decrypt_key.as_deref().map_or(Value::Null, path_value),
+                );
+                params.insert(
+                    "include_restricted".to_string(),
+                    Value::Bool(include_restricted),
+                );
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "extract",
+                    vec![input_from_path(&pack_dir)],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    extract_pack_id(&output, json),
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Lineage {
+            pack_dir,
+            store,
+            json,
+        } => {
+            let (output, exit_code) = lineage::execute_lineage(&pack_dir, store.as_deref(), json);
+            if !no_witness {
+                let outcome = outcomes::outcome_for("lineage", exit_code);
+                let mut params = Map::new();
+                params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                if let Some(store) = &store {
+                    params.insert("store".to_string(), Value::String(store.clone()));
+                }
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "lineage",
+                    vec![input_from_path(&pack_dir)],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    extract_pack_id(&output, json),
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Id { pack_dir, json } => {
+            let (output, exit_code) = verify::execute_id(&pack_dir, json);
+            if !no_witness {
+                let outcome = outcomes::outcome_for("id", exit_code);
+                let mut params = Map::new();
+                params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "id",
+                    vec![input_from_path(&pack_dir)],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    extract_pack_id(&output, json),
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output);
             exit_code
         }
-        Command::Push { pack_dir } => match network::push::execute_push(&pack_dir) {
-            Ok(result) => {
-                let output_text = format!("PUBLISHED {}", result.pack_id);
+        Command::Push { pack_dir, to } => {
+            match network::push::execute_push_to(&pack_dir, to.as_deref(), &logging::event_bus()) {
+                Ok(result) => {
+                    let outcome = result.outcome.as_str();
+                    let output_text = format!("{outcome} {}", result.pack_id);
+                    if !no_witness {
+                        let mut params = Map::new();
+                        params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                        params.insert("pack_id".to_string(), Value::String(result.pack_id.clone()));
+                        if let Some(to) = &to {
+                            params.insert("to".to_string(), Value::String(to.clone()));
+                        }
+                        let record = witness::WitnessRecord::new(
+                            "push",
+                            vec![input_from_path(&pack_dir)],
+                            outcome,
+                            0,
+                            params,
+                            &stdout_bytes(&output_text),
+                            Some(result.pack_id.clone()),
+                        )
+                        .with_pack_dir(&pack_dir);
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Success.into()
+                }
+                Err(envelope) => {
+                    let output_text = envelope.to_json();
+                    if !no_witness {
+                        let mut params = Map::new();
+                        params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                        if let Some(to) = &to {
+                            params.insert("to".to_string(), Value::String(to.clone()));
+                        }
+                        let record = witness::WitnessRecord::new(
+                            "push",
+                            vec![input_from_path(&pack_dir)],
+                            "REFUSAL",
+                            2,
+                            params,
+                            &stdout_bytes(&output_text),
+                            None,
+                        );
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Refusal.into()
+                }
+            }
+        }
+        Command::Pull {
+            pack_id,
+            series,
+            as_of,
+            out_dir,
+            from,
+            trust_policy,
+        } => {
+            let pulled = match (&pack_id, &series, &as_of) {
+                (Some(pack_id), _, _) => network::pull::execute_pull(
+                    pack_id,
+                    &out_dir,
+                    from.as_deref(),
+                    trust_policy.as_deref(),
+                ),
+                (None, Some(series), Some(as_of)) => network::pull::execute_pull_as_of(
+                    series,
+                    as_of,
+                    &out_dir,
+                    from.as_deref(),
+                    trust_policy.as_deref(),
+                ),
+                _ => unreachable!("clap requires PACK_ID or --series with --as-of"),
+            };
+            let mut extra_params = Map::new();
+            if let (Some(series), Some(as_of)) = (&series, &as_of) {
+                extra_params.insert("series".to_string(), Value::String(series.clone()));
+                extra_params.insert("as_of".to_string(), Value::String(as_of.clone()));
+            }
+            if let Some(from) = &from {
+                extra_params.insert("from".to_string(), Value::String(from.clone()));
+            }
+            if let Some(path) = &trust_policy {
+                extra_params.insert("trust_policy".to_string(), path_value(path));
+            }
+            match pulled {
+                Ok(result) => {
+                    let output_text =
+                        format!("FETCHED {}\n{}", result.pack_id, result.out_dir.display());
+                    if let Some(deprecation) = &result.deprecation {
+                        eprintln!(
+                            "pack: warning: {} is DEPRECATED: {}",
+                            result.pack_id,
+                            deprecation.describe(&result.pack_id, &result.trusted_signers)
+                        );
+                    }
+                    if !no_witness {
+                        let mut params = extra_params;
+                        params.insert("pack_id".to_string(), Value::String(result.pack_id.clone()));
+                        params.insert("out_dir".to_string(), path_value(&result.out_dir));
+                        let record = witness::WitnessRecord::new(
+                            "pull",
+                            vec![],
+                            "FETCHED",
+                            0,
+                            params,
+                            &stdout_bytes(&output_text),
+                            Some(result.pack_id.clone()),
+                        )
+                        .with_pack_dir(&result.out_dir);
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Success.into()
+                }
+                Err(envelope) => {
+                    let output_text = envelope.to_json();
+                    if !no_witness {
+                        let mut params = extra_params;
+                        if let Some(pack_id) = &pack_id {
+                            params.insert("pack_id".to_string(), Value::String(pack_id.clone()));
+                        }
+                        params.insert("out_dir".to_string(), path_value(&out_dir));
+                        let record = witness::WitnessRecord::new(
+                            "pull",
+                            vec![],
+                            "REFUSAL",
+                            2,
+                            params,
+                            &stdout_bytes(&output_text),
+                            pack_id.clone(),
+                        );
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Refusal.into()
+                }
+            }
+        }
+        Command::Deprecate {
+            pack_id,
+            reason,
+            store,
+            unsigned,
+            signing_key,
+        } => {
+            let deprecated = network::deprecate::execute_deprecate(
+                &pack_id,
+                &reason,
+                store.as_deref(),
+                unsigned,
+                signing_key.as_deref(),
+            );
+            let (output_text, outcome, exit_code) = match &deprecated {
+                Ok(result) => (
+                    format!("DEPRECATED {}", result.pack_id),
+                    "DEPRECATED",
+                    ExitCode::Success,
+                ),
+                Err(envelope) => (envelope.to_json(), "REFUSAL", ExitCode::Refusal),
+            };
+            if !no_witness {
+                let mut params = Map::new();
+                params.insert("pack_id".to_string(), Value::String(pack_id.clone()));
+                params.insert("reason".to_string(), Value::String(reason.clone()));
+                if let Some(store) = &store {
+                    params.insert("store".to_string(), Value::String(store.clone()));
+                }
+                params.insert("unsigned".to_string(), Value::Bool(unsigned));
+                let record = witness::WitnessRecord::new(
+                    "deprecate",
+                    vec![],
+                    outcome,
+                    exit_code.into(),
+                    params,
+                    &stdout_bytes(&output_text),
+                    deprecated.is_ok().then(|| pack_id.clone()),
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output_text);
+            exit_code.into()
+        }
+        Command::Watch {
+            store_root,
+            on_invalid,
+            once,
+            interval,
+        } => {
+            let options = watch::WatchOptions {
+                on_invalid: on_invalid.clone(),
+                once,
+                interval: interval.map(std::time::Duration::from_secs),
+            };
+            let watched = watch::execute_watch(&store_root, &options, |event| {
+                let line = format!(
+                    "{} {} {}",
+                    event.outcome,
+                    event.pack_id.as_deref().unwrap_or("-"),
+                    event.pack_dir.display()
+                );
+                cli::print_line(line);
                 if !no_witness {
                     let mut params = Map::new();
-                    params.insert("pack_dir".to_string(), path_value(&pack_dir));
-                    params.insert("pack_id".to_string(), Value::String(result.pack_id.clone()));
+                    params.insert("store_root".to_string(), path_value(&store_root));
+                    params.insert("pack_dir".to_string(), path_value(event.pack_dir));
+                    if let Some(hook) = &on_invalid {
+                        params.insert("on_invalid".to_string(), Value::String(hook.clone()));
+                    }
                     let record = witness::WitnessRecord::new(
-                        "push",
-                        vec![input_from_path(&pack_dir)],
-                        "PUBLISHED",
-                        0,
+                        "watch",
+                        vec![input_from_path(event.pack_dir)],
+                        event.outcome.to_string(),
+                        event.exit_code,
                         params,
-                        &stdout_bytes(&output_text),
-                        Some(result.pack_id.clone()),
+                        &stdout_bytes(event.report),
+                        event.pack_id.clone(),
                     );
                     append_witness_warning(&record);
                 }
-                println!("{output_text}");
-                ExitCode::Success.into()
+                if cli::stdout_closed() {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+            match watched {
+                Ok(summary) if summary.failed > 0 => ExitCode::Invalid.into(),
+                Ok(_) => ExitCode::Success.into(),
+                Err(envelope) => {
+                    let output_text = envelope.to_json();
+                    if !no_witness {
+                        let mut params = Map::new();
+                        params.insert("store_root".to_string(), path_value(&store_root));
+                        let record = witness::WitnessRecord::new(
+                            "watch",
+                            vec![input_from_path(&store_root)],
+                            "REFUSAL",
+                            2,
+                            params,
+                            &stdout_bytes(&output_text),
+                            None,
+                        );
+                        append_witness_warning(&record);
+                    }
+                    cli::print_line(output_text);
+                    ExitCode::Refusal.into()
+                }
             }
-            Err(envelope) => {
-                let output_text = envelope.to_json();
-                if !no_witness {
-                    let mut params = Map::new();
-                    params.insert("pack_dir".to_string(), path_value(&pack_dir));
-                    let record = witness::WitnessRecord::new(
-                        "push",
-                        vec![input_from_path(&pack_dir)],
-                        "REFUSAL",
-                        2,
-                        params,
-                        &stdout_bytes(&output_text),
+        }
+        Command::Gc {
+            store,
+            keep_last,
+            keep_since,
+            retain,
+            dry_run,
+            json,
+        } => {
+            let root =
+                network::store::filesystem_store_root(store.as_deref(), config::env_or_config);
+            let options = network::gc::GcOptions {
+                keep_last,
+                keep_since,
+                retain,
+                dry_run,
+            };
+            let (output, exit_code) = match &root {
+                Some(root) => network::gc::execute_gc(root, &options, json),
+                None => (
+                    refusal::RefusalEnvelope::new(
+                        refusal::RefusalCode::Io,
+                        Some(
+                            "pack gc requires a filesystem store (--store or PACK_STORE)"
+                                .to_string(),
+                        ),
                         None,
-                    );
-                    append_witness_warning(&record);
+                    )
+                    .to_json(),
+                    ExitCode::Refusal.into(),
+                ),
+            };
+            if !no_witness {
+                let outcome = outcomes::outcome_for("gc", exit_code);
+                let mut params = Map::new();
+                if let Some(root) = &root {
+                    params.insert("store".to_string(), path_value(root));
+                }
+                if let Some(keep_last) = options.keep_last {
+                    params.insert("keep_last".to_string(), Value::from(keep_last as u64));
+                }
+                if let Some(keep_since) = &options.keep_since {
+                    params.insert("keep_since".to_string(), Value::String(keep_since.clone()));
+                }
+                if let Some(retain) = &options.retain {
+                    params.insert("retain".to_string(), path_value(retain));
+                }
+                if dry_run {
+                    params.insert("dry_run".to_string(), Value::Bool(true));
+                }
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "gc",
+                    vec![],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    None,
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Store { command } => dispatch_store(command, no_witness),
+        Command::SelfTest { json } => {
+            let (output, exit_code) = selftest::execute_self_test(json);
+            if !no_witness {
+                let outcome = outcomes::outcome_for("self-test", exit_code);
+                let mut params = Map::new();
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "self-test",
+                    vec![],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    None,
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Attest {
+            pack_dir,
+            output,
+            sign,
+            signing_key,
+        } => {
+            let options = attest::AttestOptions {
+                sign,
+                signing_key: signing_key.clone(),
+                output: output.clone(),
+            };
+            let (output_text, outcome, exit_code, pack_id) =
+                match attest::execute_attest(&pack_dir, &options) {
+                    Ok(result) => {
+                        let output_text = match &output {
+                            Some(path) => {
+                                format!("ATTESTED {}\n{}", result.pack_id, path.display())
+                            }
+                            None => result.attestation,
+                        };
+                        (output_text, "ATTESTED", 0, Some(result.pack_id))
+                    }
+                    Err(envelope) => (envelope.to_json(), "REFUSAL", 2, None),
+                };
+            if !no_witness {
+                let mut params = Map::new();
+                params.insert("pack_dir".to_string(), path_value(&pack_dir));
+                if let Some(path) = &output {
+                    params.insert("output".to_string(), path_value(path));
+                }
+                if sign {
+                    params.insert("sign".to_string(), Value::Bool(true));
                 }
-                println!("{output_text}");
-                ExitCode::Refusal.into()
+                let record = witness::WitnessRecord::new(
+                    "attest",
+                    vec![input_from_path(&pack_dir)],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output_text),
+                    pack_id,
+                )
+                .with_pack_dir(&pack_dir);
+                append_witness_warning(&record);
             }
-        },
-        Command::Pull { pack_id, out_dir } => match network::pull::execute_pull(&pack_id, &out_dir)
-        {
-            Ok(result) => {
-                let output_text =
-                    format!("FETCHED {}\n{}", result.pack_id, result.out_dir.display());
-                if !no_witness {
+            cli::print_line(output_text);
+            exit_code
+        }
+        Command::Detect {
+            file,
+            member_path,
+            detect_rules,
+            json,
+        } => {
+            let (output, exit_code) = detect::execute_detect(
+                &file,
+                member_path.as_deref(),
+                detect_rules.as_deref(),
+                json,
+            );
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Canon { file } => {
+            let (output, exit_code) = canonical::execute_canon(&file);
+            cli::print_line(output);
+            exit_code
+        }
+        // Witness query subcommands do NOT record witness.
+        Command::Witness { command } => dispatch_witness(command),
+        Command::Outcomes { json } => {
+            let (output, exit_code) = outcomes::execute_outcomes(json);
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Quickstart { dir, json } => {
+            let (output, exit_code) = quickstart::execute_quickstart(&dir, json);
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Config {
+            command: ConfigCommand::Show { json },
+        } => {
+            let (output, exit_code) = config::execute_config_show(json);
+            cli::print_line(output);
+            exit_code
+        }
+        Command::Schema {
+            command:
+                SchemaCommand::Validate {
+                    file,
+                    artifact_version,
+                    schema_dir,
+                    json,
+                },
+        } => {
+            let (output, exit_code) = verify::execute_schema_validate(
+                &file,
+                artifact_version.as_deref(),
+                schema_dir.as_deref(),
+                json,
+            );
+            cli::print_line(output);
+            exit_code
+        }
+    }
+}
+
+fn dispatch_remote_diff(
+    remote: &[network::manifest::RemotePack],
+    json: bool,
+    options: &diff::DiffOptions,
+    no_witness: bool,
+) -> u8 {
+    let [a, b] = remote else {
+        let _ = Cli::command()
+            .error(
+                clap::error::ErrorKind::WrongNumberOfValues,
+                format!(
+                    "--remote must be given exactly twice (A, then B); got {}",
+                    remote.len()
+                ),
+            )
+            .print();
+        return ExitCode::Refusal.into();
+    };
+    let (output, exit_code) = diff::execute_remote_diff(a, b, json, options);
+    if !no_witness {
+        let outcome = outcomes::outcome_for("diff", exit_code);
+        let mut params = Map::new();
+        params.insert("a".to_string(), Value::String(a.to_string()));
+        params.insert("b".to_string(), Value::String(b.to_string()));
+        params.insert("remote".to_string(), Value::Bool(true));
+        params.insert("json".to_string(), Value::Bool(json));
+        let record = witness::WitnessRecord::new(
+            "diff",
+            vec![],
+            outcome,
+            exit_code,
+            params,
+            &stdout_bytes(&output),
+            None,
+        );
+        append_witness_warning(&record);
+    }
+    cli::print_line(output);
+    exit_code
+}
+
+fn dispatch_seal_transaction(
+    plan: &Path,
+    options: &seal::command::SealOptions,
+    json: bool,
+    no_witness: bool,
+) -> u8 {
+    let sealed = seal::transaction::load_transaction_plan(plan)
+        .and_then(|seals| seal::transaction::execute_seal_transaction(&seals, options));
+    match sealed {
+        Ok(results) => {
+            let reports: Vec<_> = results.iter().map(seal::report::SealReport::new).collect();
+            let output_text = if json {
+                serde_json::to_string_pretty(&reports)
+                    .expect("seal report serialization cannot fail")
+            } else {
+                reports
+                    .iter()
+                    .map(seal::report::SealReport::to_human)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            if !no_witness {
+                for result in &results {
                     let mut params = Map::new();
-                    params.insert("pack_id".to_string(), Value::String(result.pack_id.clone()));
-                    params.insert("out_dir".to_string(), path_value(&result.out_dir));
+                    params.insert("transaction".to_string(), path_value(plan));
+                    params.insert(
+                        "member_count".to_string(),
+                        Value::from(result.member_count as u64),
+                    );
+                    params.insert("output_dir".to_string(), path_value(&result.output_dir));
+                    if json {
+                        params.insert("json".to_string(), Value::Bool(true));
+                    }
                     let record = witness::WitnessRecord::new(
-                        "pull",
-                        vec![],
-                        "FETCHED",
+                        "seal",
+                        result.witness_inputs.clone(),
+                        "PACK_CREATED",
                         0,
                         params,
                         &stdout_bytes(&output_text),
                         Some(result.pack_id.clone()),
+                    )
+                    .with_pack_size(
+                        result.member_count,
+                        result
+                            .witness_inputs
+                            .iter()
+                            .filter_map(|input| input.bytes)
+                            .sum(),
                     );
                     append_witness_warning(&record);
                 }
-                println!("{output_text}");
-                ExitCode::Success.into()
             }
-            Err(envelope) => {
-                let output_text = envelope.to_json();
-                if !no_witness {
-                    let mut params = Map::new();
-                    params.insert("pack_id".to_string(), Value::String(pack_id.clone()));
-                    params.insert("out_dir".to_string(), path_value(&out_dir));
-                    let record = witness::WitnessRecord::new(
-                        "pull",
-                        vec![],
-                        "REFUSAL",
-                        2,
-                        params,
-                        &stdout_bytes(&output_text),
-                        Some(pack_id.clone()),
-                    );
-                    append_witness_warning(&record);
+            cli::print_line(output_text);
+            ExitCode::Success.into()
+        }
+        Err(envelope) => {
+            let output_text = envelope.to_json();
+            if !no_witness {
+                let mut params = Map::new();
+                params.insert("transaction".to_string(), path_value(plan));
+                let record = witness::WitnessRecord::new(
+                    "seal",
+                    vec![input_from_path(plan)],
+                    "REFUSAL",
+                    2,
+                    params,
+                    &stdout_bytes(&output_text),
+                    None,
+                );
+                append_witness_warning(&record);
+            }
+            cli::print_line(output_text);
+            ExitCode::Refusal.into()
+        }
+    }
+}
+
+fn dispatch_store(command: StoreCommand, no_witness: bool) -> u8 {
+    match command {
+        StoreCommand::Refcount {
+            store,
+            min_refs,
+            hash,
+            json,
+        } => {
+            let root =
+                network::store::filesystem_store_root(store.as_deref(), config::env_or_config);
+            let (output, exit_code) = match &root {
+                Some(root) => {
+                    let options = network::refcount::RefcountOptions {
+                        min_refs,
+                        hash: hash.clone(),
+                    };
+                    network::refcount::execute_refcount(root, &options, json)
+                }
+                None => (
+                    refusal::RefusalEnvelope::new(
+                        refusal::RefusalCode::Io,
+                        Some(
+                            "pack store refcount requires a filesystem store (--store or PACK_STORE)"
+                                .to_string(),
+                        ),
+                        None,
+                    )
+                    .to_json(),
+                    ExitCode::Refusal.into(),
+                ),
+            };
+            if !no_witness {
+                let outcome = outcomes::outcome_for("store", exit_code);
+                let mut params = Map::new();
+                if let Some(root) = &root {
+                    params.insert("store".to_string(), path_value(root));
+                }
+                if min_refs > 1 {
+                    params.insert("min_refs".to_string(), Value::from(min_refs as u64));
                 }
-                println!("{output_text}");
-                ExitCode::Refusal.into()
+                if let Some(hash) = &hash {
+                    params.insert("hash".to_string(), Value::String(hash.clone()));
+                }
+                params.insert("json".to_string(), Value::Bool(json));
+                let record = witness::WitnessRecord::new(
+                    "store refcount",
+                    vec![],
+                    outcome,
+                    exit_code,
+                    params,
+                    &stdout_bytes(&output),
+                    None,
+                );
+                append_witness_warning(&record);
             }
-        },
-        // Witness query subcommands do NOT record witness.
-        Command::Witness { command } => dispatch_witness(command),
+            cli::print_line(output);
+            exit_code
+        }
     }
 }
 
 fn dispatch_witness(command: WitnessCommand) -> u8 {
     match command {
-        WitnessCommand::Query { filters, json } => {
-            println!("{}", witness::query::execute_query(&filters, json));
+        WitnessCommand::Query {
+            filters,
+            report_corrupt,
+            json,
+        } => {
+            cli::print_line(witness::query::execute_query(
+                &filters,
+                json,
+                report_corrupt,
+            ));
             ExitCode::Success.into()
         }
         WitnessCommand::Last { json } => {
-            println!("{}", witness::query::execute_last(json));
+            cli::print_line(witness::query::execute_last(json));
+            ExitCode::Success.into()
+        }
+        WitnessCommand::Tail {
+            filters,
+            lines,
+            follow,
+            interval,
+            json,
+        } => {
+            let options = witness::tail::TailOptions {
+                lines,
+                follow,
+                interval: interval.map(std::time::Duration::from_secs),
+            };
+            witness::tail::execute_tail(&filters, &options, json, |line| {
+                cli::print_line(line);
+                if cli::stdout_closed() {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
             ExitCode::Success.into()
         }
         WitnessCommand::Count { filters, json } => {
-            println!("{}", witness::query::execute_count(&filters, json));
+            cli::print_line(witness::query::execute_count(&filters, json));
             ExitCode::Success.into()
         }
+        WitnessCommand::Validate { json } => {
+            let (output, exit_code) = witness::validate::execute_validate(json);
+            cli::print_line(output);
+            exit_code
+        }
+        WitnessCommand::Export {
+            filters,
+            format,
+            partition,
+            out,
+            json,
+        } => {
+            let (output, exit_code) =
+                witness::export::execute_export(&filters, format, partition, &out, json);
+            cli::print_line(output);
+            exit_code
+        }
+        WitnessCommand::VerifyChain { expect_head, json } => {
+            let (output, exit_code) =
+                witness::chain::execute_verify_chain(expect_head.as_deref(), json);
+            cli::print_line(output);
+            exit_code
+        }
+    }
+}
+
+/// `seal --progress`: report collection totals on stderr at most once a second.
+fn progress_events(events: events::EventBus) -> events::EventBus {
+    let last_report = std::sync::Mutex::new(std::time::Instant::now());
+    events.subscribe(move |event| {
+        if let events::PackEvent::CollectProgress { files, bytes } = event {
+            let mut last_report = last_report.lock().unwrap_or_else(|e| e.into_inner());
+            if last_report.elapsed() >= std::time::Duration::from_secs(1) {
+                eprintln!("pack: collecting: {files} files, {bytes} bytes");
+                *last_report = std::time::Instant::now();
+            }
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// The progress line for a seal or verify run at a terminal. It stays off
+/// while `--verbose`/`--log-json` lines are being written to stderr.
+fn interactive_progress(events: events::EventBus, json: bool) -> events::EventBus {
+    if !logging::enabled() && cli::progress_enabled(json) {
+        cli::progress_line(events)
+    } else {
+        events
     }
 }
 
@@ -0,0 +1,195 @@
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::config::env_or_config;
+use crate::diff::compare_manifests;
+use crate::network::store::{resolve_store, PackTransport};
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::manifest::Manifest;
+use crate::verify::read_manifest;
+
+use super::report::{LineageNode, LineageReport, LINEAGE_REPORT_VERSION};
+
+/// Execute `pack lineage <PACK_DIR>`.
+///
+/// Returns (output, exit_code): 0 when every ancestor was found, 1 when any
+/// was not, 2 with a refusal when the pack itself cannot be read.
+pub fn execute_lineage(pack_dir: &Path, store: Option<&str>, json_output: bool) -> (String, u8) {
+    match pack_lineage(pack_dir, store) {
+        Ok(report) => {
+            let exit_code = if report.outcome == "COMPLETE" { 0 } else { 1 };
+            let output = if json_output {
+                report.to_json()
+            } else {
+                report.to_human()
+            };
+            (output, exit_code)
+        }
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Walk the ancestry of `pack_dir` (a directory or `.pack.tar`) through the
+/// manifests' `lineage.parent_pack_ids`, reading each parent's manifest from
+/// `store` (default `PACK_STORE`) and diffing it against its child.
+///
+/// The store is only needed when the pack has parents. A parent the store
+/// cannot supply, or whose manifest does not hash to its ID, ends that branch
+/// of the tree and makes the report `INCOMPLETE`. Because every ID is
+/// checked, a manifest cannot name itself as an ancestor.
+pub fn pack_lineage(
+    pack_dir: &Path,
+    store: Option<&str>,
+) -> Result<LineageReport, Box<RefusalEnvelope>> {
+    let manifest = read_manifest(pack_dir)?;
+    let recomputed = manifest.recompute_pack_id();
+    if recomputed != manifest.pack_id {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some("manifest.json does not hash to its pack_id".to_string()),
+            Some(json!({
+                "pack": pack_dir.display().to_string(),
+                "expected": manifest.pack_id,
+                "actual": recomputed,
+            })),
+        )));
+    }
+
+    let mut complete = true;
+    let parents = if parent_ids(&manifest).is_empty() {
+        Vec::new()
+    } else {
+        let transport = resolve_store(store, "lineage", env_or_config)?;
+        walk_parents(&manifest, transport.as_ref(), &mut complete)
+    };
+    Ok(LineageReport {
+        version: LINEAGE_REPORT_VERSION.to_string(),
+        outcome: if complete { "COMPLETE" } else { "INCOMPLETE" }.to_string(),
+        pack_id: manifest.pack_id.clone(),
+        pack: LineageNode {
+            parents,
+            ..found_node(&manifest)
+        },
+    })
+}
+
+fn parent_ids(manifest: &Manifest) -> &[String] {
+    manifest
+        .lineage
+        .as_ref()
+        .map_or(&[], |lineage| lineage.parent_pack_ids.as_slice())
+}
+
+fn walk_parents(
+    child: &Manifest,
+    transport: &dyn PackTransport,
+    complete: &mut bool,
+) -> Vec<LineageNode> {
+    parent_ids(child)
+        .iter()
+        .map(|pack_id| match transport.fetch_manifest(pack_id) {
+            Ok(parent) if parent.recompute_pack_id() != *pack_id => {
+                *complete = false;
+                missing_node(
+                    pack_id,
+                    "PACK_ID_MISMATCH",
+                    format!("stored manifest hashes to {}", parent.recompute_pack_id()),
+                )
+            }
+            Ok(parent) => LineageNode {
+                diff: Some(compare_manifests(&parent, child)),
+                parents: walk_parents(&parent, transport, complete),
+                ..found_node(&parent)
+            },
+            Err(envelope) => {
+                *complete = false;
+                missing_node(pack_id, "UNAVAILABLE", envelope.refusal.message)
+            }
+        })
+        .collect()
+}
+
+fn found_node(manifest: &Manifest) -> LineageNode {
+    LineageNode {
+        pack_id: manifest.pack_id.clone(),
+        status: "FOUND".to_string(),
+        created: Some(manifest.created.clone()),
+        note: manifest.note.clone(),
+        member_count: Some(manifest.member_count),
+        reason: None,
+        diff: None,
+        parents: Vec::new(),
+    }
+}
+
+fn missing_node(pack_id: &str, status: &str, reason: String) -> LineageNode {
+    LineageNode {
+        pack_id: pack_id.to_string(),
+        status: status.to_string(),
+        created: None,
+        note: None,
+        member_count: None,
+        reason: Some(reason),
+        diff: None,
+        parents: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::store::FilesystemStore;
+    use crate::seal::command::{execute_seal, execute_seal_with_options, SealOptions};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn lineage_walks_parents_in_the_store_with_step_diffs() {
+        let tmp = TempDir::new().unwrap();
+        let store = tmp.path().join("store");
+        let file = |name: &str| {
+            let path = tmp.path().join(name);
+            fs::write(&path, name).unwrap();
+            path
+        };
+        let sealed = |artifact, parent: Option<&Path>, out: &str| {
+            let options = SealOptions {
+                merge: parent.map(Path::to_path_buf),
+                ..SealOptions::default()
+            };
+            execute_seal_with_options(&[artifact], Some(&tmp.path().join(out)), None, &options)
+                .unwrap()
+        };
+        let jan = execute_seal(&[file("jan.json")], Some(&tmp.path().join("jan")), None).unwrap();
+        let feb = sealed(file("feb.json"), Some(&jan.output_dir), "feb");
+        let mar = sealed(file("mar.json"), Some(&feb.output_dir), "mar");
+        let store_spec = store.to_string_lossy().to_string();
+
+        // Only the newest pack is in the store so far: feb is unavailable.
+        let report = pack_lineage(&mar.output_dir, Some(&store_spec)).unwrap();
+        assert_eq!(report.outcome, "INCOMPLETE");
+        assert_eq!(report.pack.parents[0].status, "UNAVAILABLE");
+
+        let transport = FilesystemStore::new(&store);
+        for pack in [&jan, &feb] {
+            let manifest = read_manifest(&pack.output_dir).unwrap();
+            transport.publish(&pack.output_dir, &manifest).unwrap();
+        }
+        let report = pack_lineage(&mar.output_dir, Some(&store_spec)).unwrap();
+        assert_eq!(report.outcome, "COMPLETE");
+        let feb_node = &report.pack.parents[0];
+        assert_eq!(feb_node.pack_id, feb.pack_id);
+        let added = &feb_node.diff.as_ref().unwrap().added;
+        assert_eq!(added[0].path, "mar.json");
+        assert_eq!(feb_node.parents[0].pack_id, jan.pack_id);
+        assert!(feb_node.parents[0].parents.is_empty());
+
+        let human = report.to_human();
+        assert!(
+            human.contains(&format!("    <- {}", jan.pack_id)),
+            "{human}"
+        );
+        assert!(human.contains("+ feb.json"), "{human}");
+    }
+}
@@ -0,0 +1,5 @@
+mod command;
+mod report;
+
+pub use command::{execute_lineage, pack_lineage};
+pub use report::{LineageNode, LineageReport, LINEAGE_REPORT_VERSION};
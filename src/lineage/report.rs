@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+use crate::cli::SortOrder;
+use crate::diff::DiffReport;
+
+/// Version tag for `pack lineage --json` output.
+pub const LINEAGE_REPORT_VERSION: &str = "pack.lineage.v0";
+
+/// A pack and its ancestry, as printed by `pack lineage --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageReport {
+    pub version: String,
+    /// `COMPLETE` when every ancestor was read from the store, `INCOMPLETE`
+    /// when any was not.
+    pub outcome: String,
+    /// The pack whose ancestry was walked.
+    pub pack_id: String,
+    pub pack: LineageNode,
+}
+
+/// One pack in the ancestry tree.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageNode {
+    pub pack_id: String,
+    /// `FOUND`, `UNAVAILABLE` (the store could not supply the manifest), or
+    /// `PACK_ID_MISMATCH` (the store's manifest does not hash to this ID).
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<usize>,
+    /// Why the pack is not `FOUND`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Changes from this pack to the one naming it as a parent. Absent for
+    /// the starting pack and for parents that could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<DiffReport>,
+    /// This pack's own parents, in `lineage.parent_pack_ids` order.
+    pub parents: Vec<LineageNode>,
+}
+
+impl LineageReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("lineage report serialization cannot fail")
+    }
+
+    /// `pack lineage: COMPLETE`, then the tree: each parent indented under
+    /// the pack it led to, followed by the members that step changed.
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![format!("pack lineage: {}", self.outcome)];
+        self.pack.render(0, &mut lines);
+        lines.join("\n")
+    }
+}
+
+impl LineageNode {
+    fn render(&self, depth: usize, lines: &mut Vec<String>) {
+        let indent = "  ".repeat(depth + 1);
+        let arrow = if depth == 0 { "" } else { "<- " };
+        let summary = match (&self.created, self.member_count, &self.reason) {
+            (Some(created), Some(count), _) => {
+                format!("{} ({created}, {count} members)", self.pack_id)
+            }
+            (_, _, Some(reason)) => format!("{} {}: {reason}", self.pack_id, self.status),
+            _ => format!("{} {}", self.pack_id, self.status),
+        };
+        lines.push(format!("{indent}{arrow}{summary}"));
+        if let Some(note) = &self.note {
+            lines.push(format!("{indent}   note: {note}"));
+        }
+        if let Some(diff) = &self.diff {
+            // Skip the diff header and its a/b pack IDs, already shown above.
+            for line in diff.to_human(SortOrder::Bytewise).lines().skip(3) {
+                lines.push(format!("{indent} {line}"));
+            }
+        }
+        for parent in &self.parents {
+            parent.render(depth + 1, lines);
+        }
+    }
+}
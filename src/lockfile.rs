@@ -0,0 +1,90 @@
+//! Exclusive locks for files that concurrent pack processes update in place:
+//! the witness ledger and a filesystem store's index.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a waiter sleeps between attempts.
+const RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// An exclusive OS lock (`flock` on Unix, `LockFileEx` on Windows) on a lock
+/// file, held until drop.
+///
+/// The lock belongs to the open file, not to the file's existence: the OS
+/// releases it when the holder exits or is killed, so a crashed writer never
+/// leaves a stale lock behind and no one has to guess when to break one. The
+/// lock file itself is left in place, since deleting it would let a later
+/// waiter lock a new file while another still holds the old one.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Lock `path`, creating it if needed, waiting up to `timeout` for the
+    /// current holder to finish.
+    pub fn acquire(path: &Path, timeout: Duration) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WouldBlock,
+                        format!(
+                            "lock held for over {}s: {}",
+                            timeout.as_secs(),
+                            path.display()
+                        ),
+                    ));
+                }
+                Err(TryLockError::Error(e)) => return Err(e),
+            }
+        }
+    }
+}
+
+/// `<file>.lock`, the lock file guarding `file`.
+pub fn lock_path_for(file: &Path) -> PathBuf {
+    let mut name = file.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    file.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn second_holder_waits_and_a_leftover_file_does_not_block() {
+        let tmp = TempDir::new().unwrap();
+        let path = lock_path_for(&tmp.path().join("witness.jsonl"));
+        assert_eq!(path, tmp.path().join("witness.jsonl.lock"));
+
+        // A lock file left by a killed process holds no OS lock.
+        std::fs::write(&path, "").unwrap();
+        let held = FileLock::acquire(&path, Duration::ZERO).unwrap();
+        let err = FileLock::acquire(&path, Duration::from_millis(50)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+        let waiter = {
+            let path = path.clone();
+            thread::spawn(move || FileLock::acquire(&path, Duration::from_secs(5)).is_ok())
+        };
+        thread::sleep(Duration::from_millis(50));
+        drop(held);
+        assert!(waiter.join().unwrap());
+    }
+}
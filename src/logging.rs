@@ -0,0 +1,214 @@
+//! `--verbose` and `--log-json`: a stderr log of what a command is doing.
+//!
+//! The log is one more subscriber on the [`EventBus`] a command already
+//! publishes to, plus a record when the command starts and finishes. Each
+//! record carries the `phase` it belongs to and `elapsed_ms` since the command
+//! started; member events also carry `member` and `bytes`. Stdout is never
+//! touched, so `--json` output stays machine-readable.
+
+use std::ops::ControlFlow;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use serde_json::{Map, Value};
+
+use crate::events::{EventBus, PackEvent};
+
+/// How much a log record is worth: shown at `-v`, `-vv`, or `-vvv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Command start and finish, the final manifest, findings, publishes.
+    Info = 1,
+    /// One record per member collected, hashed, or verified.
+    Debug = 2,
+    /// Running totals from seal's input walk.
+    Trace = 3,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Logger {
+    level: u8,
+    json: bool,
+    started: Instant,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Whether `--verbose` or `--log-json` is writing to stderr.
+pub fn enabled() -> bool {
+    LOGGER.get().is_some_and(|logger| logger.level > 0)
+}
+
+/// Install the process logger from `-v` (repeatable) and `--log-json`.
+///
+/// `--log-json` alone logs at `-vv`, so an orchestrator gets per-member
+/// progress from one flag. With neither flag nothing is logged.
+pub fn init(verbose: u8, json: bool) {
+    let level = match verbose {
+        0 if json => LogLevel::Debug as u8,
+        verbose => verbose.min(LogLevel::Trace as u8),
+    };
+    let _ = LOGGER.set(Logger {
+        level,
+        json,
+        started: Instant::now(),
+    });
+}
+
+/// An [`EventBus`] that logs every event at or below the configured level;
+/// the default, free bus when logging is off.
+pub fn event_bus() -> EventBus {
+    if !enabled() {
+        return EventBus::default();
+    }
+    EventBus::default().subscribe(|event| {
+        log_event(event);
+        ControlFlow::Continue(())
+    })
+}
+
+/// Log that `command` started.
+pub fn command_started(command: &str) {
+    let mut fields = Map::new();
+    fields.insert("command".to_string(), Value::String(command.to_string()));
+    write(LogLevel::Info, "start", "command_started", fields);
+}
+
+/// Log that `command` finished with `exit_code`.
+pub fn command_finished(command: &str, exit_code: u8) {
+    let mut fields = Map::new();
+    fields.insert("command".to_string(), Value::String(command.to_string()));
+    fields.insert("exit_code".to_string(), Value::from(exit_code));
+    write(LogLevel::Info, "done", "command_finished", fields);
+}
+
+fn log_event(event: &PackEvent) {
+    let (level, phase) = classify(event);
+    if let Some((name, fields)) = event_fields(event) {
+        write(level, phase, &name, fields);
+    }
+}
+
+/// The event's name and its fields, with `path` logged as `member` and
+/// `size` as `bytes`.
+fn event_fields(event: &PackEvent) -> Option<(String, Map<String, Value>)> {
+    let Ok(Value::Object(mut fields)) = serde_json::to_value(event) else {
+        return None;
+    };
+    let name = match fields.remove("event") {
+        Some(Value::String(name)) => name,
+        _ => return None,
+    };
+    if let Some(path) = fields.remove("path") {
+        // Findings without a path serialize it as null.
+        if !path.is_null() {
+            fields.insert("member".to_string(), path);
+        }
+    }
+    if let Some(size) = fields.remove("size") {
+        fields.insert("bytes".to_string(), size);
+    }
+    Some((name, fields))
+}
+
+/// The level and phase of an event.
+fn classify(event: &PackEvent) -> (LogLevel, &'static str) {
+    match event {
+        PackEvent::CollectProgress { .. } => (LogLevel::Trace, "collect"),
+        PackEvent::ArtifactCollected { .. } => (LogLevel::Debug, "collect"),
+        PackEvent::HashingStarted { .. } => (LogLevel::Info, "hash"),
+        PackEvent::MemberHashed { .. } => (LogLevel::Debug, "hash"),
+        PackEvent::ManifestFinalized { .. } => (LogLevel::Info, "finalize"),
+        PackEvent::MemberVerified { .. } => (LogLevel::Debug, "verify"),
+        PackEvent::VerificationFinding { .. } => (LogLevel::Info, "verify"),
+        PackEvent::PackPublished { .. } => (LogLevel::Info, "publish"),
+    }
+}
+
+fn write(level: LogLevel, phase: &str, event: &str, fields: Map<String, Value>) {
+    let Some(logger) = LOGGER.get() else {
+        return;
+    };
+    if level as u8 > logger.level {
+        return;
+    }
+    let elapsed_ms = logger.started.elapsed().as_secs_f64() * 1000.0;
+    eprintln!(
+        "{}",
+        render(level, phase, event, elapsed_ms, fields, logger.json)
+    );
+}
+
+/// One log line: a JSON object with `--log-json`, otherwise
+/// `pack: [elapsed] phase: event key=value ...`.
+fn render(
+    level: LogLevel,
+    phase: &str,
+    event: &str,
+    elapsed_ms: f64,
+    fields: Map<String, Value>,
+    json: bool,
+) -> String {
+    let elapsed_ms = (elapsed_ms * 1000.0).round() / 1000.0;
+    if json {
+        let mut record = fields;
+        record.insert(
+            "level".to_string(),
+            Value::String(level.as_str().to_string()),
+        );
+        record.insert("phase".to_string(), Value::String(phase.to_string()));
+        record.insert("event".to_string(), Value::String(event.to_string()));
+        record.insert("elapsed_ms".to_string(), Value::from(elapsed_ms));
+        return Value::Object(record).to_string();
+    }
+    let mut line = format!("pack: [{:>9.3}s] {phase}: {event}", elapsed_ms / 1000.0);
+    for (key, value) in fields {
+        match value {
+            Value::String(text) => line.push_str(&format!(" {key}={text}")),
+            value => line.push_str(&format!(" {key}={value}")),
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn member_events_render_with_phase_member_and_bytes() {
+        let event = PackEvent::MemberHashed {
+            path: "rules.json".to_string(),
+            bytes_hash: "sha256:ab".to_string(),
+            size: 12,
+        };
+        let (level, phase) = classify(&event);
+        assert_eq!((level, phase), (LogLevel::Debug, "hash"));
+        let (name, fields) = event_fields(&event).unwrap();
+        assert_eq!(name, "member_hashed");
+
+        let line = render(level, phase, &name, 1.5, fields.clone(), true);
+        let record: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(record["phase"], "hash");
+        assert_eq!(record["member"], "rules.json");
+        assert_eq!(record["bytes"], 12);
+        assert_eq!(record["elapsed_ms"], 1.5);
+        assert_eq!(record["level"], "debug");
+
+        let line = render(level, phase, &name, 1.5, fields, false);
+        assert_eq!(
+            line,
+            "pack: [    0.002s] hash: member_hashed bytes=12 bytes_hash=sha256:ab member=rules.json"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::canonical::canonicalize;
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::sign::{load_signing_key, parse_public_key};
+
+use super::store::{enclosing_store, resolve_store, FilesystemStore, PackTransport};
+
+/// A `pack deprecate` note, kept on the pack's entry in the store index.
+///
+/// When signed, the signature covers the canonical JSON of
+/// `{deprecated_at, pack_id, reason}`. The note carries its own public key,
+/// so a valid signature proves only that the note is intact; who wrote it is
+/// established by checking that key against keys the reader trusts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub reason: String,
+    pub deprecated_at: String,
+    /// Signer's public key, hex.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// Ed25519 signature, hex.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl Deprecation {
+    /// A note for `pack_id` dated now, signed when `key` is given.
+    pub fn new(pack_id: &str, reason: &str, key: Option<&SigningKey>) -> Self {
+        let mut deprecation = Self {
+            reason: reason.to_string(),
+            deprecated_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            public_key: None,
+            signature: None,
+        };
+        if let Some(key) = key {
+            let signature = key.sign(&deprecation.signed_bytes(pack_id));
+            deprecation.public_key = Some(hex::encode(key.verifying_key().as_bytes()));
+            deprecation.signature = Some(hex::encode(signature.to_bytes()));
+        }
+        deprecation
+    }
+
+    /// `None` when the note is unsigned; otherwise whether the signature is a
+    /// valid signature over this note for `pack_id`.
+    pub fn signature_valid(&self, pack_id: &str) -> Option<bool> {
+        if self.public_key.is_none() && self.signature.is_none() {
+            return None;
+        }
+        let signer = self
+            .public_key
+            .as_deref()
+            .and_then(|key| parse_public_key(key).ok());
+        let signature = self
+            .signature
+            .as_deref()
+            .and_then(|sig| hex::decode(sig).ok())
+            .and_then(|bytes| Signature::from_slice(&bytes).ok());
+        Some(match (signer, signature) {
+            (Some(signer), Some(signature)) => signer
+                .verify_strict(&self.signed_bytes(pack_id), &signature)
+                .is_ok(),
+            _ => false,
+        })
+    }
+
+    /// Whether the note carries a valid signature by one of `trusted`.
+    pub fn signed_by_any(&self, pack_id: &str, trusted: &[VerifyingKey]) -> bool {
+        let signer = self
+            .public_key
+            .as_deref()
+            .and_then(|key| parse_public_key(key).ok());
+        self.signature_valid(pack_id) == Some(true)
+            && signer.is_some_and(|signer| trusted.contains(&signer))
+    }
+
+    /// One-line summary for human output and warnings, saying who signed the
+    /// note. `trusted` are the keys the reader trusts (`verify --public-key`,
+    /// a trust policy's `allowed_signers`); with none, the signer is named
+    /// but not checked.
+    pub fn describe(&self, pack_id: &str, trusted: &[VerifyingKey]) -> String {
+        let signer = self.public_key.as_deref().unwrap_or_default();
+        let status = match self.signature_valid(pack_id) {
+            None => "unsigned".to_string(),
+            Some(false) => "deprecation signature does not verify".to_string(),
+            Some(true) if trusted.is_empty() => format!("signed by {signer}, signer not checked"),
+            Some(true) if self.signed_by_any(pack_id, trusted) => {
+                format!("signed by trusted key {signer}")
+            }
+            Some(true) => format!("signed by {signer}, not a trusted key"),
+        };
+        format!(
+            "{} (deprecated {}) ({status})",
+            self.reason, self.deprecated_at
+        )
+    }
+
+    fn signed_bytes(&self, pack_id: &str) -> Vec<u8> {
+        canonicalize(&json!({
+            "deprecated_at": self.deprecated_at,
+            "pack_id": pack_id,
+            "reason": self.reason,
+        }))
+        .into_bytes()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecateResult {
+    pub pack_id: String,
+    pub deprecation: Deprecation,
+}
+
+/// Record a deprecation note for `pack_id` in the store named by `store`
+/// (falling back to `PACK_STORE`), signed unless `unsigned` is set. The
+/// pack's bytes and `pack_id` are untouched; `verify`, `inspect`, and `pull`
+/// surface the note.
+pub fn execute_deprecate(
+    pack_id: &str,
+    reason: &str,
+    store: Option<&str>,
+    unsigned: bool,
+    signing_key: Option<&Path>,
+) -> Result<DeprecateResult, Box<RefusalEnvelope>> {
+    if reason.trim().is_empty() {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some("pack deprecate requires a non-empty --reason".to_string()),
+            Some(json!({ "pack_id": pack_id })),
+        )));
+    }
+    let transport = resolve_store(store, "deprecate", crate::config::env_or_config)?;
+    let key = if unsigned {
+        None
+    } else {
+        let key = load_signing_key(signing_key).map_err(|message| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("{message} (or pass --unsigned)")),
+                signing_key.map(|path| json!({ "signing_key": path.display().to_string() })),
+            ))
+        })?;
+        Some(key)
+    };
+    let deprecation = Deprecation::new(pack_id, reason, key.as_ref());
+    transport.deprecate(pack_id, &deprecation)?;
+    Ok(DeprecateResult {
+        pack_id: pack_id.to_string(),
+        deprecation,
+    })
+}
+
+/// Look up a deprecation note for `pack_id`: first in the store that holds
+/// `pack_dir` (a `<root>/sha256/<hex>/` directory), if there is one on disk,
+/// then in `store`.
+pub fn find_deprecation(
+    pack_dir: Option<&Path>,
+    pack_id: &str,
+    store: Option<&Path>,
+) -> Option<Deprecation> {
+    let enclosing = pack_dir.and_then(enclosing_store).map(|(root, _)| root);
+    enclosing
+        .into_iter()
+        .chain(store.map(PathBuf::from))
+        .find_map(|root| FilesystemStore::new(root).deprecation(pack_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_note_verifies_only_for_its_pack() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let pack_id = format!("sha256:{}", "a".repeat(64));
+        let deprecation = Deprecation::new(&pack_id, "superseded by v2", Some(&key));
+        assert_eq!(deprecation.signature_valid(&pack_id), Some(true));
+        assert_eq!(deprecation.signature_valid("sha256:other"), Some(false));
+
+        let mut tampered = deprecation.clone();
+        tampered.reason = "fine after all".to_string();
+        assert_eq!(tampered.signature_valid(&pack_id), Some(false));
+        assert!(tampered.describe(&pack_id, &[]).contains("does not verify"));
+
+        let unsigned = Deprecation::new(&pack_id, "old", None);
+        assert_eq!(unsigned.signature_valid(&pack_id), None);
+        assert!(unsigned.describe(&pack_id, &[]).ends_with("(unsigned)"));
+    }
+
+    #[test]
+    fn self_signed_note_is_trusted_only_for_a_trusted_key() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let forger = SigningKey::from_bytes(&[9; 32]);
+        let pack_id = format!("sha256:{}", "a".repeat(64));
+        let trusted = [key.verifying_key()];
+
+        let genuine = Deprecation::new(&pack_id, "superseded by v2", Some(&key));
+        assert!(genuine.signed_by_any(&pack_id, &trusted));
+        assert!(genuine
+            .describe(&pack_id, &trusted)
+            .contains("signed by trusted key"));
+        assert!(genuine
+            .describe(&pack_id, &[])
+            .contains("signer not checked"));
+
+        // Anyone who can write index.json can sign a note with their own key.
+        let forged = Deprecation::new(&pack_id, "withdrawn", Some(&forger));
+        assert_eq!(forged.signature_valid(&pack_id), Some(true));
+        assert!(!forged.signed_by_any(&pack_id, &trusted));
+        assert!(forged
+            .describe(&pack_id, &trusted)
+            .contains("not a trusted key"));
+    }
+}
@@ -0,0 +1,469 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::manifest::Manifest;
+
+use super::index::update_index;
+use super::store::FilesystemStore;
+
+/// Version tag for `pack gc` output.
+pub const GC_REPORT_VERSION: &str = "pack.gc.v0";
+
+/// What `pack gc` removed (or would remove, with `--dry-run`) from a store.
+#[derive(Debug, Clone, Serialize)]
+pub struct GcReport {
+    pub version: String,
+    pub store: String,
+    pub dry_run: bool,
+    /// Packs read from the store index.
+    pub packs: usize,
+    /// Newest first, each with the first rule that kept it.
+    pub kept: Vec<KeptPack>,
+    /// Newest first.
+    pub removed: Vec<RemovedPack>,
+    /// Bytes on disk under the removed pack directories.
+    pub reclaimed_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeptPack {
+    pub pack_id: String,
+    pub created: String,
+    pub reason: KeepReason,
+}
+
+/// Why a pack survived: the retention list wins, then `--keep-last`, then
+/// `--keep-since`. A pack whose `created` does not parse is never removed,
+/// and neither is a lineage parent of a kept pack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepReason {
+    Retained,
+    KeepLast,
+    KeepSince,
+    Undated,
+    Lineage,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemovedPack {
+    pub pack_id: String,
+    pub created: String,
+    pub bytes: u64,
+}
+
+impl GcReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("gc report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let verb = if self.dry_run {
+            "would remove"
+        } else {
+            "removed"
+        };
+        let mut lines = vec![
+            format!("pack gc: {}", self.store),
+            format!(
+                "  {} packs, {} kept, {verb} {} ({} bytes)",
+                self.packs,
+                self.kept.len(),
+                self.removed.len(),
+                self.reclaimed_bytes
+            ),
+        ];
+        for pack in &self.removed {
+            lines.push(format!(
+                "  - {}  {}  {:>12}",
+                pack.pack_id, pack.created, pack.bytes
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Retention rules for [`store_gc`]. A pack is removed only when no rule
+/// keeps it.
+#[derive(Debug, Clone, Default)]
+pub struct GcOptions {
+    /// Keep the N most recently created packs.
+    pub keep_last: Option<usize>,
+    /// Keep packs created at or after this RFC3339 timestamp or `YYYY-MM-DD`.
+    pub keep_since: Option<String>,
+    /// File of pack IDs that are never removed, one per line.
+    pub retain: Option<PathBuf>,
+    /// Report what would be removed without removing it.
+    pub dry_run: bool,
+}
+
+/// Execute `pack gc`.
+///
+/// Returns (output, exit_code): 0 with the report, or 2 with a refusal.
+pub fn execute_gc(root: &Path, options: &GcOptions, json_output: bool) -> (String, u8) {
+    match store_gc(root, options) {
+        Ok(report) if json_output => (report.to_json(), 0),
+        Ok(report) => (report.to_human(), 0),
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Remove the packs in a filesystem store that no retention rule keeps.
+///
+/// The index is reconciled with the packs on disk first, as `push` does, and
+/// again after removal. Each pack directory is renamed to a dot-prefixed name
+/// before it is deleted, so an interrupted run never leaves a partial pack
+/// where readers look for one.
+pub fn store_gc(root: &Path, options: &GcOptions) -> Result<GcReport, Box<RefusalEnvelope>> {
+    if !root.is_dir() {
+        return Err(refusal(
+            root,
+            format!("Store root is not a directory: {}", root.display()),
+        ));
+    }
+    let keep_since = options
+        .keep_since
+        .as_deref()
+        .map(parse_keep_since)
+        .transpose()
+        .map_err(|message| refusal(root, message))?;
+    let retained = match &options.retain {
+        Some(path) => load_retention_list(path).map_err(|message| refusal(root, message))?,
+        None => BTreeSet::new(),
+    };
+    let index = update_index(root).map_err(|e| {
+        refusal(
+            root,
+            format!("Cannot update store index: {}: {e}", root.display()),
+        )
+    })?;
+
+    let mut packs: Vec<_> = index
+        .packs
+        .iter()
+        .map(|entry| {
+            let created = DateTime::parse_from_rfc3339(&entry.created)
+                .ok()
+                .map(|time| time.with_timezone(&Utc));
+            (entry, created)
+        })
+        .collect();
+    // Newest first; ties fall back to pack_id so runs are deterministic.
+    packs.sort_by(|(a, a_time), (b, b_time)| {
+        b_time.cmp(a_time).then_with(|| a.pack_id.cmp(&b.pack_id))
+    });
+
+    let store = FilesystemStore::new(root);
+    let mut reasons: Vec<Option<KeepReason>> = packs
+        .iter()
+        .enumerate()
+        .map(|(rank, (entry, created))| {
+            if retained.contains(&entry.pack_id) {
+                Some(KeepReason::Retained)
+            } else if options.keep_last.is_some_and(|keep_last| rank < keep_last) {
+                Some(KeepReason::KeepLast)
+            } else if let Some(created) = created {
+                keep_since
+                    .is_some_and(|since| *created >= since)
+                    .then_some(KeepReason::KeepSince)
+            } else {
+                Some(KeepReason::Undated)
+            }
+        })
+        .collect();
+    // A kept pack's lineage must stay resolvable, so its parents (and
+    // theirs) are kept too.
+    let rank_of: BTreeMap<&str, usize> = packs
+        .iter()
+        .enumerate()
+        .map(|(rank, (entry, _))| (entry.pack_id.as_str(), rank))
+        .collect();
+    let mut pending: Vec<usize> = (0..packs.len()).filter(|&i| reasons[i].is_some()).collect();
+    while let Some(rank) = pending.pop() {
+        for parent in lineage_parents(&store, &packs[rank].0.pack_id) {
+            if let Some(&parent_rank) = rank_of.get(parent.as_str()) {
+                if reasons[parent_rank].is_none() {
+                    reasons[parent_rank] = Some(KeepReason::Lineage);
+                    pending.push(parent_rank);
+                }
+            }
+        }
+    }
+
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    let mut doomed = Vec::new();
+    for ((entry, _), reason) in packs.iter().zip(reasons) {
+        let Some(pack_dir) = store.pack_dir(&entry.pack_id) else {
+            continue;
+        };
+        match reason {
+            None => {
+                removed.push(RemovedPack {
+                    pack_id: entry.pack_id.clone(),
+                    created: entry.created.clone(),
+                    bytes: dir_size(&pack_dir).map_err(|e| {
+                        refusal(root, format!("Cannot measure {}: {e}", pack_dir.display()))
+                    })?,
+                });
+                doomed.push(pack_dir);
+            }
+            Some(reason) => kept.push(KeptPack {
+                pack_id: entry.pack_id.clone(),
+                created: entry.created.clone(),
+                reason,
+            }),
+        }
+    }
+
+    if !options.dry_run && !doomed.is_empty() {
+        for pack_dir in &doomed {
+            remove_pack_dir(pack_dir)
+                .map_err(|e| refusal(root, format!("Cannot remove {}: {e}", pack_dir.display())))?;
+        }
+        update_index(root).map_err(|e| {
+            refusal(
+                root,
+                format!("Cannot update store index: {}: {e}", root.display()),
+            )
+        })?;
+    }
+
+    Ok(GcReport {
+        version: GC_REPORT_VERSION.to_string(),
+        store: root.display().to_string(),
+        dry_run: options.dry_run,
+        packs: index.packs.len(),
+        kept,
+        reclaimed_bytes: removed.iter().map(|pack| pack.bytes).sum(),
+        removed,
+    })
+}
+
+/// `lineage.parent_pack_ids` of a stored pack; none if its manifest cannot
+/// be read.
+fn lineage_parents(store: &FilesystemStore, pack_id: &str) -> Vec<String> {
+    store
+        .pack_dir(pack_id)
+        .and_then(|dir| fs::read(dir.join("manifest.json")).ok())
+        .and_then(|bytes| serde_json::from_slice::<Manifest>(&bytes).ok())
+        .and_then(|manifest| manifest.lineage)
+        .map(|lineage| lineage.parent_pack_ids)
+        .unwrap_or_default()
+}
+
+/// `--keep-since`: an RFC3339 timestamp, or a `YYYY-MM-DD` date meaning
+/// midnight UTC.
+fn parse_keep_since(raw: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+        .ok_or_else(|| {
+            format!("Invalid --keep-since {raw:?}: expected an RFC3339 timestamp or YYYY-MM-DD")
+        })
+}
+
+/// Pack IDs listed one per line; blank lines and `#` comments are skipped.
+fn load_retention_list(path: &Path) -> Result<BTreeSet<String>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read retention list: {}: {e}", path.display()))?;
+    let probe = FilesystemStore::new("");
+    let mut retained = BTreeSet::new();
+    for (number, line) in content.lines().enumerate() {
+        let pack_id = line.split('#').next().unwrap_or_default().trim();
+        if pack_id.is_empty() {
+            continue;
+        }
+        if probe.pack_dir(pack_id).is_none() {
+            return Err(format!(
+                "Invalid pack_id in retention list: {}:{}: {pack_id:?}",
+                path.display(),
+                number + 1
+            ));
+        }
+        retained.insert(pack_id.to_string());
+    }
+    Ok(retained)
+}
+
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut total = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += dir_size(&entry.path())?;
+        } else {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+fn remove_pack_dir(pack_dir: &Path) -> io::Result<()> {
+    let parent = pack_dir.parent().expect("store pack dirs have a parent");
+    let name = pack_dir.file_name().unwrap_or_default().to_string_lossy();
+    let trash = parent.join(format!(".pack-gc-{name}"));
+    fs::rename(pack_dir, &trash)?;
+    fs::remove_dir_all(&trash)
+}
+
+fn refusal(root: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "store": root.display().to_string() })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::store::PackTransport;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    #[test]
+    fn packs_no_rule_keeps_are_removed_and_retained_packs_survive() {
+        let src = TempDir::new().unwrap();
+        let store_root = src.path().join("store");
+        let store = FilesystemStore::new(&store_root);
+        let mut ids = Vec::new();
+        for (index, created) in [
+            "2026-01-01T00:00:00Z",
+            "2026-02-01T00:00:00Z",
+            "2026-03-01T00:00:00Z",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let artifact = src.path().join(format!("{index}.json"));
+            fs::write(&artifact, index.to_string()).unwrap();
+            let out = src.path().join(format!("pack{index}"));
+            execute_seal(std::slice::from_ref(&artifact), Some(&out), None).unwrap();
+            let mut manifest: Manifest =
+                serde_json::from_slice(&fs::read(out.join("manifest.json")).unwrap()).unwrap();
+            store.publish(&out, &manifest).unwrap();
+            // Backdate the stored manifest so the index sees distinct ages.
+            manifest.created = created.to_string();
+            let dir = store.pack_dir(&manifest.pack_id).unwrap();
+            fs::write(
+                dir.join("manifest.json"),
+                serde_json::to_vec(&manifest).unwrap(),
+            )
+            .unwrap();
+            ids.push(manifest.pack_id);
+        }
+        fs::remove_file(store_root.join("index.json")).unwrap();
+
+        let retain = src.path().join("retain.txt");
+        fs::write(&retain, format!("# audit hold\n{}\n", ids[0])).unwrap();
+        let options = GcOptions {
+            keep_last: Some(1),
+            retain: Some(retain),
+            dry_run: true,
+            ..GcOptions::default()
+        };
+        let report = store_gc(&store_root, &options).unwrap();
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].pack_id, ids[1]);
+        assert!(report.reclaimed_bytes > 0);
+        let reasons: Vec<_> = report.kept.iter().map(|pack| pack.reason).collect();
+        assert_eq!(reasons, [KeepReason::KeepLast, KeepReason::Retained]);
+        assert!(store.pack_dir(&ids[1]).unwrap().is_dir());
+
+        let options = GcOptions {
+            dry_run: false,
+            keep_since: Some("2026-02-01".to_string()),
+            ..options
+        };
+        let report = store_gc(&store_root, &options).unwrap();
+        assert!(report.removed.is_empty());
+
+        let options = GcOptions {
+            keep_since: None,
+            ..options
+        };
+        let report = store_gc(&store_root, &options).unwrap();
+        assert_eq!(report.removed[0].pack_id, ids[1]);
+        assert!(!store.pack_dir(&ids[1]).unwrap().exists());
+        assert_eq!(update_index(&store_root).unwrap().packs.len(), 2);
+
+        let bad = GcOptions {
+            keep_since: Some("last tuesday".to_string()),
+            ..GcOptions::default()
+        };
+        assert_eq!(
+            store_gc(&store_root, &bad).unwrap_err().refusal.code,
+            "E_IO"
+        );
+    }
+
+    #[test]
+    fn lineage_parents_of_kept_packs_are_kept() {
+        use crate::seal::command::{execute_seal_with_options, SealOptions};
+
+        let src = TempDir::new().unwrap();
+        let store_root = src.path().join("store");
+        let store = FilesystemStore::new(&store_root);
+        let publish = |name: &str, merge: Option<PathBuf>, created: &str| {
+            let artifact = src.path().join(format!("{name}.json"));
+            fs::write(&artifact, name).unwrap();
+            let out = src.path().join(name);
+            let options = SealOptions {
+                merge,
+                ..SealOptions::default()
+            };
+            execute_seal_with_options(&[artifact], Some(&out), None, &options).unwrap();
+            let mut manifest: Manifest =
+                serde_json::from_slice(&fs::read(out.join("manifest.json")).unwrap()).unwrap();
+            store.publish(&out, &manifest).unwrap();
+            manifest.created = created.to_string();
+            let dir = store.pack_dir(&manifest.pack_id).unwrap();
+            fs::write(
+                dir.join("manifest.json"),
+                serde_json::to_vec(&manifest).unwrap(),
+            )
+            .unwrap();
+            (out, manifest.pack_id)
+        };
+        let (grandparent_dir, grandparent) = publish("a", None, "2026-01-01T00:00:00Z");
+        let (parent_dir, parent) = publish("b", Some(grandparent_dir), "2026-02-01T00:00:00Z");
+        let (_, unrelated) = publish("c", None, "2026-03-01T00:00:00Z");
+        let (_, child) = publish("d", Some(parent_dir), "2026-04-01T00:00:00Z");
+        fs::remove_file(store_root.join("index.json")).unwrap();
+
+        let options = GcOptions {
+            keep_last: Some(1),
+            ..GcOptions::default()
+        };
+        let report = store_gc(&store_root, &options).unwrap();
+        let kept: Vec<_> = report
+            .kept
+            .iter()
+            .map(|pack| (pack.pack_id.as_str(), pack.reason))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                (child.as_str(), KeepReason::KeepLast),
+                (parent.as_str(), KeepReason::Lineage),
+                (grandparent.as_str(), KeepReason::Lineage),
+            ]
+        );
+        let removed: Vec<_> = report.removed.iter().map(|pack| &pack.pack_id).collect();
+        assert_eq!(removed, [&unrelated]);
+        assert!(store.pack_dir(&grandparent).unwrap().is_dir());
+    }
+}
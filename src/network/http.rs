@@ -0,0 +1,585 @@
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::collect::is_safe_member_path;
+use crate::seal::copy::set_executable;
+use crate::seal::manifest::Manifest;
+use crate::sign::SIGNATURE_FILE;
+use crate::timestamp::TIMESTAMP_FILE;
+
+use super::store::{PackTransport, PublishOutcome};
+use super::transport::{refusal_for_transport, TransportError};
+
+/// Store spec prefix selecting [`HttpStore`]: `cas+https://host/path`.
+pub const HTTP_STORE_SCHEME: &str = "cas+";
+
+/// Bearer token sent to an HTTP store.
+pub const PACK_STORE_TOKEN_ENV: &str = "PACK_STORE_TOKEN";
+
+/// Members larger than this (8 MiB) are uploaded in resumable chunks.
+pub const DEFAULT_CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Attempts per request before a transient failure refuses.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Content-addressed HTTP store.
+///
+/// Member bytes are blobs at `{base}/blobs/{bytes_hash}` (the
+/// `ciphertext_hash` of an encrypted member), shared by every pack that holds
+/// them, and each pack is its `{base}/packs/{pack_id}/manifest.json`
+/// (with `manifest.sig.json` beside it when signed). A push uploads the blobs
+/// the store lacks and then the manifest, so a pack is only visible once all
+/// of its bytes are. Members over the chunk size go through
+/// `{base}/uploads/{bytes_hash}`: `HEAD` reports the `Upload-Offset` received
+/// so far and each `PATCH` appends one chunk at that offset, so an interrupted
+/// upload continues where it stopped.
+///
+/// Network errors, `429`, and `5xx` responses are retried with exponential
+/// backoff.
+#[derive(Debug, Clone)]
+pub struct HttpStore {
+    base_url: String,
+    token: Option<String>,
+    timeout: Duration,
+    chunk_bytes: u64,
+    backoff: Duration,
+}
+
+impl HttpStore {
+    pub fn new(base_url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.filter(|token| !token.trim().is_empty()),
+            timeout: Duration::from_secs(60),
+            chunk_bytes: DEFAULT_CHUNK_BYTES,
+            backoff: Duration::from_millis(500),
+        }
+    }
+
+    pub fn with_chunk_bytes(mut self, chunk_bytes: u64) -> Self {
+        self.chunk_bytes = chunk_bytes.max(1);
+        self
+    }
+
+    /// Delay before the first retry; each later retry waits twice as long.
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(self.timeout)
+            .timeout_read(self.timeout)
+            .timeout_write(self.timeout)
+            .build();
+        let request = agent.request(method, &format!("{}/{path}", self.base_url));
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        }
+    }
+
+    /// Run `send` until it succeeds, fails permanently, or runs out of
+    /// attempts. `send` is given the attempt number, starting at 0.
+    fn with_retry<T>(
+        &self,
+        mut send: impl FnMut(u32) -> Result<T, StoreError>,
+    ) -> Result<T, StoreError> {
+        let mut attempt = 0;
+        loop {
+            match send(attempt) {
+                Err(error) if error.is_transient() && attempt + 1 < MAX_ATTEMPTS => {
+                    thread::sleep(self.backoff * 2u32.pow(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// `GET` or `HEAD` `path`; a `404` is `None`.
+    fn fetch_path(&self, method: &str, path: &str) -> Result<Option<ureq::Response>, StoreError> {
+        match self.with_retry(|_| Ok(self.request(method, path).call()?)) {
+            Ok(response) => Ok(Some(response)),
+            Err(StoreError::Http(error)) if matches!(*error, ureq::Error::Status(404, _)) => {
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn exists(&self, path: &str) -> Result<bool, StoreError> {
+        Ok(self.fetch_path("HEAD", path)?.is_some())
+    }
+
+    fn put_file(&self, path: &str, file: &Path) -> Result<(), StoreError> {
+        self.with_retry(|_| {
+            let reader = fs::File::open(file).map_err(|e| StoreError::local(file, e))?;
+            Ok(self.request("PUT", path).send(reader)?)
+        })
+        .map(drop)
+    }
+
+    /// Bytes of `hash` the store has received so far.
+    fn upload_offset(&self, hash: &str) -> Result<u64, StoreError> {
+        Ok(self
+            .fetch_path("HEAD", &upload_path(hash))?
+            .map_or(0, |response| offset_header(&response)))
+    }
+
+    /// Upload `file` in chunks, starting from what the store already holds.
+    fn upload_chunks(&self, hash: &str, file: &Path, total: u64) -> Result<(), StoreError> {
+        let mut source = fs::File::open(file).map_err(|e| StoreError::local(file, e))?;
+        let mut offset = self.upload_offset(hash)?;
+        while offset < total {
+            offset = self.with_retry(|attempt| {
+                // A failed PATCH may still have landed part of the chunk.
+                let start = if attempt == 0 {
+                    offset
+                } else {
+                    self.upload_offset(hash)?
+                };
+                let len = self.chunk_bytes.min(total - start);
+                let mut chunk = Vec::with_capacity(len as usize);
+                source
+                    .seek(SeekFrom::Start(start))
+                    .and_then(|_| (&mut source).take(len).read_to_end(&mut chunk))
+                    .map_err(|e| StoreError::local(file, e))?;
+                let response = self
+                    .request("PATCH", &upload_path(hash))
+                    .set("Upload-Offset", &start.to_string())
+                    .set("Upload-Length", &total.to_string())
+                    .set("Content-Type", "application/offset+octet-stream")
+                    .send_bytes(&chunk)?;
+                Ok(match response.header("Upload-Offset") {
+                    Some(_) => offset_header(&response),
+                    None => start + len,
+                })
+            })?;
+        }
+        Ok(())
+    }
+
+    fn store_pack(
+        &self,
+        pack_dir: &Path,
+        manifest: &Manifest,
+    ) -> Result<PublishOutcome, StoreError> {
+        let pack = pack_path(&manifest.pack_id);
+        if self.exists(&format!("{pack}/manifest.json"))? {
+            return Ok(PublishOutcome::AlreadyPresent);
+        }
+        for member in &manifest.members {
+            let blob = blob_path(member.stored_hash());
+            if self.exists(&blob)? {
+                continue;
+            }
+            let source = pack_dir.join(&member.path);
+            let size = fs::metadata(&source)
+                .map_err(|e| StoreError::local(&source, e))?
+                .len();
+            if size > self.chunk_bytes {
+                self.upload_chunks(member.stored_hash(), &source, size)?;
+            } else {
+                self.put_file(&blob, &source)?;
+            }
+        }
+        for sidecar in [SIGNATURE_FILE, TIMESTAMP_FILE] {
+            if pack_dir.join(sidecar).is_file() {
+                self.put_file(&format!("{pack}/{sidecar}"), &pack_dir.join(sidecar))?;
+            }
+        }
+        self.put_file(
+            &format!("{pack}/manifest.json"),
+            &pack_dir.join("manifest.json"),
+        )?;
+        Ok(PublishOutcome::Published)
+    }
+
+    fn download(&self, path: &str, dest: &Path) -> Result<bool, Box<RefusalEnvelope>> {
+        let Some(response) = self
+            .fetch_path("GET", path)
+            .map_err(|e| self.refusal("pull", e))?
+        else {
+            return Ok(false);
+        };
+        if let Some(dir) = dest.parent() {
+            fs::create_dir_all(dir).map_err(|e| write_refusal(dest, e))?;
+        }
+        let mut file = fs::File::create(dest).map_err(|e| write_refusal(dest, e))?;
+        io::copy(&mut response.into_reader(), &mut file).map_err(|e| write_refusal(dest, e))?;
+        Ok(true)
+    }
+
+    fn parse_manifest(
+        &self,
+        pack_id: &str,
+        bytes: &[u8],
+    ) -> Result<Manifest, Box<RefusalEnvelope>> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::BadPack,
+                Some(format!("Invalid manifest.json in store for {pack_id}: {e}")),
+                Some(json!({ "store": self.base_url, "pack_id": pack_id })),
+            ))
+        })
+    }
+
+    fn refusal(&self, action: &str, error: StoreError) -> Box<RefusalEnvelope> {
+        let error = match error {
+            StoreError::Local(message) => {
+                return Box::new(RefusalEnvelope::new(
+                    RefusalCode::Io,
+                    Some(message),
+                    Some(json!({ "action": action, "store": self.base_url })),
+                ))
+            }
+            StoreError::Http(error) => *error,
+        };
+        let error = match error {
+            ureq::Error::Status(status, response) => TransportError::Server {
+                status,
+                body: response
+                    .into_string()
+                    .ok()
+                    .map(|text| serde_json::from_str(&text).unwrap_or(Value::String(text))),
+            },
+            ureq::Error::Transport(error) => TransportError::Network {
+                message: error.to_string(),
+            },
+        };
+        let mut envelope = refusal_for_transport(action, &error);
+        if let Some(detail) = envelope.refusal.detail.as_mut() {
+            detail["store"] = json!(self.base_url);
+        }
+        Box::new(envelope)
+    }
+}
+
+impl PackTransport for HttpStore {
+    fn publish(
+        &self,
+        pack_dir: &Path,
+        manifest: &Manifest,
+    ) -> Result<PublishOutcome, Box<RefusalEnvelope>> {
+        self.store_pack(pack_dir, manifest)
+            .map_err(|e| self.refusal("push", e))
+    }
+
+    fn fetch(&self, pack_id: &str, dest: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+        // The manifest is downloaded once and parsed from the staged copy,
+        // so the manifest that is verified is the one that lands on disk.
+        let pack = pack_path(pack_id);
+        let manifest_path = dest.join("manifest.json");
+        if !self.download(&format!("{pack}/manifest.json"), &manifest_path)? {
+            return Err(not_found(pack_id, &self.base_url));
+        }
+        let bytes = fs::read(&manifest_path).map_err(|e| write_refusal(&manifest_path, e))?;
+        let manifest = self.parse_manifest(pack_id, &bytes)?;
+        for sidecar in [SIGNATURE_FILE, TIMESTAMP_FILE] {
+            self.download(&format!("{pack}/{sidecar}"), &dest.join(sidecar))?;
+        }
+        for member in &manifest.members {
+            if !is_safe_member_path(&member.path) {
+                return Err(Box::new(RefusalEnvelope::new(
+                    RefusalCode::BadPack,
+                    Some(format!(
+                        "Unsafe member path in fetched manifest: {}",
+                        member.path
+                    )),
+                    Some(json!({ "pack_id": pack_id, "path": member.path })),
+                )));
+            }
+            let target = dest.join(&member.path);
+            if !self.download(&blob_path(member.stored_hash()), &target)? {
+                return Err(Box::new(RefusalEnvelope::new(
+                    RefusalCode::Io,
+                    Some(format!(
+                        "Store is missing member {} ({})",
+                        member.path,
+                        member.stored_hash()
+                    )),
+                    Some(json!({ "store": self.base_url, "pack_id": pack_id })),
+                )));
+            }
+            if member.executable == Some(true) {
+                set_executable(&target).map_err(|e| write_refusal(&target, e))?;
+            }
+        }
+        Ok(manifest)
+    }
+
+    fn fetch_manifest(&self, pack_id: &str) -> Result<Manifest, Box<RefusalEnvelope>> {
+        let response = self
+            .fetch_path("GET", &format!("{}/manifest.json", pack_path(pack_id)))
+            .map_err(|e| self.refusal("pull", e))?
+            .ok_or_else(|| not_found(pack_id, &self.base_url))?;
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .map_err(|e| {
+                let message = format!("Cannot read manifest.json for {pack_id}: {e}");
+                self.refusal("pull", StoreError::Local(message))
+            })?;
+        self.parse_manifest(pack_id, &bytes)
+    }
+}
+
+fn pack_path(pack_id: &str) -> String {
+    format!("packs/{pack_id}")
+}
+
+fn blob_path(bytes_hash: &str) -> String {
+    format!("blobs/{bytes_hash}")
+}
+
+fn upload_path(bytes_hash: &str) -> String {
+    format!("uploads/{bytes_hash}")
+}
+
+fn offset_header(response: &ureq::Response) -> u64 {
+    response
+        .header("Upload-Offset")
+        .and_then(|offset| offset.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// A failed request, or a local file that could not be read for one.
+enum StoreError {
+    Http(Box<ureq::Error>),
+    Local(String),
+}
+
+impl StoreError {
+    fn local(path: &Path, error: io::Error) -> Self {
+        Self::Local(format!("Cannot read {}: {error}", path.display()))
+    }
+
+    /// Network failures, `429`, and `5xx` are worth retrying.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Http(error) => match error.as_ref() {
+                ureq::Error::Status(status, _) => *status == 429 || *status >= 500,
+                ureq::Error::Transport(_) => true,
+            },
+            Self::Local(_) => false,
+        }
+    }
+}
+
+impl From<ureq::Error> for StoreError {
+    fn from(error: ureq::Error) -> Self {
+        Self::Http(Box::new(error))
+    }
+}
+
+fn not_found(pack_id: &str, store: &str) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(format!("Pack not found in store: {pack_id}")),
+        Some(json!({ "store": store, "pack_id": pack_id })),
+    ))
+}
+
+fn write_refusal(path: &Path, error: io::Error) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(format!("Cannot write {}: {error}", path.display())),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+    use tiny_http::{Header, Response, Server};
+
+    #[derive(Default)]
+    struct CasState {
+        files: BTreeMap<String, Vec<u8>>,
+        uploads: BTreeMap<String, Vec<u8>>,
+        patched_bytes: usize,
+        /// Answer the next PATCH with 503 after keeping its bytes.
+        fail_next_patch: bool,
+        /// Files replaced by the given body once they have been served, as
+        /// by a store that changes between requests.
+        swap_after_get: BTreeMap<String, Vec<u8>>,
+    }
+
+    /// An in-memory store speaking the `HttpStore` protocol.
+    fn spawn_store(state: Arc<Mutex<CasState>>) -> (Arc<Server>, thread::JoinHandle<()>) {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let handle = thread::spawn({
+            let server = Arc::clone(&server);
+            move || {
+                for mut request in server.incoming_requests() {
+                    let authorized = request.headers().iter().any(|h| {
+                        h.field.equiv("Authorization") && h.value.as_str() == "Bearer t0ken"
+                    });
+                    let path = request.url().trim_start_matches('/').to_string();
+                    let mut body = Vec::new();
+                    request.as_reader().read_to_end(&mut body).unwrap();
+                    let mut state = state.lock().unwrap();
+                    let method = request.method().as_str().to_string();
+                    let response = if !authorized {
+                        Response::from_data(Vec::new()).with_status_code(401)
+                    } else if let Some(hash) = path.strip_prefix("uploads/") {
+                        let received = state.uploads.entry(hash.to_string()).or_default();
+                        if method == "PATCH" {
+                            received.extend_from_slice(&body);
+                        }
+                        let offset = received.len();
+                        let header =
+                            Header::from_bytes("Upload-Offset", offset.to_string()).unwrap();
+                        if method == "PATCH" {
+                            state.patched_bytes += body.len();
+                            let total: usize = request
+                                .headers()
+                                .iter()
+                                .find(|h| h.field.equiv("Upload-Length"))
+                                .unwrap()
+                                .value
+                                .as_str()
+                                .parse()
+                                .unwrap();
+                            if offset == total {
+                                let blob = state.uploads.remove(hash).unwrap();
+                                state.files.insert(format!("blobs/{hash}"), blob);
+                            }
+                        }
+                        if std::mem::take(&mut state.fail_next_patch) && method == "PATCH" {
+                            Response::from_data(Vec::new()).with_status_code(503)
+                        } else {
+                            Response::from_data(Vec::new()).with_header(header)
+                        }
+                    } else {
+                        match (method.as_str(), state.files.get(&path)) {
+                            ("PUT", _) => {
+                                state.files.insert(path, body);
+                                Response::from_data(Vec::new()).with_status_code(201)
+                            }
+                            ("GET", Some(data)) => {
+                                let response = Response::from_data(data.clone());
+                                if let Some(swapped) = state.swap_after_get.remove(&path) {
+                                    state.files.insert(path, swapped);
+                                }
+                                response
+                            }
+                            ("HEAD", Some(data)) => Response::from_data(data.clone()),
+                            _ => Response::from_data(Vec::new()).with_status_code(404),
+                        }
+                    };
+                    request.respond(response).unwrap();
+                }
+            }
+        });
+        (server, handle)
+    }
+
+    #[test]
+    fn push_resumes_chunked_uploads_and_pull_reassembles_the_pack() {
+        let tmp = TempDir::new().unwrap();
+        let big = tmp.path().join("tape.csv");
+        fs::write(&big, "loan,balance\n1,100\n2,250\n3,975\n").unwrap();
+        let small = tmp.path().join("a.json");
+        fs::write(&small, r#"{"a": 1}"#).unwrap();
+        let pack_dir = tmp.path().join("pack");
+        execute_seal(&[big, small], Some(&pack_dir), None).unwrap();
+        let manifest: Manifest =
+            serde_json::from_str(&fs::read_to_string(pack_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        let tape = manifest
+            .members
+            .iter()
+            .find(|m| m.path == "tape.csv")
+            .unwrap();
+
+        // An earlier push got the first 8 bytes of the tape through.
+        let state = Arc::new(Mutex::new(CasState::default()));
+        state
+            .lock()
+            .unwrap()
+            .uploads
+            .insert(tape.bytes_hash.clone(), b"loan,bal".to_vec());
+        state.lock().unwrap().fail_next_patch = true;
+        let (server, handle) = spawn_store(Arc::clone(&state));
+        let base_url = format!("http://{}", server.server_addr());
+        let store = HttpStore::new(&base_url, Some("t0ken".to_string()))
+            .with_chunk_bytes(8)
+            .with_backoff(Duration::ZERO);
+
+        let outcome = store.publish(&pack_dir, &manifest).unwrap();
+        assert_eq!(outcome, PublishOutcome::Published);
+        assert_eq!(
+            state.lock().unwrap().patched_bytes as u64,
+            tape.size_bytes.unwrap() - 8
+        );
+        assert_eq!(
+            store.publish(&pack_dir, &manifest).unwrap(),
+            PublishOutcome::AlreadyPresent
+        );
+
+        let dest = tmp.path().join("pulled");
+        fs::create_dir(&dest).unwrap();
+        assert_eq!(store.fetch(&manifest.pack_id, &dest).unwrap(), manifest);
+        assert_eq!(
+            fs::read(dest.join("tape.csv")).unwrap(),
+            fs::read(pack_dir.join("tape.csv")).unwrap()
+        );
+
+        let anonymous = HttpStore::new(&base_url, None).with_backoff(Duration::ZERO);
+        let refusal = anonymous.fetch_manifest(&manifest.pack_id).unwrap_err();
+        assert_eq!(refusal.refusal.detail.as_ref().unwrap()["status"], 401);
+
+        server.unblock();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn fetch_returns_the_manifest_it_staged() {
+        let tmp = TempDir::new().unwrap();
+        let artifact = tmp.path().join("a.json");
+        fs::write(&artifact, r#"{"a": 1}"#).unwrap();
+        let pack_dir = tmp.path().join("pack");
+        execute_seal(&[artifact], Some(&pack_dir), None).unwrap();
+        let staged_bytes = fs::read(pack_dir.join("manifest.json")).unwrap();
+        let manifest: Manifest = serde_json::from_slice(&staged_bytes).unwrap();
+
+        let state = Arc::new(Mutex::new(CasState::default()));
+        let (server, handle) = spawn_store(Arc::clone(&state));
+        let base_url = format!("http://{}", server.server_addr());
+        let store =
+            HttpStore::new(&base_url, Some("t0ken".to_string())).with_backoff(Duration::ZERO);
+        store.publish(&pack_dir, &manifest).unwrap();
+
+        // A second GET of the manifest would see a different pack.
+        let mut other = manifest.clone();
+        other.note = Some("swapped".to_string());
+        state.lock().unwrap().swap_after_get.insert(
+            format!("packs/{}/manifest.json", manifest.pack_id),
+            serde_json::to_vec(&other).unwrap(),
+        );
+
+        let dest = tmp.path().join("pulled");
+        fs::create_dir(&dest).unwrap();
+        let fetched = store.fetch(&manifest.pack_id, &dest).unwrap();
+        let on_disk = fs::read(dest.join("manifest.json")).unwrap();
+        assert_eq!(on_disk, staged_bytes);
+        assert_eq!(fetched, manifest);
+
+        server.unblock();
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::lockfile::FileLock;
+use crate::seal::manifest::Manifest;
+
+use super::deprecate::Deprecation;
+
+/// Index file kept at the root of a filesystem store.
+pub const INDEX_FILE: &str = "index.json";
+
+/// Index schema version.
+pub const INDEX_VERSION: &str = "pack.index.v0";
+
+const LOCK_FILE: &str = ".index.lock";
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `index.json`: one entry per pack held by the store, sorted by `pack_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreIndex {
+    pub version: String,
+    pub packs: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub pack_id: String,
+    pub created: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub member_count: usize,
+    /// Set by `pack deprecate`; the pack itself is never modified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<Deprecation>,
+}
+
+impl From<&Manifest> for IndexEntry {
+    fn from(manifest: &Manifest) -> Self {
+        Self {
+            pack_id: manifest.pack_id.clone(),
+            created: manifest.created.clone(),
+            note: manifest.note.clone(),
+            member_count: manifest.member_count,
+            deprecation: None,
+        }
+    }
+}
+
+/// Reconcile `<root>/index.json` with the packs on disk.
+///
+/// Entries for packs that are no longer present are dropped, and packs missing
+/// from the index are read back from their manifests, so a crashed or manual
+/// change to the store is repaired on the next update. The file is replaced
+/// atomically under an exclusive lock.
+pub fn update_index(root: &Path) -> io::Result<StoreIndex> {
+    let _lock = lock_index(root)?;
+    let entries = reconcile_entries(root)?;
+    let index = StoreIndex {
+        version: INDEX_VERSION.to_string(),
+        packs: entries.into_values().collect(),
+    };
+    write_index(root, &index)?;
+    Ok(index)
+}
+
+/// Record `deprecation` on the index entry for `pack_id`, reconciling the index
+/// first. Returns `Ok(None)` when the store does not hold the pack.
+pub fn deprecate_in_index(
+    root: &Path,
+    pack_id: &str,
+    deprecation: Deprecation,
+) -> io::Result<Option<IndexEntry>> {
+    let _lock = lock_index(root)?;
+    let mut entries = reconcile_entries(root)?;
+    let Some(entry) = entries.get_mut(pack_id) else {
+        return Ok(None);
+    };
+    entry.deprecation = Some(deprecation);
+    let updated = entry.clone();
+    let index = StoreIndex {
+        version: INDEX_VERSION.to_string(),
+        packs: entries.into_values().collect(),
+    };
+    write_index(root, &index)?;
+    Ok(Some(updated))
+}
+
+/// Index entries merged with the packs on disk. Callers hold the index lock.
+fn reconcile_entries(root: &Path) -> io::Result<BTreeMap<String, IndexEntry>> {
+    let mut entries: BTreeMap<String, IndexEntry> = read_index(root)
+        .map(|index| {
+            index
+                .packs
+                .into_iter()
+                .map(|entry| (entry.pack_id.clone(), entry))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let on_disk = stored_pack_ids(root)?;
+    entries.retain(|pack_id, _| on_disk.contains_key(pack_id));
+    for (pack_id, dir) in on_disk {
+        if entries.contains_key(&pack_id) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(dir.join("manifest.json")) else {
+            continue;
+        };
+        if let Ok(manifest) = serde_json::from_str::<Manifest>(&content) {
+            if manifest.pack_id == pack_id {
+                entries.insert(pack_id, IndexEntry::from(&manifest));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Read `<root>/index.json`, or `None` when it is missing or unreadable.
+pub fn read_index(root: &Path) -> Option<StoreIndex> {
+    let content = fs::read_to_string(root.join(INDEX_FILE)).ok()?;
+    serde_json::from_str::<StoreIndex>(&content)
+        .ok()
+        .filter(|index| index.version == INDEX_VERSION)
+}
+
+/// Complete packs under `<root>/sha256/`, keyed by pack_id. Staging
+/// directories and entries without a manifest are skipped.
+fn stored_pack_ids(root: &Path) -> io::Result<BTreeMap<String, PathBuf>> {
+    let mut packs = BTreeMap::new();
+    let dir = root.join("sha256");
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(packs),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let Some(hex) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if hex.starts_with('.') || !entry.path().join("manifest.json").is_file() {
+            continue;
+        }
+        packs.insert(format!("sha256:{hex}"), entry.path());
+    }
+    Ok(packs)
+}
+
+/// Exclusive lock on `.index.lock` in the store root, held until drop. A
+/// crashed `push` or `pull` releases it on exit.
+fn lock_index(root: &Path) -> io::Result<FileLock> {
+    fs::create_dir_all(root)?;
+    FileLock::acquire(&root.join(LOCK_FILE), LOCK_TIMEOUT)
+}
+
+fn write_index(root: &Path, index: &StoreIndex) -> io::Result<()> {
+    let mut bytes = serde_json::to_vec_pretty(index).map_err(io::Error::other)?;
+    bytes.push(b'\n');
+    let mut tmp = tempfile::Builder::new()
+        .prefix(".index-")
+        .tempfile_in(root)?;
+    tmp.write_all(&bytes)?;
+    tmp.as_file().sync_all()?;
+    tmp.persist(root.join(INDEX_FILE)).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn stored_pack(root: &Path, hex_char: char, note: Option<&str>) -> String {
+        let mut manifest = Manifest::new(
+            "2026-01-01T00:00:00Z".to_string(),
+            note.map(str::to_string),
+            "pack 0.0.0".to_string(),
+            vec![],
+        );
+        let hex: String = std::iter::repeat_n(hex_char, 64).collect();
+        manifest.pack_id = format!("sha256:{hex}");
+        let dir = root.join("sha256").join(&hex);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_string(&manifest).unwrap(),
+        )
+        .unwrap();
+        manifest.pack_id
+    }
+
+    #[test]
+    fn reconciles_added_and_removed_packs() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let a = stored_pack(root, 'a', Some("first"));
+        let b = stored_pack(root, 'b', None);
+
+        let index = update_index(root).unwrap();
+        let ids: Vec<_> = index.packs.iter().map(|e| e.pack_id.as_str()).collect();
+        assert_eq!(ids, vec![a.as_str(), b.as_str()]);
+        assert_eq!(index.packs[0].note.as_deref(), Some("first"));
+        assert_eq!(read_index(root).unwrap(), index);
+
+        fs::remove_dir_all(root.join("sha256").join(&a[7..])).unwrap();
+        let index = update_index(root).unwrap();
+        assert_eq!(index.packs.len(), 1);
+        assert_eq!(index.packs[0].pack_id, b);
+    }
+
+    #[test]
+    fn corrupt_index_is_rebuilt_from_disk() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let a = stored_pack(root, 'c', None);
+        fs::write(root.join(INDEX_FILE), "{not json").unwrap();
+        // A crashed push leaves its lock file behind, but not the lock.
+        fs::write(root.join(LOCK_FILE), "").unwrap();
+
+        let index = update_index(root).unwrap();
+        assert_eq!(index.packs.len(), 1);
+        assert_eq!(index.packs[0].pack_id, a);
+    }
+
+    #[test]
+    fn deprecation_survives_reconciliation() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        let a = stored_pack(root, 'd', None);
+        let deprecation = Deprecation::new(&a, "superseded", None);
+
+        let entry = deprecate_in_index(root, &a, deprecation.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.deprecation.as_ref(), Some(&deprecation));
+        stored_pack(root, 'e', None);
+        let index = update_index(root).unwrap();
+        assert_eq!(index.packs[0].deprecation, Some(deprecation));
+        assert_eq!(index.packs[1].deprecation, None);
+
+        let missing = format!("sha256:{}", "f".repeat(64));
+        let none = deprecate_in_index(root, &missing, Deprecation::new(&missing, "x", None));
+        assert!(none.unwrap().is_none());
+    }
+}
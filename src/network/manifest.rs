@@ -0,0 +1,150 @@
+use std::fmt;
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::manifest::Manifest;
+
+use super::store::resolve_store;
+
+/// A pack addressed in a store: `<PACK_ID>[@<STORE>]`, as given to
+/// `pack diff --remote`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemotePack {
+    pub pack_id: String,
+    /// Store spec as for `pull --from`; `None` falls back to `PACK_STORE`, then
+    /// `PACK_DATA_FABRIC_BASE_URL`.
+    pub store: Option<String>,
+}
+
+impl RemotePack {
+    /// Parse a `--remote PACK_ID[@STORE]` argument. The pack ID is split at the
+    /// first `@`, so store URLs may themselves contain `@`.
+    pub fn from_flag(raw: &str) -> Result<Self, String> {
+        let (pack_id, store) = match raw.split_once('@') {
+            Some((pack_id, store)) if !store.is_empty() => (pack_id, Some(store.to_string())),
+            Some(_) => return Err(format!("--remote store is empty in {raw:?}")),
+            None => (raw, None),
+        };
+        let valid = pack_id.strip_prefix("sha256:").is_some_and(|hex| {
+            hex.len() == 64 && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        });
+        if !valid {
+            return Err(format!(
+                "--remote expects sha256:<64 hex>[@STORE], got {raw:?}"
+            ));
+        }
+        Ok(Self {
+            pack_id: pack_id.to_string(),
+            store,
+        })
+    }
+}
+
+impl fmt::Display for RemotePack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.store {
+            Some(store) => write!(f, "{}@{store}", self.pack_id),
+            None => write!(f, "{}", self.pack_id),
+        }
+    }
+}
+
+/// Fetch only the manifest of a stored pack and check it is the pack asked
+/// for: `pack.v0`, the requested `pack_id`, and a `pack_id` that recomputes
+/// from the manifest bytes. Members are never transferred, so their hashes
+/// are trusted as recorded.
+pub fn fetch_remote_manifest(remote: &RemotePack) -> Result<Manifest, Box<RefusalEnvelope>> {
+    let transport = resolve_store(
+        remote.store.as_deref(),
+        "diff",
+        crate::config::env_or_config,
+    )?;
+    let manifest = transport.fetch_manifest(&remote.pack_id)?;
+
+    let bad_manifest = |message: String| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(message),
+            Some(json!({
+                "pack_id": remote.pack_id,
+                "store": remote.store,
+            })),
+        ))
+    };
+    if manifest.version != "pack.v0" {
+        return Err(bad_manifest(format!(
+            "Fetched manifest for {} has unsupported version: {}",
+            remote.pack_id, manifest.version
+        )));
+    }
+    if manifest.pack_id != remote.pack_id {
+        return Err(bad_manifest(format!(
+            "Fetched manifest pack_id does not match request: expected {}, got {}",
+            remote.pack_id, manifest.pack_id
+        )));
+    }
+    if manifest.recompute_pack_id() != manifest.pack_id {
+        return Err(bad_manifest(format!(
+            "Fetched manifest for {} does not hash to its pack_id",
+            remote.pack_id
+        )));
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::store::{FilesystemStore, PackTransport};
+    use crate::seal::command::execute_seal;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const ID: &str = "sha256:0000000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fn parses_pack_id_with_optional_store() {
+        assert_eq!(
+            RemotePack::from_flag(ID).unwrap(),
+            RemotePack {
+                pack_id: ID.to_string(),
+                store: None,
+            }
+        );
+        let remote = RemotePack::from_flag(&format!("{ID}@https://u@eu.example/api")).unwrap();
+        assert_eq!(remote.store.as_deref(), Some("https://u@eu.example/api"));
+        assert_eq!(remote.to_string(), format!("{ID}@https://u@eu.example/api"));
+
+        assert!(RemotePack::from_flag("sha256:abc").is_err());
+        assert!(RemotePack::from_flag(&format!("{ID}@")).is_err());
+    }
+
+    #[test]
+    fn fetches_manifest_without_members_and_rejects_tampering() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.json");
+        fs::write(&file, "{}").unwrap();
+        let sealed = execute_seal(&[file], Some(&tmp.path().join("pack")), None).unwrap();
+        let store = FilesystemStore::new(tmp.path().join("store"));
+        let manifest: Manifest =
+            serde_json::from_slice(&fs::read(sealed.output_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        store.publish(&sealed.output_dir, &manifest).unwrap();
+
+        let stored = store.pack_dir(&sealed.pack_id).unwrap();
+        fs::remove_file(stored.join("a.json")).unwrap();
+        let remote = RemotePack {
+            pack_id: sealed.pack_id.clone(),
+            store: Some(store.root().display().to_string()),
+        };
+        assert_eq!(fetch_remote_manifest(&remote).unwrap(), manifest);
+
+        let tampered = fs::read_to_string(stored.join("manifest.json"))
+            .unwrap()
+            .replace("\"a.json\"", "\"b.json\"");
+        fs::write(stored.join("manifest.json"), tampered).unwrap();
+        let err = fetch_remote_manifest(&remote).unwrap_err();
+        assert_eq!(err.refusal.code, "E_BAD_PACK");
+    }
+}
@@ -1,3 +1,10 @@
+pub mod deprecate;
+pub mod gc;
+pub mod http;
+pub mod index;
+pub mod manifest;
 pub mod pull;
 pub mod push;
+pub mod refcount;
+pub mod store;
 pub mod transport;
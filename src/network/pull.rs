@@ -3,21 +3,32 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, SecondsFormat, Utc};
+use ed25519_dalek::VerifyingKey;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sha2::{Digest, Sha256};
 
+use crate::cli::HashAlgorithm;
 use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::copy::set_executable;
+use crate::seal::hasher::{hash_algorithm_of, hash_bytes};
 use crate::seal::manifest::Manifest;
-use crate::verify::run_checks;
+use crate::sign::read_signature;
+use crate::verify::{check_trust_policy, load_trust_policy, run_checks, TrustPolicy};
 
-use super::push::DATA_FABRIC_BASE_URL_ENV;
+use super::deprecate::Deprecation;
+use super::store::{check_store_slot, index_enclosing_store, resolve_store, PackTransport};
 use super::transport::{refusal_for_transport, DataFabricTransport, TransportRequest};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PullResult {
     pub pack_id: String,
     pub out_dir: PathBuf,
+    /// Deprecation note the store holds for the pack, if any.
+    pub deprecation: Option<Deprecation>,
+    /// The trust policy's `allowed_signers`, which the note's signer is
+    /// checked against.
+    pub trusted_signers: Vec<VerifyingKey>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -34,6 +45,12 @@ struct StoredMember {
     bytes_b64: String,
 }
 
+/// Remote index answer for "the pack for series X as of T".
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+struct SeriesResolution {
+    pack_id: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct DecodedPack {
     pack_id: String,
@@ -47,60 +64,167 @@ struct DecodedMember {
     bytes: Vec<u8>,
 }
 
-pub fn execute_pull(pack_id: &str, out_dir: &Path) -> Result<PullResult, Box<RefusalEnvelope>> {
-    let base_url = data_fabric_base_url_from_env(|key| std::env::var(key).ok())?;
-    execute_pull_with_base_url(pack_id, out_dir, &base_url)
+/// Fetch `pack_id` from the store named by `from` (falling back to
+/// `PACK_STORE`, then `PACK_DATA_FABRIC_BASE_URL`) and materialize it at
+/// `out_dir` only after it verifies and satisfies `trust_policy`, if given.
+pub fn execute_pull(
+    pack_id: &str,
+    out_dir: &Path,
+    from: Option<&str>,
+    trust_policy: Option<&Path>,
+) -> Result<PullResult, Box<RefusalEnvelope>> {
+    let policy = trust_policy.map(load_policy).transpose()?;
+    let transport = resolve_store(from, "pull", crate::config::env_or_config)?;
+    pull_with_transport(pack_id, out_dir, transport.as_ref(), policy.as_ref())
 }
 
-fn execute_pull_with_base_url(
-    pack_id: &str,
+/// Resolve the pack that was current for `series` at `as_of` via the store's
+/// index, then fetch it exactly like `execute_pull`.
+pub fn execute_pull_as_of(
+    series: &str,
+    as_of: &str,
     out_dir: &Path,
-    base_url: &str,
+    from: Option<&str>,
+    trust_policy: Option<&Path>,
 ) -> Result<PullResult, Box<RefusalEnvelope>> {
-    let request = TransportRequest::get(pack_path(pack_id));
-    let transport = DataFabricTransport::new(base_url);
-    let stored: StoredPack = transport
-        .send_json(&request)
-        .map_err(|error| Box::new(refusal_for_transport("pull", &error)))?;
-    let decoded = decode_stored_pack(pack_id, stored)?;
-    materialize_pack(&decoded, out_dir)?;
+    let policy = trust_policy.map(load_policy).transpose()?;
+    let transport = resolve_store(from, "pull", crate::config::env_or_config)?;
+    let pack_id = resolve_series_as_of(series, as_of, transport.as_ref())?;
+    pull_with_transport(&pack_id, out_dir, transport.as_ref(), policy.as_ref())
+}
 
-    Ok(PullResult {
-        pack_id: decoded.pack_id,
-        out_dir: out_dir.to_path_buf(),
+fn load_policy(path: &Path) -> Result<TrustPolicy, Box<RefusalEnvelope>> {
+    load_trust_policy(path).map_err(|message| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(message),
+            Some(json!({ "trust_policy": path.display().to_string() })),
+        ))
     })
 }
 
-fn data_fabric_base_url_from_env<F>(get_env: F) -> Result<String, Box<RefusalEnvelope>>
-where
-    F: FnOnce(&str) -> Option<String>,
-{
-    let Some(raw) = get_env(DATA_FABRIC_BASE_URL_ENV) else {
+fn resolve_series_as_of(
+    series: &str,
+    as_of: &str,
+    transport: &dyn PackTransport,
+) -> Result<String, Box<RefusalEnvelope>> {
+    if series.is_empty()
+        || !series
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+    {
         return Err(Box::new(RefusalEnvelope::new(
             RefusalCode::Io,
             Some(format!(
-                "pack pull requires {DATA_FABRIC_BASE_URL_ENV} to be set"
+                "Invalid series name {series:?}: expected [A-Za-z0-9._-]+"
             )),
-            Some(json!({
-                "env": DATA_FABRIC_BASE_URL_ENV,
-            })),
+            Some(json!({ "series": series })),
         )));
-    };
+    }
+    let as_of_utc = DateTime::parse_from_rfc3339(as_of)
+        .map_err(|error| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Invalid --as-of timestamp {as_of:?}: {error}")),
+                Some(json!({ "as_of": as_of })),
+            ))
+        })?
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::AutoSi, true);
+
+    let resolved = transport.resolve_as_of(series, &as_of_utc)?;
 
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
+    if !resolved.starts_with("sha256:") {
         return Err(Box::new(RefusalEnvelope::new(
-            RefusalCode::Io,
+            RefusalCode::BadPack,
             Some(format!(
-                "pack pull requires non-empty {DATA_FABRIC_BASE_URL_ENV}"
+                "Remote index returned an invalid pack_id for {series} as of {as_of_utc}: {resolved}"
             )),
             Some(json!({
-                "env": DATA_FABRIC_BASE_URL_ENV,
+                "series": series,
+                "as_of": as_of_utc,
+                "pack_id": resolved,
             })),
         )));
     }
 
-    Ok(trimmed.to_string())
+    Ok(resolved)
+}
+
+/// Fetch through `transport` into staging, verify, then move into `out_dir`.
+pub fn pull_with_transport(
+    pack_id: &str,
+    out_dir: &Path,
+    transport: &dyn PackTransport,
+    trust_policy: Option<&TrustPolicy>,
+) -> Result<PullResult, Box<RefusalEnvelope>> {
+    let staging_dir = prepare_staging(out_dir)?;
+    check_store_slot(out_dir, pack_id)?;
+    let manifest = transport.fetch(pack_id, staging_dir.path())?;
+    verify_staged_pack(pack_id, &manifest, staging_dir.path())?;
+    if let Some(policy) = trust_policy {
+        check_staged_policy(policy, &manifest, staging_dir.path())?;
+    }
+    promote_staging(staging_dir, out_dir)?;
+    index_enclosing_store(out_dir, pack_id)?;
+
+    Ok(PullResult {
+        deprecation: transport.deprecation(&manifest.pack_id),
+        trusted_signers: trust_policy
+            .and_then(|policy| policy.allowed_signers.clone())
+            .unwrap_or_default(),
+        pack_id: manifest.pack_id,
+        out_dir: out_dir.to_path_buf(),
+    })
+}
+
+/// Data-fabric fetch: `GET /packs/{pack_id}`, decoded into `dest`.
+pub(crate) fn fetch_from_data_fabric(
+    transport: &DataFabricTransport,
+    pack_id: &str,
+    dest: &Path,
+) -> Result<Manifest, Box<RefusalEnvelope>> {
+    let request = TransportRequest::get(pack_path(pack_id));
+    let stored: StoredPack = transport
+        .send_json(&request)
+        .map_err(|error| Box::new(refusal_for_transport("pull", &error)))?;
+    let decoded = decode_stored_pack(pack_id, stored)?;
+    write_decoded_pack(&decoded, dest)?;
+    Ok(decoded.manifest)
+}
+
+/// Data-fabric manifest fetch: `GET /packs/{pack_id}/manifest`, which returns
+/// the bare `manifest.json` so no member bytes are transferred.
+pub(crate) fn fetch_manifest_from_data_fabric(
+    transport: &DataFabricTransport,
+    pack_id: &str,
+) -> Result<Manifest, Box<RefusalEnvelope>> {
+    let request = TransportRequest::get(format!("{}/manifest", pack_path(pack_id)));
+    transport
+        .send_json(&request)
+        .map_err(|error| Box::new(refusal_for_transport("diff", &error)))
+}
+
+/// Data-fabric index lookup: `GET /series/{series}/as-of/{as_of}`.
+pub(crate) fn resolve_as_of_from_data_fabric(
+    transport: &DataFabricTransport,
+    series: &str,
+    as_of: &str,
+) -> Result<String, Box<RefusalEnvelope>> {
+    let request = TransportRequest::get(series_as_of_path(series, as_of));
+    let resolution: SeriesResolution = transport
+        .send_json(&request)
+        .map_err(|error| Box::new(refusal_for_transport("pull", &error)))?;
+    Ok(resolution.pack_id)
+}
+
+#[cfg(test)]
+fn execute_pull_with_base_url(
+    pack_id: &str,
+    out_dir: &Path,
+    base_url: &str,
+) -> Result<PullResult, Box<RefusalEnvelope>> {
+    pull_with_transport(pack_id, out_dir, &DataFabricTransport::new(base_url), None)
 }
 
 fn decode_stored_pack(
@@ -152,7 +276,7 @@ fn decode_stored_pack(
 
     let mut manifest_members = HashMap::new();
     for member in &stored.manifest.members {
-        manifest_members.insert(member.path.clone(), member.bytes_hash.clone());
+        manifest_members.insert(member.path.clone(), member.stored_hash().to_string());
     }
 
     let mut seen_paths = HashSet::new();
@@ -216,7 +340,9 @@ fn decode_stored_pack(
             ))
         })?;
 
-        let actual_hash = format!("sha256:{}", hex::encode(Sha256::digest(&bytes)));
+        // An unsupported prefix cannot match, so it refuses below.
+        let algorithm = hash_algorithm_of(expected_hash).unwrap_or(HashAlgorithm::Sha256);
+        let actual_hash = hash_bytes(&bytes, algorithm);
         if &actual_hash != expected_hash {
             return Err(Box::new(RefusalEnvelope::new(
                 RefusalCode::BadPack,
@@ -271,7 +397,7 @@ fn decode_stored_pack(
     })
 }
 
-fn materialize_pack(decoded: &DecodedPack, out_dir: &Path) -> Result<(), Box<RefusalEnvelope>> {
+fn prepare_staging(out_dir: &Path) -> Result<tempfile::TempDir, Box<RefusalEnvelope>> {
     if out_dir.exists() {
         let mut entries = fs::read_dir(out_dir).map_err(|error| {
             Box::new(RefusalEnvelope::new(
@@ -309,7 +435,7 @@ fn materialize_pack(decoded: &DecodedPack, out_dir: &Path) -> Result<(), Box<Ref
         })?;
     }
 
-    let staging_dir = tempfile::Builder::new()
+    tempfile::Builder::new()
         .prefix(".pack-pull-")
         .tempdir_in(staging_parent)
         .map_err(|error| {
@@ -318,30 +444,95 @@ fn materialize_pack(decoded: &DecodedPack, out_dir: &Path) -> Result<(), Box<Ref
                 Some(format!("Cannot create staging directory: {error}")),
                 None,
             ))
-        })?;
+        })
+}
+
+/// Run the full verify checks against the staged copy; a failing pack never
+/// reaches `--out`.
+fn verify_staged_pack(
+    pack_id: &str,
+    manifest: &Manifest,
+    staged: &Path,
+) -> Result<(), Box<RefusalEnvelope>> {
+    if manifest.pack_id != pack_id {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(format!(
+                "Fetched pack_id does not match request: expected {pack_id}, got {}",
+                manifest.pack_id
+            )),
+            Some(json!({
+                "requested_pack_id": pack_id,
+                "actual_pack_id": manifest.pack_id,
+            })),
+        )));
+    }
 
-    write_decoded_pack(decoded, staging_dir.path())?;
+    if manifest.version != "pack.v0" {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(format!(
+                "Fetched manifest has unsupported version: {}",
+                manifest.version
+            )),
+            Some(json!({
+                "pack_id": pack_id,
+                "version": manifest.version,
+            })),
+        )));
+    }
 
-    let (checks, findings) = run_checks(&decoded.manifest, staging_dir.path());
+    let (checks, findings) = run_checks(manifest, staged);
     if !findings.is_empty() {
         return Err(Box::new(RefusalEnvelope::new(
             RefusalCode::BadPack,
             Some(format!(
-                "Fetched pack failed integrity checks after materialization: {}",
-                decoded.pack_id
+                "Fetched pack failed integrity checks after materialization: {pack_id}"
             )),
             Some(json!({
-                "pack_id": decoded.pack_id,
+                "pack_id": pack_id,
                 "checks": checks,
                 "invalid": findings,
             })),
         )));
     }
 
-    if out_dir.exists() {
-        copy_dir_recursive(staging_dir.path(), out_dir)?;
+    Ok(())
+}
+
+/// Refuse a staged pack that violates the trust policy, listing the
+/// `TRUST_POLICY` findings. Only filesystem stores carry `manifest.sig.json`,
+/// so a pack from data-fabric never satisfies `allowed_signers`.
+fn check_staged_policy(
+    policy: &TrustPolicy,
+    manifest: &Manifest,
+    staged: &Path,
+) -> Result<(), Box<RefusalEnvelope>> {
+    let signature = read_signature(staged).ok().flatten();
+    let findings = check_trust_policy(policy, manifest, signature.as_ref(), Utc::now());
+    if findings.is_empty() {
         return Ok(());
     }
+    Err(Box::new(RefusalEnvelope::new(
+        RefusalCode::BadPack,
+        Some(format!(
+            "Fetched pack violates the trust policy: {}",
+            manifest.pack_id
+        )),
+        Some(json!({
+            "pack_id": manifest.pack_id,
+            "invalid": findings,
+        })),
+    )))
+}
+
+fn promote_staging(
+    staging_dir: tempfile::TempDir,
+    out_dir: &Path,
+) -> Result<(), Box<RefusalEnvelope>> {
+    if out_dir.exists() {
+        return copy_dir_recursive(staging_dir.path(), out_dir);
+    }
 
     match fs::rename(staging_dir.path(), out_dir) {
         Ok(()) => {
@@ -353,6 +544,13 @@ fn materialize_pack(decoded: &DecodedPack, out_dir: &Path) -> Result<(), Box<Ref
 }
 
 fn write_decoded_pack(decoded: &DecodedPack, dest_dir: &Path) -> Result<(), Box<RefusalEnvelope>> {
+    let executable: HashSet<&str> = decoded
+        .manifest
+        .members
+        .iter()
+        .filter(|m| m.executable == Some(true))
+        .map(|m| m.path.as_str())
+        .collect();
     for member in &decoded.members {
         let member_path = dest_dir.join(&member.path);
         if let Some(parent) = member_path.parent() {
@@ -368,16 +566,24 @@ fn write_decoded_pack(decoded: &DecodedPack, dest_dir: &Path) -> Result<(), Box<
             })?;
         }
 
-        fs::write(&member_path, &member.bytes).map_err(|error| {
-            Box::new(RefusalEnvelope::new(
-                RefusalCode::Io,
-                Some(format!(
-                    "Cannot write fetched member {}: {error}",
-                    member.path
-                )),
-                None,
-            ))
-        })?;
+        fs::write(&member_path, &member.bytes)
+            .and_then(|()| {
+                if executable.contains(member.path.as_str()) {
+                    set_executable(&member_path)
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|error| {
+                Box::new(RefusalEnvelope::new(
+                    RefusalCode::Io,
+                    Some(format!(
+                        "Cannot write fetched member {}: {error}",
+                        member.path
+                    )),
+                    None,
+                ))
+            })?;
     }
 
     fs::write(
@@ -449,6 +655,10 @@ fn pack_path(pack_id: &str) -> String {
     format!("/packs/{pack_id}")
 }
 
+fn series_as_of_path(series: &str, as_of: &str) -> String {
+    format!("/series/{series}/as-of/{as_of}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -550,9 +760,60 @@ mod tests {
         assert_eq!(requests, vec![(Method::Get, format!("/packs/{pack_id}"))]);
     }
 
+    #[test]
+    fn manifest_fetch_requests_only_the_manifest() {
+        let (_out, stored, pack_id) = create_stored_pack();
+        let server = spawn_server(200, serde_json::to_string(&stored.manifest).unwrap());
+
+        let manifest =
+            fetch_manifest_from_data_fabric(&DataFabricTransport::new(&server.base_url), &pack_id)
+                .unwrap();
+
+        assert_eq!(manifest, stored.manifest);
+        let requests = server.finish();
+        assert_eq!(
+            requests,
+            vec![(Method::Get, format!("/packs/{pack_id}/manifest"))]
+        );
+    }
+
+    #[test]
+    fn as_of_resolution_normalizes_timestamp_to_utc() {
+        let pack_id = format!("sha256:{}", "a".repeat(64));
+        let server = spawn_server(200, json!({ "pack_id": pack_id }).to_string());
+
+        let resolved = resolve_series_as_of(
+            "recon-monthly",
+            "2025-12-31T19:00:00-05:00",
+            &DataFabricTransport::new(&server.base_url),
+        )
+        .unwrap();
+
+        assert_eq!(resolved, pack_id);
+        let requests = server.finish();
+        assert_eq!(
+            requests,
+            vec![(
+                Method::Get,
+                "/series/recon-monthly/as-of/2026-01-01T00:00:00Z".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn as_of_rejects_bad_inputs_before_network() {
+        let offline = DataFabricTransport::new("http://127.0.0.1:9");
+        let error = resolve_series_as_of("q4/../x", "2026-01-01T00:00:00Z", &offline).unwrap_err();
+        assert!(error.refusal.message.contains("Invalid series name"));
+
+        let error = resolve_series_as_of("recon", "last tuesday", &offline).unwrap_err();
+        assert_eq!(error.refusal.code, "E_IO");
+        assert!(error.refusal.message.contains("Invalid --as-of"));
+    }
+
     #[test]
     fn missing_base_url_env_refuses() {
-        let error = data_fabric_base_url_from_env(|_| None).unwrap_err();
+        let error = resolve_store(None, "pull", |_| None).err().unwrap();
         assert_eq!(error.refusal.code, "E_IO");
         assert!(error.refusal.message.contains("PACK_DATA_FABRIC_BASE_URL"));
     }
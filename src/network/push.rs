@@ -4,75 +4,92 @@ use std::path::Path;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde_json::{json, Value};
 
+use crate::events::{EventBus, PackEvent};
 use crate::refusal::{RefusalCode, RefusalEnvelope};
 use crate::seal::manifest::Manifest;
 use crate::verify::run_checks;
 
-use super::transport::{refusal_for_transport, DataFabricTransport, TransportRequest};
+use super::store::{resolve_store, PackTransport, PublishOutcome};
+use super::transport::{
+    refusal_for_transport, DataFabricTransport, TransportError, TransportRequest,
+};
 
 pub const DATA_FABRIC_BASE_URL_ENV: &str = "PACK_DATA_FABRIC_BASE_URL";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PushResult {
     pub pack_id: String,
+    pub outcome: PublishOutcome,
 }
 
 pub fn execute_push(pack_dir: &Path) -> Result<PushResult, Box<RefusalEnvelope>> {
-    let base_url = data_fabric_base_url_from_env(|key| std::env::var(key).ok())?;
-    execute_push_with_base_url(pack_dir, &base_url)
+    execute_push_to(pack_dir, None, &EventBus::default())
 }
 
-fn execute_push_with_base_url(
+/// Push to the store named by `to`, falling back to `PACK_STORE` and then
+/// `PACK_DATA_FABRIC_BASE_URL`.
+pub fn execute_push_to(
     pack_dir: &Path,
-    base_url: &str,
+    to: Option<&str>,
+    events: &EventBus,
 ) -> Result<PushResult, Box<RefusalEnvelope>> {
-    let manifest = load_and_validate_manifest(pack_dir)?;
-    let payload = build_publish_payload(pack_dir, &manifest)?;
-    let request = TransportRequest::put(pack_path(&manifest.pack_id), payload);
-    let transport = DataFabricTransport::new(base_url);
-
-    transport
-        .send(&request)
-        .map_err(|error| Box::new(refusal_for_transport("push", &error)))?;
+    let transport = resolve_store(to, "push", crate::config::env_or_config)?;
+    push_with_transport(pack_dir, transport.as_ref(), events)
+}
 
+/// Verify `pack_dir` and publish it through `transport`.
+///
+/// Findings that block the publish are announced on `events` as
+/// `VerificationFinding`s, and a successful publish as `PackPublished`.
+pub fn push_with_transport(
+    pack_dir: &Path,
+    transport: &dyn PackTransport,
+    events: &EventBus,
+) -> Result<PushResult, Box<RefusalEnvelope>> {
+    let manifest = load_and_validate_manifest(pack_dir, events)?;
+    let outcome = transport.publish(pack_dir, &manifest)?;
+    events.notify(&PackEvent::PackPublished {
+        pack_id: manifest.pack_id.clone(),
+        outcome: outcome.as_str().to_string(),
+    });
     Ok(PushResult {
         pack_id: manifest.pack_id,
+        outcome,
     })
 }
 
-fn data_fabric_base_url_from_env<F>(get_env: F) -> Result<String, Box<RefusalEnvelope>>
-where
-    F: FnOnce(&str) -> Option<String>,
-{
-    let Some(raw) = get_env(DATA_FABRIC_BASE_URL_ENV) else {
-        return Err(Box::new(RefusalEnvelope::new(
-            RefusalCode::Io,
-            Some(format!(
-                "pack push requires {DATA_FABRIC_BASE_URL_ENV} to be set"
-            )),
-            Some(json!({
-                "env": DATA_FABRIC_BASE_URL_ENV,
-            })),
-        )));
-    };
-
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return Err(Box::new(RefusalEnvelope::new(
-            RefusalCode::Io,
-            Some(format!(
-                "pack push requires non-empty {DATA_FABRIC_BASE_URL_ENV}"
-            )),
-            Some(json!({
-                "env": DATA_FABRIC_BASE_URL_ENV,
-            })),
-        )));
+/// Data-fabric publish: one `PUT /packs/{pack_id}` carrying every member.
+pub(crate) fn publish_to_data_fabric(
+    transport: &DataFabricTransport,
+    pack_dir: &Path,
+    manifest: &Manifest,
+) -> Result<PublishOutcome, Box<RefusalEnvelope>> {
+    let payload = build_publish_payload(pack_dir, manifest)?;
+    let request = TransportRequest::put(pack_path(&manifest.pack_id), payload);
+    match transport.send(&request) {
+        Ok(_) => Ok(PublishOutcome::Published),
+        // Pack IDs are content hashes, so a conflict means the same bytes.
+        Err(TransportError::Server { status: 409, .. }) => Ok(PublishOutcome::AlreadyPresent),
+        Err(error) => Err(Box::new(refusal_for_transport("push", &error))),
     }
+}
 
-    Ok(trimmed.to_string())
+#[cfg(test)]
+fn execute_push_with_base_url(
+    pack_dir: &Path,
+    base_url: &str,
+) -> Result<PushResult, Box<RefusalEnvelope>> {
+    push_with_transport(
+        pack_dir,
+        &DataFabricTransport::new(base_url),
+        &EventBus::default(),
+    )
 }
 
-fn load_and_validate_manifest(pack_dir: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+fn load_and_validate_manifest(
+    pack_dir: &Path,
+    events: &EventBus,
+) -> Result<Manifest, Box<RefusalEnvelope>> {
     let manifest_path = pack_dir.join("manifest.json");
     let manifest_content = fs::read_to_string(&manifest_path).map_err(|error| {
         Box::new(RefusalEnvelope::new(
@@ -112,6 +129,11 @@ fn load_and_validate_manifest(pack_dir: &Path) -> Result<Manifest, Box<RefusalEn
     }
 
     let (checks, findings) = run_checks(&manifest, pack_dir);
+    for finding in &findings {
+        events.notify(&PackEvent::VerificationFinding {
+            finding: finding.clone(),
+        });
+    }
     if !findings.is_empty() {
         return Err(Box::new(RefusalEnvelope::new(
             RefusalCode::BadPack,
@@ -154,7 +176,7 @@ fn build_publish_payload(
 
         members.push(json!({
             "path": member.path,
-            "bytes_hash": member.bytes_hash,
+            "bytes_hash": member.stored_hash(),
             "bytes_b64": STANDARD.encode(bytes),
         }));
     }
@@ -246,6 +268,7 @@ mod tests {
         let result = execute_push_with_base_url(&pack_dir, &server.base_url).unwrap();
 
         assert_eq!(result.pack_id, pack_id);
+        assert_eq!(result.outcome, PublishOutcome::Published);
 
         let requests = server.finish();
         assert_eq!(requests.len(), 1);
@@ -278,11 +301,22 @@ mod tests {
 
     #[test]
     fn missing_base_url_env_refuses() {
-        let error = data_fabric_base_url_from_env(|_| None).unwrap_err();
+        let error = resolve_store(None, "push", |_| None).err().unwrap();
         assert_eq!(error.refusal.code, "E_IO");
         assert!(error.refusal.message.contains("PACK_DATA_FABRIC_BASE_URL"));
     }
 
+    #[test]
+    fn conflict_means_already_present() {
+        let (_out, pack_dir, _pack_id) = create_valid_pack();
+        let server = spawn_server(409, r#"{"status":"exists"}"#);
+
+        let result = execute_push_with_base_url(&pack_dir, &server.base_url).unwrap();
+        server.finish();
+
+        assert_eq!(result.outcome, PublishOutcome::AlreadyPresent);
+    }
+
     #[test]
     fn transport_failures_map_to_io_refusal() {
         let (_out, pack_dir, _pack_id) = create_valid_pack();
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::manifest::Manifest;
+
+use super::index::update_index;
+use super::store::FilesystemStore;
+
+/// Version tag for `pack store refcount` output.
+pub const REFCOUNT_REPORT_VERSION: &str = "pack.refcount.v0";
+
+/// How often each member content hash occurs across the packs in a store.
+#[derive(Debug, Clone, Serialize)]
+pub struct RefcountReport {
+    pub version: String,
+    pub store: String,
+    /// Packs read from the store index.
+    pub packs: usize,
+    /// Member entries across those packs.
+    pub members: usize,
+    pub unique_hashes: usize,
+    /// Bytes a content-addressed store would save by keeping one copy of
+    /// each hash.
+    pub duplicate_bytes: u64,
+    /// Most-referenced first, then by hash; filtered by `--min-refs`/`--hash`.
+    pub hashes: Vec<HashRefs>,
+}
+
+/// One content hash and the packs that reference it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HashRefs {
+    pub bytes_hash: String,
+    /// Member entries with this hash (a pack may hold it more than once).
+    pub refs: usize,
+    /// Size of one copy; absent when no copy could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    pub packs: Vec<String>,
+    /// Distinct member paths the content appears under.
+    pub paths: Vec<String>,
+}
+
+impl RefcountReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("refcount report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![
+            format!("pack store refcount: {}", self.store),
+            format!(
+                "  {} packs, {} members, {} unique hashes, {} duplicate bytes",
+                self.packs, self.members, self.unique_hashes, self.duplicate_bytes
+            ),
+        ];
+        for entry in &self.hashes {
+            let size = entry
+                .size
+                .map_or_else(|| "-".to_string(), |size| size.to_string());
+            lines.push(format!(
+                "  {:>5}  {:>12}  {}  {}",
+                entry.refs,
+                size,
+                entry.bytes_hash,
+                entry.paths.join(", ")
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Filters for [`store_refcount`].
+#[derive(Debug, Clone, Default)]
+pub struct RefcountOptions {
+    /// Only list hashes referenced at least this many times.
+    pub min_refs: usize,
+    /// Only list this hash (blast-radius lookup).
+    pub hash: Option<String>,
+}
+
+/// Execute `pack store refcount`.
+///
+/// Returns (output, exit_code): 0 with the report, or 2 with a refusal.
+pub fn execute_refcount(root: &Path, options: &RefcountOptions, json_output: bool) -> (String, u8) {
+    match store_refcount(root, options) {
+        Ok(report) if json_output => (report.to_json(), 0),
+        Ok(report) => (report.to_human(), 0),
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Count member hash references across every pack in the store index.
+///
+/// The index is reconciled with the packs on disk first, as `push` does, so
+/// the counts cover exactly the packs present. Totals always cover the whole
+/// store; `options` only filters the listed hashes.
+pub fn store_refcount(
+    root: &Path,
+    options: &RefcountOptions,
+) -> Result<RefcountReport, Box<RefusalEnvelope>> {
+    if !root.is_dir() {
+        return Err(refusal(
+            root,
+            format!("Store root is not a directory: {}", root.display()),
+        ));
+    }
+    let index = update_index(root).map_err(|e| {
+        refusal(
+            root,
+            format!("Cannot update store index: {}: {e}", root.display()),
+        )
+    })?;
+
+    let store = FilesystemStore::new(root);
+    let mut members = 0;
+    let mut by_hash: BTreeMap<String, HashRefs> = BTreeMap::new();
+    for entry in &index.packs {
+        let Some(pack_dir) = store.pack_dir(&entry.pack_id) else {
+            continue;
+        };
+        let manifest = fs::read(pack_dir.join("manifest.json"))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Manifest>(&bytes).ok())
+            .ok_or_else(|| refusal(root, format!("Cannot read manifest of {}", entry.pack_id)))?;
+        for member in &manifest.members {
+            members += 1;
+            let refs = by_hash
+                .entry(member.stored_hash().to_string())
+                .or_insert_with(|| HashRefs {
+                    bytes_hash: member.stored_hash().to_string(),
+                    refs: 0,
+                    size: None,
+                    packs: vec![],
+                    paths: vec![],
+                });
+            refs.refs += 1;
+            if refs.size.is_none() {
+                refs.size = fs::metadata(pack_dir.join(&member.path))
+                    .ok()
+                    .map(|meta| meta.len());
+            }
+            if refs.packs.last() != Some(&entry.pack_id) {
+                refs.packs.push(entry.pack_id.clone());
+            }
+            refs.paths.push(member.path.clone());
+        }
+    }
+
+    let duplicate_bytes = by_hash
+        .values()
+        .map(|refs| refs.size.unwrap_or(0) * (refs.refs as u64 - 1))
+        .sum();
+    let unique_hashes = by_hash.len();
+    let mut hashes: Vec<HashRefs> = by_hash
+        .into_values()
+        .filter(|refs| refs.refs >= options.min_refs)
+        .filter(|refs| {
+            options
+                .hash
+                .as_ref()
+                .is_none_or(|hash| *hash == refs.bytes_hash)
+        })
+        .map(|mut refs| {
+            refs.paths = refs
+                .paths
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            refs
+        })
+        .collect();
+    hashes.sort_by(|a, b| {
+        b.refs
+            .cmp(&a.refs)
+            .then_with(|| a.bytes_hash.cmp(&b.bytes_hash))
+    });
+
+    Ok(RefcountReport {
+        version: REFCOUNT_REPORT_VERSION.to_string(),
+        store: root.display().to_string(),
+        packs: index.packs.len(),
+        members,
+        unique_hashes,
+        duplicate_bytes,
+        hashes,
+    })
+}
+
+fn refusal(root: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "store": root.display().to_string() })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::store::PackTransport;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    #[test]
+    fn shared_content_is_counted_once_per_reference() {
+        let src = TempDir::new().unwrap();
+        let shared = src.path().join("rules.json");
+        let jan = src.path().join("jan.json");
+        let feb = src.path().join("feb.json");
+        fs::write(&shared, r#"{"rules":[]}"#).unwrap();
+        fs::write(&jan, "1").unwrap();
+        fs::write(&feb, "2").unwrap();
+
+        let store_root = src.path().join("store");
+        let store = FilesystemStore::new(&store_root);
+        for (name, month) in [("jan", &jan), ("feb", &feb)] {
+            let out = src.path().join(name);
+            execute_seal(&[shared.clone(), month.clone()], Some(&out), None).unwrap();
+            let manifest: Manifest =
+                serde_json::from_slice(&fs::read(out.join("manifest.json")).unwrap()).unwrap();
+            store.publish(&out, &manifest).unwrap();
+        }
+
+        let report = store_refcount(&store_root, &RefcountOptions::default()).unwrap();
+        assert_eq!((report.packs, report.members), (2, 4));
+        assert_eq!(report.unique_hashes, 3);
+        assert_eq!(report.hashes[0].refs, 2);
+        assert_eq!(report.hashes[0].paths, ["rules.json"]);
+        assert_eq!(report.duplicate_bytes, r#"{"rules":[]}"#.len() as u64);
+
+        let only_shared = RefcountOptions {
+            min_refs: 2,
+            hash: None,
+        };
+        assert_eq!(
+            store_refcount(&store_root, &only_shared)
+                .unwrap()
+                .hashes
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,530 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::manifest::Manifest;
+use crate::sign::SIGNATURE_FILE;
+use crate::timestamp::TIMESTAMP_FILE;
+
+use super::deprecate::Deprecation;
+use super::http::{HttpStore, HTTP_STORE_SCHEME, PACK_STORE_TOKEN_ENV};
+use super::index::{deprecate_in_index, read_index, update_index, INDEX_FILE};
+use super::pull::{
+    fetch_from_data_fabric, fetch_manifest_from_data_fabric, resolve_as_of_from_data_fabric,
+};
+use super::push::{publish_to_data_fabric, DATA_FABRIC_BASE_URL_ENV};
+use super::transport::DataFabricTransport;
+
+/// Store spec used by `push`/`pull` when `--to`/`--from` is not given.
+pub const PACK_STORE_ENV: &str = "PACK_STORE";
+
+/// Result of publishing a pack to a store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishOutcome {
+    /// The pack was written to the store.
+    Published,
+    /// The store already held this pack_id; nothing was written.
+    AlreadyPresent,
+}
+
+impl PublishOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Published => "PUBLISHED",
+            Self::AlreadyPresent => "ALREADY_PRESENT",
+        }
+    }
+}
+
+/// A backend that packs can be published to and fetched from.
+///
+/// Callers verify the pack before publishing and after fetching; backends only
+/// move bytes.
+pub trait PackTransport {
+    /// Publish the verified pack at `pack_dir`, described by `manifest`.
+    fn publish(
+        &self,
+        pack_dir: &Path,
+        manifest: &Manifest,
+    ) -> Result<PublishOutcome, Box<RefusalEnvelope>>;
+
+    /// Write `pack_id`'s manifest and members into the empty directory `dest`
+    /// and return the manifest.
+    fn fetch(&self, pack_id: &str, dest: &Path) -> Result<Manifest, Box<RefusalEnvelope>>;
+
+    /// Return `pack_id`'s manifest without transferring any member bytes.
+    fn fetch_manifest(&self, pack_id: &str) -> Result<Manifest, Box<RefusalEnvelope>>;
+
+    /// Resolve the pack current for `series` at `as_of` (UTC RFC3339).
+    fn resolve_as_of(&self, series: &str, _as_of: &str) -> Result<String, Box<RefusalEnvelope>> {
+        Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(format!(
+                "This store cannot resolve --series {series}; use a data-fabric store"
+            )),
+            Some(json!({ "series": series })),
+        )))
+    }
+
+    /// Record `deprecation` for the stored `pack_id` without touching the pack.
+    fn deprecate(
+        &self,
+        pack_id: &str,
+        _deprecation: &Deprecation,
+    ) -> Result<(), Box<RefusalEnvelope>> {
+        Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(format!(
+                "This store cannot record deprecations for {pack_id}; use a filesystem store"
+            )),
+            Some(json!({ "pack_id": pack_id })),
+        )))
+    }
+
+    /// The deprecation note recorded for `pack_id`, if any.
+    fn deprecation(&self, _pack_id: &str) -> Option<Deprecation> {
+        None
+    }
+}
+
+impl PackTransport for DataFabricTransport {
+    fn publish(
+        &self,
+        pack_dir: &Path,
+        manifest: &Manifest,
+    ) -> Result<PublishOutcome, Box<RefusalEnvelope>> {
+        publish_to_data_fabric(self, pack_dir, manifest)
+    }
+
+    fn fetch(&self, pack_id: &str, dest: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+        fetch_from_data_fabric(self, pack_id, dest)
+    }
+
+    fn fetch_manifest(&self, pack_id: &str) -> Result<Manifest, Box<RefusalEnvelope>> {
+        fetch_manifest_from_data_fabric(self, pack_id)
+    }
+
+    fn resolve_as_of(&self, series: &str, as_of: &str) -> Result<String, Box<RefusalEnvelope>> {
+        resolve_as_of_from_data_fabric(self, series, as_of)
+    }
+}
+
+/// Content-addressed directory tree: each pack lives at `<root>/sha256/<hex>/`.
+///
+/// Suitable for a shared NFS or object-store mount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilesystemStore {
+    root: PathBuf,
+}
+
+impl FilesystemStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory holding `pack_id`, or `None` when the ID is not `sha256:<64 hex>`.
+    pub fn pack_dir(&self, pack_id: &str) -> Option<PathBuf> {
+        let hex = pack_id.strip_prefix("sha256:")?;
+        let valid = hex.len() == 64 && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        valid.then(|| self.root.join("sha256").join(hex))
+    }
+
+    /// Copy the pack into `<root>/sha256/<hex>/` via a staging rename.
+    fn store_pack(
+        &self,
+        pack_dir: &Path,
+        manifest: &Manifest,
+    ) -> Result<PublishOutcome, Box<RefusalEnvelope>> {
+        let dest = self.pack_dir(&manifest.pack_id).ok_or_else(|| {
+            store_refusal(
+                format!("Cannot address pack_id in store: {}", manifest.pack_id),
+                &self.root,
+            )
+        })?;
+        if dest.join("manifest.json").is_file() {
+            return Ok(PublishOutcome::AlreadyPresent);
+        }
+
+        let parent = dest.parent().expect("store pack dirs have a parent");
+        fs::create_dir_all(parent).map_err(|e| {
+            store_refusal(format!("Cannot create store directory: {e}"), &self.root)
+        })?;
+        let staging = tempfile::Builder::new()
+            .prefix(".pack-push-")
+            .tempdir_in(parent)
+            .map_err(|e| store_refusal(format!("Cannot stage pack in store: {e}"), &self.root))?;
+
+        let mut paths: Vec<&str> = manifest.members.iter().map(|m| m.path.as_str()).collect();
+        paths.push("manifest.json");
+        for sidecar in [SIGNATURE_FILE, TIMESTAMP_FILE] {
+            if pack_dir.join(sidecar).is_file() {
+                paths.push(sidecar);
+            }
+        }
+        for path in paths {
+            let target = staging.path().join(path);
+            if let Some(dir) = target.parent() {
+                fs::create_dir_all(dir).map_err(|e| copy_refusal(path, e, &self.root))?;
+            }
+            fs::copy(pack_dir.join(path), &target)
+                .map_err(|e| copy_refusal(path, e, &self.root))?;
+        }
+
+        match fs::rename(staging.path(), &dest) {
+            Ok(()) => {
+                let _ = staging.keep();
+                Ok(PublishOutcome::Published)
+            }
+            // A concurrent push of the same pack_id won the race.
+            Err(_) if dest.join("manifest.json").is_file() => Ok(PublishOutcome::AlreadyPresent),
+            Err(e) => Err(store_refusal(
+                format!("Cannot move pack into store: {e}"),
+                &self.root,
+            )),
+        }
+    }
+}
+
+impl PackTransport for FilesystemStore {
+    fn publish(
+        &self,
+        pack_dir: &Path,
+        manifest: &Manifest,
+    ) -> Result<PublishOutcome, Box<RefusalEnvelope>> {
+        let outcome = self.store_pack(pack_dir, manifest)?;
+        update_index(&self.root).map_err(|e| {
+            store_refusal(
+                format!(
+                    "Pack {} is stored but {INDEX_FILE} could not be updated: {e}",
+                    manifest.pack_id
+                ),
+                &self.root,
+            )
+        })?;
+        Ok(outcome)
+    }
+
+    fn fetch(&self, pack_id: &str, dest: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+        let manifest = self.fetch_manifest(pack_id)?;
+        let src = self
+            .pack_dir(pack_id)
+            .expect("fetch_manifest accepted the pack_id");
+
+        let mut paths: Vec<&str> = manifest.members.iter().map(|m| m.path.as_str()).collect();
+        paths.push("manifest.json");
+        for sidecar in [SIGNATURE_FILE, TIMESTAMP_FILE] {
+            if src.join(sidecar).is_file() {
+                paths.push(sidecar);
+            }
+        }
+        for path in paths {
+            let target = dest.join(path);
+            if let Some(dir) = target.parent() {
+                fs::create_dir_all(dir).map_err(|e| copy_refusal(path, e, &self.root))?;
+            }
+            fs::copy(src.join(path), &target).map_err(|e| copy_refusal(path, e, &self.root))?;
+        }
+        Ok(manifest)
+    }
+
+    fn fetch_manifest(&self, pack_id: &str) -> Result<Manifest, Box<RefusalEnvelope>> {
+        let src = self
+            .pack_dir(pack_id)
+            .ok_or_else(|| store_refusal(format!("Invalid pack_id: {pack_id}"), &self.root))?;
+        let content = fs::read_to_string(src.join("manifest.json")).map_err(|e| {
+            store_refusal(
+                format!("Pack not found in store: {pack_id}: {e}"),
+                &self.root,
+            )
+        })?;
+        let manifest: Manifest = serde_json::from_str(&content).map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::BadPack,
+                Some(format!("Invalid manifest.json in store for {pack_id}: {e}")),
+                Some(json!({
+                    "store": self.root.display().to_string(),
+                    "pack_id": pack_id,
+                })),
+            ))
+        })?;
+        Ok(manifest)
+    }
+
+    fn deprecate(
+        &self,
+        pack_id: &str,
+        deprecation: &Deprecation,
+    ) -> Result<(), Box<RefusalEnvelope>> {
+        let updated = deprecate_in_index(&self.root, pack_id, deprecation.clone())
+            .map_err(|e| store_refusal(format!("Cannot update {INDEX_FILE}: {e}"), &self.root))?;
+        match updated {
+            Some(_) => Ok(()),
+            None => Err(store_refusal(
+                format!("Pack not found in store: {pack_id}"),
+                &self.root,
+            )),
+        }
+    }
+
+    fn deprecation(&self, pack_id: &str) -> Option<Deprecation> {
+        read_index(&self.root)?
+            .packs
+            .into_iter()
+            .find(|entry| entry.pack_id == pack_id)?
+            .deprecation
+    }
+}
+
+/// Root of the filesystem store named by `spec`, falling back to
+/// `PACK_STORE`; `None` when no store is configured or it is not a filesystem
+/// store.
+pub fn filesystem_store_root<F>(spec: Option<&str>, get_env: F) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let spec = spec
+        .map(str::to_string)
+        .or_else(|| get_env(PACK_STORE_ENV))
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty())?;
+    if spec.starts_with("http://")
+        || spec.starts_with("https://")
+        || spec.starts_with(HTTP_STORE_SCHEME)
+    {
+        return None;
+    }
+    Some(PathBuf::from(spec.strip_prefix("file://").unwrap_or(&spec)))
+}
+
+/// The filesystem store `pack_dir` lies in, when it is laid out as
+/// `<root>/sha256/<hex>`: the store root and `<hex>`. `pack_dir` need not
+/// exist yet, but its parent must.
+pub fn enclosing_store(pack_dir: &Path) -> Option<(PathBuf, String)> {
+    let dir = pack_dir.canonicalize().ok().or_else(|| {
+        let dir = std::path::absolute(pack_dir).ok()?;
+        Some(dir.parent()?.canonicalize().ok()?.join(dir.file_name()?))
+    })?;
+    let parent = dir.parent()?;
+    if parent.file_name()? != "sha256" {
+        return None;
+    }
+    Some((
+        parent.parent()?.to_path_buf(),
+        dir.file_name()?.to_str()?.to_string(),
+    ))
+}
+
+/// Refuse to write `pack_id` into a store directory named for another pack,
+/// which the store index would list under the wrong `pack_id`. Directories
+/// outside a store are accepted.
+pub fn check_store_slot(pack_dir: &Path, pack_id: &str) -> Result<(), Box<RefusalEnvelope>> {
+    let Some((root, hex)) = enclosing_store(pack_dir) else {
+        return Ok(());
+    };
+    if pack_id.strip_prefix("sha256:") == Some(hex.as_str()) {
+        return Ok(());
+    }
+    Err(Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(format!(
+            "{} is inside store {} but is not named for {pack_id}",
+            pack_dir.display(),
+            root.display()
+        )),
+        Some(json!({
+            "store": root.display().to_string(),
+            "output": pack_dir.display().to_string(),
+            "pack_id": pack_id,
+        })),
+    )))
+}
+
+/// Update the index of the store `pack_dir` lies in, if any, after a pack was
+/// written straight into it by `seal --output` or `pull --out` rather than
+/// pushed.
+pub fn index_enclosing_store(pack_dir: &Path, pack_id: &str) -> Result<(), Box<RefusalEnvelope>> {
+    let Some((root, _)) = enclosing_store(pack_dir) else {
+        return Ok(());
+    };
+    update_index(&root).map(drop).map_err(|e| {
+        store_refusal(
+            format!("Pack {pack_id} is stored but {INDEX_FILE} could not be updated: {e}"),
+            &root,
+        )
+    })
+}
+
+/// Pick a transport from `--to`/`--from`, then `PACK_STORE`, then the legacy
+/// `PACK_DATA_FABRIC_BASE_URL`.
+///
+/// `cas+http://` and `cas+https://` specs select an [`HttpStore`], which
+/// authenticates with `PACK_STORE_TOKEN`; `http://` and `https://` specs
+/// select data-fabric; `file://` URLs and plain paths select a
+/// [`FilesystemStore`].
+pub fn resolve_store<F>(
+    spec: Option<&str>,
+    action: &str,
+    get_env: F,
+) -> Result<Box<dyn PackTransport>, Box<RefusalEnvelope>>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let configured = spec
+        .map(str::to_string)
+        .or_else(|| get_env(PACK_STORE_ENV))
+        .map(|raw| raw.trim().to_string())
+        .filter(|raw| !raw.is_empty());
+
+    match configured {
+        Some(spec) if spec.starts_with(HTTP_STORE_SCHEME) => {
+            let base_url = &spec[HTTP_STORE_SCHEME.len()..];
+            Ok(Box::new(HttpStore::new(
+                base_url,
+                get_env(PACK_STORE_TOKEN_ENV),
+            )))
+        }
+        Some(spec) if spec.starts_with("http://") || spec.starts_with("https://") => {
+            Ok(Box::new(DataFabricTransport::new(spec)))
+        }
+        Some(spec) => {
+            let root = spec.strip_prefix("file://").unwrap_or(&spec);
+            Ok(Box::new(FilesystemStore::new(root)))
+        }
+        None => match get_env(DATA_FABRIC_BASE_URL_ENV)
+            .map(|raw| raw.trim().to_string())
+            .filter(|raw| !raw.is_empty())
+        {
+            Some(base_url) => Ok(Box::new(DataFabricTransport::new(base_url))),
+            None => Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!(
+                    "pack {action} requires a store: pass {flag}, or set {PACK_STORE_ENV} or {DATA_FABRIC_BASE_URL_ENV}",
+                    flag = match action {
+                        "push" => "--to",
+                        "diff" => "--remote <PACK_ID>@<STORE>",
+                        "deprecate" | "lineage" => "--store",
+                        _ => "--from",
+                    },
+                )),
+                Some(json!({
+                    "env": [PACK_STORE_ENV, DATA_FABRIC_BASE_URL_ENV],
+                })),
+            ))),
+        },
+    }
+}
+
+fn store_refusal(message: String, root: &Path) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "store": root.display().to_string() })),
+    ))
+}
+
+fn copy_refusal(path: &str, err: io::Error, root: &Path) -> Box<RefusalEnvelope> {
+    store_refusal(format!("Cannot copy {path}: {err}"), root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    fn sealed_pack(tmp: &TempDir) -> (PathBuf, Manifest) {
+        let file = tmp.path().join("report.json");
+        fs::write(&file, r#"{"version":"rvl.v0"}"#).unwrap();
+        let pack_dir = tmp.path().join("pack");
+        execute_seal(&[file], Some(&pack_dir), None).unwrap();
+        let manifest =
+            serde_json::from_str(&fs::read_to_string(pack_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        (pack_dir, manifest)
+    }
+
+    #[test]
+    fn filesystem_publish_is_content_addressed_and_idempotent() {
+        let tmp = TempDir::new().unwrap();
+        let (pack_dir, manifest) = sealed_pack(&tmp);
+        let store = FilesystemStore::new(tmp.path().join("store"));
+
+        let outcome = store.publish(&pack_dir, &manifest).unwrap();
+        assert_eq!(outcome, PublishOutcome::Published);
+        let stored = store.pack_dir(&manifest.pack_id).unwrap();
+        assert!(stored.join("manifest.json").is_file());
+        assert!(stored.join("report.json").is_file());
+
+        let again = store.publish(&pack_dir, &manifest).unwrap();
+        assert_eq!(again, PublishOutcome::AlreadyPresent);
+
+        let index = crate::network::index::read_index(store.root()).unwrap();
+        assert_eq!(index.packs.len(), 1);
+        assert_eq!(index.packs[0].pack_id, manifest.pack_id);
+
+        let dest = tmp.path().join("fetched");
+        fs::create_dir(&dest).unwrap();
+        let fetched = store.fetch(&manifest.pack_id, &dest).unwrap();
+        assert_eq!(fetched, manifest);
+        assert!(dest.join("report.json").is_file());
+    }
+
+    #[test]
+    fn packs_sealed_or_pulled_into_a_store_are_indexed() {
+        let tmp = TempDir::new().unwrap();
+        let (_, manifest) = sealed_pack(&tmp);
+        let hex = manifest.pack_id.strip_prefix("sha256:").unwrap();
+        let store = FilesystemStore::new(tmp.path().join("store"));
+        fs::create_dir_all(store.root().join("sha256")).unwrap();
+
+        let file = tmp.path().join("report.json");
+        let misnamed = store.root().join("sha256").join("latest");
+        let err = execute_seal(std::slice::from_ref(&file), Some(&misnamed), None).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(!misnamed.exists());
+
+        let sealed =
+            execute_seal(&[file], Some(&store.root().join("sha256").join(hex)), None).unwrap();
+        assert_eq!(sealed.pack_id, manifest.pack_id);
+        let index = crate::network::index::read_index(store.root()).unwrap();
+        assert_eq!(index.packs[0].pack_id, manifest.pack_id);
+
+        let mirror = tmp.path().join("mirror");
+        let out = mirror.join("sha256").join(hex);
+        crate::network::pull::pull_with_transport(&manifest.pack_id, &out, &store, None).unwrap();
+        let index = crate::network::index::read_index(&mirror).unwrap();
+        assert_eq!(index.packs[0].pack_id, manifest.pack_id);
+    }
+
+    #[test]
+    fn resolve_accepts_flag_or_store_env() {
+        let env = |key: &str| match key {
+            PACK_STORE_ENV => Some("/mnt/evidence".to_string()),
+            _ => None,
+        };
+        assert!(resolve_store(Some("file:///tmp/x"), "push", env).is_ok());
+        assert!(resolve_store(None, "push", env).is_ok());
+        assert!(resolve_store(Some("cas+https://store.example/packs"), "push", env).is_ok());
+        assert!(filesystem_store_root(Some("cas+https://store.example"), env).is_none());
+
+        let err = resolve_store(None, "pull", |_| None).err().unwrap();
+        assert!(err.refusal.message.contains("--from"));
+        assert!(err.refusal.message.contains(PACK_STORE_ENV));
+    }
+
+    #[test]
+    fn pack_dir_rejects_malformed_ids() {
+        let store = FilesystemStore::new("/store");
+        assert!(store.pack_dir("sha256:../../etc").is_none());
+        assert!(store
+            .pack_dir(&format!("sha256:{}", "a".repeat(64)))
+            .is_some());
+    }
+}
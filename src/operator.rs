@@ -1,7 +1,63 @@
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
+
+use crate::attest::PREDICATE_TYPE;
+use crate::config::{
+    CONFIG_ENV, CONFIG_REPORT_VERSION, HASH_ALGORITHM_ENV, OUTPUT_ROOT_ENV, SIGNING_KEY_FILE_ENV,
+};
+use crate::detect::DETECT_REPORT_VERSION;
+use crate::diff::DIFF_REPORT_VERSION;
+use crate::extract::{EXTRACT_REPORT_VERSION, SPOT_CHECK_REPORT_VERSION};
+use crate::inspect::INSPECT_REPORT_VERSION;
+use crate::lineage::LINEAGE_REPORT_VERSION;
+use crate::network::gc::GC_REPORT_VERSION;
+use crate::network::http::PACK_STORE_TOKEN_ENV;
+use crate::network::index::INDEX_VERSION;
+use crate::network::push::DATA_FABRIC_BASE_URL_ENV;
+use crate::network::refcount::REFCOUNT_REPORT_VERSION;
+use crate::network::store::PACK_STORE_ENV;
+use crate::outcomes::{outcomes_for_exit_code, COMMANDS, OUTCOMES_REPORT_VERSION};
+use crate::quickstart::QUICKSTART_REPORT_VERSION;
+use crate::refusal::RefusalCode;
+use crate::seal::command::SOURCE_DATE_EPOCH_ENV;
+use crate::seal::hasher::HASH_BUFFER_ENV;
+use crate::seal::manifest::MANIFEST_VERSION;
+use crate::seal::report::SEAL_REPORT_VERSION;
+use crate::selftest::SELF_TEST_REPORT_VERSION;
+use crate::sign::{SIGNATURE_VERSION, SIGNING_KEY_ENV};
+use crate::timestamp::TIMESTAMP_VERSION;
+use crate::verify::{
+    ID_REPORT_VERSION, SCHEMA_VALIDATE_REPORT_VERSION, VERIFY_ALL_REPORT_VERSION,
+    VERIFY_REPORT_VERSION, VERIFY_STREAM_VERSION,
+};
+use crate::witness::export::WITNESS_EXPORT_VERSION;
+use crate::witness::validate::WITNESS_SCHEMA_VERSION;
+use crate::witness::{WITNESS_CHAIN_ENV, WITNESS_ENRICH_ENV};
 
 /// Return the compiled-in operator manifest for `--describe`.
 pub fn operator_json() -> Value {
+    let refusal_codes: Map<String, Value> = RefusalCode::ALL
+        .iter()
+        .map(|code| (code.as_str().to_string(), json!(code.description())))
+        .collect();
+    let subcommands: Map<String, Value> = COMMANDS
+        .iter()
+        .map(|contract| {
+            let exit_codes: Map<String, Value> = contract
+                .exit_codes
+                .iter()
+                .map(|meaning| (meaning.code.to_string(), json!(meaning.summary())))
+                .collect();
+            (
+                contract.name.to_string(),
+                json!({
+                    "description": contract.description,
+                    "output_mode": contract.output_mode,
+                    "exit_codes": exit_codes,
+                }),
+            )
+        })
+        .collect();
+
     json!({
         "schema_version": "operator.v0",
         "name": "pack",
@@ -11,68 +67,81 @@ pub fn operator_json() -> Value {
         "license": "MIT",
         "agent_guide": "https://github.com/cmdrvl/.github/blob/main/profile/AGENT_PROMPT.md",
         "output_mode": "mixed",
-        "subcommands": {
-            "seal": {
-                "description": "Seal artifacts into an evidence pack directory",
-                "output_mode": "directory_artifact",
-                "exit_codes": {
-                    "0": "PACK_CREATED",
-                    "2": "REFUSAL"
-                }
-            },
-            "verify": {
-                "description": "Verify pack integrity (members + pack_id)",
-                "output_mode": "report",
-                "exit_codes": {
-                    "0": "OK",
-                    "1": "INVALID",
-                    "2": "REFUSAL"
-                }
-            },
-            "diff": {
-                "description": "Deterministically diff two packs (deferred in v0.1)",
-                "output_mode": "report",
-                "status": "deferred",
-                "exit_codes": {
-                    "0": "NO_CHANGES",
-                    "1": "CHANGES",
-                    "2": "REFUSAL"
-                }
-            },
-            "push": {
-                "description": "Publish a pack to data-fabric",
-                "output_mode": "status",
-                "exit_codes": {
-                    "0": "PUBLISHED",
-                    "2": "REFUSAL"
-                }
-            },
-            "pull": {
-                "description": "Fetch a pack by ID from data-fabric",
-                "output_mode": "status",
-                "exit_codes": {
-                    "0": "FETCHED",
-                    "2": "REFUSAL"
-                }
-            },
-            "witness": {
-                "description": "Query witness ledger",
-                "output_mode": "report",
-                "exit_codes": {
-                    "0": "OK"
-                }
-            }
+        "subcommands": subcommands,
+        "schema_versions": {
+            "manifest": MANIFEST_VERSION,
+            "seal_report": SEAL_REPORT_VERSION,
+            "verify_report": VERIFY_REPORT_VERSION,
+            "verify_all_report": VERIFY_ALL_REPORT_VERSION,
+            "verify_stream": VERIFY_STREAM_VERSION,
+            "diff_report": DIFF_REPORT_VERSION,
+            "inspect_report": INSPECT_REPORT_VERSION,
+            "id_report": ID_REPORT_VERSION,
+            "extract_report": EXTRACT_REPORT_VERSION,
+            "spot_check_report": SPOT_CHECK_REPORT_VERSION,
+            "lineage_report": LINEAGE_REPORT_VERSION,
+            "outcomes_report": OUTCOMES_REPORT_VERSION,
+            "witness": WITNESS_SCHEMA_VERSION,
+            "refusal": MANIFEST_VERSION,
+            "signature": SIGNATURE_VERSION,
+            "timestamp": TIMESTAMP_VERSION,
+            "store_index": INDEX_VERSION,
+            "self_test_report": SELF_TEST_REPORT_VERSION,
+            "refcount_report": REFCOUNT_REPORT_VERSION,
+            "gc_report": GC_REPORT_VERSION,
+            "quickstart_report": QUICKSTART_REPORT_VERSION,
+            "config_report": CONFIG_REPORT_VERSION,
+            "detect_report": DETECT_REPORT_VERSION,
+            "schema_validate_report": SCHEMA_VALIDATE_REPORT_VERSION,
+            "witness_export": WITNESS_EXPORT_VERSION,
+            "attestation_predicate": PREDICATE_TYPE
+        },
+        "exit_codes": {
+            "0": format!("SUCCESS ({})", outcomes_for_exit_code(0).join(", ")),
+            "1": one_of(&outcomes_for_exit_code(1)),
+            "2": "REFUSAL"
         },
-        "refusal_codes": {
-            "E_EMPTY": "seal called with no artifacts",
-            "E_IO": "Cannot read input, write output, or read pack directory",
-            "E_DUPLICATE": "Member path collision during seal (including reserved paths)",
-            "E_BAD_PACK": "Missing or invalid pack payload for verify/diff/push/pull"
+        "refusal_codes": refusal_codes,
+        "env": {
+            "EPISTEMIC_WITNESS": "Witness ledger path (default ~/.epistemic/witness.jsonl)",
+            (WITNESS_CHAIN_ENV): "Set to 1 to hash-chain witness records (prev_hash); a chained ledger stays chained",
+            (WITNESS_ENRICH_ENV): "Set to 1 to add enrichment (argv hash, duration, hostname, user, member count, pack bytes) to witness records",
+            (PACK_STORE_ENV): "Default store for push/pull/deprecate/gc/store refcount when --to/--from/--store is not given; verify and inspect read its deprecation notes",
+            (DATA_FABRIC_BASE_URL_ENV): "Data-fabric base URL used when no store is configured",
+            (SIGNING_KEY_ENV): "Ed25519 signing key for seal --sign, deprecate, and attest --sign when --signing-key is not given",
+            (HASH_BUFFER_ENV): "Read buffer size in bytes for streaming member hashes",
+            (CONFIG_ENV): "Config file path (default ~/.config/pack/config.toml)",
+            (OUTPUT_ROOT_ENV): "Parent directory for seal output when --output is not given (default pack)",
+            (HASH_ALGORITHM_ENV): "Default seal --hash-algorithm",
+            (SIGNING_KEY_FILE_ENV): "Signing key file used when neither --signing-key nor PACK_SIGNING_KEY is given",
+            (PACK_STORE_TOKEN_ENV): "Bearer token sent to a cas+http(s):// store",
+            (SOURCE_DATE_EPOCH_ENV): "Unix timestamp that seal --reproducible pins created to"
         },
-        "global_flags": ["--describe", "--schema", "--version", "--no-witness"]
+        "global_flags": [
+            "--describe",
+            "--schema",
+            "--schema-for",
+            "--version",
+            "--no-witness",
+            "--verbose",
+            "--log-json"
+        ],
+        "pipeline": {
+            "upstream": ["lock", "canon", "rvl"],
+            "downstream": []
+        }
     })
 }
 
+/// `A, B, or C`.
+fn one_of(items: &[&str]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{}, or {last}", rest.join(", ")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +165,9 @@ mod tests {
         assert!(subs.contains_key("push"));
         assert!(subs.contains_key("pull"));
         assert!(subs.contains_key("witness"));
+        assert!(subs.contains_key("watch"));
+        assert!(subs.contains_key("config"));
+        assert!(subs.contains_key("detect"));
     }
 
     #[test]
@@ -119,6 +191,45 @@ mod tests {
         assert_eq!(verify["0"], "OK");
         assert_eq!(verify["1"], "INVALID");
         assert_eq!(verify["2"], "REFUSAL");
+
+        let diff = &op["subcommands"]["diff"];
+        assert!(diff.get("status").is_none(), "diff is no longer deferred");
+        assert_eq!(diff["exit_codes"]["0"], "NO_CHANGES");
+        assert_eq!(diff["exit_codes"]["1"], "CHANGES");
+    }
+
+    #[test]
+    fn operator_manifest_lists_contract_metadata() {
+        let op = operator_json();
+        assert_eq!(op["schema_versions"]["manifest"], "pack.v0");
+        assert_eq!(op["schema_versions"]["seal_report"], "pack.seal.v0");
+        assert_eq!(op["schema_versions"]["verify_report"], "pack.verify.v0");
+        assert_eq!(op["schema_versions"]["diff_report"], "pack.diff.v0");
+        assert_eq!(op["schema_versions"]["witness"], "witness.v0");
+        assert_eq!(op["exit_codes"]["2"], "REFUSAL");
+        assert_eq!(
+            op["exit_codes"]["1"],
+            "INVALID, CHANGES, INCOMPLETE, FAILED, FAIL, or NO_SCHEMA"
+        );
+        assert_eq!(
+            op["subcommands"]["push"]["exit_codes"]["0"],
+            "PUBLISHED | ALREADY_PRESENT"
+        );
+        let codes = op["refusal_codes"].as_object().unwrap();
+        assert_eq!(codes.len(), RefusalCode::ALL.len());
+        let env = op["env"].as_object().unwrap();
+        for var in [
+            "EPISTEMIC_WITNESS",
+            "PACK_WITNESS_CHAIN",
+            "PACK_WITNESS_ENRICH",
+            "PACK_STORE",
+            "PACK_DATA_FABRIC_BASE_URL",
+            "PACK_SIGNING_KEY",
+            "PACK_HASH_BUFFER_BYTES",
+            "PACK_CONFIG",
+        ] {
+            assert!(env.contains_key(var), "missing env var {var}");
+        }
     }
 
     #[test]
@@ -0,0 +1,411 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::refusal::RefusalCode;
+
+/// Version tag for `pack outcomes --json` output.
+pub const OUTCOMES_REPORT_VERSION: &str = "pack.outcomes.v0";
+
+/// One command's exit-code and refusal contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandContract {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub output_mode: &'static str,
+    pub exit_codes: &'static [ExitMeaning],
+    /// Refusal codes the command can exit `2` with.
+    pub refusal_codes: &'static [RefusalCode],
+}
+
+/// The outcomes a command reports under one exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitMeaning {
+    pub code: u8,
+    /// The first entry is the outcome recorded in the witness ledger when
+    /// only the exit code is known.
+    pub outcomes: &'static [&'static str],
+    /// When the meaning depends on a flag, e.g. `watch --once`.
+    pub condition: Option<&'static str>,
+}
+
+const fn exit(code: u8, outcomes: &'static [&'static str]) -> ExitMeaning {
+    ExitMeaning {
+        code,
+        outcomes,
+        condition: None,
+    }
+}
+
+const REFUSAL: ExitMeaning = exit(2, &["REFUSAL"]);
+
+const READ_PACK: &[RefusalCode] = &[RefusalCode::Io, RefusalCode::BadPack, RefusalCode::Limit];
+const STORE: &[RefusalCode] = &[RefusalCode::Io, RefusalCode::BadPack];
+
+/// Every `pack` subcommand's contract. `--describe`, `pack outcomes`, and
+/// the witness outcomes recorded by the handlers are all read from here.
+pub const COMMANDS: &[CommandContract] = &[
+    CommandContract {
+        name: "seal",
+        description: "Seal artifacts into an evidence pack directory",
+        output_mode: "directory_artifact",
+        exit_codes: &[exit(0, &["PACK_CREATED"]), REFUSAL],
+        refusal_codes: &RefusalCode::ALL,
+    },
+    CommandContract {
+        name: "verify",
+        description: "Verify pack integrity (members + pack_id)",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), exit(1, &["INVALID"]), REFUSAL],
+        refusal_codes: READ_PACK,
+    },
+    CommandContract {
+        name: "diff",
+        description: "Deterministically diff two packs",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["NO_CHANGES"]), exit(1, &["CHANGES"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "inspect",
+        description: "Show a pack's manifest fields and member table",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), REFUSAL],
+        refusal_codes: READ_PACK,
+    },
+    CommandContract {
+        name: "id",
+        description: "Recompute a pack's pack_id from its manifest alone and compare it with the recorded one",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), exit(1, &["INVALID"]), REFUSAL],
+        refusal_codes: &[RefusalCode::BadPack, RefusalCode::Limit],
+    },
+    CommandContract {
+        name: "extract",
+        description: "Copy members out of a pack, re-hashing each against the manifest",
+        output_mode: "report",
+        exit_codes: &[
+            exit(0, &["EXTRACTED", "OK"]),
+            ExitMeaning {
+                code: 1,
+                outcomes: &["INVALID"],
+                condition: Some("--verify-only-selected: a sampled member did not match"),
+            },
+            REFUSAL,
+        ],
+        refusal_codes: &[
+            RefusalCode::Io,
+            RefusalCode::BadPack,
+            RefusalCode::HashMismatch,
+            RefusalCode::Limit,
+        ],
+    },
+    CommandContract {
+        name: "lineage",
+        description: "Show a pack's ancestry from the store, with the changes at each step",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["COMPLETE"]), exit(1, &["INCOMPLETE"]), REFUSAL],
+        refusal_codes: READ_PACK,
+    },
+    CommandContract {
+        name: "push",
+        description: "Publish a pack to a data-fabric or filesystem store",
+        output_mode: "status",
+        exit_codes: &[exit(0, &["PUBLISHED", "ALREADY_PRESENT"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "pull",
+        description: "Fetch and verify a pack by ID from a data-fabric or filesystem store",
+        output_mode: "status",
+        exit_codes: &[exit(0, &["FETCHED"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "deprecate",
+        description: "Record a deprecation note for a stored pack in the store index",
+        output_mode: "status",
+        exit_codes: &[exit(0, &["DEPRECATED"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "watch",
+        description: "Verify packs in a filesystem store as they appear or change, running a hook on INVALID",
+        output_mode: "stream",
+        exit_codes: &[
+            ExitMeaning {
+                code: 0,
+                outcomes: &["OK"],
+                condition: Some("--once: every pack verified"),
+            },
+            ExitMeaning {
+                code: 1,
+                outcomes: &["INVALID"],
+                condition: Some("--once: at least one pack failed"),
+            },
+            REFUSAL,
+        ],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "gc",
+        description: "Remove packs from a filesystem store that no --keep-last, --keep-since, or --retain rule keeps",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "store",
+        description: "Report on a filesystem store: refcount counts how many packs reference each member content hash",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "self-test",
+        description: "Run built-in known-answer tests of hashing, canonical JSON, and a golden seal/verify round trip",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), exit(1, &["FAILED"])],
+        refusal_codes: &[],
+    },
+    CommandContract {
+        name: "attest",
+        description: "Verify a pack and print its members as an in-toto Statement, optionally DSSE-signed",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["ATTESTED"]), REFUSAL],
+        refusal_codes: STORE,
+    },
+    CommandContract {
+        name: "detect",
+        description: "Show the member type seal would detect for a file, with the matched rule and its confidence",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), REFUSAL],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "canon",
+        description: "Print the RFC 8785 canonical form of a JSON document, as hashed for pack_id",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), REFUSAL],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "witness",
+        description: "Query, tail, validate, export, or verify the hash chain of the witness ledger",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), exit(1, &["INVALID"]), REFUSAL],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "quickstart",
+        description: "Write a starter config, detection rules file, and verify policy",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["CREATED"]), REFUSAL],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "config",
+        description: "Show the effective settings merged from the config file and environment",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"]), REFUSAL],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "schema",
+        description: "Validate an artifact file against the built-in or a directory-supplied schema for its version",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["PASS"]), exit(1, &["FAIL", "NO_SCHEMA"]), REFUSAL],
+        refusal_codes: &[RefusalCode::Io],
+    },
+    CommandContract {
+        name: "outcomes",
+        description: "List every command's outcomes, exit codes, and refusal codes",
+        output_mode: "report",
+        exit_codes: &[exit(0, &["OK"])],
+        refusal_codes: &[],
+    },
+];
+
+/// The contract for `command`, if it is a `pack` subcommand.
+pub fn command_contract(command: &str) -> Option<&'static CommandContract> {
+    COMMANDS.iter().find(|contract| contract.name == command)
+}
+
+/// The witness outcome for `command` exiting with `exit_code`: the first
+/// outcome the contract lists for that code, or `REFUSAL` for an exit code it
+/// does not list.
+pub fn outcome_for(command: &str, exit_code: u8) -> &'static str {
+    command_contract(command)
+        .and_then(|contract| contract.exit_codes.iter().find(|e| e.code == exit_code))
+        .and_then(|meaning| meaning.outcomes.first().copied())
+        .unwrap_or("REFUSAL")
+}
+
+impl ExitMeaning {
+    /// `PUBLISHED | ALREADY_PRESENT`, with any condition in parentheses.
+    pub fn summary(&self) -> String {
+        let outcomes = self.outcomes.join(" | ");
+        match self.condition {
+            Some(condition) => format!("{outcomes} ({condition})"),
+            None => outcomes,
+        }
+    }
+}
+
+/// Every distinct outcome reported under `exit_code`, in command order.
+pub fn outcomes_for_exit_code(exit_code: u8) -> Vec<&'static str> {
+    let mut outcomes = Vec::new();
+    for meaning in COMMANDS
+        .iter()
+        .flat_map(|contract| contract.exit_codes)
+        .filter(|meaning| meaning.code == exit_code)
+    {
+        for outcome in meaning.outcomes {
+            if !outcomes.contains(outcome) {
+                outcomes.push(*outcome);
+            }
+        }
+    }
+    outcomes
+}
+
+#[derive(Debug, Serialize)]
+struct OutcomesReport {
+    version: &'static str,
+    commands: Vec<CommandEntry>,
+    refusal_codes: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct CommandEntry {
+    name: &'static str,
+    description: &'static str,
+    exit_codes: Vec<ExitEntry>,
+    refusal_codes: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct ExitEntry {
+    code: u8,
+    outcomes: &'static [&'static str],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    condition: Option<&'static str>,
+}
+
+fn outcomes_report() -> OutcomesReport {
+    OutcomesReport {
+        version: OUTCOMES_REPORT_VERSION,
+        commands: COMMANDS
+            .iter()
+            .map(|contract| CommandEntry {
+                name: contract.name,
+                description: contract.description,
+                exit_codes: contract
+                    .exit_codes
+                    .iter()
+                    .map(|meaning| ExitEntry {
+                        code: meaning.code,
+                        outcomes: meaning.outcomes,
+                        condition: meaning.condition,
+                    })
+                    .collect(),
+                refusal_codes: contract.refusal_codes.iter().map(|c| c.as_str()).collect(),
+            })
+            .collect(),
+        refusal_codes: RefusalCode::ALL
+            .iter()
+            .map(|code| (code.as_str().to_string(), json!(code.description())))
+            .collect(),
+    }
+}
+
+/// Execute `pack outcomes`: the exit-code and refusal contract of every
+/// command, as a `pack.outcomes.v0` report or a human table. Always exits 0.
+pub fn execute_outcomes(json_output: bool) -> (String, u8) {
+    if json_output {
+        let report = outcomes_report();
+        let output = serde_json::to_string_pretty(&report)
+            .expect("outcomes report serialization cannot fail");
+        return (output, 0);
+    }
+    let mut lines = Vec::new();
+    for contract in COMMANDS {
+        lines.push(contract.name.to_string());
+        for meaning in contract.exit_codes {
+            lines.push(format!("  {}  {}", meaning.code, meaning.summary()));
+        }
+        if !contract.refusal_codes.is_empty() {
+            let codes: Vec<&str> = contract.refusal_codes.iter().map(|c| c.as_str()).collect();
+            lines.push(format!("     refusals: {}", codes.join(", ")));
+        }
+    }
+    (lines.join("\n"), 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn every_subcommand_has_a_contract() {
+        let cli = Cli::command();
+        let names: Vec<&str> = cli.get_subcommands().map(|c| c.get_name()).collect();
+        for name in &names {
+            assert!(command_contract(name).is_some(), "no contract for {name}");
+        }
+        for contract in COMMANDS {
+            assert!(
+                names.contains(&contract.name),
+                "stale contract {}",
+                contract.name
+            );
+            let codes: Vec<u8> = contract.exit_codes.iter().map(|e| e.code).collect();
+            let mut sorted = codes.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            assert_eq!(codes, sorted, "{} exit codes out of order", contract.name);
+            assert_eq!(
+                contract.exit_codes.iter().any(|e| e.code == 2),
+                !contract.refusal_codes.is_empty(),
+                "{} lists refusals without exit 2, or exit 2 without refusals",
+                contract.name
+            );
+        }
+    }
+
+    #[test]
+    fn outcomes_follow_the_table() {
+        assert_eq!(outcome_for("diff", 1), "CHANGES");
+        assert_eq!(outcome_for("push", 0), "PUBLISHED");
+        assert_eq!(outcome_for("verify", 2), "REFUSAL");
+        assert_eq!(
+            outcomes_for_exit_code(1),
+            [
+                "INVALID",
+                "CHANGES",
+                "INCOMPLETE",
+                "FAILED",
+                "FAIL",
+                "NO_SCHEMA"
+            ]
+        );
+
+        let (output, exit_code) = execute_outcomes(true);
+        assert_eq!(exit_code, 0);
+        let report: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["version"], OUTCOMES_REPORT_VERSION);
+        let extract = report["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["name"] == "extract")
+            .unwrap();
+        assert_eq!(extract["exit_codes"][0]["outcomes"][0], "EXTRACTED");
+        assert!(extract["refusal_codes"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("E_HASH_MISMATCH")));
+        assert!(report["refusal_codes"]["E_LIMIT"].is_string());
+    }
+}
@@ -0,0 +1,236 @@
+//! `pack quickstart`: starter config, detection rules, and policy files for a
+//! repository adopting pack.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Version tag for `pack quickstart --json` output.
+pub const QUICKSTART_REPORT_VERSION: &str = "pack.quickstart.v0";
+
+/// Settings file; every setting is listed, commented out at its default.
+const CONFIG_TOML: &str = r#"# pack settings. pack reads ~/.config/pack/config.toml, or this file with
+# PACK_CONFIG=config.toml. An environment variable wins over a value here, and
+# a command-line flag wins over both. `pack config show` prints the result.
+# Uncomment a line to set it.
+
+# Witness ledger path (EPISTEMIC_WITNESS).
+#witness = "~/.epistemic/witness.jsonl"
+
+# Hash-chain each witness record to the one before it (PACK_WITNESS_CHAIN).
+#witness_chain = false
+
+# Add the argv hash, duration, hostname, user, and member count to witness
+# records (PACK_WITNESS_ENRICH).
+#witness_enrich = false
+
+# Parent directory for `pack seal` output without --output (PACK_OUTPUT_ROOT).
+#output_root = "pack"
+
+# Member hash algorithm for `pack seal`: sha256, sha512, or blake3
+# (PACK_HASH_ALGORITHM).
+#hash_algorithm = "sha256"
+
+# Read buffer for hashing, in bytes (PACK_HASH_BUFFER_BYTES).
+#hash_buffer_bytes = 65536
+
+# Default store for push, pull, deprecate, gc, and store refcount: a
+# directory, file:// URL, or HTTP store (PACK_STORE).
+#store = "/srv/evidence-store"
+
+# Data-fabric base URL used when no store is set (PACK_DATA_FABRIC_BASE_URL).
+#data_fabric_base_url = "https://fabric.example.com"
+
+# Ed25519 key file for --sign. The key itself never goes in this file
+# (PACK_SIGNING_KEY_FILE).
+#signing_key_file = "keys/pack-signing.pem"
+"#;
+
+/// Detection rules for `--detect-rules`; the sample rules load as written.
+const DETECT_RULES_TOML: &str = r#"# Member type rules for `pack seal --detect-rules detect-rules.toml` and
+# `pack detect --detect-rules detect-rules.toml`. Rules are tried in order,
+# before the built-in ones; the first whose predicates all hold sets the
+# member's type, and the rules are recorded in the manifest.
+#
+#   type       lockfile, report, artifact, rules, pack, profile, registry, or other
+#   version    the member is a JSON object with this "version", recorded as
+#              its artifact_version
+#   glob       the member path matches: a path, a directory prefix, or a glob
+#              (`*` within a segment, `**` across)
+#   json_keys  the member is a JSON object with all of these top-level keys
+#   name       label `pack detect` reports; defaults to "rule N"
+#
+# Each rule needs at least one of version, glob, or json_keys.
+
+[[rules]]
+name = "ledger"
+type = "report"
+version = "ledger.v1"
+
+[[rules]]
+name = "ledger exports"
+type = "artifact"
+glob = "exports/**/*.csv"
+"#;
+
+/// Member policy for `pack verify --policy`. JSON has no comments; the fields
+/// are documented in the README.
+const POLICY_JSON: &str = r#"{
+  "required": [
+    { "type": "lockfile" },
+    { "artifact_version": "rvl.v0", "min": 1 }
+  ],
+  "forbidden_types": ["other"]
+}
+"#;
+
+/// The files `pack quickstart` writes, in the order it writes them.
+pub const QUICKSTART_FILES: [(&str, &str, &str); 3] = [
+    (
+        "config.toml",
+        CONFIG_TOML,
+        "PACK_CONFIG=config.toml pack config show",
+    ),
+    (
+        "detect-rules.toml",
+        DETECT_RULES_TOML,
+        "pack seal <ARTIFACTS> --detect-rules detect-rules.toml",
+    ),
+    (
+        "policy.json",
+        POLICY_JSON,
+        "pack verify <PACK_DIR> --policy policy.json",
+    ),
+];
+
+/// One file written by `pack quickstart`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScaffoldedFile {
+    pub path: String,
+    /// The command that reads it.
+    pub usage: String,
+}
+
+/// What `pack quickstart` wrote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QuickstartReport {
+    pub version: String,
+    pub outcome: String,
+    pub files: Vec<ScaffoldedFile>,
+}
+
+impl QuickstartReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("quickstart report serialization cannot fail")
+    }
+
+    /// `pack quickstart: CREATED`, then each file with the command that
+    /// reads it.
+    pub fn to_human(&self) -> String {
+        let width = self.files.iter().map(|f| f.path.len()).max().unwrap_or(0);
+        let mut lines = vec![format!("pack quickstart: {}", self.outcome)];
+        for file in &self.files {
+            lines.push(format!("  {:width$}  {}", file.path, file.usage));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack quickstart [DIR]`.
+///
+/// Returns (output, exit_code): 0 when every file was written, 2 with a
+/// refusal otherwise.
+pub fn execute_quickstart(dir: &Path, json_output: bool) -> (String, u8) {
+    match scaffold(dir) {
+        Ok(report) => {
+            let output = if json_output {
+                report.to_json()
+            } else {
+                report.to_human()
+            };
+            (output, 0)
+        }
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Write the starter files into `dir`, creating it if needed. Refuses with
+/// `E_IO`, writing nothing, when any of them already exists.
+pub fn scaffold(dir: &Path) -> Result<QuickstartReport, Box<RefusalEnvelope>> {
+    let io_refusal = |message: String, path: &Path| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(message),
+            Some(json!({ "path": path.display().to_string() })),
+        ))
+    };
+    for (name, _, _) in QUICKSTART_FILES {
+        let path = dir.join(name);
+        if path.symlink_metadata().is_ok() {
+            return Err(io_refusal(
+                format!("Refusing to overwrite {}", path.display()),
+                &path,
+            ));
+        }
+    }
+    fs::create_dir_all(dir)
+        .map_err(|e| io_refusal(format!("Cannot create {}: {e}", dir.display()), dir))?;
+
+    let mut files = Vec::new();
+    for (name, content, usage) in QUICKSTART_FILES {
+        let path = dir.join(name);
+        fs::write(&path, content)
+            .map_err(|e| io_refusal(format!("Cannot write {}: {e}", path.display()), &path))?;
+        files.push(ScaffoldedFile {
+            path: path.display().to_string(),
+            usage: usage.to_string(),
+        });
+    }
+    Ok(QuickstartReport {
+        version: QUICKSTART_REPORT_VERSION.to_string(),
+        outcome: "CREATED".to_string(),
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{parse_config, SETTINGS};
+    use crate::detect::load_detection_rules;
+    use crate::verify::load_member_policy;
+
+    #[test]
+    fn scaffolded_files_load_and_are_never_overwritten() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("repo");
+        let report = scaffold(&dir).unwrap();
+        assert_eq!(report.files.len(), 3);
+
+        // Uncommenting every setting gives a valid config naming all of them.
+        let config = fs::read_to_string(dir.join("config.toml")).unwrap();
+        let uncommented: String = config
+            .lines()
+            .filter_map(|line| line.strip_prefix('#'))
+            .filter(|line| !line.starts_with([' ', '#']) && !line.is_empty())
+            .map(|line| format!("{line}\n"))
+            .collect();
+        let values = parse_config(&uncommented).unwrap();
+        assert_eq!(values.len(), SETTINGS.len());
+
+        let rules = load_detection_rules(&dir.join("detect-rules.toml")).unwrap();
+        assert_eq!(rules[0].version.as_deref(), Some("ledger.v1"));
+        let policy = load_member_policy(&dir.join("policy.json")).unwrap();
+        assert_eq!(policy.required.len(), 2);
+
+        fs::write(dir.join("policy.json"), "{}").unwrap();
+        fs::remove_file(dir.join("config.toml")).unwrap();
+        let refusal = scaffold(&dir).unwrap_err();
+        assert_eq!(refusal.refusal.code, "E_IO");
+        assert!(!dir.join("config.toml").exists(), "nothing written");
+    }
+}
@@ -11,15 +11,53 @@ pub enum RefusalCode {
     Duplicate,
     /// Missing or invalid `manifest.json` for verify/diff/push.
     BadPack,
+    /// Member bytes disagree with a `--trust-hashes` entry, or with the
+    /// manifest while extracting.
+    HashMismatch,
+    /// A note, metadata value, or manifest exceeds its configured size limit.
+    Limit,
+    /// `seal --strict-types` found members typed only by a weak heuristic.
+    AmbiguousType,
 }
 
 impl RefusalCode {
+    /// Every refusal code, in the order `--describe` lists them.
+    pub const ALL: [RefusalCode; 7] = [
+        Self::Empty,
+        Self::Io,
+        Self::Duplicate,
+        Self::BadPack,
+        Self::HashMismatch,
+        Self::Limit,
+        Self::AmbiguousType,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Empty => "E_EMPTY",
             Self::Io => "E_IO",
             Self::Duplicate => "E_DUPLICATE",
             Self::BadPack => "E_BAD_PACK",
+            Self::HashMismatch => "E_HASH_MISMATCH",
+            Self::Limit => "E_LIMIT",
+            Self::AmbiguousType => "E_AMBIGUOUS_TYPE",
+        }
+    }
+
+    /// When the code is raised, as published by `--describe`.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Empty => "seal called with no artifacts",
+            Self::Io => "Cannot read input, write output, or read pack directory",
+            Self::Duplicate => "Member path collision during seal (including reserved paths)",
+            Self::BadPack => "Missing or invalid pack payload for verify/diff/push/pull",
+            Self::HashMismatch => {
+                "Member bytes disagree with --trust-hashes or, on extract, the manifest"
+            }
+            Self::Limit => "Note or manifest exceeds its configured size limit",
+            Self::AmbiguousType => {
+                "seal --strict-types found members typed only by filename or path"
+            }
         }
     }
 
@@ -29,6 +67,9 @@ impl RefusalCode {
             Self::Io => "IO failure reading or writing pack data",
             Self::Duplicate => "Resolved member path collision",
             Self::BadPack => "Missing or invalid manifest.json",
+            Self::HashMismatch => "Member bytes do not match the expected hash",
+            Self::Limit => "Value exceeds configured size limit",
+            Self::AmbiguousType => "Member type detection relied on a weak heuristic",
         }
     }
 }
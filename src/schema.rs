@@ -1,11 +1,14 @@
-use serde_json::{json, Value};
+use std::collections::BTreeSet;
 
-/// Return the JSON Schema for pack.v0 manifest and verify output.
+use serde_json::{json, Map, Value};
+
+/// Return the JSON Schema for pack.v0 manifests, reports, refusals, and
+/// witness records.
 pub fn pack_schema() -> Value {
     json!({
         "$schema": "https://json-schema.org/draft/2020-12/schema",
         "$id": "pack.v0",
-        "title": "pack.v0 manifest and verify schema",
+        "title": "pack.v0 manifest, report, and witness schema",
         "definitions": {
             "manifest": {
                 "type": "object",
@@ -36,6 +39,134 @@ pub fn pack_schema() -> Value {
                     "member_count": {
                         "type": "integer",
                         "minimum": 0
+                    },
+                    "annotations": {
+                        "type": "object",
+                        "additionalProperties": { "type": "string" }
+                    },
+                    "provenance": { "$ref": "#/definitions/provenance" },
+                    "encryption": {
+                        "type": "object",
+                        "required": ["scheme", "recipients"],
+                        "properties": {
+                            "scheme": { "const": "age-x25519" },
+                            "recipients": {
+                                "type": "array",
+                                "minItems": 1,
+                                "items": {
+                                    "type": "string",
+                                    "pattern": "^age1[02-9ac-hj-np-z]{58}$"
+                                }
+                            }
+                        },
+                        "additionalProperties": false
+                    },
+                    "lineage": {
+                        "type": "object",
+                        "required": ["parent_pack_ids"],
+                        "properties": {
+                            "parent_pack_ids": {
+                                "type": "array",
+                                "items": {
+                                    "type": "string",
+                                    "pattern": "^sha256:[a-f0-9]{64}$"
+                                }
+                            }
+                        },
+                        "additionalProperties": false
+                    }
+                },
+                "additionalProperties": false
+            },
+            "provenance": {
+                "type": "object",
+                "properties": {
+                    "empty_dirs": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "sealed_at": {
+                        "type": "object",
+                        "additionalProperties": {
+                            "type": "string",
+                            "format": "date-time"
+                        }
+                    },
+                    "executable_in_pack_id": { "const": true },
+                    "sources": {
+                        "type": "object",
+                        "required": ["members"],
+                        "properties": {
+                            "hostname": { "type": "string" },
+                            "user": { "type": "string" },
+                            "members": {
+                                "type": "object",
+                                "additionalProperties": {
+                                    "type": "object",
+                                    "required": ["source"],
+                                    "properties": {
+                                        "source": { "type": "string" },
+                                        "mtime": {
+                                            "type": "string",
+                                            "format": "date-time"
+                                        }
+                                    },
+                                    "additionalProperties": false
+                                }
+                            }
+                        },
+                        "additionalProperties": false
+                    },
+                    "detection_rules": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["type"],
+                            "properties": {
+                                "name": { "type": "string" },
+                                "type": {
+                                    "type": "string",
+                                    "enum": ["lockfile", "report", "artifact", "rules", "pack", "profile", "registry", "other"]
+                                },
+                                "version": { "type": "string" },
+                                "glob": { "type": "string" },
+                                "json_keys": {
+                                    "type": "array",
+                                    "items": { "type": "string" }
+                                }
+                            },
+                            "additionalProperties": false
+                        }
+                    },
+                    "reproducible": {
+                        "type": "string",
+                        "enum": ["source_date_epoch", "created_excluded"]
+                    },
+                    "skipped_symlinks": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "trust_hashes": {
+                        "type": "object",
+                        "required": ["mode", "trusted", "sampled"],
+                        "properties": {
+                            "mode": { "type": "string", "enum": ["sampled"] },
+                            "trusted": { "type": "integer", "minimum": 0 },
+                            "sampled": { "type": "integer", "minimum": 0 }
+                        },
+                        "additionalProperties": false
+                    },
+                    "remote": {
+                        "type": "object",
+                        "additionalProperties": {
+                            "type": "object",
+                            "required": ["url", "final_url"],
+                            "properties": {
+                                "url": { "type": "string" },
+                                "final_url": { "type": "string" }
+                            },
+                            "additionalProperties": false
+                        }
                     }
                 },
                 "additionalProperties": false
@@ -47,7 +178,11 @@ pub fn pack_schema() -> Value {
                     "path": { "type": "string" },
                     "bytes_hash": {
                         "type": "string",
-                        "pattern": "^sha256:[a-f0-9]{64}$"
+                        "pattern": "^(sha256:[a-f0-9]{64}|sha512:[a-f0-9]{128}|blake3:[a-f0-9]{64})$"
+                    },
+                    "size_bytes": {
+                        "type": "integer",
+                        "minimum": 0
                     },
                     "type": {
                         "type": "string",
@@ -55,10 +190,38 @@ pub fn pack_schema() -> Value {
                     },
                     "artifact_version": {
                         "type": ["string", "null"]
+                    },
+                    "content_type": { "type": "string" },
+                    "charset": { "type": "string" },
+                    "visibility": {
+                        "type": "string",
+                        "pattern": "^[a-z0-9._-]+$"
+                    },
+                    "origin": { "$ref": "#/definitions/member_origin" },
+                    "executable": { "const": true },
+                    "ciphertext_hash": {
+                        "type": "string",
+                        "pattern": "^(sha256:[a-f0-9]{64}|sha512:[a-f0-9]{128}|blake3:[a-f0-9]{64})$"
                     }
                 },
                 "additionalProperties": false
             },
+            "member_origin": {
+                "type": "object",
+                "required": ["registry", "digest"],
+                "properties": {
+                    "registry": {
+                        "type": "string",
+                        "minLength": 1
+                    },
+                    "digest": {
+                        "type": "string",
+                        "pattern": "^[a-z0-9]+([+._-][a-z0-9]+)*:[a-zA-Z0-9=_-]+$"
+                    },
+                    "media_type": { "type": "string" }
+                },
+                "additionalProperties": false
+            },
             "verify_report": {
                 "type": "object",
                 "required": ["version", "outcome", "checks", "invalid"],
@@ -79,6 +242,23 @@ pub fn pack_schema() -> Value {
                         "type": "array",
                         "items": { "$ref": "#/definitions/invalid_finding" }
                     },
+                    "warnings": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/verify_warning" }
+                    },
+                    "timestamped_at": { "type": "string" },
+                    "timings": {
+                        "type": "object",
+                        "required": ["checks_ms", "total_ms"],
+                        "properties": {
+                            "checks_ms": {
+                                "type": "object",
+                                "additionalProperties": { "type": "number", "minimum": 0 }
+                            },
+                            "total_ms": { "type": "number", "minimum": 0 }
+                        },
+                        "additionalProperties": false
+                    },
                     "refusal": {}
                 },
                 "additionalProperties": false
@@ -91,12 +271,46 @@ pub fn pack_schema() -> Value {
                     "member_count": { "type": "boolean" },
                     "member_paths": { "type": "boolean" },
                     "extra_members": { "type": "boolean" },
-                    "member_hashes": { "type": "boolean" },
+                    "member_hashes": {
+                        "enum": [true, false, "skipped"],
+                        "description": "\"skipped\" under verify --quick"
+                    },
+                    "member_sizes": { "type": "boolean" },
                     "pack_id": { "type": "boolean" },
                     "schema_validation": {
                         "type": "string",
                         "enum": ["pass", "fail", "skipped"]
-                    }
+                    },
+                    "schema_members": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/schema_member_result" }
+                    },
+                    "empty_dirs": { "type": "boolean" },
+                    "cross_references": { "type": "boolean" },
+                    "registry": { "type": "boolean" },
+                    "nested_packs": { "type": "boolean" },
+                    "signature": { "type": "boolean" },
+                    "trust_policy": { "type": "boolean" },
+                    "policy": { "type": "boolean" },
+                    "timestamp": {
+                        "enum": [true, false, "unverified"],
+                        "description": "\"unverified\" without verify --tsa-cert"
+                    },
+                    "decryption": { "type": "boolean" }
+                },
+                "additionalProperties": false
+            },
+            "schema_member_result": {
+                "type": "object",
+                "required": ["path", "artifact_version", "status"],
+                "properties": {
+                    "path": { "type": "string" },
+                    "artifact_version": { "type": "string" },
+                    "status": {
+                        "type": "string",
+                        "enum": ["pass", "fail"]
+                    },
+                    "reason": { "type": "string" }
                 },
                 "additionalProperties": false
             },
@@ -109,25 +323,418 @@ pub fn pack_schema() -> Value {
                         "enum": [
                             "MISSING_MEMBER",
                             "HASH_MISMATCH",
+                            "SIZE_MISMATCH",
                             "PACK_ID_MISMATCH",
                             "DUPLICATE_MEMBER_PATH",
                             "RESERVED_MEMBER_PATH",
                             "UNSAFE_MEMBER_PATH",
                             "NON_REGULAR_MEMBER",
                             "EXTRA_MEMBER",
-                            "MEMBER_COUNT_MISMATCH"
+                            "MEMBER_COUNT_MISMATCH",
+                            "UNREADABLE_MEMBER",
+                            "OVERSIZED_FIELD",
+                            "SIGNATURE_MISSING",
+                            "SIGNATURE_INVALID",
+                            "SIGNATURE_UNTRUSTED",
+                            "SCHEMA_VIOLATION",
+                            "CROSS_REFERENCE_MISMATCH",
+                            "CROSS_REFERENCE_MISSING",
+                            "REGISTRY_MALFORMED",
+                            "PACKKEEP_NOT_EMPTY",
+                            "EMPTY_DIR_NOT_EMPTY",
+                            "EMPTY_PACK",
+                            "NESTED_PACK_UNVERIFIABLE",
+                            "UNSUPPORTED_HASH_ALGORITHM",
+                            "TRUST_POLICY",
+                            "POLICY_VIOLATION",
+                            "CONTENT_TYPE_MISMATCH",
+                            "TIMESTAMP_MISSING",
+                            "TIMESTAMP_INVALID",
+                            "DECRYPTION_FAILED",
+                            "NONCANONICAL_MANIFEST_ENCODING",
+                            "MISSING_MEMBER_SIZE",
+                            "DEPRECATED"
                         ]
                     },
+                    "severity": {
+                        "enum": ["error", "warning"],
+                        "description": "absent for errors"
+                    },
                     "path": { "type": "string" },
                     "expected": { "type": "string" },
                     "actual": { "type": "string" }
                 },
                 "additionalProperties": false
+            },
+            "verify_warning": {
+                "type": "object",
+                "required": ["code", "reason"],
+                "properties": {
+                    "code": { "type": "string" },
+                    "severity": {
+                        "enum": ["error", "warning"],
+                        "description": "\"warning\" for warning-class findings; absent for suppressed errors"
+                    },
+                    "path": { "type": "string" },
+                    "expected": { "type": "string" },
+                    "actual": { "type": "string" },
+                    "reason": { "type": "string" }
+                },
+                "additionalProperties": false
+            },
+            "inspect_report": {
+                "type": "object",
+                "required": ["version", "pack_id", "created", "note", "tool_version", "member_count", "members"],
+                "properties": {
+                    "version": {
+                        "type": "string",
+                        "const": "pack.inspect.v0"
+                    },
+                    "pack_id": { "type": "string" },
+                    "created": { "type": "string" },
+                    "note": { "type": ["string", "null"] },
+                    "tool_version": { "type": "string" },
+                    "member_count": {
+                        "type": "integer",
+                        "minimum": 0
+                    },
+                    "members": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/inspect_member" }
+                    },
+                    "deprecation": { "$ref": "#/definitions/deprecation" }
+                },
+                "additionalProperties": false
+            },
+            "deprecation": {
+                "type": "object",
+                "required": ["reason", "deprecated_at"],
+                "properties": {
+                    "reason": { "type": "string" },
+                    "deprecated_at": {
+                        "type": "string",
+                        "format": "date-time"
+                    },
+                    "public_key": { "type": "string" },
+                    "signature": { "type": "string" }
+                },
+                "additionalProperties": false
+            },
+            "inspect_member": {
+                "type": "object",
+                "required": ["path", "type", "artifact_version", "size_bytes", "bytes_hash"],
+                "properties": {
+                    "path": { "type": "string" },
+                    "type": { "type": "string" },
+                    "artifact_version": { "type": ["string", "null"] },
+                    "size_bytes": {
+                        "type": ["integer", "null"],
+                        "minimum": 0
+                    },
+                    "bytes_hash": { "type": "string" },
+                    "content_type": { "type": "string" },
+                    "charset": { "type": "string" },
+                    "visibility": { "type": "string" },
+                    "origin": { "$ref": "#/definitions/member_origin" },
+                    "executable": { "const": true },
+                    "annotation": { "type": "string" }
+                },
+                "additionalProperties": false
+            },
+            "diff_report": {
+                "type": "object",
+                "required": ["version", "outcome", "a_pack_id", "b_pack_id", "added", "removed", "changed", "unchanged"],
+                "properties": {
+                    "version": {
+                        "type": "string",
+                        "const": "pack.diff.v0"
+                    },
+                    "outcome": {
+                        "type": "string",
+                        "enum": ["NO_CHANGES", "CHANGES"]
+                    },
+                    "a_pack_id": { "type": "string" },
+                    "b_pack_id": { "type": "string" },
+                    "added": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/diff_entry" }
+                    },
+                    "removed": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/diff_entry" }
+                    },
+                    "changed": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/diff_entry" }
+                    },
+                    "metadata_changed": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/diff_entry" }
+                    },
+                    "unchanged": {
+                        "type": "integer",
+                        "minimum": 0
+                    },
+                    "nested": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/nested_diff" }
+                    }
+                },
+                "additionalProperties": false
+            },
+            "diff_entry": {
+                "type": "object",
+                "required": ["kind", "path"],
+                "properties": {
+                    "kind": {
+                        "type": "string",
+                        "enum": ["added", "removed", "changed", "metadata_changed"]
+                    },
+                    "path": { "type": "string" },
+                    "a_hash": { "type": "string" },
+                    "b_hash": { "type": "string" },
+                    "fields": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/field_change" }
+                    },
+                    "annotation": { "type": "string" }
+                },
+                "additionalProperties": false
+            },
+            "field_change": {
+                "type": "object",
+                "required": ["field", "a", "b"],
+                "properties": {
+                    "field": { "type": "string" },
+                    "a": { "type": ["string", "null"] },
+                    "b": { "type": ["string", "null"] }
+                },
+                "additionalProperties": false
+            },
+            "nested_diff": {
+                "type": "object",
+                "required": ["path", "diff"],
+                "properties": {
+                    "path": { "type": "string" },
+                    "diff": { "$ref": "#/definitions/diff_report" }
+                },
+                "additionalProperties": false
+            },
+            "seal_report": {
+                "type": "object",
+                "required": ["version", "outcome", "pack_id", "output", "member_count"],
+                "properties": {
+                    "version": {
+                        "type": "string",
+                        "const": "pack.seal.v0"
+                    },
+                    "outcome": {
+                        "type": "string",
+                        "const": "PACK_CREATED"
+                    },
+                    "pack_id": {
+                        "type": "string",
+                        "pattern": "^sha256:[a-f0-9]{64}$"
+                    },
+                    "output": { "type": "string" },
+                    "member_count": { "type": "integer", "minimum": 0 },
+                    "resumed": { "type": "integer", "minimum": 0 },
+                    "parent_pack_id": {
+                        "type": "string",
+                        "pattern": "^sha256:[a-f0-9]{64}$"
+                    },
+                    "signer": {
+                        "type": "string",
+                        "pattern": "^[a-f0-9]{64}$"
+                    },
+                    "timestamped_at": { "type": "string" },
+                    "trust": {
+                        "type": "object",
+                        "required": ["trusted", "sampled"],
+                        "properties": {
+                            "trusted": { "type": "integer", "minimum": 0 },
+                            "sampled": { "type": "integer", "minimum": 0 }
+                        },
+                        "additionalProperties": false
+                    },
+                    "excluded_git": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "skipped_symlinks": {
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "output_work_tree": { "type": "string" }
+                },
+                "additionalProperties": false
+            },
+            "refusal_envelope": {
+                "type": "object",
+                "required": ["version", "outcome", "refusal"],
+                "properties": {
+                    "version": {
+                        "type": "string",
+                        "const": "pack.v0"
+                    },
+                    "outcome": {
+                        "type": "string",
+                        "const": "REFUSAL"
+                    },
+                    "refusal": {
+                        "type": "object",
+                        "required": ["code", "message", "next_command"],
+                        "properties": {
+                            "code": {
+                                "type": "string",
+                                "enum": ["E_EMPTY", "E_IO", "E_DUPLICATE", "E_BAD_PACK", "E_HASH_MISMATCH", "E_LIMIT", "E_AMBIGUOUS_TYPE"]
+                            },
+                            "message": { "type": "string" },
+                            "detail": {},
+                            "next_command": { "type": ["string", "null"] }
+                        },
+                        "additionalProperties": false
+                    }
+                },
+                "additionalProperties": false
+            },
+            "witness_record": {
+                "type": "object",
+                "required": ["tool", "version", "outcome"],
+                "properties": {
+                    "id": { "type": "string" },
+                    "tool": { "type": "string" },
+                    "version": {
+                        "type": "string",
+                        "description": "Tool semver, or the legacy schema tag witness.v0"
+                    },
+                    "command": { "type": ["string", "null"] },
+                    "binary_hash": { "type": "string" },
+                    "inputs": {
+                        "type": "array",
+                        "items": { "$ref": "#/definitions/witness_input" }
+                    },
+                    "params": { "type": "object" },
+                    "outcome": { "type": "string" },
+                    "exit_code": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": 255
+                    },
+                    "output_hash": { "type": "string" },
+                    "ts": {
+                        "type": "string",
+                        "format": "date-time"
+                    },
+                    "timestamp": {
+                        "type": "string",
+                        "format": "date-time",
+                        "description": "Legacy alias for ts"
+                    },
+                    "pack_id": { "type": ["string", "null"] },
+                    "prev_hash": {
+                        "type": "string",
+                        "pattern": "^sha256:[a-f0-9]{64}$",
+                        "description": "Hash of the previous ledger line in a chained ledger"
+                    },
+                    "enrichment": { "$ref": "#/definitions/witness_enrichment" }
+                },
+                "anyOf": [
+                    { "required": ["ts"] },
+                    { "required": ["timestamp"] }
+                ]
+            },
+            "witness_enrichment": {
+                "type": "object",
+                "description": "Run context added when PACK_WITNESS_ENRICH=1",
+                "required": ["argv_hash"],
+                "properties": {
+                    "argv_hash": {
+                        "type": "string",
+                        "pattern": "^sha256:[a-f0-9]{64}$",
+                        "description": "Hash of the command-line arguments after the program name"
+                    },
+                    "duration_ms": {
+                        "type": "integer",
+                        "minimum": 0
+                    },
+                    "hostname": { "type": "string" },
+                    "user": { "type": "string" },
+                    "member_count": {
+                        "type": "integer",
+                        "minimum": 0
+                    },
+                    "pack_bytes": {
+                        "type": "integer",
+                        "minimum": 0
+                    }
+                }
+            },
+            "witness_input": {
+                "type": "object",
+                "required": ["path"],
+                "properties": {
+                    "path": { "type": "string" },
+                    "hash": { "type": "string" },
+                    "bytes": {
+                        "type": "integer",
+                        "minimum": 0
+                    }
+                }
             }
         }
     })
 }
 
+/// Return a standalone schema for one top-level definition (e.g. `diff_report`).
+///
+/// The root validates the named definition directly; `definitions` keeps only
+/// what it references, transitively, so existing `$ref`s still resolve.
+/// Returns `None` for an unknown definition.
+pub fn definition_schema(name: &str) -> Option<Value> {
+    let full = pack_schema();
+    let definitions = full["definitions"].as_object()?;
+    definitions.get(name)?;
+
+    let mut needed = BTreeSet::new();
+    let mut pending = vec![name.to_string()];
+    while let Some(current) = pending.pop() {
+        if !needed.insert(current.clone()) {
+            continue;
+        }
+        collect_refs(&definitions[&current], &mut pending);
+    }
+    let kept: Map<String, Value> = needed
+        .into_iter()
+        .map(|def| (def.clone(), definitions[&def].clone()))
+        .collect();
+
+    Some(json!({
+        "$schema": full["$schema"],
+        "$id": format!("pack.v0#{name}"),
+        "title": format!("pack.v0 {name}"),
+        "$ref": format!("#/definitions/{name}"),
+        "definitions": kept,
+    }))
+}
+
+fn collect_refs(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(target) = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix("#/definitions/"))
+            {
+                out.push(target.to_string());
+            }
+            map.values().for_each(|v| collect_refs(v, out));
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_refs(v, out)),
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,6 +748,9 @@ mod tests {
         assert!(defs.contains_key("verify_report"));
         assert!(defs.contains_key("verify_checks"));
         assert!(defs.contains_key("invalid_finding"));
+        assert!(defs.contains_key("witness_record"));
+        assert!(defs.contains_key("witness_input"));
+        assert!(defs.contains_key("witness_enrichment"));
     }
 
     #[test]
@@ -169,4 +779,39 @@ mod tests {
         assert_eq!(s["$id"], "pack.v0");
         assert!(s["title"].as_str().is_some());
     }
+
+    #[test]
+    fn schema_defines_diff_and_refusal_shapes() {
+        let s = pack_schema();
+        assert_eq!(
+            s["definitions"]["diff_report"]["properties"]["version"]["const"],
+            crate::diff::DIFF_REPORT_VERSION
+        );
+        let codes = s["definitions"]["refusal_envelope"]["properties"]["refusal"]["properties"]
+            ["code"]["enum"]
+            .as_array()
+            .unwrap();
+        assert_eq!(codes.len(), crate::refusal::RefusalCode::ALL.len());
+    }
+
+    #[test]
+    fn definition_schema_keeps_only_referenced_definitions() {
+        let s = definition_schema("diff_report").unwrap();
+        assert_eq!(s["$ref"], "#/definitions/diff_report");
+        let defs: Vec<&String> = s["definitions"].as_object().unwrap().keys().collect();
+        assert_eq!(
+            defs,
+            ["diff_entry", "diff_report", "field_change", "nested_diff"]
+        );
+
+        let manifest = definition_schema("manifest").unwrap();
+        let defs: Vec<&String> = manifest["definitions"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect();
+        assert_eq!(defs, ["manifest", "member", "member_origin", "provenance"]);
+
+        assert!(definition_schema("nope").is_none());
+    }
 }
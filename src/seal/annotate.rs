@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::collect::MemberCandidate;
+use crate::seal::limits::check_field_size;
+
+/// A `--annotate <MEMBER_PATH>=<TEXT>` label for one member, such as
+/// `loans/tape.csv=this is the disputed loan tape`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberAnnotation {
+    pub path: String,
+    pub text: String,
+}
+
+impl MemberAnnotation {
+    /// Parse a `--annotate MEMBER_PATH=TEXT` argument. The path ends at the
+    /// first `=`, so the text may contain more.
+    pub fn from_flag(raw: &str) -> Result<Self, String> {
+        let (path, text) = raw
+            .split_once('=')
+            .ok_or_else(|| format!("--annotate expects MEMBER_PATH=TEXT, got {raw:?}"))?;
+        if path.is_empty() {
+            return Err(format!("--annotate member path is empty in {raw:?}"));
+        }
+        if text.trim().is_empty() {
+            return Err(format!("--annotate text is empty in {raw:?}"));
+        }
+        Ok(Self {
+            path: path.to_string(),
+            text: text.to_string(),
+        })
+    }
+}
+
+/// Resolve `annotations` against the collected members, returning member
+/// path → text.
+///
+/// A path that names no member refuses with `E_IO`, and one annotated twice
+/// keeps the last text. Each text is held to the `note` size limit.
+pub fn resolve_annotations(
+    candidates: &[MemberCandidate],
+    annotations: &[MemberAnnotation],
+    max_bytes: usize,
+) -> Result<BTreeMap<String, String>, Box<RefusalEnvelope>> {
+    let mut texts = BTreeMap::new();
+    for annotation in annotations {
+        if !candidates
+            .iter()
+            .any(|candidate| candidate.member_path == annotation.path)
+        {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("--annotate {} matches no member", annotation.path)),
+                Some(json!({ "path": annotation.path })),
+            )));
+        }
+        check_field_size("annotation", &annotation.text, max_bytes)?;
+        texts.insert(annotation.path.clone(), annotation.text.clone());
+    }
+    Ok(texts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn annotations_resolve_by_exact_member_path() {
+        let candidates = [MemberCandidate {
+            source: PathBuf::from("/src/tape.csv"),
+            member_path: "loans/tape.csv".to_string(),
+        }];
+        let annotation = MemberAnnotation::from_flag("loans/tape.csv=disputed; see a=b").unwrap();
+        assert_eq!(annotation.text, "disputed; see a=b");
+        let texts = resolve_annotations(&candidates, &[annotation], 1024).unwrap();
+        assert_eq!(texts["loans/tape.csv"], "disputed; see a=b");
+
+        let unknown = MemberAnnotation::from_flag("loans=all of them").unwrap();
+        let refusal = resolve_annotations(&candidates, &[unknown], 1024).unwrap_err();
+        assert_eq!(refusal.refusal.code, "E_IO");
+        assert!(MemberAnnotation::from_flag("loans/tape.csv").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Component, Path};
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::copy::set_executable;
+use crate::seal::limits::MAX_MANIFEST_BYTES;
+use crate::seal::manifest::Manifest;
+use crate::sign::SIGNATURE_FILE;
+use crate::timestamp::TIMESTAMP_FILE;
+
+/// File extension for single-file packs written by `seal --archive`.
+pub const ARCHIVE_EXTENSION: &str = ".pack.tar";
+
+/// Mode recorded for every archive entry except executable members.
+const ENTRY_MODE: u32 = 0o644;
+
+/// Mode recorded for members flagged `executable`.
+const EXECUTABLE_ENTRY_MODE: u32 = 0o755;
+
+/// Write `manifest.json`, its signature and timestamp when present, and every
+/// member of the pack at `pack_dir` into a deterministic tar at `dest`.
+///
+/// Entries are regular files only, sorted bytewise by path, with mtime 0,
+/// uid/gid 0, no owner names, and mode 0644 (0755 for `executable` members),
+/// so the same pack always yields the same archive bytes. The file is written
+/// beside `dest` and renamed into place.
+pub fn write_archive(
+    pack_dir: &Path,
+    manifest: &Manifest,
+    dest: &Path,
+) -> Result<(), Box<RefusalEnvelope>> {
+    let mut paths: Vec<&str> = manifest.members.iter().map(|m| m.path.as_str()).collect();
+    paths.push("manifest.json");
+    for sidecar in [SIGNATURE_FILE, TIMESTAMP_FILE] {
+        if pack_dir.join(sidecar).is_file() {
+            paths.push(sidecar);
+        }
+    }
+    paths.sort_unstable();
+    let executable: HashSet<&str> = manifest
+        .members
+        .iter()
+        .filter(|m| m.executable == Some(true))
+        .map(|m| m.path.as_str())
+        .collect();
+
+    let parent = dest
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let tmp = tempfile::Builder::new()
+        .prefix(".pack-archive-")
+        .tempfile_in(parent)
+        .map_err(|e| archive_refusal(dest, format!("Cannot create archive: {e}")))?;
+
+    let mut builder = tar::Builder::new(BufWriter::new(tmp.as_file()));
+    for path in paths {
+        let file = fs::File::open(pack_dir.join(path))
+            .map_err(|e| archive_refusal(dest, format!("Cannot read {path}: {e}")))?;
+        let size = file
+            .metadata()
+            .map_err(|e| archive_refusal(dest, format!("Cannot read {path}: {e}")))?
+            .len();
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(size);
+        header.set_mode(if executable.contains(path) {
+            EXECUTABLE_ENTRY_MODE
+        } else {
+            ENTRY_MODE
+        });
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+        builder
+            .append_data(&mut header, path, file)
+            .map_err(|e| archive_refusal(dest, format!("Cannot archive {path}: {e}")))?;
+    }
+    builder
+        .into_inner()
+        .and_then(|mut writer| writer.flush())
+        .map_err(|e| archive_refusal(dest, format!("Cannot write archive: {e}")))?;
+
+    tmp.persist_noclobber(dest).map_err(|e| {
+        archive_refusal(
+            dest,
+            format!("Cannot write archive {}: {}", dest.display(), e.error),
+        )
+    })?;
+    Ok(())
+}
+
+/// True when `path` names a pack archive rather than a pack directory.
+pub fn is_archive(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Unpack a pack archive into the empty directory `dest`.
+///
+/// Only regular files (and directory entries) with relative, `..`-free paths
+/// are accepted; anything else, including duplicate paths, refuses with
+/// `E_BAD_PACK`. Entries with an execute bit are unpacked executable.
+/// Verification of the unpacked tree is left to the caller.
+pub fn unpack_archive(archive: &Path, dest: &Path) -> Result<(), Box<RefusalEnvelope>> {
+    let file = fs::File::open(archive)
+        .map_err(|e| bad_archive(archive, format!("Cannot open archive: {e}")))?;
+    let mut reader = tar::Archive::new(io::BufReader::new(file));
+    let entries = reader
+        .entries()
+        .map_err(|e| bad_archive(archive, format!("Cannot read archive: {e}")))?;
+
+    for entry in entries {
+        let mut entry =
+            entry.map_err(|e| bad_archive(archive, format!("Cannot read archive: {e}")))?;
+        let path = entry
+            .path()
+            .map_err(|e| bad_archive(archive, format!("Invalid archive entry path: {e}")))?
+            .into_owned();
+        if path.as_os_str().is_empty()
+            || !path.components().all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(bad_archive(
+                archive,
+                format!("Unsafe archive entry path: {}", path.display()),
+            ));
+        }
+
+        let target = dest.join(&path);
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => {
+                fs::create_dir_all(&target).map_err(|e| io_refusal(archive, &path, e))?;
+            }
+            tar::EntryType::Regular => {
+                if target.exists() {
+                    return Err(bad_archive(
+                        archive,
+                        format!("Duplicate archive entry: {}", path.display()),
+                    ));
+                }
+                if let Some(dir) = target.parent() {
+                    fs::create_dir_all(dir).map_err(|e| io_refusal(archive, &path, e))?;
+                }
+                let mut out =
+                    fs::File::create(&target).map_err(|e| io_refusal(archive, &path, e))?;
+                io::copy(&mut entry, &mut out).map_err(|e| io_refusal(archive, &path, e))?;
+                if entry.header().mode().is_ok_and(|mode| mode & 0o111 != 0) {
+                    set_executable(&target).map_err(|e| io_refusal(archive, &path, e))?;
+                }
+            }
+            other => {
+                return Err(bad_archive(
+                    archive,
+                    format!(
+                        "Unsupported archive entry type {other:?}: {}",
+                        path.display()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Read `manifest.json` out of a pack archive without unpacking members.
+///
+/// Refuses with `E_BAD_PACK` when the archive has no manifest and with
+/// `E_LIMIT` when the manifest exceeds [`MAX_MANIFEST_BYTES`].
+pub fn read_archive_manifest(archive: &Path) -> Result<Vec<u8>, Box<RefusalEnvelope>> {
+    let file = fs::File::open(archive)
+        .map_err(|e| bad_archive(archive, format!("Cannot open archive: {e}")))?;
+    let mut reader = tar::Archive::new(io::BufReader::new(file));
+    let entries = reader
+        .entries()
+        .map_err(|e| bad_archive(archive, format!("Cannot read archive: {e}")))?;
+    for entry in entries {
+        let mut entry =
+            entry.map_err(|e| bad_archive(archive, format!("Cannot read archive: {e}")))?;
+        let is_manifest = entry
+            .path()
+            .is_ok_and(|path| path.as_os_str() == "manifest.json");
+        if !is_manifest || entry.header().entry_type() != tar::EntryType::Regular {
+            continue;
+        }
+        let size = entry.size();
+        if size > MAX_MANIFEST_BYTES {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Limit,
+                Some(format!(
+                    "manifest.json is {size} bytes, exceeding the {MAX_MANIFEST_BYTES}-byte limit"
+                )),
+                Some(json!({ "archive": archive.display().to_string() })),
+            )));
+        }
+        let mut bytes = Vec::with_capacity(size as usize);
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| bad_archive(archive, format!("Cannot read manifest.json: {e}")))?;
+        return Ok(bytes);
+    }
+    Err(bad_archive(
+        archive,
+        "Archive has no manifest.json".to_string(),
+    ))
+}
+
+fn archive_refusal(dest: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "archive": dest.display().to_string() })),
+    ))
+}
+
+fn bad_archive(archive: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::BadPack,
+        Some(message),
+        Some(json!({ "archive": archive.display().to_string() })),
+    ))
+}
+
+fn io_refusal(archive: &Path, path: &Path, err: io::Error) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(format!("Cannot unpack {}: {err}", path.display())),
+        Some(json!({ "archive": archive.display().to_string() })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    fn sealed(tmp: &TempDir) -> (std::path::PathBuf, Manifest) {
+        let src = tmp.path().join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("b.json"), "{}").unwrap();
+        fs::write(src.join("nested").join("a.txt"), "alpha").unwrap();
+        let pack_dir = tmp.path().join("pack");
+        execute_seal(&[src], Some(&pack_dir), None).unwrap();
+        let manifest =
+            serde_json::from_str(&fs::read_to_string(pack_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        (pack_dir, manifest)
+    }
+
+    #[test]
+    fn archive_is_deterministic_and_round_trips() {
+        let tmp = TempDir::new().unwrap();
+        let (pack_dir, manifest) = sealed(&tmp);
+
+        let first = tmp.path().join("one.pack.tar");
+        let second = tmp.path().join("two.pack.tar");
+        write_archive(&pack_dir, &manifest, &first).unwrap();
+        write_archive(&pack_dir, &manifest, &second).unwrap();
+        assert_eq!(fs::read(&first).unwrap(), fs::read(&second).unwrap());
+
+        let out = tmp.path().join("unpacked");
+        fs::create_dir(&out).unwrap();
+        unpack_archive(&first, &out).unwrap();
+        for member in &manifest.members {
+            assert_eq!(
+                fs::read(out.join(&member.path)).unwrap(),
+                fs::read(pack_dir.join(&member.path)).unwrap()
+            );
+        }
+        assert!(out.join("manifest.json").is_file());
+    }
+
+    #[test]
+    fn unpack_refuses_escaping_paths() {
+        let tmp = TempDir::new().unwrap();
+        let archive = tmp.path().join("evil.pack.tar");
+        {
+            let mut builder = tar::Builder::new(fs::File::create(&archive).unwrap());
+            let mut header = tar::Header::new_gnu();
+            header.set_size(4);
+            header.set_mode(ENTRY_MODE);
+            // `append_data` rejects `..`, so write the raw name into the header.
+            header.as_gnu_mut().unwrap().name[..9].copy_from_slice(b"../escape");
+            header.set_cksum();
+            builder.append(&header, &b"evil"[..]).unwrap();
+            builder.finish().unwrap();
+        }
+
+        let out = tmp.path().join("out");
+        fs::create_dir(&out).unwrap();
+        let err = unpack_archive(&archive, &out).unwrap_err();
+        assert_eq!(err.refusal.code, "E_BAD_PACK");
+        assert!(!tmp.path().join("escape").exists());
+    }
+}
@@ -1,8 +1,11 @@
 use std::ffi::OsStr;
 use std::fs;
+use std::ops::ControlFlow;
 use std::path::{Component, Path, PathBuf};
 
 use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::git::is_git_metadata;
+use crate::seal::limits::check_collection_limits;
 
 /// A candidate member resolved from input artifacts.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,6 +16,61 @@ pub struct MemberCandidate {
     pub member_path: String,
 }
 
+/// A directory in an input tree that contributes no members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmptyDir {
+    /// The source directory on disk.
+    pub source: PathBuf,
+    /// The directory's normalized path within the pack.
+    pub member_path: String,
+}
+
+/// A symlink in an input that `SymlinkPolicy::Skip` left out of the pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedSymlink {
+    /// The symlink on disk.
+    pub source: PathBuf,
+    /// The member path it would have had.
+    pub member_path: String,
+}
+
+/// What collection does with symlinks in its inputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Refuse with `E_IO`.
+    #[default]
+    Refuse,
+    /// Collect the target as if it were at the symlink's path, refusing a
+    /// directory link that leads back to one of its own ancestors.
+    Follow,
+    /// Leave the symlink out and record it in `skipped_symlinks`.
+    Skip,
+}
+
+impl SymlinkPolicy {
+    /// The witness parameter value for this policy.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Refuse => "refuse",
+            Self::Follow => "follow",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+/// Collected candidates plus what collection skipped along the way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Collection {
+    pub candidates: Vec<MemberCandidate>,
+    /// Source paths of `.git` directories/files excluded from the pack.
+    pub excluded_git: Vec<PathBuf>,
+    /// Empty directories, innermost only, sorted by member path. A directory
+    /// whose only entries are excluded `.git` metadata counts as empty.
+    pub empty_dirs: Vec<EmptyDir>,
+    /// Symlinks left out under `SymlinkPolicy::Skip`, sorted by member path.
+    pub skipped_symlinks: Vec<SkippedSymlink>,
+}
+
 /// Shorthand for creating a boxed refusal.
 fn refusal(
     code: RefusalCode,
@@ -35,6 +93,17 @@ fn utf8_component(component: &OsStr, source: &Path) -> Result<String, Box<Refusa
     })
 }
 
+fn windows_prefix_refusal(source: &Path) -> Box<RefusalEnvelope> {
+    refusal(
+        RefusalCode::Io,
+        Some(format!(
+            "Member path would start with a Windows drive or UNC prefix: {}",
+            source.display()
+        )),
+        None,
+    )
+}
+
 fn relative_member_path(path: &Path, source: &Path) -> Result<String, Box<RefusalEnvelope>> {
     let mut components = Vec::new();
 
@@ -43,7 +112,10 @@ fn relative_member_path(path: &Path, source: &Path) -> Result<String, Box<Refusa
             Component::CurDir => {}
             Component::ParentDir => components.push("..".to_string()),
             Component::Normal(value) => components.push(utf8_component(value, source)?),
-            Component::RootDir | Component::Prefix(_) => {
+            Component::Prefix(_) => {
+                return Err(windows_prefix_refusal(source));
+            }
+            Component::RootDir => {
                 return Err(refusal(
                     RefusalCode::Io,
                     Some(format!(
@@ -74,17 +146,69 @@ fn relative_member_path(path: &Path, source: &Path) -> Result<String, Box<Refusa
 ///
 /// - File arguments become a single member using the file's basename.
 /// - Directory arguments are recursively walked; members use `<dir_basename>/<relative_path>`.
-/// - Only regular files are admissible; symlinks/sockets/devices/FIFOs produce an error.
+/// - Only regular files are admissible; symlinks/sockets/devices/FIFOs produce an error
+///   (see [`CollectOptions::symlinks`] to follow or skip symlinks).
 /// - Results are sorted by bytewise ascending member path.
+/// - `.git` metadata is excluded; see [`collect_artifacts_with`] to include it.
 pub fn collect_artifacts(inputs: &[PathBuf]) -> Result<Vec<MemberCandidate>, Box<RefusalEnvelope>> {
+    collect_artifacts_with(inputs, CollectOptions::default())
+        .map(|collection| collection.candidates)
+}
+
+/// Running totals reported to [`CollectOptions::on_progress`] during a walk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectProgress {
+    /// Member files found so far.
+    pub files: usize,
+    /// Their combined size in bytes.
+    pub bytes: u64,
+}
+
+type ProgressFn<'a> = dyn FnMut(&CollectProgress) -> ControlFlow<()> + 'a;
+
+/// Limits and hooks for [`collect_artifacts_with`].
+#[derive(Default)]
+pub struct CollectOptions<'a> {
+    /// Include `.git` metadata instead of excluding it.
+    pub allow_git: bool,
+    /// Refuse with `E_LIMIT` as soon as the walk finds more members than this.
+    pub max_members: Option<usize>,
+    /// Refuse with `E_LIMIT` as soon as the members found total more bytes
+    /// than this.
+    pub max_total_bytes: Option<u64>,
+    /// Called after every directory entry; `ControlFlow::Break` cancels the
+    /// walk with `E_IO`.
+    pub on_progress: Option<&'a mut ProgressFn<'a>>,
+    /// Refuse, follow, or skip symlinks.
+    pub symlinks: SymlinkPolicy,
+}
+
+/// Collect artifacts, excluding `.git` directories and gitfiles unless
+/// `options.allow_git`.
+///
+/// Refuses with `E_EMPTY` when every input was excluded.
+pub fn collect_artifacts_with(
+    inputs: &[PathBuf],
+    options: CollectOptions<'_>,
+) -> Result<Collection, Box<RefusalEnvelope>> {
     if inputs.is_empty() {
         return Err(refusal(RefusalCode::Empty, None, None));
     }
 
-    let mut candidates = Vec::new();
+    let mut walk = Walk {
+        options,
+        progress: CollectProgress::default(),
+        collection: Collection::default(),
+        ancestors: Vec::new(),
+    };
 
     for input in inputs {
-        let meta = fs::symlink_metadata(input).map_err(|e| {
+        if !walk.options.allow_git && input.file_name().is_some_and(is_git_metadata) {
+            walk.collection.excluded_git.push(input.clone());
+            continue;
+        }
+
+        let mut meta = fs::symlink_metadata(input).map_err(|e| {
             refusal(
                 RefusalCode::Io,
                 Some(format!("Cannot read input: {}: {e}", input.display())),
@@ -93,11 +217,21 @@ pub fn collect_artifacts(inputs: &[PathBuf]) -> Result<Vec<MemberCandidate>, Box
         })?;
 
         if meta.is_symlink() {
-            return Err(refusal(
-                RefusalCode::Io,
-                Some(format!("Non-regular input (symlink): {}", input.display())),
-                None,
-            ));
+            match walk.options.symlinks {
+                SymlinkPolicy::Refuse => return Err(symlink_refusal(input)),
+                SymlinkPolicy::Skip => {
+                    let member_path = match input.file_name() {
+                        Some(name) => utf8_component(name, input)?,
+                        None => input.display().to_string(),
+                    };
+                    walk.collection.skipped_symlinks.push(SkippedSymlink {
+                        source: input.clone(),
+                        member_path,
+                    });
+                    continue;
+                }
+                SymlinkPolicy::Follow => meta = follow_symlink(input)?,
+            }
         }
 
         if meta.is_file() {
@@ -112,12 +246,15 @@ pub fn collect_artifacts(inputs: &[PathBuf]) -> Result<Vec<MemberCandidate>, Box
                 input,
             )?;
 
-            candidates.push(MemberCandidate {
-                source: input.clone(),
-                member_path,
-            });
+            walk.push(
+                MemberCandidate {
+                    source: input.clone(),
+                    member_path,
+                },
+                meta.len(),
+            )?;
         } else if meta.is_dir() {
-            collect_dir(input, input, &mut candidates)?;
+            walk.collect_dir(input, input)?;
         } else {
             return Err(refusal(
                 RefusalCode::Io,
@@ -127,128 +264,304 @@ pub fn collect_artifacts(inputs: &[PathBuf]) -> Result<Vec<MemberCandidate>, Box
         }
     }
 
+    let Collection {
+        mut candidates,
+        excluded_git,
+        mut empty_dirs,
+        mut skipped_symlinks,
+    } = walk.collection;
+
+    if candidates.is_empty() && !excluded_git.is_empty() {
+        return Err(refusal(
+            RefusalCode::Empty,
+            Some("Every input is git metadata; pass --allow-git to seal it".to_string()),
+            Some(serde_json::json!({
+                "excluded_git": excluded_git
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>(),
+            })),
+        ));
+    }
+
     // Deterministic: bytewise ascending path order.
     candidates.sort_by(|a, b| a.member_path.cmp(&b.member_path));
+    empty_dirs.sort_by(|a, b| a.member_path.cmp(&b.member_path));
+    skipped_symlinks.sort_by(|a, b| a.member_path.cmp(&b.member_path));
+
+    Ok(Collection {
+        candidates,
+        excluded_git,
+        empty_dirs,
+        skipped_symlinks,
+    })
+}
 
-    Ok(candidates)
+fn symlink_refusal(path: &Path) -> Box<RefusalEnvelope> {
+    refusal(
+        RefusalCode::Io,
+        Some(format!("Non-regular input (symlink): {}", path.display())),
+        Some(serde_json::json!({
+            "symlink": path.display().to_string(),
+            "hint": "pass --follow-symlinks or --skip-symlinks",
+        })),
+    )
 }
 
-/// Recursively collect regular files from a directory.
-fn collect_dir(
-    root: &Path,
-    dir: &Path,
-    candidates: &mut Vec<MemberCandidate>,
-) -> Result<(), Box<RefusalEnvelope>> {
-    let dir_basename = root
-        .file_name()
-        .ok_or_else(|| {
-            refusal(
-                RefusalCode::Io,
-                Some(format!(
-                    "Cannot determine directory name: {}",
-                    root.display()
-                )),
-                None,
-            )
-        })?
-        .to_str()
-        .ok_or_else(|| {
-            refusal(
-                RefusalCode::Io,
-                Some(format!(
-                    "Non-UTF-8 directory name is not supported: {}",
-                    root.display()
-                )),
-                None,
-            )
-        })?;
+/// Metadata of a symlink's target, refusing a dangling link.
+fn follow_symlink(path: &Path) -> Result<fs::Metadata, Box<RefusalEnvelope>> {
+    fs::metadata(path).map_err(|e| {
+        refusal(
+            RefusalCode::Io,
+            Some(format!("Cannot follow symlink: {}: {e}", path.display())),
+            None,
+        )
+    })
+}
 
-    // Collect and sort entries for deterministic traversal.
-    let mut entries: Vec<fs::DirEntry> = fs::read_dir(dir)
-        .map_err(|e| {
-            refusal(
-                RefusalCode::Io,
-                Some(format!("Cannot read directory: {}: {e}", dir.display())),
-                None,
-            )
-        })?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| {
-            refusal(
-                RefusalCode::Io,
-                Some(format!(
-                    "Error reading directory entry: {}: {e}",
-                    dir.display()
-                )),
-                None,
-            )
-        })?;
-    entries.sort_by_key(|e| e.file_name());
+/// State of one collection walk: what has been found so far and the limits
+/// it is checked against as it grows.
+struct Walk<'a> {
+    options: CollectOptions<'a>,
+    progress: CollectProgress,
+    collection: Collection,
+    /// Canonical paths of the directories being walked, outermost first, so
+    /// a followed symlink back into one of them is caught as a loop.
+    ancestors: Vec<PathBuf>,
+}
 
-    for entry in entries {
-        let meta = entry.metadata().map_err(|e| {
-            refusal(
-                RefusalCode::Io,
-                Some(format!("Cannot stat: {}: {e}", entry.path().display())),
-                None,
-            )
-        })?;
+impl Walk<'_> {
+    /// Add a member, refusing as soon as it would exceed `max_members` or
+    /// `max_total_bytes`.
+    fn push(&mut self, candidate: MemberCandidate, bytes: u64) -> Result<(), Box<RefusalEnvelope>> {
+        // A Unix file named `C:report.json` would land outside the pack
+        // directory when extracted on Windows.
+        if has_windows_prefix(&candidate.member_path) {
+            return Err(windows_prefix_refusal(&candidate.source));
+        }
+        check_collection_limits(
+            self.options.max_members,
+            self.options.max_total_bytes,
+            self.progress.files + 1,
+            self.progress.bytes + bytes,
+            Some(&candidate.source),
+        )?;
+        self.collection.candidates.push(candidate);
+        self.progress.files += 1;
+        self.progress.bytes += bytes;
+        self.report()
+    }
 
-        // Check symlink via symlink_metadata
-        let sym_meta = fs::symlink_metadata(entry.path()).map_err(|e| {
-            refusal(
+    /// Report progress; the hook may cancel the walk.
+    fn report(&mut self) -> Result<(), Box<RefusalEnvelope>> {
+        let Some(on_progress) = self.options.on_progress.as_deref_mut() else {
+            return Ok(());
+        };
+        match on_progress(&self.progress) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(refusal(
                 RefusalCode::Io,
-                Some(format!("Cannot stat: {}: {e}", entry.path().display())),
-                None,
-            )
-        })?;
-        if sym_meta.is_symlink() {
+                Some("Collection cancelled".to_string()),
+                Some(serde_json::json!({
+                    "cancelled": true,
+                    "files": self.progress.files,
+                    "bytes": self.progress.bytes,
+                })),
+            )),
+        }
+    }
+
+    /// Refuse a followed directory symlink that resolves to a directory
+    /// already being walked, which would recurse forever.
+    fn check_loop(&self, link: &Path) -> Result<(), Box<RefusalEnvelope>> {
+        let target = canonical(link)?;
+        if self.ancestors.contains(&target) {
             return Err(refusal(
                 RefusalCode::Io,
                 Some(format!(
-                    "Non-regular input (symlink): {}",
-                    entry.path().display()
+                    "Symlink loop: {} -> {}",
+                    link.display(),
+                    target.display()
                 )),
                 None,
             ));
         }
+        Ok(())
+    }
 
-        if meta.is_dir() {
-            collect_dir(root, &entry.path(), candidates)?;
-        } else if meta.is_file() {
+    /// Recursively collect regular files from a directory, recording
+    /// directories that contribute nothing in `empty_dirs`.
+    ///
+    /// Unless `allow_git`, `.git` entries are skipped and recorded in
+    /// `excluded_git`.
+    fn collect_dir(&mut self, root: &Path, dir: &Path) -> Result<(), Box<RefusalEnvelope>> {
+        let dir_basename = root
+            .file_name()
+            .ok_or_else(|| {
+                refusal(
+                    RefusalCode::Io,
+                    Some(format!(
+                        "Cannot determine directory name (a filesystem, drive, or share root cannot be sealed; pass a directory inside it): {}",
+                        root.display()
+                    )),
+                    None,
+                )
+            })?
+            .to_str()
+            .ok_or_else(|| {
+                refusal(
+                    RefusalCode::Io,
+                    Some(format!(
+                        "Non-UTF-8 directory name is not supported: {}",
+                        root.display()
+                    )),
+                    None,
+                )
+            })?;
+
+        // Collect and sort entries for deterministic traversal.
+        let mut entries: Vec<fs::DirEntry> = fs::read_dir(dir)
+            .map_err(|e| {
+                refusal(
+                    RefusalCode::Io,
+                    Some(format!("Cannot read directory: {}: {e}", dir.display())),
+                    None,
+                )
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                refusal(
+                    RefusalCode::Io,
+                    Some(format!(
+                        "Error reading directory entry: {}: {e}",
+                        dir.display()
+                    )),
+                    None,
+                )
+            })?;
+        entries.sort_by_key(|e| e.file_name());
+        if self.options.symlinks == SymlinkPolicy::Follow {
+            self.ancestors.push(canonical(dir)?);
+        }
+        let collected_before = (
+            self.collection.candidates.len(),
+            self.collection.empty_dirs.len(),
+        );
+
+        for entry in entries {
+            if !self.options.allow_git && is_git_metadata(&entry.file_name()) {
+                self.collection.excluded_git.push(entry.path());
+                self.report()?;
+                continue;
+            }
+
+            // Check symlink via symlink_metadata
             let entry_path = entry.path();
-            let relative = entry_path.strip_prefix(root).map_err(|e| {
+            let mut meta = fs::symlink_metadata(&entry_path).map_err(|e| {
                 refusal(
                     RefusalCode::Io,
-                    Some(format!("Path prefix error: {e}")),
+                    Some(format!("Cannot stat: {}: {e}", entry_path.display())),
                     None,
                 )
             })?;
+            let member_path = || -> Result<String, Box<RefusalEnvelope>> {
+                let relative = entry_path.strip_prefix(root).map_err(|e| {
+                    refusal(
+                        RefusalCode::Io,
+                        Some(format!("Path prefix error: {e}")),
+                        None,
+                    )
+                })?;
+                let relative = relative_member_path(relative, &entry_path)?;
+                Ok(format!("{dir_basename}/{relative}"))
+            };
+            if meta.is_symlink() {
+                match self.options.symlinks {
+                    SymlinkPolicy::Refuse => return Err(symlink_refusal(&entry_path)),
+                    SymlinkPolicy::Skip => {
+                        self.collection.skipped_symlinks.push(SkippedSymlink {
+                            source: entry_path.clone(),
+                            member_path: member_path()?,
+                        });
+                        self.report()?;
+                        continue;
+                    }
+                    SymlinkPolicy::Follow => {
+                        meta = follow_symlink(&entry_path)?;
+                        if meta.is_dir() {
+                            self.check_loop(&entry_path)?;
+                        }
+                    }
+                }
+            }
 
-            let relative = relative_member_path(relative, &entry_path)?;
-            let member_path = format!("{dir_basename}/{relative}");
+            if meta.is_dir() {
+                self.report()?;
+                self.collect_dir(root, &entry_path)?;
+            } else if meta.is_file() {
+                let member_path = member_path()?;
+                self.push(
+                    MemberCandidate {
+                        source: entry_path,
+                        member_path,
+                    },
+                    meta.len(),
+                )?;
+            } else {
+                return Err(refusal(
+                    RefusalCode::Io,
+                    Some(format!("Non-regular input: {}", entry_path.display())),
+                    None,
+                ));
+            }
+        }
+        if self.options.symlinks == SymlinkPolicy::Follow {
+            self.ancestors.pop();
+        }
 
-            candidates.push(MemberCandidate {
-                source: entry_path,
+        let collected = (
+            self.collection.candidates.len(),
+            self.collection.empty_dirs.len(),
+        );
+        if collected == collected_before {
+            let member_path = match dir.strip_prefix(root) {
+                Ok(relative) if relative.as_os_str().is_empty() => dir_basename.to_string(),
+                Ok(relative) => format!("{dir_basename}/{}", relative_member_path(relative, dir)?),
+                Err(e) => {
+                    return Err(refusal(
+                        RefusalCode::Io,
+                        Some(format!("Path prefix error: {e}")),
+                        None,
+                    ))
+                }
+            };
+            self.collection.empty_dirs.push(EmptyDir {
+                source: dir.to_path_buf(),
                 member_path,
             });
-        } else {
-            return Err(refusal(
-                RefusalCode::Io,
-                Some(format!("Non-regular input: {}", entry.path().display())),
-                None,
-            ));
         }
+
+        Ok(())
     }
+}
 
-    Ok(())
+fn canonical(path: &Path) -> Result<PathBuf, Box<RefusalEnvelope>> {
+    fs::canonicalize(path).map_err(|e| {
+        refusal(
+            RefusalCode::Io,
+            Some(format!("Cannot resolve path: {}: {e}", path.display())),
+            None,
+        )
+    })
 }
-/// Validate that a member path is safe (no absolute, no `..`).
+
+/// Validate that a member path is safe (no absolute, no `..`, no Windows
+/// drive or UNC prefix).
 pub fn is_safe_member_path(path: &str) -> bool {
     if path.is_empty() {
         return false;
     }
-    if path.starts_with('/') {
+    if path.starts_with('/') || has_windows_prefix(path) {
         return false;
     }
     for segment in path.split('/') {
@@ -259,6 +572,17 @@ pub fn is_safe_member_path(path: &str) -> bool {
     true
 }
 
+/// True when `path` starts with a drive letter (`C:`, drive-absolute or
+/// drive-relative) or a backslash (root-relative or UNC `\\server\share`),
+/// either of which escapes the pack directory on Windows.
+pub fn has_windows_prefix(path: &str) -> bool {
+    match path.as_bytes() {
+        [b'\\', ..] => true,
+        [drive, b':', ..] => drive.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +650,56 @@ mod tests {
         assert_eq!(candidates[0].member_path, r"evidence/odd\name.json");
     }
 
+    #[test]
+    fn git_metadata_is_excluded_unless_allowed() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join(".git").join("objects")).unwrap();
+        fs::write(repo.join(".git").join("HEAD"), "ref: refs/heads/main").unwrap();
+        fs::write(repo.join("a.json"), "{}").unwrap();
+
+        let collection =
+            collect_artifacts_with(std::slice::from_ref(&repo), CollectOptions::default()).unwrap();
+        assert_eq!(collection.candidates.len(), 1);
+        assert_eq!(collection.candidates[0].member_path, "repo/a.json");
+        assert_eq!(collection.excluded_git, vec![repo.join(".git")]);
+
+        let collection = collect_artifacts_with(
+            std::slice::from_ref(&repo),
+            CollectOptions {
+                allow_git: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(collection.candidates.len(), 2);
+        assert!(collection.excluded_git.is_empty());
+
+        let err =
+            collect_artifacts_with(&[repo.join(".git")], CollectOptions::default()).unwrap_err();
+        assert_eq!(err.refusal.code, "E_EMPTY");
+    }
+
+    #[test]
+    fn innermost_empty_directories_are_recorded() {
+        let tmp = TempDir::new().unwrap();
+        let tree = tmp.path().join("tree");
+        fs::create_dir_all(tree.join("staging/partitions/2026-01")).unwrap();
+        fs::create_dir_all(tree.join("logs")).unwrap();
+        fs::write(tree.join("logs/run.log"), "ok").unwrap();
+        let bare = tmp.path().join("bare");
+        fs::create_dir_all(&bare).unwrap();
+
+        let collection = collect_artifacts_with(&[tree, bare], CollectOptions::default()).unwrap();
+        assert_eq!(collection.candidates.len(), 1);
+        let empty: Vec<&str> = collection
+            .empty_dirs
+            .iter()
+            .map(|dir| dir.member_path.as_str())
+            .collect();
+        assert_eq!(empty, ["bare", "tree/staging/partitions/2026-01"]);
+    }
+
     #[test]
     fn results_are_sorted_bytewise() {
         let tmp = TempDir::new().unwrap();
@@ -357,6 +731,98 @@ mod tests {
         assert!(err.refusal.message.contains("symlink"));
     }
 
+    #[test]
+    fn symlinks_can_be_followed_with_loop_detection_or_skipped() {
+        use std::os::unix::fs as unix_fs;
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("tree");
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::write(dir.join("data/a.json"), "{}").unwrap();
+        unix_fs::symlink(dir.join("data/a.json"), dir.join("b.json")).unwrap();
+        unix_fs::symlink(dir.join("data"), dir.join("linked")).unwrap();
+        let collect = |symlinks| {
+            collect_artifacts_with(
+                std::slice::from_ref(&dir),
+                CollectOptions {
+                    symlinks,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let followed = collect(SymlinkPolicy::Follow).unwrap();
+        let paths: Vec<_> = followed
+            .candidates
+            .iter()
+            .map(|c| c.member_path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            ["tree/b.json", "tree/data/a.json", "tree/linked/a.json"]
+        );
+
+        let skipped = collect(SymlinkPolicy::Skip).unwrap();
+        assert_eq!(skipped.candidates.len(), 1);
+        let links: Vec<_> = skipped
+            .skipped_symlinks
+            .iter()
+            .map(|link| link.member_path.as_str())
+            .collect();
+        assert_eq!(links, ["tree/b.json", "tree/linked"]);
+
+        unix_fs::symlink(&dir, dir.join("data/up")).unwrap();
+        let err = collect(SymlinkPolicy::Follow).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(err.refusal.message.contains("Symlink loop"));
+    }
+
+    #[test]
+    fn max_members_and_cancellation_stop_the_walk_early() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("many");
+        fs::create_dir(&dir).unwrap();
+        for i in 0..5 {
+            fs::write(dir.join(format!("{i}.json")), "{}").unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut on_progress = |progress: &CollectProgress| {
+            seen.push(*progress);
+            ControlFlow::Continue(())
+        };
+        let err = collect_artifacts_with(
+            std::slice::from_ref(&dir),
+            CollectOptions {
+                max_members: Some(3),
+                on_progress: Some(&mut on_progress),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.refusal.code, "E_LIMIT");
+        assert_eq!(err.refusal.detail.unwrap()["max_members"], 3);
+        assert_eq!(seen.last().unwrap().files, 3);
+        assert_eq!(seen.last().unwrap().bytes, 6);
+
+        let mut cancel_after_two = |progress: &CollectProgress| {
+            if progress.files == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        let err = collect_artifacts_with(
+            &[dir],
+            CollectOptions {
+                on_progress: Some(&mut cancel_after_two),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert_eq!(err.refusal.detail.unwrap()["files"], 2);
+    }
+
     #[test]
     fn nonexistent_input_refuses_with_e_io() {
         let result = collect_artifacts(&[PathBuf::from("/nonexistent/file.json")]);
@@ -373,5 +839,23 @@ mod tests {
         assert!(!is_safe_member_path("/absolute/path"));
         assert!(!is_safe_member_path("../escape"));
         assert!(!is_safe_member_path("dir/../escape"));
+        assert!(!is_safe_member_path("C:/Windows/win.ini"));
+        assert!(!is_safe_member_path("c:report.json"));
+        assert!(!is_safe_member_path(r"\\server\share\a.json"));
+        assert!(!is_safe_member_path(r"\a.json"));
+        assert!(is_safe_member_path("dir/C:x.json"));
+        assert!(is_safe_member_path("12:00.json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn drive_letter_file_names_refuse() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("C:report.json");
+        fs::write(&file, "{}").unwrap();
+
+        let err = collect_artifacts(&[file]).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(err.refusal.message.contains("Windows drive or UNC prefix"));
     }
 }
@@ -1,28 +1,40 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use serde_json::json;
 
 use super::collect::MemberCandidate;
 use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::sign::SIGNATURE_FILE;
+use crate::timestamp::TIMESTAMP_FILE;
 
 /// Reserved member path that cannot be used by any input artifact.
 pub const RESERVED_MANIFEST_PATH: &str = "manifest.json";
 
 /// Check the resolved member set for path collisions and reserved-name violations.
 ///
+/// Paths that differ only by case also collide: extracting them on a
+/// case-insensitive filesystem (Windows, default macOS) would leave one file
+/// overwriting the other.
+///
 /// Returns `Ok(())` if all member paths are unique and none use reserved names.
 /// Returns `Err` with `E_DUPLICATE` refusal containing collision details.
 pub fn check_collisions(candidates: &[MemberCandidate]) -> Result<(), Box<RefusalEnvelope>> {
     let mut seen = HashSet::new();
+    let mut folded: HashMap<String, &MemberCandidate> = HashMap::new();
 
     for candidate in candidates {
-        // Check reserved path
-        if candidate.member_path == RESERVED_MANIFEST_PATH {
+        // Check reserved paths, in any case for case-insensitive filesystems
+        if candidate
+            .member_path
+            .eq_ignore_ascii_case(RESERVED_MANIFEST_PATH)
+            || candidate.member_path.eq_ignore_ascii_case(SIGNATURE_FILE)
+            || candidate.member_path.eq_ignore_ascii_case(TIMESTAMP_FILE)
+        {
             return Err(Box::new(RefusalEnvelope::new(
                 RefusalCode::Duplicate,
                 Some("Reserved member path collision".to_string()),
                 Some(json!({
-                    "path": RESERVED_MANIFEST_PATH,
+                    "path": candidate.member_path,
                     "sources": [candidate.source.display().to_string()]
                 })),
             )));
@@ -46,6 +58,21 @@ pub fn check_collisions(candidates: &[MemberCandidate]) -> Result<(), Box<Refusa
                 })),
             )));
         }
+
+        if let Some(other) = folded.insert(candidate.member_path.to_lowercase(), candidate) {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Duplicate,
+                Some("Member paths differ only by case".to_string()),
+                Some(json!({
+                    "path": candidate.member_path,
+                    "conflicts_with": other.member_path,
+                    "sources": [
+                        other.source.display().to_string(),
+                        candidate.source.display().to_string()
+                    ]
+                })),
+            )));
+        }
     }
 
     Ok(())
@@ -72,6 +99,18 @@ mod tests {
         assert!(check_collisions(&candidates).is_ok());
     }
 
+    #[test]
+    fn signature_path_is_reserved() {
+        let candidates = vec![candidate("/a/manifest.sig.json", SIGNATURE_FILE)];
+        let err = check_collisions(&candidates).unwrap_err();
+        assert_eq!(err.refusal.code, "E_DUPLICATE");
+        let candidates = vec![candidate("/a/manifest.tst.json", TIMESTAMP_FILE)];
+        let err = check_collisions(&candidates).unwrap_err();
+        assert_eq!(err.refusal.code, "E_DUPLICATE");
+        let err = check_collisions(&[candidate("/a/MANIFEST.JSON", "MANIFEST.JSON")]).unwrap_err();
+        assert_eq!(err.refusal.code, "E_DUPLICATE");
+    }
+
     #[test]
     fn empty_candidates_passes() {
         assert!(check_collisions(&[]).is_ok());
@@ -91,6 +130,19 @@ mod tests {
         assert_eq!(sources.len(), 2);
     }
 
+    #[test]
+    fn case_only_difference_returns_e_duplicate() {
+        let candidates = vec![
+            candidate("/a/Report.json", "evidence/Report.json"),
+            candidate("/a/report.json", "evidence/report.json"),
+        ];
+        let err = check_collisions(&candidates).unwrap_err();
+        assert_eq!(err.refusal.code, "E_DUPLICATE");
+        let detail = err.refusal.detail.as_ref().unwrap();
+        assert_eq!(detail["path"], "evidence/report.json");
+        assert_eq!(detail["conflicts_with"], "evidence/Report.json");
+    }
+
     #[test]
     fn reserved_manifest_path_returns_e_duplicate() {
         let candidates = vec![candidate("/a/manifest.json", "manifest.json")];
@@ -1,14 +1,43 @@
+use std::collections::BTreeMap;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde_json::json;
 
+use crate::cli::{EmptyDirs, HashAlgorithm};
+use crate::detect::load_detection_rules;
+use crate::encrypt::Recipient;
+use crate::events::{EventBus, PackEvent};
+use crate::network::store::{check_store_slot, index_enclosing_store};
 use crate::refusal::{RefusalCode, RefusalEnvelope};
-use crate::seal::collect::collect_artifacts;
+use crate::seal::annotate::{resolve_annotations, MemberAnnotation};
+use crate::seal::archive::{write_archive, ARCHIVE_EXTENSION};
+use crate::seal::collect::{
+    collect_artifacts_with, CollectOptions, CollectProgress, Collection, MemberCandidate,
+    SymlinkPolicy,
+};
 use crate::seal::collision::check_collisions;
-use crate::seal::copy::copy_and_hash;
-use crate::seal::finalize::finalize_manifest;
-use crate::witness::WitnessInput;
+use crate::seal::copy::copy_and_hash_resumable;
+use crate::seal::finalize::{finalize_manifest_with_annotations, SealAnnotations};
+use crate::seal::git::enclosing_work_tree;
+use crate::seal::limits::{check_collection_limits, check_field_size, DEFAULT_MAX_NOTE_BYTES};
+use crate::seal::manifest::{
+    Manifest, MemberSource, Provenance, RemoteSource, ReproducibleMode, SourceProvenance,
+    TrustHashes, TrustHashesMode, PACKKEEP_FILE,
+};
+use crate::seal::merge::ParentPack;
+use crate::seal::origin::load_origins;
+use crate::seal::remote::{fetch_remote, is_remote, RemoteArtifact, RemoteOptions};
+use crate::seal::resume::{Checkpoint, CHECKPOINT_FILE, STAGING_SUBDIR};
+use crate::seal::stdin::{is_stdin, stage_stdin, stdin_member_path, STDIN_ARTIFACT};
+use crate::seal::trust::{load_trusted_hashes, plan_trust, TrustPlan};
+use crate::seal::visibility::{resolve_visibility, VisibilityRule};
+use crate::seal::zip::{expand_zip, is_zip, ExpandLimits};
+use crate::sign::{load_signing_key, write_signature, PackSignature, SIGNATURE_FILE};
+use crate::timestamp::{write_timestamp, PackTimestamp, TIMESTAMP_FILE};
+use crate::witness::{hostname, user, WitnessInput};
 
 /// Execute the full `pack seal` flow.
 ///
@@ -24,35 +53,542 @@ pub fn execute_seal(
     output: Option<&Path>,
     note: Option<String>,
 ) -> Result<SealResult, Box<RefusalEnvelope>> {
+    execute_seal_with_options(artifacts, output, note, &SealOptions::default())
+}
+
+/// Optional seal behaviours beyond the positional inputs.
+#[derive(Debug, Clone, Default)]
+pub struct SealOptions {
+    /// `--trust-hashes`: precomputed hashes keyed by source path.
+    pub trust_hashes: Option<PathBuf>,
+    /// `--hash-algorithm`: digest for member `bytes_hash` values.
+    pub hash_algorithm: HashAlgorithm,
+    /// `--max-note-bytes`: note size ceiling; `None` uses `DEFAULT_MAX_NOTE_BYTES`.
+    pub max_note_bytes: Option<usize>,
+    /// `--allow-git`: include `.git` metadata and skip the work-tree output check.
+    pub allow_git: bool,
+    /// Size limit and redirect policy for URL artifacts.
+    pub remote: RemoteOptions,
+    /// `--archive`: write a single deterministic `.pack.tar` instead of a directory.
+    pub archive: bool,
+    /// `--sign`: write an Ed25519 `manifest.sig.json` next to the manifest.
+    pub sign: bool,
+    /// `--signing-key`: key file for `--sign`; `None` reads `PACK_SIGNING_KEY`.
+    pub signing_key: Option<PathBuf>,
+    /// `--timestamp-url`: Time Stamp Authority to obtain an RFC 3161 token
+    /// from, written to `manifest.tst.json`.
+    pub timestamp_url: Option<String>,
+    /// `--encrypt`: age recipients to encrypt every member to.
+    pub encrypt_to: Vec<Recipient>,
+    /// `--restrict`: visibility levels to record on matching members.
+    pub restrict: Vec<VisibilityRule>,
+    /// `--origins`: JSON file of upstream descriptors keyed by member path.
+    pub origins: Option<PathBuf>,
+    /// `--annotate`: reviewer labels for individual members.
+    pub annotate: Vec<MemberAnnotation>,
+    /// `--empty-dirs`: what to record for empty directories in input trees.
+    pub empty_dirs: EmptyDirs,
+    /// `--follow-symlinks` / `--skip-symlinks`: what to do with symlinks in
+    /// input trees.
+    pub symlinks: SymlinkPolicy,
+    /// `--expand-archives`: seal each `.zip` artifact as a directory named
+    /// after it, so its entries become members.
+    pub expand_archives: bool,
+    /// `--member-timestamps`: record when each member finished copying in
+    /// the manifest's `provenance.sealed_at`.
+    pub member_timestamps: bool,
+    /// `--max-members`: refuse with `E_LIMIT` as soon as collection finds
+    /// more members than this.
+    pub max_members: Option<usize>,
+    /// `--max-total-bytes`: refuse with `E_LIMIT` as soon as the members
+    /// collected total more bytes than this.
+    pub max_total_bytes: Option<u64>,
+    /// Lifecycle events (collection progress, hashed members, the final
+    /// manifest); a subscriber may cancel at progress and hashing events.
+    pub events: EventBus,
+    /// `--stdin-name`: member path for the `-` artifact, which is read from
+    /// stdin.
+    pub stdin_name: Option<String>,
+    /// `--hash-executable`: cover member `executable` flags with `pack_id`.
+    pub hash_executable: bool,
+    /// Parent of the default output path when none is given; `None` uses
+    /// `pack`.
+    pub output_root: Option<PathBuf>,
+    /// `--provenance`: record each member's absolute source path and mtime,
+    /// the hostname, and the invoking user in the manifest's
+    /// `provenance.sources`.
+    pub provenance: bool,
+    /// `--strict-types`: refuse with `E_AMBIGUOUS_TYPE` when a member's type
+    /// rests on its filename or path alone.
+    pub strict_types: bool,
+    /// `--detect-rules`: custom type detection rules, tried before the
+    /// built-in ones and recorded in the manifest's
+    /// `provenance.detection_rules`.
+    pub detect_rules: Option<PathBuf>,
+    /// `--resume`: stage members in this directory and checkpoint each one,
+    /// so a failed seal re-run with the same directory continues where it
+    /// stopped.
+    pub resume: Option<PathBuf>,
+    /// `--reproducible`: make `pack_id` independent of the seal time, by
+    /// pinning `created` to `source_date_epoch` when given and otherwise
+    /// leaving `created` out of `pack_id`.
+    pub reproducible: bool,
+    /// Value of `SOURCE_DATE_EPOCH` (Unix seconds), read by `--reproducible`.
+    pub source_date_epoch: Option<String>,
+    /// `--merge`: existing pack (directory or `.pack.tar`) whose members are
+    /// carried into the new pack, recorded as its parent in `lineage`.
+    pub merge: Option<PathBuf>,
+}
+
+/// Environment variable that pins `created` under `seal --reproducible`.
+pub const SOURCE_DATE_EPOCH_ENV: &str = "SOURCE_DATE_EPOCH";
+
+/// Execute `pack seal` with explicit [`SealOptions`].
+pub fn execute_seal_with_options(
+    artifacts: &[PathBuf],
+    output: Option<&Path>,
+    note: Option<String>,
+    options: &SealOptions,
+) -> Result<SealResult, Box<RefusalEnvelope>> {
+    let staged = stage_seal(artifacts, output, note, options)?;
+    promote_seal(staged)
+}
+
+/// A seal that is complete in its staging directory and only awaits promotion
+/// to `final_dir`.
+pub(crate) struct StagedSeal {
+    staging_dir: Staging,
+    manifest: Manifest,
+    archive: bool,
+    pub(crate) result: SealResult,
+}
+
+/// Run every seal step except promotion: collect, copy and hash into staging,
+/// finalize (and sign) the manifest, and check the output path is free.
+///
+/// Nothing outside the staging directory is written; dropping the returned
+/// [`StagedSeal`] discards the work.
+pub(crate) fn stage_seal(
+    artifacts: &[PathBuf],
+    output: Option<&Path>,
+    note: Option<String>,
+    options: &SealOptions,
+) -> Result<StagedSeal, Box<RefusalEnvelope>> {
+    if let Some(note) = &note {
+        let limit = options.max_note_bytes.unwrap_or(DEFAULT_MAX_NOTE_BYTES);
+        check_field_size("note", note, limit)?;
+    }
+
+    // Load the signing key up front so a bad key refuses before any copying
+    let signing_key = if options.sign {
+        let key = load_signing_key(options.signing_key.as_deref()).map_err(|message| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(message),
+                options
+                    .signing_key
+                    .as_ref()
+                    .map(|path| json!({ "signing_key": path.display().to_string() })),
+            ))
+        })?;
+        Some(key)
+    } else {
+        None
+    };
+
+    let detection_rules = match &options.detect_rules {
+        Some(path) => load_detection_rules(path).map_err(|message| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(message),
+                Some(json!({ "detect_rules": path.display().to_string() })),
+            ))
+        })?,
+        None => Vec::new(),
+    };
+
+    // 0. Download URL artifacts, read stdin, and expand zip artifacts so they
+    // can be collected like local files
+    let stdin_name = stdin_member_path(artifacts, options.stdin_name.as_deref())?;
+    let mut downloads = None;
+    let mut remote = Vec::new();
+    let mut stdin_member = None;
+    let mut local_artifacts = Vec::with_capacity(artifacts.len());
+    let mut zip_limits = ExpandLimits::new(options.max_members, options.max_total_bytes);
+    for (index, artifact) in artifacts.iter().enumerate() {
+        if let (true, Some(name)) = (is_stdin(artifact), stdin_name) {
+            let dir = input_dir(&mut downloads)?.path().join(index.to_string());
+            stdin_member = Some(MemberCandidate {
+                source: stage_stdin(io::stdin().lock(), name, &dir)?,
+                member_path: name.to_string(),
+            });
+            continue;
+        }
+        let (local, fetched) = if is_remote(artifact) {
+            let dir = input_dir(&mut downloads)?;
+            let url = artifact.to_string_lossy();
+            let fetched = fetch_remote(&url, &dir.path().join(index.to_string()), &options.remote)?;
+            (fetched.local.clone(), Some(fetched))
+        } else {
+            (artifact.clone(), None)
+        };
+        let local = if options.expand_archives && is_zip(&local) {
+            let dir = input_dir(&mut downloads)?
+                .path()
+                .join(format!("{index}.expanded"));
+            expand_zip(&local, &dir, &mut zip_limits)?
+        } else {
+            local
+        };
+        // Every member collected from `local` (one file, or an expanded zip's
+        // entries) came from the URL.
+        if let Some(fetched) = fetched {
+            remote.push((local.clone(), fetched));
+        }
+        local_artifacts.push(local);
+    }
+
     // 1. Collect
-    let candidates = collect_artifacts(artifacts)?;
+    let mut on_progress = |progress: &CollectProgress| {
+        options.events.emit(&PackEvent::CollectProgress {
+            files: progress.files,
+            bytes: progress.bytes,
+        })
+    };
+    let mut collection = if local_artifacts.is_empty() && stdin_member.is_some() {
+        Collection::default()
+    } else {
+        collect_artifacts_with(
+            &local_artifacts,
+            CollectOptions {
+                allow_git: options.allow_git,
+                max_members: options.max_members,
+                max_total_bytes: options.max_total_bytes,
+                on_progress: Some(&mut on_progress),
+                symlinks: options.symlinks,
+            },
+        )?
+    };
+    if let Some(member) = &stdin_member {
+        collection.candidates.push(member.clone());
+        collection
+            .candidates
+            .sort_by(|a, b| a.member_path.cmp(&b.member_path));
+    }
+    let mut candidates = collection.candidates;
+    let parent = match &options.merge {
+        Some(path) => Some(ParentPack::open(path)?),
+        None => None,
+    };
+    if let Some(parent) = &parent {
+        parent.add_members(&mut candidates)?;
+    }
+    let pinned_created = match (options.reproducible, &options.source_date_epoch) {
+        (true, Some(epoch)) => Some(source_date_epoch(epoch)?),
+        _ => None,
+    };
+    let mut provenance = Provenance {
+        reproducible: options.reproducible.then_some(if pinned_created.is_some() {
+            ReproducibleMode::SourceDateEpoch
+        } else {
+            ReproducibleMode::CreatedExcluded
+        }),
+        executable_in_pack_id: options.hash_executable,
+        detection_rules,
+        skipped_symlinks: collection
+            .skipped_symlinks
+            .iter()
+            .map(|link| link.member_path.clone())
+            .collect(),
+        ..Provenance::default()
+    };
+    // .packkeep member path -> the empty source directory it stands for. Every
+    // .packkeep is copied from one zero-byte placeholder, kept until staged.
+    let mut packkeep = BTreeMap::new();
+    let _placeholder = match options.empty_dirs {
+        EmptyDirs::Drop => None,
+        EmptyDirs::List => {
+            provenance.empty_dirs = collection
+                .empty_dirs
+                .iter()
+                .map(|dir| dir.member_path.clone())
+                .collect();
+            None
+        }
+        EmptyDirs::Keep if collection.empty_dirs.is_empty() => None,
+        EmptyDirs::Keep => {
+            let placeholder = tempfile::NamedTempFile::new().map_err(|e| {
+                Box::new(RefusalEnvelope::new(
+                    RefusalCode::Io,
+                    Some(format!("Cannot create {PACKKEEP_FILE} placeholder: {e}")),
+                    None,
+                ))
+            })?;
+            for dir in &collection.empty_dirs {
+                let member_path = format!("{}/{PACKKEEP_FILE}", dir.member_path);
+                candidates.push(MemberCandidate {
+                    source: placeholder.path().to_path_buf(),
+                    member_path: member_path.clone(),
+                });
+                packkeep.insert(member_path, dir.source.join(PACKKEEP_FILE));
+            }
+            candidates.sort_by(|a, b| a.member_path.cmp(&b.member_path));
+            Some(placeholder)
+        }
+    };
+    // stdin, merged, and .packkeep members are not part of the walk.
+    let total_bytes = match options.max_total_bytes {
+        Some(_) => candidates
+            .iter()
+            .map(|candidate| fs::metadata(&candidate.source).map_or(0, |meta| meta.len()))
+            .sum(),
+        None => 0,
+    };
+    check_collection_limits(
+        options.max_members,
+        options.max_total_bytes,
+        candidates.len(),
+        total_bytes,
+        None,
+    )?;
 
     // 2. Collision check
     check_collisions(&candidates)?;
+    for candidate in &candidates {
+        options.events.notify(&PackEvent::ArtifactCollected {
+            path: candidate.member_path.clone(),
+            source: candidate.source.display().to_string(),
+        });
+    }
+    let mut annotations = SealAnnotations {
+        visibility: resolve_visibility(&candidates, &options.restrict)?,
+        origins: match &options.origins {
+            Some(path) => load_origins(path, &candidates)?,
+            None => BTreeMap::new(),
+        },
+        member_annotations: resolve_annotations(
+            &candidates,
+            &options.annotate,
+            options.max_note_bytes.unwrap_or(DEFAULT_MAX_NOTE_BYTES),
+        )?,
+        provenance,
+        strict_types: options.strict_types,
+        encrypt_to: options.encrypt_to.clone(),
+        lineage: parent.as_ref().map(ParentPack::lineage),
+    };
+    if let Some(parent) = &parent {
+        parent.carry_annotations(&mut annotations);
+    }
 
-    // 3. Staging dir (in parent of final output or system temp)
-    let created = Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let trust_plan = match &options.trust_hashes {
+        Some(_) if options.hash_algorithm != HashAlgorithm::Sha256 => {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!(
+                    "--trust-hashes holds sha256 hashes and cannot be used with --hash-algorithm {}",
+                    options.hash_algorithm.as_str()
+                )),
+                None,
+            )));
+        }
+        Some(path) => {
+            let trusted = load_trusted_hashes(path)?;
+            Some(plan_trust(&candidates, &trusted, trust_nonce()))
+        }
+        None => None,
+    };
+    // Without this a pack sealed on trust would look fully hashed.
+    annotations.provenance.trust_hashes = trust_plan.as_ref().map(|plan| TrustHashes {
+        mode: TrustHashesMode::Sampled,
+        trusted: plan.trusted_count(),
+        sampled: plan.sampled_count(),
+    });
 
-    // Create staging in system temp
-    let staging_dir = tempfile::tempdir().map_err(|e| {
+    // 3. Staging dir: system temp, or the --resume directory with its
+    // checkpoint, whose first attempt fixes `created`
+    let staging_refusal = |e: io::Error| {
         Box::new(RefusalEnvelope::new(
             RefusalCode::Io,
             Some(format!("Cannot create staging directory: {e}")),
             None,
         ))
-    })?;
+    };
+    let (staging_dir, created, mut checkpoint) = match &options.resume {
+        Some(dir) => {
+            let checkpoint =
+                Checkpoint::open(dir, options.hash_algorithm).map_err(staging_refusal)?;
+            checkpoint.prune(&candidates).map_err(staging_refusal)?;
+            let created = checkpoint.created().to_string();
+            (Staging::resume(dir), created, Some(checkpoint))
+        }
+        None => (
+            Staging::Temp(tempfile::tempdir().map_err(staging_refusal)?),
+            Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            None,
+        ),
+    };
+    let created = pinned_created.unwrap_or(created);
 
     // 4. Copy and hash
-    let copied = copy_and_hash(&candidates, staging_dir.path())?;
+    options.events.notify(&PackEvent::HashingStarted {
+        members: candidates.len(),
+        bytes: candidates
+            .iter()
+            .filter_map(|candidate| fs::metadata(&candidate.source).ok())
+            .map(|meta| meta.len())
+            .sum(),
+    });
+    let copied = copy_and_hash_resumable(
+        &candidates,
+        staging_dir.path(),
+        trust_plan.as_ref().unwrap_or(&TrustPlan::default()),
+        options.hash_algorithm,
+        &annotations.provenance.detection_rules,
+        &options.events,
+        checkpoint.as_mut(),
+    )?;
+    if options.member_timestamps {
+        annotations.provenance.sealed_at = copied
+            .iter()
+            .map(|member| (member.member_path.clone(), member.sealed_at.clone()))
+            .collect();
+    }
+
+    // The URL artifact a candidate was downloaded from, if any.
+    let remote_of = |candidate: &MemberCandidate| -> Option<&RemoteArtifact> {
+        remote
+            .iter()
+            .find(|(root, _)| candidate.source.starts_with(root))
+            .map(|(_, fetched)| fetched)
+    };
+    // What each candidate was sealed from, as given (the `.packkeep` path an
+    // empty directory stands for, `-` for stdin, a URL, or a local path), and
+    // the path on disk behind it, if any.
+    let source_of = |candidate: &MemberCandidate| -> (String, Option<PathBuf>) {
+        match packkeep.get(&candidate.member_path) {
+            Some(stands_for) => (stands_for.display().to_string(), Some(stands_for.clone())),
+            None if stdin_member
+                .as_ref()
+                .is_some_and(|member| member.source == candidate.source) =>
+            {
+                (STDIN_ARTIFACT.to_string(), None)
+            }
+            None => remote_of(candidate).map_or_else(
+                || {
+                    let local = candidate.source.clone();
+                    (local.display().to_string(), Some(local))
+                },
+                |r| (r.url.clone(), None),
+            ),
+        }
+    };
+    annotations.provenance.remote = candidates
+        .iter()
+        .filter_map(|candidate| {
+            remote_of(candidate).map(|r| {
+                let source = RemoteSource {
+                    url: r.url.clone(),
+                    final_url: r.final_url.clone(),
+                };
+                (candidate.member_path.clone(), source)
+            })
+        })
+        .collect();
+    if options.provenance {
+        annotations.provenance.sources = Some(SourceProvenance {
+            hostname: hostname(),
+            user: user(),
+            members: candidates
+                .iter()
+                .map(|candidate| {
+                    let member = match source_of(candidate) {
+                        (source, Some(local)) => MemberSource {
+                            source: std::path::absolute(&local)
+                                .map_or(source, |path| path.display().to_string()),
+                            mtime: source_mtime(&local),
+                        },
+                        (source, None) => MemberSource {
+                            source,
+                            mtime: None,
+                        },
+                    };
+                    (candidate.member_path.clone(), member)
+                })
+                .collect(),
+        });
+    }
 
     // 5. Finalize manifest
-    let manifest = finalize_manifest(&copied, staging_dir.path(), created, note)?;
+    let manifest = finalize_manifest_with_annotations(
+        &copied,
+        staging_dir.path(),
+        created,
+        note,
+        &annotations,
+    )?;
+    options.events.notify(&PackEvent::ManifestFinalized {
+        pack_id: manifest.pack_id.clone(),
+        member_count: manifest.member_count,
+    });
+    let signature = signing_key.map(|key| PackSignature::sign(&manifest, &key));
+    if let Some(signature) = &signature {
+        write_signature(staging_dir.path(), signature).map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot write {SIGNATURE_FILE}: {e}")),
+                None,
+            ))
+        })?;
+    }
+    let timestamp = match &options.timestamp_url {
+        Some(url) => {
+            let timestamp = PackTimestamp::request(&manifest, url).map_err(|message| {
+                Box::new(RefusalEnvelope::new(
+                    RefusalCode::Io,
+                    Some(message),
+                    Some(json!({ "timestamp_url": url })),
+                ))
+            })?;
+            write_timestamp(staging_dir.path(), &timestamp).map_err(|e| {
+                Box::new(RefusalEnvelope::new(
+                    RefusalCode::Io,
+                    Some(format!("Cannot write {TIMESTAMP_FILE}: {e}")),
+                    None,
+                ))
+            })?;
+            Some(timestamp)
+        }
+        None => None,
+    };
 
     // 6. Determine final output path and atomically promote
+    let output_root = options
+        .output_root
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("pack"));
     let final_dir = match output {
         Some(dir) => dir.to_path_buf(),
-        None => PathBuf::from("pack").join(&manifest.pack_id),
+        None if options.archive => {
+            output_root.join(format!("{}{ARCHIVE_EXTENSION}", manifest.pack_id))
+        }
+        None => output_root.join(&manifest.pack_id),
     };
+    let output_work_tree = if options.allow_git {
+        None
+    } else {
+        enclosing_work_tree(&final_dir)
+    };
+
+    if options.archive && final_dir.exists() {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(format!(
+                "Output archive already exists: {}",
+                final_dir.display()
+            )),
+            None,
+        )));
+    }
 
     // Refuse if target exists and is non-empty
     if final_dir.exists() {
@@ -71,6 +607,67 @@ pub fn execute_seal(
         }
     }
 
+    let result = SealResult {
+        pack_id: manifest.pack_id.clone(),
+        output_dir: final_dir,
+        member_count: manifest.member_count,
+        witness_inputs: candidates
+            .iter()
+            .zip(copied.iter())
+            .map(|(candidate, copied_member)| WitnessInput {
+                path: source_of(candidate).0,
+                hash: Some(copied_member.bytes_hash.clone()),
+                bytes: Some(copied_member.size),
+            })
+            .collect(),
+        trust: trust_plan.map(|plan| TrustSummary {
+            trusted: plan.trusted_count(),
+            sampled: plan.sampled_count(),
+        }),
+        excluded_git: collection.excluded_git,
+        skipped_symlinks: collection
+            .skipped_symlinks
+            .into_iter()
+            .map(|link| link.source)
+            .collect(),
+        output_work_tree,
+        remote: remote.into_iter().map(|(_, fetched)| fetched).collect(),
+        signer: signature.map(|signature| signature.public_key),
+        timestamp: timestamp.map(|timestamp| (timestamp.tsa_url, timestamp.gen_time)),
+        resumed: checkpoint.map(|checkpoint| checkpoint.resumed()),
+        parent_pack_id: parent.as_ref().map(|parent| parent.pack_id().to_string()),
+    };
+    Ok(StagedSeal {
+        staging_dir,
+        manifest,
+        archive: options.archive,
+        result,
+    })
+}
+
+/// `created` for a `SOURCE_DATE_EPOCH` value.
+fn source_date_epoch(epoch: &str) -> Result<String, Box<RefusalEnvelope>> {
+    epoch
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .and_then(|seconds| DateTime::<Utc>::from_timestamp(seconds, 0))
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .ok_or_else(|| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!(
+                    "Invalid {SOURCE_DATE_EPOCH_ENV}: {epoch:?} is not a Unix timestamp in seconds"
+                )),
+                None,
+            ))
+        })
+}
+
+/// Move a staged seal to its output path (step 6).
+pub(crate) fn promote_seal(staged: StagedSeal) -> Result<SealResult, Box<RefusalEnvelope>> {
+    let final_dir = &staged.result.output_dir;
+
     // Create parent of final_dir if needed
     if let Some(parent) = final_dir.parent() {
         if !parent.exists() {
@@ -84,40 +681,169 @@ pub fn execute_seal(
         }
     }
 
-    // Atomic rename from staging to final
-    // Note: rename may fail across filesystems; in that case, fall back to copy
-    if fs::rename(staging_dir.path(), &final_dir).is_err() {
-        // Fallback: copy tree
-        copy_dir_recursive(staging_dir.path(), &final_dir)?;
+    // A directory sealed straight into a store must be named for its pack.
+    if !staged.archive {
+        check_store_slot(final_dir, &staged.result.pack_id)?;
     }
 
-    // Prevent tempdir cleanup from failing (dir was moved)
-    // into_path() consumes the TempDir without trying to remove it
-    let _ = staging_dir.keep();
+    if staged.archive {
+        write_archive(staged.staging_dir.path(), &staged.manifest, final_dir)?;
+        staged.staging_dir.close();
+    } else {
+        // Atomic rename from staging to final
+        // Note: rename may fail across filesystems; in that case, fall back to copy
+        promote_staging(staged.staging_dir, final_dir, |from, to| {
+            fs::rename(from, to)
+        })?;
+        index_enclosing_store(final_dir, &staged.result.pack_id)?;
+    }
 
-    Ok(SealResult {
-        pack_id: manifest.pack_id.clone(),
-        output_dir: final_dir,
-        member_count: manifest.member_count,
-        witness_inputs: candidates
-            .iter()
-            .zip(copied.iter())
-            .map(|(candidate, copied_member)| WitnessInput {
-                path: candidate.source.display().to_string(),
-                hash: Some(copied_member.bytes_hash.clone()),
-                bytes: Some(copied_member.size),
-            })
-            .collect(),
-    })
+    Ok(staged.result)
 }
 
 /// Result of a successful seal operation.
 #[derive(Debug)]
 pub struct SealResult {
     pub pack_id: String,
+    /// Pack directory, or the `.pack.tar` file with `--archive`.
     pub output_dir: PathBuf,
     pub member_count: usize,
     pub witness_inputs: Vec<WitnessInput>,
+    /// Present when `--trust-hashes` was used.
+    pub trust: Option<TrustSummary>,
+    /// `.git` metadata skipped during collection (empty with `--allow-git`).
+    pub excluded_git: Vec<PathBuf>,
+    /// Symlinks left out by `--skip-symlinks`.
+    pub skipped_symlinks: Vec<PathBuf>,
+    /// Git work tree enclosing the output, where the pack could be committed.
+    pub output_work_tree: Option<PathBuf>,
+    /// URL artifacts fetched for this seal, in argument order.
+    pub remote: Vec<RemoteArtifact>,
+    /// Hex public key of the `--sign` signer.
+    pub signer: Option<String>,
+    /// With `--timestamp-url`: the TSA and the token's `genTime`.
+    pub timestamp: Option<(String, String)>,
+    /// With `--resume`: members taken from the checkpoint rather than copied.
+    pub resumed: Option<usize>,
+    /// With `--merge`: `pack_id` of the pack whose members were carried in.
+    pub parent_pack_id: Option<String>,
+}
+
+/// How many members took a trusted hash versus were re-hashed as a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustSummary {
+    pub trusted: usize,
+    pub sampled: usize,
+}
+
+/// A source file's modification time, as RFC 3339 UTC to the millisecond.
+fn source_mtime(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    Some(DateTime::<Utc>::from(modified).to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+}
+
+/// Per-run nonce so each seal samples a different subset of trusted hashes.
+fn trust_nonce() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Temporary directory for downloaded URL artifacts, stdin, and expanded zip
+/// artifacts, created on first use.
+fn input_dir(
+    dir: &mut Option<tempfile::TempDir>,
+) -> Result<&tempfile::TempDir, Box<RefusalEnvelope>> {
+    match dir {
+        Some(dir) => Ok(dir),
+        None => Ok(dir.insert(tempfile::tempdir().map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot create download directory: {e}")),
+                None,
+            ))
+        })?)),
+    }
+}
+
+/// How the staging directory reached the final output path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Promotion {
+    Renamed,
+    Copied,
+}
+
+/// Move `staging` to `final_dir`, falling back to a recursive copy when `rename`
+/// fails (e.g. staging and output on different filesystems).
+///
+/// After a rename the tree lives at `final_dir`, so the `TempDir` is released
+/// without cleanup; after a copy the staging tree is removed instead of leaked.
+fn promote_staging(
+    staging: Staging,
+    final_dir: &Path,
+    rename: impl FnOnce(&Path, &Path) -> std::io::Result<()>,
+) -> Result<Promotion, Box<RefusalEnvelope>> {
+    if rename(staging.path(), final_dir).is_ok() {
+        staging.keep();
+        return Ok(Promotion::Renamed);
+    }
+
+    copy_dir_recursive(staging.path(), final_dir)?;
+    // The pack is already complete at final_dir; a failed cleanup must not fail the seal.
+    staging.close();
+    Ok(Promotion::Copied)
+}
+
+/// Where a seal stages its members until promotion.
+pub(crate) enum Staging {
+    /// A system temp directory, removed if the seal fails.
+    Temp(tempfile::TempDir),
+    /// The `staging/` tree of a `--resume` directory, kept after a failure so
+    /// the next attempt can continue from its checkpoint.
+    Resume { dir: PathBuf, staging: PathBuf },
+}
+
+impl Staging {
+    fn resume(dir: &Path) -> Self {
+        Self::Resume {
+            dir: dir.to_path_buf(),
+            staging: dir.join(STAGING_SUBDIR),
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Self::Temp(dir) => dir.path(),
+            Self::Resume { staging, .. } => staging,
+        }
+    }
+
+    /// Release the staging tree after it was renamed into place. A `--resume`
+    /// directory is left empty by the rename, so its checkpoint goes with it.
+    fn keep(self) {
+        match self {
+            Self::Temp(dir) => {
+                let _ = dir.keep();
+            }
+            Self::Resume { dir, .. } => {
+                let _ = fs::remove_file(dir.join(CHECKPOINT_FILE));
+                let _ = fs::remove_dir(dir);
+            }
+        }
+    }
+
+    /// Remove the staging tree once the pack is complete elsewhere.
+    fn close(self) {
+        match self {
+            Self::Temp(dir) => {
+                let _ = dir.close();
+            }
+            Self::Resume { dir, .. } => {
+                let _ = fs::remove_dir_all(dir);
+            }
+        }
+    }
 }
 
 /// Recursively copy a directory tree.
@@ -171,7 +897,9 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Box<RefusalEnvelope>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sha2::Digest;
     use std::fs;
+    use std::ops::ControlFlow;
     use tempfile::TempDir;
 
     fn create_test_artifacts(tmp: &TempDir) -> Vec<PathBuf> {
@@ -188,6 +916,44 @@ mod tests {
         vec![lock, report]
     }
 
+    fn staging_with_member() -> (Staging, PathBuf) {
+        let staging = TempDir::new().unwrap();
+        fs::create_dir_all(staging.path().join("nested")).unwrap();
+        fs::write(staging.path().join("nested").join("m.json"), "{}").unwrap();
+        let staging_path = staging.path().to_path_buf();
+        (Staging::Temp(staging), staging_path)
+    }
+
+    #[test]
+    fn promotion_by_copy_removes_staging_tree() {
+        let out = TempDir::new().unwrap();
+        let final_dir = out.path().join("final");
+        let (staging, staging_path) = staging_with_member();
+
+        let promotion = promote_staging(staging, &final_dir, |_, _| {
+            Err(std::io::Error::other("simulated cross-device rename"))
+        })
+        .unwrap();
+
+        assert_eq!(promotion, Promotion::Copied);
+        assert!(final_dir.join("nested").join("m.json").is_file());
+        assert!(!staging_path.exists(), "staging tree leaked after copy");
+    }
+
+    #[test]
+    fn promotion_by_rename_moves_staging_tree() {
+        let out = TempDir::new().unwrap();
+        let final_dir = out.path().join("final");
+        let (staging, staging_path) = staging_with_member();
+
+        let promotion =
+            promote_staging(staging, &final_dir, |from, to| fs::rename(from, to)).unwrap();
+
+        assert_eq!(promotion, Promotion::Renamed);
+        assert!(final_dir.join("nested").join("m.json").is_file());
+        assert!(!staging_path.exists());
+    }
+
     #[test]
     fn seal_creates_pack_directory() {
         let src = TempDir::new().unwrap();
@@ -234,6 +1000,159 @@ mod tests {
         assert_eq!(manifest["note"], "Q4 recon");
     }
 
+    #[test]
+    fn seal_records_member_timestamps_on_request() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifacts = create_test_artifacts(&src);
+
+        let plain = execute_seal(&artifacts, Some(&out.path().join("plain")), None).unwrap();
+        let manifest = fs::read_to_string(plain.output_dir.join("manifest.json")).unwrap();
+        assert!(!manifest.contains("provenance"));
+
+        let options = SealOptions {
+            member_timestamps: true,
+            ..SealOptions::default()
+        };
+        let timed =
+            execute_seal_with_options(&artifacts, Some(&out.path().join("timed")), None, &options)
+                .unwrap();
+        let manifest: Manifest =
+            serde_json::from_slice(&fs::read(timed.output_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        let sealed_at = manifest.provenance.unwrap().sealed_at;
+        assert_eq!(
+            sealed_at.keys().collect::<Vec<_>>(),
+            ["nov.lock.json", "rvl.report.json"]
+        );
+        let created = chrono::DateTime::parse_from_rfc3339(&manifest.created).unwrap();
+        for time in sealed_at.values() {
+            assert!(chrono::DateTime::parse_from_rfc3339(time).unwrap() >= created);
+        }
+    }
+
+    #[test]
+    fn reproducible_seals_share_a_pack_id() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifacts = create_test_artifacts(&src);
+        let seal = |name: &str, options: &SealOptions| {
+            let output = out.path().join(name);
+            let result = execute_seal_with_options(&artifacts, Some(&output), None, options);
+            let manifest: Manifest =
+                serde_json::from_slice(&fs::read(output.join("manifest.json")).unwrap()).unwrap();
+            (result.unwrap().pack_id, manifest)
+        };
+
+        let pinned = SealOptions {
+            reproducible: true,
+            source_date_epoch: Some("1767225600".to_string()),
+            ..SealOptions::default()
+        };
+        let (first, manifest) = seal("a", &pinned);
+        assert_eq!(manifest.created, "2026-01-01T00:00:00Z");
+        assert_eq!(
+            manifest.provenance.unwrap().reproducible,
+            Some(ReproducibleMode::SourceDateEpoch)
+        );
+        assert_eq!(seal("b", &pinned).0, first);
+
+        let excluded = SealOptions {
+            reproducible: true,
+            ..SealOptions::default()
+        };
+        let (first, mut manifest) = seal("c", &excluded);
+        manifest.created = "2000-01-01T00:00:00Z".to_string();
+        assert_eq!(manifest.recompute_pack_id(), first);
+
+        let invalid = SealOptions {
+            source_date_epoch: Some("yesterday".to_string()),
+            ..pinned
+        };
+        let refusal = execute_seal_with_options(&artifacts, None, None, &invalid).unwrap_err();
+        assert!(refusal.refusal.message.contains(SOURCE_DATE_EPOCH_ENV));
+    }
+
+    #[test]
+    fn resumed_seal_matches_an_uninterrupted_one() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifacts = create_test_artifacts(&src);
+        let resume_dir = out.path().join("resume");
+
+        let interrupted = SealOptions {
+            resume: Some(resume_dir.clone()),
+            events: crate::events::EventBus::default().subscribe(|event| match event {
+                crate::events::PackEvent::MemberHashed { .. } => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }),
+            ..SealOptions::default()
+        };
+        let output = out.path().join("pack");
+        assert!(execute_seal_with_options(&artifacts, Some(&output), None, &interrupted).is_err());
+        let log = fs::read_to_string(resume_dir.join(CHECKPOINT_FILE)).unwrap();
+        assert_eq!(log.lines().count(), 2, "header and one member");
+
+        // The same first attempt, had it checkpointed nothing.
+        let fresh_dir = out.path().join("fresh");
+        fs::create_dir_all(&fresh_dir).unwrap();
+        let header = log.lines().next().unwrap();
+        fs::write(fresh_dir.join(CHECKPOINT_FILE), format!("{header}\n")).unwrap();
+
+        let resumed = SealOptions {
+            resume: Some(resume_dir.clone()),
+            ..SealOptions::default()
+        };
+        let resumed = execute_seal_with_options(&artifacts, Some(&output), None, &resumed).unwrap();
+        assert_eq!(resumed.resumed, Some(1));
+        assert!(!resume_dir.exists(), "resume dir left after success");
+
+        let fresh = SealOptions {
+            resume: Some(fresh_dir),
+            ..SealOptions::default()
+        };
+        let fresh = execute_seal_with_options(
+            &artifacts,
+            Some(&out.path().join("fresh_pack")),
+            None,
+            &fresh,
+        )
+        .unwrap();
+        assert_eq!(fresh.resumed, Some(0));
+        assert_eq!(resumed.pack_id, fresh.pack_id);
+    }
+
+    #[test]
+    fn seal_records_source_provenance_on_request() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifacts = create_test_artifacts(&src);
+
+        let plain = execute_seal(&artifacts, Some(&out.path().join("plain")), None).unwrap();
+        let options = SealOptions {
+            provenance: true,
+            ..SealOptions::default()
+        };
+        let traced =
+            execute_seal_with_options(&artifacts, Some(&out.path().join("traced")), None, &options)
+                .unwrap();
+        assert_ne!(traced.pack_id, plain.pack_id);
+
+        let manifest: Manifest =
+            serde_json::from_slice(&fs::read(traced.output_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        let sources = manifest.provenance.unwrap().sources.unwrap();
+        assert_eq!(sources.user, crate::witness::user());
+        assert_eq!(
+            sources.members.keys().collect::<Vec<_>>(),
+            ["nov.lock.json", "rvl.report.json"]
+        );
+        let lock = &sources.members["nov.lock.json"];
+        assert!(Path::new(&lock.source).is_absolute());
+        assert!(lock.source.ends_with("nov.lock.json"));
+        chrono::DateTime::parse_from_rfc3339(lock.mtime.as_deref().unwrap()).unwrap();
+    }
+
     #[test]
     fn seal_refuses_non_empty_output_dir() {
         let src = TempDir::new().unwrap();
@@ -255,6 +1174,78 @@ mod tests {
         assert_eq!(err.refusal.code, "E_EMPTY");
     }
 
+    #[test]
+    fn seal_with_trusted_hashes_records_summary() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifacts = create_test_artifacts(&src);
+
+        let mut hashes = serde_json::Map::new();
+        for path in &artifacts {
+            let bytes = fs::read(path).unwrap();
+            let hash = format!("sha256:{}", hex::encode(sha2::Sha256::digest(&bytes)));
+            hashes.insert(path.display().to_string(), serde_json::Value::String(hash));
+        }
+        let hashes_path = src.path().join("hashes.json");
+        fs::write(&hashes_path, serde_json::Value::Object(hashes).to_string()).unwrap();
+
+        let options = SealOptions {
+            trust_hashes: Some(hashes_path),
+            ..SealOptions::default()
+        };
+        let result = execute_seal_with_options(
+            &artifacts,
+            Some(&out.path().join("trusted")),
+            None,
+            &options,
+        )
+        .unwrap();
+
+        let trust = result.trust.unwrap();
+        assert_eq!(trust.sampled, 1);
+        assert_eq!(trust.trusted, 1);
+        let untrusted = execute_seal(&artifacts, Some(&out.path().join("plain")), None).unwrap();
+        assert!(untrusted.trust.is_none());
+        let manifest = |dir: &Path| -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(dir.join("manifest.json")).unwrap()).unwrap()
+        };
+        let trusted_manifest = manifest(&result.output_dir);
+        let plain_manifest = manifest(&untrusted.output_dir);
+        assert_eq!(trusted_manifest["members"], plain_manifest["members"]);
+        // The pack itself says its hashes were taken on trust.
+        assert_eq!(
+            trusted_manifest["provenance"]["trust_hashes"],
+            serde_json::json!({"mode": "sampled", "trusted": 1, "sampled": 1})
+        );
+        assert!(plain_manifest.get("provenance").is_none());
+    }
+
+    #[test]
+    fn seal_refuses_oversized_note() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let artifacts = create_test_artifacts(&src);
+        let output_dir = out.path().join("big_note");
+
+        let huge = "x".repeat(DEFAULT_MAX_NOTE_BYTES + 1);
+        let err = execute_seal(&artifacts, Some(&output_dir), Some(huge)).unwrap_err();
+        assert_eq!(err.refusal.code, "E_LIMIT");
+        assert!(!output_dir.exists());
+
+        let options = SealOptions {
+            max_note_bytes: Some(4),
+            ..SealOptions::default()
+        };
+        let err = execute_seal_with_options(
+            &artifacts,
+            Some(&output_dir),
+            Some("hello".to_string()),
+            &options,
+        )
+        .unwrap_err();
+        assert_eq!(err.refusal.detail.unwrap()["limit"], 4);
+    }
+
     #[test]
     fn seal_member_bytes_match_source() {
         let src = TempDir::new().unwrap();
@@ -1,10 +1,17 @@
 use std::fs;
-use std::io::{self, Read, Write};
+use std::io::{self, Write};
 use std::path::Path;
 
-use sha2::{Digest, Sha256};
+use chrono::{SecondsFormat, Utc};
 
 use super::collect::MemberCandidate;
+use super::hasher::{hash_buffer_size, stream_hash};
+use super::resume::Checkpoint;
+use super::sniff::{DetectSample, MemberDetection, SampledWriter};
+use super::trust::{mismatch_refusal, TrustDecision, TrustPlan};
+use crate::cli::HashAlgorithm;
+use crate::detect::DetectionRule;
+use crate::events::{EventBus, PackEvent};
 use crate::refusal::{RefusalCode, RefusalEnvelope};
 
 /// Result of copying a single member into the pack output directory.
@@ -12,10 +19,18 @@ use crate::refusal::{RefusalCode, RefusalEnvelope};
 pub struct CopiedMember {
     /// The member path within the pack directory.
     pub member_path: String,
-    /// `sha256:<hex>` hash of the copied bytes.
+    /// `<algorithm>:<hex>` hash of the copied bytes.
     pub bytes_hash: String,
     /// Number of bytes copied.
     pub size: u64,
+    /// RFC 3339 UTC time, to the millisecond, at which the member finished
+    /// copying and hashing.
+    pub sealed_at: String,
+    /// The source had an execute bit set; the copy has one too.
+    pub executable: bool,
+    /// Type detected from the bytes as they were copied; `None` when the
+    /// member was not copied in this run (resumed), so finalize reads it.
+    pub detection: Option<MemberDetection>,
 }
 
 /// Copy members into the staging directory and compute their SHA256 hashes.
@@ -27,62 +42,213 @@ pub struct CopiedMember {
 pub fn copy_and_hash(
     candidates: &[MemberCandidate],
     staging_dir: &Path,
+) -> Result<Vec<CopiedMember>, Box<RefusalEnvelope>> {
+    copy_and_hash_with_trust(
+        candidates,
+        staging_dir,
+        &TrustPlan::default(),
+        HashAlgorithm::Sha256,
+        &EventBus::default(),
+    )
+}
+
+/// Like [`copy_and_hash`], but honours a `--trust-hashes` plan and hashes
+/// with `algorithm`. Members are typed with the built-in detection rules.
+///
+/// Trusted members are copied without hashing and take their provided hash;
+/// sampled members are hashed and refused with `E_HASH_MISMATCH` on disagreement.
+/// Each member is announced on `events` as it is done; a subscriber that
+/// breaks cancels the copy with `E_IO`.
+pub fn copy_and_hash_with_trust(
+    candidates: &[MemberCandidate],
+    staging_dir: &Path,
+    trust: &TrustPlan,
+    algorithm: HashAlgorithm,
+    events: &EventBus,
+) -> Result<Vec<CopiedMember>, Box<RefusalEnvelope>> {
+    copy_and_hash_resumable(candidates, staging_dir, trust, algorithm, &[], events, None)
+}
+
+/// Like [`copy_and_hash_with_trust`], but types members with
+/// `detection_rules` tried first, and takes members an earlier attempt left
+/// in `checkpoint` and logs each newly copied member to it, so a failed
+/// `seal --resume` can continue where it stopped.
+pub fn copy_and_hash_resumable(
+    candidates: &[MemberCandidate],
+    staging_dir: &Path,
+    trust: &TrustPlan,
+    algorithm: HashAlgorithm,
+    detection_rules: &[DetectionRule],
+    events: &EventBus,
+    mut checkpoint: Option<&mut Checkpoint>,
 ) -> Result<Vec<CopiedMember>, Box<RefusalEnvelope>> {
     let mut results = Vec::with_capacity(candidates.len());
+    let buffer_size = hash_buffer_size();
 
     for candidate in candidates {
-        let dest = staging_dir.join(&candidate.member_path);
+        let resumed = checkpoint
+            .as_deref_mut()
+            .and_then(|checkpoint| checkpoint.take(candidate));
+        let copied = match resumed {
+            Some(copied) => copied,
+            None => {
+                let copied = copy_member(
+                    candidate,
+                    staging_dir,
+                    trust,
+                    algorithm,
+                    detection_rules,
+                    buffer_size,
+                )?;
+                if let Some(checkpoint) = checkpoint.as_deref_mut() {
+                    checkpoint
+                        .record(candidate, &copied)
+                        .map_err(|e| io_refusal_detail(&candidate.member_path, "checkpoint", e))?;
+                }
+                copied
+            }
+        };
 
-        // Create parent directories if needed.
-        if let Some(parent) = dest.parent() {
-            fs::create_dir_all(parent).map_err(|e| io_refusal(&candidate.member_path, e))?;
+        let hashed = PackEvent::MemberHashed {
+            path: copied.member_path.clone(),
+            bytes_hash: copied.bytes_hash.clone(),
+            size: copied.size,
+        };
+        results.push(copied);
+        if events.emit(&hashed).is_break() {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some("Seal cancelled".to_string()),
+                Some(serde_json::json!({
+                    "cancelled": true,
+                    "members_hashed": results.len(),
+                })),
+            )));
         }
+    }
+
+    Ok(results)
+}
 
-        // Copy and hash in one pass.
-        let (bytes_hash, size) =
-            copy_and_hash_file(&candidate.source, &dest, &candidate.member_path)?;
+/// Copy one candidate into `staging_dir`, hashing it unless `trust` says to
+/// take its provided hash, and typing it from the same pass over its bytes.
+fn copy_member(
+    candidate: &MemberCandidate,
+    staging_dir: &Path,
+    trust: &TrustPlan,
+    algorithm: HashAlgorithm,
+    detection_rules: &[DetectionRule],
+    buffer_size: usize,
+) -> Result<CopiedMember, Box<RefusalEnvelope>> {
+    let dest = staging_dir.join(&candidate.member_path);
 
-        results.push(CopiedMember {
-            member_path: candidate.member_path.clone(),
-            bytes_hash,
-            size,
-        });
+    // Create parent directories if needed.
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_refusal(&candidate.member_path, e))?;
     }
 
-    Ok(results)
+    // Copy and hash in one pass.
+    let decision = trust.get(&candidate.member_path);
+    let hash = !matches!(decision, Some(TrustDecision::Trusted(_)));
+    let mut sample = DetectSample::new();
+    let (computed, size, executable) = copy_and_hash_file(
+        &candidate.source,
+        &dest,
+        &candidate.member_path,
+        algorithm,
+        hash,
+        buffer_size,
+        &mut sample,
+    )?;
+    let bytes_hash = match decision {
+        Some(TrustDecision::Trusted(provided)) => provided.clone(),
+        Some(TrustDecision::Sampled(provided)) if *provided != computed => {
+            return Err(mismatch_refusal(
+                &candidate.member_path,
+                provided,
+                &computed,
+            ));
+        }
+        _ => computed,
+    };
+
+    Ok(CopiedMember {
+        member_path: candidate.member_path.clone(),
+        bytes_hash,
+        size,
+        sealed_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        executable,
+        detection: Some(sample.detect(&candidate.member_path, detection_rules)),
+    })
 }
 
-/// Copy a single file while computing its SHA256 hash.
+/// Copy a single file while computing its hash in `buffer_size` chunks and
+/// capturing what type detection needs into `sample`.
+///
+/// When `hash` is false the bytes are only copied and the returned hash is empty.
+/// An executable source yields an executable copy. Returns (hash, size,
+/// executable).
 fn copy_and_hash_file(
     source: &Path,
     dest: &Path,
     member_path: &str,
-) -> Result<(String, u64), Box<RefusalEnvelope>> {
-    let mut reader =
+    algorithm: HashAlgorithm,
+    hash: bool,
+    buffer_size: usize,
+    sample: &mut DetectSample,
+) -> Result<(String, u64, bool), Box<RefusalEnvelope>> {
+    let reader =
         fs::File::open(source).map_err(|e| io_refusal_detail(member_path, "read source", e))?;
+    let executable = reader
+        .metadata()
+        .map_err(|e| io_refusal_detail(member_path, "read source", e))
+        .map(|meta| is_executable(&meta))?;
     let mut writer =
         fs::File::create(dest).map_err(|e| io_refusal_detail(member_path, "write dest", e))?;
 
-    let mut hasher = Sha256::new();
-    let mut buf = [0u8; 8192];
-    let mut total: u64 = 0;
+    let mut sink = SampledWriter {
+        inner: &mut writer,
+        sample,
+    };
+    let (bytes_hash, size) = stream_hash(reader, Some(&mut sink), buffer_size, algorithm, hash)
+        .map_err(|e| io_refusal_detail(member_path, "copy", e))?;
+    writer
+        .flush()
+        .map_err(|e| io_refusal_detail(member_path, "write", e))?;
+    if executable {
+        set_executable(dest).map_err(|e| io_refusal_detail(member_path, "chmod", e))?;
+    }
+    Ok((bytes_hash, size, executable))
+}
 
-    loop {
-        let n = reader
-            .read(&mut buf)
-            .map_err(|e| io_refusal_detail(member_path, "read", e))?;
-        if n == 0 {
-            break;
-        }
-        hasher.update(&buf[..n]);
-        writer
-            .write_all(&buf[..n])
-            .map_err(|e| io_refusal_detail(member_path, "write", e))?;
-        total += n as u64;
+/// True when any execute bit is set; always false off Unix.
+pub fn is_executable(meta: &fs::Metadata) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        false
     }
+}
 
-    let hash = hex::encode(hasher.finalize());
-    Ok((format!("sha256:{hash}"), total))
+/// Grant execute wherever `path` grants read (0644 becomes 0755); a no-op
+/// off Unix.
+pub fn set_executable(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path)?.permissions().mode();
+        fs::set_permissions(path, fs::Permissions::from_mode(mode | (mode & 0o444) >> 2))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(())
+    }
 }
 
 fn io_refusal(member_path: &str, err: io::Error) -> Box<RefusalEnvelope> {
@@ -188,6 +354,84 @@ mod tests {
         assert_eq!(err.refusal.code, "E_IO");
     }
 
+    #[test]
+    fn trusted_member_uses_provided_hash() {
+        let src_tmp = TempDir::new().unwrap();
+        let staging = TempDir::new().unwrap();
+        let candidate = make_candidate(&src_tmp, "t.json", b"trusted bytes");
+        let provided = format!("sha256:{}", "b".repeat(64));
+        let mut plan = TrustPlan::default();
+        plan.decisions.insert(
+            "t.json".to_string(),
+            TrustDecision::Trusted(provided.clone()),
+        );
+
+        let results = copy_and_hash_with_trust(
+            &[candidate],
+            staging.path(),
+            &plan,
+            HashAlgorithm::Sha256,
+            &EventBus::default(),
+        )
+        .unwrap();
+        assert_eq!(results[0].bytes_hash, provided);
+        assert_eq!(
+            fs::read(staging.path().join("t.json")).unwrap(),
+            b"trusted bytes"
+        );
+    }
+
+    #[test]
+    fn sampled_member_mismatch_refuses() {
+        let src_tmp = TempDir::new().unwrap();
+        let staging = TempDir::new().unwrap();
+        let candidate = make_candidate(&src_tmp, "s.json", b"sampled bytes");
+        let mut plan = TrustPlan::default();
+        plan.decisions.insert(
+            "s.json".to_string(),
+            TrustDecision::Sampled(format!("sha256:{}", "c".repeat(64))),
+        );
+
+        let err = copy_and_hash_with_trust(
+            &[candidate],
+            staging.path(),
+            &plan,
+            HashAlgorithm::Sha256,
+            &EventBus::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.refusal.code, "E_HASH_MISMATCH");
+        assert_eq!(err.refusal.detail.as_ref().unwrap()["path"], "s.json");
+    }
+
+    #[test]
+    fn breaking_on_a_hashed_member_cancels_the_copy() {
+        let src_tmp = TempDir::new().unwrap();
+        let staging = TempDir::new().unwrap();
+        let candidates = [
+            make_candidate(&src_tmp, "a.json", b"a"),
+            make_candidate(&src_tmp, "b.json", b"b"),
+        ];
+        let events = EventBus::default().subscribe(|event| match event {
+            PackEvent::MemberHashed { path, .. } if path == "a.json" => {
+                std::ops::ControlFlow::Break(())
+            }
+            _ => std::ops::ControlFlow::Continue(()),
+        });
+
+        let err = copy_and_hash_with_trust(
+            &candidates,
+            staging.path(),
+            &TrustPlan::default(),
+            HashAlgorithm::Sha256,
+            &events,
+        )
+        .unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert_eq!(err.refusal.detail.unwrap()["members_hashed"], 1);
+        assert!(!staging.path().join("b.json").exists());
+    }
+
     #[test]
     fn empty_file_hashes_correctly() {
         let src_tmp = TempDir::new().unwrap();
@@ -1,51 +1,150 @@
+use std::collections::BTreeMap;
 use std::fs;
+use std::io;
 use std::path::Path;
 
-use crate::detect::detect_member_type;
+use serde_json::json;
+
+use crate::detect::DetectionRule;
+use crate::encrypt::{encrypt_file, Recipient, ENCRYPTION_SCHEME};
 use crate::refusal::{RefusalCode, RefusalEnvelope};
 use crate::seal::copy::CopiedMember;
-use crate::seal::manifest::{Manifest, Member};
+use crate::seal::hasher::{hash_algorithm_of, hash_buffer_size, hash_file};
+use crate::seal::manifest::{Encryption, Lineage, Manifest, Member, MemberOrigin, Provenance};
+use crate::seal::sniff::{DetectSample, MemberDetection};
+
+/// Members larger than this (256 MiB) are typed by path alone; content-based
+/// detection would have to load the whole file.
+pub const MAX_DETECT_BYTES: u64 = 256 * 1024 * 1024;
 
 /// Build the manifest from copied members, finalize pack_id, and write manifest.json.
 ///
 /// Steps:
-/// 1. For each copied member, read content to detect type and artifact version.
-/// 2. Build members list sorted by path (already sorted from collect).
-/// 3. Create manifest with `pack_id: ""`, finalize via self-hash.
-/// 4. Write `manifest.json` into the staging directory.
+/// 1. For each copied member, take the type and content type detected while
+///    it was copied, or for a member resumed from an earlier attempt, read it
+///    (up to `MAX_DETECT_BYTES`) to detect them.
+/// 2. Under `seal --encrypt`, encrypt each member in place and record the
+///    hash of its ciphertext.
+/// 3. Build members list sorted by path (already sorted from collect).
+/// 4. Create manifest with `pack_id: ""`, finalize via self-hash.
+/// 5. Write `manifest.json` into the staging directory.
 pub fn finalize_manifest(
     copied: &[CopiedMember],
     staging_dir: &Path,
     created: String,
     note: Option<String>,
+) -> Result<Manifest, Box<RefusalEnvelope>> {
+    finalize_manifest_with_annotations(
+        copied,
+        staging_dir,
+        created,
+        note,
+        &SealAnnotations::default(),
+    )
+}
+
+/// Metadata supplied at seal time rather than detected from bytes. Per-member
+/// maps are keyed by member path.
+#[derive(Debug, Clone, Default)]
+pub struct SealAnnotations {
+    /// Visibility levels resolved from `seal --restrict`.
+    pub visibility: BTreeMap<String, String>,
+    /// Upstream descriptors loaded from `seal --origins`.
+    pub origins: BTreeMap<String, MemberOrigin>,
+    /// Labels from `seal --annotate`, recorded as the manifest's
+    /// `annotations`.
+    pub member_annotations: BTreeMap<String, String>,
+    /// Recorded as the manifest's `provenance` unless empty.
+    pub provenance: Provenance,
+    /// `seal --strict-types`: refuse with `E_AMBIGUOUS_TYPE` instead of
+    /// recording a type detected by filename or path alone.
+    pub strict_types: bool,
+    /// `seal --encrypt`: replace each member with its age encryption to
+    /// these recipients once its type is detected.
+    pub encrypt_to: Vec<Recipient>,
+    /// Recorded as the manifest's `lineage` (`seal --merge`).
+    pub lineage: Option<Lineage>,
+}
+
+/// [`finalize_manifest`], recording `annotations` on the matching members and
+/// in the manifest.
+pub fn finalize_manifest_with_annotations(
+    copied: &[CopiedMember],
+    staging_dir: &Path,
+    created: String,
+    note: Option<String>,
+    annotations: &SealAnnotations,
 ) -> Result<Manifest, Box<RefusalEnvelope>> {
     let tool_version = env!("CARGO_PKG_VERSION").to_string();
 
     let mut members = Vec::with_capacity(copied.len());
+    let mut ambiguous = Vec::new();
     for cm in copied {
         let file_path = staging_dir.join(&cm.member_path);
-        let content = fs::read(&file_path).map_err(|e| {
-            Box::new(RefusalEnvelope::new(
-                RefusalCode::Io,
-                Some(format!(
-                    "Cannot read copied member for type detection: {}: {e}",
-                    cm.member_path
-                )),
-                None,
-            ))
-        })?;
+        let MemberDetection {
+            member_type: detected,
+            content_type,
+        } = match &cm.detection {
+            Some(detection) => detection.clone(),
+            None => detect_staged(cm, &file_path, &annotations.provenance.detection_rules)?,
+        };
+        if annotations.strict_types && detected.is_ambiguous() {
+            ambiguous.push(json!({
+                "path": cm.member_path,
+                "type": detected.member_type,
+                "rule": detected.rule,
+                "confidence": detected.confidence.as_str(),
+            }));
+        }
 
-        let detected = detect_member_type(&content, &cm.member_path);
+        let ciphertext_hash = if annotations.encrypt_to.is_empty() {
+            None
+        } else {
+            Some(encrypt_member(cm, &file_path, &annotations.encrypt_to)?)
+        };
 
         members.push(Member {
             path: cm.member_path.clone(),
             bytes_hash: cm.bytes_hash.clone(),
+            size_bytes: Some(cm.size),
             member_type: detected.member_type,
             artifact_version: detected.artifact_version,
+            content_type: Some(content_type.media_type),
+            charset: content_type.charset,
+            visibility: annotations.visibility.get(&cm.member_path).cloned(),
+            origin: annotations.origins.get(&cm.member_path).cloned(),
+            executable: cm.executable.then_some(true),
+            ciphertext_hash,
         });
     }
 
+    if !ambiguous.is_empty() {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::AmbiguousType,
+            Some(format!(
+                "{} member(s) typed only by filename or path under --strict-types; run `pack detect` on them",
+                ambiguous.len()
+            )),
+            Some(json!({ "members": ambiguous })),
+        )));
+    }
+
     let mut manifest = Manifest::new(created, note, tool_version, members);
+    manifest.annotations = annotations.member_annotations.clone();
+    if !annotations.provenance.is_empty() {
+        manifest.provenance = Some(annotations.provenance.clone());
+    }
+    if !annotations.encrypt_to.is_empty() {
+        manifest.encryption = Some(Encryption {
+            scheme: ENCRYPTION_SCHEME.to_string(),
+            recipients: annotations
+                .encrypt_to
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        });
+    }
+    manifest.lineage = annotations.lineage.clone();
     manifest.finalize();
 
     // Write manifest.json
@@ -62,6 +161,47 @@ pub fn finalize_manifest(
     Ok(manifest)
 }
 
+/// Detect the type of a staged member that was not typed while copied.
+fn detect_staged(
+    cm: &CopiedMember,
+    file_path: &Path,
+    rules: &[DetectionRule],
+) -> Result<MemberDetection, Box<RefusalEnvelope>> {
+    let mut sample = DetectSample::new();
+    fs::File::open(file_path)
+        .and_then(|mut file| io::copy(&mut file, &mut sample))
+        .map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!(
+                    "Cannot read copied member for type detection: {}: {e}",
+                    cm.member_path
+                )),
+                None,
+            ))
+        })?;
+    Ok(sample.detect(&cm.member_path, rules))
+}
+
+/// Encrypt a staged member in place and hash the ciphertext with the
+/// algorithm of its `bytes_hash`.
+fn encrypt_member(
+    cm: &CopiedMember,
+    file_path: &Path,
+    recipients: &[Recipient],
+) -> Result<String, Box<RefusalEnvelope>> {
+    let algorithm = hash_algorithm_of(&cm.bytes_hash).unwrap_or_default();
+    encrypt_file(file_path, recipients)
+        .and_then(|()| hash_file(file_path, hash_buffer_size(), algorithm))
+        .map_err(|e| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot encrypt member {}: {e}", cm.member_path)),
+                None,
+            ))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,11 +222,17 @@ mod tests {
                 member_path: "nov.lock.json".to_string(),
                 bytes_hash: "sha256:aaa".to_string(),
                 size: lock_content.len() as u64,
+                sealed_at: "2026-01-15T10:29:58.120Z".to_string(),
+                executable: false,
+                detection: None,
             },
             CopiedMember {
                 member_path: "notes.txt".to_string(),
                 bytes_hash: "sha256:bbb".to_string(),
                 size: 5,
+                sealed_at: "2026-01-15T10:29:59.004Z".to_string(),
+                executable: false,
+                detection: None,
             },
         ];
         (staging, copied)
@@ -131,6 +277,33 @@ mod tests {
         assert_eq!(txt_member.unwrap().artifact_version, None);
     }
 
+    #[test]
+    fn members_typed_during_copy_are_not_read_again() {
+        let source = TempDir::new().unwrap();
+        let path = source.path().join("nov.lock.json");
+        fs::write(&path, br#"{"version": "lock.v0"}"#).unwrap();
+        let staging = TempDir::new().unwrap();
+        let candidate = crate::seal::collect::MemberCandidate {
+            source: path,
+            member_path: "nov.lock.json".to_string(),
+        };
+        let copied = crate::seal::copy::copy_and_hash(&[candidate], staging.path()).unwrap();
+        fs::remove_file(staging.path().join("nov.lock.json")).unwrap();
+
+        let manifest = finalize_manifest(
+            &copied,
+            staging.path(),
+            "2026-01-15T10:30:00Z".to_string(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(manifest.members[0].member_type, "lockfile");
+        assert_eq!(
+            manifest.members[0].content_type.as_deref(),
+            Some("application/json")
+        );
+    }
+
     #[test]
     fn writes_manifest_json_to_staging() {
         let (staging, copied) = setup_staging();
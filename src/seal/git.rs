@@ -0,0 +1,55 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Name of git's metadata directory (or gitfile, for worktrees and submodules).
+pub const GIT_DIR_NAME: &str = ".git";
+
+/// True when a path component is git metadata that seal excludes by default.
+pub fn is_git_metadata(name: &OsStr) -> bool {
+    name == GIT_DIR_NAME
+}
+
+/// Return the git work tree that would contain `path`, if any.
+///
+/// `path` need not exist yet (seal checks its output before creating it); the
+/// nearest ancestor holding a `.git` entry is reported.
+pub fn enclosing_work_tree(path: &Path) -> Option<PathBuf> {
+    let absolute = std::path::absolute(path).ok()?;
+    absolute
+        .ancestors()
+        .find(|dir| dir.join(GIT_DIR_NAME).exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn finds_work_tree_for_not_yet_created_output() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join(GIT_DIR_NAME)).unwrap();
+
+        let found = enclosing_work_tree(&repo.join("out").join("pack")).unwrap();
+        assert_eq!(found, std::path::absolute(&repo).unwrap());
+    }
+
+    #[test]
+    fn gitfile_marks_work_tree() {
+        let tmp = TempDir::new().unwrap();
+        let worktree = tmp.path().join("wt");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(
+            worktree.join(GIT_DIR_NAME),
+            "gitdir: ../main/.git/worktrees/wt",
+        )
+        .unwrap();
+
+        assert!(enclosing_work_tree(&worktree.join("pack")).is_some());
+        assert!(is_git_metadata(OsStr::new(".git")));
+        assert!(!is_git_metadata(OsStr::new(".github")));
+    }
+}
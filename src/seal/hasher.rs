@@ -0,0 +1,203 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::cli::HashAlgorithm;
+
+/// Default read buffer for streaming hashes (64 KiB).
+pub const DEFAULT_HASH_BUFFER_BYTES: usize = 64 * 1024;
+
+/// Override for the streaming buffer size, in bytes.
+pub const HASH_BUFFER_ENV: &str = "PACK_HASH_BUFFER_BYTES";
+
+/// Buffer size used by seal and verify: `PACK_HASH_BUFFER_BYTES` when it is a
+/// positive integer, otherwise [`DEFAULT_HASH_BUFFER_BYTES`].
+pub fn hash_buffer_size() -> usize {
+    crate::config::env_or_config(HASH_BUFFER_ENV)
+        .and_then(|raw| raw.trim().parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_HASH_BUFFER_BYTES)
+}
+
+/// Format a finished digest as `sha256:<hex>`.
+pub fn format_sha256(hasher: Sha256) -> String {
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
+
+/// The algorithm named by the prefix of a `<algorithm>:<hex>` hash, or `None`
+/// when the prefix is not one pack supports.
+pub fn hash_algorithm_of(hash: &str) -> Option<HashAlgorithm> {
+    match hash.split_once(':')?.0 {
+        "sha256" => Some(HashAlgorithm::Sha256),
+        "sha512" => Some(HashAlgorithm::Sha512),
+        "blake3" => Some(HashAlgorithm::Blake3),
+        _ => None,
+    }
+}
+
+/// An in-progress digest for one [`HashAlgorithm`].
+pub enum MemberHasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl MemberHasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Sha512 => Self::Sha512(Sha512::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(bytes),
+            Self::Sha512(hasher) => hasher.update(bytes),
+            Self::Blake3(hasher) => {
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    /// Finish the digest as `<algorithm>:<hex>`.
+    pub fn finalize(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format_sha256(hasher),
+            Self::Sha512(hasher) => format!("sha512:{}", hex::encode(hasher.finalize())),
+            Self::Blake3(hasher) => format!("blake3:{}", hasher.finalize().to_hex()),
+        }
+    }
+}
+
+/// Feeds written bytes to the digest, so a stream can be hashed as it is
+/// produced.
+impl Write for MemberHasher {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// One-shot `<algorithm>:<hex>` of an in-memory buffer.
+pub fn hash_bytes(bytes: &[u8], algorithm: HashAlgorithm) -> String {
+    let mut hasher = MemberHasher::new(algorithm);
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// Hash `reader` in fixed-size chunks, optionally teeing the bytes into `sink`.
+///
+/// Memory use is bounded by `buffer_size` regardless of input length. When
+/// `hash` is false the bytes are only copied and the returned hash is empty.
+pub fn stream_hash<R: Read, W: Write>(
+    mut reader: R,
+    mut sink: Option<&mut W>,
+    buffer_size: usize,
+    algorithm: HashAlgorithm,
+    hash: bool,
+) -> io::Result<(String, u64)> {
+    let mut hasher = MemberHasher::new(algorithm);
+    let mut buf = vec![0u8; buffer_size.max(1)];
+    let mut total: u64 = 0;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if hash {
+            hasher.update(&buf[..n]);
+        }
+        if let Some(sink) = sink.as_deref_mut() {
+            sink.write_all(&buf[..n])?;
+        }
+        total += n as u64;
+    }
+
+    if !hash {
+        return Ok((String::new(), total));
+    }
+    Ok((hasher.finalize(), total))
+}
+
+/// [`stream_hash`] with SHA-256.
+pub fn stream_sha256<R: Read, W: Write>(
+    reader: R,
+    sink: Option<&mut W>,
+    buffer_size: usize,
+    hash: bool,
+) -> io::Result<(String, u64)> {
+    stream_hash(reader, sink, buffer_size, HashAlgorithm::Sha256, hash)
+}
+
+/// Streaming `<algorithm>:<hex>` of a file on disk.
+pub fn hash_file(path: &Path, buffer_size: usize, algorithm: HashAlgorithm) -> io::Result<String> {
+    let file = fs::File::open(path)?;
+    stream_hash(file, None::<&mut io::Sink>, buffer_size, algorithm, true).map(|(hash, _)| hash)
+}
+
+/// Streaming `sha256:<hex>` of a file on disk.
+pub fn sha256_file(path: &Path, buffer_size: usize) -> io::Result<String> {
+    hash_file(path, buffer_size, HashAlgorithm::Sha256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn chunked_hash_matches_one_shot_digest() {
+        let bytes: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let expected = format!("sha256:{}", hex::encode(Sha256::digest(&bytes)));
+
+        for buffer_size in [1, 7, 4096, DEFAULT_HASH_BUFFER_BYTES, 1 << 20] {
+            let (hash, total) =
+                stream_sha256(bytes.as_slice(), None::<&mut io::Sink>, buffer_size, true).unwrap();
+            assert_eq!(hash, expected, "buffer_size={buffer_size}");
+            assert_eq!(total, bytes.len() as u64);
+        }
+    }
+
+    #[test]
+    fn tees_bytes_into_sink_and_hashes_files() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("member.bin");
+        fs::write(&path, b"streamed").unwrap();
+
+        let mut sink = Vec::new();
+        let (hash, _) = stream_sha256(&b"streamed"[..], Some(&mut sink), 3, true).unwrap();
+        assert_eq!(sink, b"streamed");
+        assert_eq!(sha256_file(&path, 3).unwrap(), hash);
+    }
+
+    #[test]
+    fn each_algorithm_records_its_prefix() {
+        for algorithm in [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha512,
+            HashAlgorithm::Blake3,
+        ] {
+            let (hash, _) =
+                stream_hash(&b"abc"[..], None::<&mut io::Sink>, 2, algorithm, true).unwrap();
+            assert_eq!(hash, hash_bytes(b"abc", algorithm));
+            assert_eq!(hash_algorithm_of(&hash), Some(algorithm));
+        }
+        assert_eq!(
+            hash_bytes(b"abc", HashAlgorithm::Blake3),
+            "blake3:6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert_eq!(hash_algorithm_of("md5:900150983cd24fb0"), None);
+        assert_eq!(hash_algorithm_of("deadbeef"), None);
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Default ceiling for the manifest `note` field (64 KiB).
+pub const DEFAULT_MAX_NOTE_BYTES: usize = 64 * 1024;
+
+/// Ceiling for reading a third-party `manifest.json` during verify (64 MiB).
+pub const MAX_MANIFEST_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Refuse with `E_LIMIT` when a metadata value exceeds its byte limit.
+pub fn check_field_size(
+    field: &str,
+    value: &str,
+    limit: usize,
+) -> Result<(), Box<RefusalEnvelope>> {
+    if value.len() <= limit {
+        return Ok(());
+    }
+    Err(Box::new(RefusalEnvelope::new(
+        RefusalCode::Limit,
+        Some(format!(
+            "{field} is {} bytes, exceeding the {limit}-byte limit",
+            value.len()
+        )),
+        Some(json!({
+            "field": field,
+            "limit": limit,
+            "actual": value.len(),
+        })),
+    )))
+}
+
+/// Refuse with `E_LIMIT` when `members` files totalling `total_bytes` exceed
+/// `--max-members` or `--max-total-bytes`. `source` is the input at which a
+/// collection walk stopped, when known.
+///
+/// The refusal detail holds the limit crossed, every limit set, and the
+/// observed totals, counting the member that crossed it.
+pub fn check_collection_limits(
+    max_members: Option<usize>,
+    max_total_bytes: Option<u64>,
+    members: usize,
+    total_bytes: u64,
+    source: Option<&Path>,
+) -> Result<(), Box<RefusalEnvelope>> {
+    let (exceeded, summary) = match (max_members, max_total_bytes) {
+        (Some(limit), _) if members > limit => {
+            ("max_members", format!("more than {limit} members"))
+        }
+        (_, Some(limit)) if total_bytes > limit => {
+            ("max_total_bytes", format!("more than {limit} bytes"))
+        }
+        _ => return Ok(()),
+    };
+    let message = match source {
+        Some(source) => format!("Inputs contain {summary}; stopped at {}", source.display()),
+        None => format!("Inputs contain {summary}"),
+    };
+    let mut detail = json!({
+        "exceeded": exceeded,
+        "members": members,
+        "total_bytes": total_bytes,
+    });
+    if let Some(limit) = max_members {
+        detail["max_members"] = json!(limit);
+    }
+    if let Some(limit) = max_total_bytes {
+        detail["max_total_bytes"] = json!(limit);
+    }
+    if let Some(source) = source {
+        detail["stopped_at"] = json!(source.display().to_string());
+    }
+    Err(Box::new(RefusalEnvelope::new(
+        RefusalCode::Limit,
+        Some(message),
+        Some(detail),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn within_limit_passes() {
+        assert!(check_field_size("note", "short", 5).is_ok());
+    }
+
+    #[test]
+    fn over_limit_refuses_with_detail() {
+        let err = check_field_size("note", "too long", 4).unwrap_err();
+        assert_eq!(err.refusal.code, "E_LIMIT");
+        let detail = err.refusal.detail.unwrap();
+        assert_eq!(detail["field"], "note");
+        assert_eq!(detail["limit"], 4);
+        assert_eq!(detail["actual"], 8);
+    }
+
+    #[test]
+    fn collection_limits_report_the_observed_totals() {
+        assert!(check_collection_limits(Some(2), Some(100), 2, 100, None).is_ok());
+
+        let err = check_collection_limits(Some(2), Some(100), 2, 101, Some(Path::new("big.bin")))
+            .unwrap_err();
+        assert_eq!(err.refusal.code, "E_LIMIT");
+        let detail = err.refusal.detail.unwrap();
+        assert_eq!(detail["exceeded"], "max_total_bytes");
+        assert_eq!(detail["max_members"], 2);
+        assert_eq!(detail["total_bytes"], 101);
+        assert_eq!(detail["stopped_at"], "big.bin");
+
+        let err = check_collection_limits(Some(2), None, 3, 0, None).unwrap_err();
+        assert_eq!(err.refusal.detail.unwrap()["exceeded"], "max_members");
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Read `--from-list` artifact paths from `path`, or from stdin when `path`
+/// is `-`.
+///
+/// Entries are newline-delimited (`\n` or `\r\n`), or NUL-delimited with
+/// `-0` so paths may contain newlines. Empty entries are skipped. Paths are
+/// returned as written: they resolve against the working directory and go
+/// through the same collection and collision rules as positional artifacts.
+pub fn read_artifact_list(path: &Path, nul: bool) -> Result<Vec<PathBuf>, Box<RefusalEnvelope>> {
+    let bytes = if path == Path::new("-") {
+        let mut bytes = Vec::new();
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .map(|_| bytes)
+            .map_err(|e| refusal(path, format!("Cannot read artifact list from stdin: {e}")))?
+    } else {
+        fs::read(path).map_err(|e| {
+            refusal(
+                path,
+                format!("Cannot read artifact list: {}: {e}", path.display()),
+            )
+        })?
+    };
+    let content = String::from_utf8(bytes).map_err(|_| {
+        refusal(
+            path,
+            format!("Artifact list is not UTF-8: {}", path.display()),
+        )
+    })?;
+    Ok(parse_artifact_list(&content, nul))
+}
+
+fn parse_artifact_list(content: &str, nul: bool) -> Vec<PathBuf> {
+    let entries: Vec<&str> = if nul {
+        content.split('\0').collect()
+    } else {
+        content.lines().collect()
+    };
+    entries
+        .into_iter()
+        .filter(|entry| !entry.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn refusal(path: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "from_list": path.display().to_string() })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn newline_and_nul_delimited_lists() {
+        assert_eq!(
+            parse_artifact_list("a.json\r\n\nreports/b.json\n", false),
+            [PathBuf::from("a.json"), PathBuf::from("reports/b.json")]
+        );
+        assert_eq!(
+            parse_artifact_list("odd\nname.json\0c.json\0", true),
+            [PathBuf::from("odd\nname.json"), PathBuf::from("c.json")]
+        );
+    }
+
+    #[test]
+    fn unreadable_list_refuses() {
+        let tmp = TempDir::new().unwrap();
+        let err = read_artifact_list(&tmp.path().join("missing.txt"), false).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(err.refusal.detail.unwrap()["from_list"].is_string());
+    }
+}
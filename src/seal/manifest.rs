@@ -1,18 +1,209 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::canonical::canonicalize;
+use crate::detect::DetectionRule;
+
 /// Manifest schema version.
 pub const MANIFEST_VERSION: &str = "pack.v0";
 
+/// Zero-byte placeholder member that stands for an empty directory under
+/// `seal --empty-dirs keep`.
+pub const PACKKEEP_FILE: &str = ".packkeep";
+
 /// A member descriptor within the pack manifest.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Member {
     pub path: String,
     pub bytes_hash: String,
+    /// Size of the member file in bytes, covered by `pack_id`. Verify checks
+    /// it before hashing (`SIZE_MISMATCH`). Absent in older manifests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
     #[serde(rename = "type")]
     pub member_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub artifact_version: Option<String>,
+    /// Media type detected from the member's leading bytes at seal, e.g.
+    /// `application/json`, `text/csv`, or `application/octet-stream`.
+    /// Absent in older manifests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Character set of a text member (`utf-8`, `utf-16le`, `utf-16be`);
+    /// absent for binary members and in older manifests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charset: Option<String>,
+    /// Access level set by `seal --restrict` (e.g. `auditors-only`); absent
+    /// members are unrestricted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// Upstream content-addressed source recorded by `seal --origins`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<MemberOrigin>,
+    /// The source file had an execute bit set; only ever `Some(true)`. Left
+    /// out of `pack_id` unless `provenance.executable_in_pack_id` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executable: Option<bool>,
+    /// Hash of the member file as stored, set when `seal --encrypt` replaced
+    /// the plaintext (which `bytes_hash` and `size_bytes` still describe)
+    /// with age ciphertext. Same algorithm as `bytes_hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ciphertext_hash: Option<String>,
+}
+
+impl Member {
+    /// Hash of the bytes on disk: `ciphertext_hash` for an encrypted member,
+    /// otherwise `bytes_hash`. Stores address member blobs by it.
+    pub fn stored_hash(&self) -> &str {
+        self.ciphertext_hash.as_deref().unwrap_or(&self.bytes_hash)
+    }
+}
+
+/// OCI-style descriptor linking a member to the registry content it came from.
+///
+/// `digest` addresses the upstream blob, which may differ from `bytes_hash`
+/// (for example a compressed layer that was unpacked before sealing).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MemberOrigin {
+    /// Registry or repository reference, e.g. `ghcr.io/acme/models`.
+    pub registry: String,
+    /// `<algorithm>:<encoded>` digest of the upstream content.
+    pub digest: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+}
+
+/// How the pack was assembled, beyond what each member records.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Provenance {
+    /// Empty input directories recorded by `seal --empty-dirs list`, as
+    /// member-style paths. They have no members and are not materialized in
+    /// the pack.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub empty_dirs: Vec<String>,
+    /// Member path → RFC 3339 time the member finished copying and hashing,
+    /// recorded by `seal --member-timestamps`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sealed_at: BTreeMap<String, String>,
+    /// Member `executable` flags are covered by `pack_id`, set by
+    /// `seal --hash-executable`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub executable_in_pack_id: bool,
+    /// Where the members were sealed from, recorded by `seal --provenance`.
+    /// It describes the sealing machine, so it is covered by `pack_id` and
+    /// the same bytes sealed elsewhere get a different `pack_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sources: Option<SourceProvenance>,
+    /// Custom type detection rules loaded by `seal --detect-rules`, in the
+    /// order they were tried, so member types can be reproduced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub detection_rules: Vec<DetectionRule>,
+    /// How `seal --reproducible` kept `pack_id` independent of when the pack
+    /// was sealed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reproducible: Option<ReproducibleMode>,
+    /// Member paths of symlinks left out by `seal --skip-symlinks`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_symlinks: Vec<String>,
+    /// How many member hashes `seal --trust-hashes` took from a hashes file
+    /// rather than computing them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust_hashes: Option<TrustHashes>,
+    /// Member path → the URL a member was downloaded from, recorded for
+    /// every URL artifact. Covered by `pack_id`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub remote: BTreeMap<String, RemoteSource>,
+}
+
+impl Provenance {
+    pub fn is_empty(&self) -> bool {
+        self.empty_dirs.is_empty()
+            && self.sealed_at.is_empty()
+            && !self.executable_in_pack_id
+            && self.sources.is_none()
+            && self.detection_rules.is_empty()
+            && self.reproducible.is_none()
+            && self.skipped_symlinks.is_empty()
+            && self.trust_hashes.is_none()
+            && self.remote.is_empty()
+    }
+}
+
+/// How a `seal --reproducible` pack keeps `created` from changing `pack_id`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReproducibleMode {
+    /// `created` was pinned to `SOURCE_DATE_EPOCH`.
+    SourceDateEpoch,
+    /// `created` is the seal time and is left out of `pack_id`.
+    CreatedExcluded,
+}
+
+impl ReproducibleMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SourceDateEpoch => "source_date_epoch",
+            Self::CreatedExcluded => "created_excluded",
+        }
+    }
+}
+
+/// Member hashes taken from a `seal --trust-hashes` file: `trusted` members
+/// use the file's hash unchecked, and `sampled` members were re-hashed and
+/// matched it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrustHashes {
+    pub mode: TrustHashesMode,
+    pub trusted: usize,
+    pub sampled: usize,
+}
+
+/// How `seal --trust-hashes` checked the hashes file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustHashesMode {
+    /// A random sample of trusted members was re-hashed.
+    Sampled,
+}
+
+impl TrustHashesMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sampled => "sampled",
+        }
+    }
+}
+
+/// The machine, user, and source files a pack was sealed from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SourceProvenance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Member path → the source it was copied from.
+    pub members: BTreeMap<String, MemberSource>,
+}
+
+/// One member's source: an absolute path, a URL, or `-` for stdin.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MemberSource {
+    pub source: String,
+    /// RFC 3339 UTC modification time of a local source file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<String>,
+}
+
+/// Where a URL artifact's bytes came from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemoteSource {
+    /// URL as given on the command line.
+    pub url: String,
+    /// URL the bytes were served from, after redirects.
+    pub final_url: String,
 }
 
 /// The pack.v0 manifest.
@@ -26,6 +217,36 @@ pub struct Manifest {
     pub tool_version: String,
     pub members: Vec<Member>,
     pub member_count: usize,
+    /// Member path → reviewer label recorded by `seal --annotate`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Recipients the members were encrypted to by `seal --encrypt`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<Encryption>,
+    /// Packs this one was built from, recorded by `seal --merge`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<Lineage>,
+}
+
+/// How the members of an encrypted pack were encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Encryption {
+    /// Always `age-x25519`: each member file is an age v1 file.
+    pub scheme: String,
+    /// The `age1...` recipients any one of whose identities decrypts every
+    /// member.
+    pub recipients: Vec<String>,
+}
+
+/// Where a pack's members came from when it was built from other packs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Lineage {
+    /// `pack_id`s of the packs whose members were carried into this one.
+    pub parent_pack_ids: Vec<String>,
 }
 
 impl Manifest {
@@ -45,18 +266,22 @@ impl Manifest {
             tool_version,
             members,
             member_count,
+            annotations: BTreeMap::new(),
+            provenance: None,
+            encryption: None,
+            lineage: None,
         }
     }
 
     /// Compute and set the deterministic `pack_id` via the self-hash contract:
     ///
     /// 1. Serialize manifest with `pack_id: ""`
-    /// 2. Canonical JSON (serde_json with sorted keys via `to_string`)
+    /// 2. RFC 8785 canonical JSON via [`crate::canonical::canonicalize`]
     /// 3. SHA256 over canonical bytes
     /// 4. Set `pack_id` to `sha256:<hex>`
     pub fn finalize(&mut self) {
         self.pack_id = String::new();
-        let canonical = canonical_json(self);
+        let canonical = pack_id_input(self);
         let hash = sha256_hex(canonical.as_bytes());
         self.pack_id = format!("sha256:{hash}");
     }
@@ -65,7 +290,7 @@ impl Manifest {
     pub fn recompute_pack_id(&self) -> String {
         let mut copy = self.clone();
         copy.pack_id = String::new();
-        let canonical = canonical_json(&copy);
+        let canonical = pack_id_input(&copy);
         let hash = sha256_hex(canonical.as_bytes());
         format!("sha256:{hash}")
     }
@@ -76,40 +301,34 @@ impl Manifest {
     }
 }
 
-/// Produce canonical JSON: deterministic key ordering via serde_json::Value
-/// round-trip, then serialize with sorted maps.
+/// The manifest in RFC 8785 canonical JSON, the same form `pack_id` hashes.
 fn canonical_json(manifest: &Manifest) -> String {
-    // serde_json::to_value + to_string produces deterministic output because
-    // serde_json preserves insertion order from the struct derive order.
-    // For true canonical form we round-trip through Value to ensure stability.
     let value = serde_json::to_value(manifest).expect("manifest serialization cannot fail");
-    sorted_json(&value)
+    canonicalize(&value)
 }
 
-/// Recursively serialize a serde_json::Value with sorted object keys.
-fn sorted_json(value: &serde_json::Value) -> String {
-    match value {
-        serde_json::Value::Object(map) => {
-            let mut keys: Vec<&String> = map.keys().collect();
-            keys.sort();
-            let entries: Vec<String> = keys
-                .iter()
-                .map(|k| {
-                    format!(
-                        "{}:{}",
-                        serde_json::to_string(k).unwrap(),
-                        sorted_json(&map[*k])
-                    )
-                })
-                .collect();
-            format!("{{{}}}", entries.join(","))
+/// The canonical JSON `pack_id` hashes: the manifest without member
+/// `executable` flags, unless the pack opted into hashing them, and without
+/// `created` when the pack was sealed with it excluded.
+fn pack_id_input(manifest: &Manifest) -> String {
+    let mut value = serde_json::to_value(manifest).expect("manifest serialization cannot fail");
+    let provenance = manifest.provenance.as_ref();
+    let hash_executable = provenance.is_some_and(|provenance| provenance.executable_in_pack_id);
+    if provenance.and_then(|provenance| provenance.reproducible)
+        == Some(ReproducibleMode::CreatedExcluded)
+    {
+        if let Some(object) = value.as_object_mut() {
+            object.remove("created");
         }
-        serde_json::Value::Array(arr) => {
-            let entries: Vec<String> = arr.iter().map(sorted_json).collect();
-            format!("[{}]", entries.join(","))
+    }
+    if !hash_executable {
+        if let Some(members) = value["members"].as_array_mut() {
+            for member in members.iter_mut().filter_map(|m| m.as_object_mut()) {
+                member.remove("executable");
+            }
         }
-        _ => serde_json::to_string(value).unwrap(),
     }
+    canonicalize(&value)
 }
 
 fn sha256_hex(data: &[u8]) -> String {
@@ -128,14 +347,28 @@ mod tests {
             Member {
                 path: "a.json".to_string(),
                 bytes_hash: "sha256:aaaa".to_string(),
+                size_bytes: None,
                 member_type: "report".to_string(),
                 artifact_version: Some("rvl.v0".to_string()),
+                visibility: None,
+                origin: None,
+                executable: None,
+                ciphertext_hash: None,
+                content_type: None,
+                charset: None,
             },
             Member {
                 path: "b.lock.json".to_string(),
                 bytes_hash: "sha256:bbbb".to_string(),
+                size_bytes: None,
                 member_type: "lockfile".to_string(),
                 artifact_version: Some("lock.v0".to_string()),
+                visibility: None,
+                origin: None,
+                executable: None,
+                ciphertext_hash: None,
+                content_type: None,
+                charset: None,
             },
         ]
     }
@@ -198,6 +431,27 @@ mod tests {
         assert_eq!(m.pack_id, recomputed);
     }
 
+    #[test]
+    fn created_excluded_pack_id_ignores_created() {
+        let manifest = |created: &str| {
+            let mut m = Manifest::new(
+                created.to_string(),
+                None,
+                "0.1.0".to_string(),
+                sample_members(),
+            );
+            m.provenance = Some(Provenance {
+                reproducible: Some(ReproducibleMode::CreatedExcluded),
+                ..Provenance::default()
+            });
+            m.finalize();
+            m
+        };
+        let first = manifest("2026-01-15T10:30:00Z");
+        assert_eq!(first.pack_id, manifest("2026-03-01T08:00:00Z").pack_id);
+        assert_eq!(first.pack_id, first.recompute_pack_id());
+    }
+
     #[test]
     fn pack_id_changes_with_note() {
         let mut m1 = Manifest::new(
@@ -313,4 +567,38 @@ mod tests {
         let b2 = m.to_canonical_bytes();
         assert_eq!(b1, b2);
     }
+
+    #[test]
+    fn executable_flags_enter_pack_id_only_when_opted_in() {
+        let new = || {
+            Manifest::new(
+                "2026-01-15T10:30:00Z".to_string(),
+                None,
+                "0.1.0".to_string(),
+                sample_members(),
+            )
+        };
+        let mut plain = new();
+        plain.finalize();
+
+        let mut flagged = new();
+        flagged.members[0].executable = Some(true);
+        flagged.finalize();
+        assert_eq!(flagged.pack_id, plain.pack_id);
+        assert!(String::from_utf8(flagged.to_canonical_bytes())
+            .unwrap()
+            .contains(r#""executable":true"#));
+
+        let strict = |executable| {
+            let mut m = new();
+            m.members[0].executable = executable;
+            m.provenance = Some(Provenance {
+                executable_in_pack_id: true,
+                ..Provenance::default()
+            });
+            m.finalize();
+            m.pack_id
+        };
+        assert_ne!(strict(Some(true)), strict(None));
+    }
 }
@@ -0,0 +1,238 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+use tempfile::TempDir;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::archive::{is_archive, unpack_archive};
+use crate::seal::collect::MemberCandidate;
+use crate::seal::finalize::SealAnnotations;
+use crate::seal::manifest::{Lineage, Manifest};
+use crate::verify::{read_manifest, verify_pack, VerifyOptions, VerifyOutcome};
+
+/// An existing pack whose members `seal --merge` carries into the new pack.
+pub(crate) struct ParentPack {
+    dir: PathBuf,
+    manifest: Manifest,
+    /// Scratch directory an archive parent was unpacked into, kept until the
+    /// members have been copied.
+    _unpacked: Option<TempDir>,
+}
+
+impl ParentPack {
+    /// Open the pack directory or `.pack.tar` at `path` and verify it.
+    ///
+    /// A parent that does not verify is refused with `E_BAD_PACK`: its
+    /// members would be hashed afresh into the new pack, hiding the damage.
+    /// An encrypted parent is refused with `E_IO`, since its member files are
+    /// ciphertext that would be sealed as plaintext.
+    pub(crate) fn open(path: &Path) -> Result<Self, Box<RefusalEnvelope>> {
+        let refuse = |code: RefusalCode, message: String, detail: serde_json::Value| {
+            Box::new(RefusalEnvelope::new(code, Some(message), Some(detail)))
+        };
+        let unpacked = if is_archive(path) {
+            let scratch = tempfile::tempdir().map_err(|e| {
+                refuse(
+                    RefusalCode::Io,
+                    format!("Cannot create scratch directory: {e}"),
+                    json!({ "merge": path.display().to_string() }),
+                )
+            })?;
+            unpack_archive(path, scratch.path())?;
+            Some(scratch)
+        } else {
+            None
+        };
+        let dir = unpacked.as_ref().map_or_else(
+            || path.to_path_buf(),
+            |scratch| scratch.path().to_path_buf(),
+        );
+
+        let report = verify_pack(&dir, &VerifyOptions::default());
+        match report.outcome {
+            VerifyOutcome::OK => {}
+            VerifyOutcome::INVALID => {
+                let findings: Vec<_> = report
+                    .invalid
+                    .iter()
+                    .map(|f| json!({ "code": f.code, "path": f.path }))
+                    .collect();
+                return Err(refuse(
+                    RefusalCode::BadPack,
+                    format!("Cannot merge {}: pack does not verify", path.display()),
+                    json!({ "merge": path.display().to_string(), "invalid": findings }),
+                ));
+            }
+            VerifyOutcome::REFUSAL => {
+                let reason = report.refusal.unwrap_or_default();
+                return Err(refuse(
+                    RefusalCode::BadPack,
+                    format!(
+                        "Cannot merge {}: {}",
+                        path.display(),
+                        reason["message"]
+                            .as_str()
+                            .unwrap_or("verify refused the pack")
+                    ),
+                    json!({ "merge": path.display().to_string(), "refusal": reason }),
+                ));
+            }
+        }
+
+        let manifest = read_manifest(&dir)?;
+        if manifest.encryption.is_some() {
+            return Err(refuse(
+                RefusalCode::Io,
+                format!("Cannot merge {}: its members are encrypted", path.display()),
+                json!({ "merge": path.display().to_string(), "pack_id": manifest.pack_id }),
+            ));
+        }
+        Ok(ParentPack {
+            dir,
+            manifest,
+            _unpacked: unpacked,
+        })
+    }
+
+    pub(crate) fn pack_id(&self) -> &str {
+        &self.manifest.pack_id
+    }
+
+    /// Add the parent's members to `candidates`, keeping them in path order.
+    ///
+    /// A new artifact that resolves to a path the parent already holds is an
+    /// `E_DUPLICATE` refusal listing every such path: a merge adds members,
+    /// it does not replace them.
+    pub(crate) fn add_members(
+        &self,
+        candidates: &mut Vec<MemberCandidate>,
+    ) -> Result<(), Box<RefusalEnvelope>> {
+        let conflicts: Vec<_> = candidates
+            .iter()
+            .filter(|candidate| {
+                self.manifest
+                    .members
+                    .iter()
+                    .any(|member| member.path == candidate.member_path)
+            })
+            .map(|candidate| {
+                json!({
+                    "path": candidate.member_path,
+                    "source": candidate.source.display().to_string(),
+                })
+            })
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Duplicate,
+                Some(format!(
+                    "{} artifact(s) resolve to member paths already in the merged pack",
+                    conflicts.len()
+                )),
+                Some(json!({
+                    "parent_pack_id": self.manifest.pack_id,
+                    "conflicts": conflicts,
+                })),
+            )));
+        }
+        candidates.extend(self.manifest.members.iter().map(|member| MemberCandidate {
+            source: self.dir.join(&member.path),
+            member_path: member.path.clone(),
+        }));
+        candidates.sort_by(|a, b| a.member_path.cmp(&b.member_path));
+        Ok(())
+    }
+
+    /// Keep the visibility, origin, and annotation the parent recorded for
+    /// its members, except where this seal records its own.
+    pub(crate) fn carry_annotations(&self, annotations: &mut SealAnnotations) {
+        for member in &self.manifest.members {
+            if let Some(level) = &member.visibility {
+                annotations
+                    .visibility
+                    .entry(member.path.clone())
+                    .or_insert_with(|| level.clone());
+            }
+            if let Some(origin) = &member.origin {
+                annotations
+                    .origins
+                    .entry(member.path.clone())
+                    .or_insert_with(|| origin.clone());
+            }
+        }
+        for (path, label) in &self.manifest.annotations {
+            annotations
+                .member_annotations
+                .entry(path.clone())
+                .or_insert_with(|| label.clone());
+        }
+    }
+
+    /// The new pack's lineage: the parent's `pack_id`.
+    pub(crate) fn lineage(&self) -> Lineage {
+        Lineage {
+            parent_pack_ids: vec![self.manifest.pack_id.clone()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::{execute_seal, execute_seal_with_options, SealOptions};
+    use std::fs;
+
+    #[test]
+    fn merge_carries_parent_members_and_records_lineage() {
+        let tmp = TempDir::new().unwrap();
+        let jan = tmp.path().join("jan.json");
+        let feb = tmp.path().join("feb.json");
+        fs::write(&jan, "1").unwrap();
+        fs::write(&feb, "2").unwrap();
+        let parent = execute_seal(
+            std::slice::from_ref(&jan),
+            Some(&tmp.path().join("q1")),
+            None,
+        )
+        .unwrap();
+
+        let options = SealOptions {
+            merge: Some(parent.output_dir.clone()),
+            ..SealOptions::default()
+        };
+        let merged =
+            execute_seal_with_options(&[feb], Some(&tmp.path().join("q1-late")), None, &options)
+                .unwrap();
+        assert_eq!(merged.member_count, 2);
+        assert_eq!(
+            merged.parent_pack_id.as_deref(),
+            Some(parent.pack_id.as_str())
+        );
+        let manifest = read_manifest(&merged.output_dir).unwrap();
+        assert_eq!(
+            manifest.lineage.unwrap().parent_pack_ids,
+            vec![parent.pack_id.clone()]
+        );
+        let report = verify_pack(&merged.output_dir, &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+
+        // jan.json is already in the parent.
+        let err =
+            execute_seal_with_options(&[jan], Some(&tmp.path().join("conflict")), None, &options)
+                .unwrap_err();
+        assert_eq!(err.refusal.code, "E_DUPLICATE");
+        assert!(!tmp.path().join("conflict").exists());
+    }
+
+    #[test]
+    fn tampered_parent_is_refused() {
+        let tmp = TempDir::new().unwrap();
+        let jan = tmp.path().join("jan.json");
+        fs::write(&jan, "1").unwrap();
+        let parent = execute_seal(&[jan], Some(&tmp.path().join("q1")), None).unwrap();
+        fs::write(parent.output_dir.join("jan.json"), "one").unwrap();
+
+        let err = ParentPack::open(&parent.output_dir).err().unwrap();
+        assert_eq!(err.refusal.code, "E_BAD_PACK");
+    }
+}
@@ -1,6 +1,23 @@
+pub mod annotate;
+pub mod archive;
 pub mod collect;
 pub mod collision;
 pub mod command;
 pub mod copy;
 pub mod finalize;
+pub mod git;
+pub mod hasher;
+pub mod limits;
+pub mod list;
 pub mod manifest;
+pub mod merge;
+pub mod origin;
+pub mod remote;
+pub mod report;
+pub mod resume;
+pub mod sniff;
+pub mod stdin;
+pub mod transaction;
+pub mod trust;
+pub mod visibility;
+pub mod zip;
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::collect::MemberCandidate;
+use crate::seal::manifest::MemberOrigin;
+
+/// Load a `--origins` file and resolve it against the collected members,
+/// returning member path → origin.
+///
+/// The file maps member paths to descriptors:
+/// `{"<member path>": {"registry": "...", "digest": "sha256:...", "media_type": "..."}}`.
+/// A key that names no member refuses with `E_IO`, so a typo cannot silently
+/// drop provenance.
+pub fn load_origins(
+    path: &Path,
+    candidates: &[MemberCandidate],
+) -> Result<BTreeMap<String, MemberOrigin>, Box<RefusalEnvelope>> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        refusal(
+            path,
+            format!("Cannot read origins file: {}: {e}", path.display()),
+        )
+    })?;
+    let origins: BTreeMap<String, MemberOrigin> = serde_json::from_str(&content).map_err(|e| {
+        refusal(
+            path,
+            format!("Invalid origins file: {}: {e}", path.display()),
+        )
+    })?;
+
+    for (member, origin) in &origins {
+        if !candidates.iter().any(|c| &c.member_path == member) {
+            return Err(refusal(
+                path,
+                format!("Origin for {member} matches no member"),
+            ));
+        }
+        if origin.registry.is_empty() {
+            return Err(refusal(
+                path,
+                format!("Origin for {member} has an empty registry"),
+            ));
+        }
+        if !is_digest(&origin.digest) {
+            return Err(refusal(
+                path,
+                format!(
+                    "Invalid origin digest for {member}: expected <algorithm>:<encoded>, got {}",
+                    origin.digest
+                ),
+            ));
+        }
+    }
+    Ok(origins)
+}
+
+/// OCI digest grammar: `algorithm ":" encoded`, where the algorithm is
+/// lowercase alphanumeric components joined by `+._-` and the encoding is
+/// `[A-Za-z0-9=_-]+`.
+fn is_digest(digest: &str) -> bool {
+    let Some((algorithm, encoded)) = digest.split_once(':') else {
+        return false;
+    };
+    let algorithm_ok = algorithm.split(['+', '.', '_', '-']).all(|part| {
+        !part.is_empty()
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    });
+    let encoded_ok = !encoded.is_empty()
+        && encoded
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"=_-".contains(&b));
+    algorithm_ok && encoded_ok
+}
+
+fn refusal(path: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "origins": path.display().to_string() })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn candidates(paths: &[&str]) -> Vec<MemberCandidate> {
+        paths
+            .iter()
+            .map(|path| MemberCandidate {
+                source: (*path).into(),
+                member_path: path.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loads_descriptors_for_known_members() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("origins.json");
+        fs::write(
+            &file,
+            r#"{"model.bin": {"registry": "ghcr.io/acme/models", "digest": "sha256:abc123", "media_type": "application/vnd.oci.image.layer.v1.tar"}}"#,
+        )
+        .unwrap();
+
+        let origins = load_origins(&file, &candidates(&["model.bin", "rules.json"])).unwrap();
+        assert_eq!(origins["model.bin"].registry, "ghcr.io/acme/models");
+        assert_eq!(
+            origins["model.bin"].media_type.as_deref(),
+            Some("application/vnd.oci.image.layer.v1.tar")
+        );
+        assert!(!origins.contains_key("rules.json"));
+    }
+
+    #[test]
+    fn unknown_members_and_bad_digests_refuse() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("origins.json");
+        let members = candidates(&["model.bin"]);
+
+        fs::write(
+            &file,
+            r#"{"nope.bin": {"registry": "r", "digest": "sha256:ab"}}"#,
+        )
+        .unwrap();
+        let err = load_origins(&file, &members).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(err.refusal.message.contains("matches no member"));
+
+        fs::write(
+            &file,
+            r#"{"model.bin": {"registry": "r", "digest": "abc"}}"#,
+        )
+        .unwrap();
+        assert!(load_origins(&file, &members).is_err());
+    }
+
+    #[test]
+    fn digest_grammar() {
+        assert!(is_digest(
+            "sha256:6c3c624b58dbbcd3c0dd82b4c53f04194d1247c6eebdaab7c610cf7d66709b3b"
+        ));
+        assert!(is_digest(
+            "multihash+base58:QmRZxt2b1FVZPNqd8hsiykDL3TdBDeTSPX9Kv46HmX4Gx8"
+        ));
+        assert!(!is_digest("sha256:"));
+        assert!(!is_digest("SHA256:abc"));
+        assert!(!is_digest("sha256"));
+    }
+}
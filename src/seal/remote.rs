@@ -0,0 +1,323 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Default ceiling for a single remote artifact (1 GiB).
+pub const DEFAULT_MAX_REMOTE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Redirect hops followed before giving up.
+const MAX_REDIRECTS: usize = 5;
+
+/// How remote (`http://` / `https://`) artifacts are fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteOptions {
+    pub max_bytes: u64,
+    /// Follow redirects that land on a different host.
+    pub allow_cross_host_redirects: bool,
+}
+
+impl Default for RemoteOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_REMOTE_BYTES,
+            allow_cross_host_redirects: false,
+        }
+    }
+}
+
+/// A remote artifact downloaded to local staging.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteArtifact {
+    /// URL as given on the command line.
+    pub url: String,
+    /// URL the bytes were finally served from, after redirects.
+    pub final_url: String,
+    /// Downloaded file, named after the last URL path segment.
+    pub local: PathBuf,
+    pub bytes: u64,
+}
+
+/// True when an artifact argument names a remote source rather than a local path.
+pub fn is_remote(arg: &Path) -> bool {
+    arg.to_str().is_some_and(|s| {
+        ["http://", "https://", "s3://"]
+            .iter()
+            .any(|scheme| s.starts_with(scheme))
+    })
+}
+
+/// Download `url` into `dest_dir`, enforcing the size limit and redirect policy.
+pub fn fetch_remote(
+    url: &str,
+    dest_dir: &Path,
+    options: &RemoteOptions,
+) -> Result<RemoteArtifact, Box<RefusalEnvelope>> {
+    if url.starts_with("s3://") {
+        return Err(refusal(
+            url,
+            "s3:// inputs are not fetched directly; pass a presigned https:// URL".to_string(),
+        ));
+    }
+    let name = url_file_name(url)
+        .ok_or_else(|| refusal(url, "Cannot determine a member name from URL".to_string()))?;
+
+    let agent = ureq::AgentBuilder::new()
+        .redirects(0)
+        .timeout_connect(Duration::from_secs(30))
+        .timeout_read(Duration::from_secs(300))
+        .build();
+
+    let origin_host = url_host(url);
+    let mut current = url.to_string();
+    let mut hops = 0;
+    let response = loop {
+        let response = match agent.get(&current).call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, _)) => {
+                return Err(refusal(url, format!("HTTP {status} fetching {current}")));
+            }
+            Err(ureq::Error::Transport(e)) => {
+                return Err(refusal(url, format!("Cannot fetch {current}: {e}")));
+            }
+        };
+        if !(300..400).contains(&response.status()) {
+            break response;
+        }
+        hops += 1;
+        if hops > MAX_REDIRECTS {
+            return Err(refusal(url, format!("More than {MAX_REDIRECTS} redirects")));
+        }
+        let location = response
+            .header("Location")
+            .ok_or_else(|| refusal(url, format!("Redirect without Location from {current}")))?;
+        let next = resolve_location(&current, location);
+        if url_host(&next) != origin_host && !options.allow_cross_host_redirects {
+            return Err(refusal(
+                url,
+                format!(
+                    "Redirect to a different host refused: {next} (use --allow-cross-host-redirects)"
+                ),
+            ));
+        }
+        current = next;
+    };
+
+    if let Some(length) = response
+        .header("Content-Length")
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        if length > options.max_bytes {
+            return Err(limit_refusal(url, options.max_bytes, length));
+        }
+    }
+
+    fs::create_dir_all(dest_dir)
+        .map_err(|e| refusal(url, format!("Cannot stage download: {e}")))?;
+    let local = dest_dir.join(&name);
+    let mut file = fs::File::create(&local)
+        .map_err(|e| refusal(url, format!("Cannot stage download: {e}")))?;
+    let mut reader = response.into_reader().take(options.max_bytes + 1);
+    let bytes = io::copy(&mut reader, &mut file)
+        .and_then(|n| file.flush().map(|()| n))
+        .map_err(|e| refusal(url, format!("Cannot download {current}: {e}")))?;
+    if bytes > options.max_bytes {
+        return Err(limit_refusal(url, options.max_bytes, bytes));
+    }
+
+    Ok(RemoteArtifact {
+        url: url.to_string(),
+        final_url: current,
+        local,
+        bytes,
+    })
+}
+
+/// Host (with port) of an absolute URL, ignoring userinfo.
+fn url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://")?.1;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+fn resolve_location(current: &str, location: &str) -> String {
+    if location.contains("://") {
+        return location.to_string();
+    }
+    let (scheme, rest) = current.split_once("://").unwrap_or(("http", current));
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    if let Some(path) = location.strip_prefix("//") {
+        format!("{scheme}://{path}")
+    } else if location.starts_with('/') {
+        format!("{scheme}://{authority}{location}")
+    } else {
+        let base = current
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(current)
+            .rsplit_once('/')
+            .map_or(current, |(dir, _)| dir);
+        format!("{base}/{location}")
+    }
+}
+
+fn url_file_name(url: &str) -> Option<String> {
+    let rest = url.split_once("://")?.1;
+    let path = rest.split(['?', '#']).next()?;
+    let (_, path) = path.split_once('/')?;
+    let name = path.rsplit('/').next()?;
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+fn refusal(url: &str, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "url": url })),
+    ))
+}
+
+fn limit_refusal(url: &str, limit: u64, actual: u64) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Limit,
+        Some(format!(
+            "Remote artifact exceeds the {limit}-byte limit: {url}"
+        )),
+        Some(json!({
+            "url": url,
+            "limit": limit,
+            "actual": actual,
+        })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tempfile::TempDir;
+    use tiny_http::{Header, Response, Server, StatusCode};
+
+    /// Serve `responses` in order: (status, Location header, body).
+    fn serve(
+        responses: Vec<(u16, Option<String>, &'static str)>,
+    ) -> (String, thread::JoinHandle<()>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let handle = thread::spawn(move || {
+            for (status, location, body) in responses {
+                let request = server.recv().unwrap();
+                let mut response = Response::from_string(body).with_status_code(StatusCode(status));
+                if let Some(location) = location {
+                    response.add_header(Header::from_bytes("Location", location).unwrap());
+                }
+                request.respond(response).unwrap();
+            }
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_same_host_redirect_and_names_by_path() {
+        let (base, handle) = serve(vec![
+            (302, Some("/v2/rules.json?sig=abc".to_string()), ""),
+            (200, None, r#"{"rules":[]}"#),
+        ]);
+        let tmp = TempDir::new().unwrap();
+        let url = format!("{base}/v1/rules.json");
+
+        let fetched = fetch_remote(&url, tmp.path(), &RemoteOptions::default()).unwrap();
+        handle.join().unwrap();
+
+        assert_eq!(fetched.local, tmp.path().join("rules.json"));
+        assert_eq!(fetched.final_url, format!("{base}/v2/rules.json?sig=abc"));
+        assert_eq!(fetched.bytes, 12);
+        assert_eq!(
+            fs::read_to_string(&fetched.local).unwrap(),
+            r#"{"rules":[]}"#
+        );
+    }
+
+    #[test]
+    fn sealed_url_is_recorded_in_provenance() {
+        let (base, handle) = serve(vec![
+            (302, Some("/v2/rules.json".to_string()), ""),
+            (200, None, r#"{"rules":[]}"#),
+        ]);
+        let tmp = TempDir::new().unwrap();
+        let url = format!("{base}/v1/rules.json");
+
+        let sealed = crate::seal::command::execute_seal(
+            &[PathBuf::from(&url)],
+            Some(&tmp.path().join("pack")),
+            None,
+        )
+        .unwrap();
+        handle.join().unwrap();
+
+        let manifest: crate::seal::manifest::Manifest =
+            serde_json::from_slice(&fs::read(sealed.output_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        let source = &manifest.provenance.unwrap().remote["rules.json"];
+        assert_eq!(source.url, url);
+        assert_eq!(source.final_url, format!("{base}/v2/rules.json"));
+    }
+
+    #[test]
+    fn cross_host_redirect_refuses_unless_allowed() {
+        let (base, handle) = serve(vec![(
+            301,
+            Some("http://elsewhere.invalid/rules.json".to_string()),
+            "",
+        )]);
+        let tmp = TempDir::new().unwrap();
+
+        let err = fetch_remote(
+            &format!("{base}/rules.json"),
+            tmp.path(),
+            &RemoteOptions::default(),
+        )
+        .unwrap_err();
+        handle.join().unwrap();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(err.refusal.message.contains("different host"));
+    }
+
+    #[test]
+    fn oversized_download_refuses_with_limit() {
+        let (base, handle) = serve(vec![(200, None, "0123456789")]);
+        let tmp = TempDir::new().unwrap();
+        let options = RemoteOptions {
+            max_bytes: 4,
+            ..RemoteOptions::default()
+        };
+
+        let err = fetch_remote(&format!("{base}/big.bin"), tmp.path(), &options).unwrap_err();
+        handle.join().unwrap();
+        assert_eq!(err.refusal.code, "E_LIMIT");
+    }
+
+    #[test]
+    fn url_helpers() {
+        assert!(is_remote(Path::new("https://example.com/a.json")));
+        assert!(!is_remote(Path::new("fixtures/a.json")));
+        assert_eq!(
+            url_file_name("https://h/x/y.json?z=1").as_deref(),
+            Some("y.json")
+        );
+        assert_eq!(url_file_name("https://h/"), None);
+        assert_eq!(
+            url_host("https://u:p@Host:8443/a").as_deref(),
+            Some("host:8443")
+        );
+        assert_eq!(
+            resolve_location("https://h/a/b.json", "c.json"),
+            "https://h/a/c.json"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+
+use crate::seal::command::SealResult;
+
+/// Version tag for `pack seal --json` output.
+pub const SEAL_REPORT_VERSION: &str = "pack.seal.v0";
+
+/// A successful seal, as printed by `pack seal --json`.
+///
+/// Failures print the standard refusal envelope instead, so callers parse
+/// JSON either way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SealReport {
+    pub version: String,
+    pub outcome: String,
+    pub pack_id: String,
+    /// Pack directory, or the `.pack.tar` file with `--archive`.
+    pub output: String,
+    pub member_count: usize,
+    /// With `--resume`: members taken from the checkpoint instead of copied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resumed: Option<usize>,
+    /// With `--merge`: `pack_id` of the pack whose members were carried in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_pack_id: Option<String>,
+    /// Hex public key of the `--sign` signer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// `genTime` of the `--timestamp-url` token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamped_at: Option<String>,
+    /// Present when `--trust-hashes` was used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trust: Option<SealTrust>,
+    /// `.git` metadata skipped during collection.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub excluded_git: Vec<String>,
+    /// Symlinks left out by `--skip-symlinks`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_symlinks: Vec<String>,
+    /// Git work tree enclosing the output, where the pack could be committed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_work_tree: Option<String>,
+}
+
+/// How many members took a trusted hash versus were re-hashed as a sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SealTrust {
+    pub trusted: usize,
+    pub sampled: usize,
+}
+
+impl SealReport {
+    pub fn new(result: &SealResult) -> Self {
+        Self {
+            version: SEAL_REPORT_VERSION.to_string(),
+            outcome: "PACK_CREATED".to_string(),
+            pack_id: result.pack_id.clone(),
+            output: result.output_dir.display().to_string(),
+            member_count: result.member_count,
+            resumed: result.resumed,
+            parent_pack_id: result.parent_pack_id.clone(),
+            signer: result.signer.clone(),
+            timestamped_at: result
+                .timestamp
+                .as_ref()
+                .map(|(_, gen_time)| gen_time.clone()),
+            trust: result.trust.map(|trust| SealTrust {
+                trusted: trust.trusted,
+                sampled: trust.sampled,
+            }),
+            excluded_git: result
+                .excluded_git
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+            skipped_symlinks: result
+                .skipped_symlinks
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+            output_work_tree: result
+                .output_work_tree
+                .as_ref()
+                .map(|path| path.display().to_string()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("seal report serialization cannot fail")
+    }
+
+    /// The plain-text form: `PACK_CREATED <pack_id>` and the output path.
+    pub fn to_human(&self) -> String {
+        format!("PACK_CREATED {}\n{}", self.pack_id, self.output)
+    }
+}
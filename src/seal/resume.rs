@@ -0,0 +1,270 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::collect::MemberCandidate;
+use super::copy::CopiedMember;
+use super::hasher::{hash_buffer_size, stream_hash};
+use crate::cli::HashAlgorithm;
+
+/// Checkpoint log inside a `--resume` directory, beside `staging/`.
+pub const CHECKPOINT_FILE: &str = "checkpoint.jsonl";
+/// Version tag on the checkpoint log's first line.
+pub const CHECKPOINT_VERSION: &str = "pack.checkpoint.v0";
+/// Subdirectory of a `--resume` directory that members are copied into.
+pub const STAGING_SUBDIR: &str = "staging";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckpointHeader {
+    version: String,
+    /// The `created` time of the first attempt, reused so a resumed seal
+    /// produces the same manifest as an uninterrupted one.
+    created: String,
+    hash_algorithm: String,
+}
+
+/// One member that was copied and hashed by an earlier attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CheckpointEntry {
+    member_path: String,
+    source: String,
+    source_size: u64,
+    source_mtime: Option<String>,
+    bytes_hash: String,
+    size: u64,
+    sealed_at: String,
+    executable: bool,
+}
+
+/// The checkpoint log of a `seal --resume` directory: members already in
+/// `staging/`, appended to as each new member is copied.
+pub struct Checkpoint {
+    dir: PathBuf,
+    log: fs::File,
+    created: String,
+    algorithm: HashAlgorithm,
+    entries: BTreeMap<String, CheckpointEntry>,
+    resumed: usize,
+}
+
+impl Checkpoint {
+    /// Open the checkpoint in `dir`, creating it on the first attempt.
+    ///
+    /// A log written with another hash algorithm is started over. Lines that
+    /// do not parse, such as one cut short by a crash, are ignored.
+    pub fn open(dir: &Path, algorithm: HashAlgorithm) -> io::Result<Self> {
+        fs::create_dir_all(dir.join(STAGING_SUBDIR))?;
+        let path = dir.join(CHECKPOINT_FILE);
+        let mut header = None;
+        let mut entries = BTreeMap::new();
+        if let Ok(file) = fs::File::open(&path) {
+            let mut lines = BufReader::new(file).lines().map_while(Result::ok);
+            header = lines
+                .next()
+                .and_then(|line| serde_json::from_str::<CheckpointHeader>(&line).ok())
+                .filter(|header| {
+                    header.version == CHECKPOINT_VERSION
+                        && header.hash_algorithm == algorithm.as_str()
+                });
+            if header.is_some() {
+                for line in lines {
+                    if let Ok(entry) = serde_json::from_str::<CheckpointEntry>(&line) {
+                        entries.insert(entry.member_path.clone(), entry);
+                    }
+                }
+            }
+        }
+
+        let (log, created) = match header {
+            Some(header) => {
+                let log = fs::OpenOptions::new().append(true).open(&path)?;
+                (log, header.created)
+            }
+            None => {
+                let created = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+                let mut log = fs::File::create(&path)?;
+                let header = CheckpointHeader {
+                    version: CHECKPOINT_VERSION.to_string(),
+                    created: created.clone(),
+                    hash_algorithm: algorithm.as_str().to_string(),
+                };
+                writeln!(log, "{}", serde_json::to_string(&header)?)?;
+                (log, created)
+            }
+        };
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            log,
+            created,
+            algorithm,
+            entries,
+            resumed: 0,
+        })
+    }
+
+    /// Directory members are staged in.
+    pub fn staging_dir(&self) -> PathBuf {
+        self.dir.join(STAGING_SUBDIR)
+    }
+
+    /// `created` of the first attempt.
+    pub fn created(&self) -> &str {
+        &self.created
+    }
+
+    /// Members taken from the checkpoint instead of being copied again.
+    pub fn resumed(&self) -> usize {
+        self.resumed
+    }
+
+    /// Remove staged files left by an earlier attempt that are not among
+    /// `candidates`, so the pack holds only this seal's members.
+    pub fn prune(&self, candidates: &[MemberCandidate]) -> io::Result<()> {
+        let keep: BTreeSet<&str> = candidates
+            .iter()
+            .map(|candidate| candidate.member_path.as_str())
+            .collect();
+        prune_dir(&self.staging_dir(), "", &keep)
+    }
+
+    /// The staged copy of `candidate` from an earlier attempt, if its source
+    /// is unchanged and the staged bytes still hash to the checkpointed value.
+    pub fn take(&mut self, candidate: &MemberCandidate) -> Option<CopiedMember> {
+        let entry = self.entries.get(&candidate.member_path)?;
+        let source = fs::metadata(&candidate.source).ok()?;
+        if entry.source != candidate.source.display().to_string()
+            || entry.source_size != source.len()
+            || entry.source_mtime != mtime(&source)
+        {
+            return None;
+        }
+        let staged = fs::File::open(self.staging_dir().join(&entry.member_path)).ok()?;
+        let (bytes_hash, size) = stream_hash(
+            staged,
+            None::<&mut io::Sink>,
+            hash_buffer_size(),
+            self.algorithm,
+            true,
+        )
+        .ok()?;
+        if bytes_hash != entry.bytes_hash || size != entry.size {
+            return None;
+        }
+        self.resumed += 1;
+        Some(CopiedMember {
+            member_path: entry.member_path.clone(),
+            bytes_hash: entry.bytes_hash.clone(),
+            size: entry.size,
+            sealed_at: entry.sealed_at.clone(),
+            executable: entry.executable,
+            detection: None,
+        })
+    }
+
+    /// Append a freshly copied member to the log.
+    pub fn record(&mut self, candidate: &MemberCandidate, copied: &CopiedMember) -> io::Result<()> {
+        let source = fs::metadata(&candidate.source)?;
+        let entry = CheckpointEntry {
+            member_path: copied.member_path.clone(),
+            source: candidate.source.display().to_string(),
+            source_size: source.len(),
+            source_mtime: mtime(&source),
+            bytes_hash: copied.bytes_hash.clone(),
+            size: copied.size,
+            sealed_at: copied.sealed_at.clone(),
+            executable: copied.executable,
+        };
+        writeln!(self.log, "{}", serde_json::to_string(&entry)?)?;
+        self.log.flush()
+    }
+}
+
+fn mtime(meta: &fs::Metadata) -> Option<String> {
+    let modified = meta.modified().ok()?;
+    Some(DateTime::<Utc>::from(modified).to_rfc3339_opts(SecondsFormat::Nanos, true))
+}
+
+fn prune_dir(dir: &Path, prefix: &str, keep: &BTreeSet<&str>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let member_path = format!("{prefix}{name}");
+        if entry.file_type()?.is_dir() {
+            prune_dir(&entry.path(), &format!("{member_path}/"), keep)?;
+            if fs::read_dir(entry.path())?.next().is_none() {
+                fs::remove_dir(entry.path())?;
+            }
+        } else if !keep.contains(member_path.as_str()) {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_members_resume_and_changed_ones_do_not() {
+        let src = tempfile::tempdir().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let candidate = |name: &str, content: &str| {
+            let source = src.path().join(name);
+            fs::write(&source, content).unwrap();
+            MemberCandidate {
+                source,
+                member_path: name.to_string(),
+            }
+        };
+        let a = candidate("a.txt", "alpha");
+        let b = candidate("b.txt", "beta");
+
+        let mut first = Checkpoint::open(dir.path(), HashAlgorithm::Sha256).unwrap();
+        for member in [&a, &b] {
+            let staged = first.staging_dir().join(&member.member_path);
+            fs::copy(&member.source, &staged).unwrap();
+            let (bytes_hash, size) = stream_hash(
+                fs::File::open(&staged).unwrap(),
+                None::<&mut io::Sink>,
+                64,
+                HashAlgorithm::Sha256,
+                true,
+            )
+            .unwrap();
+            let copied = CopiedMember {
+                member_path: member.member_path.clone(),
+                bytes_hash,
+                size,
+                sealed_at: "2026-01-15T10:00:00.000Z".to_string(),
+                executable: false,
+                detection: None,
+            };
+            first.record(member, &copied).unwrap();
+        }
+        fs::write(first.staging_dir().join("stale.txt"), "old").unwrap();
+        let created = first.created().to_string();
+        drop(first);
+
+        // b's staged copy was damaged; stale.txt is no longer an input.
+        fs::write(dir.path().join("staging/b.txt"), "bet").unwrap();
+        let mut resumed = Checkpoint::open(dir.path(), HashAlgorithm::Sha256).unwrap();
+        assert_eq!(resumed.created(), created);
+        resumed.prune(&[a.clone(), b.clone()]).unwrap();
+        assert!(!dir.path().join("staging/stale.txt").exists());
+        assert_eq!(
+            resumed.take(&a).unwrap().sealed_at,
+            "2026-01-15T10:00:00.000Z"
+        );
+        assert!(resumed.take(&b).is_none());
+        assert_eq!(resumed.resumed(), 1);
+
+        // Another algorithm starts over.
+        let mut other = Checkpoint::open(dir.path(), HashAlgorithm::Blake3).unwrap();
+        assert!(other.take(&a).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+use std::io::{self, Write};
+
+use crate::detect::{
+    detect_content_type, detect_member_type_with_rules, ContentType, DetectionRule,
+    MemberTypeResult, SNIFF_BYTES,
+};
+
+use super::finalize::MAX_DETECT_BYTES;
+
+/// A member's type and content type, as recorded in its manifest entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberDetection {
+    pub member_type: MemberTypeResult,
+    pub content_type: ContentType,
+}
+
+/// The bytes of a member that type detection needs, captured as they stream
+/// past during copy so detection does not read the member a second time.
+///
+/// Content is kept up to [`MAX_DETECT_BYTES`]. Past that, and as soon as the
+/// first [`SNIFF_BYTES`] are not UTF-8, only those leading bytes are kept:
+/// content detection needs the whole file as text, so neither case can be
+/// typed by content and holding the rest would only cost memory.
+#[derive(Debug, Default)]
+pub struct DetectSample {
+    bytes: Vec<u8>,
+    seen: u64,
+    binary: bool,
+}
+
+impl DetectSample {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Detect the type of the member at `member_path` from the captured bytes,
+    /// trying `rules` first as [`detect_member_type_with_rules`] does.
+    pub fn detect(&self, member_path: &str, rules: &[DetectionRule]) -> MemberDetection {
+        // An oversized member is typed by path alone, as if it were empty.
+        let content: &[u8] = if self.seen > MAX_DETECT_BYTES {
+            &[]
+        } else {
+            &self.bytes
+        };
+        MemberDetection {
+            member_type: detect_member_type_with_rules(content, member_path, rules),
+            content_type: detect_content_type(&self.bytes, member_path),
+        }
+    }
+
+    fn keeps_all(&self) -> bool {
+        !self.binary && self.seen <= MAX_DETECT_BYTES
+    }
+}
+
+impl Write for DetectSample {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.seen += buf.len() as u64;
+        if self.keeps_all() {
+            self.bytes.extend_from_slice(buf);
+            if !self.binary && self.bytes.len() >= SNIFF_BYTES {
+                // A character cut off at the end of the head is not binary.
+                self.binary = std::str::from_utf8(&self.bytes[..SNIFF_BYTES])
+                    .is_err_and(|e| e.error_len().is_some());
+            }
+        } else if self.bytes.len() < SNIFF_BYTES {
+            let take = (SNIFF_BYTES - self.bytes.len()).min(buf.len());
+            self.bytes.extend_from_slice(&buf[..take]);
+        }
+        if !self.keeps_all() && self.bytes.len() > SNIFF_BYTES {
+            self.bytes.truncate(SNIFF_BYTES);
+            self.bytes.shrink_to_fit();
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Copies into `inner` while capturing a [`DetectSample`].
+pub(crate) struct SampledWriter<'a, W> {
+    pub inner: W,
+    pub sample: &'a mut DetectSample,
+}
+
+impl<W: Write> Write for SampledWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.sample.write_all(&buf[..n])?;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_of(bytes: &[u8], chunk: usize) -> DetectSample {
+        let mut sample = DetectSample::new();
+        for piece in bytes.chunks(chunk) {
+            sample.write_all(piece).unwrap();
+        }
+        sample
+    }
+
+    #[test]
+    fn streamed_sample_detects_like_whole_content() {
+        let mut lockfile = br#"{"version": "lock.v0", "rows": ["#.to_vec();
+        lockfile.extend(vec![b' '; 3 * SNIFF_BYTES]);
+        lockfile.extend(b"]}");
+        let mut binary = vec![0xFFu8; SNIFF_BYTES];
+        binary.extend(b"schema_version: 1\nprofile_id: p\n");
+        let mut split_char = vec![b'a'; SNIFF_BYTES - 1];
+        split_char.extend("é\nschema_version: 1\nprofile_id: p\n".as_bytes());
+
+        for (path, content) in [
+            ("nov.lock.json", lockfile),
+            ("blob.yaml", binary.clone()),
+            ("profile.yaml", split_char),
+        ] {
+            let expected = MemberDetection {
+                member_type: detect_member_type_with_rules(&content, path, &[]),
+                content_type: detect_content_type(&content, path),
+            };
+            for chunk in [1, 7, 4096, content.len()] {
+                let sample = sample_of(&content, chunk);
+                assert_eq!(sample.detect(path, &[]), expected, "{path} chunk={chunk}");
+            }
+        }
+        assert_eq!(sample_of(&binary, 64).bytes.len(), SNIFF_BYTES);
+    }
+}
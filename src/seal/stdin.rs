@@ -0,0 +1,124 @@
+use std::fs;
+use std::io::{self, BufWriter, Read};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::collect::is_safe_member_path;
+
+/// Artifact argument that seals stdin as a member named by `--stdin-name`.
+pub const STDIN_ARTIFACT: &str = "-";
+
+/// True when an artifact argument is [`STDIN_ARTIFACT`].
+pub fn is_stdin(arg: &Path) -> bool {
+    arg == Path::new(STDIN_ARTIFACT)
+}
+
+/// Check `--stdin-name` against the artifacts it applies to and return the
+/// member path for the `-` artifact, if there is one.
+///
+/// Refuses with `E_IO` when `-` has no name, a name is given without `-`,
+/// `-` appears more than once, or the name is not a relative member path.
+pub fn stdin_member_path<'a>(
+    artifacts: &[PathBuf],
+    stdin_name: Option<&'a str>,
+) -> Result<Option<&'a str>, Box<RefusalEnvelope>> {
+    let count = artifacts.iter().filter(|arg| is_stdin(arg)).count();
+    match (count, stdin_name) {
+        (0, None) => Ok(None),
+        (0, Some(name)) => Err(refusal(
+            "--stdin-name requires `-` among the artifacts",
+            name,
+        )),
+        (1, Some(name)) if is_valid_name(name) => Ok(Some(name)),
+        (1, Some(name)) => Err(refusal(
+            format!("--stdin-name must be a relative member path: {name}"),
+            name,
+        )),
+        (1, None) => Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some("Artifact `-` requires --stdin-name to name the member".to_string()),
+            None,
+        ))),
+        (_, name) => Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some("Stdin can be sealed only once; `-` was given more than once".to_string()),
+            name.map(|name| json!({ "stdin_name": name })),
+        ))),
+    }
+}
+
+/// Refuse when `--from-list -` has already consumed stdin and `-` is also an
+/// artifact.
+pub fn check_stdin_unshared(
+    from_list: &Path,
+    artifacts: &[PathBuf],
+) -> Result<(), Box<RefusalEnvelope>> {
+    if is_stdin(from_list) && artifacts.iter().any(|arg| is_stdin(arg)) {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some("Stdin cannot be both the --from-list source and the `-` artifact".to_string()),
+            Some(json!({ "from_list": STDIN_ARTIFACT })),
+        )));
+    }
+    Ok(())
+}
+
+/// Stream `reader` into a file under `dest_dir` so it can be copied and
+/// hashed like any other member. Returns the file's path.
+pub fn stage_stdin<R: Read>(
+    mut reader: R,
+    name: &str,
+    dest_dir: &Path,
+) -> Result<PathBuf, Box<RefusalEnvelope>> {
+    let staged = dest_dir.join("stdin");
+    fs::create_dir_all(dest_dir)
+        .and_then(|_| fs::File::create(&staged))
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            io::copy(&mut reader, &mut writer)?;
+            writer.into_inner().map_err(|e| e.into_error())?.sync_all()
+        })
+        .map_err(|e| refusal(format!("Cannot read stdin for {name}: {e}"), name))?;
+    Ok(staged)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    is_safe_member_path(name)
+        && !name.contains('\\')
+        && name
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != ".")
+}
+
+fn refusal(message: impl Into<String>, name: &str) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message.into()),
+        Some(json!({ "stdin_name": name })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stdin_name_must_pair_with_a_single_dash() {
+        let dash = vec![PathBuf::from("a.json"), PathBuf::from("-")];
+        let plain = vec![PathBuf::from("a.json")];
+
+        assert_eq!(
+            stdin_member_path(&dash, Some("reports/out.json")).unwrap(),
+            Some("reports/out.json")
+        );
+        assert_eq!(stdin_member_path(&plain, None).unwrap(), None);
+        assert!(stdin_member_path(&dash, None).is_err());
+        assert!(stdin_member_path(&plain, Some("out.json")).is_err());
+        assert!(stdin_member_path(&[PathBuf::from("-"), PathBuf::from("-")], Some("x")).is_err());
+        for bad in ["", "/abs.json", "../up.json", "a//b", "./a", "a\\b"] {
+            assert!(stdin_member_path(&dash, Some(bad)).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::annotate::MemberAnnotation;
+use crate::seal::command::{promote_seal, stage_seal, SealOptions, SealResult};
+use crate::seal::limits::{check_field_size, DEFAULT_MAX_NOTE_BYTES};
+use crate::seal::visibility::VisibilityRule;
+
+/// One seal within a `seal --transaction` plan.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlannedSeal {
+    pub artifacts: Vec<PathBuf>,
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// `PATTERN=LEVEL` rules, as for `--restrict`.
+    #[serde(default)]
+    pub restrict: Vec<String>,
+    /// Origins file, as for `--origins`.
+    #[serde(default)]
+    pub origins: Option<PathBuf>,
+    /// `MEMBER_PATH=TEXT` labels, as for `--annotate`.
+    #[serde(default)]
+    pub annotate: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransactionPlan {
+    seals: Vec<PlannedSeal>,
+}
+
+/// Load a transaction plan: `{"seals": [{"artifacts": [...], "output": ...,
+/// "note": ..., "restrict": [...], "origins": ..., "annotate": [...]}, ...]}`.
+///
+/// Relative paths resolve against the working directory, as on the command line.
+pub fn load_transaction_plan(path: &Path) -> Result<Vec<PlannedSeal>, Box<RefusalEnvelope>> {
+    let plan_refusal = |message: String| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::Io,
+            Some(message),
+            Some(json!({ "transaction": path.display().to_string() })),
+        ))
+    };
+    let content = fs::read_to_string(path).map_err(|e| {
+        plan_refusal(format!(
+            "Cannot read transaction plan {}: {e}",
+            path.display()
+        ))
+    })?;
+    let plan: TransactionPlan = serde_json::from_str(&content)
+        .map_err(|e| plan_refusal(format!("Invalid transaction plan {}: {e}", path.display())))?;
+    if plan.seals.is_empty() {
+        return Err(Box::new(RefusalEnvelope::new(
+            RefusalCode::Empty,
+            Some(format!("Transaction plan has no seals: {}", path.display())),
+            None,
+        )));
+    }
+    Ok(plan.seals)
+}
+
+/// Seal every planned pack or none of them.
+///
+/// All seals are staged first; any staging failure discards every staging
+/// directory before anything reaches an output path. The packs are then
+/// promoted in plan order, and if a promotion fails the packs already promoted
+/// are removed again. Refusals keep the failing seal's code and message, with
+/// `detail.transaction` naming the seal index and what was rolled back.
+pub fn execute_seal_transaction(
+    seals: &[PlannedSeal],
+    options: &SealOptions,
+) -> Result<Vec<SealResult>, Box<RefusalEnvelope>> {
+    // Check every plan entry before staging any of them
+    let mut rules = Vec::with_capacity(seals.len());
+    for (index, seal) in seals.iter().enumerate() {
+        if seal.artifacts.is_empty() {
+            return Err(in_transaction(
+                Box::new(RefusalEnvelope::new(RefusalCode::Empty, None, None)),
+                index,
+                &[],
+            ));
+        }
+        if let Some(note) = &seal.note {
+            let limit = options.max_note_bytes.unwrap_or(DEFAULT_MAX_NOTE_BYTES);
+            check_field_size("note", note, limit).map_err(|e| in_transaction(e, index, &[]))?;
+        }
+        let flag_refusal = |message: String| {
+            in_transaction(
+                Box::new(RefusalEnvelope::new(RefusalCode::Io, Some(message), None)),
+                index,
+                &[],
+            )
+        };
+        let restrict = seal
+            .restrict
+            .iter()
+            .map(|raw| VisibilityRule::from_flag(raw))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(flag_refusal)?;
+        let annotate = seal
+            .annotate
+            .iter()
+            .map(|raw| MemberAnnotation::from_flag(raw))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(flag_refusal)?;
+        rules.push((restrict, annotate));
+    }
+
+    let mut staged = Vec::with_capacity(seals.len());
+    let mut outputs = BTreeSet::new();
+    for (index, (seal, (restrict, annotate))) in seals.iter().zip(rules).enumerate() {
+        let seal_options = SealOptions {
+            restrict,
+            annotate,
+            origins: seal.origins.clone(),
+            ..options.clone()
+        };
+        let pack = stage_seal(
+            &seal.artifacts,
+            seal.output.as_deref(),
+            seal.note.clone(),
+            &seal_options,
+        )
+        .map_err(|e| in_transaction(e, index, &[]))?;
+        if !outputs.insert(pack.result.output_dir.clone()) {
+            return Err(in_transaction(
+                Box::new(RefusalEnvelope::new(
+                    RefusalCode::Duplicate,
+                    Some(format!(
+                        "Two seals in the transaction write to {}",
+                        pack.result.output_dir.display()
+                    )),
+                    None,
+                )),
+                index,
+                &[],
+            ));
+        }
+        staged.push(pack);
+    }
+
+    let mut promoted: Vec<(PathBuf, bool)> = Vec::with_capacity(staged.len());
+    let mut results = Vec::with_capacity(staged.len());
+    for (index, pack) in staged.into_iter().enumerate() {
+        let output = pack.result.output_dir.clone();
+        let existed = output.exists();
+        match promote_seal(pack) {
+            Ok(result) => {
+                promoted.push((output, existed));
+                results.push(result);
+            }
+            Err(e) => {
+                let rolled_back = roll_back(&promoted);
+                return Err(in_transaction(e, index, &rolled_back));
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Remove promoted outputs in reverse order, restoring output directories that
+/// existed (empty) before the transaction. Returns the outputs removed.
+fn roll_back(promoted: &[(PathBuf, bool)]) -> Vec<PathBuf> {
+    let mut rolled_back = Vec::with_capacity(promoted.len());
+    for (output, existed) in promoted.iter().rev() {
+        let removed = if output.is_dir() {
+            fs::remove_dir_all(output)
+        } else {
+            fs::remove_file(output)
+        };
+        match removed {
+            Ok(()) => {
+                if *existed {
+                    let _ = fs::create_dir(output);
+                }
+                rolled_back.push(output.clone());
+            }
+            Err(e) => eprintln!(
+                "pack: transaction rollback could not remove {}: {e}",
+                output.display()
+            ),
+        }
+    }
+    rolled_back
+}
+
+fn in_transaction(
+    mut envelope: Box<RefusalEnvelope>,
+    index: usize,
+    rolled_back: &[PathBuf],
+) -> Box<RefusalEnvelope> {
+    let transaction = json!({
+        "seal": index,
+        "rolled_back": rolled_back
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>(),
+    });
+    envelope.refusal.detail = Some(match envelope.refusal.detail.take() {
+        Some(Value::Object(mut detail)) => {
+            detail.insert("transaction".to_string(), transaction);
+            Value::Object(detail)
+        }
+        Some(other) => json!({ "cause": other, "transaction": transaction }),
+        None => json!({ "transaction": transaction }),
+    });
+    envelope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn planned(artifact: &Path, output: &Path) -> PlannedSeal {
+        PlannedSeal {
+            artifacts: vec![artifact.to_path_buf()],
+            output: Some(output.to_path_buf()),
+            note: None,
+            restrict: vec![],
+            origins: None,
+            annotate: vec![],
+        }
+    }
+
+    #[test]
+    fn all_packs_are_promoted_together() {
+        let tmp = TempDir::new().unwrap();
+        let input = tmp.path().join("inputs.json");
+        let output = tmp.path().join("outputs.json");
+        fs::write(&input, "{}").unwrap();
+        fs::write(&output, "[]").unwrap();
+        let seals = [
+            planned(&input, &tmp.path().join("packs/inputs")),
+            planned(&output, &tmp.path().join("packs/outputs")),
+        ];
+
+        let results = execute_seal_transaction(&seals, &SealOptions::default()).unwrap();
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert!(result.output_dir.join("manifest.json").is_file());
+        }
+    }
+
+    #[test]
+    fn staging_failure_leaves_no_output() {
+        let tmp = TempDir::new().unwrap();
+        let input = tmp.path().join("inputs.json");
+        fs::write(&input, "{}").unwrap();
+        let seals = [
+            planned(&input, &tmp.path().join("packs/inputs")),
+            planned(
+                &tmp.path().join("missing.json"),
+                &tmp.path().join("packs/outputs"),
+            ),
+        ];
+
+        let err = execute_seal_transaction(&seals, &SealOptions::default()).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert_eq!(err.refusal.detail.unwrap()["transaction"]["seal"], 1);
+        assert!(!tmp.path().join("packs").exists());
+    }
+
+    #[test]
+    fn promotion_failure_rolls_back_earlier_packs() {
+        let tmp = TempDir::new().unwrap();
+        let input = tmp.path().join("inputs.json");
+        fs::write(&input, "{}").unwrap();
+        let first = tmp.path().join("packs/inputs");
+        // A file where the second pack's parent directory must go.
+        fs::write(tmp.path().join("blocked"), "").unwrap();
+        let seals = [
+            planned(&input, &first),
+            planned(&input, &tmp.path().join("blocked/outputs")),
+        ];
+
+        let err = execute_seal_transaction(&seals, &SealOptions::default()).unwrap_err();
+        let transaction = &err.refusal.detail.unwrap()["transaction"];
+        assert_eq!(transaction["seal"], 1);
+        assert_eq!(transaction["rolled_back"][0], first.display().to_string());
+        assert!(!first.exists());
+    }
+
+    #[test]
+    fn duplicate_outputs_refuse() {
+        let tmp = TempDir::new().unwrap();
+        let input = tmp.path().join("inputs.json");
+        fs::write(&input, "{}").unwrap();
+        let out = tmp.path().join("pack");
+        let seals = [planned(&input, &out), planned(&input, &out)];
+
+        let err = execute_seal_transaction(&seals, &SealOptions::default()).unwrap_err();
+        assert_eq!(err.refusal.code, "E_DUPLICATE");
+        assert!(!out.exists());
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use super::collect::MemberCandidate;
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Percentage of trusted members that are re-hashed to spot-check the hashes file.
+pub const TRUST_SAMPLE_PERCENT: usize = 10;
+
+/// Precomputed `sha256:<hex>` hashes keyed by source path, loaded from `--trust-hashes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedHashes {
+    hashes: BTreeMap<PathBuf, String>,
+}
+
+impl TrustedHashes {
+    /// Look up the trusted hash for a source path.
+    pub fn get(&self, source: &Path) -> Option<&str> {
+        self.hashes.get(&normalize(source)).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+/// How a single member's hash is obtained during copy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrustDecision {
+    /// Use the provided hash without re-hashing the bytes.
+    Trusted(String),
+    /// Re-hash the bytes and refuse if they disagree with the provided hash.
+    Sampled(String),
+}
+
+/// Per-member trust decisions keyed by member path, plus counts for provenance.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustPlan {
+    pub decisions: BTreeMap<String, TrustDecision>,
+}
+
+impl TrustPlan {
+    pub fn get(&self, member_path: &str) -> Option<&TrustDecision> {
+        self.decisions.get(member_path)
+    }
+
+    pub fn trusted_count(&self) -> usize {
+        self.decisions
+            .values()
+            .filter(|d| matches!(d, TrustDecision::Trusted(_)))
+            .count()
+    }
+
+    pub fn sampled_count(&self) -> usize {
+        self.decisions
+            .values()
+            .filter(|d| matches!(d, TrustDecision::Sampled(_)))
+            .count()
+    }
+}
+
+/// Load a `{"<source path>": "sha256:<hex>"}` hashes file.
+pub fn load_trusted_hashes(path: &Path) -> Result<TrustedHashes, Box<RefusalEnvelope>> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        refusal(format!(
+            "Cannot read trusted hashes file: {}: {e}",
+            path.display()
+        ))
+    })?;
+    let raw: BTreeMap<String, String> = serde_json::from_str(&content).map_err(|e| {
+        refusal(format!(
+            "Invalid trusted hashes file: {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    let mut hashes = BTreeMap::new();
+    for (source, hash) in raw {
+        if !is_sha256_hash(&hash) {
+            return Err(refusal(format!(
+                "Invalid trusted hash for {source}: expected sha256:<64 hex>, got {hash}"
+            )));
+        }
+        hashes.insert(normalize(Path::new(&source)), hash);
+    }
+
+    Ok(TrustedHashes { hashes })
+}
+
+/// Decide which candidates use their trusted hash and which are re-hashed.
+///
+/// Candidates without a trusted hash are hashed normally and do not appear in the
+/// plan. At least one trusted member (and `TRUST_SAMPLE_PERCENT` of them, rounded
+/// up) is sampled; selection is keyed on `nonce` so each run picks a fresh sample.
+pub fn plan_trust(
+    candidates: &[MemberCandidate],
+    trusted: &TrustedHashes,
+    nonce: u64,
+) -> TrustPlan {
+    let eligible: Vec<(&MemberCandidate, &str)> = candidates
+        .iter()
+        .filter_map(|c| trusted.get(&c.source).map(|hash| (c, hash)))
+        .collect();
+
+    let sample_size = eligible
+        .len()
+        .div_ceil(100 / TRUST_SAMPLE_PERCENT)
+        .min(eligible.len());
+    let mut ranked: Vec<(blake3::Hash, &str)> = eligible
+        .iter()
+        .map(|(c, _)| {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&nonce.to_le_bytes());
+            hasher.update(c.member_path.as_bytes());
+            (hasher.finalize(), c.member_path.as_str())
+        })
+        .collect();
+    ranked.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+    let sampled: BTreeSet<&str> = ranked
+        .iter()
+        .take(sample_size)
+        .map(|(_, path)| *path)
+        .collect();
+
+    let decisions = eligible
+        .into_iter()
+        .map(|(c, hash)| {
+            let decision = if sampled.contains(c.member_path.as_str()) {
+                TrustDecision::Sampled(hash.to_string())
+            } else {
+                TrustDecision::Trusted(hash.to_string())
+            };
+            (c.member_path.clone(), decision)
+        })
+        .collect();
+
+    TrustPlan { decisions }
+}
+
+/// Refusal for a sampled member whose bytes disagree with the hashes file.
+pub fn mismatch_refusal(member_path: &str, expected: &str, actual: &str) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::HashMismatch,
+        Some(format!(
+            "Trusted hash does not match sampled member bytes: {member_path}"
+        )),
+        Some(json!({
+            "path": member_path,
+            "expected": expected,
+            "actual": actual,
+        })),
+    ))
+}
+
+fn is_sha256_hash(value: &str) -> bool {
+    value.strip_prefix("sha256:").is_some_and(|hex| {
+        hex.len() == 64 && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    })
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn refusal(message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(RefusalCode::Io, Some(message), None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const HASH_A: &str = "sha256:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    fn candidate(tmp: &TempDir, name: &str) -> MemberCandidate {
+        let source = tmp.path().join(name);
+        fs::write(&source, name).unwrap();
+        MemberCandidate {
+            source,
+            member_path: name.to_string(),
+        }
+    }
+
+    fn write_hashes(tmp: &TempDir, entries: &[(&Path, &str)]) -> PathBuf {
+        let map: BTreeMap<String, String> = entries
+            .iter()
+            .map(|(p, h)| (p.display().to_string(), h.to_string()))
+            .collect();
+        let path = tmp.path().join("hashes.json");
+        fs::write(&path, serde_json::to_string(&map).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_and_looks_up_by_source_path() {
+        let tmp = TempDir::new().unwrap();
+        let c = candidate(&tmp, "a.json");
+        let hashes_path = write_hashes(&tmp, &[(&c.source, HASH_A)]);
+
+        let trusted = load_trusted_hashes(&hashes_path).unwrap();
+        assert_eq!(trusted.len(), 1);
+        assert_eq!(trusted.get(&c.source), Some(HASH_A));
+    }
+
+    #[test]
+    fn malformed_hash_refuses() {
+        let tmp = TempDir::new().unwrap();
+        let c = candidate(&tmp, "a.json");
+        let hashes_path = write_hashes(&tmp, &[(&c.source, "md5:abc")]);
+
+        let err = load_trusted_hashes(&hashes_path).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+        assert!(err.refusal.message.contains("Invalid trusted hash"));
+    }
+
+    #[test]
+    fn plan_samples_at_least_one_member() {
+        let tmp = TempDir::new().unwrap();
+        let candidates: Vec<MemberCandidate> = (0..25)
+            .map(|i| candidate(&tmp, &format!("m{i:02}.json")))
+            .collect();
+        let entries: Vec<(&Path, &str)> = candidates
+            .iter()
+            .map(|c| (c.source.as_path(), HASH_A))
+            .collect();
+        let trusted = load_trusted_hashes(&write_hashes(&tmp, &entries)).unwrap();
+
+        let plan = plan_trust(&candidates, &trusted, 7);
+        assert_eq!(plan.sampled_count(), 3);
+        assert_eq!(plan.trusted_count(), 22);
+
+        let single = plan_trust(&candidates[..1], &trusted, 7);
+        assert_eq!(single.sampled_count(), 1);
+        assert_eq!(single.trusted_count(), 0);
+    }
+
+    #[test]
+    fn untrusted_candidates_are_not_planned() {
+        let tmp = TempDir::new().unwrap();
+        let c = candidate(&tmp, "a.json");
+        let plan = plan_trust(&[c], &TrustedHashes::default(), 1);
+        assert!(plan.decisions.is_empty());
+    }
+}
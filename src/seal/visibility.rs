@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::collect::MemberCandidate;
+
+/// A `--restrict <PATTERN>=<LEVEL>` rule tagging matching members with a
+/// visibility level (e.g. `auditors-only`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibilityRule {
+    pub pattern: String,
+    pub level: String,
+}
+
+impl VisibilityRule {
+    /// Parse a `--restrict PATTERN=LEVEL` argument.
+    ///
+    /// Levels are lowercase ASCII letters, digits, `-`, `_`, and `.`.
+    pub fn from_flag(raw: &str) -> Result<Self, String> {
+        let (pattern, level) = raw
+            .rsplit_once('=')
+            .ok_or_else(|| format!("--restrict expects PATTERN=LEVEL, got {raw:?}"))?;
+        if pattern.is_empty() {
+            return Err(format!("--restrict pattern is empty in {raw:?}"));
+        }
+        let valid_level = !level.is_empty()
+            && level
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"-_.".contains(&b));
+        if !valid_level {
+            return Err(format!(
+                "--restrict level {level:?} must be lowercase letters, digits, '-', '_' or '.'"
+            ));
+        }
+        Ok(Self {
+            pattern: pattern.to_string(),
+            level: level.to_string(),
+        })
+    }
+}
+
+/// Resolve `rules` against the collected members, returning member path → level.
+///
+/// When several rules match a member the last one wins. A rule that matches no
+/// member refuses with `E_IO`, so a typo cannot silently leave content public.
+pub fn resolve_visibility(
+    candidates: &[MemberCandidate],
+    rules: &[VisibilityRule],
+) -> Result<BTreeMap<String, String>, Box<RefusalEnvelope>> {
+    let mut levels = BTreeMap::new();
+    for rule in rules {
+        let mut matched = false;
+        for member in candidates {
+            if path_matches(&rule.pattern, &member.member_path) {
+                levels.insert(member.member_path.clone(), rule.level.clone());
+                matched = true;
+            }
+        }
+        if !matched {
+            return Err(Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("--restrict {} matches no member", rule.pattern)),
+                Some(json!({ "pattern": rule.pattern, "level": rule.level })),
+            )));
+        }
+    }
+    Ok(levels)
+}
+
+/// Match a member path against a pattern: an exact path, a directory prefix
+/// (`reports` or `reports/` covers `reports/**`), or a glob where `*` matches
+/// within one path segment and `**` matches any number of segments.
+pub(crate) fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    if !pattern.contains('*') {
+        return path == pattern
+            || path
+                .strip_prefix(pattern)
+                .is_some_and(|rest| rest.starts_with('/'));
+    }
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    segments_match(&pattern, &path)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((head, rest)) => path.split_first().is_some_and(|(first, tail)| {
+            segment_matches(head, first) && segments_match(rest, tail)
+        }),
+    }
+}
+
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == segment,
+        Some((prefix, rest)) => {
+            let Some(remaining) = segment.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=remaining.len())
+                .filter(|&i| remaining.is_char_boundary(i))
+                .any(|i| segment_matches(rest, &remaining[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(paths: &[&str]) -> Vec<MemberCandidate> {
+        paths
+            .iter()
+            .map(|path| MemberCandidate {
+                source: (*path).into(),
+                member_path: path.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn patterns_match_paths_prefixes_and_globs() {
+        assert!(path_matches("a.json", "a.json"));
+        assert!(path_matches("reports", "reports/q1.json"));
+        assert!(path_matches("reports/", "reports/q1.json"));
+        assert!(!path_matches("reports", "reports-old/q1.json"));
+        assert!(path_matches("reports/*.json", "reports/q1.json"));
+        assert!(!path_matches("reports/*.json", "reports/2026/q1.json"));
+        assert!(path_matches("**/*.csv", "a/b/loans.csv"));
+        assert!(path_matches("**/*.csv", "loans.csv"));
+    }
+
+    #[test]
+    fn last_rule_wins_and_unmatched_rules_refuse() {
+        let members = candidates(&["pii/loans.csv", "pii/summary.json", "report.json"]);
+        let rules = [
+            VisibilityRule::from_flag("pii=auditors-only").unwrap(),
+            VisibilityRule::from_flag("pii/*.json=internal").unwrap(),
+        ];
+        let levels = resolve_visibility(&members, &rules).unwrap();
+        assert_eq!(levels["pii/loans.csv"], "auditors-only");
+        assert_eq!(levels["pii/summary.json"], "internal");
+        assert!(!levels.contains_key("report.json"));
+
+        let missing = [VisibilityRule::from_flag("nope=internal").unwrap()];
+        let err = resolve_visibility(&members, &missing).unwrap_err();
+        assert_eq!(err.refusal.code, "E_IO");
+    }
+
+    #[test]
+    fn flag_requires_pattern_and_simple_level() {
+        assert!(VisibilityRule::from_flag("reports").is_err());
+        assert!(VisibilityRule::from_flag("=internal").is_err());
+        assert!(VisibilityRule::from_flag("reports=Auditors Only").is_err());
+        assert_eq!(
+            VisibilityRule::from_flag("a=b=auditors-only").unwrap(),
+            VisibilityRule {
+                pattern: "a=b".to_string(),
+                level: "auditors-only".to_string(),
+            }
+        );
+    }
+}
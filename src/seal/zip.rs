@@ -0,0 +1,439 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+use zip::ZipArchive;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::collect::is_safe_member_path;
+use crate::seal::copy::set_executable;
+use crate::seal::limits::check_collection_limits;
+
+/// File extension of artifacts that `seal --expand-archives` expands.
+pub const ZIP_EXTENSION: &str = ".zip";
+
+/// Unix file type bits and the types an entry may have.
+const MODE_TYPE: u32 = 0o170_000;
+const MODE_REGULAR: u32 = 0o100_000;
+const MODE_DIRECTORY: u32 = 0o040_000;
+const MODE_SYMLINK: u32 = 0o120_000;
+
+/// True when an artifact is a zip file to expand under `--expand-archives`.
+pub fn is_zip(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| zip_stem(name).is_some())
+        && path.is_file()
+}
+
+/// A zip file name without its `.zip` extension, matched case-insensitively.
+fn zip_stem(name: &str) -> Option<&str> {
+    let at = name.len().checked_sub(ZIP_EXTENSION.len())?;
+    let extension = name.get(at..)?;
+    extension
+        .eq_ignore_ascii_case(ZIP_EXTENSION)
+        .then(|| &name[..at])
+}
+
+/// Running member and byte totals across the zips one seal expands, held to
+/// `--max-members` and `--max-total-bytes` before each entry is written.
+#[derive(Debug, Clone, Default)]
+pub struct ExpandLimits {
+    max_members: Option<usize>,
+    max_total_bytes: Option<u64>,
+    members: usize,
+    total_bytes: u64,
+}
+
+impl ExpandLimits {
+    pub fn new(max_members: Option<usize>, max_total_bytes: Option<u64>) -> Self {
+        Self {
+            max_members,
+            max_total_bytes,
+            ..Self::default()
+        }
+    }
+
+    /// Count one more entry of `size` bytes, refusing with `E_LIMIT` if
+    /// that crosses a limit.
+    fn admit(&mut self, entry: &Path, size: u64) -> Result<(), Box<RefusalEnvelope>> {
+        self.members += 1;
+        self.total_bytes = self.total_bytes.saturating_add(size);
+        check_collection_limits(
+            self.max_members,
+            self.max_total_bytes,
+            self.members,
+            self.total_bytes,
+            Some(entry),
+        )
+    }
+}
+
+/// Unpack the zip file `archive` into `dest_root/<name>`, where `<name>` is
+/// the archive's file name without `.zip`, and return that directory.
+///
+/// Sealing the returned directory makes every entry a member under the
+/// `<name>/` prefix. Stored and deflated entries are supported and checked
+/// against their recorded size and CRC-32. Entry names must be UTF-8,
+/// relative, and free of `..` segments (with `/` or `\` as separator) and
+/// Windows drive or UNC prefixes; symlink and other non-regular entries,
+/// encrypted entries, and duplicate names are refused. Each file entry is
+/// counted against `limits` at its recorded size before it is written, and
+/// never written past that size, so a crossed limit refuses with `E_LIMIT`
+/// before the bytes land on disk. Every other refusal is `E_IO`, naming the
+/// archive and, where there is one, the entry.
+pub fn expand_zip(
+    archive: &Path,
+    dest_root: &Path,
+    limits: &mut ExpandLimits,
+) -> Result<PathBuf, Box<RefusalEnvelope>> {
+    let name = archive
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| zip_refusal(archive, None, "Zip file name is not UTF-8".to_string()))?;
+    let stem = zip_stem(name)
+        .filter(|stem| !matches!(*stem, "" | "." | ".."))
+        .unwrap_or(name);
+    let dest = dest_root.join(stem);
+
+    let file = fs::File::open(archive)
+        .map_err(|e| zip_refusal(archive, None, format!("Cannot open zip: {e}")))?;
+    let mut zip = ZipArchive::new(file)
+        .map_err(|e| zip_refusal(archive, None, format!("Cannot read zip: {e}")))?;
+    // The reader keys entries by name, so an exact duplicate would vanish
+    // silently; compare its count with the records actually on disk.
+    let records = count_central_records(archive, zip.central_directory_start())
+        .map_err(|e| zip_refusal(archive, None, format!("Cannot read zip: {e}")))?;
+    if records != zip.len() {
+        return Err(zip_refusal(
+            archive,
+            None,
+            "Zip has duplicate entry names".to_string(),
+        ));
+    }
+    fs::create_dir_all(&dest)
+        .map_err(|e| zip_refusal(archive, None, format!("Cannot expand zip: {e}")))?;
+
+    let mut seen = HashSet::new();
+    for index in 0..zip.len() {
+        // Metadata comes from the raw entry, so encrypted entries can be
+        // named in the refusal rather than failing to open.
+        let entry = zip
+            .by_index_raw(index)
+            .map_err(|e| zip_refusal(archive, None, format!("Cannot read zip: {e}")))?;
+        let entry_name = std::str::from_utf8(entry.name_raw())
+            .map_err(|_| zip_refusal(archive, None, "Zip entry name is not UTF-8".to_string()))?
+            .to_string();
+        let unix_mode = entry.unix_mode();
+        let (encrypted, size) = (entry.encrypted(), entry.size());
+        drop(entry);
+
+        let refuse = |message: String| zip_refusal(archive, Some(&entry_name), message);
+        let path = entry_name.strip_suffix('/').unwrap_or(&entry_name);
+        if !is_safe_member_path(path) || path.split(['/', '\\']).any(|s| s == "..") {
+            return Err(refuse(format!("Unsafe zip entry path: {entry_name}")));
+        }
+        match unix_mode.map(|mode| mode & MODE_TYPE) {
+            Some(MODE_SYMLINK) => {
+                return Err(refuse(format!("Symlink zip entry: {entry_name}")));
+            }
+            None | Some(0 | MODE_REGULAR | MODE_DIRECTORY) => {}
+            Some(other) => {
+                return Err(refuse(format!(
+                    "Unsupported zip entry type {other:o}: {entry_name}"
+                )));
+            }
+        }
+        if !seen.insert(path.to_string()) {
+            return Err(refuse(format!("Duplicate zip entry: {entry_name}")));
+        }
+
+        let target = dest.join(path);
+        let is_dir = entry_name.ends_with('/')
+            || unix_mode.map(|mode| mode & MODE_TYPE) == Some(MODE_DIRECTORY);
+        if is_dir {
+            fs::create_dir_all(&target)
+                .map_err(|e| refuse(format!("Cannot expand {entry_name}: {e}")))?;
+            continue;
+        }
+        if encrypted {
+            return Err(refuse(format!("Encrypted zip entry: {entry_name}")));
+        }
+        limits.admit(&archive.join(path), size)?;
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| refuse(format!("Cannot expand {entry_name}: {e}")))?;
+        }
+        let reader = zip
+            .by_index(index)
+            .map_err(|e| refuse(format!("Cannot read zip entry {entry_name}: {e}")))?;
+        extract_entry(reader, &entry_name, size, &target).map_err(refuse)?;
+        if unix_mode.is_some_and(|mode| mode & 0o111 != 0) {
+            set_executable(&target)
+                .map_err(|e| refuse(format!("Cannot expand {entry_name}: {e}")))?;
+        }
+    }
+    Ok(dest)
+}
+
+/// Number of central directory records starting at byte `start` of
+/// `archive`, counted up to the first non-record signature.
+fn count_central_records(archive: &Path, start: u64) -> io::Result<usize> {
+    const SIGNATURE: u32 = 0x0201_4b50;
+    const FIXED_LEN: usize = 46;
+    let mut reader = BufReader::new(fs::File::open(archive)?);
+    reader.seek(SeekFrom::Start(start))?;
+    let mut count = 0;
+    let mut header = [0u8; FIXED_LEN];
+    loop {
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(count),
+            Err(e) => return Err(e),
+        }
+        let u16_at = |at: usize| i64::from(u16::from_le_bytes([header[at], header[at + 1]]));
+        if u32::from_le_bytes([header[0], header[1], header[2], header[3]]) != SIGNATURE {
+            return Ok(count);
+        }
+        count += 1;
+        reader.seek_relative(u16_at(28) + u16_at(30) + u16_at(32))?;
+    }
+}
+
+/// Decompress one entry of recorded `size` to `target`. The zip reader checks
+/// the CRC-32 at the end of the entry; more or fewer bytes than recorded is
+/// refused, and nothing past `size` is written.
+fn extract_entry(entry: impl Read, name: &str, size: u64, target: &Path) -> Result<(), String> {
+    let write_error = |e: io::Error| format!("Cannot expand {name}: {e}");
+    let out = fs::File::create(target).map_err(write_error)?;
+    let mut writer = BufWriter::new(out);
+    let mut reader = entry.take(size);
+    let written = io::copy(&mut reader, &mut writer).map_err(|e| {
+        format!("Zip entry {name} does not match its recorded size and CRC-32: {e}")
+    })?;
+    writer.flush().map_err(write_error)?;
+    let mut entry = reader.into_inner();
+    let overflow = entry.read(&mut [0u8; 1]).map_err(|e| {
+        format!("Zip entry {name} does not match its recorded size and CRC-32: {e}")
+    })?;
+    if written != size || overflow != 0 {
+        return Err(format!(
+            "Zip entry {name} does not match its recorded size and CRC-32"
+        ));
+    }
+    Ok(())
+}
+
+fn zip_refusal(archive: &Path, entry: Option<&str>, message: String) -> Box<RefusalEnvelope> {
+    let mut detail = json!({ "archive": archive.display().to_string() });
+    if let Some(entry) = entry {
+        detail["entry"] = json!(entry);
+    }
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(detail),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::{execute_seal_with_options, SealOptions};
+    use crate::verify::read_manifest;
+    use flate2::write::DeflateEncoder;
+    use flate2::{Compression, Crc};
+    use tempfile::TempDir;
+
+    const LOCAL_HEADER: u32 = 0x0403_4b50;
+    const CENTRAL_HEADER: u32 = 0x0201_4b50;
+    const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+    const HOST_UNIX: u16 = 3;
+    const METHOD_STORED: u16 = 0;
+    const METHOD_DEFLATED: u16 = 8;
+
+    /// Write a zip of (name, content, Unix mode, deflate) entries.
+    fn write_zip(path: &Path, entries: &[(&str, &[u8], u32, bool)]) {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, content, mode, deflate) in entries {
+            let data = if *deflate {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(content).unwrap();
+                encoder.finish().unwrap()
+            } else {
+                content.to_vec()
+            };
+            let mut crc = Crc::new();
+            crc.update(content);
+            let method = if *deflate {
+                METHOD_DEFLATED
+            } else {
+                METHOD_STORED
+            };
+            let offset = out.len() as u32;
+            let mut common = Vec::new();
+            common.extend_from_slice(&0u16.to_le_bytes());
+            common.extend_from_slice(&method.to_le_bytes());
+            common.extend_from_slice(&[0; 4]);
+            common.extend_from_slice(&crc.sum().to_le_bytes());
+            common.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            common.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            common.extend_from_slice(&0u16.to_le_bytes());
+
+            out.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+            out.extend_from_slice(&20u16.to_le_bytes());
+            out.extend_from_slice(&common);
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&data);
+
+            central.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+            central.extend_from_slice(&((HOST_UNIX << 8) | 20).to_le_bytes());
+            central.extend_from_slice(&20u16.to_le_bytes());
+            central.extend_from_slice(&common);
+            central.extend_from_slice(&[0; 6]);
+            central.extend_from_slice(&(mode << 16).to_le_bytes());
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let dir_offset = out.len() as u32;
+        out.extend_from_slice(&central);
+        out.extend_from_slice(&END_OF_DIRECTORY.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        out.extend_from_slice(&dir_offset.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        fs::write(path, out).unwrap();
+    }
+
+    #[test]
+    fn zip_entries_seal_under_the_archive_name() {
+        let tmp = TempDir::new().unwrap();
+        let zip = tmp.path().join("Bundle.ZIP");
+        write_zip(
+            &zip,
+            &[
+                ("data/", b"", 0o040_755, false),
+                ("data/loans.csv", b"id,amount\n1,100\n", 0o100_644, true),
+                ("run.sh", b"#!/bin/sh\n", 0o100_755, false),
+            ],
+        );
+        let options = SealOptions {
+            expand_archives: true,
+            ..SealOptions::default()
+        };
+        let sealed =
+            execute_seal_with_options(&[zip], Some(&tmp.path().join("pack")), None, &options)
+                .unwrap();
+        let manifest = read_manifest(&sealed.output_dir).unwrap();
+        let paths: Vec<&str> = manifest.members.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, ["Bundle/data/loans.csv", "Bundle/run.sh"]);
+        assert_eq!(
+            fs::read(sealed.output_dir.join("Bundle/data/loans.csv")).unwrap(),
+            b"id,amount\n1,100\n"
+        );
+        #[cfg(unix)]
+        assert_eq!(manifest.members[1].executable, Some(true));
+    }
+
+    #[test]
+    fn unsafe_zip_entries_are_refused() {
+        let tmp = TempDir::new().unwrap();
+        let refused = |entry: (&str, &[u8], u32, bool)| {
+            let zip = tmp.path().join("in.zip");
+            write_zip(&zip, &[entry]);
+            let out = TempDir::new().unwrap();
+            let envelope = expand_zip(&zip, out.path(), &mut ExpandLimits::default()).unwrap_err();
+            assert_eq!(envelope.refusal.code, "E_IO");
+            envelope.refusal.message
+        };
+        assert_eq!(
+            refused(("../escape.txt", b"x", 0o100_644, false)),
+            "Unsafe zip entry path: ../escape.txt"
+        );
+        assert_eq!(
+            refused(("a\\..\\..\\escape.txt", b"x", 0o100_644, false)),
+            "Unsafe zip entry path: a\\..\\..\\escape.txt"
+        );
+        assert_eq!(
+            refused(("/etc/passwd", b"x", 0o100_644, false)),
+            "Unsafe zip entry path: /etc/passwd"
+        );
+        assert_eq!(
+            refused(("link", b"/etc/passwd", 0o120_777, false)),
+            "Symlink zip entry: link"
+        );
+
+        let zip = tmp.path().join("corrupt.zip");
+        write_zip(&zip, &[("a.txt", b"hello", 0o100_644, false)]);
+        let mut bytes = fs::read(&zip).unwrap();
+        bytes[30 + "a.txt".len()] = b'j';
+        fs::write(&zip, bytes).unwrap();
+        let out = TempDir::new().unwrap();
+        let envelope = expand_zip(&zip, out.path(), &mut ExpandLimits::default()).unwrap_err();
+        assert!(
+            envelope
+                .refusal
+                .message
+                .starts_with("Zip entry a.txt does not match its recorded size and CRC-32"),
+            "{}",
+            envelope.refusal.message
+        );
+    }
+
+    #[test]
+    fn duplicate_entries_are_refused() {
+        let tmp = TempDir::new().unwrap();
+        let zip = tmp.path().join("in.zip");
+        write_zip(
+            &zip,
+            &[
+                ("a.txt", b"one", 0o100_644, false),
+                ("a.txt", b"two", 0o100_644, false),
+            ],
+        );
+        let out = TempDir::new().unwrap();
+        let envelope = expand_zip(&zip, out.path(), &mut ExpandLimits::default()).unwrap_err();
+        assert_eq!(
+            envelope.refusal.code, "E_IO",
+            "{}",
+            envelope.refusal.message
+        );
+    }
+
+    #[test]
+    fn limits_refuse_before_an_entry_is_written() {
+        let tmp = TempDir::new().unwrap();
+        let zip = tmp.path().join("in.zip");
+        write_zip(
+            &zip,
+            &[
+                ("a.txt", b"12345678", 0o100_644, false),
+                ("b.txt", b"12345678", 0o100_644, true),
+            ],
+        );
+
+        let out = TempDir::new().unwrap();
+        let mut limits = ExpandLimits::new(None, Some(10));
+        let envelope = expand_zip(&zip, out.path(), &mut limits).unwrap_err();
+        assert_eq!(envelope.refusal.code, "E_LIMIT");
+        let detail = envelope.refusal.detail.unwrap();
+        assert_eq!(detail["exceeded"], "max_total_bytes");
+        assert_eq!(detail["total_bytes"], 16);
+        assert!(out.path().join("in/a.txt").exists());
+        assert!(!out.path().join("in/b.txt").exists());
+
+        // The totals run across every zip in the seal.
+        let out = TempDir::new().unwrap();
+        let mut limits = ExpandLimits::new(Some(3), None);
+        expand_zip(&zip, &out.path().join("first"), &mut limits).unwrap();
+        let envelope = expand_zip(&zip, &out.path().join("second"), &mut limits).unwrap_err();
+        assert_eq!(envelope.refusal.detail.unwrap()["exceeded"], "max_members");
+        assert!(!out.path().join("second/in/b.txt").exists());
+    }
+}
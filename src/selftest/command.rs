@@ -0,0 +1,354 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::canonical::canonicalize;
+use crate::cli::HashAlgorithm;
+use crate::seal::command::{execute_seal_with_options, SealOptions};
+use crate::seal::hasher::{hash_bytes, stream_sha256, DEFAULT_HASH_BUFFER_BYTES};
+use crate::seal::manifest::Manifest;
+use crate::verify::{verify_pack, VerifyOptions, VerifyOutcome};
+
+/// Version tag for `pack self-test --json` output.
+pub const SELF_TEST_REPORT_VERSION: &str = "pack.selftest.v0";
+
+/// FIPS 180-2 SHA-256 vectors; the last input is `'a'` repeated 1,000,000 times.
+const SHA256_VECTORS: &[(&str, &[u8], usize, &str)] = &[
+    (
+        "empty",
+        b"",
+        1,
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    ),
+    (
+        "abc",
+        b"abc",
+        1,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+    ),
+    (
+        "two_blocks",
+        b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        1,
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+    ),
+    (
+        "million_a",
+        b"a",
+        1_000_000,
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+    ),
+];
+
+/// FIPS 180-2 SHA-512 and BLAKE3 reference vectors for `abc`, covering the
+/// other `--hash-algorithm` choices.
+const OTHER_HASH_VECTORS: &[(HashAlgorithm, &str)] = &[
+    (
+        HashAlgorithm::Sha512,
+        "sha512:ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+    ),
+    (
+        HashAlgorithm::Blake3,
+        "blake3:6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+    ),
+];
+
+/// Input JSON and the canonical form `pack_id` hashes: object keys sorted
+/// bytewise at every depth, no insignificant whitespace.
+const CANONICAL_JSON_VECTORS: &[(&str, &str, &str)] = &[
+    (
+        "key_order",
+        r#"{"b": 1, "a": {"d": [3, 2, 1], "c": null}}"#,
+        r#"{"a":{"c":null,"d":[3,2,1]},"b":1}"#,
+    ),
+    (
+        "escapes",
+        r#"{"é": "line\nbreak", "a": "\u0001", "A": "\"q\" \\ \/"}"#,
+        r#"{"A":"\"q\" \\ /","a":"\u0001","é":"line\nbreak"}"#,
+    ),
+    (
+        "scalars",
+        r#"[true, false, 0, -1, 1.5, 18446744073709551615, ""]"#,
+        r#"[true,false,0,-1,1.5,18446744073709551615,""]"#,
+    ),
+    (
+        "numbers",
+        r#"[1.0, -0.0, 1e21, 1e-7, 0.000001, 123456789012345680000, 5e-324]"#,
+        r#"[1,0,1e+21,1e-7,0.000001,123456789012345680000,5e-324]"#,
+    ),
+];
+
+/// Fixed `created` and `tool_version` substituted into the golden pack's
+/// manifest, so its `pack_id` does not depend on when or by which release
+/// the pack was sealed.
+const GOLDEN_CREATED: &str = "2026-01-01T00:00:00Z";
+const GOLDEN_TOOL_VERSION: &str = "0.0.0-self-test";
+const GOLDEN_PACK_ID: &str =
+    "sha256:767c79a2f095fc2c2d03acb8324ea803e8e37841c6c1243bcebc1669d73884e4";
+
+/// One known-answer check.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl SelfTestCheck {
+    fn compare(name: impl Into<String>, expected: &str, actual: &str) -> Self {
+        Self {
+            name: name.into(),
+            passed: expected == actual,
+            expected: Some(expected.to_string()),
+            actual: Some(actual.to_string()),
+            message: None,
+        }
+    }
+
+    fn failed(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: false,
+            expected: None,
+            actual: None,
+            message: Some(message.into()),
+        }
+    }
+}
+
+/// Result of `pack self-test`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub version: String,
+    pub outcome: String,
+    pub tool_version: String,
+    pub checks: Vec<SelfTestCheck>,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("self-test report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![format!("pack self-test: {}", self.outcome)];
+        for check in &self.checks {
+            let mut line = format!(
+                "  {} {}",
+                if check.passed { "PASS" } else { "FAIL" },
+                check.name
+            );
+            if !check.passed {
+                if let (Some(expected), Some(actual)) = (&check.expected, &check.actual) {
+                    line.push_str(&format!(": expected {expected}, got {actual}"));
+                }
+                if let Some(message) = &check.message {
+                    line.push_str(&format!(": {message}"));
+                }
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack self-test`.
+///
+/// Returns (output, exit_code): 0 when every check passes, 1 otherwise.
+pub fn execute_self_test(json_output: bool) -> (String, u8) {
+    let report = run_self_test();
+    let exit_code = if report.passed() { 0 } else { 1 };
+    let output = if json_output {
+        report.to_json()
+    } else {
+        report.to_human()
+    };
+    (output, exit_code)
+}
+
+/// Run the built-in known-answer tests: SHA-256 vectors through the
+/// streaming hasher, SHA-512 and BLAKE3 vectors, canonical JSON vectors, and a seal/verify round trip of
+/// a golden mini-pack in a temporary directory.
+pub fn run_self_test() -> SelfTestReport {
+    let mut checks = Vec::new();
+
+    for (name, unit, repeat, expected) in SHA256_VECTORS {
+        let input = unit.repeat(*repeat);
+        for buffer_size in [7, DEFAULT_HASH_BUFFER_BYTES] {
+            let actual = stream_sha256(input.as_slice(), None::<&mut io::Sink>, buffer_size, true)
+                .map(|(hash, _)| hash)
+                .unwrap_or_else(|e| e.to_string());
+            checks.push(SelfTestCheck::compare(
+                format!("sha256/{name}/buffer_{buffer_size}"),
+                &format!("sha256:{expected}"),
+                &actual,
+            ));
+        }
+    }
+
+    for (algorithm, expected) in OTHER_HASH_VECTORS {
+        checks.push(SelfTestCheck::compare(
+            format!("{}/abc", algorithm.as_str()),
+            expected,
+            &hash_bytes(b"abc", *algorithm),
+        ));
+    }
+
+    for (name, input, expected) in CANONICAL_JSON_VECTORS {
+        let check = match serde_json::from_str::<Value>(input) {
+            Ok(value) => SelfTestCheck::compare(
+                format!("canonical_json/{name}"),
+                expected,
+                &canonicalize(&value),
+            ),
+            Err(e) => SelfTestCheck::failed(format!("canonical_json/{name}"), e.to_string()),
+        };
+        checks.push(check);
+    }
+
+    checks.extend(golden_pack_checks());
+
+    let outcome = if checks.iter().all(|check| check.passed) {
+        "OK"
+    } else {
+        "FAILED"
+    };
+    SelfTestReport {
+        version: SELF_TEST_REPORT_VERSION.to_string(),
+        outcome: outcome.to_string(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        checks,
+    }
+}
+
+/// Seal two fixed files, check the manifest against [`GOLDEN_PACK_ID`],
+/// verify the pack, then corrupt a member and check verify catches it.
+fn golden_pack_checks() -> Vec<SelfTestCheck> {
+    let tmp = match tempfile::tempdir() {
+        Ok(tmp) => tmp,
+        Err(e) => {
+            return vec![SelfTestCheck::failed(
+                "pack/seal",
+                format!("cannot create a temporary directory: {e}"),
+            )]
+        }
+    };
+    let inputs = tmp.path().join("inputs");
+    let written = fs::create_dir(&inputs)
+        .and_then(|_| fs::write(inputs.join("notes.txt"), "pack self-test\n"))
+        .and_then(|_| {
+            fs::write(
+                inputs.join("rules.json"),
+                "{\"version\":\"rules.v0\",\"rules\":[]}\n",
+            )
+        });
+    if let Err(e) = written {
+        return vec![SelfTestCheck::failed(
+            "pack/seal",
+            format!("cannot write golden inputs: {e}"),
+        )];
+    }
+
+    let output = tmp.path().join("pack");
+    let sealed = execute_seal_with_options(
+        &[inputs],
+        Some(&output),
+        Some("golden".to_string()),
+        &SealOptions::default(),
+    );
+    if let Err(envelope) = sealed {
+        return vec![SelfTestCheck::failed("pack/seal", envelope.to_json())];
+    }
+
+    let mut checks = vec![golden_pack_id_check(&output)];
+
+    let report = verify_pack(&output, &VerifyOptions::default());
+    checks.push(SelfTestCheck::compare(
+        "pack/verify",
+        "OK",
+        &report.outcome.to_string(),
+    ));
+
+    // Same length as the original, so only hashing can catch it.
+    let tampered = fs::write(output.join("inputs/notes.txt"), "pack self-tesT\n");
+    checks.push(match tampered {
+        Ok(()) => {
+            let report = verify_pack(&output, &VerifyOptions::default());
+            let detected = report.outcome == VerifyOutcome::INVALID
+                && report
+                    .invalid
+                    .iter()
+                    .any(|finding| finding.code == "HASH_MISMATCH");
+            let actual = report
+                .invalid
+                .iter()
+                .map(|finding| finding.code.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            let mut check = SelfTestCheck::compare(
+                "pack/verify_detects_tampering",
+                "INVALID HASH_MISMATCH",
+                &format!("{} {actual}", report.outcome),
+            );
+            check.passed = detected;
+            check
+        }
+        Err(e) => SelfTestCheck::failed(
+            "pack/verify_detects_tampering",
+            format!("cannot modify the sealed member: {e}"),
+        ),
+    });
+
+    checks
+}
+
+fn golden_pack_id_check(pack_dir: &Path) -> SelfTestCheck {
+    let manifest_path = pack_dir.join("manifest.json");
+    let manifest = fs::read(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice::<Manifest>(&bytes).map_err(|e| e.to_string()));
+    match manifest {
+        Ok(mut manifest) => {
+            manifest.created = GOLDEN_CREATED.to_string();
+            manifest.tool_version = GOLDEN_TOOL_VERSION.to_string();
+            SelfTestCheck::compare(
+                "pack/golden_pack_id",
+                GOLDEN_PACK_ID,
+                &manifest.recompute_pack_id(),
+            )
+        }
+        Err(e) => SelfTestCheck::failed(
+            "pack/golden_pack_id",
+            format!("cannot read {}: {e}", manifest_path.display()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_built_in_check_passes() {
+        let report = run_self_test();
+        let failed: Vec<_> = report.checks.iter().filter(|c| !c.passed).collect();
+        assert!(failed.is_empty(), "{failed:#?}");
+        assert_eq!(report.outcome, "OK");
+        assert!(report
+            .checks
+            .iter()
+            .any(|c| c.name == "pack/golden_pack_id"));
+    }
+}
@@ -0,0 +1,5 @@
+mod command;
+
+pub use command::{
+    execute_self_test, run_self_test, SelfTestCheck, SelfTestReport, SELF_TEST_REPORT_VERSION,
+};
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::config::SIGNING_KEY_FILE_ENV;
+
+/// Signing key material used by `seal --sign` when `--signing-key` is not given.
+pub const SIGNING_KEY_ENV: &str = "PACK_SIGNING_KEY";
+
+/// Load an Ed25519 signing key from `path`, or when `path` is `None` from
+/// `PACK_SIGNING_KEY`, then the `signing_key_file` setting.
+///
+/// Accepts a PKCS#8 PEM (`openssl genpkey -algorithm ed25519`) or a raw 32-byte
+/// seed encoded as hex or base64.
+pub fn load_signing_key(path: Option<&Path>) -> Result<SigningKey, String> {
+    let (text, source) = match (path, std::env::var(SIGNING_KEY_ENV)) {
+        (None, Ok(text)) => (text, SIGNING_KEY_ENV.to_string()),
+        (path, _) => {
+            let configured = crate::config::env_or_config(SIGNING_KEY_FILE_ENV).map(PathBuf::from);
+            let path = path.or(configured.as_deref()).ok_or_else(|| {
+                format!(
+                    "--sign requires --signing-key, {SIGNING_KEY_ENV}, or {SIGNING_KEY_FILE_ENV}"
+                )
+            })?;
+            (
+                fs::read_to_string(path)
+                    .map_err(|e| format!("Cannot read signing key {}: {e}", path.display()))?,
+                path.display().to_string(),
+            )
+        }
+    };
+    parse_signing_key(&text).map_err(|e| format!("Invalid signing key in {source}: {e}"))
+}
+
+/// Load an Ed25519 public key from a SPKI PEM or raw 32 bytes as hex or base64.
+pub fn load_public_key(path: &Path) -> Result<VerifyingKey, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read public key {}: {e}", path.display()))?;
+    parse_public_key(&text).map_err(|e| format!("Invalid public key in {}: {e}", path.display()))
+}
+
+fn parse_signing_key(text: &str) -> Result<SigningKey, String> {
+    let text = text.trim();
+    if text.starts_with("-----BEGIN") {
+        return SigningKey::from_pkcs8_pem(text).map_err(|e| e.to_string());
+    }
+    Ok(SigningKey::from_bytes(&raw_key_bytes(text)?))
+}
+
+pub(crate) fn parse_public_key(text: &str) -> Result<VerifyingKey, String> {
+    let text = text.trim();
+    if text.starts_with("-----BEGIN") {
+        return VerifyingKey::from_public_key_pem(text).map_err(|e| e.to_string());
+    }
+    VerifyingKey::from_bytes(&raw_key_bytes(text)?).map_err(|e| e.to_string())
+}
+
+fn raw_key_bytes(text: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(text)
+        .or_else(|_| STANDARD.decode(text))
+        .map_err(|_| "expected PEM, or 32 bytes as hex or base64".to_string())?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 key bytes, got {}", bytes.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    // RFC 8032 test vector 1.
+    const SEED_HEX: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const PUBLIC_HEX: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+
+    #[test]
+    fn raw_keys_parse_as_hex_or_base64() {
+        let from_hex = parse_signing_key(SEED_HEX).unwrap();
+        let from_b64 = parse_signing_key(&STANDARD.encode(hex::decode(SEED_HEX).unwrap())).unwrap();
+        assert_eq!(from_hex.to_bytes(), from_b64.to_bytes());
+        assert_eq!(hex::encode(from_hex.verifying_key().as_bytes()), PUBLIC_HEX);
+        assert_eq!(
+            parse_public_key(PUBLIC_HEX).unwrap(),
+            from_hex.verifying_key()
+        );
+    }
+
+    #[test]
+    fn key_file_errors_name_the_source() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("short.key");
+        fs::write(&path, "abcd").unwrap();
+        let err = load_signing_key(Some(&path)).unwrap_err();
+        assert!(err.contains("short.key"));
+        assert!(err.contains("expected 32 key bytes"));
+    }
+}
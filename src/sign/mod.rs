@@ -0,0 +1,9 @@
+mod key;
+mod signature;
+
+pub(crate) use key::parse_public_key;
+pub use key::{load_public_key, load_signing_key, SIGNING_KEY_ENV};
+pub use signature::{
+    read_signature, read_signature_from, write_signature, PackSignature, SignatureError,
+    SIGNATURE_FILE, SIGNATURE_VERSION,
+};
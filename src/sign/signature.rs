@@ -0,0 +1,159 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::seal::manifest::Manifest;
+use crate::verify::{DirSource, PackSource};
+
+use super::key::parse_public_key;
+
+/// Detached signature file written next to `manifest.json`.
+pub const SIGNATURE_FILE: &str = "manifest.sig.json";
+
+/// Signature file schema version.
+pub const SIGNATURE_VERSION: &str = "pack.sig.v0";
+
+const ALGORITHM: &str = "ed25519";
+
+/// `manifest.sig.json`: an Ed25519 signature over the canonical manifest bytes.
+///
+/// The file sits outside the manifest, so signing never changes `pack_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackSignature {
+    pub version: String,
+    pub algorithm: String,
+    pub pack_id: String,
+    /// Signer's public key, hex.
+    pub public_key: String,
+    /// Signature over `Manifest::to_canonical_bytes`, hex.
+    pub signature: String,
+}
+
+/// Why a pack's signature did not validate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The signature file is unreadable, malformed, or does not match the manifest.
+    Invalid(String),
+    /// The signature is valid but was made by a key other than the pinned one.
+    Untrusted { signer: String },
+}
+
+impl PackSignature {
+    /// Sign `manifest` (already finalized) with `key`.
+    pub fn sign(manifest: &Manifest, key: &SigningKey) -> Self {
+        let signature = key.sign(&manifest.to_canonical_bytes());
+        Self {
+            version: SIGNATURE_VERSION.to_string(),
+            algorithm: ALGORITHM.to_string(),
+            pack_id: manifest.pack_id.clone(),
+            public_key: hex::encode(key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    /// Check the signature against `manifest`, and against `trusted` when a
+    /// signer key is pinned.
+    pub fn verify(
+        &self,
+        manifest: &Manifest,
+        trusted: Option<&VerifyingKey>,
+    ) -> Result<(), SignatureError> {
+        let invalid = |message: &str| SignatureError::Invalid(message.to_string());
+        if self.version != SIGNATURE_VERSION || self.algorithm != ALGORITHM {
+            return Err(SignatureError::Invalid(format!(
+                "unsupported signature {} / {}",
+                self.version, self.algorithm
+            )));
+        }
+        if self.pack_id != manifest.pack_id {
+            return Err(invalid("signature is for a different pack_id"));
+        }
+        let signer = parse_public_key(&self.public_key).map_err(SignatureError::Invalid)?;
+        let signature = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| invalid("signature is not 64 hex-encoded bytes"))?;
+        signer
+            .verify_strict(&manifest.to_canonical_bytes(), &signature)
+            .map_err(|_| invalid("signature does not match manifest"))?;
+        if trusted.is_some_and(|key| *key != signer) {
+            return Err(SignatureError::Untrusted {
+                signer: self.public_key.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Write `manifest.sig.json` into `pack_dir`.
+pub fn write_signature(pack_dir: &Path, signature: &PackSignature) -> io::Result<()> {
+    let mut bytes = serde_json::to_vec_pretty(signature).map_err(io::Error::other)?;
+    bytes.push(b'\n');
+    fs::write(pack_dir.join(SIGNATURE_FILE), bytes)
+}
+
+/// Read `manifest.sig.json` from `pack_dir`; `Ok(None)` when the pack is unsigned.
+pub fn read_signature(pack_dir: &Path) -> Result<Option<PackSignature>, SignatureError> {
+    read_signature_from(&DirSource::new(pack_dir))
+}
+
+/// [`read_signature`] for a pack held in any [`PackSource`].
+pub fn read_signature_from(
+    source: &dyn PackSource,
+) -> Result<Option<PackSignature>, SignatureError> {
+    let content = match source.read_to_string(SIGNATURE_FILE) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(SignatureError::Invalid(format!(
+                "cannot read {SIGNATURE_FILE}: {e}"
+            )))
+        }
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| SignatureError::Invalid(format!("invalid {SIGNATURE_FILE}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::new(
+            "2026-01-01T00:00:00Z".to_string(),
+            Some("signed".to_string()),
+            "0.0.0".to_string(),
+            vec![],
+        );
+        manifest.finalize();
+        manifest
+    }
+
+    #[test]
+    fn signature_round_trips_and_detects_tampering() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut manifest = manifest();
+        let signature = PackSignature::sign(&manifest, &key);
+        assert_eq!(signature.verify(&manifest, None), Ok(()));
+        assert_eq!(
+            signature.verify(&manifest, Some(&key.verifying_key())),
+            Ok(())
+        );
+
+        let other = SigningKey::from_bytes(&[8u8; 32]).verifying_key();
+        assert!(matches!(
+            signature.verify(&manifest, Some(&other)),
+            Err(SignatureError::Untrusted { .. })
+        ));
+
+        manifest.note = Some("edited".to_string());
+        assert!(matches!(
+            signature.verify(&manifest, None),
+            Err(SignatureError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+//! Just enough DER to build an RFC 3161 request and read the fields of a
+//! response that `pack` checks. Not a general ASN.1 parser: high tag numbers
+//! and BER indefinite lengths are rejected.
+
+pub const BOOLEAN: u8 = 0x01;
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OID: u8 = 0x06;
+pub const GENERALIZED_TIME: u8 = 0x18;
+pub const SEQUENCE: u8 = 0x30;
+pub const SET: u8 = 0x31;
+
+/// Constructed context-specific tag `[n]`.
+pub const fn context(n: u8) -> u8 {
+    0xA0 | n
+}
+
+/// Encode one element from its tag and content bytes.
+pub fn encode(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Encode a non-negative INTEGER in its minimal two's-complement form.
+pub fn encode_unsigned(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|b| **b == 0).count().min(7);
+    let mut content = Vec::with_capacity(9);
+    if bytes[skip] & 0x80 != 0 {
+        content.push(0);
+    }
+    content.extend_from_slice(&bytes[skip..]);
+    encode(INTEGER, &content)
+}
+
+/// One decoded element: its tag, content bytes, and full encoding.
+#[derive(Debug, Clone, Copy)]
+pub struct Element<'a> {
+    pub tag: u8,
+    pub content: &'a [u8],
+    pub raw: &'a [u8],
+}
+
+/// Reads consecutive elements from a buffer.
+pub struct Reader<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { rest: bytes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.rest.first().copied()
+    }
+
+    pub fn next(&mut self) -> Result<Element<'a>, String> {
+        let bytes = self.rest;
+        let (&tag, after_tag) = bytes.split_first().ok_or("unexpected end of DER")?;
+        if tag & 0x1F == 0x1F {
+            return Err("multi-byte DER tags are not supported".to_string());
+        }
+        let (&first, after_len) = after_tag.split_first().ok_or("truncated DER length")?;
+        let (len, header) = match first {
+            0x80 => return Err("indefinite length is not DER".to_string()),
+            n if n < 0x80 => (usize::from(n), 2),
+            n => {
+                let count = usize::from(n & 0x7F);
+                if count > std::mem::size_of::<usize>() || after_len.len() < count {
+                    return Err("invalid DER length".to_string());
+                }
+                let len = after_len[..count]
+                    .iter()
+                    .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+                (len, 2 + count)
+            }
+        };
+        let end = header
+            .checked_add(len)
+            .filter(|end| *end <= bytes.len())
+            .ok_or("DER element overruns its buffer")?;
+        self.rest = &bytes[end..];
+        Ok(Element {
+            tag,
+            content: &bytes[header..end],
+            raw: &bytes[..end],
+        })
+    }
+
+    /// The next element's content, which must carry `tag`.
+    pub fn expect(&mut self, tag: u8, what: &str) -> Result<&'a [u8], String> {
+        let element = self.next().map_err(|e| format!("{what}: {e}"))?;
+        if element.tag != tag {
+            return Err(format!(
+                "{what}: expected tag 0x{tag:02x}, found 0x{:02x}",
+                element.tag
+            ));
+        }
+        Ok(element.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lengths_and_integers_round_trip() {
+        let long = vec![7u8; 300];
+        let encoded = encode(OCTET_STRING, &long);
+        assert_eq!(&encoded[..4], &[0x04, 0x82, 0x01, 0x2C]);
+        let mut reader = Reader::new(&encoded);
+        assert_eq!(reader.expect(OCTET_STRING, "blob").unwrap(), &long[..]);
+        assert!(reader.is_empty());
+
+        assert_eq!(encode_unsigned(0), vec![0x02, 0x01, 0x00]);
+        assert_eq!(encode_unsigned(0x80), vec![0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(encode_unsigned(0x0102), vec![0x02, 0x02, 0x01, 0x02]);
+
+        assert!(Reader::new(&[0x30, 0x80, 0x00, 0x00]).next().is_err());
+        assert!(Reader::new(&[0x04, 0x05, 0x01]).next().is_err());
+    }
+}
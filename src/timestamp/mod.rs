@@ -0,0 +1,9 @@
+mod der;
+mod token;
+mod trust;
+
+pub use token::{
+    read_timestamp, read_timestamp_from, write_timestamp, PackTimestamp, TIMESTAMP_FILE,
+    TIMESTAMP_VERSION,
+};
+pub use trust::TsaTrust;
@@ -0,0 +1,604 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{NaiveDateTime, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::seal::manifest::Manifest;
+use crate::verify::{DirSource, PackSource};
+
+use super::der::{self, Reader};
+use super::trust::TsaTrust;
+
+/// Trusted timestamp file written next to `manifest.json`.
+pub const TIMESTAMP_FILE: &str = "manifest.tst.json";
+
+/// Timestamp file schema version.
+pub const TIMESTAMP_VERSION: &str = "pack.timestamp.v0";
+
+/// Largest Time Stamp Authority response read, in bytes.
+const MAX_RESPONSE_BYTES: u64 = 1024 * 1024;
+
+/// id-sha256 (2.16.840.1.101.3.4.2.1).
+pub(super) const SHA256_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+/// id-signedData (1.2.840.113549.1.7.2).
+const SIGNED_DATA_OID: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+/// id-ct-TSTInfo (1.2.840.113549.1.9.16.1.4).
+pub(super) const TST_INFO_OID: &[u8] = &[
+    0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x10, 0x01, 0x04,
+];
+
+/// `manifest.tst.json`: an RFC 3161 timestamp token over the canonical
+/// manifest bytes.
+///
+/// Like the signature, the file sits outside the manifest, so timestamping
+/// never changes `pack_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackTimestamp {
+    pub version: String,
+    pub pack_id: String,
+    /// Time Stamp Authority the token was obtained from.
+    pub tsa_url: String,
+    /// `sha256:<hex>` of `Manifest::to_canonical_bytes`, as stamped.
+    pub message_imprint: String,
+    /// The token's `genTime`, RFC 3339.
+    pub gen_time: String,
+    /// DER `TimeStampToken` (a CMS `ContentInfo`), base64.
+    pub token: String,
+}
+
+/// The `TSTInfo` fields `pack` checks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TstInfo {
+    imprint_algorithm: Vec<u8>,
+    imprint: Vec<u8>,
+    gen_time: String,
+    nonce: Option<Vec<u8>>,
+}
+
+/// A decoded `TimeStampToken`: its `TSTInfo` and the CMS parts that sign it.
+pub(super) struct Token<'a> {
+    info: TstInfo,
+    /// DER `TSTInfo`, the content the signer's `messageDigest` covers.
+    pub(super) tst_info: &'a [u8],
+    /// DER certificates the token carries, the TSA's among them.
+    pub(super) certificates: Vec<&'a [u8]>,
+    /// Content of the `signerInfos` SET.
+    pub(super) signer_infos: &'a [u8],
+}
+
+impl Token<'_> {
+    /// The token's `genTime`, RFC 3339.
+    pub(super) fn gen_time(&self) -> &str {
+        &self.info.gen_time
+    }
+}
+
+impl PackTimestamp {
+    /// Obtain a timestamp token for `manifest` (already finalized) from the
+    /// Time Stamp Authority at `tsa_url`.
+    pub fn request(manifest: &Manifest, tsa_url: &str) -> Result<Self, String> {
+        let digest = Sha256::digest(manifest.to_canonical_bytes());
+        let nonce = nonce(&manifest.pack_id);
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(Duration::from_secs(30))
+            .timeout_read(Duration::from_secs(60))
+            .build();
+        let response = agent
+            .post(tsa_url)
+            .set("Content-Type", "application/timestamp-query")
+            .send_bytes(&request_body(&digest, nonce))
+            .map_err(|e| format!("Timestamp request to {tsa_url} failed: {e}"))?;
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_end(&mut body)
+            .map_err(|e| format!("Cannot read timestamp response from {tsa_url}: {e}"))?;
+
+        let token = response_token(&body)
+            .map_err(|e| format!("Invalid timestamp response from {tsa_url}: {e}"))?;
+        let info = parse_token(token)
+            .map_err(|e| format!("Invalid timestamp token from {tsa_url}: {e}"))?
+            .info;
+        if info.imprint != digest.as_slice() {
+            return Err(format!(
+                "Timestamp token from {tsa_url} is for a different message"
+            ));
+        }
+        if info.nonce.as_deref() != Some(&der::encode_unsigned(nonce)[2..]) {
+            return Err(format!(
+                "Timestamp token from {tsa_url} does not echo the request nonce"
+            ));
+        }
+        Ok(Self {
+            version: TIMESTAMP_VERSION.to_string(),
+            pack_id: manifest.pack_id.clone(),
+            tsa_url: tsa_url.to_string(),
+            message_imprint: format!("sha256:{}", hex::encode(digest)),
+            gen_time: info.gen_time,
+            token: STANDARD.encode(token),
+        })
+    }
+
+    /// Check the token stamps `manifest`, returning its `genTime`.
+    ///
+    /// With `trust`, the token's CMS signature must also verify under a TSA
+    /// certificate that chains to one of its anchors. Without it the
+    /// signature is not checked, so the time is only what the file claims.
+    pub fn verify(&self, manifest: &Manifest, trust: Option<&TsaTrust>) -> Result<String, String> {
+        if self.version != TIMESTAMP_VERSION {
+            return Err(format!("unsupported timestamp version {}", self.version));
+        }
+        if self.pack_id != manifest.pack_id {
+            return Err("timestamp is for a different pack_id".to_string());
+        }
+        let token = STANDARD
+            .decode(&self.token)
+            .map_err(|e| format!("token is not base64: {e}"))?;
+        let token = parse_token(&token)?;
+        let info = &token.info;
+        if info.imprint_algorithm != SHA256_OID {
+            return Err("token message imprint is not sha256".to_string());
+        }
+        let digest = Sha256::digest(manifest.to_canonical_bytes());
+        if info.imprint != digest.as_slice() {
+            return Err("token does not stamp this manifest".to_string());
+        }
+        if self.message_imprint != format!("sha256:{}", hex::encode(digest))
+            || self.gen_time != info.gen_time
+        {
+            return Err(format!("{TIMESTAMP_FILE} fields disagree with its token"));
+        }
+        if let Some(trust) = trust {
+            trust.check(&token)?;
+        }
+        Ok(token.info.gen_time)
+    }
+}
+
+/// DER `TimeStampReq` for a sha256 `digest`, asking for the TSA certificate.
+fn request_body(digest: &[u8], nonce: u64) -> Vec<u8> {
+    let algorithm = [
+        der::encode(der::OID, SHA256_OID),
+        der::encode(der::NULL, &[]),
+    ]
+    .concat();
+    let imprint = [
+        der::encode(der::SEQUENCE, &algorithm),
+        der::encode(der::OCTET_STRING, digest),
+    ]
+    .concat();
+    der::encode(
+        der::SEQUENCE,
+        &[
+            der::encode_unsigned(1),
+            der::encode(der::SEQUENCE, &imprint),
+            der::encode_unsigned(nonce),
+            der::encode(der::BOOLEAN, &[0xFF]),
+        ]
+        .concat(),
+    )
+}
+
+/// The `timeStampToken` of a granted `TimeStampResp`.
+fn response_token(body: &[u8]) -> Result<&[u8], String> {
+    let mut response = Reader::new(Reader::new(body).expect(der::SEQUENCE, "TimeStampResp")?);
+    let mut status_info = Reader::new(response.expect(der::SEQUENCE, "PKIStatusInfo")?);
+    let status = status_info.expect(der::INTEGER, "PKIStatus")?;
+    // granted (0) or grantedWithMods (1)
+    if !matches!(status, [0] | [1]) {
+        let mut reason = format!("TSA refused the request (status {})", hex::encode(status));
+        if status_info.peek_tag() == Some(der::SEQUENCE) {
+            let mut texts = Reader::new(status_info.expect(der::SEQUENCE, "PKIFreeText")?);
+            while !texts.is_empty() {
+                let text = texts.next()?;
+                reason.push_str(&format!(": {}", String::from_utf8_lossy(text.content)));
+            }
+        }
+        return Err(reason);
+    }
+    if response.is_empty() {
+        return Err("granted response carries no token".to_string());
+    }
+    Ok(response.next()?.raw)
+}
+
+/// Unwrap `ContentInfo` → `SignedData` → `encapContentInfo` → `TSTInfo`,
+/// keeping the certificates and signer infos beside it.
+fn parse_token(token: &[u8]) -> Result<Token<'_>, String> {
+    let mut content_info = Reader::new(Reader::new(token).expect(der::SEQUENCE, "ContentInfo")?);
+    if content_info.expect(der::OID, "contentType")? != SIGNED_DATA_OID {
+        return Err("token is not CMS SignedData".to_string());
+    }
+    let signed_data = Reader::new(content_info.expect(der::context(0), "content")?)
+        .expect(der::SEQUENCE, "SignedData")?;
+    let mut signed_data = Reader::new(signed_data);
+    signed_data.expect(der::INTEGER, "version")?;
+    signed_data.expect(der::SET, "digestAlgorithms")?;
+    let mut encap = Reader::new(signed_data.expect(der::SEQUENCE, "encapContentInfo")?);
+    if encap.expect(der::OID, "eContentType")? != TST_INFO_OID {
+        return Err("token does not carry a TSTInfo".to_string());
+    }
+    let tst_info = Reader::new(encap.expect(der::context(0), "eContent")?)
+        .expect(der::OCTET_STRING, "eContent")?;
+    let mut certificates = Vec::new();
+    if signed_data.peek_tag() == Some(der::context(0)) {
+        let mut certs = Reader::new(signed_data.expect(der::context(0), "certificates")?);
+        while !certs.is_empty() {
+            let cert = certs.next()?;
+            // Attribute and other certificate formats are skipped.
+            if cert.tag == der::SEQUENCE {
+                certificates.push(cert.raw);
+            }
+        }
+    }
+    if signed_data.peek_tag() == Some(der::context(1)) {
+        signed_data.next()?;
+    }
+    let signer_infos = signed_data.expect(der::SET, "signerInfos")?;
+    Ok(Token {
+        info: parse_tst_info(tst_info)?,
+        tst_info,
+        certificates,
+        signer_infos,
+    })
+}
+
+fn parse_tst_info(bytes: &[u8]) -> Result<TstInfo, String> {
+    let mut info = Reader::new(Reader::new(bytes).expect(der::SEQUENCE, "TSTInfo")?);
+    info.expect(der::INTEGER, "version")?;
+    info.expect(der::OID, "policy")?;
+    let mut imprint = Reader::new(info.expect(der::SEQUENCE, "messageImprint")?);
+    let imprint_algorithm = Reader::new(imprint.expect(der::SEQUENCE, "hashAlgorithm")?)
+        .expect(der::OID, "algorithm")?
+        .to_vec();
+    let imprint = imprint.expect(der::OCTET_STRING, "hashedMessage")?.to_vec();
+    info.expect(der::INTEGER, "serialNumber")?;
+    let gen_time = generalized_time(info.expect(der::GENERALIZED_TIME, "genTime")?)?;
+    // accuracy and ordering may precede the nonce; the rest follow it.
+    let mut nonce = None;
+    while !info.is_empty() {
+        let element = info.next()?;
+        if element.tag == der::INTEGER {
+            nonce = Some(element.content.to_vec());
+            break;
+        }
+    }
+    Ok(TstInfo {
+        imprint_algorithm,
+        imprint,
+        gen_time,
+        nonce,
+    })
+}
+
+/// `YYYYMMDDHHMMSS[.fff]Z` as RFC 3339.
+fn generalized_time(content: &[u8]) -> Result<String, String> {
+    let text = std::str::from_utf8(content).map_err(|_| "genTime is not ASCII".to_string())?;
+    NaiveDateTime::parse_from_str(text, "%Y%m%d%H%M%S%.fZ")
+        .map(|time| time.and_utc().to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        .map_err(|e| format!("invalid genTime {text:?}: {e}"))
+}
+
+/// A request nonce that differs between seals of the same pack.
+fn nonce(pack_id: &str) -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let digest = Sha256::new()
+        .chain_update(now.to_be_bytes())
+        .chain_update(std::process::id().to_be_bytes())
+        .chain_update(pack_id)
+        .finalize();
+    u64::from_be_bytes(digest[..8].try_into().expect("sha256 is 32 bytes"))
+}
+
+/// Write `manifest.tst.json` into `pack_dir`.
+pub fn write_timestamp(pack_dir: &Path, timestamp: &PackTimestamp) -> io::Result<()> {
+    let mut bytes = serde_json::to_vec_pretty(timestamp).map_err(io::Error::other)?;
+    bytes.push(b'\n');
+    fs::write(pack_dir.join(TIMESTAMP_FILE), bytes)
+}
+
+/// Read `manifest.tst.json` from `pack_dir`; `Ok(None)` when the pack has no
+/// timestamp.
+pub fn read_timestamp(pack_dir: &Path) -> Result<Option<PackTimestamp>, String> {
+    read_timestamp_from(&DirSource::new(pack_dir))
+}
+
+/// [`read_timestamp`] for a pack held in any [`PackSource`].
+pub fn read_timestamp_from(source: &dyn PackSource) -> Result<Option<PackTimestamp>, String> {
+    let content = match source.read_to_string(TIMESTAMP_FILE) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("cannot read {TIMESTAMP_FILE}: {e}")),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("invalid {TIMESTAMP_FILE}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timestamp::trust::{
+        CONTENT_TYPE_OID, ED25519_OID, MESSAGE_DIGEST_OID, TIME_STAMPING_OID,
+    };
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::sync::Arc;
+    use std::thread;
+    use tiny_http::{Response, Server};
+
+    const BIT_STRING: u8 = 0x03;
+    const UTF8_STRING: u8 = 0x0C;
+    const UTC_TIME: u8 = 0x17;
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::new(
+            "2026-01-01T00:00:00Z".to_string(),
+            Some("stamped".to_string()),
+            "0.0.0".to_string(),
+            vec![],
+        );
+        manifest.finalize();
+        manifest
+    }
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn name(common_name: &str) -> Vec<u8> {
+        let cn = [
+            der::encode(der::OID, &[0x55, 0x04, 0x03]),
+            der::encode(UTF8_STRING, common_name.as_bytes()),
+        ]
+        .concat();
+        der::encode(
+            der::SEQUENCE,
+            &der::encode(der::SET, &der::encode(der::SEQUENCE, &cn)),
+        )
+    }
+
+    /// A v3 Ed25519 certificate for `subject`, issued and signed by `issuer`,
+    /// with one critical `extension` (its OID and value).
+    fn certificate(
+        serial: u64,
+        subject: (&str, &SigningKey),
+        issuer: (&str, &SigningKey),
+        extension: (&[u8], Vec<u8>),
+    ) -> Vec<u8> {
+        let algorithm = der::encode(der::SEQUENCE, &der::encode(der::OID, ED25519_OID));
+        let bits = |bytes: &[u8]| der::encode(BIT_STRING, &[&[0u8], bytes].concat());
+        let validity = [
+            der::encode(UTC_TIME, b"250101000000Z"),
+            der::encode(UTC_TIME, b"350101000000Z"),
+        ]
+        .concat();
+        let public_key = [
+            algorithm.clone(),
+            bits(subject.1.verifying_key().as_bytes()),
+        ]
+        .concat();
+        let extension = [
+            der::encode(der::OID, extension.0),
+            der::encode(der::BOOLEAN, &[0xFF]),
+            der::encode(der::OCTET_STRING, &extension.1),
+        ]
+        .concat();
+        let tbs = der::encode(
+            der::SEQUENCE,
+            &[
+                der::encode(der::context(0), &der::encode_unsigned(2)),
+                der::encode_unsigned(serial),
+                algorithm.clone(),
+                name(issuer.0),
+                der::encode(der::SEQUENCE, &validity),
+                name(subject.0),
+                der::encode(der::SEQUENCE, &public_key),
+                der::encode(
+                    der::context(3),
+                    &der::encode(der::SEQUENCE, &der::encode(der::SEQUENCE, &extension)),
+                ),
+            ]
+            .concat(),
+        );
+        let signature = issuer.1.sign(&tbs).to_bytes();
+        der::encode(der::SEQUENCE, &[tbs, algorithm, bits(&signature)].concat())
+    }
+
+    /// A self-signed CA certificate.
+    fn root_certificate(common_name: &str, key: &SigningKey) -> Vec<u8> {
+        let basic_constraints = der::encode(der::SEQUENCE, &der::encode(der::BOOLEAN, &[0xFF]));
+        certificate(
+            1,
+            (common_name, key),
+            (common_name, key),
+            (&[0x55, 0x1D, 0x13], basic_constraints),
+        )
+    }
+
+    /// The TSA certificate `grant` signs with, issued by "Test Root".
+    fn tsa_certificate() -> Vec<u8> {
+        let usage = der::encode(der::SEQUENCE, &der::encode(der::OID, TIME_STAMPING_OID));
+        certificate(
+            2,
+            ("Test TSA", &key(2)),
+            ("Test Root", &key(1)),
+            (&[0x55, 0x1D, 0x25], usage),
+        )
+    }
+
+    fn trust(root: &[u8]) -> TsaTrust {
+        let pem = format!(
+            "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+            STANDARD.encode(root)
+        );
+        TsaTrust::from_pem(pem.as_bytes()).unwrap()
+    }
+
+    /// A granted `TimeStampResp` for a `TimeStampReq`, as a TSA would send,
+    /// signed by the "Test TSA" certificate it carries.
+    fn grant(request: &[u8]) -> Vec<u8> {
+        let mut req = Reader::new(Reader::new(request).expect(der::SEQUENCE, "req").unwrap());
+        req.expect(der::INTEGER, "version").unwrap();
+        let imprint = req.next().unwrap().raw;
+        let nonce = req.next().unwrap().raw;
+        let tst_info = der::encode(
+            der::SEQUENCE,
+            &[
+                der::encode_unsigned(1),
+                der::encode(der::OID, &[0x2A, 0x03, 0x04]),
+                imprint.to_vec(),
+                der::encode_unsigned(42),
+                der::encode(der::GENERALIZED_TIME, b"20260315120000.25Z"),
+                der::encode(der::BOOLEAN, &[0x00]),
+                nonce.to_vec(),
+            ]
+            .concat(),
+        );
+        let encap = [
+            der::encode(der::OID, TST_INFO_OID),
+            der::encode(der::context(0), &der::encode(der::OCTET_STRING, &tst_info)),
+        ]
+        .concat();
+        let sha256 = der::encode(der::SEQUENCE, &der::encode(der::OID, SHA256_OID));
+        let attribute = |kind: &[u8], value: Vec<u8>| {
+            der::encode(
+                der::SEQUENCE,
+                &[der::encode(der::OID, kind), der::encode(der::SET, &value)].concat(),
+            )
+        };
+        let signed_attrs = [
+            attribute(CONTENT_TYPE_OID, der::encode(der::OID, TST_INFO_OID)),
+            attribute(
+                MESSAGE_DIGEST_OID,
+                der::encode(der::OCTET_STRING, &Sha256::digest(&tst_info)),
+            ),
+        ]
+        .concat();
+        let signature = key(2).sign(&der::encode(der::SET, &signed_attrs));
+        let signer_info = [
+            der::encode_unsigned(1),
+            der::encode(
+                der::SEQUENCE,
+                &[name("Test Root"), der::encode_unsigned(2)].concat(),
+            ),
+            sha256.clone(),
+            der::encode(der::context(0), &signed_attrs),
+            der::encode(der::SEQUENCE, &der::encode(der::OID, ED25519_OID)),
+            der::encode(der::OCTET_STRING, &signature.to_bytes()),
+        ]
+        .concat();
+        let signed_data = [
+            der::encode_unsigned(3),
+            der::encode(der::SET, &sha256),
+            der::encode(der::SEQUENCE, &encap),
+            der::encode(der::context(0), &tsa_certificate()),
+            der::encode(der::SET, &der::encode(der::SEQUENCE, &signer_info)),
+        ]
+        .concat();
+        let token = der::encode(
+            der::SEQUENCE,
+            &[
+                der::encode(der::OID, SIGNED_DATA_OID),
+                der::encode(der::context(0), &der::encode(der::SEQUENCE, &signed_data)),
+            ]
+            .concat(),
+        );
+        let status = der::encode(der::SEQUENCE, &der::encode_unsigned(0));
+        der::encode(der::SEQUENCE, &[status, token].concat())
+    }
+
+    fn spawn_tsa(answer: fn(&[u8]) -> Vec<u8>) -> (Arc<Server>, String) {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!("http://{}/tsa", server.server_addr());
+        thread::spawn({
+            let server = Arc::clone(&server);
+            move || {
+                for mut request in server.incoming_requests() {
+                    let mut body = Vec::new();
+                    request.as_reader().read_to_end(&mut body).unwrap();
+                    request.respond(Response::from_data(answer(&body))).unwrap();
+                }
+            }
+        });
+        (server, url)
+    }
+
+    #[test]
+    fn token_from_tsa_verifies_and_detects_tampering() {
+        let (_server, url) = spawn_tsa(grant);
+        let mut manifest = manifest();
+        let timestamp = PackTimestamp::request(&manifest, &url).unwrap();
+        assert_eq!(timestamp.gen_time, "2026-03-15T12:00:00.250Z");
+        assert_eq!(
+            timestamp.verify(&manifest, None),
+            Ok(timestamp.gen_time.clone())
+        );
+
+        let mut edited = timestamp.clone();
+        edited.gen_time = "2020-01-01T00:00:00Z".to_string();
+        assert!(edited.verify(&manifest, None).is_err());
+
+        manifest.note = Some("edited".to_string());
+        manifest.finalize();
+        let mut moved = timestamp.clone();
+        moved.pack_id = manifest.pack_id.clone();
+        assert_eq!(
+            moved.verify(&manifest, None),
+            Err("token does not stamp this manifest".to_string())
+        );
+    }
+
+    #[test]
+    fn token_signature_must_chain_to_the_tsa_cert() {
+        let (_server, url) = spawn_tsa(grant);
+        let manifest = manifest();
+        let timestamp = PackTimestamp::request(&manifest, &url).unwrap();
+        let root = trust(&root_certificate("Test Root", &key(1)));
+        assert_eq!(
+            timestamp.verify(&manifest, Some(&root)),
+            Ok("2026-03-15T12:00:00.250Z".to_string())
+        );
+
+        // A TSA the verifier never chose to trust.
+        let other = trust(&root_certificate("Other Root", &key(3)));
+        let err = timestamp.verify(&manifest, Some(&other)).unwrap_err();
+        assert!(err.starts_with("TSA certificate is not trusted"), "{err}");
+
+        // The signature is the token's last 64 bytes.
+        let mut token = STANDARD.decode(&timestamp.token).unwrap();
+        *token.last_mut().unwrap() ^= 1;
+        let forged = PackTimestamp {
+            token: STANDARD.encode(token),
+            ..timestamp.clone()
+        };
+        assert!(forged.verify(&manifest, None).is_ok());
+        assert_eq!(
+            forged.verify(&manifest, Some(&root)),
+            Err("token signature does not verify under any certificate it carries".to_string())
+        );
+    }
+
+    #[test]
+    fn rejected_request_reports_tsa_status() {
+        fn reject(_: &[u8]) -> Vec<u8> {
+            let text = der::encode(0x0C, b"bad alg");
+            let status = [der::encode_unsigned(2), der::encode(der::SEQUENCE, &text)].concat();
+            der::encode(der::SEQUENCE, &der::encode(der::SEQUENCE, &status))
+        }
+        let (_server, url) = spawn_tsa(reject);
+        let err = PackTimestamp::request(&manifest(), &url).unwrap_err();
+        assert!(
+            err.ends_with("TSA refused the request (status 02): bad alg"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! The TSA side of a timestamp token: its CMS signature, and the TSA
+//! certificate's chain to an anchor the verifier chose.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::DateTime;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use webpki::ring as algs;
+use webpki::{anchor_from_trusted_cert, EndEntityCert, KeyUsage};
+
+use super::der::{self, Reader};
+use super::token::{Token, SHA256_OID, TST_INFO_OID};
+
+/// id-sha384 (2.16.840.1.101.3.4.2.2).
+const SHA384_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02];
+/// id-sha512 (2.16.840.1.101.3.4.2.3).
+const SHA512_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+/// rsaEncryption (1.2.840.113549.1.1.1), hashed with the digest algorithm.
+const RSA_OID: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01];
+/// sha256WithRSAEncryption (1.2.840.113549.1.1.11).
+const RSA_SHA256_OID: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0B];
+/// sha384WithRSAEncryption (1.2.840.113549.1.1.12).
+const RSA_SHA384_OID: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C];
+/// sha512WithRSAEncryption (1.2.840.113549.1.1.13).
+const RSA_SHA512_OID: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0D];
+/// ecdsa-with-SHA256 (1.2.840.10045.4.3.2).
+const ECDSA_SHA256_OID: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02];
+/// ecdsa-with-SHA384 (1.2.840.10045.4.3.3).
+const ECDSA_SHA384_OID: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03];
+/// id-Ed25519 (1.3.101.112).
+pub(super) const ED25519_OID: &[u8] = &[0x2B, 0x65, 0x70];
+/// id-contentType signed attribute (1.2.840.113549.1.9.3).
+pub(super) const CONTENT_TYPE_OID: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x03];
+/// id-messageDigest signed attribute (1.2.840.113549.1.9.4).
+pub(super) const MESSAGE_DIGEST_OID: &[u8] =
+    &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x04];
+/// id-kp-timeStamping (1.3.6.1.5.5.7.3.8), required of the TSA certificate.
+pub(super) const TIME_STAMPING_OID: &[u8] = &[0x2B, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x08];
+
+/// `verify --tsa-cert`: the CA certificates a timestamp token's TSA
+/// certificate must chain to.
+#[derive(Debug, Clone)]
+pub struct TsaTrust {
+    anchors: Vec<CertificateDer<'static>>,
+}
+
+impl TsaTrust {
+    /// Read the PEM certificates in `path`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let pem = fs::read(path)
+            .map_err(|e| format!("Cannot read TSA certificate {}: {e}", path.display()))?;
+        Self::from_pem(&pem).map_err(|e| format!("Invalid TSA certificate {}: {e}", path.display()))
+    }
+
+    pub fn from_pem(pem: &[u8]) -> Result<Self, String> {
+        let anchors = CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        if anchors.is_empty() {
+            return Err("no PEM CERTIFICATE block".to_string());
+        }
+        for anchor in &anchors {
+            anchor_from_trusted_cert(anchor).map_err(|e| e.to_string())?;
+        }
+        Ok(Self { anchors })
+    }
+
+    /// Check `token` is signed by the TSA: its one signer's attributes must
+    /// digest the `TSTInfo`, the signature over them must verify under a
+    /// certificate the token carries, and that certificate must be valid for
+    /// time stamping at `genTime` through a chain to one of the anchors.
+    pub(super) fn check(&self, token: &Token<'_>) -> Result<(), String> {
+        let signer = SignerInfo::parse(token.signer_infos)?;
+        if signer.content_type != Some(TST_INFO_OID) {
+            return Err("token signature does not cover a TSTInfo".to_string());
+        }
+        let digest = digest(signer.digest_algorithm, token.tst_info)?;
+        if signer.message_digest != Some(digest.as_slice()) {
+            return Err("token signature does not cover its TSTInfo".to_string());
+        }
+        let algorithms = signature_algorithms(signer.signature_algorithm, signer.digest_algorithm)?;
+        // Signed attributes are signed as a SET OF, not under the [0] that
+        // stores them.
+        let signed = [&[der::SET], &signer.signed_attrs[1..]].concat();
+
+        let certificates: Vec<CertificateDer<'_>> = token
+            .certificates
+            .iter()
+            .map(|cert| CertificateDer::from(*cert))
+            .collect();
+        // Whichever carried certificate the signature verifies under is the
+        // signer; it is trusted only once it chains to an anchor.
+        let Some(index) = certificates.iter().position(|cert| {
+            EndEntityCert::try_from(cert).is_ok_and(|cert| {
+                algorithms.iter().any(|alg| {
+                    cert.verify_signature(*alg, &signed, signer.signature)
+                        .is_ok()
+                })
+            })
+        }) else {
+            return Err(if certificates.is_empty() {
+                "token carries no TSA certificate".to_string()
+            } else {
+                "token signature does not verify under any certificate it carries".to_string()
+            });
+        };
+        let signer_cert = EndEntityCert::try_from(&certificates[index])
+            .map_err(|e| format!("invalid TSA certificate: {e}"))?;
+        let intermediates: Vec<CertificateDer<'_>> = certificates
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, cert)| cert.clone())
+            .collect();
+        let anchors = self
+            .anchors
+            .iter()
+            .map(anchor_from_trusted_cert)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("invalid TSA certificate: {e}"))?;
+        signer_cert
+            .verify_for_usage(
+                webpki::ALL_VERIFICATION_ALGS,
+                &anchors,
+                &intermediates,
+                unix_time(token.gen_time())?,
+                KeyUsage::required(TIME_STAMPING_OID),
+                None,
+                None,
+            )
+            .map_err(|e| format!("TSA certificate is not trusted: {e:?}"))?;
+        Ok(())
+    }
+}
+
+/// The `SignerInfo` fields the signature check reads.
+struct SignerInfo<'a> {
+    digest_algorithm: &'a [u8],
+    /// The full `[0]` element, tag included.
+    signed_attrs: &'a [u8],
+    content_type: Option<&'a [u8]>,
+    message_digest: Option<&'a [u8]>,
+    signature_algorithm: &'a [u8],
+    signature: &'a [u8],
+}
+
+impl<'a> SignerInfo<'a> {
+    fn parse(signer_infos: &'a [u8]) -> Result<Self, String> {
+        let mut infos = Reader::new(signer_infos);
+        if infos.is_empty() {
+            return Err("token is not signed".to_string());
+        }
+        let mut info = Reader::new(infos.expect(der::SEQUENCE, "SignerInfo")?);
+        if !infos.is_empty() {
+            return Err("token has more than one signer".to_string());
+        }
+        info.expect(der::INTEGER, "version")?;
+        // issuerAndSerialNumber or subjectKeyIdentifier; the signer is found
+        // by its signature instead.
+        info.next()?;
+        let digest_algorithm = algorithm(info.expect(der::SEQUENCE, "digestAlgorithm")?)?;
+        let attrs = info.next()?;
+        if attrs.tag != der::context(0) {
+            return Err("token signature has no signed attributes".to_string());
+        }
+        let signature_algorithm = algorithm(info.expect(der::SEQUENCE, "signatureAlgorithm")?)?;
+        let signature = info.expect(der::OCTET_STRING, "signature")?;
+
+        let (mut content_type, mut message_digest) = (None, None);
+        let mut list = Reader::new(attrs.content);
+        while !list.is_empty() {
+            let mut attribute = Reader::new(list.expect(der::SEQUENCE, "Attribute")?);
+            let kind = attribute.expect(der::OID, "attrType")?;
+            let mut values = Reader::new(attribute.expect(der::SET, "attrValues")?);
+            if kind == CONTENT_TYPE_OID {
+                content_type = Some(values.expect(der::OID, "contentType")?);
+            } else if kind == MESSAGE_DIGEST_OID {
+                message_digest = Some(values.expect(der::OCTET_STRING, "messageDigest")?);
+            }
+        }
+        Ok(Self {
+            digest_algorithm,
+            signed_attrs: attrs.raw,
+            content_type,
+            message_digest,
+            signature_algorithm,
+            signature,
+        })
+    }
+}
+
+/// The OID of an `AlgorithmIdentifier`.
+fn algorithm(identifier: &[u8]) -> Result<&[u8], String> {
+    Reader::new(identifier).expect(der::OID, "algorithm")
+}
+
+fn digest(algorithm: &[u8], content: &[u8]) -> Result<Vec<u8>, String> {
+    match algorithm {
+        SHA256_OID => Ok(Sha256::digest(content).to_vec()),
+        SHA384_OID => Ok(Sha384::digest(content).to_vec()),
+        SHA512_OID => Ok(Sha512::digest(content).to_vec()),
+        _ => Err("unsupported token digest algorithm".to_string()),
+    }
+}
+
+/// The algorithms a CMS `signatureAlgorithm` may mean; ECDSA names only the
+/// hash, so both supported curves are candidates.
+fn signature_algorithms(
+    signature: &[u8],
+    digest: &[u8],
+) -> Result<Vec<&'static dyn SignatureVerificationAlgorithm>, String> {
+    Ok(match (signature, digest) {
+        (RSA_SHA256_OID, _) | (RSA_OID, SHA256_OID) => vec![algs::RSA_PKCS1_2048_8192_SHA256],
+        (RSA_SHA384_OID, _) | (RSA_OID, SHA384_OID) => vec![algs::RSA_PKCS1_2048_8192_SHA384],
+        (RSA_SHA512_OID, _) | (RSA_OID, SHA512_OID) => vec![algs::RSA_PKCS1_2048_8192_SHA512],
+        (ECDSA_SHA256_OID, _) => vec![algs::ECDSA_P256_SHA256, algs::ECDSA_P384_SHA256],
+        (ECDSA_SHA384_OID, _) => vec![algs::ECDSA_P256_SHA384, algs::ECDSA_P384_SHA384],
+        (ED25519_OID, _) => vec![algs::ED25519],
+        _ => return Err("unsupported token signature algorithm".to_string()),
+    })
+}
+
+/// An RFC 3339 `genTime` as the time the chain is checked at.
+fn unix_time(gen_time: &str) -> Result<UnixTime, String> {
+    let seconds = DateTime::parse_from_rfc3339(gen_time)
+        .map_err(|e| format!("invalid genTime {gen_time:?}: {e}"))?
+        .timestamp();
+    let seconds =
+        u64::try_from(seconds).map_err(|_| format!("genTime {gen_time} is before 1970"))?;
+    Ok(UnixTime::since_unix_epoch(Duration::from_secs(seconds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchors_must_be_pem_certificates() {
+        assert_eq!(
+            TsaTrust::from_pem(b"not a certificate").unwrap_err(),
+            "no PEM CERTIFICATE block"
+        );
+        let garbage = b"-----BEGIN CERTIFICATE-----\nMAA=\n-----END CERTIFICATE-----\n";
+        assert!(TsaTrust::from_pem(garbage).is_err());
+    }
+}
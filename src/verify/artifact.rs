@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::catalog::SchemaCatalog;
+use super::schema::{parse_json, validate_builtin};
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Version tag for `pack schema validate --json` output.
+pub const SCHEMA_VALIDATE_REPORT_VERSION: &str = "pack.schema_validate.v0";
+
+/// `schema` of a report checked by a schema compiled into pack.
+pub const BUILTIN_SCHEMA: &str = "built-in";
+
+/// Whether one artifact file matches its schema, as printed by
+/// `pack schema validate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaValidateReport {
+    pub version: String,
+    /// `PASS`, `FAIL`, or `NO_SCHEMA` when no schema applies to the file.
+    pub outcome: String,
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_version: Option<String>,
+    /// `built-in`, or the `--schema-dir` file the artifact was checked against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl SchemaValidateReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("schema validate report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![format!("{} {}", self.outcome, self.file)];
+        if let Some(version) = &self.artifact_version {
+            lines.push(format!("artifact_version: {version}"));
+        }
+        if let Some(schema) = &self.schema {
+            lines.push(format!("schema: {schema}"));
+        }
+        if let Some(reason) = &self.reason {
+            lines.push(format!("reason: {reason}"));
+        }
+        lines.join("\n")
+    }
+
+    fn exit_code(&self) -> u8 {
+        if self.outcome == "PASS" {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Execute `pack schema validate`: check `file` against the schema for its
+/// artifact version, which is `artifact_version` if given and otherwise its
+/// top-level JSON `version`.
+///
+/// A schema in `schema_dir` for that version is used in place of the
+/// built-in one. Exits 0 on `PASS`, 1 on `FAIL` or `NO_SCHEMA`, and refuses
+/// with `E_IO` when the file or the schema directory cannot be read.
+pub fn execute_schema_validate(
+    file: &Path,
+    artifact_version: Option<&str>,
+    schema_dir: Option<&Path>,
+    json: bool,
+) -> (String, u8) {
+    let catalog = match schema_dir.map(SchemaCatalog::load).transpose() {
+        Ok(catalog) => catalog.unwrap_or_default(),
+        Err(refusal) => return (refusal.to_json(), 2),
+    };
+    let content = match fs::read(file) {
+        Ok(content) => content,
+        Err(e) => {
+            let refusal = RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot read {}: {e}", file.display())),
+                None,
+            );
+            return (refusal.to_json(), 2);
+        }
+    };
+    let report = validate_file(file, &content, artifact_version, &catalog);
+    let output = if json {
+        report.to_json()
+    } else {
+        report.to_human()
+    };
+    (output, report.exit_code())
+}
+
+fn validate_file(
+    file: &Path,
+    content: &[u8],
+    artifact_version: Option<&str>,
+    catalog: &SchemaCatalog,
+) -> SchemaValidateReport {
+    let version = artifact_version.map(str::to_string).or_else(|| {
+        parse_json(content)
+            .ok()?
+            .get("version")?
+            .as_str()
+            .map(str::to_string)
+    });
+    let mut report = SchemaValidateReport {
+        version: SCHEMA_VALIDATE_REPORT_VERSION.to_string(),
+        outcome: "NO_SCHEMA".to_string(),
+        file: file.display().to_string(),
+        artifact_version: version.clone(),
+        schema: None,
+        reason: None,
+    };
+    let Some(version) = version else {
+        report.reason = Some(
+            "no top-level \"version\" string; pass --artifact-version to name the schema"
+                .to_string(),
+        );
+        return report;
+    };
+    let checked = match catalog.validate(&version, content) {
+        Some(result) => {
+            let path = catalog.schema_path(&version).map(Path::display);
+            report.schema = path.map(|path| path.to_string());
+            Some(result)
+        }
+        None => validate_builtin(&version, content).inspect(|_| {
+            report.schema = Some(BUILTIN_SCHEMA.to_string());
+        }),
+    };
+    match checked {
+        Some(Ok(())) => report.outcome = "PASS".to_string(),
+        Some(Err(reason)) => {
+            report.outcome = "FAIL".to_string();
+            report.reason = Some(reason);
+        }
+        None => report.reason = Some(format!("no schema for artifact version \"{version}\"")),
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn outcome(output: &str) -> serde_json::Value {
+        serde_json::from_str(output).unwrap()
+    }
+
+    #[test]
+    fn validates_against_built_in_and_directory_schemas() {
+        let tmp = TempDir::new().unwrap();
+        let rules = tmp.path().join("rules.json");
+        std::fs::write(&rules, r#"{"version": "verify.rules.v0"}"#).unwrap();
+        let (output, code) = execute_schema_validate(&rules, None, None, true);
+        let report = outcome(&output);
+        assert_eq!((report["outcome"].as_str(), code), (Some("FAIL"), 1));
+        assert_eq!(report["schema"], BUILTIN_SCHEMA);
+        assert_eq!(report["reason"], "missing or non-array \"rules\" field");
+
+        let (output, code) = execute_schema_validate(&rules, Some("loans.v1"), None, true);
+        assert_eq!(
+            (outcome(&output)["outcome"].as_str(), code),
+            (Some("NO_SCHEMA"), 1)
+        );
+
+        let schemas = tmp.path().join("schemas");
+        std::fs::create_dir(&schemas).unwrap();
+        std::fs::write(
+            schemas.join("verify.rules.v0.schema.json"),
+            r#"{"type": "object", "required": ["version"]}"#,
+        )
+        .unwrap();
+        let (output, code) = execute_schema_validate(&rules, None, Some(&schemas), false);
+        assert_eq!(code, 0, "{output}");
+        assert!(output.starts_with("PASS "), "{output}");
+        assert!(output.contains("verify.rules.v0.schema.json"), "{output}");
+
+        let (output, code) =
+            execute_schema_validate(&tmp.path().join("missing.json"), None, None, true);
+        assert_eq!(code, 2);
+        assert!(output.contains("E_IO"), "{output}");
+    }
+}
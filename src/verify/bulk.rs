@@ -0,0 +1,280 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::seal::archive::is_archive;
+
+use super::command::{verify_pack, VerifyOptions};
+use super::report::{VerifyOutcome, VerifyReport};
+
+/// Version tag for `pack verify --all --json` output.
+pub const VERIFY_ALL_REPORT_VERSION: &str = "pack.verify_all.v0";
+
+/// Every pack found under a root, as printed by `pack verify --all`.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyAllReport {
+    pub version: String,
+    /// `OK` when every pack is OK, `INVALID` when any pack is not, and
+    /// `REFUSAL` when the root could not be walked.
+    pub outcome: VerifyOutcome,
+    pub root: String,
+    pub summary: VerifyAllSummary,
+    /// One entry per pack in path order.
+    pub packs: Vec<PackVerifyResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refusal: Option<serde_json::Value>,
+}
+
+/// Pack counts by outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct VerifyAllSummary {
+    pub total: usize,
+    pub ok: usize,
+    pub invalid: usize,
+    pub refusal: usize,
+}
+
+/// One pack's `pack.verify.v0` report, with its path relative to the root.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackVerifyResult {
+    pub path: String,
+    #[serde(flatten)]
+    pub report: VerifyReport,
+}
+
+impl VerifyAllReport {
+    fn new(root: &Path, packs: Vec<PackVerifyResult>) -> Self {
+        let mut summary = VerifyAllSummary {
+            total: packs.len(),
+            ..VerifyAllSummary::default()
+        };
+        for pack in &packs {
+            match pack.report.outcome {
+                VerifyOutcome::OK => summary.ok += 1,
+                VerifyOutcome::INVALID => summary.invalid += 1,
+                VerifyOutcome::REFUSAL => summary.refusal += 1,
+            }
+        }
+        let outcome = if summary.ok == summary.total {
+            VerifyOutcome::OK
+        } else {
+            VerifyOutcome::INVALID
+        };
+        Self {
+            version: VERIFY_ALL_REPORT_VERSION.to_string(),
+            outcome,
+            root: root.display().to_string(),
+            summary,
+            packs,
+            refusal: None,
+        }
+    }
+
+    fn refusal(root: &Path, message: String) -> Self {
+        Self {
+            version: VERIFY_ALL_REPORT_VERSION.to_string(),
+            outcome: VerifyOutcome::REFUSAL,
+            root: root.display().to_string(),
+            summary: VerifyAllSummary::default(),
+            packs: vec![],
+            refusal: Some(json!({ "code": "E_IO", "message": message })),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("verify report serialization cannot fail")
+    }
+
+    /// One `OUTCOME path` line per pack, with its findings or refusal, then
+    /// the totals.
+    pub fn to_human(&self) -> String {
+        let mut lines = Vec::new();
+        for pack in &self.packs {
+            lines.push(format!("{} {}", pack.report.outcome, pack.path));
+            for finding in &pack.report.invalid {
+                let mut entry = format!("  - {}", finding.code);
+                if let Some(path) = &finding.path {
+                    entry.push_str(&format!(" ({path})"));
+                }
+                lines.push(entry);
+            }
+            if let Some(refusal) = &pack.report.refusal {
+                lines.push(format!("  refusal: {refusal}"));
+            }
+        }
+        match &self.refusal {
+            Some(refusal) => {
+                lines.push(format!("pack verify --all: REFUSAL\n  refusal: {refusal}"))
+            }
+            None => lines.push(format!(
+                "pack verify --all: {} ({} packs: {} OK, {} INVALID, {} REFUSAL)",
+                self.outcome,
+                self.summary.total,
+                self.summary.ok,
+                self.summary.invalid,
+                self.summary.refusal
+            )),
+        }
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack verify --all`: verify every pack under `root` with `jobs`
+/// worker threads.
+///
+/// Returns (report, exit_code): 0 when every pack is OK, 1 when any is
+/// INVALID or refused, and 2 when `root` cannot be walked or holds no packs.
+pub fn execute_verify_all(
+    root: &Path,
+    json_output: bool,
+    options: &VerifyOptions,
+    jobs: usize,
+) -> (String, u8) {
+    let report = verify_all(root, options, jobs);
+    let exit_code = match report.outcome {
+        VerifyOutcome::OK => 0,
+        VerifyOutcome::INVALID => 1,
+        VerifyOutcome::REFUSAL => 2,
+    };
+    let output = if json_output {
+        report.to_json()
+    } else {
+        report.to_human()
+    };
+    (output, exit_code)
+}
+
+/// Verify every pack under `root` and return the aggregate report.
+pub fn verify_all(root: &Path, options: &VerifyOptions, jobs: usize) -> VerifyAllReport {
+    let packs = match discover_packs(root) {
+        Ok(packs) => packs,
+        Err(e) => {
+            return VerifyAllReport::refusal(
+                root,
+                format!("Cannot read pack root: {}: {e}", root.display()),
+            )
+        }
+    };
+    if packs.is_empty() {
+        return VerifyAllReport::refusal(root, format!("No packs found under {}", root.display()));
+    }
+
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(vec![None; packs.len()]);
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, packs.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(pack) = packs.get(index) else {
+                    break;
+                };
+                let report = verify_pack(pack, options);
+                reports.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(report);
+            });
+        }
+    });
+
+    let results = packs
+        .iter()
+        .zip(reports.into_inner().unwrap_or_else(|e| e.into_inner()))
+        .map(|(pack, report)| {
+            let path = pack.strip_prefix(root).unwrap_or(pack).to_string_lossy();
+            PackVerifyResult {
+                // The root itself is the only pack.
+                path: if path.is_empty() {
+                    ".".to_string()
+                } else {
+                    path.replace('\\', "/")
+                },
+                report: report.expect("every pack is verified before the scope ends"),
+            }
+        })
+        .collect();
+    VerifyAllReport::new(root, results)
+}
+
+/// Directories holding a `manifest.json` and `.pack.tar` files under `root`,
+/// in path order. A pack directory is not searched further, since packs
+/// embedded in it are its members; dot-prefixed entries and symlinks are
+/// skipped.
+pub fn discover_packs(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut packs = Vec::new();
+    if root.join("manifest.json").is_file() {
+        packs.push(root.to_path_buf());
+    } else {
+        discover_in(root, &mut packs)?;
+    }
+    Ok(packs)
+}
+
+fn discover_in(dir: &Path, packs: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if path.join("manifest.json").is_file() {
+                packs.push(path);
+            } else {
+                discover_in(&path, packs)?;
+            }
+        } else if file_type.is_file() && is_archive(&path) {
+            packs.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use tempfile::TempDir;
+
+    #[test]
+    fn every_pack_under_the_root_is_verified_in_path_order() {
+        let src = TempDir::new().unwrap();
+        let root = TempDir::new().unwrap();
+        let artifact = src.path().join("a.json");
+        fs::write(&artifact, r#"{"a": 1}"#).unwrap();
+        for output in ["2026/01/good", "2026/02/bad", "archive/good"] {
+            execute_seal(
+                std::slice::from_ref(&artifact),
+                Some(&root.path().join(output)),
+                None,
+            )
+            .unwrap();
+        }
+        fs::write(root.path().join("2026/02/bad/a.json"), "tampered").unwrap();
+        fs::create_dir_all(root.path().join(".staging/x")).unwrap();
+        fs::write(root.path().join(".staging/x/manifest.json"), "{}").unwrap();
+
+        let report = verify_all(root.path(), &VerifyOptions::default(), 2);
+        let paths: Vec<_> = report.packs.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, ["2026/01/good", "2026/02/bad", "archive/good"]);
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        assert_eq!(
+            report.summary,
+            VerifyAllSummary {
+                total: 3,
+                ok: 2,
+                invalid: 1,
+                refusal: 0
+            }
+        );
+
+        let empty = TempDir::new().unwrap();
+        let report = verify_all(empty.path(), &VerifyOptions::default(), 1);
+        assert_eq!(report.outcome, VerifyOutcome::REFUSAL);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use super::schema::parse_json;
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+/// Name of a schema in a schema directory: `<artifact_version>.schema.json`.
+pub const SCHEMA_FILE_SUFFIX: &str = ".schema.json";
+
+/// Errors listed in a failure reason before the rest are counted.
+const MAX_REPORTED_ERRORS: usize = 5;
+
+/// JSON Schema (draft 2020-12) documents loaded from a directory, keyed by
+/// the artifact version in their file name, so teams can check their own
+/// artifact types without a new pack release.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaCatalog {
+    schemas: BTreeMap<String, CatalogSchema>,
+}
+
+#[derive(Debug, Clone)]
+struct CatalogSchema {
+    path: PathBuf,
+    validator: jsonschema::Validator,
+}
+
+impl SchemaCatalog {
+    /// Compile every `*.schema.json` in `dir`; other files are ignored.
+    ///
+    /// An unreadable directory, a file that is not JSON, or a document that
+    /// is not a valid schema refuses with `E_IO`. Schemas are compiled
+    /// offline: a `$ref` must resolve within its own document.
+    pub fn load(dir: &Path) -> Result<Self, Box<RefusalEnvelope>> {
+        let refuse = |file: Option<&Path>, message: String| {
+            Box::new(RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(message),
+                Some(json!({
+                    "schema_dir": dir.display().to_string(),
+                    "file": file.map(|file| file.display().to_string()),
+                })),
+            ))
+        };
+        let entries = fs::read_dir(dir).map_err(|e| {
+            refuse(
+                None,
+                format!("Cannot read schema directory {}: {e}", dir.display()),
+            )
+        })?;
+        let mut schemas = BTreeMap::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| refuse(None, format!("Cannot read schema directory: {e}")))?
+                .path();
+            let Some(version) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(SCHEMA_FILE_SUFFIX))
+                .filter(|version| !version.is_empty())
+            else {
+                continue;
+            };
+            let schema = fs::read(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|e| e.to_string()))
+                .map_err(|e| {
+                    refuse(
+                        Some(&path),
+                        format!("Cannot read schema {}: {e}", path.display()),
+                    )
+                })?;
+            let validator = jsonschema::draft202012::new(&schema).map_err(|e| {
+                refuse(
+                    Some(&path),
+                    format!("Invalid JSON Schema {}: {e}", path.display()),
+                )
+            })?;
+            schemas.insert(
+                version.to_string(),
+                CatalogSchema {
+                    path: path.clone(),
+                    validator,
+                },
+            );
+        }
+        Ok(Self { schemas })
+    }
+
+    /// Whether the catalog has a schema for `version`.
+    pub fn contains(&self, version: &str) -> bool {
+        self.schemas.contains_key(version)
+    }
+
+    /// The file holding the schema for `version`, if the catalog has one.
+    pub fn schema_path(&self, version: &str) -> Option<&Path> {
+        self.schemas
+            .get(version)
+            .map(|schema| schema.path.as_path())
+    }
+
+    /// Check `content` against the schema for `version`; `None` when the
+    /// catalog has no schema for it. The error names each failing location
+    /// as a JSON pointer.
+    pub fn validate(&self, version: &str, content: &[u8]) -> Option<Result<(), String>> {
+        let schema = self.schemas.get(version)?;
+        let instance = match parse_json(content) {
+            Ok(instance) => instance,
+            Err(reason) => return Some(Err(reason)),
+        };
+        let errors: Vec<String> = schema
+            .validator
+            .iter_errors(&instance)
+            .map(|error| {
+                let location = error.instance_path().to_string();
+                let location = if location.is_empty() { "/" } else { &location };
+                format!("{location}: {error}")
+            })
+            .collect();
+        Some(match errors.len() {
+            0 => Ok(()),
+            n if n > MAX_REPORTED_ERRORS => Err(format!(
+                "{}; and {} more",
+                errors[..MAX_REPORTED_ERRORS].join("; "),
+                n - MAX_REPORTED_ERRORS
+            )),
+            _ => Err(errors.join("; ")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn schemas_are_keyed_by_file_name_and_report_every_location() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("loans.v1.schema.json"),
+            r##"{
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "required": ["version", "rows"],
+                "properties": {"rows": {"type": "array", "items": {"$ref": "#/$defs/row"}}},
+                "$defs": {"row": {"type": "object", "required": ["id"]}}
+            }"##,
+        )
+        .unwrap();
+        fs::write(tmp.path().join("README.md"), "not a schema").unwrap();
+
+        let catalog = SchemaCatalog::load(tmp.path()).unwrap();
+        assert!(catalog.schema_path("README").is_none());
+        assert_eq!(catalog.validate("lock.v0", b"{}"), None);
+        assert_eq!(
+            catalog.validate(
+                "loans.v1",
+                br#"{"version": "loans.v1", "rows": [{"id": 1}]}"#
+            ),
+            Some(Ok(()))
+        );
+        let reason = catalog
+            .validate("loans.v1", br#"{"rows": [{"id": 1}, {}]}"#)
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            reason.contains("/: \"version\" is a required property"),
+            "{reason}"
+        );
+        assert!(
+            reason.contains("/rows/1: \"id\" is a required property"),
+            "{reason}"
+        );
+
+        fs::write(tmp.path().join("bad.schema.json"), r#"{"type": 7}"#).unwrap();
+        let refusal = SchemaCatalog::load(tmp.path()).unwrap_err();
+        assert_eq!(refusal.refusal.code, "E_IO");
+    }
+}
@@ -1,19 +1,79 @@
 use std::collections::HashSet;
-use std::fs;
+use std::io;
 use std::path::Path;
+use std::time::Instant;
 
-use sha2::{Digest, Sha256};
-
+use crate::cli::HashAlgorithm;
+use crate::events::{EventBus, PackEvent};
 use crate::seal::collect::is_safe_member_path;
-use crate::seal::manifest::Manifest;
+use crate::seal::hasher::{hash_algorithm_of, hash_buffer_size, hash_bytes, stream_hash};
+use crate::seal::manifest::{Manifest, PACKKEEP_FILE};
+use crate::sign::SIGNATURE_FILE;
+use crate::timestamp::TIMESTAMP_FILE;
 
-use super::report::{InvalidFinding, VerifyChecks};
+use super::catalog::SchemaCatalog;
+use super::report::{HashCheck, InvalidFinding, Severity, VerifyChecks, VerifyTimings};
 use super::schema::validate_schemas;
+use super::source::{DirSource, EntryKind, PackSource};
+use super::stream::FindingStream;
 
 /// Run all integrity checks on a parsed manifest against its pack directory.
 ///
 /// Returns (checks, findings). If findings is empty, the pack is OK.
 pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<InvalidFinding>) {
+    run_timed_checks(
+        manifest,
+        &DirSource::new(pack_dir),
+        &SchemaCatalog::default(),
+        &mut VerifyTimings::default(),
+        &mut FindingStream::new(&EventBus::default(), &[]),
+    )
+}
+
+/// [`run_checks`] against any [`PackSource`], recording how long each check
+/// takes in `timings` and announcing each hashed member and each finding as
+/// it is found on `stream`. Members are schema-checked against `schemas`
+/// before the compiled-in validators.
+pub(super) fn run_timed_checks(
+    manifest: &Manifest,
+    source: &dyn PackSource,
+    schemas: &SchemaCatalog,
+    timings: &mut VerifyTimings,
+    stream: &mut FindingStream,
+) -> (VerifyChecks, Vec<InvalidFinding>) {
+    run_checks_in_mode(manifest, source, false, schemas, timings, stream)
+}
+
+/// [`run_timed_checks`] without reading member bytes (`verify --quick`).
+///
+/// Members must still exist as regular files, and a recorded `size_bytes`
+/// must match the file (`SIZE_MISMATCH`); member hashes and schema
+/// validation are reported as skipped.
+pub(super) fn run_quick_checks(
+    manifest: &Manifest,
+    source: &dyn PackSource,
+    timings: &mut VerifyTimings,
+    stream: &mut FindingStream,
+) -> (VerifyChecks, Vec<InvalidFinding>) {
+    run_checks_in_mode(
+        manifest,
+        source,
+        true,
+        &SchemaCatalog::default(),
+        timings,
+        stream,
+    )
+}
+
+fn run_checks_in_mode(
+    manifest: &Manifest,
+    source: &dyn PackSource,
+    quick: bool,
+    schemas: &SchemaCatalog,
+    timings: &mut VerifyTimings,
+    stream: &mut FindingStream,
+) -> (VerifyChecks, Vec<InvalidFinding>) {
+    let mut lap = Instant::now();
     let mut checks = VerifyChecks {
         manifest_parse: true, // Already parsed if we got here
         ..Default::default()
@@ -25,20 +85,26 @@ pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<In
     if !checks.member_count {
         findings.push(InvalidFinding {
             code: "MEMBER_COUNT_MISMATCH".to_string(),
+            severity: Severity::Error,
             path: None,
             expected: Some(manifest.member_count.to_string()),
             actual: Some(manifest.members.len().to_string()),
         });
     }
+    timings.lap("member_count", &mut lap);
 
     // Check 2: member paths — unique, not reserved, safe
     let mut path_ok = true;
     let mut seen_paths = HashSet::new();
     for member in &manifest.members {
         // Reserved path check
-        if member.path == "manifest.json" {
+        if member.path == "manifest.json"
+            || member.path == SIGNATURE_FILE
+            || member.path == TIMESTAMP_FILE
+        {
             findings.push(InvalidFinding {
                 code: "RESERVED_MEMBER_PATH".to_string(),
+                severity: Severity::Error,
                 path: Some(member.path.clone()),
                 expected: None,
                 actual: None,
@@ -50,6 +116,7 @@ pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<In
         if !seen_paths.insert(&member.path) {
             findings.push(InvalidFinding {
                 code: "DUPLICATE_MEMBER_PATH".to_string(),
+                severity: Severity::Error,
                 path: Some(member.path.clone()),
                 expected: None,
                 actual: None,
@@ -61,6 +128,7 @@ pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<In
         if !is_safe_member_path(&member.path) {
             findings.push(InvalidFinding {
                 code: "UNSAFE_MEMBER_PATH".to_string(),
+                severity: Severity::Error,
                 path: Some(member.path.clone()),
                 expected: None,
                 actual: None,
@@ -69,16 +137,51 @@ pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<In
         }
     }
     checks.member_paths = path_ok;
+    timings.lap("member_paths", &mut lap);
 
-    // Check 3: each member exists as regular non-symlink file, and hash matches
+    // Check 3: each member exists as regular non-symlink file, and hash
+    // matches (or, quick, the recorded size does)
     let mut hashes_ok = true;
+    let mut sizes_ok = true;
+    let mut sizes_checked = false;
+    let buffer_size = hash_buffer_size();
+    if !quick {
+        stream.events().notify(&PackEvent::HashingStarted {
+            members: manifest.members.len(),
+            bytes: manifest.members.iter().filter_map(|m| m.size_bytes).sum(),
+        });
+    }
     for member in &manifest.members {
-        let member_path = pack_dir.join(&member.path);
+        // Report findings so far before reading the next member
+        stream.flush(&findings);
+
+        // Check exists; permission errors are unreadable, not missing
+        let meta = match source.metadata(&member.path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                findings.push(InvalidFinding {
+                    code: "MISSING_MEMBER".to_string(),
+                    severity: Severity::Error,
+                    path: Some(member.path.clone()),
+                    expected: None,
+                    actual: None,
+                });
+                hashes_ok = false;
+                continue;
+            }
+            Err(e) => {
+                findings.push(unreadable_member(&member.path, &e));
+                hashes_ok = false;
+                continue;
+            }
+        };
+        let size = meta.len;
 
-        // Check exists
-        if !member_path.exists() {
+        // Symlinks, directories and devices are not members
+        if meta.kind != EntryKind::File {
             findings.push(InvalidFinding {
-                code: "MISSING_MEMBER".to_string(),
+                code: "NON_REGULAR_MEMBER".to_string(),
+                severity: Severity::Error,
                 path: Some(member.path.clone()),
                 expected: None,
                 actual: None,
@@ -87,79 +190,104 @@ pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<In
             continue;
         }
 
-        // Check symlink
-        if let Ok(meta) = fs::symlink_metadata(&member_path) {
-            if meta.is_symlink() {
+        // Check the recorded size first: a member of the wrong size cannot
+        // hash correctly, so it is not read at all. An encrypted member's
+        // size is that of its plaintext, checked under --decrypt-key.
+        if let Some(expected) = member
+            .size_bytes
+            .filter(|_| member.ciphertext_hash.is_none())
+        {
+            sizes_checked = true;
+            if size != expected {
                 findings.push(InvalidFinding {
-                    code: "NON_REGULAR_MEMBER".to_string(),
+                    code: "SIZE_MISMATCH".to_string(),
+                    severity: Severity::Error,
                     path: Some(member.path.clone()),
-                    expected: None,
-                    actual: None,
-                });
-                hashes_ok = false;
-                continue;
-            }
-            if !meta.is_file() {
-                findings.push(InvalidFinding {
-                    code: "NON_REGULAR_MEMBER".to_string(),
-                    path: Some(member.path.clone()),
-                    expected: None,
-                    actual: None,
+                    expected: Some(expected.to_string()),
+                    actual: Some(size.to_string()),
                 });
+                sizes_ok = false;
                 hashes_ok = false;
                 continue;
             }
         }
+        if quick {
+            continue;
+        }
 
-        // Check hash
-        if let Ok(content) = fs::read(&member_path) {
-            let mut hasher = Sha256::new();
-            hasher.update(&content);
-            let hash = format!("sha256:{}", hex::encode(hasher.finalize()));
-            if hash != member.bytes_hash {
-                findings.push(InvalidFinding {
-                    code: "HASH_MISMATCH".to_string(),
-                    path: Some(member.path.clone()),
-                    expected: Some(member.bytes_hash.clone()),
-                    actual: Some(hash),
-                });
+        // Check hash (streamed, so multi-GB members do not load into memory)
+        // with the algorithm named by the recorded hash's prefix. For an
+        // encrypted member this is the hash of its ciphertext.
+        let expected = member.stored_hash();
+        let Some(algorithm) = hash_algorithm_of(expected) else {
+            findings.push(InvalidFinding {
+                code: "UNSUPPORTED_HASH_ALGORITHM".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some("sha256, sha512, or blake3".to_string()),
+                actual: Some(expected.to_string()),
+            });
+            hashes_ok = false;
+            continue;
+        };
+        let hashed = source.open(&member.path).and_then(|reader| {
+            stream_hash(reader, None::<&mut io::Sink>, buffer_size, algorithm, true)
+        });
+        let hash = match hashed.map(|(hash, _)| hash) {
+            Ok(hash) => hash,
+            Err(e) => {
+                findings.push(unreadable_member(&member.path, &e));
                 hashes_ok = false;
+                continue;
             }
+        };
+        stream.events().notify(&PackEvent::MemberVerified {
+            path: member.path.clone(),
+            size,
+            ok: hash == expected,
+        });
+        if hash != expected {
+            findings.push(InvalidFinding {
+                code: "HASH_MISMATCH".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(expected.to_string()),
+                actual: Some(hash),
+            });
+            hashes_ok = false;
         }
     }
-    checks.member_hashes = hashes_ok;
+    stream.flush(&findings);
+    checks.member_sizes = sizes_checked.then_some(sizes_ok);
+    if quick {
+        checks.member_hashes = HashCheck::Skipped;
+        timings.lap("member_sizes", &mut lap);
+    } else {
+        checks.member_hashes = hashes_ok.into();
+        timings.lap("member_hashes", &mut lap);
+    }
 
-    // Check 4: no extra files beyond manifest.json + declared members
+    // Check 4: no extra files beyond manifest.json, its signature and
+    // timestamp, and declared members
     let mut extra_ok = true;
-    if let Ok(entries) = fs::read_dir(pack_dir) {
-        let declared: HashSet<String> = manifest.members.iter().map(|m| m.path.clone()).collect();
-
-        for entry in entries.flatten() {
-            let name = entry.file_name().to_string_lossy().to_string();
-            if name == "manifest.json" {
-                continue;
-            }
-            if entry.path().is_dir() {
-                // Check recursively for declared members with dir prefixes
-                check_extra_recursive(
-                    &entry.path(),
-                    &name,
-                    &declared,
-                    &mut findings,
-                    &mut extra_ok,
-                );
-            } else if !declared.contains(&name) {
-                findings.push(InvalidFinding {
-                    code: "EXTRA_MEMBER".to_string(),
-                    path: Some(name),
-                    expected: None,
-                    actual: None,
-                });
-                extra_ok = false;
-            }
+    let declared: HashSet<&str> = manifest.members.iter().map(|m| m.path.as_str()).collect();
+    for path in source.files() {
+        if path == "manifest.json" || path == SIGNATURE_FILE || path == TIMESTAMP_FILE {
+            continue;
+        }
+        if !declared.contains(path.as_str()) {
+            findings.push(InvalidFinding {
+                code: "EXTRA_MEMBER".to_string(),
+                severity: Severity::Error,
+                path: Some(path),
+                expected: None,
+                actual: None,
+            });
+            extra_ok = false;
         }
     }
     checks.extra_members = extra_ok;
+    timings.lap("extra_members", &mut lap);
 
     // Check 5: recompute pack_id
     let recomputed = manifest.recompute_pack_id();
@@ -167,41 +295,105 @@ pub fn run_checks(manifest: &Manifest, pack_dir: &Path) -> (VerifyChecks, Vec<In
     if !checks.pack_id {
         findings.push(InvalidFinding {
             code: "PACK_ID_MISMATCH".to_string(),
+            severity: Severity::Error,
             path: None,
             expected: Some(manifest.pack_id.clone()),
             actual: Some(recomputed),
         });
     }
+    timings.lap("pack_id", &mut lap);
 
-    // Schema validation: validate known artifact types against local catalog
-    let (schema_outcome, schema_findings) = validate_schemas(&manifest.members, pack_dir);
+    // Check 6: recorded empty directories are still empty
+    let (empty_dirs_ok, empty_dir_findings) = check_empty_dirs(manifest);
+    checks.empty_dirs = empty_dirs_ok;
+    findings.extend(empty_dir_findings);
+    timings.lap("empty_dirs", &mut lap);
+
+    // Schema validation: validate known artifact types against the catalog
+    if quick {
+        return (checks, findings);
+    }
+    let (schema_outcome, schema_members, schema_findings) =
+        validate_schemas(&manifest.members, source, schemas);
     checks.schema_validation = schema_outcome.as_str().to_string();
+    checks.schema_members = schema_members;
     findings.extend(schema_findings);
+    timings.lap("schema_validation", &mut lap);
 
     (checks, findings)
 }
 
-fn check_extra_recursive(
-    dir: &Path,
-    prefix: &str,
-    declared: &HashSet<String>,
-    findings: &mut Vec<InvalidFinding>,
-    extra_ok: &mut bool,
-) {
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let relative = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
-            if entry.path().is_dir() {
-                check_extra_recursive(&entry.path(), &relative, declared, findings, extra_ok);
-            } else if !declared.contains(&relative) {
+/// Check directories the pack records as empty, either as `.packkeep` members
+/// or in `provenance.empty_dirs`: a `.packkeep` must be zero bytes and alone
+/// in its directory, and a listed directory must hold no members.
+///
+/// Returns `None` for packs that record no empty directories.
+fn check_empty_dirs(manifest: &Manifest) -> (Option<bool>, Vec<InvalidFinding>) {
+    let keep_suffix = format!("/{PACKKEEP_FILE}");
+    let mut dirs = Vec::new();
+    let mut findings = Vec::new();
+    for member in &manifest.members {
+        let Some(dir) = member.path.strip_suffix(&keep_suffix) else {
+            continue;
+        };
+        let algorithm = hash_algorithm_of(&member.bytes_hash).unwrap_or(HashAlgorithm::Sha256);
+        let empty_hash = hash_bytes(b"", algorithm);
+        if member.bytes_hash != empty_hash {
+            findings.push(InvalidFinding {
+                code: "PACKKEEP_NOT_EMPTY".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(empty_hash),
+                actual: Some(member.bytes_hash.clone()),
+            });
+        }
+        dirs.push((dir, Some(member.path.as_str())));
+    }
+    if let Some(provenance) = &manifest.provenance {
+        for dir in &provenance.empty_dirs {
+            if !is_safe_member_path(dir) {
                 findings.push(InvalidFinding {
-                    code: "EXTRA_MEMBER".to_string(),
-                    path: Some(relative),
+                    code: "UNSAFE_MEMBER_PATH".to_string(),
+                    severity: Severity::Error,
+                    path: Some(dir.clone()),
                     expected: None,
                     actual: None,
                 });
-                *extra_ok = false;
+                continue;
             }
+            dirs.push((dir.as_str(), None));
         }
     }
+    if dirs.is_empty() {
+        return (None, findings);
+    }
+
+    for (dir, placeholder) in dirs {
+        let prefix = format!("{dir}/");
+        let occupant = manifest.members.iter().find(|member| {
+            Some(member.path.as_str()) != placeholder
+                && (member.path == dir || member.path.starts_with(&prefix))
+        });
+        if let Some(occupant) = occupant {
+            findings.push(InvalidFinding {
+                code: "EMPTY_DIR_NOT_EMPTY".to_string(),
+                severity: Severity::Error,
+                path: Some(dir.to_string()),
+                expected: None,
+                actual: Some(occupant.path.clone()),
+            });
+        }
+    }
+    (Some(findings.is_empty()), findings)
+}
+
+/// Finding for a member that exists but cannot be read, carrying the OS error.
+fn unreadable_member(path: &str, err: &io::Error) -> InvalidFinding {
+    InvalidFinding {
+        code: "UNREADABLE_MEMBER".to_string(),
+        severity: Severity::Error,
+        path: Some(path.to_string()),
+        expected: None,
+        actual: Some(err.to_string()),
+    }
 }
@@ -1,95 +1,632 @@
-use std::fs;
-use std::path::Path;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use chrono::Utc;
 use serde_json::json;
 
+use crate::cli::EmptyPackPolicy;
+use crate::encrypt::load_identities;
+use crate::events::{EventBus, PackEvent};
+use crate::network::deprecate::find_deprecation;
+use crate::seal::archive::{is_archive, unpack_archive};
+use crate::seal::limits::{DEFAULT_MAX_NOTE_BYTES, MAX_MANIFEST_BYTES};
 use crate::seal::manifest::Manifest;
+use crate::sign::{load_public_key, read_signature_from, SignatureError, SIGNATURE_FILE};
+use crate::timestamp::{read_timestamp_from, TsaTrust, TIMESTAMP_FILE};
 
-use super::checks::run_checks;
-use super::report::{VerifyOutcome, VerifyReport};
+use super::catalog::SchemaCatalog;
+use super::checks::{run_quick_checks, run_timed_checks};
+use super::decrypt::check_decryption;
+use super::deep::check_cross_references;
+use super::encoding::decode_manifest;
+use super::member_policy::{check_content_types, check_member_policy, load_member_policy};
+use super::nested::check_nested_packs;
+use super::policy::{check_trust_policy, load_trust_policy};
+use super::registry::check_registry_tables;
+use super::repair::RepairPlan;
+use super::report::{
+    InvalidFinding, Severity, TimestampCheck, VerifyOutcome, VerifyReport, VerifyTimings,
+    VerifyWarning,
+};
+use super::source::{DirSource, ManifestFileSource, PackSource};
+use super::stream::{finding_line, summary_line, FindingStream};
+use super::suppress::{apply_suppressions, load_suppressions, Suppression};
+
+/// Optional verify behaviours beyond the pack directory and output mode.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    /// `--member-root`: resolve member bytes from this directory instead of the
+    /// pack directory. Manifest paths stay relative; only the root changes.
+    pub member_root: Option<PathBuf>,
+    /// `--against`: verify the members under the pack directory (or
+    /// `member_root`) against this manifest file instead of the pack's own
+    /// `manifest.json`.
+    pub against: Option<PathBuf>,
+    /// `--max-note-bytes`: note or annotation size above which an
+    /// `OVERSIZED_FIELD` finding is raised; `None` uses
+    /// `DEFAULT_MAX_NOTE_BYTES`.
+    pub max_note_bytes: Option<usize>,
+    /// `--ignore-finding CODE[:PATH]`: findings downgraded to warnings.
+    pub ignore_findings: Vec<Suppression>,
+    /// `--suppressions`: JSON file of justified suppressions.
+    pub suppressions_file: Option<PathBuf>,
+    /// `--require-signature`: an unsigned pack is INVALID.
+    pub require_signature: bool,
+    /// `--public-key`: only this signer's signatures are accepted.
+    pub public_key: Option<PathBuf>,
+    /// `--deep`: check that report members reference lockfiles and members
+    /// present in the pack.
+    pub deep: bool,
+    /// `--validate-registry`: check that CSV/TSV registry members parse and
+    /// match the row counts declared in `registry.json`.
+    pub validate_registry: bool,
+    /// Filesystem store whose index is consulted for a deprecation note, in
+    /// addition to the store holding the pack directory (`PACK_STORE`).
+    pub store: Option<PathBuf>,
+    /// `--empty-pack`: whether a pack with no members warns (the default),
+    /// fails, or passes silently.
+    pub empty_pack: EmptyPackPolicy,
+    /// `--resolve-nested`: also verify packs embedded as members.
+    pub resolve_nested: bool,
+    /// `--timings`: report how long each check took.
+    pub timings: bool,
+    /// `--trust-policy`: JSON file of rules (minimum tool version, allowed
+    /// signers, maximum age) the pack must satisfy.
+    pub trust_policy: Option<PathBuf>,
+    /// `--policy`: JSON file of members the pack must contain and member
+    /// types it may not.
+    pub policy: Option<PathBuf>,
+    /// Receives a `MemberVerified` for each hashed member and a
+    /// `VerificationFinding` for each finding that fails the pack, as soon as
+    /// the check that found it reports it.
+    pub events: EventBus,
+    /// `--quick`: check member existence and recorded sizes instead of
+    /// hashing member bytes.
+    pub quick: bool,
+    /// `--strict`: warning-class findings fail the pack instead of being
+    /// reported as warnings.
+    pub strict: bool,
+    /// `--check-timestamp`: require a `manifest.tst.json` whose RFC 3161
+    /// token stamps the manifest.
+    pub check_timestamp: bool,
+    /// `--tsa-cert`: PEM CA certificates the timestamp token's TSA
+    /// certificate must chain to; without it the token's signature is not
+    /// checked.
+    pub tsa_cert: Option<PathBuf>,
+    /// `--decrypt-key`: age identity file to decrypt encrypted members with
+    /// and check their plaintext against the manifest.
+    pub decrypt_key: Option<PathBuf>,
+    /// `--schema-dir`: directory of `<artifact_version>.schema.json` JSON
+    /// Schemas that take precedence over the compiled-in validators.
+    pub schema_dir: Option<PathBuf>,
+}
 
 /// Execute `pack verify` on a pack directory.
 ///
 /// Returns (report, exit_code).
 pub fn execute_verify(pack_dir: &Path, json_output: bool) -> (String, u8) {
-    // Step 1: Read manifest.json
-    let manifest_path = pack_dir.join("manifest.json");
-
-    let manifest_content = match fs::read_to_string(&manifest_path) {
-        Ok(content) => content,
-        Err(e) => {
-            let report = VerifyReport::refusal(json!({
-                "code": "E_BAD_PACK",
-                "message": format!("Cannot read manifest.json: {e}"),
-            }));
-            let output = if json_output {
-                report.to_json()
-            } else {
-                report.to_human()
-            };
-            return (output, 2);
+    execute_verify_with_options(pack_dir, json_output, &VerifyOptions::default())
+}
+
+/// Execute `pack verify` with explicit [`VerifyOptions`].
+pub fn execute_verify_with_options(
+    pack_dir: &Path,
+    json_output: bool,
+    options: &VerifyOptions,
+) -> (String, u8) {
+    render(&verify_pack(pack_dir, options), json_output)
+}
+
+/// Like [`execute_verify_with_options`], and also write a
+/// [`RepairPlan`] for the findings to `repair_report`.
+///
+/// A report that cannot be written is an `E_IO` refusal.
+pub fn execute_verify_with_repair_report(
+    pack_dir: &Path,
+    json_output: bool,
+    options: &VerifyOptions,
+    repair_report: &Path,
+) -> (String, u8) {
+    let report = verify_pack(pack_dir, options);
+    if let Err(e) = RepairPlan::from_report(pack_dir, &report).write(repair_report) {
+        return render(
+            &refusal(
+                "E_IO",
+                format!(
+                    "Cannot write repair report: {}: {e}",
+                    repair_report.display()
+                ),
+            ),
+            json_output,
+        );
+    }
+    render(&report, json_output)
+}
+
+fn render(report: &VerifyReport, json_output: bool) -> (String, u8) {
+    let output = if json_output {
+        report.to_json()
+    } else {
+        report.to_human()
+    };
+    (output, exit_code(report))
+}
+
+fn exit_code(report: &VerifyReport) -> u8 {
+    match report.outcome {
+        VerifyOutcome::OK => 0,
+        VerifyOutcome::INVALID => 1,
+        VerifyOutcome::REFUSAL => 2,
+    }
+}
+
+/// Execute `pack verify --json-lines`: hand each finding that fails the pack
+/// to `emit` as a `pack.verify_stream.v0` line as soon as it is found, and
+/// return the closing summary line with the exit code.
+pub fn execute_verify_json_lines(
+    pack_dir: &Path,
+    options: &VerifyOptions,
+    emit: impl Fn(String) + Send + Sync + 'static,
+) -> (String, u8) {
+    let options = VerifyOptions {
+        events: options.events.clone().subscribe(move |event| {
+            if let PackEvent::VerificationFinding { finding } = event {
+                emit(finding_line(finding));
+            }
+            ControlFlow::Continue(())
+        }),
+        ..options.clone()
+    };
+    let report = verify_pack(pack_dir, &options);
+    (summary_line(&report), exit_code(&report))
+}
+
+/// Verify a pack directory (or `.pack.tar`) and return the typed report.
+///
+/// Refusals are reported in-band with outcome `REFUSAL`, exactly as
+/// `pack verify --json` prints them.
+pub fn verify_pack(pack_dir: &Path, options: &VerifyOptions) -> VerifyReport {
+    let started = Instant::now();
+    // A .pack.tar is unpacked to scratch space and verified like a directory.
+    if is_archive(pack_dir) {
+        let scratch = match tempfile::tempdir() {
+            Ok(dir) => dir,
+            Err(e) => return refusal("E_IO", format!("Cannot create scratch directory: {e}")),
+        };
+        if let Err(envelope) = unpack_archive(pack_dir, scratch.path()) {
+            return refusal(&envelope.refusal.code, envelope.refusal.message);
+        }
+        return verify_pack_dir(scratch.path(), options, started);
+    }
+    verify_pack_dir(pack_dir, options, started)
+}
+
+fn verify_pack_dir(pack_dir: &Path, options: &VerifyOptions, started: Instant) -> VerifyReport {
+    let member_root = options.member_root.as_deref().unwrap_or(pack_dir);
+    if let Some(manifest) = &options.against {
+        return verify_sources(
+            &ManifestFileSource::new(manifest),
+            &DirSource::new(member_root),
+            options,
+            started,
+        );
+    }
+    verify_sources(
+        &DirSource::new(pack_dir),
+        &DirSource::new(member_root),
+        options,
+        started,
+    )
+}
+
+/// Verify a pack whose files are read through `source` rather than from a
+/// directory, e.g. a [`MemorySource`](super::MemorySource) or a caller's own
+/// archive or remote reader.
+///
+/// Checks and refusals are those of [`verify_pack`]. `options.member_root`
+/// does not apply: members are read from `source` beside the manifest.
+pub fn verify_source(source: &dyn PackSource, options: &VerifyOptions) -> VerifyReport {
+    verify_sources(source, source, options, Instant::now())
+}
+
+/// Verify the manifest and sidecars in `pack` against the members in
+/// `members` (the same source unless `--member-root` is given).
+fn verify_sources(
+    pack: &dyn PackSource,
+    members: &dyn PackSource,
+    options: &VerifyOptions,
+    started: Instant,
+) -> VerifyReport {
+    let mut timings = VerifyTimings::default();
+    let mut lap = Instant::now();
+
+    // Step 1: Read manifest.json (bounded: third-party manifests may be hostile)
+    if let Ok(meta) = pack.metadata("manifest.json") {
+        if meta.len > MAX_MANIFEST_BYTES {
+            return refusal(
+                "E_LIMIT",
+                format!(
+                    "manifest.json is {} bytes, exceeding the {MAX_MANIFEST_BYTES}-byte limit",
+                    meta.len
+                ),
+            );
+        }
+    }
+
+    let manifest_bytes = match pack.read("manifest.json") {
+        Ok(bytes) => bytes,
+        Err(e) => return refusal("E_BAD_PACK", format!("Cannot read manifest.json: {e}")),
+    };
+    let (manifest_content, manifest_encoding) = match decode_manifest(&manifest_bytes) {
+        Ok(decoded) => decoded,
+        Err(message) => {
+            return refusal(
+                "E_BAD_PACK",
+                format!("Cannot read manifest.json: {message}"),
+            )
         }
     };
 
     // Step 2: Parse manifest
     let manifest: Manifest = match serde_json::from_str(&manifest_content) {
         Ok(m) => m,
-        Err(e) => {
-            let report = VerifyReport::refusal(json!({
-                "code": "E_BAD_PACK",
-                "message": format!("Invalid manifest.json: {e}"),
-            }));
-            let output = if json_output {
-                report.to_json()
-            } else {
-                report.to_human()
-            };
-            return (output, 2);
-        }
+        Err(e) => return refusal("E_BAD_PACK", format!("Invalid manifest.json: {e}")),
     };
 
     // Step 3: Validate pack.v0
     if manifest.version != "pack.v0" {
-        let report = VerifyReport::refusal(json!({
-            "code": "E_BAD_PACK",
-            "message": format!("Unsupported manifest version: {}", manifest.version),
-        }));
-        let output = if json_output {
-            report.to_json()
-        } else {
-            report.to_human()
+        return refusal(
+            "E_BAD_PACK",
+            format!("Unsupported manifest version: {}", manifest.version),
+        );
+    }
+    timings.lap("manifest_parse", &mut lap);
+
+    // Step 4: Run integrity checks against the member root
+    if let Some(member_root) = members.local_dir().filter(|root| !root.is_dir()) {
+        return refusal(
+            "E_IO",
+            format!("Cannot read member root: {}", member_root.display()),
+        );
+    }
+    let mut suppressions = options.ignore_findings.clone();
+    if let Some(path) = &options.suppressions_file {
+        match load_suppressions(path) {
+            Ok(loaded) => suppressions.extend(loaded),
+            Err(message) => return refusal("E_IO", message),
+        }
+    }
+
+    let trusted_key = match options.public_key.as_deref().map(load_public_key) {
+        Some(Ok(key)) => Some(key),
+        Some(Err(message)) => return refusal("E_IO", message),
+        None => None,
+    };
+    let trust_policy = match options.trust_policy.as_deref().map(load_trust_policy) {
+        Some(Ok(policy)) => Some(policy),
+        Some(Err(message)) => return refusal("E_IO", message),
+        None => None,
+    };
+    let member_policy = match options.policy.as_deref().map(load_member_policy) {
+        Some(Ok(policy)) => Some(policy),
+        Some(Err(message)) => return refusal("E_IO", message),
+        None => None,
+    };
+    let tsa_trust = match options.tsa_cert.as_deref().map(TsaTrust::load) {
+        Some(Ok(trust)) => Some(trust),
+        Some(Err(message)) => return refusal("E_IO", message),
+        None => None,
+    };
+    let identities = match options.decrypt_key.as_deref().map(load_identities) {
+        Some(Ok(identities)) => Some(identities),
+        Some(Err(message)) => return refusal("E_IO", message),
+        None => None,
+    };
+    let schemas = match options.schema_dir.as_deref().map(SchemaCatalog::load) {
+        Some(Ok(catalog)) => catalog,
+        Some(Err(envelope)) => return refusal(&envelope.refusal.code, envelope.refusal.message),
+        None => SchemaCatalog::default(),
+    };
+
+    // Findings that fail the pack are announced as each check reports them.
+    let mut stream = FindingStream::new(&options.events, &suppressions);
+    let (mut checks, mut findings) = if options.quick {
+        run_quick_checks(&manifest, members, &mut timings, &mut stream)
+    } else {
+        run_timed_checks(&manifest, members, &schemas, &mut timings, &mut stream)
+    };
+    stream.flush(&findings);
+    lap = Instant::now();
+    // --quick reads no member bytes, so it decrypts nothing either.
+    if let Some(identities) = identities.as_deref().filter(|_| !options.quick) {
+        let (decrypted, decrypt_findings) =
+            check_decryption(&manifest, members, identities, &findings);
+        checks.decryption = decrypted;
+        findings.extend(decrypt_findings);
+        stream.flush(&findings);
+        timings.lap("decryption", &mut lap);
+    }
+    if options.deep {
+        let (resolved, cross_findings) = check_cross_references(&manifest, members);
+        checks.cross_references = Some(resolved);
+        findings.extend(cross_findings);
+        stream.flush(&findings);
+        timings.lap("cross_references", &mut lap);
+    }
+    if options.validate_registry {
+        let (well_formed, registry_findings) = check_registry_tables(&manifest, members);
+        checks.registry = Some(well_formed);
+        findings.extend(registry_findings);
+        stream.flush(&findings);
+        timings.lap("registry", &mut lap);
+    }
+    if options.resolve_nested {
+        let (nested_ok, nested_findings) =
+            check_nested_packs(&manifest, members, options, &findings);
+        checks.nested_packs = Some(nested_ok);
+        findings.extend(nested_findings);
+        stream.flush(&findings);
+        timings.lap("nested_packs", &mut lap);
+    }
+
+    // The signature lives beside manifest.json, not under --member-root.
+    let signature_required = options.require_signature || trusted_key.is_some();
+    let signature_file = read_signature_from(pack);
+    match &signature_file {
+        Ok(Some(signature)) => {
+            let verified = signature.verify(&manifest, trusted_key.as_ref());
+            checks.signature = Some(verified.is_ok());
+            if let Err(error) = verified {
+                findings.push(signature_finding(error));
+            }
+        }
+        Ok(None) if signature_required => {
+            checks.signature = Some(false);
+            findings.push(InvalidFinding {
+                code: "SIGNATURE_MISSING".to_string(),
+                severity: Severity::Error,
+                path: Some(SIGNATURE_FILE.to_string()),
+                expected: None,
+                actual: None,
+            });
+        }
+        Ok(None) => {}
+        Err(error) => {
+            checks.signature = Some(false);
+            findings.push(signature_finding(error.clone()));
+        }
+    }
+    if checks.signature.is_some() {
+        timings.lap("signature", &mut lap);
+    }
+
+    // The timestamp, like the signature, lives beside manifest.json.
+    // Its time is only reported once the TSA's signature has been checked.
+    let mut timestamped_at = None;
+    if options.check_timestamp {
+        let check = match read_timestamp_from(pack) {
+            Ok(Some(timestamp)) => match timestamp.verify(&manifest, tsa_trust.as_ref()) {
+                Ok(gen_time) if tsa_trust.is_some() => {
+                    timestamped_at = Some(gen_time);
+                    TimestampCheck::Passed
+                }
+                Ok(_) => TimestampCheck::Unverified,
+                Err(message) => {
+                    findings.push(timestamp_finding("TIMESTAMP_INVALID", Some(message)));
+                    TimestampCheck::Failed
+                }
+            },
+            Ok(None) => {
+                findings.push(timestamp_finding("TIMESTAMP_MISSING", None));
+                TimestampCheck::Failed
+            }
+            Err(message) => {
+                findings.push(timestamp_finding("TIMESTAMP_INVALID", Some(message)));
+                TimestampCheck::Failed
+            }
         };
-        return (output, 2);
+        checks.timestamp = Some(check);
+        timings.lap("timestamp", &mut lap);
+    }
+
+    if let Some(policy) = &trust_policy {
+        let signature = signature_file.as_ref().ok().and_then(Option::as_ref);
+        let violations = check_trust_policy(policy, &manifest, signature, Utc::now());
+        checks.trust_policy = Some(violations.is_empty());
+        findings.extend(violations);
+        timings.lap("trust_policy", &mut lap);
     }
 
-    // Step 4: Run integrity checks
-    let (checks, findings) = run_checks(&manifest, pack_dir);
+    // Policy is evaluated on the manifest once integrity has been checked.
+    if let Some(policy) = &member_policy {
+        let mut violations = check_member_policy(policy, &manifest);
+        // Content type rules are checked against the member bytes too, unless
+        // --quick, which reads none.
+        if policy.limits_content_types() && !options.quick {
+            violations.extend(check_content_types(&manifest, members));
+        }
+        checks.policy = Some(violations.is_empty());
+        findings.extend(violations);
+        timings.lap("policy", &mut lap);
+    }
+
+    let note_limit = options.max_note_bytes.unwrap_or(DEFAULT_MAX_NOTE_BYTES);
+    if let Some(note) = manifest.note.as_ref().filter(|n| n.len() > note_limit) {
+        findings.push(InvalidFinding {
+            code: "OVERSIZED_FIELD".to_string(),
+            severity: Severity::Error,
+            path: None,
+            expected: Some(format!("note <= {note_limit} bytes")),
+            actual: Some(format!("note = {} bytes", note.len())),
+        });
+    }
+    // Seal holds each annotation to the same limit as the note.
+    for (path, text) in &manifest.annotations {
+        if text.len() > note_limit {
+            findings.push(InvalidFinding {
+                code: "OVERSIZED_FIELD".to_string(),
+                severity: Severity::Error,
+                path: Some(path.clone()),
+                expected: Some(format!("annotation <= {note_limit} bytes")),
+                actual: Some(format!("annotation = {} bytes", text.len())),
+            });
+        }
+    }
+
+    if manifest.members.is_empty() && options.empty_pack == EmptyPackPolicy::Fail {
+        findings.push(empty_pack_finding());
+    }
+
+    // Warning-class findings: the pack verifies, but something about it is
+    // out of date. --strict fails the pack on them instead.
+    let mut advisories = Vec::new();
+    // Deprecation is listed first so it is seen. Its signer is checked
+    // against the keys this verify trusts.
+    let trusted_signers: Vec<_> = trusted_key
+        .iter()
+        .copied()
+        .chain(
+            trust_policy
+                .iter()
+                .flat_map(|policy| policy.allowed_signers.iter().flatten().copied()),
+        )
+        .collect();
+    if let Some(deprecation) = find_deprecation(
+        pack.local_dir(),
+        &manifest.pack_id,
+        options.store.as_deref(),
+    ) {
+        advisories.push(VerifyWarning {
+            finding: InvalidFinding {
+                code: "DEPRECATED".to_string(),
+                severity: Severity::Warning,
+                path: None,
+                expected: None,
+                actual: None,
+            },
+            reason: deprecation.describe(&manifest.pack_id, &trusted_signers),
+        });
+    }
+    // pack_id hashes the parsed fields, so a re-encoded manifest still
+    // verifies; the warning tells the operator to restore plain UTF-8.
+    if let Some(encoding) = manifest_encoding {
+        advisories.push(VerifyWarning {
+            finding: InvalidFinding {
+                code: "NONCANONICAL_MANIFEST_ENCODING".to_string(),
+                severity: Severity::Warning,
+                path: Some("manifest.json".to_string()),
+                expected: Some("utf-8".to_string()),
+                actual: Some(encoding.as_str().to_string()),
+            },
+            reason: format!(
+                "manifest.json was decoded from {}; seal writes UTF-8 without a byte order mark",
+                encoding.as_str()
+            ),
+        });
+    }
+    let without_size = manifest
+        .members
+        .iter()
+        .filter(|member| member.size_bytes.is_none())
+        .count();
+    if without_size > 0 {
+        advisories.push(VerifyWarning {
+            finding: InvalidFinding {
+                code: "MISSING_MEMBER_SIZE".to_string(),
+                severity: Severity::Warning,
+                path: None,
+                expected: Some("size_bytes on every member".to_string()),
+                actual: Some(format!(
+                    "{without_size} of {} members without size_bytes",
+                    manifest.members.len()
+                )),
+            },
+            reason: "sealed before size_bytes was recorded; --quick cannot check these members"
+                .to_string(),
+        });
+    }
+    if options.strict {
+        findings.extend(advisories.drain(..).map(|advisory| InvalidFinding {
+            severity: Severity::Error,
+            ..advisory.finding
+        }));
+    }
+
+    stream.flush(&findings);
+    let (findings, suppressed) = apply_suppressions(findings, &suppressions);
+    let mut warnings = advisories;
+    warnings.extend(suppressed);
+    if manifest.members.is_empty() && options.empty_pack == EmptyPackPolicy::Warn {
+        warnings.push(VerifyWarning {
+            finding: InvalidFinding {
+                severity: Severity::Warning,
+                ..empty_pack_finding()
+            },
+            reason: "pack has no members; it verifies nothing".to_string(),
+        });
+    }
 
     let report = if findings.is_empty() {
         VerifyReport::ok(manifest.pack_id.clone(), checks)
     } else {
         VerifyReport::invalid(Some(manifest.pack_id.clone()), checks, findings)
-    };
+    }
+    .with_warnings(warnings)
+    .with_timestamp(timestamped_at);
+    if options.timings {
+        report.with_timings(timings.finish(started))
+    } else {
+        report
+    }
+}
 
-    let exit_code = match report.outcome {
-        VerifyOutcome::OK => 0,
-        VerifyOutcome::INVALID => 1,
-        VerifyOutcome::REFUSAL => 2,
-    };
+fn empty_pack_finding() -> InvalidFinding {
+    InvalidFinding {
+        code: "EMPTY_PACK".to_string(),
+        severity: Severity::Error,
+        path: None,
+        expected: Some("member_count >= 1".to_string()),
+        actual: Some("member_count = 0".to_string()),
+    }
+}
 
-    let output = if json_output {
-        report.to_json()
-    } else {
-        report.to_human()
+fn signature_finding(error: SignatureError) -> InvalidFinding {
+    let (code, actual) = match error {
+        SignatureError::Invalid(message) => ("SIGNATURE_INVALID", message),
+        SignatureError::Untrusted { signer } => ("SIGNATURE_UNTRUSTED", signer),
     };
+    InvalidFinding {
+        code: code.to_string(),
+        severity: Severity::Error,
+        path: Some(SIGNATURE_FILE.to_string()),
+        expected: None,
+        actual: Some(actual),
+    }
+}
 
-    (output, exit_code)
+fn timestamp_finding(code: &str, actual: Option<String>) -> InvalidFinding {
+    InvalidFinding {
+        code: code.to_string(),
+        severity: Severity::Error,
+        path: Some(TIMESTAMP_FILE.to_string()),
+        expected: None,
+        actual,
+    }
+}
+
+pub(super) fn refusal(code: &str, message: String) -> VerifyReport {
+    VerifyReport::refusal(json!({
+        "code": code,
+        "message": message,
+    }))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::seal::command::execute_seal;
+    use crate::verify::{HashCheck, VERIFY_STREAM_VERSION};
     use std::fs;
     use tempfile::TempDir;
 
@@ -103,6 +640,86 @@ mod tests {
         (out, result.pack_id)
     }
 
+    #[test]
+    fn archive_verifies_like_a_directory() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let file = src.path().join("data.lock.json");
+        fs::write(&file, r#"{"version":"lock.v0","rows":5}"#).unwrap();
+        let archive = out.path().join("p.pack.tar");
+        let options = crate::seal::command::SealOptions {
+            archive: true,
+            ..Default::default()
+        };
+        let result = crate::seal::command::execute_seal_with_options(
+            &[file],
+            Some(&archive),
+            None,
+            &options,
+        )
+        .unwrap();
+        assert!(archive.is_file());
+
+        let (output, code) = execute_verify(&archive, true);
+        assert_eq!(code, 0, "{output}");
+        assert!(output.contains(&result.pack_id));
+
+        fs::write(&archive, "not a tar").unwrap();
+        let (_, code) = execute_verify(&archive, true);
+        assert_eq!(code, 2);
+    }
+
+    #[test]
+    fn signed_pack_verifies_and_signature_can_be_required() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let file = src.path().join("data.lock.json");
+        fs::write(&file, r#"{"version":"lock.v0","rows":5}"#).unwrap();
+        let key_path = src.path().join("signing.key");
+        fs::write(&key_path, hex::encode([3u8; 32])).unwrap();
+        let pub_path = src.path().join("signing.pub");
+        let public = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]).verifying_key();
+        fs::write(&pub_path, hex::encode(public.as_bytes())).unwrap();
+
+        let signed_dir = out.path().join("signed");
+        let options = crate::seal::command::SealOptions {
+            sign: true,
+            signing_key: Some(key_path),
+            ..Default::default()
+        };
+        let signed = crate::seal::command::execute_seal_with_options(
+            std::slice::from_ref(&file),
+            Some(&signed_dir),
+            None,
+            &options,
+        )
+        .unwrap();
+        let unsigned = execute_seal(&[file], Some(&out.path().join("unsigned")), None).unwrap();
+        assert_eq!(
+            signed.pack_id, unsigned.pack_id,
+            "signing must not change pack_id"
+        );
+
+        let require = VerifyOptions {
+            require_signature: true,
+            public_key: Some(pub_path),
+            ..Default::default()
+        };
+        let (output, code) = execute_verify_with_options(&signed_dir, true, &require);
+        assert_eq!(code, 0, "{output}");
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["checks"]["signature"], true);
+
+        let (output, code) = execute_verify_with_options(&unsigned.output_dir, true, &require);
+        assert_eq!(code, 1);
+        assert!(output.contains("SIGNATURE_MISSING"));
+
+        // Unsigned packs still verify when no signature is required.
+        let (output, code) = execute_verify(&unsigned.output_dir, true);
+        assert_eq!(code, 0);
+        assert!(!output.contains("signature"));
+    }
+
     #[test]
     fn valid_pack_verifies_ok() {
         let (out, _pack_id) = create_valid_pack();
@@ -135,17 +752,39 @@ mod tests {
     fn tampered_member_is_invalid() {
         let (out, _) = create_valid_pack();
         let pack_path = out.path().join("p");
-        // Tamper with the member
-        fs::write(pack_path.join("data.lock.json"), "TAMPERED").unwrap();
+        // Tamper with the member, keeping its size
+        fs::write(
+            pack_path.join("data.lock.json"),
+            r#"{"version":"lock.v0","rows":6}"#,
+        )
+        .unwrap();
 
         let (output, code) = execute_verify(&pack_path, true);
         assert_eq!(code, 1);
         let report: serde_json::Value = serde_json::from_str(&output).unwrap();
         assert_eq!(report["outcome"], "INVALID");
+        assert_eq!(report["checks"]["member_sizes"], true);
         let findings = report["invalid"].as_array().unwrap();
         assert!(findings.iter().any(|f| f["code"] == "HASH_MISMATCH"));
     }
 
+    #[test]
+    fn resized_member_is_a_size_mismatch_without_hashing() {
+        let (out, _) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        fs::write(pack_path.join("data.lock.json"), "TAMPERED").unwrap();
+
+        let report = verify_pack(&pack_path, &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        assert_eq!(report.checks.member_sizes, Some(false));
+        assert_eq!(report.checks.member_hashes, HashCheck::Failed);
+        let codes: Vec<_> = report.invalid.iter().map(|f| f.code.as_str()).collect();
+        assert_eq!(codes[0], "SIZE_MISMATCH");
+        assert!(!codes.contains(&"HASH_MISMATCH"));
+        assert_eq!(report.invalid[0].expected.as_deref(), Some("30"));
+        assert_eq!(report.invalid[0].actual.as_deref(), Some("8"));
+    }
+
     #[test]
     fn extra_file_is_invalid() {
         let (out, _) = create_valid_pack();
@@ -197,6 +836,428 @@ mod tests {
             .any(|f| f["code"] == "PACK_ID_MISMATCH" || f["code"] == "HASH_MISMATCH"));
     }
 
+    #[test]
+    fn member_root_resolves_bytes_from_alternate_location() {
+        let (out, _) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        let manifest_only = TempDir::new().unwrap();
+        fs::copy(
+            pack_path.join("manifest.json"),
+            manifest_only.path().join("manifest.json"),
+        )
+        .unwrap();
+        fs::remove_file(pack_path.join("manifest.json")).unwrap();
+
+        let (_, code) = execute_verify(manifest_only.path(), true);
+        assert_eq!(code, 1, "members are absent next to the manifest");
+
+        let options = VerifyOptions {
+            member_root: Some(pack_path.clone()),
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(manifest_only.path(), true, &options);
+        assert_eq!(code, 0, "{output}");
+
+        fs::write(pack_path.join("data.lock.json"), "TAMPERED").unwrap();
+        let (output, code) = execute_verify_with_options(manifest_only.path(), true, &options);
+        assert_eq!(code, 1);
+        assert!(output.contains("SIZE_MISMATCH"));
+    }
+
+    #[test]
+    fn members_verify_against_a_separate_manifest_file() {
+        let (out, pack_id) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        let mailed = TempDir::new().unwrap();
+        let manifest = mailed.path().join("q4-manifest.json");
+        fs::rename(pack_path.join("manifest.json"), &manifest).unwrap();
+
+        let options = VerifyOptions {
+            against: Some(manifest.clone()),
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(&pack_path, true, &options);
+        assert_eq!(code, 0, "{output}");
+        assert!(output.contains(&pack_id));
+
+        fs::write(pack_path.join("stray.txt"), "x").unwrap();
+        let (output, code) = execute_verify_with_options(&pack_path, true, &options);
+        assert_eq!(code, 1);
+        assert!(output.contains("EXTRA_MEMBER"));
+
+        let options = VerifyOptions {
+            against: Some(mailed.path().join("missing.json")),
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(&pack_path, true, &options);
+        assert_eq!(code, 2);
+        assert!(output.contains("missing.json"), "{output}");
+    }
+
+    #[test]
+    fn missing_member_root_is_refusal() {
+        let (out, _) = create_valid_pack();
+        let options = VerifyOptions {
+            member_root: Some(out.path().join("nowhere")),
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(&out.path().join("p"), true, &options);
+        assert_eq!(code, 2);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["refusal"]["code"], "E_IO");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unreadable_member_is_reported_and_others_still_checked() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let locked = src.path().join("locked.json");
+        let open = src.path().join("open.json");
+        fs::write(&locked, r#"{"version":"lock.v0"}"#).unwrap();
+        fs::write(&open, "{}").unwrap();
+        let pack_path = out.path().join("p");
+        execute_seal(&[locked, open], Some(&pack_path), None).unwrap();
+
+        let member = pack_path.join("locked.json");
+        fs::set_permissions(&member, fs::Permissions::from_mode(0o000)).unwrap();
+        fs::write(pack_path.join("open.json"), "tampered").unwrap();
+        if fs::read(&member).is_ok() {
+            // Running as root: permission bits are not enforced.
+            return;
+        }
+
+        let (output, code) = execute_verify(&pack_path, true);
+        fs::set_permissions(&member, fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(code, 1);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let findings = report["invalid"].as_array().unwrap();
+        let unreadable = findings
+            .iter()
+            .find(|f| f["code"] == "UNREADABLE_MEMBER")
+            .unwrap();
+        assert_eq!(unreadable["path"], "locked.json");
+        assert!(unreadable["actual"].as_str().unwrap().contains("os error"));
+        assert!(findings
+            .iter()
+            .any(|f| f["code"] == "HASH_MISMATCH" && f["path"] == "open.json"));
+    }
+
+    #[test]
+    fn oversized_note_is_flagged() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let file = src.path().join("data.lock.json");
+        fs::write(&file, r#"{"version":"lock.v0"}"#).unwrap();
+        let pack_path = out.path().join("p");
+        execute_seal(&[file], Some(&pack_path), Some("a".repeat(32))).unwrap();
+
+        let (_, code) = execute_verify(&pack_path, true);
+        assert_eq!(code, 0);
+
+        let options = VerifyOptions {
+            max_note_bytes: Some(16),
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(&pack_path, true, &options);
+        assert_eq!(code, 1);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["invalid"][0]["code"], "OVERSIZED_FIELD");
+        assert_eq!(report["invalid"][0]["actual"], "note = 32 bytes");
+    }
+
+    #[test]
+    fn oversized_annotation_is_flagged() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let file = src.path().join("data.lock.json");
+        fs::write(&file, r#"{"version":"lock.v0"}"#).unwrap();
+        let pack_path = out.path().join("p");
+        let options = crate::seal::command::SealOptions {
+            annotate: vec![crate::seal::annotate::MemberAnnotation {
+                path: "data.lock.json".to_string(),
+                text: "b".repeat(32),
+            }],
+            ..Default::default()
+        };
+        crate::seal::command::execute_seal_with_options(&[file], Some(&pack_path), None, &options)
+            .unwrap();
+
+        let options = VerifyOptions {
+            max_note_bytes: Some(16),
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(&pack_path, true, &options);
+        assert_eq!(code, 1);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["invalid"][0]["code"], "OVERSIZED_FIELD");
+        assert_eq!(report["invalid"][0]["path"], "data.lock.json");
+        assert_eq!(report["invalid"][0]["actual"], "annotation = 32 bytes");
+    }
+
+    #[test]
+    fn json_lines_streams_unsuppressed_findings_before_the_summary() {
+        let (out, pack_id) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        fs::write(
+            pack_path.join("data.lock.json"),
+            r#"{"version":"lock.v0","rows":6}"#,
+        )
+        .unwrap();
+        fs::write(pack_path.join("README.md"), "vendor notes").unwrap();
+        fs::write(pack_path.join("stray.txt"), "left behind").unwrap();
+
+        let lines = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = std::sync::Arc::clone(&lines);
+        let options = VerifyOptions {
+            ignore_findings: vec![Suppression::from_flag("EXTRA_MEMBER:README.md")],
+            ..VerifyOptions::default()
+        };
+        let (summary, code) = execute_verify_json_lines(&pack_path, &options, move |line| {
+            sink.lock().unwrap().push(line)
+        });
+        assert_eq!(code, 1);
+
+        let findings: Vec<serde_json::Value> = lines
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let codes: Vec<_> = findings.iter().map(|f| f["code"].clone()).collect();
+        assert_eq!(codes, ["HASH_MISMATCH", "EXTRA_MEMBER"]);
+        assert_eq!(findings[0]["version"], VERIFY_STREAM_VERSION);
+        assert_eq!(findings[0]["record"], "finding");
+        assert_eq!(findings[1]["path"], "stray.txt");
+
+        let summary: serde_json::Value = serde_json::from_str(&summary).unwrap();
+        assert_eq!(summary["record"], "summary");
+        assert_eq!(summary["outcome"], "INVALID");
+        assert_eq!(summary["pack_id"], pack_id.as_str());
+        assert_eq!(summary["finding_count"], 2);
+        assert_eq!(summary["warnings"][0]["path"], "README.md");
+        assert!(summary.get("invalid").is_none());
+    }
+
+    #[test]
+    fn ignored_finding_becomes_warning() {
+        let (out, _pack_id) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        fs::write(pack_path.join("README.md"), "vendor notes").unwrap();
+
+        let (_, code) = execute_verify(&pack_path, true);
+        assert_eq!(code, 1);
+
+        let options = VerifyOptions {
+            ignore_findings: vec![Suppression::from_flag("EXTRA_MEMBER:README.md")],
+            ..VerifyOptions::default()
+        };
+        let (output, code) = execute_verify_with_options(&pack_path, true, &options);
+        assert_eq!(code, 0);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["outcome"], "OK");
+        assert_eq!(report["warnings"][0]["code"], "EXTRA_MEMBER");
+        assert_eq!(report["warnings"][0]["path"], "README.md");
+        assert_eq!(
+            report["warnings"][0]["reason"],
+            "--ignore-finding EXTRA_MEMBER:README.md"
+        );
+    }
+
+    #[test]
+    fn empty_pack_warns_by_default_and_follows_policy() {
+        let tmp = TempDir::new().unwrap();
+        let mut manifest = Manifest::new(
+            "2026-01-15T00:00:00Z".to_string(),
+            None,
+            "0.1.0".to_string(),
+            vec![],
+        );
+        manifest.finalize();
+        fs::write(
+            tmp.path().join("manifest.json"),
+            manifest.to_canonical_bytes(),
+        )
+        .unwrap();
+
+        let report = verify_pack(tmp.path(), &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.warnings[0].finding.code, "EMPTY_PACK");
+
+        let policy = |empty_pack| VerifyOptions {
+            empty_pack,
+            ..VerifyOptions::default()
+        };
+        let report = verify_pack(tmp.path(), &policy(EmptyPackPolicy::Fail));
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        assert_eq!(report.invalid[0].code, "EMPTY_PACK");
+        let report = verify_pack(tmp.path(), &policy(EmptyPackPolicy::Allow));
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert!(report.warnings.is_empty());
+
+        let (out, _) = create_valid_pack();
+        let report = verify_pack(&out.path().join("p"), &policy(EmptyPackPolicy::Fail));
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+    }
+
+    #[test]
+    fn utf16_manifest_verifies_with_encoding_warning() {
+        let (out, _) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        let manifest_path = pack_path.join("manifest.json");
+        let text = fs::read_to_string(&manifest_path).unwrap();
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        fs::write(&manifest_path, utf16).unwrap();
+
+        let report = verify_pack(&pack_path, &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(
+            report.warnings[0].finding.code,
+            "NONCANONICAL_MANIFEST_ENCODING"
+        );
+        assert_eq!(
+            report.warnings[0].finding.actual.as_deref(),
+            Some("utf-16le")
+        );
+    }
+
+    #[test]
+    fn strict_fails_on_warning_class_findings() {
+        let (out, _) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        let manifest_path = pack_path.join("manifest.json");
+        let mut manifest: Manifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        for member in &mut manifest.members {
+            member.size_bytes = None;
+        }
+        manifest.finalize();
+        fs::write(&manifest_path, manifest.to_canonical_bytes()).unwrap();
+
+        let report = verify_pack(&pack_path, &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.warnings[0].finding.code, "MISSING_MEMBER_SIZE");
+        assert_eq!(report.warnings[0].finding.severity, Severity::Warning);
+        assert_eq!(
+            report.warnings[0].finding.actual.as_deref(),
+            Some("1 of 1 members without size_bytes")
+        );
+
+        let strict = VerifyOptions {
+            strict: true,
+            ..VerifyOptions::default()
+        };
+        let report = verify_pack(&pack_path, &strict);
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        assert_eq!(report.invalid[0].code, "MISSING_MEMBER_SIZE");
+        assert_eq!(report.invalid[0].severity, Severity::Error);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn check_timestamp_requires_a_matching_token() {
+        let (out, pack_id) = create_valid_pack();
+        let pack_path = out.path().join("p");
+        let options = VerifyOptions {
+            check_timestamp: true,
+            ..VerifyOptions::default()
+        };
+        let report = verify_pack(&pack_path, &options);
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        assert_eq!(report.checks.timestamp, Some(TimestampCheck::Failed));
+        assert_eq!(report.invalid[0].code, "TIMESTAMP_MISSING");
+
+        let timestamp = crate::timestamp::PackTimestamp {
+            version: crate::timestamp::TIMESTAMP_VERSION.to_string(),
+            pack_id,
+            tsa_url: "http://tsa.invalid".to_string(),
+            message_imprint: "sha256:00".to_string(),
+            gen_time: "2026-01-01T00:00:00Z".to_string(),
+            token: "MAA=".to_string(),
+        };
+        crate::timestamp::write_timestamp(&pack_path, &timestamp).unwrap();
+        let report = verify_pack(&pack_path, &options);
+        assert_eq!(report.invalid[0].code, "TIMESTAMP_INVALID");
+        assert!(report.timestamped_at.is_none());
+
+        // Without --check-timestamp the sidecar is neither checked nor extra.
+        let report = verify_pack(&pack_path, &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.checks.timestamp, None);
+
+        let missing_cert = VerifyOptions {
+            tsa_cert: Some(out.path().join("tsa.pem")),
+            ..options
+        };
+        let report = verify_pack(&pack_path, &missing_cert);
+        assert_eq!(report.outcome, VerifyOutcome::REFUSAL);
+    }
+
+    #[test]
+    fn encrypted_pack_verifies_ciphertext_and_decrypts_with_the_key() {
+        let src = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let file = src.path().join("data.lock.json");
+        let plaintext = r#"{"version":"lock.v0","rows":5}"#;
+        fs::write(&file, plaintext).unwrap();
+        let alice = crate::encrypt::Identity::generate();
+        let mallory = crate::encrypt::Identity::generate();
+        let key = |name: &str, identity: &crate::encrypt::Identity| {
+            let path = src.path().join(name);
+            fs::write(&path, identity.to_secret_string()).unwrap();
+            path
+        };
+        let (alice_key, mallory_key) = (key("alice.txt", &alice), key("mallory.txt", &mallory));
+
+        let pack_path = out.path().join("p");
+        let options = crate::seal::command::SealOptions {
+            encrypt_to: vec![alice.recipient()],
+            ..Default::default()
+        };
+        crate::seal::command::execute_seal_with_options(&[file], Some(&pack_path), None, &options)
+            .unwrap();
+        let member = fs::read(pack_path.join("data.lock.json")).unwrap();
+        assert!(member.starts_with(b"age-encryption.org/v1\n"));
+        let manifest: Manifest =
+            serde_json::from_slice(&fs::read(pack_path.join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(manifest.members[0].size_bytes, Some(plaintext.len() as u64));
+        assert_eq!(
+            manifest.encryption.unwrap().recipients,
+            vec![alice.recipient().to_string()]
+        );
+
+        // Without a key the ciphertext is checked, and nothing is decrypted.
+        let report = verify_pack(&pack_path, &VerifyOptions::default());
+        assert_eq!(report.outcome, VerifyOutcome::OK, "{:?}", report.invalid);
+        assert_eq!(report.checks.decryption, None);
+
+        let with_key = |path: &Path| VerifyOptions {
+            decrypt_key: Some(path.to_path_buf()),
+            ..VerifyOptions::default()
+        };
+        let report = verify_pack(&pack_path, &with_key(&alice_key));
+        assert_eq!(report.outcome, VerifyOutcome::OK, "{:?}", report.invalid);
+        assert_eq!(report.checks.decryption, Some(true));
+
+        let report = verify_pack(&pack_path, &with_key(&mallory_key));
+        assert_eq!(report.checks.decryption, Some(false));
+        assert_eq!(report.invalid[0].code, "DECRYPTION_FAILED");
+
+        // Altered ciphertext fails the hash check and is not decrypted.
+        let mut altered = member.clone();
+        *altered.last_mut().unwrap() ^= 1;
+        fs::write(pack_path.join("data.lock.json"), altered).unwrap();
+        let report = verify_pack(&pack_path, &with_key(&alice_key));
+        let codes: Vec<&str> = report.invalid.iter().map(|f| f.code.as_str()).collect();
+        assert_eq!(codes, vec!["HASH_MISMATCH"]);
+        assert_eq!(report.checks.decryption, Some(true));
+    }
+
     #[test]
     fn invalid_json_manifest_is_refusal() {
         let tmp = TempDir::new().unwrap();
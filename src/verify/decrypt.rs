@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+use crate::encrypt::{decrypt, Identity};
+use crate::seal::hasher::{hash_algorithm_of, MemberHasher};
+use crate::seal::manifest::Manifest;
+
+use super::report::{InvalidFinding, Severity};
+use super::source::PackSource;
+
+/// Decrypt each encrypted member with `identities` and check its plaintext
+/// against the recorded `bytes_hash` and `size_bytes` (`--decrypt-key`).
+///
+/// Members that already have a finding (missing, or ciphertext that does not
+/// match `ciphertext_hash`) are not decrypted. A member that will not decrypt
+/// is `DECRYPTION_FAILED` with the reason in `actual`. Returns `None` for the
+/// check when no member is encrypted.
+pub fn check_decryption(
+    manifest: &Manifest,
+    members: &dyn PackSource,
+    identities: &[Identity],
+    prior: &[InvalidFinding],
+) -> (Option<bool>, Vec<InvalidFinding>) {
+    let failed: HashSet<&str> = prior.iter().filter_map(|f| f.path.as_deref()).collect();
+    let mut encrypted = false;
+    let mut findings = Vec::new();
+    for member in &manifest.members {
+        if member.ciphertext_hash.is_none() {
+            continue;
+        }
+        encrypted = true;
+        if failed.contains(member.path.as_str()) {
+            continue;
+        }
+        // An unsupported prefix cannot match, so it is a HASH_MISMATCH below.
+        let mut hasher =
+            MemberHasher::new(hash_algorithm_of(&member.bytes_hash).unwrap_or_default());
+        let decrypted = members
+            .open(&member.path)
+            .map_err(|e| e.to_string())
+            .and_then(|reader| decrypt(reader, &mut hasher, identities));
+        let size = match decrypted {
+            Ok(size) => size,
+            Err(reason) => {
+                findings.push(InvalidFinding {
+                    code: "DECRYPTION_FAILED".to_string(),
+                    severity: Severity::Error,
+                    path: Some(member.path.clone()),
+                    expected: None,
+                    actual: Some(reason),
+                });
+                continue;
+            }
+        };
+        if let Some(expected) = member.size_bytes.filter(|expected| *expected != size) {
+            findings.push(InvalidFinding {
+                code: "SIZE_MISMATCH".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(expected.to_string()),
+                actual: Some(size.to_string()),
+            });
+            continue;
+        }
+        let hash = hasher.finalize();
+        if hash != member.bytes_hash {
+            findings.push(InvalidFinding {
+                code: "HASH_MISMATCH".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(member.bytes_hash.clone()),
+                actual: Some(hash),
+            });
+        }
+    }
+    (encrypted.then_some(findings.is_empty()), findings)
+}
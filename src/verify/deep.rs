@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use super::report::{InvalidFinding, Severity};
+use super::source::PackSource;
+use crate::seal::manifest::Manifest;
+
+/// Report versions whose references `--deep` follows.
+const REPORT_VERSIONS: [&str; 4] = ["rvl.v0", "shape.v0", "verify.v0", "compare.v0"];
+
+/// Check that report members agree with the rest of the pack (`--deep`).
+///
+/// Report JSON is searched at any depth for two kinds of reference:
+///
+/// - lockfile hashes: string fields named `lock_hash` or ending in
+///   `_lock_hash`, and `lock_hashes` arrays. Each must be the `bytes_hash` of
+///   a `lockfile` member, else `CROSS_REFERENCE_MISMATCH`.
+/// - member paths: string fields named `member` or ending in `_member`, and
+///   `members` arrays of strings. Each must name a member of this pack, else
+///   `CROSS_REFERENCE_MISSING`.
+///
+/// Reports that cannot be read or parsed are skipped; the hash and schema
+/// checks already cover them. Returns (all references resolved, findings).
+pub fn check_cross_references(
+    manifest: &Manifest,
+    members: &dyn PackSource,
+) -> (bool, Vec<InvalidFinding>) {
+    let lock_hashes: BTreeSet<&str> = manifest
+        .members
+        .iter()
+        .filter(|m| m.member_type == "lockfile")
+        .map(|m| m.bytes_hash.as_str())
+        .collect();
+    let paths: BTreeSet<&str> = manifest.members.iter().map(|m| m.path.as_str()).collect();
+
+    let mut findings = Vec::new();
+    for member in &manifest.members {
+        let is_report = member
+            .artifact_version
+            .as_deref()
+            .is_some_and(|v| REPORT_VERSIONS.contains(&v));
+        if !is_report {
+            continue;
+        }
+        let Some(report) = members
+            .read(&member.path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+        else {
+            continue;
+        };
+
+        let mut references = References::default();
+        references.collect(&report, None);
+        for hash in references.lock_hashes {
+            if !lock_hashes.contains(hash.as_str()) {
+                findings.push(InvalidFinding {
+                    code: "CROSS_REFERENCE_MISMATCH".to_string(),
+                    severity: Severity::Error,
+                    path: Some(member.path.clone()),
+                    expected: Some("bytes_hash of a lockfile member".to_string()),
+                    actual: Some(hash),
+                });
+            }
+        }
+        for target in references.members {
+            if !paths.contains(target.as_str()) {
+                findings.push(InvalidFinding {
+                    code: "CROSS_REFERENCE_MISSING".to_string(),
+                    severity: Severity::Error,
+                    path: Some(member.path.clone()),
+                    expected: Some("path of a pack member".to_string()),
+                    actual: Some(target),
+                });
+            }
+        }
+    }
+    (findings.is_empty(), findings)
+}
+
+/// References found in one report, deduplicated and in sorted order.
+#[derive(Default)]
+struct References {
+    lock_hashes: BTreeSet<String>,
+    members: BTreeSet<String>,
+}
+
+impl References {
+    fn collect(&mut self, value: &Value, key: Option<&str>) {
+        match (value, key) {
+            (Value::String(s), Some(key)) if key == "lock_hash" || key.ends_with("_lock_hash") => {
+                self.lock_hashes.insert(s.clone());
+            }
+            (Value::String(s), Some(key)) if key == "member" || key.ends_with("_member") => {
+                self.members.insert(s.clone());
+            }
+            (Value::Array(items), Some("lock_hashes")) => {
+                let hashes = items.iter().filter_map(Value::as_str).map(str::to_string);
+                self.lock_hashes.extend(hashes);
+            }
+            (Value::Array(items), Some("members")) => {
+                let members = items.iter().filter_map(Value::as_str).map(str::to_string);
+                self.members.extend(members);
+                items.iter().for_each(|item| self.collect(item, None));
+            }
+            (Value::Array(items), _) => items.iter().for_each(|item| self.collect(item, None)),
+            (Value::Object(map), _) => {
+                for (key, value) in map {
+                    self.collect(value, Some(key));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use crate::verify::source::DirSource;
+    use sha2::{Digest, Sha256};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn sealed(files: &[(&str, String)]) -> (TempDir, Manifest) {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        let paths: Vec<_> = files
+            .iter()
+            .map(|(name, content)| {
+                let path = src.join(name);
+                fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+        let out = tmp.path().join("pack");
+        execute_seal(&paths, Some(&out), None).unwrap();
+        let manifest =
+            serde_json::from_slice(&fs::read(out.join("manifest.json")).unwrap()).unwrap();
+        (tmp, manifest)
+    }
+
+    #[test]
+    fn resolved_references_pass() {
+        let lock = r#"{"version":"lock.v0","rows":1}"#;
+        let hash = format!("sha256:{}", hex::encode(Sha256::digest(lock)));
+        let (tmp, manifest) = sealed(&[
+            ("nov.lock.json", lock.to_string()),
+            (
+                "rvl.report.json",
+                format!(r#"{{"version":"rvl.v0","inputs":{{"old_lock_hash":"{hash}"}}}}"#),
+            ),
+            (
+                "compare.report.json",
+                r#"{"version":"compare.v0","baseline_member":"nov.lock.json"}"#.to_string(),
+            ),
+        ]);
+
+        let (ok, findings) =
+            check_cross_references(&manifest, &DirSource::new(tmp.path().join("pack")));
+        assert!(ok, "{findings:?}");
+    }
+
+    #[test]
+    fn dangling_references_are_findings() {
+        let stale = format!("sha256:{}", "0".repeat(64));
+        let (tmp, manifest) = sealed(&[
+            ("nov.lock.json", r#"{"version":"lock.v0"}"#.to_string()),
+            (
+                "rvl.report.json",
+                format!(r#"{{"version":"rvl.v0","lock_hashes":["{stale}"]}}"#),
+            ),
+            (
+                "compare.report.json",
+                r#"{"version":"compare.v0","members":["dec.lock.json"]}"#.to_string(),
+            ),
+        ]);
+
+        let (ok, findings) =
+            check_cross_references(&manifest, &DirSource::new(tmp.path().join("pack")));
+        assert!(!ok);
+        let codes: Vec<(&str, &str)> = findings
+            .iter()
+            .map(|f| (f.code.as_str(), f.actual.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("CROSS_REFERENCE_MISSING", "dec.lock.json"),
+                ("CROSS_REFERENCE_MISMATCH", stale.as_str()),
+            ]
+        );
+    }
+}
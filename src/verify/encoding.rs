@@ -0,0 +1,115 @@
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// A manifest encoding other than plain UTF-8, which is what seal writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestEncoding {
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl ManifestEncoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Utf8Bom => "utf-8-bom",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+        }
+    }
+}
+
+/// Decode manifest bytes that Windows tooling may have re-encoded.
+///
+/// A UTF-8 or UTF-16 byte order mark is honoured and stripped; without one,
+/// UTF-16 is recognised by the NUL byte beside the leading ASCII character
+/// every JSON document starts with. Returns the text and, when it was not
+/// plain UTF-8, the encoding it was decoded from.
+pub fn decode_manifest(bytes: &[u8]) -> Result<(String, Option<ManifestEncoding>), String> {
+    let (encoding, body) = if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+        (Some(ManifestEncoding::Utf8Bom), rest)
+    } else if let Some(rest) = bytes.strip_prefix(UTF16LE_BOM) {
+        (Some(ManifestEncoding::Utf16Le), rest)
+    } else if let Some(rest) = bytes.strip_prefix(UTF16BE_BOM) {
+        (Some(ManifestEncoding::Utf16Be), rest)
+    } else {
+        match bytes {
+            [first, 0, ..] if *first != 0 => (Some(ManifestEncoding::Utf16Le), bytes),
+            [0, second, ..] if *second != 0 => (Some(ManifestEncoding::Utf16Be), bytes),
+            _ => (None, bytes),
+        }
+    };
+
+    let text = match encoding {
+        None | Some(ManifestEncoding::Utf8Bom) => {
+            String::from_utf8(body.to_vec()).map_err(|e| format!("not valid UTF-8: {e}"))?
+        }
+        Some(utf16) => {
+            if body.len() % 2 != 0 {
+                return Err(format!("not valid {}: odd byte length", utf16.as_str()));
+            }
+            let units: Vec<u16> = body
+                .chunks_exact(2)
+                .map(|pair| match utf16 {
+                    ManifestEncoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+                    _ => u16::from_le_bytes([pair[0], pair[1]]),
+                })
+                .collect();
+            String::from_utf16(&units).map_err(|e| format!("not valid {}: {e}", utf16.as_str()))?
+        }
+    };
+    Ok((text, encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|unit| {
+                if big_endian {
+                    unit.to_be_bytes()
+                } else {
+                    unit.to_le_bytes()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn boms_and_utf16_decode_to_the_same_text() {
+        let json = r#"{"note":"café"}"#;
+        assert_eq!(
+            decode_manifest(json.as_bytes()).unwrap(),
+            (json.to_string(), None)
+        );
+
+        let cases = [
+            (
+                [UTF8_BOM, json.as_bytes()].concat(),
+                ManifestEncoding::Utf8Bom,
+            ),
+            (
+                [UTF16LE_BOM, &utf16(json, false)].concat(),
+                ManifestEncoding::Utf16Le,
+            ),
+            (
+                [UTF16BE_BOM, &utf16(json, true)].concat(),
+                ManifestEncoding::Utf16Be,
+            ),
+            (utf16(json, false), ManifestEncoding::Utf16Le),
+            (utf16(json, true), ManifestEncoding::Utf16Be),
+        ];
+        for (bytes, encoding) in cases {
+            assert_eq!(
+                decode_manifest(&bytes).unwrap(),
+                (json.to_string(), Some(encoding))
+            );
+        }
+
+        assert!(decode_manifest(&[0xFF, 0xFE, b'{']).is_err());
+        assert!(decode_manifest(&[b'{', 0xC3]).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::seal::archive::{is_archive, read_archive_manifest};
+use crate::seal::limits::MAX_MANIFEST_BYTES;
+use crate::seal::manifest::Manifest;
+
+use super::encoding::decode_manifest;
+use super::report::VerifyOutcome;
+
+/// Version tag for `pack id --json` output.
+pub const ID_REPORT_VERSION: &str = "pack.id.v0";
+
+/// A pack's recorded `pack_id` beside the one its manifest hashes to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IdReport {
+    pub version: String,
+    /// `OK` when the IDs match, `INVALID` when they do not.
+    pub outcome: VerifyOutcome,
+    pub pack: String,
+    /// The `pack_id` recorded in the manifest.
+    pub pack_id: String,
+    /// The `pack_id` the manifest hashes to.
+    pub recomputed: String,
+}
+
+impl IdReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("id report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        format!(
+            "pack id: {}\n  pack_id: {}\n  recomputed: {}",
+            self.outcome, self.pack_id, self.recomputed
+        )
+    }
+}
+
+/// Execute `pack id <PACK_DIR>`.
+///
+/// Returns (output, exit_code): 0 when the recorded and recomputed IDs match,
+/// 1 when they differ, and 2 with a refusal when the manifest cannot be read.
+pub fn execute_id(pack_dir: &Path, json_output: bool) -> (String, u8) {
+    match pack_id_report(pack_dir) {
+        Ok(report) => {
+            let exit_code = if report.outcome == VerifyOutcome::OK {
+                0
+            } else {
+                1
+            };
+            let output = if json_output {
+                report.to_json()
+            } else {
+                report.to_human()
+            };
+            (output, exit_code)
+        }
+        Err(envelope) => (envelope.to_json(), 2),
+    }
+}
+
+/// Recompute the `pack_id` of the manifest in `pack_dir` (a directory or
+/// `.pack.tar`). Only the manifest is read: member bytes are not hashed, so
+/// a match says nothing about them; use `pack verify` for that.
+pub fn pack_id_report(pack_dir: &Path) -> Result<IdReport, Box<RefusalEnvelope>> {
+    let manifest = read_manifest(pack_dir)?;
+    let recomputed = manifest.recompute_pack_id();
+    Ok(IdReport {
+        version: ID_REPORT_VERSION.to_string(),
+        outcome: if recomputed == manifest.pack_id {
+            VerifyOutcome::OK
+        } else {
+            VerifyOutcome::INVALID
+        },
+        pack: pack_dir.display().to_string(),
+        pack_id: manifest.pack_id,
+        recomputed,
+    })
+}
+
+/// Read and parse the `pack.v0` manifest of `pack_dir` (a directory or
+/// `.pack.tar`) without touching members.
+///
+/// Refuses with `E_LIMIT` when the manifest exceeds [`MAX_MANIFEST_BYTES`]
+/// and with `E_BAD_PACK` when it is missing, undecodable, or another version.
+pub(crate) fn read_manifest(pack_dir: &Path) -> Result<Manifest, Box<RefusalEnvelope>> {
+    let bad_pack = |message: String| {
+        Box::new(RefusalEnvelope::new(
+            RefusalCode::BadPack,
+            Some(message),
+            Some(json!({ "pack": pack_dir.display().to_string() })),
+        ))
+    };
+    let bytes = if is_archive(pack_dir) {
+        read_archive_manifest(pack_dir)?
+    } else {
+        let manifest_path = pack_dir.join("manifest.json");
+        if let Ok(meta) = fs::metadata(&manifest_path) {
+            if meta.len() > MAX_MANIFEST_BYTES {
+                return Err(Box::new(RefusalEnvelope::new(
+                    RefusalCode::Limit,
+                    Some(format!(
+                        "manifest.json is {} bytes, exceeding the {MAX_MANIFEST_BYTES}-byte limit",
+                        meta.len()
+                    )),
+                    None,
+                )));
+            }
+        }
+        fs::read(&manifest_path).map_err(|e| bad_pack(format!("Cannot read manifest.json: {e}")))?
+    };
+    let (content, _) = decode_manifest(&bytes)
+        .map_err(|message| bad_pack(format!("Cannot read manifest.json: {message}")))?;
+    let manifest: Manifest = serde_json::from_str(&content)
+        .map_err(|e| bad_pack(format!("Invalid manifest.json: {e}")))?;
+    if manifest.version != "pack.v0" {
+        return Err(bad_pack(format!(
+            "Unsupported manifest version: {}",
+            manifest.version
+        )));
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::{execute_seal_with_options, SealOptions};
+    use tempfile::TempDir;
+
+    #[test]
+    fn recomputed_id_matches_until_the_manifest_is_edited() {
+        let tmp = TempDir::new().unwrap();
+        let artifact = tmp.path().join("a.json");
+        fs::write(&artifact, r#"{"a": 1}"#).unwrap();
+        let archive = tmp.path().join("pack.pack.tar");
+        let options = SealOptions {
+            archive: true,
+            ..SealOptions::default()
+        };
+        let sealed = execute_seal_with_options(
+            std::slice::from_ref(&artifact),
+            Some(&archive),
+            None,
+            &options,
+        )
+        .unwrap();
+
+        let report = pack_id_report(&archive).unwrap();
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.pack_id, sealed.pack_id);
+        assert_eq!(report.recomputed, sealed.pack_id);
+
+        let dir = tmp.path().join("dir");
+        fs::create_dir(&dir).unwrap();
+        crate::seal::archive::unpack_archive(&archive, &dir).unwrap();
+        let manifest_path = dir.join("manifest.json");
+        let edited = fs::read_to_string(&manifest_path)
+            .unwrap()
+            .replace("\"a.json\"", "\"b.json\"");
+        fs::write(&manifest_path, edited).unwrap();
+        let (output, exit_code) = execute_id(&dir, true);
+        assert_eq!(exit_code, 1);
+        let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["outcome"], "INVALID");
+        assert_eq!(report["pack_id"], sealed.pack_id.as_str());
+
+        assert_eq!(execute_id(tmp.path(), false).1, 2);
+    }
+}
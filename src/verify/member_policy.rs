@@ -0,0 +1,390 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::detect::{detect_content_type, MEMBER_TYPES, SNIFF_BYTES};
+use crate::seal::manifest::{Manifest, Member};
+use crate::seal::visibility::path_matches;
+
+use super::report::{InvalidFinding, Severity};
+use super::source::PackSource;
+
+/// What a pack must contain, loaded from `--policy`:
+/// `{"required": [{"type": "lockfile"}, {"artifact_version": "rvl.v0"}],
+/// "forbidden_types": ["other"], "allowed_content_types": ["application/json"]}`.
+/// Every rule is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberPolicy {
+    /// Each entry must match at least `min` members.
+    #[serde(default)]
+    pub required: Vec<MemberRequirement>,
+    /// When set, every member's type must be one of these.
+    #[serde(default)]
+    pub allowed_types: Option<Vec<String>>,
+    /// No member may have one of these types.
+    #[serde(default)]
+    pub forbidden_types: Vec<String>,
+    /// When set, every member's recorded `content_type` must be one of
+    /// these; a member sealed without one does not qualify.
+    #[serde(default)]
+    pub allowed_content_types: Option<Vec<String>>,
+    /// No member may have one of these content types.
+    #[serde(default)]
+    pub forbidden_content_types: Vec<String>,
+}
+
+impl MemberPolicy {
+    /// True when any rule reads members' `content_type`.
+    pub fn limits_content_types(&self) -> bool {
+        self.allowed_content_types.is_some()
+            || !self.forbidden_content_types.is_empty()
+            || self.required.iter().any(|r| r.content_type.is_some())
+    }
+}
+
+/// Members a policy requires; every selector that is set must hold.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberRequirement {
+    #[serde(default, rename = "type")]
+    pub member_type: Option<String>,
+    #[serde(default)]
+    pub artifact_version: Option<String>,
+    /// Member path pattern, as for `seal --restrict`.
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default = "default_min")]
+    pub min: usize,
+}
+
+fn default_min() -> usize {
+    1
+}
+
+impl MemberRequirement {
+    fn matches(&self, member: &Member) -> bool {
+        self.member_type
+            .as_ref()
+            .is_none_or(|member_type| *member_type == member.member_type)
+            && self
+                .artifact_version
+                .as_ref()
+                .is_none_or(|version| member.artifact_version.as_ref() == Some(version))
+            && self
+                .path
+                .as_ref()
+                .is_none_or(|pattern| path_matches(pattern, &member.path))
+            && self
+                .content_type
+                .as_ref()
+                .is_none_or(|content_type| member.content_type.as_ref() == Some(content_type))
+    }
+
+    /// `type = lockfile, artifact_version = rvl.v0`
+    fn describe(&self) -> String {
+        let selectors = [
+            ("type", &self.member_type),
+            ("artifact_version", &self.artifact_version),
+            ("path", &self.path),
+            ("content_type", &self.content_type),
+        ];
+        selectors
+            .iter()
+            .filter_map(|(name, value)| value.as_ref().map(|value| format!("{name} = {value}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Load a member policy file, rejecting unknown member types.
+pub fn load_member_policy(path: &Path) -> Result<MemberPolicy, String> {
+    let invalid = |message: String| format!("Invalid policy: {}: {message}", path.display());
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read policy: {}: {e}", path.display()))?;
+    let policy: MemberPolicy =
+        serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let types = policy
+        .required
+        .iter()
+        .filter_map(|requirement| requirement.member_type.as_ref())
+        .chain(policy.allowed_types.iter().flatten())
+        .chain(&policy.forbidden_types);
+    for member_type in types {
+        if !MEMBER_TYPES.contains(&member_type.as_str()) {
+            return Err(invalid(format!(
+                "type {member_type:?} is not one of {}",
+                MEMBER_TYPES.join(", ")
+            )));
+        }
+    }
+    for (index, requirement) in policy.required.iter().enumerate() {
+        if requirement.describe().is_empty() {
+            return Err(invalid(format!(
+                "required[{index}] needs at least one of type, artifact_version, path, or content_type"
+            )));
+        }
+        if requirement.min == 0 {
+            return Err(invalid(format!("required[{index}] min must be at least 1")));
+        }
+    }
+    Ok(policy)
+}
+
+/// Evaluate `policy` against a manifest, returning one `POLICY_VIOLATION`
+/// finding per unmet requirement and per member of a disallowed type.
+pub fn check_member_policy(policy: &MemberPolicy, manifest: &Manifest) -> Vec<InvalidFinding> {
+    let mut findings = Vec::new();
+    for requirement in &policy.required {
+        let found = manifest
+            .members
+            .iter()
+            .filter(|member| requirement.matches(member))
+            .count();
+        if found < requirement.min {
+            findings.push(InvalidFinding {
+                code: "POLICY_VIOLATION".to_string(),
+                severity: Severity::Error,
+                path: None,
+                expected: Some(format!(
+                    ">= {} members with {}",
+                    requirement.min,
+                    requirement.describe()
+                )),
+                actual: Some(format!("{found} members")),
+            });
+        }
+    }
+    for member in &manifest.members {
+        let expected = if policy.forbidden_types.contains(&member.member_type) {
+            Some(format!(
+                "type not in [{}]",
+                policy.forbidden_types.join(", ")
+            ))
+        } else {
+            policy
+                .allowed_types
+                .as_ref()
+                .filter(|allowed| !allowed.contains(&member.member_type))
+                .map(|allowed| format!("type in [{}]", allowed.join(", ")))
+        };
+        if let Some(expected) = expected {
+            findings.push(InvalidFinding {
+                code: "POLICY_VIOLATION".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(expected),
+                actual: Some(format!("type = {}", member.member_type)),
+            });
+        }
+
+        let content_type = member.content_type.as_deref();
+        let expected = if content_type.is_some_and(|content_type| {
+            policy
+                .forbidden_content_types
+                .iter()
+                .any(|f| f == content_type)
+        }) {
+            Some(format!(
+                "content_type not in [{}]",
+                policy.forbidden_content_types.join(", ")
+            ))
+        } else {
+            policy
+                .allowed_content_types
+                .as_ref()
+                .filter(|allowed| !content_type.is_some_and(|c| allowed.iter().any(|a| a == c)))
+                .map(|allowed| format!("content_type in [{}]", allowed.join(", ")))
+        };
+        if let Some(expected) = expected {
+            findings.push(InvalidFinding {
+                code: "POLICY_VIOLATION".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(expected),
+                actual: Some(format!("content_type = {}", content_type.unwrap_or("-"))),
+            });
+        }
+    }
+    findings
+}
+
+/// Re-detect each member's content type from its first [`SNIFF_BYTES`] bytes,
+/// so a content type policy is not met by a member relabelled in the
+/// manifest. A member whose bytes sniff as another media type than it records
+/// is a `CONTENT_TYPE_MISMATCH` finding.
+///
+/// Encrypted members and members sealed without a `content_type` are
+/// skipped, as are unreadable ones, which the hash check already reports.
+pub fn check_content_types(manifest: &Manifest, members: &dyn PackSource) -> Vec<InvalidFinding> {
+    let mut findings = Vec::new();
+    for member in &manifest.members {
+        let Some(recorded) = member
+            .content_type
+            .as_deref()
+            .filter(|_| member.ciphertext_hash.is_none())
+        else {
+            continue;
+        };
+        let mut head = Vec::with_capacity(SNIFF_BYTES);
+        let read = members
+            .open(&member.path)
+            .and_then(|reader| reader.take(SNIFF_BYTES as u64).read_to_end(&mut head));
+        if read.is_err() {
+            continue;
+        }
+        let detected = detect_content_type(&head, &member.path).media_type;
+        if detected != recorded {
+            findings.push(InvalidFinding {
+                code: "CONTENT_TYPE_MISMATCH".to_string(),
+                severity: Severity::Error,
+                path: Some(member.path.clone()),
+                expected: Some(recorded.to_string()),
+                actual: Some(detected),
+            });
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::MemorySource;
+
+    fn member(path: &str, member_type: &str, artifact_version: Option<&str>) -> Member {
+        Member {
+            path: path.to_string(),
+            bytes_hash: format!("sha256:{}", "0".repeat(64)),
+            size_bytes: None,
+            member_type: member_type.to_string(),
+            artifact_version: artifact_version.map(str::to_string),
+            visibility: None,
+            origin: None,
+            executable: None,
+            ciphertext_hash: None,
+            content_type: None,
+            charset: None,
+        }
+    }
+
+    #[test]
+    fn unmet_requirements_and_forbidden_types_are_violations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.json");
+        fs::write(
+            &path,
+            r#"{"required": [{"type": "lockfile"}, {"artifact_version": "rvl.v0", "min": 2}],
+                "forbidden_types": ["other"]}"#,
+        )
+        .unwrap();
+        let policy = load_member_policy(&path).unwrap();
+        let manifest = Manifest::new(
+            "2026-01-15T10:00:00Z".to_string(),
+            None,
+            "0.2.3".to_string(),
+            vec![
+                member("nov.lock.json", "lockfile", Some("lock.v0")),
+                member("rvl.report.json", "report", Some("rvl.v0")),
+                member("notes.txt", "other", None),
+            ],
+        );
+
+        let findings = check_member_policy(&policy, &manifest);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[0].expected.as_deref(),
+            Some(">= 2 members with artifact_version = rvl.v0")
+        );
+        assert_eq!(findings[0].actual.as_deref(), Some("1 members"));
+        assert_eq!(findings[1].path.as_deref(), Some("notes.txt"));
+    }
+
+    #[test]
+    fn binaries_in_a_json_only_pack_are_violations() {
+        let policy = MemberPolicy {
+            allowed_content_types: Some(vec!["application/json".to_string()]),
+            ..MemberPolicy::default()
+        };
+        let typed = |path: &str, content_type: Option<&str>| Member {
+            content_type: content_type.map(str::to_string),
+            ..member(path, "other", None)
+        };
+        let manifest = Manifest::new(
+            "2026-01-15T10:00:00Z".to_string(),
+            None,
+            "0.2.3".to_string(),
+            vec![
+                typed("a.json", Some("application/json")),
+                typed("b.json", Some("application/octet-stream")),
+                typed("old.json", None),
+            ],
+        );
+
+        let findings = check_member_policy(&policy, &manifest);
+        let flagged: Vec<_> = findings
+            .iter()
+            .map(|f| (f.path.as_deref().unwrap(), f.actual.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![
+                ("b.json", "content_type = application/octet-stream"),
+                ("old.json", "content_type = -"),
+            ]
+        );
+    }
+
+    #[test]
+    fn mislabelled_binary_is_a_content_type_mismatch() {
+        let labelled = |path: &str| Member {
+            content_type: Some("application/json".to_string()),
+            ..member(path, "other", None)
+        };
+        let manifest = Manifest::new(
+            "2026-01-15T10:00:00Z".to_string(),
+            None,
+            "0.2.3".to_string(),
+            vec![labelled("a.json"), labelled("b.json")],
+        );
+        let members: MemorySource = [
+            ("a.json", b"{\"ok\": true}".to_vec()),
+            ("b.json", b"\x7fELF\x00\x01binary".to_vec()),
+        ]
+        .into_iter()
+        .collect();
+        let policy = MemberPolicy {
+            allowed_content_types: Some(vec!["application/json".to_string()]),
+            ..MemberPolicy::default()
+        };
+        assert!(policy.limits_content_types());
+        assert!(check_member_policy(&policy, &manifest).is_empty());
+
+        let findings = check_content_types(&manifest, &members);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].code, "CONTENT_TYPE_MISMATCH");
+        assert_eq!(findings[0].path.as_deref(), Some("b.json"));
+        assert_eq!(
+            findings[0].actual.as_deref(),
+            Some("application/octet-stream")
+        );
+    }
+
+    #[test]
+    fn unknown_types_and_empty_requirements_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.json");
+        fs::write(&path, r#"{"allowed_types": ["ledger"]}"#).unwrap();
+        assert!(load_member_policy(&path)
+            .unwrap_err()
+            .contains("type \"ledger\""));
+        fs::write(&path, r#"{"required": [{"min": 3}]}"#).unwrap();
+        assert!(load_member_policy(&path)
+            .unwrap_err()
+            .contains("required[0]"));
+    }
+}
@@ -1,8 +1,48 @@
+mod artifact;
+mod bulk;
+mod catalog;
 mod checks;
 mod command;
+mod decrypt;
+mod deep;
+mod encoding;
+mod id;
+mod member_policy;
+mod nested;
+mod policy;
+mod registry;
+mod repair;
 mod report;
 mod schema;
+mod source;
+mod stream;
+mod suppress;
 
+pub use artifact::{
+    execute_schema_validate, SchemaValidateReport, BUILTIN_SCHEMA, SCHEMA_VALIDATE_REPORT_VERSION,
+};
+pub use bulk::{
+    discover_packs, execute_verify_all, verify_all, PackVerifyResult, VerifyAllReport,
+    VerifyAllSummary, VERIFY_ALL_REPORT_VERSION,
+};
+pub use catalog::{SchemaCatalog, SCHEMA_FILE_SUFFIX};
 pub(crate) use checks::run_checks;
-pub use command::execute_verify;
-pub use report::{VerifyOutcome, VerifyReport};
+pub use command::{
+    execute_verify, execute_verify_json_lines, execute_verify_with_options,
+    execute_verify_with_repair_report, verify_pack, verify_source, VerifyOptions,
+};
+pub(crate) use encoding::decode_manifest;
+pub(crate) use id::read_manifest;
+pub use id::{execute_id, pack_id_report, IdReport, ID_REPORT_VERSION};
+pub use member_policy::{
+    check_content_types, check_member_policy, load_member_policy, MemberPolicy, MemberRequirement,
+};
+pub use policy::{check_trust_policy, load_trust_policy, TrustPolicy};
+pub use repair::{RepairAction, RepairPlan, REPAIR_PLAN_VERSION};
+pub use report::{
+    HashCheck, InvalidFinding, SchemaMemberResult, Severity, TimestampCheck, VerifyOutcome,
+    VerifyReport, VerifyWarning, VERIFY_REPORT_VERSION,
+};
+pub use source::{DirSource, EntryKind, EntryMeta, MemorySource, PackSource};
+pub use stream::VERIFY_STREAM_VERSION;
+pub use suppress::Suppression;
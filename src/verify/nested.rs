@@ -0,0 +1,175 @@
+use std::collections::BTreeSet;
+use std::fs;
+
+use super::command::{refusal, verify_pack, verify_source, VerifyOptions};
+use super::report::{InvalidFinding, Severity, VerifyOutcome, VerifyReport};
+use super::source::{PackSource, SubSource};
+use crate::seal::archive::ARCHIVE_EXTENSION;
+use crate::seal::manifest::Manifest;
+
+/// Verify the packs embedded in this one (`--resolve-nested`).
+///
+/// A member typed `pack` at `<dir>/manifest.json` makes `<dir>` a nested
+/// pack directory; a member ending in `.pack.tar` is a nested archive. Each
+/// is verified in full, recursively, and its findings are reported with the
+/// nested pack's path prefixed to theirs (a finding without a path gets the
+/// prefix itself). A finding the outer pack already reports for the same
+/// path is not repeated. A nested pack that cannot be verified at all is a
+/// `NESTED_PACK_UNVERIFIABLE` finding.
+///
+/// Returns (every nested pack verified OK, findings).
+pub fn check_nested_packs(
+    manifest: &Manifest,
+    members: &dyn PackSource,
+    options: &VerifyOptions,
+    outer_findings: &[InvalidFinding],
+) -> (bool, Vec<InvalidFinding>) {
+    let nested_options = VerifyOptions {
+        max_note_bytes: options.max_note_bytes,
+        deep: options.deep,
+        validate_registry: options.validate_registry,
+        quick: options.quick,
+        strict: options.strict,
+        schema_dir: options.schema_dir.clone(),
+        resolve_nested: true,
+        ..VerifyOptions::default()
+    };
+    let mut seen: BTreeSet<(String, Option<String>)> = outer_findings
+        .iter()
+        .map(|f| (f.code.clone(), f.path.clone()))
+        .collect();
+
+    let mut all_ok = true;
+    let mut findings = Vec::new();
+    for prefix in nested_pack_paths(manifest) {
+        let report = match members.local_dir() {
+            Some(member_root) => verify_pack(&member_root.join(&prefix), &nested_options),
+            None => verify_nested_in(members, &prefix, &nested_options),
+        };
+        match report.outcome {
+            VerifyOutcome::OK => continue,
+            VerifyOutcome::INVALID => {
+                for mut finding in report.invalid {
+                    finding.path = Some(match finding.path {
+                        Some(path) => format!("{prefix}/{path}"),
+                        None => prefix.clone(),
+                    });
+                    if seen.insert((finding.code.clone(), finding.path.clone())) {
+                        findings.push(finding);
+                    }
+                }
+            }
+            VerifyOutcome::REFUSAL => {
+                let reason = report.refusal.unwrap_or_default();
+                findings.push(InvalidFinding {
+                    code: "NESTED_PACK_UNVERIFIABLE".to_string(),
+                    severity: Severity::Error,
+                    path: Some(prefix),
+                    expected: None,
+                    actual: Some(
+                        reason["message"]
+                            .as_str()
+                            .unwrap_or("verify refused the nested pack")
+                            .to_string(),
+                    ),
+                });
+            }
+        }
+        all_ok = false;
+    }
+    (all_ok, findings)
+}
+
+/// Verify the nested pack at `prefix` in a source with no directory on disk.
+///
+/// A nested directory is read through the outer source; a nested archive is
+/// copied to scratch space, since archives are unpacked from a file.
+fn verify_nested_in(
+    members: &dyn PackSource,
+    prefix: &str,
+    options: &VerifyOptions,
+) -> VerifyReport {
+    if !prefix.ends_with(ARCHIVE_EXTENSION) {
+        return verify_source(&SubSource::new(members, prefix), options);
+    }
+    let bytes = match members.read(prefix) {
+        Ok(bytes) => bytes,
+        Err(e) => return refusal("E_IO", format!("Cannot read {prefix}: {e}")),
+    };
+    let scratch = match tempfile::tempdir() {
+        Ok(dir) => dir,
+        Err(e) => return refusal("E_IO", format!("Cannot create scratch directory: {e}")),
+    };
+    let archive = scratch.path().join(format!("nested{ARCHIVE_EXTENSION}"));
+    if let Err(e) = fs::write(&archive, bytes) {
+        return refusal("E_IO", format!("Cannot write {}: {e}", archive.display()));
+    }
+    verify_pack(&archive, options)
+}
+
+/// Pack directories and archives embedded as members, in manifest order.
+fn nested_pack_paths(manifest: &Manifest) -> Vec<String> {
+    manifest
+        .members
+        .iter()
+        .filter_map(|member| {
+            if member.member_type == "pack" {
+                member
+                    .path
+                    .strip_suffix("/manifest.json")
+                    .map(str::to_string)
+            } else if member.path.ends_with(ARCHIVE_EXTENSION) {
+                Some(member.path.clone())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn nested_findings_carry_the_nested_prefix() {
+        let src = TempDir::new().unwrap();
+        let file = src.path().join("jan.json");
+        fs::write(&file, "1").unwrap();
+        let out = TempDir::new().unwrap();
+        let inner = out.path().join("months");
+        execute_seal(&[file], Some(&inner), None).unwrap();
+        let outer = out.path().join("quarter");
+        execute_seal(&[inner], Some(&outer), None).unwrap();
+
+        let options = VerifyOptions {
+            resolve_nested: true,
+            ..VerifyOptions::default()
+        };
+        let report = verify_pack(&outer, &options);
+        assert_eq!(report.outcome, VerifyOutcome::OK);
+        assert_eq!(report.checks.nested_packs, Some(true));
+
+        // Rewrite the nested manifest's note: the outer pack sees a resized
+        // member, the nested check sees the nested pack_id no longer holds.
+        let nested_manifest = outer.join("months/manifest.json");
+        let mut manifest: serde_json::Value =
+            serde_json::from_slice(&fs::read(&nested_manifest).unwrap()).unwrap();
+        manifest["note"] = "edited".into();
+        fs::write(&nested_manifest, manifest.to_string()).unwrap();
+
+        let report = verify_pack(&outer, &options);
+        assert_eq!(report.outcome, VerifyOutcome::INVALID);
+        assert_eq!(report.checks.nested_packs, Some(false));
+        let codes: Vec<_> = report
+            .invalid
+            .iter()
+            .map(|f| (f.code.as_str(), f.path.as_deref()))
+            .collect();
+        assert!(codes.contains(&("SIZE_MISMATCH", Some("months/manifest.json"))));
+        assert!(codes.contains(&("PACK_ID_MISMATCH", Some("months"))));
+    }
+}
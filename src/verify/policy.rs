@@ -0,0 +1,243 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::VerifyingKey;
+use serde::Deserialize;
+
+use crate::seal::manifest::Manifest;
+use crate::sign::{parse_public_key, PackSignature};
+
+use super::report::{InvalidFinding, Severity};
+
+/// Which packs an organization accepts, loaded from `--trust-policy`:
+/// `{"min_tool_version": "0.2.1", "allowed_signers": ["<public key>"],
+/// "max_age_days": 365}`. Every rule is optional.
+#[derive(Debug, Clone, Default)]
+pub struct TrustPolicy {
+    /// Packs sealed by an older pack release are rejected.
+    pub min_tool_version: Option<String>,
+    /// When set, the pack must carry a valid signature by one of these keys.
+    pub allowed_signers: Option<Vec<VerifyingKey>>,
+    /// Packs created more than this many days ago are rejected.
+    pub max_age_days: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TrustPolicyFile {
+    #[serde(default)]
+    min_tool_version: Option<String>,
+    #[serde(default)]
+    allowed_signers: Option<Vec<String>>,
+    #[serde(default)]
+    max_age_days: Option<u64>,
+}
+
+/// Load a trust policy file. Signer keys are PEM, or 32 bytes as hex or
+/// base64, as for `--public-key`.
+pub fn load_trust_policy(path: &Path) -> Result<TrustPolicy, String> {
+    let invalid = |message: String| format!("Invalid trust policy: {}: {message}", path.display());
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read trust policy: {}: {e}", path.display()))?;
+    let file: TrustPolicyFile =
+        serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    if let Some(version) = &file.min_tool_version {
+        if parse_version(version).is_none() {
+            return Err(invalid(format!(
+                "min_tool_version {version:?} is not a semver version"
+            )));
+        }
+    }
+    let allowed_signers = file
+        .allowed_signers
+        .map(|keys| {
+            keys.iter()
+                .map(|key| {
+                    parse_public_key(key)
+                        .map_err(|e| invalid(format!("allowed signer {key:?}: {e}")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    Ok(TrustPolicy {
+        min_tool_version: file.min_tool_version,
+        allowed_signers,
+        max_age_days: file.max_age_days,
+    })
+}
+
+/// Evaluate `policy` against a pack, returning one `TRUST_POLICY` finding per
+/// violated rule.
+///
+/// `signature` is the pack's `manifest.sig.json`, if it has one; it only
+/// satisfies `allowed_signers` when it also verifies against the manifest.
+pub fn check_trust_policy(
+    policy: &TrustPolicy,
+    manifest: &Manifest,
+    signature: Option<&PackSignature>,
+    now: DateTime<Utc>,
+) -> Vec<InvalidFinding> {
+    let mut findings = Vec::new();
+    let mut violation = |expected: String, actual: String| {
+        findings.push(InvalidFinding {
+            code: "TRUST_POLICY".to_string(),
+            severity: Severity::Error,
+            path: None,
+            expected: Some(expected),
+            actual: Some(actual),
+        });
+    };
+
+    if let Some(minimum) = &policy.min_tool_version {
+        let too_old = match (
+            parse_version(&manifest.tool_version),
+            parse_version(minimum),
+        ) {
+            (Some(actual), Some(minimum)) => actual < minimum,
+            _ => true,
+        };
+        if too_old {
+            violation(
+                format!("tool_version >= {minimum}"),
+                format!("tool_version = {}", manifest.tool_version),
+            );
+        }
+    }
+
+    if let Some(signers) = &policy.allowed_signers {
+        let actual = match signature {
+            None => Some("unsigned".to_string()),
+            Some(signature) if signature.verify(manifest, None).is_err() => {
+                Some("invalid signature".to_string())
+            }
+            Some(signature) => {
+                let signer = parse_public_key(&signature.public_key).ok();
+                (!signers.iter().any(|key| Some(key) == signer.as_ref()))
+                    .then(|| format!("signed by {}", signature.public_key))
+            }
+        };
+        if let Some(actual) = actual {
+            violation("signed by an allowed signer".to_string(), actual);
+        }
+    }
+
+    if let Some(max_days) = policy.max_age_days {
+        match DateTime::parse_from_rfc3339(&manifest.created) {
+            Ok(created) => {
+                let age_days = (now - created.with_timezone(&Utc)).num_days();
+                if age_days > max_days as i64 {
+                    violation(
+                        format!("created within {max_days} days"),
+                        format!("created {age_days} days ago ({})", manifest.created),
+                    );
+                }
+            }
+            Err(_) => violation(
+                format!("created within {max_days} days"),
+                format!("created = {:?} (not RFC3339)", manifest.created),
+            ),
+        }
+    }
+
+    findings
+}
+
+/// A comparable semver: the numeric core, then releases after pre-releases.
+#[derive(Debug, PartialEq, Eq)]
+struct Version {
+    core: [u64; 3],
+    pre_release: Option<String>,
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.core
+            .cmp(&other.core)
+            .then_with(|| match (&self.pre_release, &other.pre_release) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn parse_version(text: &str) -> Option<Version> {
+    let text = text
+        .split_once('+')
+        .map_or(text, |(version, _build)| version);
+    let (core, pre_release) = match text.split_once('-') {
+        Some((core, pre)) => (core, Some(pre.to_string())),
+        None => (text, None),
+    };
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let core = [parts.next()??, parts.next()??, parts.next()??];
+    parts
+        .next()
+        .is_none()
+        .then_some(Version { core, pre_release })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    fn manifest(tool_version: &str, created: &str) -> Manifest {
+        let mut manifest =
+            Manifest::new(created.to_string(), None, tool_version.to_string(), vec![]);
+        manifest.finalize();
+        manifest
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-06-01T00:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn each_violated_rule_is_one_finding() {
+        let trusted = SigningKey::from_bytes(&[1u8; 32]);
+        let other = SigningKey::from_bytes(&[2u8; 32]);
+        let policy = TrustPolicy {
+            min_tool_version: Some("0.2.1".to_string()),
+            allowed_signers: Some(vec![trusted.verifying_key()]),
+            max_age_days: Some(30),
+        };
+
+        let fresh = manifest("0.2.3", "2026-05-20T00:00:00Z");
+        let signed = PackSignature::sign(&fresh, &trusted);
+        assert!(check_trust_policy(&policy, &fresh, Some(&signed), now()).is_empty());
+
+        let stale = manifest("0.2.0", "2026-01-01T00:00:00Z");
+        let wrong_signer = PackSignature::sign(&stale, &other);
+        let actual: Vec<_> = check_trust_policy(&policy, &stale, Some(&wrong_signer), now())
+            .into_iter()
+            .map(|finding| finding.actual.unwrap())
+            .collect();
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual[0], "tool_version = 0.2.0");
+        assert!(actual[1].starts_with("signed by "));
+        assert!(actual[2].starts_with("created 151 days ago"));
+
+        let unsigned = check_trust_policy(&policy, &fresh, None, now());
+        assert_eq!(unsigned[0].actual.as_deref(), Some("unsigned"));
+    }
+
+    #[test]
+    fn pre_releases_sort_before_their_release() {
+        let v = |text| parse_version(text).unwrap();
+        assert!(v("0.2.1-rc.1") < v("0.2.1"));
+        assert!(v("0.2.1") < v("0.10.0"));
+        assert_eq!(v("1.0.0+build.5"), v("1.0.0"));
+        assert!(parse_version("1.0").is_none());
+        assert!(parse_version("1.0.0.0").is_none());
+    }
+}
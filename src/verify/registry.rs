@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::report::{InvalidFinding, Severity};
+use super::source::PackSource;
+use crate::seal::manifest::{Manifest, Member};
+
+/// Check registry tables parse as CSV or TSV (`--validate-registry`).
+///
+/// A registry table is a `registry` member ending in `.csv` or `.tsv`. Each
+/// must be UTF-8 (a leading byte order mark is allowed), have a header of
+/// distinct, non-empty column names, and give every row as many fields as
+/// the header. A `registry` member named `registry.json` or
+/// `*.registry.json` may declare row counts for the tables beside it:
+///
+/// ```json
+/// {"tables": {"loans.csv": {"row_count": 120}}}
+/// ```
+///
+/// Declared paths are relative to the declaring file's directory; each must
+/// be a table in the pack with that many rows, not counting the header.
+/// Every problem is a `REGISTRY_MALFORMED` finding on the offending member.
+/// Encrypted members and members that cannot be read are skipped; the hash
+/// checks already cover them. Returns (all tables well-formed, findings).
+pub fn check_registry_tables(
+    manifest: &Manifest,
+    members: &dyn PackSource,
+) -> (bool, Vec<InvalidFinding>) {
+    let mut findings = Vec::new();
+    let mut declared = BTreeMap::new();
+    for member in manifest.members.iter().filter(|m| is_declaration(m)) {
+        if let Some(bytes) = readable(member, members) {
+            read_declaration(&member.path, &bytes, &mut declared, &mut findings);
+        }
+    }
+
+    let mut tables = BTreeSet::new();
+    for member in manifest
+        .members
+        .iter()
+        .filter(|m| table_delimiter(m).is_some())
+    {
+        tables.insert(member.path.as_str());
+        let Some(bytes) = readable(member, members) else {
+            continue;
+        };
+        let delimiter = table_delimiter(member).expect("filtered to tables");
+        match parse_table(&bytes, delimiter) {
+            Ok(rows) => {
+                if let Some(declaration) = declared.get(member.path.as_str()) {
+                    if declaration.row_count != rows {
+                        findings.push(malformed(
+                            &member.path,
+                            format!(
+                                "{} rows, as declared in {}",
+                                declaration.row_count, declaration.by
+                            ),
+                            format!("{rows} rows"),
+                        ));
+                    }
+                }
+            }
+            Err((expected, actual)) => findings.push(malformed(&member.path, expected, actual)),
+        }
+    }
+
+    for (path, declaration) in &declared {
+        if !tables.contains(path.as_str()) {
+            findings.push(malformed(
+                &declaration.by,
+                format!("registry table {path}"),
+                "not a .csv or .tsv registry member".to_string(),
+            ));
+        }
+    }
+    (findings.is_empty(), findings)
+}
+
+/// A row count one registry file declares for one table.
+struct Declaration {
+    row_count: u64,
+    /// Path of the declaring registry file.
+    by: String,
+}
+
+fn is_declaration(member: &Member) -> bool {
+    let name = member.path.rsplit('/').next().unwrap_or(&member.path);
+    member.member_type == "registry"
+        && (name == "registry.json" || name.ends_with(".registry.json"))
+}
+
+fn table_delimiter(member: &Member) -> Option<u8> {
+    if member.member_type != "registry" {
+        return None;
+    }
+    let extension = Path::new(&member.path).extension()?.to_str()?;
+    match extension.to_ascii_lowercase().as_str() {
+        "csv" => Some(b','),
+        "tsv" => Some(b'\t'),
+        _ => None,
+    }
+}
+
+fn readable(member: &Member, members: &dyn PackSource) -> Option<Vec<u8>> {
+    if member.ciphertext_hash.is_some() {
+        return None;
+    }
+    members.read(&member.path).ok()
+}
+
+fn read_declaration(
+    path: &str,
+    bytes: &[u8],
+    declared: &mut BTreeMap<String, Declaration>,
+    findings: &mut Vec<InvalidFinding>,
+) {
+    let document: Value = match serde_json::from_slice(bytes) {
+        Ok(document) => document,
+        Err(e) => {
+            findings.push(malformed(
+                path,
+                "JSON".to_string(),
+                format!("invalid JSON: {e}"),
+            ));
+            return;
+        }
+    };
+    let Some(tables) = document.get("tables") else {
+        return;
+    };
+    let Some(tables) = tables.as_object() else {
+        findings.push(malformed(
+            path,
+            "tables: an object of table paths".to_string(),
+            format!("tables: {tables}"),
+        ));
+        return;
+    };
+    let dir = path.rsplit_once('/').map(|(dir, _)| dir);
+    for (table, entry) in tables {
+        let Some(row_count) = entry.get("row_count").and_then(Value::as_u64) else {
+            findings.push(malformed(
+                path,
+                format!("tables.{table}.row_count: a non-negative integer"),
+                format!("tables.{table}: {entry}"),
+            ));
+            continue;
+        };
+        let table = match dir {
+            Some(dir) => format!("{dir}/{table}"),
+            None => table.clone(),
+        };
+        declared.insert(
+            table,
+            Declaration {
+                row_count,
+                by: path.to_string(),
+            },
+        );
+    }
+}
+
+fn malformed(path: &str, expected: String, actual: String) -> InvalidFinding {
+    InvalidFinding {
+        code: "REGISTRY_MALFORMED".to_string(),
+        severity: Severity::Error,
+        path: Some(path.to_string()),
+        expected: Some(expected),
+        actual: Some(actual),
+    }
+}
+
+/// Parse a table and return its data row count, or the first problem as
+/// (expected, actual).
+///
+/// CSV follows RFC 4180: fields may be quoted, with `""` for a quote inside
+/// one. TSV fields are never quoted. Rows end in `\n` or `\r\n`, and the
+/// last one need not.
+fn parse_table(bytes: &[u8], delimiter: u8) -> Result<u64, (String, String)> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let text = std::str::from_utf8(bytes).map_err(|e| {
+        (
+            "UTF-8".to_string(),
+            format!("invalid UTF-8 at byte {}", e.valid_up_to()),
+        )
+    })?;
+    let mut rows = Rows::new(text, delimiter);
+
+    let header = match rows.next() {
+        Some(row) => row?,
+        None => return Err(("a header row".to_string(), "empty table".to_string())),
+    };
+    let mut seen = BTreeSet::new();
+    for (index, column) in header.iter().enumerate() {
+        if column.trim().is_empty() {
+            return Err((
+                "non-empty column names".to_string(),
+                format!("column {} is unnamed", index + 1),
+            ));
+        }
+        if !seen.insert(column.as_str()) {
+            return Err((
+                "distinct column names".to_string(),
+                format!("column {column:?} repeats"),
+            ));
+        }
+    }
+
+    let mut count = 0;
+    for row in rows {
+        let row = row?;
+        count += 1;
+        if row.len() != header.len() {
+            return Err((
+                format!("{} fields per row", header.len()),
+                format!("row {count}: {} fields", row.len()),
+            ));
+        }
+    }
+    Ok(count)
+}
+
+/// Iterator over the rows of a CSV or TSV table.
+struct Rows<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+    quoted: bool,
+    delimiter: char,
+}
+
+impl<'a> Rows<'a> {
+    fn new(text: &'a str, delimiter: u8) -> Self {
+        Rows {
+            text,
+            pos: 0,
+            line: 1,
+            quoted: delimiter == b',',
+            delimiter: delimiter as char,
+        }
+    }
+}
+
+impl Iterator for Rows<'_> {
+    type Item = Result<Vec<String>, (String, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.text.len() {
+            return None;
+        }
+        let start_line = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut chars = self.text[self.pos..].char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek().is_some_and(|&(_, next)| next == '"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => in_quotes = false,
+                    '\n' => {
+                        self.line += 1;
+                        field.push(c);
+                    }
+                    _ => field.push(c),
+                }
+                continue;
+            }
+            match c {
+                '"' if self.quoted && field.is_empty() => in_quotes = true,
+                '\n' => {
+                    self.pos += offset + 1;
+                    self.line += 1;
+                    if field.ends_with('\r') {
+                        field.pop();
+                    }
+                    fields.push(field);
+                    return Some(Ok(fields));
+                }
+                c if c == self.delimiter => fields.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+        self.pos = self.text.len();
+        if in_quotes {
+            return Some(Err((
+                "a closing quote".to_string(),
+                format!("quoted field opened on line {start_line} never closes"),
+            )));
+        }
+        fields.push(field);
+        Some(Ok(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::command::execute_seal;
+    use crate::verify::{read_manifest, MemorySource};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn tables_parse_with_quotes_crlf_and_a_bom() {
+        let csv = "\u{FEFF}id,name\r\n1,\"Smith, J\"\r\n2,\"say \"\"hi\"\"\nthere\"\r\n";
+        assert_eq!(parse_table(csv.as_bytes(), b','), Ok(2));
+        assert_eq!(parse_table(b"id\tname\n1\t\"x\n", b'\t'), Ok(1));
+
+        let err = |text: &[u8], delimiter| parse_table(text, delimiter).unwrap_err().1;
+        assert_eq!(err(b"id,name\n1\n", b','), "row 1: 1 fields");
+        assert_eq!(err(b"id,id\n", b','), "column \"id\" repeats");
+        assert_eq!(err(b"id,,name\n", b','), "column 2 is unnamed");
+        assert_eq!(
+            err(b"id\n\"open\n", b','),
+            "quoted field opened on line 2 never closes"
+        );
+        assert_eq!(err(b"id\n\xFF\n", b','), "invalid UTF-8 at byte 3");
+        assert_eq!(err(b"", b','), "empty table");
+    }
+
+    #[test]
+    fn declared_row_counts_are_checked() {
+        let tmp = TempDir::new().unwrap();
+        let registry = tmp.path().join("registry");
+        fs::create_dir(&registry).unwrap();
+        fs::write(registry.join("loans.csv"), "id,amount\n1,100\n2,250\n").unwrap();
+        fs::write(registry.join("rates.tsv"), "tenor\trate\n1y\t4.1\n").unwrap();
+        fs::write(
+            registry.join("registry.json"),
+            r#"{"tables": {"loans.csv": {"row_count": 3}, "rates.tsv": {"row_count": 1}, "gone.csv": {"row_count": 0}}}"#,
+        )
+        .unwrap();
+        let sealed = execute_seal(&[registry], Some(&tmp.path().join("pack")), None).unwrap();
+        let manifest = read_manifest(&sealed.output_dir).unwrap();
+        let members: MemorySource = manifest
+            .members
+            .iter()
+            .map(|m| {
+                let bytes = fs::read(sealed.output_dir.join(&m.path)).unwrap();
+                (m.path.clone(), bytes)
+            })
+            .collect();
+
+        let (ok, findings) = check_registry_tables(&manifest, &members);
+        assert!(!ok);
+        let found: Vec<_> = findings
+            .iter()
+            .map(|f| (f.path.as_deref().unwrap(), f.actual.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("registry/loans.csv", "2 rows"),
+                (
+                    "registry/registry.json",
+                    "not a .csv or .tsv registry member"
+                ),
+            ]
+        );
+        assert_eq!(findings[0].code, "REGISTRY_MALFORMED");
+    }
+}
@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::report::{InvalidFinding, VerifyOutcome, VerifyReport};
+
+/// Version tag for `pack verify --repair-report` output.
+pub const REPAIR_PLAN_VERSION: &str = "pack.repair.v0";
+
+/// Remediation steps for the findings of one verify run.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairPlan {
+    pub version: String,
+    pub pack_dir: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<String>,
+    pub outcome: VerifyOutcome,
+    /// One step per finding, in report order; empty when the pack is OK.
+    pub actions: Vec<RepairAction>,
+}
+
+/// What to do about one finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepairAction {
+    /// `re-source`, `restore`, `remove`, `fix-permissions`, `reseal`, or
+    /// `investigate`.
+    pub action: String,
+    /// The finding code (or refusal code) this step resolves.
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// For `re-source`: the hash the replacement bytes must have.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_hash: Option<String>,
+    pub instruction: String,
+}
+
+impl RepairPlan {
+    /// Derive the plan from a verify report. A refusal becomes a single
+    /// `investigate` step carrying the refusal message.
+    pub fn from_report(pack_dir: &Path, report: &VerifyReport) -> Self {
+        let mut actions: Vec<RepairAction> = report.invalid.iter().map(action_for).collect();
+        if let Some(refusal) = &report.refusal {
+            actions.push(RepairAction {
+                action: "investigate".to_string(),
+                code: refusal["code"].as_str().unwrap_or("REFUSAL").to_string(),
+                path: None,
+                expected_hash: None,
+                actual_hash: None,
+                instruction: refusal["message"]
+                    .as_str()
+                    .unwrap_or("verify refused the pack")
+                    .to_string(),
+            });
+        }
+        Self {
+            version: REPAIR_PLAN_VERSION.to_string(),
+            pack_dir: pack_dir.display().to_string(),
+            pack_id: report.pack_id.clone(),
+            outcome: report.outcome.clone(),
+            actions,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("repair plan serialization cannot fail")
+    }
+
+    /// Write the plan to `path`, replacing any previous report.
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_json() + "\n")
+    }
+}
+
+fn action_for(finding: &InvalidFinding) -> RepairAction {
+    let path = finding.path.as_deref().unwrap_or("-");
+    let (action, instruction) = match finding.code.as_str() {
+        "HASH_MISMATCH" => (
+            "re-source",
+            format!(
+                "Replace {path} with the original bytes from its source; they must hash to {}",
+                finding.expected.as_deref().unwrap_or("the recorded bytes_hash")
+            ),
+        ),
+        "SIZE_MISMATCH" => (
+            "re-source",
+            format!(
+                "Replace {path} with the original bytes from its source; they must be {} bytes",
+                finding.expected.as_deref().unwrap_or("the recorded size_bytes")
+            ),
+        ),
+        "MISSING_MEMBER" => (
+            "restore",
+            format!("Restore {path} from its source or a copy of the pack"),
+        ),
+        "EXTRA_MEMBER" => (
+            "remove",
+            format!("Remove {path}; it is not declared in the manifest"),
+        ),
+        "UNREADABLE_MEMBER" => (
+            "fix-permissions",
+            format!("Make {path} readable by the verifying user, then verify again"),
+        ),
+        "PACK_ID_MISMATCH" | "MEMBER_COUNT_MISMATCH" => (
+            "reseal",
+            "manifest.json was edited after sealing; restore the original manifest or reseal the artifacts"
+                .to_string(),
+        ),
+        code => (
+            "investigate",
+            match &finding.path {
+                Some(path) => format!("Review {code} on {path}"),
+                None => format!("Review {code}"),
+            },
+        ),
+    };
+    RepairAction {
+        action: action.to_string(),
+        code: finding.code.clone(),
+        path: finding.path.clone(),
+        expected_hash: (finding.code == "HASH_MISMATCH")
+            .then(|| finding.expected.clone())
+            .flatten(),
+        actual_hash: (finding.code == "HASH_MISMATCH")
+            .then(|| finding.actual.clone())
+            .flatten(),
+        instruction,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::report::{Severity, VerifyChecks};
+
+    fn finding(code: &str, path: Option<&str>) -> InvalidFinding {
+        InvalidFinding {
+            code: code.to_string(),
+            severity: Severity::Error,
+            path: path.map(str::to_string),
+            expected: (code == "HASH_MISMATCH").then(|| "sha256:aa".to_string()),
+            actual: (code == "HASH_MISMATCH").then(|| "sha256:bb".to_string()),
+        }
+    }
+
+    #[test]
+    fn findings_map_to_repair_actions() {
+        let report = VerifyReport::invalid(
+            Some("sha256:pack".to_string()),
+            VerifyChecks::default(),
+            vec![
+                finding("HASH_MISMATCH", Some("a.json")),
+                finding("MISSING_MEMBER", Some("b.json")),
+                finding("EXTRA_MEMBER", Some("notes.txt")),
+                finding("PACK_ID_MISMATCH", None),
+                finding("SCHEMA_VIOLATION", Some("c.json")),
+            ],
+        );
+        let plan = RepairPlan::from_report(Path::new("p"), &report);
+        let actions: Vec<_> = plan.actions.iter().map(|a| a.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            ["re-source", "restore", "remove", "reseal", "investigate"]
+        );
+        assert_eq!(plan.actions[0].expected_hash.as_deref(), Some("sha256:aa"));
+        assert_eq!(plan.actions[0].actual_hash.as_deref(), Some("sha256:bb"));
+        assert_eq!(plan.actions[2].path.as_deref(), Some("notes.txt"));
+    }
+}
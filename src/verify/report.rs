@@ -1,5 +1,11 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
 
+/// Verify report schema version.
+pub const VERIFY_REPORT_VERSION: &str = "pack.verify.v0";
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VerifyOutcome {
     OK,
@@ -23,9 +29,136 @@ pub struct VerifyChecks {
     pub member_count: bool,
     pub member_paths: bool,
     pub extra_members: bool,
-    pub member_hashes: bool,
+    pub member_hashes: HashCheck,
+    /// Recorded `size_bytes` match the member files; absent when no member
+    /// records a size or sizes were not checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_sizes: Option<bool>,
     pub pack_id: bool,
     pub schema_validation: String,
+    /// Per-member schema results behind `schema_validation`, in manifest
+    /// order; members without a local schema are not listed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema_members: Vec<SchemaMemberResult>,
+    /// Recorded empty directories (`.packkeep` members or
+    /// `provenance.empty_dirs`) are still empty; absent when there are none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empty_dirs: Option<bool>,
+    /// `--deep` cross-artifact reference check; absent without `--deep`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cross_references: Option<bool>,
+    /// `--validate-registry` check of CSV/TSV registry tables; absent without
+    /// `--validate-registry`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<bool>,
+    /// `--resolve-nested` check of embedded packs; absent without
+    /// `--resolve-nested`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nested_packs: Option<bool>,
+    /// Signature check; absent when the pack is unsigned and none was required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<bool>,
+    /// `--trust-policy` rules all hold; absent without `--trust-policy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust_policy: Option<bool>,
+    /// `--policy` requirements all hold; absent without `--policy`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<bool>,
+    /// `--check-timestamp`: `manifest.tst.json` stamps this manifest, or
+    /// `"unverified"` when it does but no `--tsa-cert` was given to check the
+    /// TSA's signature; absent without `--check-timestamp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<TimestampCheck>,
+    /// `--decrypt-key`: every encrypted member decrypts to its recorded
+    /// `bytes_hash` and `size_bytes`; absent without `--decrypt-key` or when
+    /// no member is encrypted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decryption: Option<bool>,
+}
+
+/// The member hash check: `true` or `false`, or `"skipped"` under
+/// `verify --quick`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashCheck {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl From<bool> for HashCheck {
+    fn from(passed: bool) -> Self {
+        if passed {
+            Self::Passed
+        } else {
+            Self::Failed
+        }
+    }
+}
+
+impl Serialize for HashCheck {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Passed => serializer.serialize_bool(true),
+            Self::Failed => serializer.serialize_bool(false),
+            Self::Skipped => serializer.serialize_str("skipped"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HashCheck {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Bool(bool),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Bool(passed) => Ok(passed.into()),
+            Raw::Text(text) if text == "skipped" => Ok(Self::Skipped),
+            Raw::Text(text) => Err(serde::de::Error::custom(format!(
+                "member_hashes must be true, false, or \"skipped\", not {text:?}"
+            ))),
+        }
+    }
+}
+
+/// The timestamp check: `true` or `false`, or `"unverified"` when the token
+/// stamps the manifest but its signature was not checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampCheck {
+    Passed,
+    Failed,
+    Unverified,
+}
+
+impl Serialize for TimestampCheck {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Passed => serializer.serialize_bool(true),
+            Self::Failed => serializer.serialize_bool(false),
+            Self::Unverified => serializer.serialize_str("unverified"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimestampCheck {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Bool(bool),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Bool(true) => Ok(Self::Passed),
+            Raw::Bool(false) => Ok(Self::Failed),
+            Raw::Text(text) if text == "unverified" => Ok(Self::Unverified),
+            Raw::Text(text) => Err(serde::de::Error::custom(format!(
+                "timestamp must be true, false, or \"unverified\", not {text:?}"
+            ))),
+        }
+    }
 }
 
 impl Default for VerifyChecks {
@@ -35,16 +168,58 @@ impl Default for VerifyChecks {
             member_count: false,
             member_paths: false,
             extra_members: false,
-            member_hashes: false,
+            member_hashes: HashCheck::Failed,
+            member_sizes: None,
             pack_id: false,
             schema_validation: "skipped".to_string(),
+            schema_members: vec![],
+            empty_dirs: None,
+            cross_references: None,
+            registry: None,
+            nested_packs: None,
+            signature: None,
+            trust_policy: None,
+            policy: None,
+            timestamp: None,
+            decryption: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Schema validation result for one member with a known `artifact_version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaMemberResult {
+    pub path: String,
+    pub artifact_version: String,
+    /// `pass` or `fail`.
+    pub status: String,
+    /// The rule the member broke, when `status` is `fail`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Whether a finding fails verification (`error`) or is only reported
+/// (`warning`). `verify --strict` promotes warnings to errors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn is_error(&self) -> bool {
+        *self == Self::Error
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InvalidFinding {
     pub code: String,
+    /// Omitted for errors, so only warning-class findings carry it.
+    #[serde(default, skip_serializing_if = "Severity::is_error")]
+    pub severity: Severity,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -53,6 +228,14 @@ pub struct InvalidFinding {
     pub actual: Option<String>,
 }
 
+/// A finding that did not fail verification, with the reason it was downgraded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyWarning {
+    #[serde(flatten)]
+    pub finding: InvalidFinding,
+    pub reason: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerifyReport {
     pub version: String,
@@ -61,18 +244,61 @@ pub struct VerifyReport {
     pub pack_id: Option<String>,
     pub checks: VerifyChecks,
     pub invalid: Vec<InvalidFinding>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<VerifyWarning>,
+    /// `genTime` of a `--check-timestamp` token whose TSA signature verified
+    /// under `--tsa-cert`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamped_at: Option<String>,
+    /// `--timings` only; absent on refusal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<VerifyTimings>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refusal: Option<serde_json::Value>,
 }
 
+/// Wall-clock time spent verifying a pack, for tracking verify SLOs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyTimings {
+    /// Milliseconds per check, keyed like `checks`; checks that did not run
+    /// are absent.
+    pub checks_ms: BTreeMap<String, f64>,
+    /// Milliseconds for the whole verify, including unpacking an archive and
+    /// loading suppressions and keys.
+    pub total_ms: f64,
+}
+
+impl VerifyTimings {
+    /// Record the time since `lap` under `check` and restart `lap`.
+    pub(crate) fn lap(&mut self, check: &str, lap: &mut Instant) {
+        self.checks_ms
+            .insert(check.to_string(), millis(lap.elapsed()));
+        *lap = Instant::now();
+    }
+
+    /// Record the total time since `started`.
+    pub(crate) fn finish(mut self, started: Instant) -> Self {
+        self.total_ms = millis(started.elapsed());
+        self
+    }
+}
+
+/// Milliseconds with microsecond precision.
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1_000.0
+}
+
 impl VerifyReport {
     pub fn ok(pack_id: String, checks: VerifyChecks) -> Self {
         Self {
-            version: "pack.verify.v0".to_string(),
+            version: VERIFY_REPORT_VERSION.to_string(),
             outcome: VerifyOutcome::OK,
             pack_id: Some(pack_id),
             checks,
             invalid: vec![],
+            warnings: vec![],
+            timestamped_at: None,
+            timings: None,
             refusal: None,
         }
     }
@@ -83,26 +309,50 @@ impl VerifyReport {
         findings: Vec<InvalidFinding>,
     ) -> Self {
         Self {
-            version: "pack.verify.v0".to_string(),
+            version: VERIFY_REPORT_VERSION.to_string(),
             outcome: VerifyOutcome::INVALID,
             pack_id,
             checks,
             invalid: findings,
+            warnings: vec![],
+            timestamped_at: None,
+            timings: None,
             refusal: None,
         }
     }
 
     pub fn refusal(reason: serde_json::Value) -> Self {
         Self {
-            version: "pack.verify.v0".to_string(),
+            version: VERIFY_REPORT_VERSION.to_string(),
             outcome: VerifyOutcome::REFUSAL,
             pack_id: None,
             checks: VerifyChecks::default(),
             invalid: vec![],
+            warnings: vec![],
+            timestamped_at: None,
+            timings: None,
             refusal: Some(reason),
         }
     }
 
+    /// Attach `--timings` output.
+    pub fn with_timings(mut self, timings: VerifyTimings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
+    /// Attach the `genTime` of a verified `--check-timestamp` token.
+    pub fn with_timestamp(mut self, gen_time: Option<String>) -> Self {
+        self.timestamped_at = gen_time;
+        self
+    }
+
+    /// Attach downgraded findings; they are reported but do not affect the outcome.
+    pub fn with_warnings(mut self, warnings: Vec<VerifyWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     pub fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).expect("verify report serialization cannot fail")
     }
@@ -113,6 +363,12 @@ impl VerifyReport {
         if let Some(id) = &self.pack_id {
             lines.push(format!("  pack_id: {id}"));
         }
+        if let Some(gen_time) = &self.timestamped_at {
+            lines.push(format!("  timestamped_at: {gen_time}"));
+        }
+        if self.checks.member_hashes == HashCheck::Skipped {
+            lines.push("  member_hashes: skipped (--quick)".to_string());
+        }
         if !self.invalid.is_empty() {
             lines.push("  findings:".to_string());
             for f in &self.invalid {
@@ -123,6 +379,23 @@ impl VerifyReport {
                 lines.push(entry);
             }
         }
+        if !self.warnings.is_empty() {
+            lines.push("  warnings:".to_string());
+            for w in &self.warnings {
+                let mut entry = format!("    - {}", w.finding.code);
+                if let Some(p) = &w.finding.path {
+                    entry.push_str(&format!(" ({p})"));
+                }
+                entry.push_str(&format!(": {}", w.reason));
+                lines.push(entry);
+            }
+        }
+        if let Some(timings) = &self.timings {
+            lines.push(format!("  timings: {} ms total", timings.total_ms));
+            for (check, ms) in &timings.checks_ms {
+                lines.push(format!("    - {check}: {ms} ms"));
+            }
+        }
         if let Some(r) = &self.refusal {
             lines.push(format!("  refusal: {r}"));
         }
@@ -1,7 +1,6 @@
-use std::fs;
-use std::path::Path;
-
-use super::report::InvalidFinding;
+use super::catalog::SchemaCatalog;
+use super::report::{InvalidFinding, SchemaMemberResult, Severity};
+use super::source::PackSource;
 use crate::seal::manifest::Member;
 
 /// Result of schema validation across all members.
@@ -27,58 +26,81 @@ impl SchemaOutcome {
 
 /// Run schema validation on all members that have a known artifact_version.
 ///
-/// Reads each member file from `pack_dir`, parses it, and checks required
-/// fields for the declared artifact version. Returns (outcome, findings).
+/// Reads each member file from `source`, parses it, and checks it against
+/// the schema for the declared artifact version: the one in `catalog`
+/// (`--schema-dir`) if there is one, otherwise the compiled-in field checks.
+/// Returns (outcome, per-member results, findings).
 pub fn validate_schemas(
     members: &[Member],
-    pack_dir: &Path,
-) -> (SchemaOutcome, Vec<InvalidFinding>) {
+    source: &dyn PackSource,
+    catalog: &SchemaCatalog,
+) -> (SchemaOutcome, Vec<SchemaMemberResult>, Vec<InvalidFinding>) {
     let mut findings = Vec::new();
+    let mut results = Vec::new();
     let mut checked = 0u32;
 
     for member in members {
+        // Ciphertext cannot be parsed; --decrypt-key checks its plaintext hash.
+        if member.ciphertext_hash.is_some() {
+            continue;
+        }
         let version = match &member.artifact_version {
             Some(v) => v.as_str(),
             None => continue, // No artifact_version → skip
         };
 
-        // Only validate types that have a local schema definition.
-        let validator = match schema_for_version(version) {
-            Some(v) => v,
-            None => continue, // Known type but no schema yet → skip
-        };
+        // Only validate types that have a catalog or local schema definition.
+        if !catalog.contains(version) && schema_for_version(version).is_none() {
+            continue; // Known type but no schema yet → skip
+        }
 
         checked += 1;
 
-        let member_path = pack_dir.join(&member.path);
-        let content = match fs::read(&member_path) {
+        let content = match source.read(&member.path) {
             Ok(c) => c,
             Err(_) => continue, // Missing file is caught by hash checks, not schema
         };
 
-        if let Err(reason) = validator(&content) {
+        let reason = catalog
+            .validate(version, &content)
+            .or_else(|| validate_builtin(version, &content))
+            .and_then(Result::err);
+        if let Some(reason) = &reason {
             findings.push(InvalidFinding {
                 code: "SCHEMA_VIOLATION".to_string(),
+                severity: Severity::Error,
                 path: Some(member.path.clone()),
                 expected: Some(format!("valid {version} schema")),
-                actual: Some(reason),
+                actual: Some(reason.clone()),
             });
         }
+        results.push(SchemaMemberResult {
+            path: member.path.clone(),
+            artifact_version: version.to_string(),
+            status: if reason.is_some() { "fail" } else { "pass" }.to_string(),
+            reason,
+        });
     }
 
     if checked == 0 {
-        return (SchemaOutcome::Skipped, findings);
+        return (SchemaOutcome::Skipped, results, findings);
     }
 
     if findings.is_empty() {
-        (SchemaOutcome::Pass, findings)
+        (SchemaOutcome::Pass, results, findings)
     } else {
-        (SchemaOutcome::Fail, findings)
+        (SchemaOutcome::Fail, results, findings)
     }
 }
 
 type Validator = fn(&[u8]) -> Result<(), String>;
 
+/// Check `content` against the compiled-in schema for `version`; `None` when
+/// pack has no schema for it.
+pub(super) fn validate_builtin(version: &str, content: &[u8]) -> Option<Result<(), String>> {
+    schema_for_version(version).map(|validator| validator(content))
+}
+
 /// Return a compiled-in schema validator for a known artifact version, or None.
 fn schema_for_version(version: &str) -> Option<Validator> {
     match version {
@@ -147,7 +169,7 @@ fn validate_pack_v0(content: &[u8]) -> Result<(), String> {
     Ok(())
 }
 
-fn parse_json(content: &[u8]) -> Result<serde_json::Value, String> {
+pub(super) fn parse_json(content: &[u8]) -> Result<serde_json::Value, String> {
     let text =
         std::str::from_utf8(content).map_err(|_| "content is not valid UTF-8".to_string())?;
     serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}"))
@@ -169,13 +191,21 @@ fn check_version_field(value: &serde_json::Value, expected: &str) -> Result<(),
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::verify::source::DirSource;
 
     fn member(path: &str, version: Option<&str>) -> Member {
         Member {
             path: path.to_string(),
             bytes_hash: "sha256:placeholder".to_string(),
+            size_bytes: None,
             member_type: "test".to_string(),
             artifact_version: version.map(|v| v.to_string()),
+            visibility: None,
+            origin: None,
+            executable: None,
+            ciphertext_hash: None,
+            content_type: None,
+            charset: None,
         }
     }
 
@@ -186,7 +216,11 @@ mod tests {
         std::fs::write(tmp.path().join("data.csv"), "a,b\n1,2").unwrap();
         std::fs::write(tmp.path().join("readme.txt"), "hello").unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Skipped);
         assert!(findings.is_empty());
     }
@@ -201,7 +235,11 @@ mod tests {
         )
         .unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Pass);
         assert!(findings.is_empty());
     }
@@ -216,7 +254,11 @@ mod tests {
         )
         .unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Pass);
         assert!(findings.is_empty());
     }
@@ -231,7 +273,11 @@ mod tests {
         )
         .unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Pass);
         assert!(findings.is_empty());
     }
@@ -246,7 +292,11 @@ mod tests {
         )
         .unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Fail);
         assert_eq!(findings.len(), 1);
         assert_eq!(findings[0].code, "SCHEMA_VIOLATION");
@@ -263,7 +313,11 @@ mod tests {
         )
         .unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Fail);
         assert_eq!(findings.len(), 1);
         assert!(findings[0].actual.as_ref().unwrap().contains("non-array"));
@@ -275,7 +329,11 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         std::fs::write(tmp.path().join("data.lock.json"), "NOT JSON AT ALL").unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Fail);
         assert_eq!(findings.len(), 1);
         assert!(findings[0]
@@ -299,7 +357,11 @@ mod tests {
         .unwrap();
         std::fs::write(tmp.path().join("unknown.txt"), "text").unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, _, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Pass);
         assert!(findings.is_empty());
     }
@@ -322,8 +384,70 @@ mod tests {
         )
         .unwrap();
 
-        let (outcome, findings) = validate_schemas(&members, tmp.path());
+        let (outcome, results, findings) = validate_schemas(
+            &members,
+            &DirSource::new(tmp.path()),
+            &SchemaCatalog::default(),
+        );
         assert_eq!(outcome, SchemaOutcome::Fail);
         assert_eq!(findings.len(), 1);
+        let statuses: Vec<(&str, &str)> = results
+            .iter()
+            .map(|r| (r.path.as_str(), r.status.as_str()))
+            .collect();
+        assert_eq!(
+            statuses,
+            [("good.lock.json", "pass"), ("bad.lock.json", "fail")]
+        );
+        assert_eq!(
+            results[1].reason.as_deref(),
+            Some("expected version \"lock.v0\", got \"lock.v99\"")
+        );
+        assert_eq!(results[0].reason, None);
+    }
+
+    #[test]
+    fn catalog_schemas_take_precedence_and_add_versions() {
+        let members = vec![
+            member("nov.lock.json", Some("lock.v0")),
+            member("loans.json", Some("loans.v1")),
+            member("notes.json", Some("notes.v1")),
+        ];
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("nov.lock.json"), r#"{"version":"lock.v0"}"#).unwrap();
+        std::fs::write(tmp.path().join("loans.json"), r#"{"rows":"none"}"#).unwrap();
+        std::fs::write(tmp.path().join("notes.json"), "not json").unwrap();
+        let schemas = tmp.path().join("schemas");
+        std::fs::create_dir(&schemas).unwrap();
+        std::fs::write(
+            schemas.join("lock.v0.schema.json"),
+            r#"{"type": "object", "required": ["rows"]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            schemas.join("loans.v1.schema.json"),
+            r#"{"properties": {"rows": {"type": "array"}}}"#,
+        )
+        .unwrap();
+        let catalog = SchemaCatalog::load(&schemas).unwrap();
+
+        let (outcome, results, findings) =
+            validate_schemas(&members, &DirSource::new(tmp.path()), &catalog);
+        assert_eq!(outcome, SchemaOutcome::Fail);
+        assert_eq!(findings.len(), 2);
+        let reasons: Vec<_> = results
+            .iter()
+            .map(|r| (r.path.as_str(), r.reason.as_deref()))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                ("nov.lock.json", Some("/: \"rows\" is a required property")),
+                (
+                    "loans.json",
+                    Some("/rows: \"none\" is not of type \"array\"")
+                ),
+            ]
+        );
     }
 }
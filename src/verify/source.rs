@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use crate::sign::SIGNATURE_FILE;
+use crate::timestamp::TIMESTAMP_FILE;
+
+/// What a path in a [`PackSource`] names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// A regular file; the only kind a member may be.
+    File,
+    /// A directory holding other entries.
+    Dir,
+    /// Anything else: a symlink, device, or socket.
+    Other,
+}
+
+/// Kind and length of one entry in a [`PackSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryMeta {
+    pub kind: EntryKind,
+    /// Byte length of a file; for other kinds, whatever the source reports.
+    pub len: u64,
+}
+
+/// Read-only view of a pack's files, so verification does not assume they
+/// are on disk.
+///
+/// Paths are relative and `/`-separated, exactly as the manifest records
+/// them; `manifest.json` and its sidecars sit at the top level. A path that
+/// does not exist is an [`io::ErrorKind::NotFound`] error.
+pub trait PackSource {
+    /// Kind and length of the entry at `path`.
+    fn metadata(&self, path: &str) -> io::Result<EntryMeta>;
+
+    /// Open the file at `path` for streaming.
+    fn open(&self, path: &str) -> io::Result<Box<dyn Read + '_>>;
+
+    /// Every non-directory entry, at any depth. Used to find files the
+    /// manifest does not declare; a source that cannot be listed returns
+    /// what it can.
+    fn files(&self) -> Vec<String>;
+
+    /// The directory on disk holding this source, if there is one.
+    ///
+    /// Store lookups (deprecation notes) and nested packs use it; a source
+    /// without one is verified entirely through this trait.
+    fn local_dir(&self) -> Option<&Path> {
+        None
+    }
+
+    /// The whole file at `path`.
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.open(path)?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// The whole file at `path` as UTF-8.
+    fn read_to_string(&self, path: &str) -> io::Result<String> {
+        String::from_utf8(self.read(path)?).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )
+        })
+    }
+}
+
+/// A pack directory (or member root) on disk.
+#[derive(Debug, Clone)]
+pub struct DirSource {
+    root: PathBuf,
+}
+
+impl DirSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        DirSource { root: root.into() }
+    }
+}
+
+impl PackSource for DirSource {
+    /// The length follows symlinks, so a dangling link is missing; the kind
+    /// does not, so a link to a regular file is still [`EntryKind::Other`].
+    fn metadata(&self, path: &str) -> io::Result<EntryMeta> {
+        let full = self.root.join(path);
+        let len = fs::metadata(&full)?.len();
+        let meta = fs::symlink_metadata(&full)?;
+        let kind = if meta.is_symlink() {
+            EntryKind::Other
+        } else if meta.is_file() {
+            EntryKind::File
+        } else if meta.is_dir() {
+            EntryKind::Dir
+        } else {
+            EntryKind::Other
+        };
+        Ok(EntryMeta { kind, len })
+    }
+
+    fn open(&self, path: &str) -> io::Result<Box<dyn Read + '_>> {
+        Ok(Box::new(fs::File::open(self.root.join(path))?))
+    }
+
+    fn files(&self) -> Vec<String> {
+        let mut files = Vec::new();
+        collect_files(&self.root, None, &mut files);
+        files
+    }
+
+    fn local_dir(&self) -> Option<&Path> {
+        Some(&self.root)
+    }
+}
+
+fn collect_files(dir: &Path, prefix: Option<&str>, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative = match prefix {
+            Some(prefix) => format!("{prefix}/{name}"),
+            None => name,
+        };
+        if entry.path().is_dir() {
+            collect_files(&entry.path(), Some(&relative), files);
+        } else {
+            files.push(relative);
+        }
+    }
+}
+
+/// A pack held in memory: file paths mapped to their bytes.
+///
+/// Directories are implied by the paths under them.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) the file at `path`.
+    pub fn insert(&mut self, path: impl Into<String>, bytes: impl Into<Vec<u8>>) {
+        self.files.insert(path.into(), bytes.into());
+    }
+
+    /// Remove the file at `path`, returning its bytes.
+    pub fn remove(&mut self, path: &str) -> Option<Vec<u8>> {
+        self.files.remove(path)
+    }
+
+    fn file(&self, path: &str) -> io::Result<&[u8]> {
+        self.files
+            .get(path)
+            .map(Vec::as_slice)
+            .ok_or_else(|| not_found(path))
+    }
+}
+
+impl<P: Into<String>, B: Into<Vec<u8>>> FromIterator<(P, B)> for MemorySource {
+    fn from_iter<I: IntoIterator<Item = (P, B)>>(iter: I) -> Self {
+        let mut source = MemorySource::new();
+        for (path, bytes) in iter {
+            source.insert(path, bytes);
+        }
+        source
+    }
+}
+
+impl PackSource for MemorySource {
+    fn metadata(&self, path: &str) -> io::Result<EntryMeta> {
+        if let Some(bytes) = self.files.get(path) {
+            return Ok(EntryMeta {
+                kind: EntryKind::File,
+                len: bytes.len() as u64,
+            });
+        }
+        let prefix = format!("{path}/");
+        if self.files.keys().any(|file| file.starts_with(&prefix)) {
+            return Ok(EntryMeta {
+                kind: EntryKind::Dir,
+                len: 0,
+            });
+        }
+        Err(not_found(path))
+    }
+
+    fn open(&self, path: &str) -> io::Result<Box<dyn Read + '_>> {
+        Ok(Box::new(Cursor::new(self.file(path)?)))
+    }
+
+    fn files(&self) -> Vec<String> {
+        self.files.keys().cloned().collect()
+    }
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        self.file(path).map(<[u8]>::to_vec)
+    }
+}
+
+/// The part of another source under `prefix`: a pack nested in a pack.
+pub(crate) struct SubSource<'a> {
+    inner: &'a dyn PackSource,
+    prefix: String,
+}
+
+impl<'a> SubSource<'a> {
+    pub(crate) fn new(inner: &'a dyn PackSource, prefix: &str) -> Self {
+        SubSource {
+            inner,
+            prefix: format!("{prefix}/"),
+        }
+    }
+}
+
+impl PackSource for SubSource<'_> {
+    fn metadata(&self, path: &str) -> io::Result<EntryMeta> {
+        self.inner.metadata(&format!("{}{path}", self.prefix))
+    }
+
+    fn open(&self, path: &str) -> io::Result<Box<dyn Read + '_>> {
+        self.inner.open(&format!("{}{path}", self.prefix))
+    }
+
+    fn files(&self) -> Vec<String> {
+        self.inner
+            .files()
+            .into_iter()
+            .filter_map(|file| file.strip_prefix(&self.prefix).map(str::to_string))
+            .collect()
+    }
+}
+
+/// A manifest file kept apart from its members (`verify --against`).
+///
+/// `manifest.json` is read from the given file, whatever it is named, and
+/// the signature and timestamp sidecars from the directory holding it.
+/// Nothing else is in this source.
+pub(crate) struct ManifestFileSource {
+    manifest: PathBuf,
+    sidecars: DirSource,
+}
+
+impl ManifestFileSource {
+    pub(crate) fn new(manifest: &Path) -> Self {
+        let dir = manifest
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        ManifestFileSource {
+            manifest: manifest.to_path_buf(),
+            sidecars: DirSource::new(dir),
+        }
+    }
+
+    fn manifest_error(&self, e: io::Error) -> io::Error {
+        io::Error::new(e.kind(), format!("{}: {e}", self.manifest.display()))
+    }
+}
+
+impl PackSource for ManifestFileSource {
+    fn metadata(&self, path: &str) -> io::Result<EntryMeta> {
+        match path {
+            "manifest.json" => {
+                let meta = fs::metadata(&self.manifest).map_err(|e| self.manifest_error(e))?;
+                let kind = if meta.is_file() {
+                    EntryKind::File
+                } else {
+                    EntryKind::Other
+                };
+                Ok(EntryMeta {
+                    kind,
+                    len: meta.len(),
+                })
+            }
+            SIGNATURE_FILE | TIMESTAMP_FILE => self.sidecars.metadata(path),
+            _ => Err(not_found(path)),
+        }
+    }
+
+    fn open(&self, path: &str) -> io::Result<Box<dyn Read + '_>> {
+        match path {
+            "manifest.json" => Ok(Box::new(
+                fs::File::open(&self.manifest).map_err(|e| self.manifest_error(e))?,
+            )),
+            SIGNATURE_FILE | TIMESTAMP_FILE => self.sidecars.open(path),
+            _ => Err(not_found(path)),
+        }
+    }
+
+    fn files(&self) -> Vec<String> {
+        ["manifest.json", SIGNATURE_FILE, TIMESTAMP_FILE]
+            .into_iter()
+            .filter(|path| self.metadata(path).is_ok())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+fn not_found(path: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("{path}: not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn memory_source_implies_directories() {
+        let source: MemorySource = [("a.json", "{}"), ("dir/b.txt", "hi")]
+            .into_iter()
+            .collect();
+        assert_eq!(source.metadata("a.json").unwrap().kind, EntryKind::File);
+        assert_eq!(source.metadata("dir/b.txt").unwrap().len, 2);
+        assert_eq!(source.metadata("dir").unwrap().kind, EntryKind::Dir);
+        let missing = source.metadata("di").unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+        assert_eq!(source.read_to_string("dir/b.txt").unwrap(), "hi");
+
+        let sub = SubSource::new(&source, "dir");
+        assert_eq!(sub.files(), vec!["b.txt".to_string()]);
+        assert_eq!(sub.read("b.txt").unwrap(), b"hi");
+    }
+
+    #[test]
+    fn dir_source_lists_files_at_every_depth() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("x/y")).unwrap();
+        fs::write(tmp.path().join("top.txt"), "1").unwrap();
+        fs::write(tmp.path().join("x/y/deep.txt"), "22").unwrap();
+
+        let source = DirSource::new(tmp.path());
+        let mut files = source.files();
+        files.sort();
+        assert_eq!(files, vec!["top.txt", "x/y/deep.txt"]);
+        assert_eq!(source.metadata("x").unwrap().kind, EntryKind::Dir);
+        assert_eq!(source.metadata("x/y/deep.txt").unwrap().len, 2);
+        assert_eq!(source.local_dir(), Some(tmp.path()));
+    }
+}
@@ -0,0 +1,100 @@
+use serde::Serialize;
+
+use super::report::{InvalidFinding, VerifyChecks, VerifyOutcome, VerifyTimings, VerifyWarning};
+use super::suppress::Suppression;
+use super::VerifyReport;
+use crate::events::{EventBus, PackEvent};
+
+/// Version tag on every `pack verify --json-lines` record.
+pub const VERIFY_STREAM_VERSION: &str = "pack.verify_stream.v0";
+
+/// Announces findings that fail the pack on an [`EventBus`] as the checks
+/// producing them report them, rather than once verify is done.
+pub(super) struct FindingStream<'a> {
+    events: &'a EventBus,
+    suppressions: &'a [Suppression],
+    reported: usize,
+}
+
+impl<'a> FindingStream<'a> {
+    pub(super) fn new(events: &'a EventBus, suppressions: &'a [Suppression]) -> Self {
+        Self {
+            events,
+            suppressions,
+            reported: 0,
+        }
+    }
+
+    pub(super) fn events(&self) -> &EventBus {
+        self.events
+    }
+
+    /// Send a `VerificationFinding` for each unsuppressed finding added to
+    /// `findings` since the last flush.
+    pub(super) fn flush(&mut self, findings: &[InvalidFinding]) {
+        for finding in &findings[self.reported.min(findings.len())..] {
+            if !self.suppressions.iter().any(|s| s.matches(finding)) {
+                self.events.notify(&PackEvent::VerificationFinding {
+                    finding: finding.clone(),
+                });
+            }
+        }
+        self.reported = findings.len();
+    }
+}
+
+#[derive(Serialize)]
+struct FindingRecord<'a> {
+    version: &'static str,
+    record: &'static str,
+    #[serde(flatten)]
+    finding: &'a InvalidFinding,
+}
+
+/// The last `--json-lines` record: the report without its `invalid` list,
+/// whose findings were streamed before it.
+#[derive(Serialize)]
+struct SummaryRecord<'a> {
+    version: &'static str,
+    record: &'static str,
+    outcome: &'a VerifyOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pack_id: Option<&'a str>,
+    checks: &'a VerifyChecks,
+    finding_count: usize,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    warnings: &'a [VerifyWarning],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamped_at: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timings: Option<&'a VerifyTimings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refusal: Option<&'a serde_json::Value>,
+}
+
+/// One `{"record": "finding", ...}` line.
+pub(super) fn finding_line(finding: &InvalidFinding) -> String {
+    serde_json::to_string(&FindingRecord {
+        version: VERIFY_STREAM_VERSION,
+        record: "finding",
+        finding,
+    })
+    .expect("finding serializes")
+}
+
+/// The closing `{"record": "summary", ...}` line for `report`.
+pub(super) fn summary_line(report: &VerifyReport) -> String {
+    serde_json::to_string(&SummaryRecord {
+        version: VERIFY_STREAM_VERSION,
+        record: "summary",
+        outcome: &report.outcome,
+        pack_id: report.pack_id.as_deref(),
+        checks: &report.checks,
+        finding_count: report.invalid.len(),
+        warnings: &report.warnings,
+        timestamped_at: report.timestamped_at.as_deref(),
+        timings: report.timings.as_ref(),
+        refusal: report.refusal.as_ref(),
+    })
+    .expect("summary serializes")
+}
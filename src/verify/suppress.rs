@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::report::{InvalidFinding, VerifyWarning};
+
+/// A finding code (optionally scoped to one member path) that verify downgrades
+/// from INVALID to a recorded warning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub justification: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SuppressionsFile {
+    suppressions: Vec<Suppression>,
+}
+
+impl Suppression {
+    /// Parse an `--ignore-finding CODE[:PATH]` argument.
+    pub fn from_flag(raw: &str) -> Self {
+        let (code, path) = match raw.split_once(':') {
+            Some((code, path)) => (code, Some(path.to_string())),
+            None => (raw, None),
+        };
+        Self {
+            code: code.to_string(),
+            path,
+            justification: format!("--ignore-finding {raw}"),
+        }
+    }
+
+    pub(super) fn matches(&self, finding: &InvalidFinding) -> bool {
+        self.code == finding.code
+            && self
+                .path
+                .as_ref()
+                .is_none_or(|path| finding.path.as_ref() == Some(path))
+    }
+}
+
+/// Load a suppressions file:
+/// `{"suppressions": [{"code": "...", "path": "...", "justification": "..."}]}`.
+///
+/// Every entry must carry a non-empty justification.
+pub fn load_suppressions(path: &Path) -> Result<Vec<Suppression>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read suppressions file: {}: {e}", path.display()))?;
+    let file: SuppressionsFile = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid suppressions file: {}: {e}", path.display()))?;
+    if let Some(entry) = file
+        .suppressions
+        .iter()
+        .find(|s| s.justification.trim().is_empty())
+    {
+        return Err(format!(
+            "Suppression for {} in {} has an empty justification",
+            entry.code,
+            path.display()
+        ));
+    }
+    Ok(file.suppressions)
+}
+
+/// Split findings into those that still fail verify and suppressed warnings.
+pub fn apply_suppressions(
+    findings: Vec<InvalidFinding>,
+    suppressions: &[Suppression],
+) -> (Vec<InvalidFinding>, Vec<VerifyWarning>) {
+    let mut remaining = Vec::new();
+    let mut warnings = Vec::new();
+    for finding in findings {
+        match suppressions.iter().find(|s| s.matches(&finding)) {
+            Some(suppression) => warnings.push(VerifyWarning {
+                finding,
+                reason: suppression.justification.clone(),
+            }),
+            None => remaining.push(finding),
+        }
+    }
+    (remaining, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::report::Severity;
+    use tempfile::TempDir;
+
+    fn finding(code: &str, path: &str) -> InvalidFinding {
+        InvalidFinding {
+            code: code.to_string(),
+            severity: Severity::Error,
+            path: Some(path.to_string()),
+            expected: None,
+            actual: None,
+        }
+    }
+
+    #[test]
+    fn flag_suppresses_by_code_or_code_and_path() {
+        let findings = vec![
+            finding("EXTRA_MEMBER", "README.md"),
+            finding("EXTRA_MEMBER", "payload.bin"),
+            finding("HASH_MISMATCH", "a.json"),
+        ];
+
+        let (remaining, warnings) = apply_suppressions(
+            findings.clone(),
+            &[Suppression::from_flag("EXTRA_MEMBER:README.md")],
+        );
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].reason,
+            "--ignore-finding EXTRA_MEMBER:README.md"
+        );
+
+        let (remaining, warnings) =
+            apply_suppressions(findings, &[Suppression::from_flag("EXTRA_MEMBER")]);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].code, "HASH_MISMATCH");
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn file_requires_justification() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("suppressions.json");
+        fs::write(
+            &path,
+            r#"{"suppressions":[{"code":"EXTRA_MEMBER","path":"README.md","justification":"vendor README"}]}"#,
+        )
+        .unwrap();
+        let loaded = load_suppressions(&path).unwrap();
+        assert_eq!(loaded[0].justification, "vendor README");
+
+        fs::write(
+            &path,
+            r#"{"suppressions":[{"code":"EXTRA_MEMBER","justification":"  "}]}"#,
+        )
+        .unwrap();
+        let err = load_suppressions(&path).unwrap_err();
+        assert!(err.contains("empty justification"));
+    }
+}
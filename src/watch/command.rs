@@ -0,0 +1,217 @@
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+use serde_json::json;
+
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+use crate::verify::{execute_verify_with_options, VerifyOptions, VerifyOutcome};
+
+use super::scan::StoreSnapshot;
+
+/// Seconds between full rescans when no file event arrives.
+pub const DEFAULT_WATCH_INTERVAL_SECS: u64 = 2;
+
+/// Quiet period after a file event before rescanning, so a burst of writes
+/// (one push) triggers one verification pass.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Options for `pack watch`.
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    /// `--on-invalid`: shell command run for every pack that does not verify OK.
+    pub on_invalid: Option<String>,
+    /// `--once`: verify every pack once and return instead of watching.
+    pub once: bool,
+    /// `--interval`: rescan period; `None` uses `DEFAULT_WATCH_INTERVAL_SECS`.
+    pub interval: Option<Duration>,
+}
+
+/// One verification performed by the watcher.
+#[derive(Debug)]
+pub struct WatchEvent<'a> {
+    pub pack_dir: &'a Path,
+    pub pack_id: Option<String>,
+    pub outcome: VerifyOutcome,
+    pub exit_code: u8,
+    /// The `pack.verify.v0` JSON report.
+    pub report: &'a str,
+}
+
+/// Totals for a `--once` sweep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WatchSummary {
+    pub verified: usize,
+    pub failed: usize,
+}
+
+/// Verify every pack in the filesystem store at `root`, then (unless `--once`)
+/// keep verifying packs that appear or change.
+///
+/// File events from the OS (inotify, FSEvents, ...) wake the watcher early; a
+/// full rescan every interval catches anything the events miss, such as
+/// network filesystems. Packs are re-verified only when their file listing
+/// (paths, sizes, mtimes) changes. `on_event` is called for every
+/// verification before the `--on-invalid` hook runs; returning
+/// `ControlFlow::Break` stops the watcher after that hook.
+///
+/// Returns only with `--once`, when `on_event` breaks, or with a refusal when
+/// the store cannot be read on the first scan.
+pub fn execute_watch(
+    root: &Path,
+    options: &WatchOptions,
+    mut on_event: impl FnMut(&WatchEvent) -> ControlFlow<()>,
+) -> Result<WatchSummary, Box<RefusalEnvelope>> {
+    if !root.is_dir() {
+        return Err(watch_refusal(
+            root,
+            format!("Store root is not a directory: {}", root.display()),
+        ));
+    }
+    let interval = options
+        .interval
+        .unwrap_or(Duration::from_secs(DEFAULT_WATCH_INTERVAL_SECS));
+
+    let mut previous = StoreSnapshot::default();
+    let mut first_scan = true;
+    let events = if options.once {
+        None
+    } else {
+        start_watcher(root, interval)
+    };
+    let mut summary = WatchSummary::default();
+
+    loop {
+        match StoreSnapshot::scan(root) {
+            Ok(current) => {
+                for pack_dir in current.changed_since(&previous) {
+                    let (report, exit_code) =
+                        execute_verify_with_options(&pack_dir, true, &VerifyOptions::default());
+                    let event = WatchEvent {
+                        pack_dir: &pack_dir,
+                        pack_id: serde_json::from_str::<serde_json::Value>(&report)
+                            .ok()
+                            .and_then(|v| v["pack_id"].as_str().map(str::to_string)),
+                        outcome: match exit_code {
+                            0 => VerifyOutcome::OK,
+                            1 => VerifyOutcome::INVALID,
+                            _ => VerifyOutcome::REFUSAL,
+                        },
+                        exit_code,
+                        report: &report,
+                    };
+                    let flow = on_event(&event);
+                    summary.verified += 1;
+                    if event.outcome != VerifyOutcome::OK {
+                        summary.failed += 1;
+                        if let Some(hook) = &options.on_invalid {
+                            run_hook(hook, &event);
+                        }
+                    }
+                    if flow.is_break() {
+                        return Ok(summary);
+                    }
+                }
+                previous = current;
+            }
+            Err(e) if first_scan => {
+                return Err(watch_refusal(root, format!("Cannot scan store: {e}")));
+            }
+            Err(e) => eprintln!("pack: watch: cannot scan store: {e}"),
+        }
+        first_scan = false;
+
+        if options.once {
+            return Ok(summary);
+        }
+        wait_for_change(events.as_ref(), interval);
+    }
+}
+
+type EventReceiver = (
+    notify::RecommendedWatcher,
+    Receiver<notify::Result<notify::Event>>,
+);
+
+fn start_watcher(root: &Path, interval: Duration) -> Option<EventReceiver> {
+    let (tx, rx) = mpsc::channel();
+    let watcher = notify::recommended_watcher(tx).and_then(|mut watcher| {
+        watcher.watch(root, RecursiveMode::Recursive)?;
+        Ok(watcher)
+    });
+    match watcher {
+        Ok(watcher) => Some((watcher, rx)),
+        Err(e) => {
+            eprintln!(
+                "pack: watch: file events unavailable ({e}); rescanning every {}s",
+                interval.as_secs_f64()
+            );
+            None
+        }
+    }
+}
+
+fn wait_for_change(events: Option<&EventReceiver>, interval: Duration) {
+    let Some((_, rx)) = events else {
+        std::thread::sleep(interval);
+        return;
+    };
+    match rx.recv_timeout(interval) {
+        Ok(_) => while rx.recv_timeout(DEBOUNCE).is_ok() {},
+        Err(RecvTimeoutError::Timeout) => {}
+        Err(RecvTimeoutError::Disconnected) => std::thread::sleep(interval),
+    }
+}
+
+/// Run `hook` through the platform shell with the pack in the environment and
+/// the verify report on stdin. Hook failures are reported, never fatal.
+fn run_hook(hook: &str, event: &WatchEvent) {
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(hook);
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(hook);
+        command
+    };
+    command
+        .env("PACK_DIR", event.pack_dir)
+        .env("PACK_ID", event.pack_id.as_deref().unwrap_or(""))
+        .env("PACK_VERIFY_OUTCOME", event.outcome.to_string())
+        .stdin(Stdio::piped());
+
+    let status = command.spawn().and_then(|mut child| {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that ignores stdin may close it early; that is not an error.
+            let _ = stdin.write_all(event.report.as_bytes());
+        }
+        child.wait()
+    });
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!(
+            "pack: watch: --on-invalid hook exited with {status} for {}",
+            event.pack_dir.display()
+        ),
+        Err(e) => eprintln!(
+            "pack: watch: cannot run --on-invalid hook for {}: {e}",
+            event.pack_dir.display()
+        ),
+    }
+}
+
+fn watch_refusal(root: &Path, message: String) -> Box<RefusalEnvelope> {
+    Box::new(RefusalEnvelope::new(
+        RefusalCode::Io,
+        Some(message),
+        Some(json!({ "store": root.display().to_string() })),
+    ))
+}
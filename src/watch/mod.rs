@@ -0,0 +1,7 @@
+mod command;
+mod scan;
+
+pub use command::{
+    execute_watch, WatchEvent, WatchOptions, WatchSummary, DEFAULT_WATCH_INTERVAL_SECS,
+};
+pub use scan::StoreSnapshot;
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Directory under a store root holding `sha256/<hex>/` pack directories.
+const PACKS_DIR: &str = "sha256";
+
+/// Snapshot of every pack directory in a store, keyed by path, with a
+/// fingerprint of its file listing (paths, sizes, mtimes).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StoreSnapshot {
+    packs: BTreeMap<PathBuf, blake3::Hash>,
+}
+
+impl StoreSnapshot {
+    /// Scan `root` for pack directories. Dot-prefixed entries (in-flight push
+    /// staging, index lock) are skipped; a missing `sha256/` is an empty store.
+    pub fn scan(root: &Path) -> io::Result<Self> {
+        let mut packs = BTreeMap::new();
+        let entries = match fs::read_dir(root.join(PACKS_DIR)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with('.') || !entry.file_type()?.is_dir()
+            {
+                continue;
+            }
+            let dir = entry.path();
+            let mut hasher = blake3::Hasher::new();
+            fingerprint_dir(&dir, &dir, &mut hasher)?;
+            packs.insert(dir, hasher.finalize());
+        }
+        Ok(Self { packs })
+    }
+
+    /// Pack directories that are new in `self` or whose fingerprint differs
+    /// from `previous`, in path order.
+    pub fn changed_since(&self, previous: &StoreSnapshot) -> Vec<PathBuf> {
+        self.packs
+            .iter()
+            .filter(|(dir, hash)| previous.packs.get(*dir) != Some(hash))
+            .map(|(dir, _)| dir.clone())
+            .collect()
+    }
+}
+
+fn fingerprint_dir(base: &Path, dir: &Path, hasher: &mut blake3::Hasher) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_dir() {
+            fingerprint_dir(base, &path, hasher)?;
+            continue;
+        }
+        let mtime = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+        let relative = path.strip_prefix(base).unwrap_or(&path);
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&metadata.len().to_le_bytes());
+        hasher.update(&mtime.to_le_bytes());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_new_and_modified_packs_only() {
+        let tmp = TempDir::new().unwrap();
+        let a = tmp.path().join("sha256").join("aaaa");
+        let b = tmp.path().join("sha256").join("bbbb");
+        fs::create_dir_all(&a).unwrap();
+        fs::write(a.join("manifest.json"), "{}").unwrap();
+        fs::create_dir_all(tmp.path().join("sha256").join(".pack-push-x")).unwrap();
+
+        let first = StoreSnapshot::scan(tmp.path()).unwrap();
+        assert_eq!(
+            first.changed_since(&StoreSnapshot::default()),
+            vec![a.clone()]
+        );
+        assert!(StoreSnapshot::scan(tmp.path())
+            .unwrap()
+            .changed_since(&first)
+            .is_empty());
+
+        fs::create_dir_all(&b).unwrap();
+        fs::write(b.join("manifest.json"), "{}").unwrap();
+        fs::write(a.join("member.txt"), "added").unwrap();
+        let second = StoreSnapshot::scan(tmp.path()).unwrap();
+        assert_eq!(second.changed_since(&first), vec![a, b]);
+    }
+
+    #[test]
+    fn missing_pack_directory_is_empty_store() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(
+            StoreSnapshot::scan(tmp.path()).unwrap(),
+            StoreSnapshot::default()
+        );
+    }
+}
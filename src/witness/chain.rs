@@ -0,0 +1,241 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ledger::{line_hash, witness_ledger_path};
+use super::validate::LedgerIssue;
+
+/// Version tag for `pack witness verify-chain --json` output.
+pub const CHAIN_REPORT_VERSION: &str = "pack.witness.chain.v0";
+
+/// Result of walking the `prev_hash` links of a chained ledger.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainVerification {
+    pub version: String,
+    pub outcome: String,
+    pub ledger: String,
+    pub records: usize,
+    /// Records from the first chained record onwards.
+    pub chained: usize,
+    /// Line of the first record carrying `prev_hash`; absent for an unchained ledger.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_start: Option<usize>,
+    /// `sha256:` of the last record. Keep it elsewhere and pass it back with
+    /// `--expect-head` to detect truncation later.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    pub invalid: Vec<LedgerIssue>,
+}
+
+impl ChainVerification {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("chain verification serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![
+            format!("pack witness verify-chain: {}", self.outcome),
+            format!("  ledger: {}", self.ledger),
+            format!("  records: {}", self.records),
+        ];
+        match self.chain_start {
+            Some(start) => lines.push(format!("  chained: {} (from line {start})", self.chained)),
+            None => lines.push("  chained: 0 (ledger is not chained)".to_string()),
+        }
+        if let Some(head) = &self.head {
+            lines.push(format!("  head: {head}"));
+        }
+        for issue in &self.invalid {
+            lines.push(format!(
+                "  line {}: {} ({})",
+                issue.line, issue.code, issue.message
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack witness verify-chain` against the configured ledger.
+///
+/// Returns (output, exit_code): 0 when every link holds, 1 otherwise.
+pub fn execute_verify_chain(expect_head: Option<&str>, json_output: bool) -> (String, u8) {
+    let verification = verify_chain(&witness_ledger_path(), expect_head);
+    let exit_code = if verification.invalid.is_empty() {
+        0
+    } else {
+        1
+    };
+    let output = if json_output {
+        verification.to_json()
+    } else {
+        verification.to_human()
+    };
+    (output, exit_code)
+}
+
+/// Check every `prev_hash` link from the first chained record to the end.
+///
+/// Records before the chain starts are legacy and not checked, so a ledger
+/// that was never chained verifies OK. After the start, an edited, inserted,
+/// or deleted line breaks the next link (`CHAIN_BROKEN`) and a record without
+/// `prev_hash` is `UNCHAINED_RECORD`. Dropping records from the end leaves
+/// every link intact; `expect_head`, a head reported earlier, catches it
+/// with `HEAD_NOT_FOUND`.
+pub fn verify_chain(path: &Path, expect_head: Option<&str>) -> ChainVerification {
+    let mut records = 0usize;
+    let mut chained = 0usize;
+    let mut chain_start = None;
+    let mut head: Option<String> = None;
+    let mut head_seen = false;
+    let mut last_line_no = 0usize;
+    let mut invalid = Vec::new();
+
+    if let Ok(file) = fs::File::open(path) {
+        for (index, line) in BufReader::new(file).split(b'\n').enumerate() {
+            let line_no = index + 1;
+            let bytes = match line {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    invalid.push(issue(line_no, "UNREADABLE_LINE", e.to_string()));
+                    break;
+                }
+            };
+            if bytes.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            records += 1;
+            last_line_no = line_no;
+
+            let prev_hash = serde_json::from_slice::<Value>(&bytes)
+                .ok()
+                .and_then(|record| record.get("prev_hash")?.as_str().map(str::to_string));
+            match prev_hash {
+                Some(prev_hash) => {
+                    chain_start.get_or_insert(line_no);
+                    let expected = head.clone().unwrap_or_else(|| line_hash(b""));
+                    if prev_hash != expected {
+                        invalid.push(issue(
+                            line_no,
+                            "CHAIN_BROKEN",
+                            format!(
+                                "prev_hash {prev_hash} does not match the preceding record ({expected})"
+                            ),
+                        ));
+                    }
+                }
+                None if chain_start.is_some() => invalid.push(issue(
+                    line_no,
+                    "UNCHAINED_RECORD",
+                    "record after the chain start has no prev_hash",
+                )),
+                None => {}
+            }
+            if chain_start.is_some() {
+                chained += 1;
+            }
+
+            let hash = line_hash(&bytes);
+            head_seen |= expect_head == Some(hash.as_str());
+            head = Some(hash);
+        }
+    }
+
+    if let Some(expected) = expect_head.filter(|_| !head_seen) {
+        invalid.push(issue(
+            last_line_no,
+            "HEAD_NOT_FOUND",
+            format!("no record hashes to {expected}: the ledger was truncated or rewritten"),
+        ));
+    }
+
+    ChainVerification {
+        version: CHAIN_REPORT_VERSION.to_string(),
+        outcome: if invalid.is_empty() { "OK" } else { "INVALID" }.to_string(),
+        ledger: path.display().to_string(),
+        records,
+        chained,
+        chain_start,
+        head,
+        invalid,
+    }
+}
+
+fn issue(line: usize, code: &str, message: impl Into<String>) -> LedgerIssue {
+    LedgerIssue {
+        line,
+        code: code.to_string(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn chained_ledger(records: &[&str]) -> String {
+        let legacy = r#"{"tool":"pack","outcome":"OK"}"#.to_string();
+        let mut prev = line_hash(legacy.as_bytes());
+        let mut lines = vec![legacy];
+        for outcome in records {
+            let line = format!(r#"{{"outcome":"{outcome}","prev_hash":"{prev}"}}"#);
+            prev = line_hash(line.as_bytes());
+            lines.push(line);
+        }
+        lines.join("\n") + "\n"
+    }
+
+    #[test]
+    fn intact_chain_after_legacy_records_is_ok() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("witness.jsonl");
+        fs::write(&path, chained_ledger(&["A", "B", "C"])).unwrap();
+
+        let verification = verify_chain(&path, None);
+        assert_eq!(verification.outcome, "OK", "{:?}", verification.invalid);
+        assert_eq!(verification.records, 4);
+        assert_eq!(verification.chained, 3);
+        assert_eq!(verification.chain_start, Some(2));
+
+        let head = verification.head.unwrap();
+        assert_eq!(verify_chain(&path, Some(&head)).outcome, "OK");
+    }
+
+    #[test]
+    fn edits_deletions_and_truncation_are_detected() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("witness.jsonl");
+        let ledger = chained_ledger(&["A", "B", "C"]);
+        fs::write(&path, &ledger).unwrap();
+        let head = verify_chain(&path, None).head.unwrap();
+
+        fs::write(
+            &path,
+            ledger.replace(r#""outcome":"B""#, r#""outcome":"X""#),
+        )
+        .unwrap();
+        let edited = verify_chain(&path, None);
+        assert_eq!(edited.invalid.len(), 1);
+        assert_eq!(
+            (edited.invalid[0].line, edited.invalid[0].code.as_str()),
+            (4, "CHAIN_BROKEN")
+        );
+
+        let mut lines: Vec<&str> = ledger.lines().collect();
+        lines.pop();
+        fs::write(&path, lines.join("\n")).unwrap();
+        assert_eq!(verify_chain(&path, None).outcome, "OK");
+        let truncated = verify_chain(&path, Some(&head));
+        assert_eq!(truncated.invalid[0].code, "HEAD_NOT_FOUND");
+
+        lines.push(r#"{"outcome":"unchained"}"#);
+        fs::write(&path, lines.join("\n")).unwrap();
+        assert_eq!(
+            verify_chain(&path, None).invalid[0].code,
+            "UNCHAINED_RECORD"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parquet::data_type::{ByteArray, ByteArrayType, Int32Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use serde::Serialize;
+
+use crate::cli::{ExportFormat, ExportPartition, WitnessFilters};
+use crate::refusal::{RefusalCode, RefusalEnvelope};
+
+use super::query::{filter_records, read_ledger};
+use super::record::WitnessRecord;
+
+/// Version tag for `pack witness export --json` output.
+pub const WITNESS_EXPORT_VERSION: &str = "pack.witness_export.v0";
+
+/// Export columns, in file order. Nested values (`inputs`, `params`,
+/// `enrichment`) are JSON text.
+pub const EXPORT_COLUMNS: [&str; 14] = [
+    "id",
+    "ts",
+    "tool",
+    "version",
+    "command",
+    "outcome",
+    "exit_code",
+    "pack_id",
+    "binary_hash",
+    "output_hash",
+    "prev_hash",
+    "inputs",
+    "params",
+    "enrichment",
+];
+
+const PARQUET_SCHEMA: &str = "message witness {
+    REQUIRED BYTE_ARRAY id (UTF8);
+    REQUIRED BYTE_ARRAY ts (UTF8);
+    REQUIRED BYTE_ARRAY tool (UTF8);
+    REQUIRED BYTE_ARRAY version (UTF8);
+    OPTIONAL BYTE_ARRAY command (UTF8);
+    REQUIRED BYTE_ARRAY outcome (UTF8);
+    REQUIRED INT32 exit_code;
+    OPTIONAL BYTE_ARRAY pack_id (UTF8);
+    OPTIONAL BYTE_ARRAY binary_hash (UTF8);
+    OPTIONAL BYTE_ARRAY output_hash (UTF8);
+    OPTIONAL BYTE_ARRAY prev_hash (UTF8);
+    OPTIONAL BYTE_ARRAY inputs (UTF8);
+    OPTIONAL BYTE_ARRAY params (UTF8);
+    OPTIONAL BYTE_ARRAY enrichment (UTF8);
+}";
+
+/// One witness record flattened to [`EXPORT_COLUMNS`]; empty values are null.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ExportRow {
+    id: String,
+    ts: String,
+    tool: String,
+    version: String,
+    command: Option<String>,
+    outcome: String,
+    exit_code: u8,
+    pack_id: Option<String>,
+    binary_hash: Option<String>,
+    output_hash: Option<String>,
+    prev_hash: Option<String>,
+    inputs: Option<String>,
+    params: Option<String>,
+    enrichment: Option<String>,
+}
+
+impl ExportRow {
+    fn new(record: &WitnessRecord) -> Self {
+        let text = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        let json = |value: serde_json::Value| match &value {
+            serde_json::Value::Null => None,
+            serde_json::Value::Array(items) if items.is_empty() => None,
+            serde_json::Value::Object(map) if map.is_empty() => None,
+            _ => Some(value.to_string()),
+        };
+        Self {
+            id: record.id.clone(),
+            ts: record.ts.clone(),
+            tool: record.tool.clone(),
+            version: record.version.clone(),
+            command: record.command.clone(),
+            outcome: record.outcome.clone(),
+            exit_code: record.exit_code,
+            pack_id: record.pack_id.clone(),
+            binary_hash: text(&record.binary_hash),
+            output_hash: text(&record.output_hash),
+            prev_hash: record.prev_hash.clone(),
+            inputs: json(serde_json::to_value(&record.inputs).unwrap_or_default()),
+            params: json(serde_json::Value::Object(record.params.clone())),
+            enrichment: json(serde_json::to_value(&record.enrichment).unwrap_or_default()),
+        }
+    }
+
+    /// Column values in [`EXPORT_COLUMNS`] order, `exit_code` as text.
+    fn values(&self) -> [Option<String>; 14] {
+        [
+            Some(self.id.clone()),
+            Some(self.ts.clone()),
+            Some(self.tool.clone()),
+            Some(self.version.clone()),
+            self.command.clone(),
+            Some(self.outcome.clone()),
+            Some(self.exit_code.to_string()),
+            self.pack_id.clone(),
+            self.binary_hash.clone(),
+            self.output_hash.clone(),
+            self.prev_hash.clone(),
+            self.inputs.clone(),
+            self.params.clone(),
+            self.enrichment.clone(),
+        ]
+    }
+}
+
+/// The outcome of `pack witness export`, as printed with `--json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportReport {
+    pub version: String,
+    pub format: String,
+    pub partition: String,
+    /// Records written across all files.
+    pub records: usize,
+    pub files: Vec<ExportedFile>,
+}
+
+/// One written file and the records in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedFile {
+    pub path: String,
+    /// `YYYY-MM` or `YYYY-MM-DD` for a partitioned export; `unknown` holds
+    /// records whose `ts` does not parse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition: Option<String>,
+    pub records: usize,
+}
+
+impl ExportReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("export report serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![format!(
+            "exported {} witness record(s) as {}",
+            self.records, self.format
+        )];
+        for file in &self.files {
+            lines.push(format!("  {} ({} record(s))", file.path, file.records));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack witness export`: write the records matching `filters` to
+/// `out` as one file, or under `out` as one file per month or day.
+pub fn execute_export(
+    filters: &WitnessFilters,
+    format: ExportFormat,
+    partition: ExportPartition,
+    out: &Path,
+    json_output: bool,
+) -> (String, u8) {
+    let records = read_ledger();
+    let rows: Vec<ExportRow> = filter_records(&records, filters, true)
+        .into_iter()
+        .map(ExportRow::new)
+        .collect();
+
+    let mut groups: BTreeMap<Option<String>, Vec<&ExportRow>> = BTreeMap::new();
+    match partition {
+        ExportPartition::None => {
+            groups.insert(None, rows.iter().collect());
+        }
+        ExportPartition::Month | ExportPartition::Day => {
+            for row in &rows {
+                groups
+                    .entry(Some(partition_key(&row.ts, partition)))
+                    .or_default()
+                    .push(row);
+            }
+        }
+    }
+
+    let mut files = Vec::with_capacity(groups.len());
+    for (key, rows) in &groups {
+        let path = match key {
+            None => out.to_path_buf(),
+            Some(key) => out
+                .join(format!("{}={key}", partition.as_str()))
+                .join(format!("witness.{}", format.extension())),
+        };
+        if let Err(message) = write_file(&path, format, rows) {
+            let refusal = RefusalEnvelope::new(
+                RefusalCode::Io,
+                Some(format!("Cannot write {}: {message}", path.display())),
+                None,
+            );
+            return (refusal.to_json(), 2);
+        }
+        files.push(ExportedFile {
+            path: path.display().to_string(),
+            partition: key.clone(),
+            records: rows.len(),
+        });
+    }
+
+    let report = ExportReport {
+        version: WITNESS_EXPORT_VERSION.to_string(),
+        format: format.extension().to_string(),
+        partition: partition.as_str().to_string(),
+        records: rows.len(),
+        files,
+    };
+    let output = if json_output {
+        report.to_json()
+    } else {
+        report.to_human()
+    };
+    (output, 0)
+}
+
+fn partition_key(ts: &str, partition: ExportPartition) -> String {
+    let pattern = match partition {
+        ExportPartition::Day => "%Y-%m-%d",
+        _ => "%Y-%m",
+    };
+    DateTime::parse_from_rfc3339(ts)
+        .map(|ts| ts.with_timezone(&Utc).format(pattern).to_string())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn write_file(path: &Path, format: ExportFormat, rows: &[&ExportRow]) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let bytes = match format {
+        ExportFormat::Json => {
+            let mut text = serde_json::to_string_pretty(rows).map_err(|e| e.to_string())?;
+            text.push('\n');
+            text.into_bytes()
+        }
+        ExportFormat::Csv => to_csv(rows).into_bytes(),
+        ExportFormat::Parquet => to_parquet(rows).map_err(|e| e.to_string())?,
+    };
+    let tmp = PathBuf::from(format!("{}.tmp", path.display()));
+    fs::write(&tmp, bytes).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+/// RFC 4180 CSV with a header row; null values are empty fields.
+fn to_csv(rows: &[&ExportRow]) -> String {
+    let field = |value: &str| {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    };
+    let mut text = EXPORT_COLUMNS.join(",");
+    text.push_str("\r\n");
+    for row in rows {
+        let values: Vec<String> = row
+            .values()
+            .iter()
+            .map(|value| value.as_deref().map(field).unwrap_or_default())
+            .collect();
+        text.push_str(&values.join(","));
+        text.push_str("\r\n");
+    }
+    text
+}
+
+/// One uncompressed row group, with `pack <version>` as the writer so the
+/// bytes depend only on the rows.
+fn to_parquet(rows: &[&ExportRow]) -> parquet::errors::Result<Vec<u8>> {
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_created_by(format!("pack {}", env!("CARGO_PKG_VERSION")))
+            .build(),
+    );
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, properties)?;
+    let mut row_group = writer.next_row_group()?;
+    let mut index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        if EXPORT_COLUMNS[index] == "exit_code" {
+            let values: Vec<i32> = rows.iter().map(|row| i32::from(row.exit_code)).collect();
+            column
+                .typed::<Int32Type>()
+                .write_batch(&values, None, None)?;
+        } else {
+            let cells: Vec<Option<String>> =
+                rows.iter().map(|row| row.values()[index].clone()).collect();
+            let levels: Vec<i16> = cells.iter().map(|cell| i16::from(cell.is_some())).collect();
+            let values: Vec<ByteArray> = cells
+                .into_iter()
+                .flatten()
+                .map(|cell| ByteArray::from(cell.into_bytes()))
+                .collect();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&values, Some(&levels), None)?;
+        }
+        column.close()?;
+        index += 1;
+    }
+    row_group.close()?;
+    writer.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn record(ts: &str, command: &str) -> WitnessRecord {
+        let mut record = WitnessRecord::new(
+            command,
+            vec![WitnessRecord::input(
+                "a, \"quoted\".json",
+                Some("sha256:abc".to_string()),
+                Some(3),
+            )],
+            "OK",
+            0,
+            serde_json::Map::new(),
+            b"out\n",
+            None,
+        );
+        record.ts = ts.to_string();
+        record
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_keeps_column_order() {
+        let row = ExportRow::new(&record("2026-01-15T10:00:00Z", "verify"));
+        let csv = to_csv(&[&row]);
+        let mut lines = csv.split("\r\n");
+        assert_eq!(lines.next().unwrap(), EXPORT_COLUMNS.join(","));
+        let data = lines.next().unwrap();
+        assert!(data.contains(",verify,OK,0,"));
+        assert!(data.contains(r#","[{""bytes"":3,"#));
+        assert!(data.contains(r#",""path"":""a, \""quoted\"".json""}]","#));
+    }
+
+    #[test]
+    fn parquet_holds_every_row_and_column() {
+        let rows = [
+            ExportRow::new(&record("2026-01-15T10:00:00Z", "seal")),
+            ExportRow::new(&record("2026-02-01T00:00:00Z", "verify")),
+        ];
+        let bytes = to_parquet(&rows.iter().collect::<Vec<_>>()).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("witness.parquet");
+        fs::write(&path, &bytes).unwrap();
+        let reader = SerializedFileReader::new(fs::File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 2);
+        assert_eq!(metadata.schema_descr().num_columns(), EXPORT_COLUMNS.len());
+        assert_eq!(
+            to_parquet(&rows.iter().collect::<Vec<_>>()).unwrap(),
+            bytes,
+            "parquet output is deterministic"
+        );
+    }
+
+    #[test]
+    fn partitions_by_month_or_day_with_unknown_for_bad_timestamps() {
+        assert_eq!(
+            partition_key("2026-01-31T23:30:00-02:00", ExportPartition::Month),
+            "2026-02"
+        );
+        assert_eq!(
+            partition_key("2026-01-15T10:00:00Z", ExportPartition::Day),
+            "2026-01-15"
+        );
+        assert_eq!(partition_key("", ExportPartition::Month), "unknown");
+    }
+}
@@ -1,16 +1,28 @@
-use std::fs::{self, OpenOptions};
-use std::io::Write;
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::lockfile::{lock_path_for, FileLock};
 
 use super::record::{canonical_json, WitnessRecord};
 
+/// Set to `1` to start hash-chaining the ledger. Once the last record carries
+/// `prev_hash`, every later append keeps the chain going.
+pub const WITNESS_CHAIN_ENV: &str = "PACK_WITNESS_CHAIN";
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const TAIL_CHUNK: u64 = 8192;
+
 /// Determine the witness ledger path.
 ///
 /// Priority:
 /// 1. `EPISTEMIC_WITNESS` env var
 /// 2. `~/.epistemic/witness.jsonl`
 pub fn witness_ledger_path() -> PathBuf {
-    witness_ledger_path_from_env(|key| std::env::var(key).ok())
+    witness_ledger_path_from_env(crate::config::env_or_config)
 }
 
 fn witness_ledger_path_from_env<F>(get_env: F) -> PathBuf
@@ -61,20 +73,97 @@ pub fn append_witness(record: &WitnessRecord) -> Result<(), String> {
         fs::create_dir_all(parent).map_err(|e| format!("Cannot create witness directory: {e}"))?;
     }
 
+    let chain_requested =
+        crate::config::env_or_config(WITNESS_CHAIN_ENV).is_some_and(|v| v.trim() == "1");
+
+    // Every append holds the lock, so records from concurrent runners never
+    // interleave; the tail is read under it so appends cannot fork a chain.
+    let _lock = FileLock::acquire(&lock_path_for(&path), LOCK_TIMEOUT)
+        .map_err(|e| format!("Cannot lock witness ledger: {e}"))?;
+    let tail = last_line(&path).map_err(|e| format!("Cannot read witness ledger tail: {e}"))?;
     let mut record = record.clone();
+    if chain_requested || tail.as_deref().is_some_and(is_chained_line) {
+        record.prev_hash = Some(line_hash(tail.as_deref().unwrap_or_default()));
+    }
     record.compute_id();
-    let line = canonical_json(&record);
 
     let mut file = OpenOptions::new()
         .create(true)
+        .read(true)
         .append(true)
         .open(&path)
         .map_err(|e| format!("Cannot open witness ledger: {e}"))?;
 
-    writeln!(file, "{line}").map_err(|e| format!("Cannot write witness record: {e}"))?;
+    // A writer killed mid-record leaves the ledger without a final newline;
+    // start a fresh line so the torn record stays on its own.
+    let mut line = if ends_mid_line(&mut file).unwrap_or(false) {
+        "\n".to_string()
+    } else {
+        String::new()
+    };
+    line.push_str(&canonical_json(&record));
+    line.push('\n');
+    file.write_all(line.as_bytes())
+        .map_err(|e| format!("Cannot write witness record: {e}"))?;
 
     Ok(())
 }
+
+fn ends_mid_line(file: &mut File) -> io::Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(false);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] != b'\n')
+}
+
+/// `sha256:<hex>` of one ledger line, without its newline. The first record
+/// of a chain started on an empty ledger links to the hash of no bytes.
+pub fn line_hash(line: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(line)))
+}
+
+fn is_chained_line(line: &[u8]) -> bool {
+    serde_json::from_slice::<serde_json::Value>(line)
+        .is_ok_and(|value| value.get("prev_hash").is_some_and(|v| v.is_string()))
+}
+
+/// The last non-blank line of the ledger, read backwards from the end so
+/// large ledgers are not scanned; `None` when the ledger is missing or blank.
+fn last_line(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut pos = file.metadata()?.len();
+    let mut tail: Vec<u8> = Vec::new();
+    loop {
+        if let Some(end) = tail.iter().rposition(|b| !b.is_ascii_whitespace()) {
+            let start = tail[..end].iter().rposition(|&b| b == b'\n');
+            if start.is_some() || pos == 0 {
+                let start = start.map_or(0, |i| i + 1);
+                let stop = tail[end..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(tail.len(), |i| end + i);
+                return Ok(Some(tail[start..stop].to_vec()));
+            }
+        } else if pos == 0 {
+            return Ok(None);
+        }
+        let read = TAIL_CHUNK.min(pos);
+        pos -= read;
+        let mut chunk = vec![0; read as usize];
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +198,9 @@ mod tests {
         assert_eq!(parsed.command.as_deref(), Some("seal"));
         assert_eq!(parsed.outcome, "PACK_CREATED");
         assert!(parsed.id.starts_with("blake3:"));
+        // The ledger line is the record's RFC 8785 form.
+        let value = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(content.trim(), crate::canonical::canonicalize(&value));
 
         std::env::remove_var("EPISTEMIC_WITNESS");
     }
@@ -154,6 +246,88 @@ mod tests {
         std::env::remove_var("EPISTEMIC_WITNESS");
     }
 
+    #[test]
+    fn concurrent_appends_keep_whole_lines() {
+        let tmp = TempDir::new().unwrap();
+        let ledger_path = tmp.path().join("witness.jsonl");
+        std::env::set_var("EPISTEMIC_WITNESS", ledger_path.display().to_string());
+
+        let mut params = serde_json::Map::new();
+        params.insert("note".to_string(), "x".repeat(4096).into());
+        let record = WitnessRecord::new(
+            "verify",
+            Vec::new(),
+            "OK",
+            0,
+            params,
+            b"pack verify: OK\n",
+            None,
+        );
+        let writers: Vec<_> = (0..8)
+            .map(|_| {
+                let record = record.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        append_witness(&record).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let content = fs::read_to_string(&ledger_path).unwrap();
+        assert_eq!(content.lines().count(), 80);
+        for line in content.lines() {
+            serde_json::from_str::<WitnessRecord>(line).unwrap();
+        }
+        std::env::remove_var("EPISTEMIC_WITNESS");
+    }
+
+    #[test]
+    fn append_after_torn_record_starts_a_new_line() {
+        let tmp = TempDir::new().unwrap();
+        let ledger_path = tmp.path().join("witness.jsonl");
+        std::env::set_var("EPISTEMIC_WITNESS", ledger_path.display().to_string());
+        fs::write(&ledger_path, r#"{"tool":"pack","outc"#).unwrap();
+
+        // A lock file left by a killed writer holds no lock.
+        let lock = tmp.path().join("witness.jsonl.lock");
+        File::create(&lock).unwrap();
+
+        let record = WitnessRecord::new(
+            "verify",
+            Vec::new(),
+            "OK",
+            0,
+            serde_json::Map::new(),
+            b"pack verify: OK\n",
+            None,
+        );
+        append_witness(&record).unwrap();
+
+        let content = fs::read_to_string(&ledger_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], r#"{"tool":"pack","outc"#);
+        serde_json::from_str::<WitnessRecord>(lines[1]).unwrap();
+        std::env::remove_var("EPISTEMIC_WITNESS");
+    }
+
+    #[test]
+    fn last_line_skips_trailing_blank_lines_across_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("witness.jsonl");
+        assert_eq!(last_line(&path).unwrap(), None);
+
+        let long = "x".repeat(TAIL_CHUNK as usize * 2 + 5);
+        fs::write(&path, format!("first\n{long}\n\n  \n")).unwrap();
+        assert_eq!(last_line(&path).unwrap(), Some(long.into_bytes()));
+
+        fs::write(&path, "only").unwrap();
+        assert_eq!(last_line(&path).unwrap(), Some(b"only".to_vec()));
+    }
+
     #[test]
     fn witness_record_has_correct_fields() {
         let record = WitnessRecord::new(
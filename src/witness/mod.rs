@@ -1,6 +1,13 @@
+pub mod chain;
+pub mod export;
 mod ledger;
 pub mod query;
 mod record;
+pub mod tail;
+pub mod validate;
 
-pub use ledger::{append_witness, witness_ledger_path};
-pub use record::{WitnessInput, WitnessRecord};
+pub use ledger::{append_witness, line_hash, witness_ledger_path, WITNESS_CHAIN_ENV};
+pub(crate) use record::{hostname, user};
+pub use record::{
+    mark_command_start, WitnessEnrichment, WitnessInput, WitnessRecord, WITNESS_ENRICH_ENV,
+};
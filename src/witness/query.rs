@@ -8,26 +8,83 @@ use crate::cli::WitnessFilters;
 
 use super::ledger::witness_ledger_path;
 use super::record::WitnessRecord;
+use super::validate::LedgerIssue;
 
-fn read_ledger() -> Vec<WitnessRecord> {
+pub(super) fn read_ledger() -> Vec<WitnessRecord> {
+    read_ledger_reporting_corrupt().0
+}
+
+/// Every parseable record in the ledger, plus the lines that are not
+/// records: torn writes from a killed runner, invalid UTF-8, or manual
+/// edits. Corrupt lines are skipped rather than ending the read.
+pub(super) fn read_ledger_reporting_corrupt() -> (Vec<WitnessRecord>, Vec<LedgerIssue>) {
     let path = witness_ledger_path();
     let file = match fs::File::open(&path) {
         Ok(file) => file,
-        Err(_) => return Vec::new(),
+        Err(_) => return (Vec::new(), Vec::new()),
     };
 
-    BufReader::new(file)
-        .lines()
-        .map_while(Result::ok)
-        .filter(|line| !line.trim().is_empty())
-        .filter_map(|line| serde_json::from_str::<WitnessRecord>(&line).ok())
-        .collect()
+    let mut records = Vec::new();
+    let mut corrupt = Vec::new();
+    for (index, line) in BufReader::new(file).split(b'\n').enumerate() {
+        let line_no = index + 1;
+        let bytes = match line {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                corrupt.push(LedgerIssue {
+                    line: line_no,
+                    code: "UNREADABLE_LINE".to_string(),
+                    message: e.to_string(),
+                });
+                break;
+            }
+        };
+        if bytes.trim_ascii().is_empty() {
+            continue;
+        }
+        match serde_json::from_slice::<WitnessRecord>(&bytes) {
+            Ok(record) => records.push(record),
+            Err(e) => corrupt.push(LedgerIssue {
+                line: line_no,
+                code: "MALFORMED_JSON".to_string(),
+                message: e.to_string(),
+            }),
+        }
+    }
+    (records, corrupt)
 }
 
 /// Execute `pack witness query` — return matching witness records.
-pub fn execute_query(filters: &WitnessFilters, json_output: bool) -> String {
-    let records = read_ledger();
+///
+/// With `report_corrupt`, lines that are not witness records are listed
+/// after the matches (human) or alongside them as
+/// `{"records": [...], "corrupt": [...]}` (JSON).
+pub fn execute_query(filters: &WitnessFilters, json_output: bool, report_corrupt: bool) -> String {
+    let (records, corrupt) = read_ledger_reporting_corrupt();
     let records = filter_records(&records, filters, true);
+    if report_corrupt {
+        if json_output {
+            return serde_json::to_string_pretty(&serde_json::json!({
+                "records": records,
+                "corrupt": corrupt,
+            }))
+            .expect("witness query serialization cannot fail");
+        }
+        let mut lines: Vec<String> = records
+            .iter()
+            .map(|record| format_record_human(record))
+            .collect();
+        if corrupt.is_empty() {
+            lines.push("No corrupt witness lines.".to_string());
+        }
+        lines.extend(corrupt.iter().map(|issue| {
+            format!(
+                "corrupt line {}: {} ({})",
+                issue.line, issue.code, issue.message
+            )
+        }));
+        return lines.join("\n");
+    }
     if records.is_empty() {
         return if json_output {
             "[]".to_string()
@@ -83,7 +140,7 @@ pub fn execute_count(filters: &WitnessFilters, json_output: bool) -> String {
     }
 }
 
-fn filter_records<'a>(
+pub(super) fn filter_records<'a>(
     records: &'a [WitnessRecord],
     filters: &WitnessFilters,
     default_to_pack: bool,
@@ -160,7 +217,7 @@ fn filters_active(filters: &WitnessFilters) -> bool {
         || filters.input_hash.is_some()
 }
 
-fn format_record_human(record: &WitnessRecord) -> String {
+pub(super) fn format_record_human(record: &WitnessRecord) -> String {
     let ts = if record.ts.is_empty() {
         "-"
     } else {
@@ -203,9 +260,9 @@ mod tests {
     #[test]
     fn query_empty_ledger() {
         let _tmp = setup_ledger();
-        let result = execute_query(&WitnessFilters::default(), false);
+        let result = execute_query(&WitnessFilters::default(), false, false);
         assert_eq!(result, "No witness records found.");
-        let json_result = execute_query(&WitnessFilters::default(), true);
+        let json_result = execute_query(&WitnessFilters::default(), true, false);
         assert_eq!(json_result, "[]");
         teardown();
     }
@@ -228,11 +285,11 @@ mod tests {
         );
         append_witness(&r).unwrap();
 
-        let result = execute_query(&WitnessFilters::default(), false);
+        let result = execute_query(&WitnessFilters::default(), false, false);
         assert!(result.contains("seal"));
         assert!(result.contains("PACK_CREATED"));
 
-        let json_result = execute_query(&WitnessFilters::default(), true);
+        let json_result = execute_query(&WitnessFilters::default(), true, false);
         let parsed: Vec<WitnessRecord> = serde_json::from_str(&json_result).unwrap();
         assert_eq!(parsed.len(), 1);
         teardown();
@@ -338,7 +395,7 @@ mod tests {
         )
         .unwrap();
 
-        let default_json = execute_query(&WitnessFilters::default(), true);
+        let default_json = execute_query(&WitnessFilters::default(), true, false);
         let default_records: Vec<WitnessRecord> = serde_json::from_str(&default_json).unwrap();
         assert_eq!(default_records.len(), 1);
         assert_eq!(default_records[0].tool, "pack");
@@ -349,6 +406,7 @@ mod tests {
                 ..WitnessFilters::default()
             },
             true,
+            false,
         );
         let hash_records: Vec<WitnessRecord> = serde_json::from_str(&hash_json).unwrap();
         assert_eq!(hash_records.len(), 1);
@@ -401,6 +459,7 @@ mod tests {
                 ..WitnessFilters::default()
             },
             true,
+            false,
         );
         let records: Vec<WitnessRecord> = serde_json::from_str(&json_result).unwrap();
         assert_eq!(records.len(), 1);
@@ -418,9 +477,43 @@ mod tests {
         )
         .unwrap();
 
-        let result = execute_query(&WitnessFilters::default(), false);
+        let result = execute_query(&WitnessFilters::default(), false, false);
         assert!(result.contains("seal"));
         assert!(result.contains("sha256:legacy"));
         teardown();
     }
+
+    #[test]
+    fn corrupt_lines_are_skipped_and_reported() {
+        let _tmp = setup_ledger();
+        let record = WitnessRecord::new(
+            "verify",
+            Vec::new(),
+            "OK",
+            0,
+            serde_json::Map::new(),
+            b"pack verify: OK\n",
+            None,
+        );
+        append_witness(&record).unwrap();
+        let ledger_path = witness_ledger_path();
+        let mut bytes = std::fs::read(&ledger_path).unwrap();
+        bytes.extend_from_slice(b"{\"tool\":\"pack\",\"outc\xff\n");
+        std::fs::write(&ledger_path, bytes).unwrap();
+        append_witness(&record).unwrap();
+
+        let plain: Vec<WitnessRecord> =
+            serde_json::from_str(&execute_query(&WitnessFilters::default(), true, false)).unwrap();
+        assert_eq!(plain.len(), 2, "records after the torn line are kept");
+
+        let report: Value =
+            serde_json::from_str(&execute_query(&WitnessFilters::default(), true, true)).unwrap();
+        assert_eq!(report["records"].as_array().unwrap().len(), 2);
+        assert_eq!(report["corrupt"][0]["line"], 2);
+        assert_eq!(report["corrupt"][0]["code"], "MALFORMED_JSON");
+
+        let human = execute_query(&WitnessFilters::default(), false, true);
+        assert!(human.contains("corrupt line 2: MALFORMED_JSON"));
+        teardown();
+    }
 }
@@ -1,6 +1,24 @@
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Instant;
+
 use chrono::{SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::canonical::canonicalize;
+use crate::seal::manifest::Manifest;
+
+/// Set to `1` to add [`WitnessEnrichment`] to every witness record.
+pub const WITNESS_ENRICH_ENV: &str = "PACK_WITNESS_ENRICH";
+
+static COMMAND_STARTED: OnceLock<Instant> = OnceLock::new();
+
+/// Record when the command started, for `enrichment.duration_ms`.
+pub fn mark_command_start() {
+    COMMAND_STARTED.get_or_init(Instant::now);
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct WitnessInput {
@@ -35,6 +53,80 @@ pub struct WitnessRecord {
     pub ts: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pack_id: Option<String>,
+    /// `sha256:` of the previous ledger line; set only in a chained ledger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// Who ran the command, where, and for how long; set only when
+    /// `PACK_WITNESS_ENRICH=1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrichment: Option<WitnessEnrichment>,
+}
+
+/// Audit context for a witness record, so an investigation can reconstruct a
+/// run without separate logs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WitnessEnrichment {
+    /// `sha256:` of the command-line arguments after the program name, each
+    /// followed by a NUL byte. Arguments may hold secrets, so only the hash
+    /// is kept.
+    pub argv_hash: String,
+    /// Wall-clock time from command start to the record.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Members in the pack the command sealed or read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<usize>,
+    /// Combined size of those members.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack_bytes: Option<u64>,
+}
+
+impl WitnessEnrichment {
+    /// Gather the context of the running process.
+    pub fn collect() -> Self {
+        let mut argv = Sha256::new();
+        for arg in std::env::args_os().skip(1) {
+            argv.update(arg.as_encoded_bytes());
+            argv.update([0]);
+        }
+        Self {
+            argv_hash: format!("sha256:{}", hex::encode(argv.finalize())),
+            duration_ms: COMMAND_STARTED
+                .get()
+                .map(|started| started.elapsed().as_millis() as u64),
+            hostname: hostname(),
+            user: user(),
+            member_count: None,
+            pack_bytes: None,
+        }
+    }
+}
+
+/// This machine's name, from the environment or the kernel.
+pub(crate) fn hostname() -> Option<String> {
+    let from_env = ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok());
+    from_env
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// The invoking user's login name, from the environment.
+pub(crate) fn user() -> Option<String> {
+    ["USER", "LOGNAME", "USERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+}
+
+fn enrichment_enabled() -> bool {
+    crate::config::env_or_config(WITNESS_ENRICH_ENV).is_some_and(|v| v.trim() == "1")
 }
 
 impl WitnessRecord {
@@ -74,7 +166,39 @@ impl WitnessRecord {
             output_hash: format!("blake3:{}", blake3::hash(output_bytes).to_hex()),
             ts: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
             pack_id,
+            prev_hash: None,
+            enrichment: enrichment_enabled().then(WitnessEnrichment::collect),
+        }
+    }
+
+    /// Record the size of the pack the command produced, when enriched.
+    pub fn with_pack_size(mut self, member_count: usize, pack_bytes: u64) -> Self {
+        if let Some(enrichment) = &mut self.enrichment {
+            enrichment.member_count = Some(member_count);
+            enrichment.pack_bytes = Some(pack_bytes);
+        }
+        self
+    }
+
+    /// Record the size of the pack at `pack_dir`, when enriched and its
+    /// manifest is readable. Members that cannot be read count as zero bytes.
+    pub fn with_pack_dir(self, pack_dir: &Path) -> Self {
+        if self.enrichment.is_none() {
+            return self;
         }
+        let Some(manifest) = std::fs::read(pack_dir.join("manifest.json"))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Manifest>(&bytes).ok())
+        else {
+            return self;
+        };
+        let pack_bytes = manifest
+            .members
+            .iter()
+            .filter_map(|member| std::fs::metadata(pack_dir.join(&member.path)).ok())
+            .map(|meta| meta.len())
+            .sum();
+        self.with_pack_size(manifest.members.len(), pack_bytes)
     }
 
     pub fn compute_id(&mut self) {
@@ -86,9 +210,9 @@ impl WitnessRecord {
     }
 }
 
+/// The record as RFC 8785 text: the bytes its `id` hashes and the ledger line.
 pub fn canonical_json(record: &WitnessRecord) -> String {
-    let value = serde_json::to_value(record).expect("WitnessRecord should serialize");
-    serde_json::to_string(&value).expect("WitnessRecord JSON should encode")
+    canonicalize(&serde_json::to_value(record).expect("WitnessRecord should serialize"))
 }
 
 fn hash_self() -> Result<String, std::io::Error> {
@@ -0,0 +1,299 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::cli::WitnessFilters;
+
+use super::ledger::witness_ledger_path;
+use super::query::{filter_records, format_record_human};
+use super::record::WitnessRecord;
+
+/// Records `pack witness tail` prints before following, as for `tail`.
+pub const DEFAULT_TAIL_LINES: usize = 10;
+
+/// Seconds between ledger polls under `--follow` when no file event arrives.
+pub const DEFAULT_TAIL_INTERVAL_SECS: u64 = 1;
+
+/// Options for `pack witness tail`.
+#[derive(Debug, Clone)]
+pub struct TailOptions {
+    /// `-n`: how many matching records to print from the end of the ledger.
+    pub lines: usize,
+    /// `--follow`: keep printing matching records as they are appended.
+    pub follow: bool,
+    /// `--interval`: poll period; `None` uses `DEFAULT_TAIL_INTERVAL_SECS`.
+    pub interval: Option<Duration>,
+}
+
+impl Default for TailOptions {
+    fn default() -> Self {
+        Self {
+            lines: DEFAULT_TAIL_LINES,
+            follow: false,
+            interval: None,
+        }
+    }
+}
+
+/// Execute `pack witness tail`: pass the last `lines` records matching
+/// `filters` to `emit`, one line each (compact JSON with `json_output`), then
+/// with `follow` keep passing records as they are appended.
+///
+/// Only complete lines are read, so a record being written is picked up once
+/// its newline lands. Lines that are not witness records are skipped, as in
+/// `query`. A ledger that shrinks was truncated or replaced and is read again
+/// from the start. Returns when not following, or when `emit` breaks.
+pub fn execute_tail(
+    filters: &WitnessFilters,
+    options: &TailOptions,
+    json_output: bool,
+    emit: impl FnMut(String) -> ControlFlow<()>,
+) {
+    tail_ledger(
+        &witness_ledger_path(),
+        filters,
+        options,
+        json_output,
+        emit,
+        || ControlFlow::Continue(()),
+    );
+}
+
+/// [`execute_tail`] on the ledger at `path`. Under `follow`, `idle` is asked
+/// after every wait whether to keep following, so a caller can stop even
+/// when no record arrives.
+fn tail_ledger(
+    path: &Path,
+    filters: &WitnessFilters,
+    options: &TailOptions,
+    json_output: bool,
+    mut emit: impl FnMut(String) -> ControlFlow<()>,
+    mut idle: impl FnMut() -> ControlFlow<()>,
+) {
+    let mut cursor = LedgerCursor::default();
+    let records = cursor.read_new(path);
+    let matching = filter_records(&records, filters, true);
+    let skip = matching.len().saturating_sub(options.lines);
+    if !options.follow && matching.is_empty() && !json_output {
+        let _ = emit("No witness records found.".to_string());
+        return;
+    }
+    for record in &matching[skip..] {
+        if emit(tail_line(record, json_output)).is_break() {
+            return;
+        }
+    }
+    if !options.follow {
+        return;
+    }
+
+    let interval = options
+        .interval
+        .unwrap_or(Duration::from_secs(DEFAULT_TAIL_INTERVAL_SECS));
+    let events = start_watcher(path, interval);
+    loop {
+        wait_for_append(events.as_ref(), interval);
+        let records = cursor.read_new(path);
+        for record in filter_records(&records, filters, true) {
+            if emit(tail_line(record, json_output)).is_break() {
+                return;
+            }
+        }
+        if idle().is_break() {
+            return;
+        }
+    }
+}
+
+fn tail_line(record: &WitnessRecord, json_output: bool) -> String {
+    if json_output {
+        serde_json::to_string(record).expect("witness record serialization cannot fail")
+    } else {
+        format_record_human(record)
+    }
+}
+
+/// How far into the ledger `tail` has read: the byte after the last complete
+/// line.
+#[derive(Debug, Default)]
+struct LedgerCursor {
+    offset: u64,
+}
+
+impl LedgerCursor {
+    /// Records on the complete lines appended since the last read. A missing
+    /// or unreadable ledger has none yet.
+    fn read_new(&mut self, path: &Path) -> Vec<WitnessRecord> {
+        let Ok(mut file) = File::open(path) else {
+            return Vec::new();
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len < self.offset {
+            self.offset = 0;
+        }
+        let mut bytes = Vec::new();
+        if file.seek(SeekFrom::Start(self.offset)).is_err() || file.read_to_end(&mut bytes).is_err()
+        {
+            return Vec::new();
+        }
+        let complete = bytes.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        self.offset += complete as u64;
+        bytes[..complete]
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.trim_ascii().is_empty())
+            .filter_map(|line| serde_json::from_slice(line).ok())
+            .collect()
+    }
+}
+
+type EventReceiver = (
+    notify::RecommendedWatcher,
+    Receiver<notify::Result<notify::Event>>,
+);
+
+/// Watch the ledger's directory rather than the file, so a ledger that does
+/// not exist yet or is replaced is still seen.
+fn start_watcher(ledger: &Path, interval: Duration) -> Option<EventReceiver> {
+    let dir = match ledger.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let (tx, rx) = mpsc::channel();
+    let watcher = notify::recommended_watcher(tx).and_then(|mut watcher| {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        Ok(watcher)
+    });
+    match watcher {
+        Ok(watcher) => Some((watcher, rx)),
+        Err(e) => {
+            eprintln!(
+                "pack: witness tail: file events unavailable ({e}); polling every {}s",
+                interval.as_secs_f64()
+            );
+            None
+        }
+    }
+}
+
+fn wait_for_append(events: Option<&EventReceiver>, interval: Duration) {
+    let Some((_, rx)) = events else {
+        std::thread::sleep(interval);
+        return;
+    };
+    match rx.recv_timeout(interval) {
+        Ok(_) => while rx.try_recv().is_ok() {},
+        Err(RecvTimeoutError::Timeout) => {}
+        Err(RecvTimeoutError::Disconnected) => std::thread::sleep(interval),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::Instant;
+    use tempfile::TempDir;
+
+    fn record(command: &str, outcome: &str) -> WitnessRecord {
+        WitnessRecord::new(
+            command,
+            Vec::new(),
+            outcome,
+            0,
+            serde_json::Map::new(),
+            b"",
+            None,
+        )
+    }
+
+    #[test]
+    fn tail_prints_the_last_matching_records_then_follows_appends() {
+        let tmp = TempDir::new().unwrap();
+        let ledger = tmp.path().join("witness.jsonl");
+        let existing: String = [
+            ("seal", "PACK_CREATED"),
+            ("verify", "OK"),
+            ("verify", "INVALID"),
+        ]
+        .iter()
+        .map(|(command, outcome)| serde_json::to_string(&record(command, outcome)).unwrap() + "\n")
+        .collect();
+        std::fs::write(&ledger, existing).unwrap();
+        let filters = WitnessFilters {
+            outcome: Some("INVALID".to_string()),
+            ..WitnessFilters::default()
+        };
+        let mut lines = Vec::new();
+        tail_ledger(
+            &ledger,
+            &WitnessFilters::default(),
+            &TailOptions {
+                lines: 2,
+                ..TailOptions::default()
+            },
+            false,
+            |line| {
+                lines.push(line);
+                ControlFlow::Continue(())
+            },
+            || ControlFlow::Continue(()),
+        );
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("verify OK"), "{lines:?}");
+
+        let writer = std::thread::spawn({
+            let ledger = ledger.clone();
+            move || {
+                std::thread::sleep(Duration::from_millis(100));
+                let mut file = std::fs::OpenOptions::new()
+                    .append(true)
+                    .open(&ledger)
+                    .unwrap();
+                let line = serde_json::to_string(&record("diff", "INVALID")).unwrap();
+                let (head, rest) = line.split_at(10);
+                // A half-written line is not read until its newline lands.
+                file.write_all(head.as_bytes()).unwrap();
+                file.flush().unwrap();
+                std::thread::sleep(Duration::from_millis(100));
+                writeln!(file, "{rest}").unwrap();
+            }
+        });
+        let options = TailOptions {
+            lines: 1,
+            follow: true,
+            interval: Some(Duration::from_millis(20)),
+        };
+        let mut records = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        tail_ledger(
+            &ledger,
+            &filters,
+            &options,
+            true,
+            |line| {
+                records.push(serde_json::from_str::<WitnessRecord>(&line).unwrap());
+                if records.len() == 2 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+            || {
+                if Instant::now() < deadline {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            },
+        );
+        writer.join().unwrap();
+        assert_eq!(records.len(), 2, "appended record not seen within 5s");
+        assert!(records.iter().all(|r| r.outcome == "INVALID"));
+        assert_eq!(records[1].command.as_deref(), Some("diff"));
+    }
+}
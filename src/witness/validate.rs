@@ -0,0 +1,286 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use chrono::DateTime;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::ledger::witness_ledger_path;
+
+/// Legacy records tag the schema in `version`; current records carry the tool semver.
+/// Legacy witness record schema tag.
+pub const WITNESS_SCHEMA_VERSION: &str = "witness.v0";
+
+/// A single malformed or unknown-version ledger line.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct LedgerIssue {
+    pub line: usize,
+    pub code: String,
+    pub message: String,
+}
+
+/// Result of scanning the witness ledger against the witness.v0 schema.
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerValidation {
+    pub version: String,
+    pub outcome: String,
+    pub ledger: String,
+    pub records: usize,
+    pub invalid: Vec<LedgerIssue>,
+}
+
+impl LedgerValidation {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("ledger validation serialization cannot fail")
+    }
+
+    pub fn to_human(&self) -> String {
+        let mut lines = vec![
+            format!("pack witness validate: {}", self.outcome),
+            format!("  ledger: {}", self.ledger),
+            format!("  records: {}", self.records),
+        ];
+        for issue in &self.invalid {
+            lines.push(format!(
+                "  line {}: {} ({})",
+                issue.line, issue.code, issue.message
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Execute `pack witness validate` against the configured ledger.
+///
+/// Returns (output, exit_code): 0 when every line is a valid record, 1 otherwise.
+pub fn execute_validate(json_output: bool) -> (String, u8) {
+    let validation = validate_ledger(&witness_ledger_path());
+    let exit_code = if validation.invalid.is_empty() { 0 } else { 1 };
+    let output = if json_output {
+        validation.to_json()
+    } else {
+        validation.to_human()
+    };
+    (output, exit_code)
+}
+
+/// Scan a ledger file line by line. A missing ledger validates as empty.
+pub fn validate_ledger(path: &Path) -> LedgerValidation {
+    let mut records = 0usize;
+    let mut invalid = Vec::new();
+
+    if let Ok(file) = fs::File::open(path) {
+        for (index, line) in BufReader::new(file).split(b'\n').enumerate() {
+            let line_no = index + 1;
+            let bytes = match line {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    invalid.push(issue(line_no, "UNREADABLE_LINE", e.to_string()));
+                    break;
+                }
+            };
+            let Ok(text) = String::from_utf8(bytes) else {
+                invalid.push(issue(line_no, "MALFORMED_JSON", "line is not valid UTF-8"));
+                continue;
+            };
+            if text.trim().is_empty() {
+                continue;
+            }
+            records += 1;
+            match serde_json::from_str::<Value>(&text) {
+                Ok(Value::Object(record)) => invalid.extend(
+                    validate_record(&record)
+                        .into_iter()
+                        .map(|(code, message)| issue(line_no, code, message)),
+                ),
+                Ok(_) => invalid.push(issue(line_no, "NOT_OBJECT", "record must be a JSON object")),
+                Err(e) => invalid.push(issue(line_no, "MALFORMED_JSON", e.to_string())),
+            }
+        }
+    }
+
+    LedgerValidation {
+        version: "pack.witness.validate.v0".to_string(),
+        outcome: if invalid.is_empty() { "OK" } else { "INVALID" }.to_string(),
+        ledger: path.display().to_string(),
+        records,
+        invalid,
+    }
+}
+
+/// Check one parsed record against the witness.v0 field contract.
+fn validate_record(record: &Map<String, Value>) -> Vec<(&'static str, String)> {
+    let mut problems = Vec::new();
+
+    for field in ["tool", "version", "outcome"] {
+        match record.get(field) {
+            Some(Value::String(_)) => {}
+            Some(_) => problems.push(("INVALID_FIELD", format!("{field} must be a string"))),
+            None => problems.push(("MISSING_FIELD", format!("missing {field}"))),
+        }
+    }
+
+    if let Some(Value::String(version)) = record.get("version") {
+        if !is_known_version(version) {
+            problems.push(("UNKNOWN_VERSION", format!("unknown version {version}")));
+        }
+    }
+
+    match record.get("ts").or_else(|| record.get("timestamp")) {
+        Some(Value::String(ts)) if DateTime::parse_from_rfc3339(ts).is_ok() => {}
+        Some(_) => problems.push((
+            "INVALID_FIELD",
+            "ts must be an RFC3339 timestamp".to_string(),
+        )),
+        None => problems.push(("MISSING_FIELD", "missing ts".to_string())),
+    }
+
+    if let Some(exit_code) = record.get("exit_code") {
+        if exit_code.as_u64().is_none_or(|code| code > 255) {
+            problems.push((
+                "INVALID_FIELD",
+                "exit_code must be an integer 0-255".to_string(),
+            ));
+        }
+    }
+
+    for field in ["id", "binary_hash", "output_hash", "prev_hash"] {
+        if record.get(field).is_some_and(|v| !v.is_string()) {
+            problems.push(("INVALID_FIELD", format!("{field} must be a string")));
+        }
+    }
+
+    for field in ["command", "pack_id"] {
+        if record
+            .get(field)
+            .is_some_and(|v| !v.is_string() && !v.is_null())
+        {
+            problems.push(("INVALID_FIELD", format!("{field} must be a string or null")));
+        }
+    }
+
+    if record.get("params").is_some_and(|v| !v.is_object()) {
+        problems.push(("INVALID_FIELD", "params must be an object".to_string()));
+    }
+
+    if record.get("enrichment").is_some_and(|v| !v.is_object()) {
+        problems.push(("INVALID_FIELD", "enrichment must be an object".to_string()));
+    }
+
+    match record.get("inputs") {
+        None => {}
+        Some(Value::Array(inputs)) => {
+            for (i, input) in inputs.iter().enumerate() {
+                let valid = input.get("path").is_some_and(Value::is_string)
+                    && input.get("hash").is_none_or(Value::is_string)
+                    && input.get("bytes").is_none_or(Value::is_u64);
+                if !valid {
+                    problems.push(("INVALID_FIELD", format!("inputs[{i}] is malformed")));
+                }
+            }
+        }
+        Some(_) => problems.push(("INVALID_FIELD", "inputs must be an array".to_string())),
+    }
+
+    problems
+}
+
+/// `witness.v0` is the legacy schema tag; anything else must be a tool semver.
+fn is_known_version(version: &str) -> bool {
+    if version == WITNESS_SCHEMA_VERSION {
+        return true;
+    }
+    if version.starts_with("witness.") {
+        return false;
+    }
+    let core = version.split(['-', '+']).next().unwrap_or("");
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn issue(line: usize, code: &str, message: impl Into<String>) -> LedgerIssue {
+    LedgerIssue {
+        line,
+        code: code.to_string(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::witness::WitnessRecord;
+    use tempfile::TempDir;
+
+    fn write_ledger(lines: &[&str]) -> (TempDir, std::path::PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("witness.jsonl");
+        fs::write(&path, lines.join("\n")).unwrap();
+        (tmp, path)
+    }
+
+    #[test]
+    fn missing_ledger_is_ok() {
+        let tmp = TempDir::new().unwrap();
+        let validation = validate_ledger(&tmp.path().join("absent.jsonl"));
+        assert_eq!(validation.outcome, "OK");
+        assert_eq!(validation.records, 0);
+    }
+
+    #[test]
+    fn current_and_legacy_records_are_valid() {
+        let mut record = WitnessRecord::new(
+            "seal",
+            vec![WitnessRecord::input("a.json", None, Some(3))],
+            "PACK_CREATED",
+            0,
+            Map::new(),
+            b"out",
+            Some("sha256:abc".to_string()),
+        );
+        record.compute_id();
+        let current = serde_json::to_string(&record).unwrap();
+        let legacy = r#"{"version":"witness.v0","tool":"pack","command":"seal","outcome":"PACK_CREATED","pack_id":null,"timestamp":"2026-01-15T10:00:00.000Z"}"#;
+        let (_tmp, path) = write_ledger(&[&current, "", legacy]);
+
+        let validation = validate_ledger(&path);
+        assert_eq!(validation.outcome, "OK", "{:?}", validation.invalid);
+        assert_eq!(validation.records, 2);
+    }
+
+    #[test]
+    fn reports_malformed_and_unknown_version_with_line_numbers() {
+        let (_tmp, path) = write_ledger(&[
+            r#"{"version":"witness.v0","tool":"pack","outcome":"OK","ts":"2026-01-15T10:00:00Z"}"#,
+            r#"{"version":"witness.v0","tool":"pack","outc"#,
+            r#"{"version":"witness.v9","tool":"pack","outcome":"OK","ts":"2026-01-15T10:00:00Z"}"#,
+            r#"{"version":"0.2.3","outcome":"OK","ts":"yesterday","exit_code":-1}"#,
+            "[1,2,3]",
+        ]);
+
+        let validation = validate_ledger(&path);
+        assert_eq!(validation.outcome, "INVALID");
+        assert_eq!(validation.records, 5);
+        let codes: Vec<(usize, &str)> = validation
+            .invalid
+            .iter()
+            .map(|i| (i.line, i.code.as_str()))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                (2, "MALFORMED_JSON"),
+                (3, "UNKNOWN_VERSION"),
+                (4, "MISSING_FIELD"),
+                (4, "INVALID_FIELD"),
+                (4, "INVALID_FIELD"),
+                (5, "NOT_OBJECT"),
+            ]
+        );
+        assert!(validation.to_human().contains("line 3: UNKNOWN_VERSION"));
+    }
+}
@@ -45,6 +45,36 @@ fn help_flag_exits_0() {
     assert!(stdout.contains("witness"));
 }
 
+#[test]
+fn closed_stdout_exits_0_without_panic() {
+    let mut child = pack_cmd()
+        .arg("--describe")
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Close the read end before pack writes, as `| head -0` would.
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+}
+
+#[test]
+fn closed_stdout_keeps_the_command_exit_code() {
+    let mut child = pack_cmd()
+        .args(["--no-witness", "verify", "some_dir", "--json"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    // A REFUSAL still exits 2, so `set -o pipefail` sees it.
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+}
+
 #[test]
 fn describe_short_circuits_before_validation() {
     // --describe should exit 0 even without a subcommand
@@ -59,6 +89,16 @@ fn schema_short_circuits_before_validation() {
     assert!(output.status.success());
 }
 
+#[test]
+fn schema_for_prints_one_standalone_definition() {
+    let output = pack_cmd().args(["--schema-for", "diff"]).output().unwrap();
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["$ref"], "#/definitions/diff_report");
+    assert!(schema["definitions"]["diff_entry"].is_object());
+    assert!(schema["definitions"].get("manifest").is_none());
+}
+
 #[test]
 fn no_command_exits_2() {
     let output = pack_cmd().output().unwrap();
@@ -83,6 +123,37 @@ fn diff_nonexistent_packs_exits_2() {
     assert_eq!(output.status.code(), Some(2));
 }
 
+#[test]
+fn diff_identical_packs_exits_0() {
+    let output = pack_cmd()
+        .args([
+            "--no-witness",
+            "diff",
+            "fixtures/packs/valid",
+            "fixtures/packs/valid",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let payload: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(payload["version"], "pack.diff.v0");
+    assert_eq!(payload["outcome"], "NO_CHANGES");
+    assert_eq!(payload["a_pack_id"], payload["b_pack_id"]);
+
+    let human = pack_cmd()
+        .args([
+            "--no-witness",
+            "diff",
+            "fixtures/packs/valid",
+            "fixtures/packs/valid",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(human.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&human.stdout).contains("NO_CHANGES"));
+}
+
 #[test]
 fn push_requires_base_url_env() {
     let output = pack_cmd().args(["push", "some_dir"]).output().unwrap();
@@ -138,6 +209,294 @@ fn push_success_exits_0_with_deterministic_status_line() {
     assert!(request.starts_with(&format!("{:?} /packs/{pack_id}", Method::Put)));
 }
 
+#[test]
+fn push_to_filesystem_store_is_idempotent() {
+    let tmp = tempfile::tempdir().unwrap();
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"version":"lock.v0","rows":5}"#).unwrap();
+    let pack_dir = tmp.path().join("pack");
+    let store = tmp.path().join("store");
+
+    let seal = pack_cmd()
+        .args([
+            "--no-witness",
+            "seal",
+            artifact.to_str().unwrap(),
+            "--output",
+            pack_dir.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(seal.status.success());
+    let manifest: Value =
+        serde_json::from_str(&std::fs::read_to_string(pack_dir.join("manifest.json")).unwrap())
+            .unwrap();
+    let pack_id = manifest["pack_id"].as_str().unwrap().to_string();
+
+    let push = || {
+        pack_cmd()
+            .args([
+                "--no-witness",
+                "push",
+                pack_dir.to_str().unwrap(),
+                "--to",
+                store.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap()
+    };
+
+    let first = push();
+    assert_eq!(first.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&first.stdout),
+        format!("PUBLISHED {pack_id}\n")
+    );
+    let hex = pack_id.strip_prefix("sha256:").unwrap();
+    assert!(store.join("sha256").join(hex).join("data.json").is_file());
+
+    let second = push();
+    assert_eq!(second.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&second.stdout),
+        format!("ALREADY_PRESENT {pack_id}\n")
+    );
+}
+
+#[test]
+fn pull_from_filesystem_store_verifies_before_materializing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"version":"lock.v0","rows":5}"#).unwrap();
+    let pack_dir = tmp.path().join("pack");
+    let store = tmp.path().join("store");
+
+    let seal = pack_cmd()
+        .args([
+            "--no-witness",
+            "seal",
+            artifact.to_str().unwrap(),
+            "--output",
+            pack_dir.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(seal.status.success());
+    let push = pack_cmd()
+        .args(["--no-witness", "push", pack_dir.to_str().unwrap()])
+        .env("PACK_STORE", &store)
+        .output()
+        .unwrap();
+    assert_eq!(push.status.code(), Some(0));
+    let manifest: Value =
+        serde_json::from_str(&std::fs::read_to_string(pack_dir.join("manifest.json")).unwrap())
+            .unwrap();
+    let pack_id = manifest["pack_id"].as_str().unwrap().to_string();
+
+    let out = tmp.path().join("recovered");
+    let pull = pack_cmd()
+        .args([
+            "--no-witness",
+            "pull",
+            &pack_id,
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .args(["--from", store.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(pull.status.code(), Some(0));
+    assert_eq!(
+        std::fs::read(out.join("data.json")).unwrap(),
+        std::fs::read(&artifact).unwrap()
+    );
+
+    let hex = pack_id.strip_prefix("sha256:").unwrap();
+    std::fs::write(store.join("sha256").join(hex).join("data.json"), "tampered").unwrap();
+    let tampered_out = tmp.path().join("tampered");
+    let pull = pack_cmd()
+        .args(["--no-witness", "pull", &pack_id, "--out"])
+        .arg(&tampered_out)
+        .args(["--from", store.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(pull.status.code(), Some(2));
+    let payload: Value = serde_json::from_slice(&pull.stdout).unwrap();
+    assert_eq!(payload["refusal"]["code"], "E_BAD_PACK");
+    assert!(!tampered_out.exists());
+}
+
+#[test]
+fn deprecated_pack_warns_on_verify_inspect_and_pull() {
+    let tmp = tempfile::tempdir().unwrap();
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"version":"lock.v0","rows":5}"#).unwrap();
+    let pack_dir = tmp.path().join("pack");
+    let store = tmp.path().join("store");
+    let key = tmp.path().join("signing.key");
+    std::fs::write(&key, "07".repeat(32)).unwrap();
+
+    let seal = pack_cmd()
+        .args([
+            "--no-witness",
+            "seal",
+            artifact.to_str().unwrap(),
+            "--output",
+        ])
+        .arg(&pack_dir)
+        .output()
+        .unwrap();
+    assert!(seal.status.success());
+    let push = pack_cmd()
+        .args(["--no-witness", "push", pack_dir.to_str().unwrap(), "--to"])
+        .arg(&store)
+        .output()
+        .unwrap();
+    assert_eq!(push.status.code(), Some(0));
+    let manifest_before = std::fs::read(pack_dir.join("manifest.json")).unwrap();
+    let manifest: Value = serde_json::from_slice(&manifest_before).unwrap();
+    let pack_id = manifest["pack_id"].as_str().unwrap().to_string();
+
+    let deprecate = pack_cmd()
+        .args(["--no-witness", "deprecate", &pack_id])
+        .args(["--reason", "superseded by restated close", "--store"])
+        .arg(&store)
+        .arg("--signing-key")
+        .arg(&key)
+        .output()
+        .unwrap();
+    assert_eq!(deprecate.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&deprecate.stdout),
+        format!("DEPRECATED {pack_id}\n")
+    );
+    let index: Value =
+        serde_json::from_str(&std::fs::read_to_string(store.join("index.json")).unwrap()).unwrap();
+    let note = &index["packs"][0]["deprecation"];
+    assert_eq!(note["reason"], "superseded by restated close");
+    assert!(note["signature"].is_string());
+
+    // The pack inside the store is found without PACK_STORE; the original
+    // directory is found through it.
+    let hex = pack_id.strip_prefix("sha256:").unwrap();
+    let stored = store.join("sha256").join(hex);
+    let verify = pack_cmd()
+        .args(["--no-witness", "verify", "--json"])
+        .arg(&stored)
+        .output()
+        .unwrap();
+    assert_eq!(verify.status.code(), Some(0));
+    let report: Value = serde_json::from_slice(&verify.stdout).unwrap();
+    assert_eq!(report["warnings"][0]["code"], "DEPRECATED");
+    assert!(report["warnings"][0]["reason"]
+        .as_str()
+        .unwrap()
+        .starts_with("superseded by restated close (deprecated "));
+    assert!(report["warnings"][0]["reason"]
+        .as_str()
+        .unwrap()
+        .ends_with("signer not checked)"));
+
+    let inspect = pack_cmd()
+        .args(["--no-witness", "inspect"])
+        .arg(&pack_dir)
+        .env("PACK_STORE", &store)
+        .output()
+        .unwrap();
+    assert_eq!(inspect.status.code(), Some(0));
+    let human = String::from_utf8_lossy(&inspect.stdout);
+    assert!(human
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with("  DEPRECATED: superseded"));
+
+    let pull = pack_cmd()
+        .args(["--no-witness", "pull", &pack_id, "--out"])
+        .arg(tmp.path().join("recovered"))
+        .arg("--from")
+        .arg(&store)
+        .output()
+        .unwrap();
+    assert_eq!(pull.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&pull.stderr).contains(&format!("{pack_id} is DEPRECATED")));
+    assert_eq!(
+        std::fs::read(pack_dir.join("manifest.json")).unwrap(),
+        manifest_before
+    );
+
+    let missing = pack_cmd()
+        .args([
+            "--no-witness",
+            "deprecate",
+            &format!("sha256:{}", "0".repeat(64)),
+        ])
+        .args(["--reason", "x", "--unsigned", "--store"])
+        .arg(&store)
+        .output()
+        .unwrap();
+    assert_eq!(missing.status.code(), Some(2));
+    let payload: Value = serde_json::from_slice(&missing.stdout).unwrap();
+    assert_eq!(payload["refusal"]["code"], "E_IO");
+}
+
+#[cfg(unix)]
+#[test]
+fn watch_once_verifies_store_and_runs_hook_on_invalid() {
+    let tmp = tempfile::tempdir().unwrap();
+    let store = tmp.path().join("store");
+    let mut pack_ids = Vec::new();
+    for name in ["good", "bad"] {
+        let artifact = tmp.path().join(format!("{name}.json"));
+        std::fs::write(&artifact, format!(r#"{{"name":"{name}"}}"#)).unwrap();
+        let pack_dir = tmp.path().join(name);
+        let seal = pack_cmd()
+            .args(["--no-witness", "seal"])
+            .arg(&artifact)
+            .arg("--output")
+            .arg(&pack_dir)
+            .output()
+            .unwrap();
+        assert!(seal.status.success());
+        let push = pack_cmd()
+            .args(["--no-witness", "push"])
+            .arg(&pack_dir)
+            .arg("--to")
+            .arg(&store)
+            .output()
+            .unwrap();
+        assert_eq!(push.status.code(), Some(0));
+        let manifest: Value =
+            serde_json::from_str(&std::fs::read_to_string(pack_dir.join("manifest.json")).unwrap())
+                .unwrap();
+        pack_ids.push(manifest["pack_id"].as_str().unwrap().to_string());
+    }
+    let bad_dir = store
+        .join("sha256")
+        .join(pack_ids[1].strip_prefix("sha256:").unwrap());
+    std::fs::write(bad_dir.join("bad.json"), "tampered").unwrap();
+
+    let hook_log = tmp.path().join("hook.log");
+    let output = pack_cmd()
+        .args(["--no-witness", "watch", "--once"])
+        .arg(&store)
+        .arg("--on-invalid")
+        .arg(format!(
+            "echo \"$PACK_VERIFY_OUTCOME $PACK_ID\" >> {}",
+            hook_log.display()
+        ))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("OK {}", pack_ids[0])));
+    assert!(stdout.contains(&format!("INVALID {}", pack_ids[1])));
+    assert_eq!(
+        std::fs::read_to_string(&hook_log).unwrap(),
+        format!("INVALID {}\n", pack_ids[1])
+    );
+}
+
 #[test]
 fn pull_requires_base_url_env() {
     let output = pack_cmd()
@@ -173,6 +532,46 @@ fn witness_count_exits_0() {
     assert!(output.status.success());
 }
 
+#[test]
+fn store_refcount_requires_a_filesystem_store() {
+    let output = pack_cmd()
+        .args(["--no-witness", "store", "refcount", "--json"])
+        .env_remove("PACK_STORE")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["refusal"]["code"], "E_IO");
+
+    let tmp = tempfile::tempdir().unwrap();
+    let output = pack_cmd()
+        .args(["--no-witness", "store", "refcount", "--json", "--store"])
+        .arg(tmp.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["version"], "pack.refcount.v0");
+    assert_eq!(report["packs"], 0);
+}
+
+#[test]
+fn self_test_passes_every_known_answer_check() {
+    let output = pack_cmd()
+        .args(["--no-witness", "self-test", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["version"], "pack.selftest.v0");
+    assert_eq!(report["outcome"], "OK");
+    let checks = report["checks"].as_array().unwrap();
+    assert!(checks.iter().all(|check| check["passed"] == true));
+    assert!(checks
+        .iter()
+        .any(|check| check["name"] == "pack/golden_pack_id"));
+}
+
 #[test]
 fn global_no_witness_flag_accepted() {
     // --no-witness is a valid global flag, should not error from clap
@@ -183,3 +582,86 @@ fn global_no_witness_flag_accepted() {
     // Still exits 2 (stub), but clap didn't reject the flag
     assert_eq!(output.status.code(), Some(2));
 }
+
+#[test]
+fn config_file_supplies_defaults_below_env_and_flags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = tmp.path().join("config.toml");
+    let out_root = tmp.path().join("packs");
+    std::fs::write(
+        &config,
+        format!(
+            "output_root = {:?}\nhash_algorithm = \"blake3\"\nstore = \"/srv/from-file\"\n",
+            out_root.to_str().unwrap()
+        ),
+    )
+    .unwrap();
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, "{}").unwrap();
+
+    let show = pack_cmd()
+        .env("PACK_CONFIG", &config)
+        .env("PACK_STORE", "/srv/from-env")
+        .args(["--no-witness", "config", "show", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(show.status.code(), Some(0));
+    let report: Value = serde_json::from_slice(&show.stdout).unwrap();
+    assert_eq!(report["version"], "pack.config.v0");
+    assert_eq!(report["file_found"], true);
+    let setting = |key: &str| {
+        report["settings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["key"] == key)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(setting("store")["value"], "/srv/from-env");
+    assert_eq!(setting("store")["source"], "env");
+    assert_eq!(setting("hash_algorithm")["source"], "file");
+    assert_eq!(setting("witness_chain")["source"], "default");
+
+    // No --output: the pack lands under output_root, hashed with the
+    // configured algorithm unless the flag overrides it.
+    let seal = |extra: &[&str]| {
+        pack_cmd()
+            .env("PACK_CONFIG", &config)
+            .args(["--no-witness", "seal", "--json", artifact.to_str().unwrap()])
+            .args(extra)
+            .output()
+            .unwrap()
+    };
+    let sealed = seal(&[]);
+    assert_eq!(sealed.status.code(), Some(0));
+    let report: Value = serde_json::from_slice(&sealed.stdout).unwrap();
+    let output = std::path::PathBuf::from(report["output"].as_str().unwrap());
+    assert!(output.starts_with(&out_root));
+    let manifest: Value =
+        serde_json::from_slice(&std::fs::read(output.join("manifest.json")).unwrap()).unwrap();
+    assert!(manifest["members"][0]["bytes_hash"]
+        .as_str()
+        .unwrap()
+        .starts_with("blake3:"));
+
+    let flagged = seal(&[
+        "--hash-algorithm",
+        "sha512",
+        "--output",
+        tmp.path().join("flagged").to_str().unwrap(),
+    ]);
+    assert_eq!(flagged.status.code(), Some(0));
+    let manifest: Value =
+        serde_json::from_slice(&std::fs::read(tmp.path().join("flagged/manifest.json")).unwrap())
+            .unwrap();
+    assert!(manifest["members"][0]["bytes_hash"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha512:"));
+
+    std::fs::write(&config, "hash_algorithm = \"md5\"\n").unwrap();
+    let refused = seal(&[]);
+    assert_eq!(refused.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("hash_algorithm"));
+}
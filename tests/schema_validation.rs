@@ -126,8 +126,9 @@ fn wrong_version_schema_fail() {
     assert_eq!(report["outcome"], "INVALID");
     assert_eq!(report["checks"]["schema_validation"], "fail");
 
-    // Should have both HASH_MISMATCH and SCHEMA_VIOLATION findings
+    // Should have both SIZE_MISMATCH (the edit added a byte) and
+    // SCHEMA_VIOLATION findings
     let findings = report["invalid"].as_array().unwrap();
-    assert!(findings.iter().any(|f| f["code"] == "HASH_MISMATCH"));
+    assert!(findings.iter().any(|f| f["code"] == "SIZE_MISMATCH"));
     assert!(findings.iter().any(|f| f["code"] == "SCHEMA_VIOLATION"));
 }
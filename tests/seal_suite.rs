@@ -57,8 +57,23 @@ fn seal_fixtures_produces_golden_manifest() {
     assert_eq!(golden_val["note"], produced_val["note"]);
     assert_eq!(produced_val["tool_version"], env!("CARGO_PKG_VERSION"));
 
-    // Members array must be identical (same order, same hashes, same types)
-    assert_eq!(golden_val["members"], produced_val["members"]);
+    // Members array must be identical (same order, same hashes, same types).
+    // The fixture predates size_bytes and content types, so sizes are checked
+    // against the member files instead and content types are only required.
+    let mut produced_members = produced_val["members"].as_array().unwrap().clone();
+    for member in &mut produced_members {
+        let fields = member.as_object_mut().unwrap();
+        assert!(fields.remove("content_type").unwrap().is_string());
+        fields.remove("charset");
+        let size = fields.remove("size_bytes").unwrap();
+        let path =
+            std::path::Path::new("fixtures/packs/valid").join(member["path"].as_str().unwrap());
+        assert_eq!(size, std::fs::metadata(path).unwrap().len());
+    }
+    assert_eq!(
+        golden_val["members"],
+        serde_json::Value::Array(produced_members)
+    );
 }
 
 /// Member paths in the manifest are sorted bytewise.
@@ -151,6 +166,235 @@ fn seal_duplicate_members_refuses() {
     assert!(stdout.contains("E_DUPLICATE"));
 }
 
+/// `--from-list -0` reads NUL-delimited paths from stdin and applies the same
+/// collision rules as positional artifacts.
+#[test]
+fn seal_from_list_on_stdin_joins_positional_artifacts() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let a = tmp.path().join("a.json");
+    let b = tmp.path().join("b.json");
+    std::fs::write(&a, "{}").unwrap();
+    std::fs::write(&b, "[]").unwrap();
+
+    let seal = |list: String, output: &std::path::Path| {
+        let mut child = pack_cmd()
+            .args(["seal", a.to_str().unwrap(), "--from-list", "-", "-0"])
+            .args(["--output", output.to_str().unwrap(), "--no-witness"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(list.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let out = tmp.path().join("pack");
+    let output = seal(format!("{}\0", b.display()), &out);
+    assert_eq!(output.status.code(), Some(0));
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["member_count"], 2);
+
+    let output = seal(format!("{}\0", a.display()), &tmp.path().join("dup"));
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("E_DUPLICATE"));
+}
+
+/// `-` seals stdin under the member path given by `--stdin-name`.
+#[test]
+fn seal_stdin_member_uses_stdin_name() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let a = tmp.path().join("a.json");
+    std::fs::write(&a, "{}").unwrap();
+    let out = tmp.path().join("pack");
+    let report = br#"{"rows":3}"#;
+
+    let mut child = pack_cmd()
+        .args(["seal", a.to_str().unwrap(), "-"])
+        .args(["--stdin-name", "reports/daily.json"])
+        .args(["--output", out.to_str().unwrap(), "--no-witness"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(report).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+
+    assert_eq!(
+        std::fs::read(out.join("reports/daily.json")).unwrap(),
+        report
+    );
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("manifest.json")).unwrap()).unwrap();
+    let paths: Vec<_> = manifest["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["a.json", "reports/daily.json"]);
+
+    let output = pack_cmd()
+        .args(["seal", "-", "--no-witness"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("--stdin-name"));
+}
+
+/// `--hash-algorithm` records its prefix on every member, and verify checks
+/// each member with the algorithm its prefix names.
+#[test]
+fn seal_json_reports_success_and_refusal_as_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let a = tmp.path().join("a.json");
+    std::fs::write(&a, "{}").unwrap();
+    let out = tmp.path().join("pack");
+
+    let output = pack_cmd()
+        .args(["seal", a.to_str().unwrap(), "--json", "--no-witness"])
+        .args(["--output", out.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(report["version"], "pack.seal.v0");
+    assert_eq!(report["outcome"], "PACK_CREATED");
+    assert_eq!(report["pack_id"], manifest["pack_id"]);
+    assert_eq!(report["output"], out.to_str().unwrap());
+    assert_eq!(report["member_count"], 1);
+    assert!(report.get("trust").is_none());
+
+    // The output directory is now taken, so the same seal refuses.
+    let output = pack_cmd()
+        .args(["seal", a.to_str().unwrap(), "--json", "--no-witness"])
+        .args(["--output", out.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let refusal: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(refusal["outcome"], "REFUSAL");
+}
+
+#[test]
+fn seal_hash_algorithm_is_recorded_and_verified() {
+    let tmp = tempfile::tempdir().unwrap();
+    let a = tmp.path().join("a.json");
+    std::fs::write(&a, "{}").unwrap();
+
+    for (algorithm, hex_len) in [("sha512", 128), ("blake3", 64)] {
+        let out = tmp.path().join(algorithm);
+        let output = pack_cmd()
+            .args(["seal", a.to_str().unwrap(), "--hash-algorithm", algorithm])
+            .args(["--output", out.to_str().unwrap(), "--no-witness"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(out.join("manifest.json")).unwrap()).unwrap();
+        let hash = manifest["members"][0]["bytes_hash"].as_str().unwrap();
+        let (prefix, hex) = hash.split_once(':').unwrap();
+        assert_eq!((prefix, hex.len()), (algorithm, hex_len));
+        assert!(manifest["pack_id"].as_str().unwrap().starts_with("sha256:"));
+
+        let verify = |dir: &std::path::Path| {
+            pack_cmd()
+                .args(["verify", dir.to_str().unwrap(), "--json", "--no-witness"])
+                .output()
+                .unwrap()
+        };
+        assert_eq!(verify(&out).status.code(), Some(0));
+
+        std::fs::write(out.join("a.json"), "[]").unwrap();
+        let output = verify(&out);
+        assert_eq!(output.status.code(), Some(1));
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(report["invalid"][0]["code"], "HASH_MISMATCH");
+        assert!(report["invalid"][0]["actual"]
+            .as_str()
+            .unwrap()
+            .starts_with(algorithm));
+    }
+}
+
+/// Executable sources are flagged in the manifest and stay executable in
+/// the pack and through an archive round trip.
+#[cfg(unix)]
+#[test]
+fn seal_preserves_executable_bit() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let script = tmp.path().join("run.sh");
+    let data = tmp.path().join("data.json");
+    std::fs::write(&script, "#!/bin/sh\necho ok\n").unwrap();
+    std::fs::write(&data, "{}").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let seal = |out: &std::path::Path, extra: &[&str]| {
+        let output = pack_cmd()
+            .args(["seal", script.to_str().unwrap(), data.to_str().unwrap()])
+            .args(["--output", out.to_str().unwrap(), "--no-witness"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+    };
+    let manifest_of = |dir: &std::path::Path| -> serde_json::Value {
+        serde_json::from_slice(&std::fs::read(dir.join("manifest.json")).unwrap()).unwrap()
+    };
+    let mode = |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode();
+
+    let out = tmp.path().join("pack");
+    seal(&out, &[]);
+    let manifest = manifest_of(&out);
+    assert_eq!(manifest["members"][0]["path"], "data.json");
+    assert!(manifest["members"][0].get("executable").is_none());
+    assert_eq!(manifest["members"][1]["executable"], true);
+    assert!(manifest.get("provenance").is_none());
+    assert_ne!(mode(&out.join("run.sh")) & 0o111, 0);
+    assert_eq!(mode(&out.join("data.json")) & 0o111, 0);
+
+    let strict_out = tmp.path().join("strict");
+    seal(&strict_out, &["--hash-executable"]);
+    let strict = manifest_of(&strict_out);
+    assert_eq!(strict["provenance"]["executable_in_pack_id"], true);
+    assert_ne!(strict["pack_id"], manifest["pack_id"]);
+
+    let archive = tmp.path().join("pack.pack.tar");
+    seal(&archive, &["--archive"]);
+    let unpacked = tmp.path().join("unpacked");
+    std::fs::create_dir(&unpacked).unwrap();
+    let status = Command::new("tar")
+        .args([
+            "-xf",
+            archive.to_str().unwrap(),
+            "-C",
+            unpacked.to_str().unwrap(),
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_ne!(mode(&unpacked.join("run.sh")) & 0o111, 0);
+    assert_eq!(mode(&unpacked.join("data.json")) & 0o111, 0);
+}
+
 /// Empty artifact list refuses with E_EMPTY.
 #[test]
 fn seal_empty_input_refuses() {
@@ -187,6 +431,113 @@ fn seal_refuses_non_empty_output_dir() {
     assert!(stdout.contains("E_IO"));
 }
 
+/// A trusted hash that disagrees with the sampled bytes refuses with E_HASH_MISMATCH.
+#[test]
+fn seal_trust_hashes_mismatch_refuses() {
+    let tmp = tempfile::tempdir().unwrap();
+    let art = tmp.path().join("data.json");
+    std::fs::write(&art, r#"{"version":"lock.v0"}"#).unwrap();
+    let hashes = tmp.path().join("hashes.json");
+    let wrong = format!("sha256:{}", "0".repeat(64));
+    std::fs::write(
+        &hashes,
+        serde_json::json!({ art.to_str().unwrap(): wrong }).to_string(),
+    )
+    .unwrap();
+    let out = tmp.path().join("trusted");
+
+    let output = pack_cmd()
+        .args([
+            "seal",
+            art.to_str().unwrap(),
+            "--output",
+            out.to_str().unwrap(),
+            "--trust-hashes",
+            hashes.to_str().unwrap(),
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["refusal"]["code"], "E_HASH_MISMATCH");
+    assert_eq!(envelope["refusal"]["detail"]["expected"], wrong);
+    assert!(!out.exists());
+}
+
+/// `--max-members` refuses during the walk, naming the first file over the limit.
+#[test]
+fn seal_max_members_refuses_with_e_limit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("data");
+    std::fs::create_dir(&dir).unwrap();
+    for name in ["a.json", "b.json", "c.json"] {
+        std::fs::write(dir.join(name), "{}").unwrap();
+    }
+    let out = tmp.path().join("limited");
+
+    let output = pack_cmd()
+        .args([
+            "seal",
+            dir.to_str().unwrap(),
+            "--output",
+            out.to_str().unwrap(),
+            "--max-members",
+            "2",
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["refusal"]["code"], "E_LIMIT");
+    assert_eq!(envelope["refusal"]["detail"]["max_members"], 2);
+    assert!(envelope["refusal"]["detail"]["stopped_at"].is_string());
+    assert!(!out.exists());
+}
+
+/// `.git` inside an input directory is excluded with a stderr note unless `--allow-git`.
+#[test]
+fn seal_excludes_git_metadata_by_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo = tmp.path().join("repo");
+    std::fs::create_dir_all(repo.join(".git")).unwrap();
+    std::fs::write(repo.join(".git").join("HEAD"), "ref: refs/heads/main").unwrap();
+    std::fs::write(repo.join("rules.json"), "{}").unwrap();
+
+    let out = tmp.path().join("excluded");
+    let output = pack_cmd()
+        .args([
+            "seal",
+            repo.to_str().unwrap(),
+            "--output",
+            out.to_str().unwrap(),
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "seal failed: {:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("excluded git metadata"));
+    let manifest: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(out.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["member_count"], 1);
+
+    let allowed = tmp.path().join("allowed");
+    let output = pack_cmd()
+        .args([
+            "seal",
+            repo.to_str().unwrap(),
+            "--output",
+            allowed.to_str().unwrap(),
+            "--allow-git",
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "seal failed: {:?}", output);
+    assert!(allowed.join("repo/.git/HEAD").is_file());
+}
+
 // ---------------------------------------------------------------------------
 // No-partial-output staging
 // ---------------------------------------------------------------------------
@@ -333,6 +684,97 @@ fn seal_type_detection_matches_expectations() {
     assert_eq!(type_map["nested_registry/loans.csv"], "registry");
 }
 
+/// `--strict-types` refuses members typed by path alone; `pack detect` shows why.
+#[test]
+fn seal_strict_types_refuses_path_typed_members() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = tmp.path().join("strict");
+
+    let output = pack_cmd()
+        .args([
+            "seal",
+            "fixtures/artifacts/nov.lock.json",
+            "fixtures/artifacts/nested_registry",
+            "--output",
+            out.to_str().unwrap(),
+            "--strict-types",
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["refusal"]["code"], "E_AMBIGUOUS_TYPE");
+    let paths: Vec<_> = envelope["refusal"]["detail"]["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        ["nested_registry/loans.csv", "nested_registry/registry.json"]
+    );
+    assert!(!out.exists());
+
+    let output = pack_cmd()
+        .args([
+            "detect",
+            "fixtures/artifacts/nov.lock.json",
+            "--member-path",
+            "registry/nov.lock.json",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["type"], "lockfile");
+    assert_eq!(report["rule"], "json_version");
+    assert_eq!(report["confidence"], "high");
+    assert_eq!(report["ambiguous"], false);
+}
+
+/// `--detect-rules` types members by an organization's rules and records them.
+#[test]
+fn seal_detect_rules_type_members_and_are_recorded() {
+    let tmp = tempfile::tempdir().unwrap();
+    let ledger = tmp.path().join("ledger.json");
+    std::fs::write(&ledger, r#"{"version": "ledger.v1", "entries": []}"#).unwrap();
+    let rules = tmp.path().join("rules.toml");
+    std::fs::write(
+        &rules,
+        "[[rules]]\nname = \"ledger\"\ntype = \"artifact\"\nversion = \"ledger.v1\"\n",
+    )
+    .unwrap();
+    let out = tmp.path().join("ruled");
+
+    let output = pack_cmd()
+        .args([
+            "seal",
+            ledger.to_str().unwrap(),
+            "fixtures/artifacts/nov.lock.json",
+            "--detect-rules",
+            rules.to_str().unwrap(),
+            "--output",
+            out.to_str().unwrap(),
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out.join("manifest.json")).unwrap()).unwrap();
+    assert_eq!(manifest["members"][0]["path"], "ledger.json");
+    assert_eq!(manifest["members"][0]["type"], "artifact");
+    assert_eq!(manifest["members"][0]["artifact_version"], "ledger.v1");
+    assert_eq!(manifest["members"][1]["type"], "lockfile");
+    assert_eq!(
+        manifest["provenance"]["detection_rules"],
+        serde_json::json!([{"name": "ledger", "type": "artifact", "version": "ledger.v1"}])
+    );
+}
+
 #[cfg(unix)]
 #[test]
 fn seal_preserves_literal_backslashes_in_directory_member_names() {
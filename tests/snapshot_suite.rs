@@ -0,0 +1,182 @@
+//! Snapshot tests for human-readable output.
+//!
+//! Operators grep these lines, so wording changes must show up as reviewed
+//! snapshot diffs. Run `cargo insta review` (or set `INSTA_UPDATE=always`)
+//! after an intentional change and commit the updated `tests/snapshots/`.
+
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+fn pack_cmd() -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_pack"));
+    cmd.arg("--no-witness");
+    cmd
+}
+
+/// Render exit code, stdout, and stderr as one snapshot body, replacing each
+/// `(needle, placeholder)` pair so run-specific values stay out of snapshots.
+fn render(args: &[&str], redactions: &[(&str, &str)]) -> String {
+    let output = pack_cmd().args(args).output().unwrap();
+    let mut text = format!(
+        "exit: {}\n--- stdout\n{}--- stderr\n{}",
+        output
+            .status
+            .code()
+            .map_or_else(|| "signal".to_string(), |c| c.to_string()),
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr),
+    );
+    for (needle, placeholder) in redactions {
+        text = text.replace(needle, placeholder);
+    }
+    text
+}
+
+fn pack_id(pack_dir: &Path) -> String {
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(pack_dir.join("manifest.json")).unwrap()).unwrap();
+    manifest["pack_id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn seal_human_output() {
+    let tmp = TempDir::new().unwrap();
+    let out = tmp.path().join("pack");
+    let out_str = out.to_str().unwrap();
+    let output = pack_cmd()
+        .args([
+            "seal",
+            "fixtures/artifacts/nov.lock.json",
+            "fixtures/artifacts/rules.json",
+            "fixtures/artifacts/nested_registry",
+            "--output",
+            out_str,
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let id = pack_id(&out);
+    let text = format!(
+        "exit: 0\n--- stdout\n{}--- stderr\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr),
+    )
+    .replace(&id, "[PACK_ID]")
+    .replace(out_str, "[OUT]");
+    insta::assert_snapshot!("seal_ok", text);
+}
+
+#[test]
+fn seal_refusal_output() {
+    insta::assert_snapshot!(
+        "seal_refusal_duplicate",
+        render(
+            &[
+                "seal",
+                "fixtures/artifacts/rules.json",
+                "fixtures/schema/pass/rules.json",
+            ],
+            &[],
+        )
+    );
+    insta::assert_snapshot!(
+        "seal_refusal_missing_input",
+        render(&["seal", "fixtures/artifacts/does-not-exist.json"], &[])
+    );
+}
+
+#[test]
+fn verify_human_outputs() {
+    for fixture in [
+        "valid",
+        "missing_member",
+        "tampered_member",
+        "tampered_manifest",
+        "extra_member",
+    ] {
+        let pack_dir = format!("fixtures/packs/{fixture}");
+        insta::assert_snapshot!(
+            format!("verify_{fixture}"),
+            render(&["verify", &pack_dir], &[])
+        );
+    }
+    insta::assert_snapshot!(
+        "verify_refusal_no_manifest",
+        render(&["verify", "fixtures/artifacts"], &[])
+    );
+}
+
+#[test]
+fn inspect_human_outputs() {
+    insta::assert_snapshot!(
+        "inspect_valid",
+        render(&["inspect", "fixtures/packs/valid"], &[])
+    );
+    insta::assert_snapshot!(
+        "inspect_member",
+        render(
+            &["inspect", "fixtures/packs/valid", "--member", "rules.json"],
+            &[]
+        )
+    );
+}
+
+fn seal_dir(tmp: &TempDir, name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let src = tmp.path().join(name).join("evidence");
+    for (path, content) in files {
+        let file = src.join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, content).unwrap();
+    }
+    let out = tmp.path().join(format!("{name}-pack"));
+    let status = pack_cmd()
+        .arg("seal")
+        .arg(&src)
+        .arg("--output")
+        .arg(&out)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    out
+}
+
+#[test]
+fn diff_human_outputs() {
+    insta::assert_snapshot!(
+        "diff_no_changes",
+        render(
+            &["diff", "fixtures/packs/valid", "fixtures/packs/valid"],
+            &[]
+        )
+    );
+
+    let tmp = TempDir::new().unwrap();
+    let a = seal_dir(
+        &tmp,
+        "a",
+        &[
+            ("kept.json", "{}"),
+            ("changed.txt", "one"),
+            ("removed.txt", "x"),
+        ],
+    );
+    let b = seal_dir(
+        &tmp,
+        "b",
+        &[
+            ("kept.json", "{}"),
+            ("changed.txt", "two"),
+            ("added.txt", "y"),
+        ],
+    );
+    let (a_id, b_id) = (pack_id(&a), pack_id(&b));
+    insta::assert_snapshot!(
+        "diff_changes",
+        render(
+            &["diff", a.to_str().unwrap(), b.to_str().unwrap()],
+            &[(&a_id, "[PACK_A]"), (&b_id, "[PACK_B]")],
+        )
+    );
+}
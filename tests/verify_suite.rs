@@ -40,6 +40,8 @@ fn valid_pack_verifies_ok() {
     assert_eq!(checks["member_hashes"], true);
     assert_eq!(checks["pack_id"], true);
     assert_eq!(checks["schema_validation"], "pass");
+    // The fixture predates size_bytes: nothing to compare, nothing reported.
+    assert!(checks.get("member_sizes").is_none());
 }
 
 /// Human-readable output format for valid pack.
@@ -132,6 +134,189 @@ fn extra_member_is_invalid() {
     assert_eq!(report["checks"]["extra_members"], false);
 }
 
+/// `--repair-report` writes a remediation plan next to the usual report.
+#[test]
+fn repair_report_lists_steps_for_each_finding() {
+    let tmp = tempfile::tempdir().unwrap();
+    let plan_path = tmp.path().join("repair.json");
+
+    let output = pack_cmd()
+        .args(["verify", "fixtures/packs/tampered_member", "--json"])
+        .args([
+            "--repair-report",
+            plan_path.to_str().unwrap(),
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["outcome"], "INVALID");
+
+    let plan: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&plan_path).unwrap()).unwrap();
+    assert_eq!(plan["version"], "pack.repair.v0");
+    assert_eq!(plan["outcome"], "INVALID");
+    let step = plan["actions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["code"] == "HASH_MISMATCH")
+        .unwrap();
+    assert_eq!(step["action"], "re-source");
+    assert_eq!(step["path"], "rvl.report.json");
+    assert_eq!(
+        step["expected_hash"],
+        report["invalid"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["code"] == "HASH_MISMATCH")
+            .unwrap()["expected"]
+    );
+
+    let output = pack_cmd()
+        .args(["verify", "fixtures/packs/valid"])
+        .args([
+            "--repair-report",
+            plan_path.to_str().unwrap(),
+            "--no-witness",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let plan: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&plan_path).unwrap()).unwrap();
+    assert_eq!(plan["outcome"], "OK");
+    assert!(plan["actions"].as_array().unwrap().is_empty());
+}
+
+/// `--timings` adds per-check and total durations; without it there are none.
+#[test]
+fn timings_report_each_check_and_the_total() {
+    let (report, _) = verify_json("fixtures/packs/valid");
+    assert!(report.get("timings").is_none());
+
+    let output = pack_cmd()
+        .args(["verify", "fixtures/packs/valid", "--json", "--timings"])
+        .arg("--no-witness")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let timings = &report["timings"];
+    let checks = timings["checks_ms"].as_object().unwrap();
+    for check in [
+        "manifest_parse",
+        "member_count",
+        "member_paths",
+        "member_hashes",
+        "extra_members",
+        "pack_id",
+        "schema_validation",
+    ] {
+        assert!(checks[check].as_f64().unwrap() >= 0.0, "{check}");
+    }
+    assert!(!checks.contains_key("cross_references"));
+    let total = timings["total_ms"].as_f64().unwrap();
+    assert!(checks.values().map(|ms| ms.as_f64().unwrap()).sum::<f64>() <= total + 0.01);
+}
+
+/// A trust policy the pack violates reports one `TRUST_POLICY` finding per
+/// rule; a policy it satisfies passes.
+#[test]
+fn trust_policy_rules_report_trust_policy_findings() {
+    let tmp = tempfile::tempdir().unwrap();
+    let run = |policy: &str| {
+        let path = tmp.path().join("policy.json");
+        std::fs::write(&path, policy).unwrap();
+        let output = pack_cmd()
+            .args(["verify", "fixtures/packs/valid", "--json", "--no-witness"])
+            .arg("--trust-policy")
+            .arg(&path)
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        (report, output.status.code())
+    };
+
+    let (report, code) = run(r#"{"min_tool_version": "0.1.0"}"#);
+    assert_eq!(code, Some(0));
+    assert_eq!(report["checks"]["trust_policy"], true);
+
+    let (report, code) = run(r#"{"min_tool_version": "0.2.1", "allowed_signers": []}"#);
+    assert_eq!(code, Some(1));
+    assert_eq!(report["checks"]["trust_policy"], false);
+    let findings: Vec<_> = report["invalid"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["code"].as_str().unwrap(), f["actual"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        findings,
+        [
+            ("TRUST_POLICY", "tool_version = 0.1.0"),
+            ("TRUST_POLICY", "unsigned")
+        ]
+    );
+
+    let (report, code) = run(r#"{"max_age": 30}"#);
+    assert_eq!(code, Some(2));
+    assert_eq!(report["refusal"]["code"], "E_IO");
+}
+
+/// `--quick` skips hashing: a same-size edit passes, a size change is a
+/// `SIZE_MISMATCH`, and `member_hashes` reads `"skipped"`.
+#[test]
+fn quick_verify_checks_sizes_without_hashing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"rows":1}"#).unwrap();
+    let pack_dir = tmp.path().join("pack");
+    let seal = pack_cmd()
+        .args([
+            "seal",
+            artifact.to_str().unwrap(),
+            "--no-witness",
+            "--output",
+        ])
+        .arg(&pack_dir)
+        .output()
+        .unwrap();
+    assert!(seal.status.success());
+    let quick = || {
+        let output = pack_cmd()
+            .args(["verify", "--quick", "--json", "--no-witness"])
+            .arg(&pack_dir)
+            .output()
+            .unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        (report, output.status.code())
+    };
+
+    let (report, code) = quick();
+    assert_eq!(code, Some(0));
+    assert_eq!(report["checks"]["member_hashes"], "skipped");
+    assert_eq!(report["checks"]["member_sizes"], true);
+    assert_eq!(report["checks"]["schema_validation"], "skipped");
+
+    std::fs::write(pack_dir.join("data.json"), r#"{"rows":2}"#).unwrap();
+    let (_, code) = quick();
+    assert_eq!(code, Some(0));
+    let (report, code) = verify_json(pack_dir.to_str().unwrap());
+    assert_eq!(code, 1);
+    assert_eq!(report["invalid"][0]["code"], "HASH_MISMATCH");
+
+    std::fs::write(pack_dir.join("data.json"), r#"{"rows":20}"#).unwrap();
+    let (report, code) = quick();
+    assert_eq!(code, Some(1));
+    assert_eq!(report["checks"]["member_sizes"], false);
+    assert_eq!(report["invalid"][0]["code"], "SIZE_MISMATCH");
+    assert_eq!(report["invalid"][0]["expected"], "10");
+    assert_eq!(report["invalid"][0]["actual"], "11");
+}
+
 // ---------------------------------------------------------------------------
 // INVALID outcomes (exit 1) — constructed at test time
 // ---------------------------------------------------------------------------
@@ -316,6 +501,70 @@ fn symlink_member_is_invalid() {
     assert!(findings.iter().any(|f| f["code"] == "NON_REGULAR_MEMBER"));
 }
 
+/// Empty directories sealed with --empty-dirs keep or list verify OK, and
+/// a manifest whose recorded empty directories hold content is INVALID.
+#[test]
+fn recorded_empty_dirs_are_checked() {
+    let tmp = tempfile::tempdir().unwrap();
+    let tree = tmp.path().join("tree");
+    std::fs::create_dir_all(tree.join("partitions/2026-01")).unwrap();
+    std::fs::write(tree.join("run.json"), "{}").unwrap();
+
+    for mode in ["keep", "list"] {
+        let pack_dir = tmp.path().join(mode);
+        let output = pack_cmd()
+            .args(["seal", tree.to_str().unwrap(), "--empty-dirs", mode])
+            .args(["--output", pack_dir.to_str().unwrap(), "--no-witness"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let (report, code) = verify_json(pack_dir.to_str().unwrap());
+        assert_eq!(code, 0, "{report}");
+        assert_eq!(report["checks"]["empty_dirs"], true);
+    }
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(tmp.path().join("list/manifest.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        manifest["provenance"]["empty_dirs"],
+        serde_json::json!(["tree/partitions/2026-01"])
+    );
+
+    let pack_dir = tmp.path().join("bad_pack");
+    std::fs::create_dir_all(pack_dir.join("logs")).unwrap();
+    std::fs::write(pack_dir.join("logs/.packkeep"), "x").unwrap();
+    let manifest = serde_json::json!({
+        "version": "pack.v0",
+        "pack_id": "sha256:fake",
+        "created": "2026-01-15T00:00:00Z",
+        "tool_version": "0.1.0",
+        "member_count": 1,
+        "members": [{
+            "path": "logs/.packkeep",
+            "bytes_hash": "sha256:2d711642b726b04401627ca9fbac32f5c8530fb1903cc4db02258717921a4881",
+            "type": "other"
+        }],
+        "provenance": { "empty_dirs": ["logs"] }
+    });
+    std::fs::write(
+        pack_dir.join("manifest.json"),
+        serde_json::to_string(&manifest).unwrap(),
+    )
+    .unwrap();
+
+    let (report, code) = verify_json(pack_dir.to_str().unwrap());
+    assert_eq!(code, 1);
+    assert_eq!(report["checks"]["empty_dirs"], false);
+    let codes: Vec<&str> = report["invalid"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|f| f["code"].as_str())
+        .collect();
+    assert!(codes.contains(&"PACKKEEP_NOT_EMPTY"));
+    assert!(codes.contains(&"EMPTY_DIR_NOT_EMPTY"));
+}
+
 // ---------------------------------------------------------------------------
 // REFUSAL outcomes (exit 2)
 // ---------------------------------------------------------------------------
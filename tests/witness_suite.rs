@@ -724,6 +724,55 @@ fn witness_query_filters_with_synthetic_ledger() {
     assert_eq!(parsed[0]["inputs"][0]["hash"], "sha256:bbb");
 }
 
+/// witness export filters by date and writes one file per month.
+#[test]
+fn witness_export_partitions_by_month() {
+    let tmp = tempfile::tempdir().unwrap();
+    let ledger = tmp.path().join("witness.jsonl");
+
+    let records = [
+        r#"{"id":"blake3:1","tool":"pack","version":"0.2.0","command":"seal","outcome":"PACK_CREATED","exit_code":0,"ts":"2025-12-31T23:00:00Z"}"#,
+        r#"{"id":"blake3:2","tool":"pack","version":"0.2.0","command":"seal","outcome":"PACK_CREATED","exit_code":0,"ts":"2026-01-15T10:00:00Z"}"#,
+        r#"{"id":"blake3:3","tool":"pack","version":"0.2.0","command":"verify","outcome":"INVALID","exit_code":1,"ts":"2026-02-01T08:00:00Z"}"#,
+    ];
+    std::fs::write(&ledger, records.join("\n") + "\n").unwrap();
+    let out = tmp.path().join("export");
+
+    let output = pack_cmd_with_witness(ledger.to_str().unwrap())
+        .args([
+            "witness",
+            "export",
+            "--since",
+            "2026-01-01T00:00:00Z",
+            "--format",
+            "csv",
+            "--partition",
+            "month",
+            "--out",
+            out.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["version"], "pack.witness_export.v0");
+    assert_eq!(summary["records"], 2);
+    let partitions: Vec<_> = summary["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["partition"].as_str().unwrap())
+        .collect();
+    assert_eq!(partitions, ["2026-01", "2026-02"]);
+
+    let february = std::fs::read_to_string(out.join("month=2026-02/witness.csv")).unwrap();
+    let lines: Vec<_> = february.lines().collect();
+    assert!(lines[0].starts_with("id,ts,tool,version,command,outcome,exit_code,"));
+    assert!(lines[1].starts_with("blake3:3,2026-02-01T08:00:00Z,pack,0.2.0,verify,INVALID,1,"));
+    assert!(!out.join("month=2025-12").exists());
+}
+
 /// witness count can target other tools in the shared ledger.
 #[test]
 fn witness_count_honors_tool_filter() {
@@ -810,3 +859,146 @@ fn witness_accumulates_across_operations() {
     let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
     assert_eq!(parsed["count"], 2);
 }
+
+/// witness validate flags a partial write by line number after a real seal record.
+#[test]
+fn witness_validate_reports_partial_write_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    let ledger = tmp.path().join("witness.jsonl");
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"version":"lock.v0"}"#).unwrap();
+
+    let seal = pack_cmd_with_witness(ledger.to_str().unwrap())
+        .args([
+            "seal",
+            artifact.to_str().unwrap(),
+            "--output",
+            tmp.path().join("out").to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(seal.status.success());
+
+    let ok = pack_cmd_with_witness(ledger.to_str().unwrap())
+        .args(["witness", "validate", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(ok.status.code(), Some(0));
+
+    let mut content = std::fs::read_to_string(&ledger).unwrap();
+    content.push_str(r#"{"tool":"pack","version":"0.2.3","outc"#);
+    std::fs::write(&ledger, content).unwrap();
+
+    let output = pack_cmd_with_witness(ledger.to_str().unwrap())
+        .args(["witness", "validate", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["outcome"], "INVALID");
+    assert_eq!(report["records"], 2);
+    assert_eq!(report["invalid"][0]["line"], 2);
+    assert_eq!(report["invalid"][0]["code"], "MALFORMED_JSON");
+}
+
+/// PACK_WITNESS_CHAIN starts a chain after legacy records; later appends keep
+/// it going without the variable, and verify-chain catches an edited record.
+#[test]
+fn chained_ledger_verifies_and_detects_edits() {
+    let tmp = tempfile::tempdir().unwrap();
+    let ledger = tmp.path().join("witness.jsonl");
+    let ledger_str = ledger.to_str().unwrap();
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"version":"lock.v0"}"#).unwrap();
+    let seal = |chain: bool, out: &str| {
+        let mut cmd = pack_cmd_with_witness(ledger_str);
+        if chain {
+            cmd.env("PACK_WITNESS_CHAIN", "1");
+        }
+        let output = cmd
+            .args(["seal", artifact.to_str().unwrap(), "--output"])
+            .arg(tmp.path().join(out))
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    };
+    seal(false, "a");
+    seal(true, "b");
+    seal(false, "c");
+
+    let records: Vec<serde_json::Value> = std::fs::read_to_string(&ledger)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(records[0].get("prev_hash").is_none());
+    assert!(records[1]["prev_hash"].is_string());
+    assert!(records[2]["prev_hash"].is_string());
+
+    let verify_chain = || {
+        pack_cmd_with_witness(ledger_str)
+            .args(["--no-witness", "witness", "verify-chain", "--json"])
+            .output()
+            .unwrap()
+    };
+    let ok = verify_chain();
+    assert_eq!(ok.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&ok.stdout).unwrap();
+    assert_eq!(report["version"], "pack.witness.chain.v0");
+    assert_eq!(report["chained"], 2);
+    assert_eq!(report["chain_start"], 2);
+
+    let content = std::fs::read_to_string(&ledger).unwrap();
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    lines[1] = lines[1].replace("PACK_CREATED", "REFUSAL");
+    std::fs::write(&ledger, lines.join("\n") + "\n").unwrap();
+    let broken = verify_chain();
+    assert_eq!(broken.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&broken.stdout).unwrap();
+    assert_eq!(report["invalid"][0]["line"], 3);
+    assert_eq!(report["invalid"][0]["code"], "CHAIN_BROKEN");
+}
+
+/// PACK_WITNESS_ENRICH adds run context to the record; it is absent otherwise.
+#[test]
+fn enrichment_is_recorded_only_when_enabled() {
+    let tmp = tempfile::tempdir().unwrap();
+    let ledger = tmp.path().join("witness.jsonl");
+    let artifact = tmp.path().join("data.json");
+    std::fs::write(&artifact, r#"{"version":"lock.v0"}"#).unwrap();
+    let seal = |enrich: bool, out: &str| {
+        let mut cmd = pack_cmd_with_witness(ledger.to_str().unwrap());
+        if enrich {
+            cmd.env("PACK_WITNESS_ENRICH", "1");
+        }
+        let output = cmd
+            .args(["seal", artifact.to_str().unwrap(), "--output"])
+            .arg(tmp.path().join(out))
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+    };
+    seal(true, "a");
+    seal(false, "b");
+
+    let records: Vec<serde_json::Value> = std::fs::read_to_string(&ledger)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let enrichment = &records[0]["enrichment"];
+    assert!(enrichment["argv_hash"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+    assert_eq!(enrichment["member_count"], 1);
+    assert_eq!(enrichment["pack_bytes"], 21);
+    assert!(enrichment["duration_ms"].is_u64());
+    assert!(records[1].get("enrichment").is_none());
+
+    let validate = pack_cmd_with_witness(ledger.to_str().unwrap())
+        .args(["--no-witness", "witness", "validate", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(validate.status.code(), Some(0));
+}